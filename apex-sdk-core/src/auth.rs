@@ -0,0 +1,455 @@
+//! Sign-in messages
+//!
+//! [`SignInMessage`] is the text an application asks a user to sign to prove
+//! control of an address, in the EIP-4361 (Sign-In with Ethereum) format as
+//! generalised by CAIP-122 to other chains:
+//!
+//! ```text
+//! example.com wants you to sign in with your Ethereum account:
+//! 0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266
+//!
+//! Sign in to Example
+//!
+//! URI: https://example.com/login
+//! Version: 1
+//! Chain ID: 1
+//! Nonce: 32891756aBcDeF12
+//! Issued At: 2024-01-01T00:00:00Z
+//! ```
+//!
+//! Signing and signature verification are chain specific and live in the
+//! adapter crates; this module builds, renders and parses messages and checks
+//! the claims in them (domain, nonce and validity window).
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use std::fmt;
+use std::str::FromStr;
+use thiserror::Error;
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::{SystemTime, UNIX_EPOCH};
+#[cfg(target_arch = "wasm32")]
+use web_time::{SystemTime, UNIX_EPOCH};
+
+/// Message format version
+pub const VERSION: &str = "1";
+
+/// Length of nonces from [`generate_nonce`]
+const NONCE_LEN: usize = 17;
+
+const PREAMBLE: &str = " wants you to sign in with your ";
+
+/// Sign-in errors
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum AuthError {
+    #[error("Invalid sign-in message: {0}")]
+    Parse(String),
+
+    #[error("Invalid signature: {0}")]
+    InvalidSignature(String),
+
+    #[error("Domain mismatch: expected {expected}, got {found}")]
+    DomainMismatch { expected: String, found: String },
+
+    #[error("Nonce mismatch")]
+    NonceMismatch,
+
+    #[error("Message expired")]
+    Expired,
+
+    #[error("Message not yet valid")]
+    NotYetValid,
+}
+
+/// Result type for sign-in operations
+pub type Result<T> = std::result::Result<T, AuthError>;
+
+/// A sign-in request for an address
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignInMessage {
+    /// Domain requesting the sign-in
+    pub domain: String,
+    /// Blockchain named in the preamble, e.g. `Ethereum`
+    pub namespace: String,
+    /// Address signing in, in the chain's canonical text form
+    pub address: String,
+    /// Human-readable statement shown to the user
+    pub statement: Option<String>,
+    /// URI of the resource being signed in to
+    pub uri: String,
+    /// Message format version
+    pub version: String,
+    /// Chain the address is on
+    pub chain_id: String,
+    /// Single-use value issued by the application
+    pub nonce: String,
+    /// When the message was issued
+    pub issued_at: DateTime<Utc>,
+    /// When the signed message stops being valid
+    pub expiration_time: Option<DateTime<Utc>>,
+    /// When the signed message becomes valid
+    pub not_before: Option<DateTime<Utc>>,
+    /// Application request identifier
+    pub request_id: Option<String>,
+    /// Resources the user is granting access to
+    pub resources: Vec<String>,
+}
+
+impl SignInMessage {
+    /// Create a message issued now, with a fresh nonce
+    pub fn new(
+        namespace: impl Into<String>,
+        domain: impl Into<String>,
+        address: impl Into<String>,
+        uri: impl Into<String>,
+        chain_id: impl Into<String>,
+    ) -> Self {
+        Self {
+            domain: domain.into(),
+            namespace: namespace.into(),
+            address: address.into(),
+            statement: None,
+            uri: uri.into(),
+            version: VERSION.to_string(),
+            chain_id: chain_id.into(),
+            nonce: generate_nonce(),
+            issued_at: now(),
+            expiration_time: None,
+            not_before: None,
+            request_id: None,
+            resources: Vec::new(),
+        }
+    }
+
+    /// Set the statement shown to the user
+    pub fn with_statement(mut self, statement: impl Into<String>) -> Self {
+        self.statement = Some(statement.into());
+        self
+    }
+
+    /// Use a nonce issued by the application
+    pub fn with_nonce(mut self, nonce: impl Into<String>) -> Self {
+        self.nonce = nonce.into();
+        self
+    }
+
+    /// Set the issue time
+    pub fn with_issued_at(mut self, issued_at: DateTime<Utc>) -> Self {
+        self.issued_at = issued_at;
+        self
+    }
+
+    /// Expire the message at `expiration_time`
+    pub fn with_expiration_time(mut self, expiration_time: DateTime<Utc>) -> Self {
+        self.expiration_time = Some(expiration_time);
+        self
+    }
+
+    /// Make the message valid only from `not_before`
+    pub fn with_not_before(mut self, not_before: DateTime<Utc>) -> Self {
+        self.not_before = Some(not_before);
+        self
+    }
+
+    /// Set the application request identifier
+    pub fn with_request_id(mut self, request_id: impl Into<String>) -> Self {
+        self.request_id = Some(request_id.into());
+        self
+    }
+
+    /// Add a resource the user is granting access to
+    pub fn with_resource(mut self, resource: impl Into<String>) -> Self {
+        self.resources.push(resource.into());
+        self
+    }
+
+    /// Check the domain, nonce and validity window against the current time
+    pub fn validate(&self, domain: &str, nonce: &str) -> Result<()> {
+        self.validate_at(domain, nonce, now())
+    }
+
+    /// Check the domain, nonce and validity window at `time`
+    pub fn validate_at(&self, domain: &str, nonce: &str, time: DateTime<Utc>) -> Result<()> {
+        if self.domain != domain {
+            return Err(AuthError::DomainMismatch {
+                expected: domain.to_string(),
+                found: self.domain.clone(),
+            });
+        }
+        if self.nonce != nonce {
+            return Err(AuthError::NonceMismatch);
+        }
+        if matches!(self.expiration_time, Some(expiry) if time >= expiry) {
+            return Err(AuthError::Expired);
+        }
+        if matches!(self.not_before, Some(start) if time < start) {
+            return Err(AuthError::NotYetValid);
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for SignInMessage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}{}{} account:", self.domain, PREAMBLE, self.namespace)?;
+        writeln!(f, "{}", self.address)?;
+        writeln!(f)?;
+        if let Some(statement) = &self.statement {
+            writeln!(f, "{}", statement)?;
+        }
+        writeln!(f)?;
+        writeln!(f, "URI: {}", self.uri)?;
+        writeln!(f, "Version: {}", self.version)?;
+        writeln!(f, "Chain ID: {}", self.chain_id)?;
+        writeln!(f, "Nonce: {}", self.nonce)?;
+        write!(f, "Issued At: {}", timestamp(&self.issued_at))?;
+        if let Some(expiration_time) = &self.expiration_time {
+            write!(f, "\nExpiration Time: {}", timestamp(expiration_time))?;
+        }
+        if let Some(not_before) = &self.not_before {
+            write!(f, "\nNot Before: {}", timestamp(not_before))?;
+        }
+        if let Some(request_id) = &self.request_id {
+            write!(f, "\nRequest ID: {}", request_id)?;
+        }
+        if !self.resources.is_empty() {
+            write!(f, "\nResources:")?;
+            for resource in &self.resources {
+                write!(f, "\n- {}", resource)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for SignInMessage {
+    type Err = AuthError;
+
+    fn from_str(message: &str) -> Result<Self> {
+        let mut lines = message.split('\n').peekable();
+        let mut next = |what: &str| {
+            lines
+                .next()
+                .ok_or_else(|| AuthError::Parse(format!("missing {}", what)))
+        };
+
+        let (domain, namespace) = next("preamble")?
+            .split_once(PREAMBLE)
+            .and_then(|(domain, rest)| Some((domain, rest.strip_suffix(" account:")?)))
+            .ok_or_else(|| AuthError::Parse("invalid preamble".to_string()))?;
+        let address = next("address")?;
+        if !next("blank line")?.is_empty() {
+            return Err(AuthError::Parse(
+                "expected blank line after address".to_string(),
+            ));
+        }
+        let statement = match next("statement")? {
+            "" => None,
+            statement => {
+                if !next("blank line")?.is_empty() {
+                    return Err(AuthError::Parse(
+                        "expected blank line after statement".to_string(),
+                    ));
+                }
+                Some(statement.to_string())
+            }
+        };
+
+        let uri = tagged(next("URI")?, "URI: ")?;
+        let version = tagged(next("version")?, "Version: ")?;
+        if version != VERSION {
+            return Err(AuthError::Parse(format!("unsupported version {}", version)));
+        }
+        let chain_id = tagged(next("chain ID")?, "Chain ID: ")?;
+        let nonce = tagged(next("nonce")?, "Nonce: ")?;
+        if nonce.len() < 8 || !nonce.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return Err(AuthError::Parse(
+                "nonce must be at least 8 alphanumeric characters".to_string(),
+            ));
+        }
+        let issued_at = parse_timestamp(tagged(next("issue time")?, "Issued At: ")?)?;
+
+        let mut optional = |tag: &str| {
+            let value = lines.peek()?.strip_prefix(tag)?;
+            lines.next();
+            Some(value)
+        };
+        let expiration_time = optional("Expiration Time: ")
+            .map(parse_timestamp)
+            .transpose()?;
+        let not_before = optional("Not Before: ").map(parse_timestamp).transpose()?;
+        let request_id = optional("Request ID: ").map(str::to_string);
+        let mut resources = Vec::new();
+        if lines.peek() == Some(&"Resources:") {
+            lines.next();
+            while let Some(resource) = lines.peek().and_then(|line| line.strip_prefix("- ")) {
+                resources.push(resource.to_string());
+                lines.next();
+            }
+        }
+        if let Some(line) = lines.next() {
+            return Err(AuthError::Parse(format!("unexpected line: {}", line)));
+        }
+
+        Ok(Self {
+            domain: domain.to_string(),
+            namespace: namespace.to_string(),
+            address: address.to_string(),
+            statement,
+            uri: uri.to_string(),
+            version: version.to_string(),
+            chain_id: chain_id.to_string(),
+            nonce: nonce.to_string(),
+            issued_at,
+            expiration_time,
+            not_before,
+            request_id,
+            resources,
+        })
+    }
+}
+
+/// Generate a random alphanumeric nonce
+pub fn generate_nonce() -> String {
+    const ALPHABET: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+    let mut bytes = [0u8; NONCE_LEN];
+    getrandom::getrandom(&mut bytes).expect("system randomness unavailable");
+    // 62 does not divide 256, but the bias is irrelevant for a nonce
+    bytes
+        .iter()
+        .map(|b| ALPHABET[*b as usize % ALPHABET.len()] as char)
+        .collect()
+}
+
+fn tagged<'a>(line: &'a str, tag: &str) -> Result<&'a str> {
+    line.strip_prefix(tag)
+        .ok_or_else(|| AuthError::Parse(format!("expected '{}'", tag.trim_end())))
+}
+
+fn timestamp(time: &DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::AutoSi, true)
+}
+
+fn parse_timestamp(value: &str) -> Result<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|time| time.with_timezone(&Utc))
+        .map_err(|e| AuthError::Parse(format!("invalid timestamp {}: {}", value, e)))
+}
+
+fn now() -> DateTime<Utc> {
+    let millis = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or_default();
+    DateTime::from_timestamp_millis(millis).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn message() -> SignInMessage {
+        SignInMessage::new(
+            "Ethereum",
+            "example.com",
+            "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266",
+            "https://example.com/login",
+            "1",
+        )
+        .with_nonce("32891756aBcDeF12")
+        .with_issued_at("2024-01-01T00:00:00Z".parse().unwrap())
+    }
+
+    #[test]
+    fn test_render_matches_eip4361() {
+        let text = message().with_statement("Sign in to Example").to_string();
+        assert_eq!(
+            text,
+            "example.com wants you to sign in with your Ethereum account:\n\
+             0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266\n\
+             \n\
+             Sign in to Example\n\
+             \n\
+             URI: https://example.com/login\n\
+             Version: 1\n\
+             Chain ID: 1\n\
+             Nonce: 32891756aBcDeF12\n\
+             Issued At: 2024-01-01T00:00:00Z"
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let issued_at = message().issued_at;
+        let full = message()
+            .with_statement("Sign in to Example")
+            .with_expiration_time(issued_at + Duration::minutes(5))
+            .with_not_before(issued_at)
+            .with_request_id("req-1")
+            .with_resource("ipfs://bafybeiemxf5abjwjbikoz4mc3a3dla6ual3jsgpdr4cjr3oz3evfyavhwq")
+            .with_resource("https://example.com/profile");
+
+        assert_eq!(full.to_string().parse::<SignInMessage>().unwrap(), full);
+        assert_eq!(
+            message().to_string().parse::<SignInMessage>().unwrap(),
+            message()
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed() {
+        let text = message().to_string();
+        assert!(text
+            .replace("Version: 1", "Version: 2")
+            .parse::<SignInMessage>()
+            .is_err());
+        assert!(text
+            .replace("32891756aBcDeF12", "short")
+            .parse::<SignInMessage>()
+            .is_err());
+        assert!(format!("{}\nExtra: line", text)
+            .parse::<SignInMessage>()
+            .is_err());
+        assert!("example.com wants you".parse::<SignInMessage>().is_err());
+    }
+
+    #[test]
+    fn test_validate() {
+        let issued_at = message().issued_at;
+        let msg = message()
+            .with_not_before(issued_at + Duration::minutes(1))
+            .with_expiration_time(issued_at + Duration::minutes(5));
+        let at = |minutes| issued_at + Duration::minutes(minutes);
+
+        assert_eq!(
+            msg.validate_at("example.com", "32891756aBcDeF12", at(2)),
+            Ok(())
+        );
+        assert_eq!(
+            msg.validate_at("example.com", "32891756aBcDeF12", at(0)),
+            Err(AuthError::NotYetValid)
+        );
+        assert_eq!(
+            msg.validate_at("example.com", "32891756aBcDeF12", at(5)),
+            Err(AuthError::Expired)
+        );
+        assert_eq!(
+            msg.validate_at("example.com", "other0000", at(2)),
+            Err(AuthError::NonceMismatch)
+        );
+        assert!(matches!(
+            msg.validate_at("evil.com", "32891756aBcDeF12", at(2)),
+            Err(AuthError::DomainMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_generate_nonce() {
+        let nonce = generate_nonce();
+        assert_eq!(nonce.len(), NONCE_LEN);
+        assert!(nonce.chars().all(|c| c.is_ascii_alphanumeric()));
+        assert_ne!(nonce, generate_nonce());
+    }
+}
@@ -0,0 +1,81 @@
+//! Errors returned by [`ChainAdapter`](crate::ChainAdapter) operations
+//!
+//! The variants mirror the EVM and Substrate adapter errors, so generic code
+//! can tell failures worth retrying from ones that will fail again.
+
+use thiserror::Error;
+
+/// Chain adapter errors
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum AdapterError {
+    #[error("Connection error: {0}")]
+    Connection(String),
+
+    #[error("Rate limited: {0}")]
+    RateLimited(String),
+
+    #[error("Transaction error: {0}")]
+    Transaction(String),
+
+    #[error("Invalid address: {0}")]
+    InvalidAddress(String),
+
+    #[error("Contract error: {0}")]
+    Contract(String),
+
+    #[error("Metadata error: {0}")]
+    Metadata(String),
+
+    #[error("Not found: {0}")]
+    NotFound(String),
+
+    #[error("Storage error: {0}")]
+    Storage(String),
+
+    #[error("Wallet error: {0}")]
+    Wallet(String),
+
+    #[error("Signature error: {0}")]
+    Signature(String),
+
+    #[error("Encoding error: {0}")]
+    Encoding(String),
+
+    #[error("{chain} adapter does not support {operation}")]
+    Unsupported { chain: String, operation: String },
+
+    #[error("Other error: {0}")]
+    Other(String),
+}
+
+impl AdapterError {
+    /// Whether the operation may succeed if retried: the node could not be
+    /// reached or asked us to slow down
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            AdapterError::Connection(_) | AdapterError::RateLimited(_)
+        )
+    }
+}
+
+/// Result type for chain adapter operations
+pub type Result<T> = std::result::Result<T, AdapterError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_retryable() {
+        assert!(AdapterError::Connection("reset".into()).is_retryable());
+        assert!(AdapterError::RateLimited("429".into()).is_retryable());
+        assert!(!AdapterError::Transaction("nonce too low".into()).is_retryable());
+        assert!(!AdapterError::NotFound("latest block".into()).is_retryable());
+        assert!(!AdapterError::Unsupported {
+            chain: "EVM".into(),
+            operation: "get_block".into(),
+        }
+        .is_retryable());
+    }
+}
@@ -0,0 +1,148 @@
+//! PKCS#11 hardware security modules
+//!
+//! [`Pkcs11Session`] logs into a token and signs with keys looked up by
+//! label. Private keys stay on the device; only public keys and signatures
+//! are read back. Chain-specific signers in the adapter crates build on this.
+
+use cryptoki::context::{CInitializeArgs, Pkcs11};
+use cryptoki::mechanism::Mechanism;
+use cryptoki::object::{Attribute, AttributeType, ObjectClass, ObjectHandle};
+use cryptoki::session::{Session, UserType};
+use cryptoki::types::AuthPin;
+use std::path::Path;
+use std::sync::Mutex;
+use thiserror::Error;
+
+/// HSM errors
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum HsmError {
+    #[error("Key not found: {0}")]
+    KeyNotFound(String),
+
+    #[error("HSM error: {0}")]
+    Backend(String),
+}
+
+/// Result type for HSM operations
+pub type Result<T> = std::result::Result<T, HsmError>;
+
+/// Signature mechanism
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignMechanism {
+    /// Raw ECDSA over a pre-hashed digest; returns `r || s`
+    Ecdsa,
+    /// Pure EdDSA over the message
+    Eddsa,
+}
+
+/// Logged-in session on a PKCS#11 token
+pub struct Pkcs11Session {
+    session: Mutex<Session>,
+}
+
+impl Pkcs11Session {
+    /// Load `module`, open the token in `slot_index` and log in with `pin`
+    pub fn open(module: impl AsRef<Path>, slot_index: usize, pin: &str) -> Result<Self> {
+        let context = Pkcs11::new(module.as_ref()).map_err(backend)?;
+        context
+            .initialize(CInitializeArgs::OsThreads)
+            .map_err(backend)?;
+
+        let slot = *context
+            .get_slots_with_token()
+            .map_err(backend)?
+            .get(slot_index)
+            .ok_or_else(|| HsmError::Backend(format!("No token in slot {}", slot_index)))?;
+        let session = context.open_ro_session(slot).map_err(backend)?;
+        session
+            .login(UserType::User, Some(&AuthPin::new(pin.to_string())))
+            .map_err(backend)?;
+
+        Ok(Self {
+            session: Mutex::new(session),
+        })
+    }
+
+    /// Public key point of the key pair labelled `label`
+    ///
+    /// Returns the raw point (SEC1 for EC keys, 32 bytes for Ed25519), with
+    /// any DER `OCTET STRING` wrapper removed.
+    pub fn public_key(&self, label: &str) -> Result<Vec<u8>> {
+        let session = self.lock();
+        let handle = find_key(&session, label, ObjectClass::PUBLIC_KEY)?;
+        let point = session
+            .get_attributes(handle, &[AttributeType::EcPoint])
+            .map_err(backend)?
+            .into_iter()
+            .find_map(|attribute| match attribute {
+                Attribute::EcPoint(point) => Some(point),
+                _ => None,
+            })
+            .ok_or_else(|| HsmError::Backend(format!("{} has no EC point", label)))?;
+        Ok(unwrap_octet_string(point))
+    }
+
+    /// Sign `data` with the private key labelled `label`
+    pub fn sign(&self, label: &str, mechanism: SignMechanism, data: &[u8]) -> Result<Vec<u8>> {
+        let session = self.lock();
+        let handle = find_key(&session, label, ObjectClass::PRIVATE_KEY)?;
+        let mechanism = match mechanism {
+            SignMechanism::Ecdsa => Mechanism::Ecdsa,
+            SignMechanism::Eddsa => Mechanism::Eddsa,
+        };
+        session.sign(&mechanism, handle, data).map_err(backend)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Session> {
+        self.session.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+fn find_key(session: &Session, label: &str, class: ObjectClass) -> Result<ObjectHandle> {
+    session
+        .find_objects(&[
+            Attribute::Class(class),
+            Attribute::Label(label.as_bytes().to_vec()),
+        ])
+        .map_err(backend)?
+        .into_iter()
+        .next()
+        .ok_or_else(|| HsmError::KeyNotFound(label.to_string()))
+}
+
+/// Strip a DER `OCTET STRING` header around a 65-byte SEC1 or 32-byte
+/// Ed25519 point
+fn unwrap_octet_string(mut point: Vec<u8>) -> Vec<u8> {
+    let wrapped = point.len() >= 2
+        && point[0] == 0x04
+        && point[1] as usize == point.len() - 2
+        && matches!(point.len() - 2, 32 | 33 | 65);
+    if wrapped {
+        point.drain(..2);
+    }
+    point
+}
+
+fn backend(e: cryptoki::error::Error) -> HsmError {
+    HsmError::Backend(e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unwrap_octet_string() {
+        let mut sec1 = vec![0x04];
+        sec1.extend_from_slice(&[7u8; 64]);
+
+        let mut wrapped = vec![0x04, 65];
+        wrapped.extend_from_slice(&sec1);
+        assert_eq!(unwrap_octet_string(wrapped), sec1);
+        assert_eq!(unwrap_octet_string(sec1.clone()), sec1);
+
+        let mut ed25519 = vec![0x04, 32];
+        ed25519.extend_from_slice(&[1u8; 32]);
+        assert_eq!(unwrap_octet_string(ed25519), vec![1u8; 32]);
+    }
+}
@@ -8,9 +8,18 @@
 //!
 //! ## Features
 //!
-//! - **Chain Adapter Trait**: Common interface for all blockchain types
+//! - **Chain Adapter Trait**: Common interface for all blockchain types:
+//!   transaction status, balances, blocks and raw transaction submission,
+//!   failing with a typed [`AdapterError`]
 //! - **Transaction Builder**: Flexible transaction construction
 //! - **Type-safe abstractions**: Generic over chain implementations
+//! - **Sign-In Messages**: [`auth::SignInMessage`] (EIP-4361 / CAIP-122)
+//! - **Secret Storage**: [`secret::SecretStore`] backends for signing keys
+//! - **HSM Signing**: [`hsm::Pkcs11Session`] for PKCS#11 tokens (`pkcs11` feature)
+//! - **Remote Signing**: [`remote_signer::RemoteSigner`] for keys in a KMS,
+//!   Vault or signing service
+//! - **Rate Limiting**: [`performance::RateLimiter`] shared per RPC endpoint
+//! - **Redaction**: [`redact::Redacted`] and endpoint scrubbing for logs
 //!
 //! ## Usage
 //!
@@ -19,35 +28,128 @@
 //! the main `apex-sdk` crate.
 //!
 //! ```rust,no_run
-//! use apex_sdk_core::ChainAdapter;
+//! use apex_sdk_core::{AdapterError, ChainAdapter};
 //! use apex_sdk_types::{Address, TransactionStatus};
+//! use std::time::Duration;
+//!
+//! const MAX_ATTEMPTS: u32 = 5;
 //!
 //! async fn check_transaction<T: ChainAdapter>(
 //!     adapter: &T,
 //!     tx_hash: &str
-//! ) -> Result<TransactionStatus, String> {
+//! ) -> Result<TransactionStatus, AdapterError> {
+//!     let mut delay = Duration::from_millis(250);
+//!     for _ in 1..MAX_ATTEMPTS {
+//!         match adapter.get_transaction_status(tx_hash).await {
+//!             // Back off before retrying, doubling the delay each time
+//!             Err(e) if e.is_retryable() => {
+//!                 tokio::time::sleep(delay).await;
+//!                 delay *= 2;
+//!             }
+//!             result => return result,
+//!         }
+//!     }
 //!     adapter.get_transaction_status(tx_hash).await
 //! }
 //! ```
 
-use apex_sdk_types::{Address, TransactionStatus};
+pub mod auth;
+pub mod error;
+#[cfg(feature = "pkcs11")]
+pub mod hsm;
+pub mod performance;
+pub mod redact;
+pub mod remote_signer;
+pub mod secret;
+
+use apex_sdk_types::{Address, BlockInfo, TransactionStatus};
 use async_trait::async_trait;
+use std::fmt;
+
+pub use error::AdapterError;
+
+/// Block to look up with [`ChainAdapter::get_block`]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum BlockRef {
+    /// Block at this height
+    Number(u64),
+    /// Block with this hash (0x-prefixed hex)
+    Hash(String),
+}
+
+impl From<u64> for BlockRef {
+    fn from(number: u64) -> Self {
+        BlockRef::Number(number)
+    }
+}
+
+impl fmt::Display for BlockRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BlockRef::Number(number) => write!(f, "#{}", number),
+            BlockRef::Hash(hash) => f.write_str(hash),
+        }
+    }
+}
 
 /// Trait for blockchain adapters
-#[async_trait]
+///
+/// Only transaction status, address validation and the chain name are
+/// required; the other operations return [`AdapterError::Unsupported`]
+/// unless the adapter implements them.
+///
+/// On `wasm32` targets the async methods are not required to be `Send`,
+/// since browser futures run on a single-threaded event loop.
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
 pub trait ChainAdapter: Send + Sync {
     /// Get the transaction status
-    async fn get_transaction_status(&self, tx_hash: &str) -> Result<TransactionStatus, String>;
+    async fn get_transaction_status(
+        &self,
+        tx_hash: &str,
+    ) -> Result<TransactionStatus, AdapterError>;
 
     /// Validate an address for this chain
     fn validate_address(&self, address: &Address) -> bool;
 
     /// Get the chain name
     fn chain_name(&self) -> &str;
+
+    /// Get the native balance of `address`, in the smallest unit
+    async fn get_balance(&self, address: &Address) -> Result<u128, AdapterError> {
+        let _ = address;
+        Err(unsupported(self.chain_name(), "get_balance"))
+    }
+
+    /// Get the header of a block, `None` if the chain does not have it
+    async fn get_block(&self, block: BlockRef) -> Result<Option<BlockInfo>, AdapterError> {
+        let _ = block;
+        Err(unsupported(self.chain_name(), "get_block"))
+    }
+
+    /// Get the header of the newest block
+    async fn latest_block(&self) -> Result<BlockInfo, AdapterError> {
+        Err(unsupported(self.chain_name(), "latest_block"))
+    }
+
+    /// Broadcast an already-signed, encoded transaction and return its hash
+    async fn submit_transaction(&self, tx: &[u8]) -> Result<String, AdapterError> {
+        let _ = tx;
+        Err(unsupported(self.chain_name(), "submit_transaction"))
+    }
+}
+
+/// Error returned by [`ChainAdapter`] operations an adapter does not implement
+fn unsupported(chain: &str, operation: &str) -> AdapterError {
+    AdapterError::Unsupported {
+        chain: chain.to_string(),
+        operation: operation.to_string(),
+    }
 }
 
 /// Transaction builder trait
-#[async_trait]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
 pub trait TransactionBuilder {
     /// Set the sender address
     fn from(&mut self, address: Address) -> &mut Self;
@@ -59,5 +161,40 @@ pub trait TransactionBuilder {
     fn amount(&mut self, amount: u128) -> &mut Self;
 
     /// Build the transaction
-    fn build(&self) -> Result<Vec<u8>, String>;
+    fn build(&self) -> Result<Vec<u8>, AdapterError>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct StatusOnly;
+
+    #[async_trait]
+    impl ChainAdapter for StatusOnly {
+        async fn get_transaction_status(&self, _: &str) -> Result<TransactionStatus, AdapterError> {
+            Ok(TransactionStatus::Unknown)
+        }
+
+        fn validate_address(&self, _: &Address) -> bool {
+            true
+        }
+
+        fn chain_name(&self) -> &str {
+            "Test"
+        }
+    }
+
+    #[tokio::test]
+    async fn test_default_operations_are_unsupported() {
+        let adapter = StatusOnly;
+        let address = Address::evm("0x742D35Cc6634C0532925A3B844bC9e7595f0bEB7");
+        let err = adapter.get_balance(&address).await.unwrap_err();
+        assert_eq!(err.to_string(), "Test adapter does not support get_balance");
+        assert!(!err.is_retryable());
+        assert!(adapter.get_block(BlockRef::from(1)).await.is_err());
+        assert!(adapter.latest_block().await.is_err());
+        assert!(adapter.submit_transaction(&[0x01]).await.is_err());
+        assert_eq!(BlockRef::Number(7).to_string(), "#7");
+    }
 }
@@ -0,0 +1,205 @@
+//! Request rate limiting for RPC endpoints
+//!
+//! [`RateLimiter`] is a token bucket: up to `burst` requests go out
+//! immediately, after which requests are admitted at `requests_per_second`.
+//! Requests beyond the bucket are queued (the caller is told how long to
+//! wait) until `max_queue` are waiting, at which point they are rejected
+//! instead of piling up behind a throttled endpoint.
+//!
+//! Limiters obtained through [`RateLimiter::for_endpoint`] are shared by
+//! every adapter talking to the same endpoint, so bulk operations across
+//! several adapters still respect a single provider quota.
+
+use crate::redact::redact_url;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock, Weak};
+use std::time::Duration;
+use thiserror::Error;
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+#[cfg(target_arch = "wasm32")]
+use web_time::Instant;
+
+/// Rate limiting errors
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum RateLimitError {
+    #[error("Rate limit queue full for {0}")]
+    QueueFull(String),
+}
+
+/// Rate limit for a single endpoint
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimitConfig {
+    /// Sustained request rate
+    pub requests_per_second: f64,
+    /// Requests allowed back-to-back before throttling starts
+    pub burst: u32,
+    /// Requests allowed to wait for a slot before new ones are rejected
+    pub max_queue: u32,
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self {
+            requests_per_second: 10.0,
+            burst: 10,
+            max_queue: 100,
+        }
+    }
+}
+
+impl RateLimitConfig {
+    /// Limit to `requests_per_second`, with a burst of the same size
+    pub fn new(requests_per_second: f64) -> Self {
+        Self {
+            requests_per_second,
+            burst: requests_per_second.ceil().max(1.0) as u32,
+            ..Self::default()
+        }
+    }
+
+    /// Set the burst size
+    pub fn with_burst(mut self, burst: u32) -> Self {
+        self.burst = burst.max(1);
+        self
+    }
+
+    /// Set how many requests may queue for a slot
+    pub fn with_max_queue(mut self, max_queue: u32) -> Self {
+        self.max_queue = max_queue;
+        self
+    }
+}
+
+#[derive(Debug)]
+struct Bucket {
+    /// Available tokens; negative while requests are queued
+    tokens: f64,
+    last_refill: Instant,
+}
+
+/// Token bucket rate limiter
+#[derive(Debug)]
+pub struct RateLimiter {
+    name: String,
+    config: RateLimitConfig,
+    bucket: Mutex<Bucket>,
+}
+
+impl RateLimiter {
+    /// Create a standalone limiter
+    pub fn new(config: RateLimitConfig) -> Self {
+        Self::named("rate limiter", config)
+    }
+
+    fn named(name: &str, config: RateLimitConfig) -> Self {
+        Self {
+            name: name.to_string(),
+            config,
+            bucket: Mutex::new(Bucket {
+                tokens: config.burst as f64,
+                last_refill: Instant::now(),
+            }),
+        }
+    }
+
+    /// Limiter shared by everything using `endpoint`
+    ///
+    /// The first caller's `config` wins while the limiter is alive.
+    pub fn for_endpoint(endpoint: &str, config: RateLimitConfig) -> Arc<Self> {
+        static REGISTRY: OnceLock<Mutex<HashMap<String, Weak<RateLimiter>>>> = OnceLock::new();
+
+        let mut registry = REGISTRY
+            .get_or_init(Default::default)
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        if let Some(limiter) = registry.get(endpoint).and_then(Weak::upgrade) {
+            return limiter;
+        }
+
+        registry.retain(|_, limiter| limiter.strong_count() > 0);
+        let limiter = Arc::new(Self::named(&redact_url(endpoint), config));
+        registry.insert(endpoint.to_string(), Arc::downgrade(&limiter));
+        limiter
+    }
+
+    /// The configured limit
+    pub fn config(&self) -> &RateLimitConfig {
+        &self.config
+    }
+
+    /// Reserve a slot, returning how long to wait before using it
+    pub fn reserve(&self) -> Result<Duration, RateLimitError> {
+        let mut bucket = self.bucket.lock().unwrap_or_else(|e| e.into_inner());
+
+        let now = Instant::now();
+        let elapsed = now.duration_since(bucket.last_refill).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * self.config.requests_per_second)
+            .min(self.config.burst as f64);
+        bucket.last_refill = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            return Ok(Duration::ZERO);
+        }
+        if bucket.tokens - 1.0 < -(self.config.max_queue as f64) {
+            return Err(RateLimitError::QueueFull(self.name.clone()));
+        }
+
+        bucket.tokens -= 1.0;
+        Ok(Duration::from_secs_f64(
+            -bucket.tokens / self.config.requests_per_second,
+        ))
+    }
+
+    /// Number of requests currently waiting for a slot
+    pub fn queued(&self) -> u32 {
+        let bucket = self.bucket.lock().unwrap_or_else(|e| e.into_inner());
+        (-bucket.tokens).ceil().max(0.0) as u32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_burst_then_queue() {
+        let limiter = RateLimiter::new(RateLimitConfig::new(10.0).with_burst(3).with_max_queue(2));
+
+        for _ in 0..3 {
+            assert_eq!(limiter.reserve().unwrap(), Duration::ZERO);
+        }
+
+        let first = limiter.reserve().unwrap();
+        let second = limiter.reserve().unwrap();
+        assert!(first > Duration::ZERO && first <= Duration::from_millis(100));
+        assert!(second > first && second <= Duration::from_millis(200));
+        assert_eq!(limiter.queued(), 2);
+
+        assert!(matches!(
+            limiter.reserve(),
+            Err(RateLimitError::QueueFull(_))
+        ));
+    }
+
+    #[test]
+    fn test_refill() {
+        let limiter = RateLimiter::new(RateLimitConfig::new(1000.0).with_burst(1));
+        assert_eq!(limiter.reserve().unwrap(), Duration::ZERO);
+        std::thread::sleep(Duration::from_millis(5));
+        assert_eq!(limiter.reserve().unwrap(), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_shared_per_endpoint() {
+        let a = RateLimiter::for_endpoint("https://rpc.example", RateLimitConfig::new(5.0));
+        let b = RateLimiter::for_endpoint("https://rpc.example", RateLimitConfig::new(50.0));
+        let other = RateLimiter::for_endpoint("https://other.example", RateLimitConfig::new(5.0));
+
+        assert!(Arc::ptr_eq(&a, &b));
+        assert!(!Arc::ptr_eq(&a, &other));
+        assert_eq!(b.config().requests_per_second, 5.0);
+    }
+}
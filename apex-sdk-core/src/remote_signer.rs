@@ -0,0 +1,329 @@
+//! Signing with keys that never enter the SDK process
+//!
+//! A [`RemoteSigner`] hands a message to wherever the key lives (a KMS, Vault,
+//! a signing service) and returns the raw signature. It is chain-agnostic:
+//! the EVM adapter wraps secp256k1 signers as a `DigestSigner`, and the
+//! Substrate adapter signs extrinsic payloads with ed25519 or sr25519 ones.
+//!
+//! Backends:
+//! - `vault::VaultTransitSigner` (feature `vault`): HashiCorp Vault transit
+//!   secrets engine
+//! - `AwsKmsSigner` in `apex-sdk-evm` (feature `aws-kms`)
+//! - `JsonRpcSigner` in `apex-sdk-substrate`
+
+use async_trait::async_trait;
+use thiserror::Error;
+
+/// Remote signer error
+#[derive(Error, Debug)]
+pub enum SignerError {
+    #[error("Backend error: {0}")]
+    Backend(String),
+
+    #[error("Invalid response: {0}")]
+    InvalidResponse(String),
+
+    #[error("Invalid input: {0}")]
+    InvalidInput(String),
+
+    #[error("Unsupported: {0}")]
+    Unsupported(String),
+}
+
+/// Result type for remote signer operations
+pub type Result<T> = std::result::Result<T, SignerError>;
+
+/// Signature scheme of a remote key
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SignatureScheme {
+    /// ECDSA over secp256k1, used by EVM chains
+    EcdsaSecp256k1,
+    /// Ed25519
+    Ed25519,
+    /// Schnorrkel over Ristretto25519, the Substrate default
+    Sr25519,
+}
+
+/// Signs messages with a key held outside the process
+///
+/// For [`SignatureScheme::EcdsaSecp256k1`] the message is a 32-byte digest and
+/// the signature is 64-byte `r || s` (or DER, for backends that only return
+/// that); recovery is left to the caller. For ed25519 and sr25519 the message
+/// is signed as is and the signature is 64 bytes.
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+pub trait RemoteSigner: Send + Sync {
+    /// Public key of the signing key
+    ///
+    /// 32 bytes for ed25519 and sr25519; SEC1 or a DER
+    /// `SubjectPublicKeyInfo` for secp256k1.
+    fn public_key(&self) -> &[u8];
+
+    /// Signature scheme of the key
+    fn scheme(&self) -> SignatureScheme;
+
+    /// Sign `message`
+    async fn sign(&self, message: &[u8]) -> Result<Vec<u8>>;
+}
+
+/// HashiCorp Vault transit signer
+///
+/// Stock Vault transit supports ed25519 keys, usable for Substrate accounts,
+/// but has no secp256k1 key type. EVM signing needs a transit-compatible
+/// engine that reports its keys as `ecdsa-secp256k1`.
+#[cfg(all(feature = "vault", not(target_arch = "wasm32")))]
+pub mod vault {
+    use super::{RemoteSigner, Result, SignatureScheme, SignerError};
+    use async_trait::async_trait;
+    use base64::engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD};
+    use base64::Engine;
+    use serde_json::{json, Value};
+
+    /// Signs with a key in a Vault transit secrets engine
+    ///
+    /// The token needs `read` on `<mount>/keys/<key>` and `update` on
+    /// `<mount>/sign/<key>`.
+    pub struct VaultTransitSigner {
+        client: reqwest::Client,
+        address: String,
+        token: String,
+        namespace: Option<String>,
+        mount: String,
+        key: String,
+        version: u64,
+        scheme: SignatureScheme,
+        public_key: Vec<u8>,
+    }
+
+    impl std::fmt::Debug for VaultTransitSigner {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("VaultTransitSigner")
+                .field("address", &self.address)
+                .field("mount", &self.mount)
+                .field("key", &self.key)
+                .field("version", &self.version)
+                .field("scheme", &self.scheme)
+                .finish_non_exhaustive()
+        }
+    }
+
+    impl VaultTransitSigner {
+        /// Use `key` on the transit engine mounted at `transit`, fetching
+        /// its latest public key
+        ///
+        /// `address` is the Vault server, e.g. `https://vault:8200`.
+        pub async fn new(
+            address: impl Into<String>,
+            token: impl Into<String>,
+            key: impl Into<String>,
+        ) -> Result<Self> {
+            Self::with_mount(address, token, "transit", key, None).await
+        }
+
+        /// Use `key` on the transit engine mounted at `mount`, optionally in
+        /// a Vault Enterprise `namespace`
+        pub async fn with_mount(
+            address: impl Into<String>,
+            token: impl Into<String>,
+            mount: impl Into<String>,
+            key: impl Into<String>,
+            namespace: Option<String>,
+        ) -> Result<Self> {
+            let mut signer = Self {
+                client: reqwest::Client::new(),
+                address: address.into().trim_end_matches('/').to_string(),
+                token: token.into(),
+                namespace,
+                mount: mount.into().trim_matches('/').to_string(),
+                key: key.into(),
+                version: 0,
+                scheme: SignatureScheme::Ed25519,
+                public_key: Vec::new(),
+            };
+
+            let url = signer.url("keys");
+            let response = signer.send(signer.client.get(url)).await?;
+            let (scheme, version, public_key) = parse_key(&response)?;
+            signer.scheme = scheme;
+            signer.version = version;
+            signer.public_key = public_key;
+            Ok(signer)
+        }
+
+        /// Key version used for signing
+        pub fn version(&self) -> u64 {
+            self.version
+        }
+
+        fn url(&self, endpoint: &str) -> String {
+            format!(
+                "{}/v1/{}/{}/{}",
+                self.address, self.mount, endpoint, self.key
+            )
+        }
+
+        async fn send(&self, request: reqwest::RequestBuilder) -> Result<Value> {
+            let mut request = request.header("X-Vault-Token", &self.token);
+            if let Some(namespace) = &self.namespace {
+                request = request.header("X-Vault-Namespace", namespace);
+            }
+            let response = request
+                .send()
+                .await
+                .map_err(|e| SignerError::Backend(format!("Vault request failed: {}", e)))?;
+            let status = response.status();
+            let body: Value = response
+                .json()
+                .await
+                .map_err(|e| SignerError::InvalidResponse(format!("Vault response: {}", e)))?;
+            if !status.is_success() {
+                return Err(SignerError::Backend(format!(
+                    "Vault returned {}: {}",
+                    status, body["errors"]
+                )));
+            }
+            Ok(body)
+        }
+    }
+
+    #[async_trait]
+    impl RemoteSigner for VaultTransitSigner {
+        fn public_key(&self) -> &[u8] {
+            &self.public_key
+        }
+
+        fn scheme(&self) -> SignatureScheme {
+            self.scheme
+        }
+
+        async fn sign(&self, message: &[u8]) -> Result<Vec<u8>> {
+            let mut body = json!({
+                "input": STANDARD.encode(message),
+                "key_version": self.version,
+            });
+            if self.scheme == SignatureScheme::EcdsaSecp256k1 {
+                if message.len() != 32 {
+                    return Err(SignerError::InvalidInput(format!(
+                        "Expected a 32-byte digest, got {} bytes",
+                        message.len()
+                    )));
+                }
+                body["prehashed"] = json!(true);
+                body["hash_algorithm"] = json!("sha2-256");
+                body["marshaling_algorithm"] = json!("jws");
+            }
+
+            let request = self.client.post(self.url("sign")).json(&body);
+            let response = self.send(request).await?;
+            parse_signature(&response, self.scheme)
+        }
+    }
+
+    /// Scheme, latest version and public key from a `keys/<key>` response
+    pub(crate) fn parse_key(response: &Value) -> Result<(SignatureScheme, u64, Vec<u8>)> {
+        let data = &response["data"];
+        let scheme = match data["type"].as_str() {
+            Some("ed25519") => SignatureScheme::Ed25519,
+            Some("ecdsa-secp256k1") => SignatureScheme::EcdsaSecp256k1,
+            Some(other) => {
+                return Err(SignerError::Unsupported(format!(
+                    "Vault key type {}",
+                    other
+                )))
+            }
+            None => return Err(SignerError::InvalidResponse("Missing key type".to_string())),
+        };
+        let version = data["latest_version"]
+            .as_u64()
+            .ok_or_else(|| SignerError::InvalidResponse("Missing key version".to_string()))?;
+
+        let entry = &data["keys"][version.to_string()];
+        let encoded = entry["public_key"]
+            .as_str()
+            .or_else(|| entry.as_str())
+            .ok_or_else(|| SignerError::InvalidResponse("Missing public key".to_string()))?;
+        // ECDSA keys come as PEM, ed25519 keys as bare base64
+        let encoded: String = encoded
+            .lines()
+            .filter(|line| !line.starts_with("-----"))
+            .collect();
+        let public_key = STANDARD
+            .decode(encoded)
+            .map_err(|e| SignerError::InvalidResponse(format!("Invalid public key: {}", e)))?;
+
+        Ok((scheme, version, public_key))
+    }
+
+    /// Raw signature bytes from a `sign/<key>` response
+    pub(crate) fn parse_signature(response: &Value, scheme: SignatureScheme) -> Result<Vec<u8>> {
+        let signature = response["data"]["signature"]
+            .as_str()
+            .ok_or_else(|| SignerError::InvalidResponse("Missing signature".to_string()))?;
+        // vault:v<version>:<base64>
+        let encoded = signature
+            .splitn(3, ':')
+            .nth(2)
+            .filter(|_| signature.starts_with("vault:"))
+            .ok_or_else(|| {
+                SignerError::InvalidResponse(format!("Unexpected signature format: {}", signature))
+            })?;
+        let decoded = match scheme {
+            SignatureScheme::EcdsaSecp256k1 => URL_SAFE_NO_PAD.decode(encoded),
+            _ => STANDARD.decode(encoded),
+        };
+        decoded.map_err(|e| SignerError::InvalidResponse(format!("Invalid signature: {}", e)))
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_parse_ed25519_key() {
+            let public_key = [7u8; 32];
+            let response = json!({
+                "data": {
+                    "type": "ed25519",
+                    "latest_version": 2,
+                    "keys": {
+                        "1": { "public_key": STANDARD.encode([1u8; 32]) },
+                        "2": { "public_key": STANDARD.encode(public_key) }
+                    }
+                }
+            });
+            let (scheme, version, key) = parse_key(&response).unwrap();
+            assert_eq!(scheme, SignatureScheme::Ed25519);
+            assert_eq!(version, 2);
+            assert_eq!(key, public_key);
+        }
+
+        #[test]
+        fn test_rejects_unsupported_key() {
+            let response = json!({
+                "data": { "type": "aes256-gcm96", "latest_version": 1, "keys": { "1": 0 } }
+            });
+            assert!(matches!(
+                parse_key(&response),
+                Err(SignerError::Unsupported(_))
+            ));
+        }
+
+        #[test]
+        fn test_parse_signature() {
+            let ed = json!({ "data": { "signature": format!("vault:v1:{}", STANDARD.encode([3u8; 64])) } });
+            assert_eq!(
+                parse_signature(&ed, SignatureScheme::Ed25519).unwrap(),
+                vec![3u8; 64]
+            );
+
+            let jws = json!({ "data": { "signature": format!("vault:v4:{}", URL_SAFE_NO_PAD.encode([0xffu8; 64])) } });
+            assert_eq!(
+                parse_signature(&jws, SignatureScheme::EcdsaSecp256k1).unwrap(),
+                vec![0xffu8; 64]
+            );
+
+            let bad = json!({ "data": { "signature": "not-a-vault-signature" } });
+            assert!(parse_signature(&bad, SignatureScheme::Ed25519).is_err());
+        }
+    }
+}
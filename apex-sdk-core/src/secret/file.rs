@@ -0,0 +1,145 @@
+//! File-backed secret store
+
+use super::{validate_id, Result, SecretBytes, SecretError, SecretStore};
+use async_trait::async_trait;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Secret store keeping one file per secret under a directory
+///
+/// Files are created with mode `0600` on Unix. Contents are stored as-is, so
+/// on its own this only moves keys out of env vars; wrap it in a
+/// `KmsSecretStore` to keep them encrypted at rest.
+pub struct FileSecretStore {
+    root: PathBuf,
+}
+
+impl FileSecretStore {
+    /// Create a store rooted at `root`, creating the directory if needed
+    pub fn new(root: impl Into<PathBuf>) -> Result<Self> {
+        let root = root.into();
+        std::fs::create_dir_all(&root).map_err(|e| {
+            SecretError::Backend(format!("Failed to create {}: {}", root.display(), e))
+        })?;
+        Ok(Self { root })
+    }
+
+    /// Directory holding the secrets
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    fn path(&self, id: &str) -> Result<PathBuf> {
+        validate_id(id)?;
+        Ok(self.root.join(id))
+    }
+}
+
+/// Run blocking file I/O on the blocking pool
+async fn blocking<T, F>(f: F) -> Result<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T> + Send + 'static,
+{
+    tokio::task::spawn_blocking(f)
+        .await
+        .map_err(|e| SecretError::Backend(format!("File I/O task failed: {}", e)))?
+}
+
+#[async_trait]
+impl SecretStore for FileSecretStore {
+    async fn get(&self, id: &str) -> Result<SecretBytes> {
+        let path = self.path(id)?;
+        let id = id.to_string();
+        blocking(move || match std::fs::read(&path) {
+            Ok(bytes) => Ok(SecretBytes::new(bytes)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Err(SecretError::NotFound(id)),
+            Err(e) => Err(SecretError::Backend(format!(
+                "Failed to read {}: {}",
+                path.display(),
+                e
+            ))),
+        })
+        .await
+    }
+
+    async fn put(&self, id: &str, secret: &SecretBytes) -> Result<()> {
+        let path = self.path(id)?;
+        let secret = secret.clone();
+        blocking(move || {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent).map_err(|e| {
+                    SecretError::Backend(format!("Failed to create {}: {}", parent.display(), e))
+                })?;
+            }
+
+            let mut options = std::fs::OpenOptions::new();
+            options.write(true).create(true).truncate(true);
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::OpenOptionsExt;
+                options.mode(0o600);
+            }
+
+            options
+                .open(&path)
+                .and_then(|mut file| file.write_all(secret.expose()))
+                .map_err(|e| {
+                    SecretError::Backend(format!("Failed to write {}: {}", path.display(), e))
+                })
+        })
+        .await
+    }
+
+    async fn delete(&self, id: &str) -> Result<()> {
+        let path = self.path(id)?;
+        blocking(move || match std::fs::remove_file(&path) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(SecretError::Backend(format!(
+                "Failed to delete {}: {}",
+                path.display(),
+                e
+            ))),
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_file_store_roundtrip() {
+        let root = std::env::temp_dir().join(format!("apex-secrets-{}", std::process::id()));
+        let store = FileSecretStore::new(&root).unwrap();
+        let secret = SecretBytes::new(vec![1, 2, 3]);
+
+        store.put("service/key", &secret).await.unwrap();
+        assert_eq!(store.get("service/key").await.unwrap(), secret);
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(root.join("service/key"))
+                .unwrap()
+                .permissions()
+                .mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        store.delete("service/key").await.unwrap();
+        store.delete("service/key").await.unwrap();
+        assert!(matches!(
+            store.get("service/key").await,
+            Err(SecretError::NotFound(_))
+        ));
+        assert!(matches!(
+            store.get("../escape").await,
+            Err(SecretError::InvalidId(_))
+        ));
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+}
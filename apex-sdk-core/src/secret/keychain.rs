@@ -0,0 +1,81 @@
+//! OS keychain secret store
+
+use super::{validate_id, Result, SecretBytes, SecretError, SecretStore};
+use async_trait::async_trait;
+use keyring::Entry;
+
+/// Secret store backed by the platform credential store
+///
+/// Uses the macOS Keychain, the Windows Credential Manager (DPAPI-protected)
+/// or the freedesktop Secret Service (GNOME Keyring, KWallet) on Linux.
+/// Secrets are stored under `service` with the secret id as the account name.
+pub struct KeychainSecretStore {
+    service: String,
+}
+
+impl KeychainSecretStore {
+    /// Create a store namespaced under `service`
+    pub fn new(service: impl Into<String>) -> Self {
+        Self {
+            service: service.into(),
+        }
+    }
+
+    /// The keychain service name
+    pub fn service(&self) -> &str {
+        &self.service
+    }
+
+    /// Run `f` with the entry for `id` on the blocking pool, since the
+    /// platform credential APIs block
+    async fn with_entry<T, F>(&self, id: &str, f: F) -> Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(Entry) -> Result<T> + Send + 'static,
+    {
+        validate_id(id)?;
+        let entry = Entry::new(&self.service, id).map_err(backend_error)?;
+        tokio::task::spawn_blocking(move || f(entry))
+            .await
+            .map_err(|e| SecretError::Backend(format!("Keychain task failed: {}", e)))?
+    }
+}
+
+impl Default for KeychainSecretStore {
+    fn default() -> Self {
+        Self::new("apex-sdk")
+    }
+}
+
+#[async_trait]
+impl SecretStore for KeychainSecretStore {
+    async fn get(&self, id: &str) -> Result<SecretBytes> {
+        let missing = SecretError::NotFound(id.to_string());
+        self.with_entry(id, move |entry| match entry.get_secret() {
+            Ok(bytes) => Ok(SecretBytes::new(bytes)),
+            Err(keyring::Error::NoEntry) => Err(missing),
+            Err(e) => Err(backend_error(e)),
+        })
+        .await
+    }
+
+    async fn put(&self, id: &str, secret: &SecretBytes) -> Result<()> {
+        let secret = secret.clone();
+        self.with_entry(id, move |entry| {
+            entry.set_secret(secret.expose()).map_err(backend_error)
+        })
+        .await
+    }
+
+    async fn delete(&self, id: &str) -> Result<()> {
+        self.with_entry(id, |entry| match entry.delete_credential() {
+            Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+            Err(e) => Err(backend_error(e)),
+        })
+        .await
+    }
+}
+
+fn backend_error(e: keyring::Error) -> SecretError {
+    SecretError::Backend(format!("Keychain error: {}", e))
+}
@@ -0,0 +1,296 @@
+//! Envelope encryption with KMS-wrapped data keys
+//!
+//! Each secret is encrypted with AES-256-GCM under a fresh data key. The data
+//! key is wrapped by a [`KeyWrapper`] (typically a cloud KMS key that never
+//! leaves the HSM) and stored alongside the ciphertext in an inner
+//! [`SecretStore`]. Reading a secret requires both access to the inner store
+//! and permission to unwrap with the KMS key.
+
+use super::{Result, SecretBytes, SecretError, SecretStore};
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use aes_gcm::{Aes256Gcm, Nonce};
+use async_trait::async_trait;
+
+const ENVELOPE_VERSION: u8 = 1;
+const NONCE_LEN: usize = 12;
+
+/// Wraps and unwraps data keys, e.g. with a cloud KMS key
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+pub trait KeyWrapper: Send + Sync {
+    /// Encrypt a data key
+    async fn wrap(&self, data_key: &SecretBytes) -> Result<Vec<u8>>;
+
+    /// Decrypt a data key previously returned by [`wrap`](Self::wrap)
+    async fn unwrap(&self, wrapped: &[u8]) -> Result<SecretBytes>;
+}
+
+/// Key wrapper using a local AES-256-GCM master key
+///
+/// Useful for tests and for deployments that inject a single master key.
+/// Prefer a KMS-backed wrapper in production.
+pub struct LocalKeyWrapper {
+    cipher: Aes256Gcm,
+}
+
+impl LocalKeyWrapper {
+    /// Create a wrapper from a 32-byte master key
+    pub fn new(master_key: &SecretBytes) -> Result<Self> {
+        Ok(Self {
+            cipher: cipher(master_key)?,
+        })
+    }
+
+    /// Create a wrapper with a random master key
+    pub fn generate() -> Self {
+        Self {
+            cipher: Aes256Gcm::new(&Aes256Gcm::generate_key(OsRng)),
+        }
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl KeyWrapper for LocalKeyWrapper {
+    async fn wrap(&self, data_key: &SecretBytes) -> Result<Vec<u8>> {
+        seal(&self.cipher, data_key.expose(), b"")
+    }
+
+    async fn unwrap(&self, wrapped: &[u8]) -> Result<SecretBytes> {
+        open(&self.cipher, wrapped, b"")
+    }
+}
+
+/// Secret store that envelope-encrypts secrets before handing them to `S`
+///
+/// The secret id is bound as associated data, so ciphertexts can't be swapped
+/// between ids.
+pub struct KmsSecretStore<W, S> {
+    wrapper: W,
+    inner: S,
+}
+
+impl<W: KeyWrapper, S: SecretStore> KmsSecretStore<W, S> {
+    /// Encrypt secrets with data keys wrapped by `wrapper`, stored in `inner`
+    pub fn new(wrapper: W, inner: S) -> Self {
+        Self { wrapper, inner }
+    }
+
+    /// The underlying ciphertext store
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<W: KeyWrapper, S: SecretStore> SecretStore for KmsSecretStore<W, S> {
+    async fn get(&self, id: &str) -> Result<SecretBytes> {
+        let envelope = self.inner.get(id).await?;
+        let envelope = envelope.expose();
+
+        if envelope.first() != Some(&ENVELOPE_VERSION) || envelope.len() < 3 {
+            return Err(SecretError::Crypto(format!(
+                "Unsupported envelope for {}",
+                id
+            )));
+        }
+        let wrapped_len = u16::from_be_bytes([envelope[1], envelope[2]]) as usize;
+        let wrapped = envelope
+            .get(3..3 + wrapped_len)
+            .ok_or_else(|| SecretError::Crypto(format!("Truncated envelope for {}", id)))?;
+
+        let data_key = self.wrapper.unwrap(wrapped).await?;
+        open(
+            &cipher(&data_key)?,
+            &envelope[3 + wrapped_len..],
+            id.as_bytes(),
+        )
+    }
+
+    async fn put(&self, id: &str, secret: &SecretBytes) -> Result<()> {
+        let data_key = SecretBytes::new(Aes256Gcm::generate_key(OsRng).to_vec());
+        let sealed = seal(&cipher(&data_key)?, secret.expose(), id.as_bytes())?;
+
+        let wrapped = self.wrapper.wrap(&data_key).await?;
+        let wrapped_len = u16::try_from(wrapped.len())
+            .map_err(|_| SecretError::Crypto("Wrapped data key too large".to_string()))?;
+
+        let mut envelope = Vec::with_capacity(3 + wrapped.len() + sealed.len());
+        envelope.push(ENVELOPE_VERSION);
+        envelope.extend_from_slice(&wrapped_len.to_be_bytes());
+        envelope.extend_from_slice(&wrapped);
+        envelope.extend_from_slice(&sealed);
+
+        self.inner.put(id, &SecretBytes::new(envelope)).await
+    }
+
+    async fn delete(&self, id: &str) -> Result<()> {
+        self.inner.delete(id).await
+    }
+
+    async fn contains(&self, id: &str) -> Result<bool> {
+        self.inner.contains(id).await
+    }
+}
+
+/// AES-256-GCM cipher for a 32-byte key
+fn cipher(key: &SecretBytes) -> Result<Aes256Gcm> {
+    Aes256Gcm::new_from_slice(key.expose())
+        .map_err(|_| SecretError::Crypto("Key must be 32 bytes".to_string()))
+}
+
+/// Encrypt to `nonce || ciphertext`
+fn seal(cipher: &Aes256Gcm, plaintext: &[u8], aad: &[u8]) -> Result<Vec<u8>> {
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+    let ciphertext = cipher
+        .encrypt(
+            &nonce,
+            Payload {
+                msg: plaintext,
+                aad,
+            },
+        )
+        .map_err(|_| SecretError::Crypto("Encryption failed".to_string()))?;
+
+    let mut out = nonce.to_vec();
+    out.extend_from_slice(&ciphertext);
+    Ok(out)
+}
+
+/// Decrypt `nonce || ciphertext`
+fn open(cipher: &Aes256Gcm, sealed: &[u8], aad: &[u8]) -> Result<SecretBytes> {
+    if sealed.len() < NONCE_LEN {
+        return Err(SecretError::Crypto("Ciphertext too short".to_string()));
+    }
+    let (nonce, ciphertext) = sealed.split_at(NONCE_LEN);
+    let nonce: [u8; NONCE_LEN] = nonce.try_into().expect("split at nonce length");
+    cipher
+        .decrypt(
+            &Nonce::from(nonce),
+            Payload {
+                msg: ciphertext,
+                aad,
+            },
+        )
+        .map(SecretBytes::new)
+        .map_err(|_| SecretError::Crypto("Decryption failed".to_string()))
+}
+
+/// AWS KMS key wrapper
+#[cfg(feature = "aws-kms")]
+pub mod aws {
+    use super::{KeyWrapper, Result, SecretBytes, SecretError};
+    use async_trait::async_trait;
+    use aws_sdk_kms::error::DisplayErrorContext;
+    use aws_sdk_kms::primitives::Blob;
+    use aws_sdk_kms::Client;
+
+    /// Wraps data keys with an AWS KMS symmetric key
+    ///
+    /// The key material never leaves KMS; access is governed by the key's IAM
+    /// policy.
+    pub struct AwsKmsKeyWrapper {
+        client: Client,
+        key_id: String,
+    }
+
+    impl AwsKmsKeyWrapper {
+        /// Use `key_id` (key ARN, id or alias) through `client`
+        pub fn new(client: Client, key_id: impl Into<String>) -> Self {
+            Self {
+                client,
+                key_id: key_id.into(),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl KeyWrapper for AwsKmsKeyWrapper {
+        async fn wrap(&self, data_key: &SecretBytes) -> Result<Vec<u8>> {
+            let output = self
+                .client
+                .encrypt()
+                .key_id(&self.key_id)
+                .plaintext(Blob::new(data_key.expose().to_vec()))
+                .send()
+                .await
+                .map_err(|e| {
+                    SecretError::Backend(format!("KMS encrypt failed: {}", DisplayErrorContext(e)))
+                })?;
+
+            output
+                .ciphertext_blob()
+                .map(|blob| blob.as_ref().to_vec())
+                .ok_or_else(|| SecretError::Backend("KMS returned no ciphertext".to_string()))
+        }
+
+        async fn unwrap(&self, wrapped: &[u8]) -> Result<SecretBytes> {
+            let output = self
+                .client
+                .decrypt()
+                .key_id(&self.key_id)
+                .ciphertext_blob(Blob::new(wrapped.to_vec()))
+                .send()
+                .await
+                .map_err(|e| {
+                    SecretError::Backend(format!("KMS decrypt failed: {}", DisplayErrorContext(e)))
+                })?;
+
+            output
+                .plaintext()
+                .map(|blob| SecretBytes::new(blob.as_ref().to_vec()))
+                .ok_or_else(|| SecretError::Backend("KMS returned no plaintext".to_string()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::secret::MemorySecretStore;
+
+    #[tokio::test]
+    async fn test_envelope_roundtrip() {
+        let store = KmsSecretStore::new(LocalKeyWrapper::generate(), MemorySecretStore::new());
+        let secret = SecretBytes::new(vec![42u8; 32]);
+
+        store.put("signer", &secret).await.unwrap();
+        assert_eq!(store.get("signer").await.unwrap(), secret);
+
+        // Ciphertext at rest differs from the plaintext
+        let stored = store.inner().get("signer").await.unwrap();
+        assert!(!stored
+            .expose()
+            .windows(secret.len())
+            .any(|w| w == secret.expose()));
+    }
+
+    #[tokio::test]
+    async fn test_envelope_bound_to_id() {
+        let store = KmsSecretStore::new(LocalKeyWrapper::generate(), MemorySecretStore::new());
+        store
+            .put("a", &SecretBytes::new(vec![1, 2, 3]))
+            .await
+            .unwrap();
+
+        let envelope = store.inner().get("a").await.unwrap();
+        store.inner().put("b", &envelope).await.unwrap();
+        assert!(matches!(store.get("b").await, Err(SecretError::Crypto(_))));
+    }
+
+    #[tokio::test]
+    async fn test_wrong_master_key_fails() {
+        let inner = MemorySecretStore::new();
+        let writer = KmsSecretStore::new(LocalKeyWrapper::generate(), inner);
+        writer
+            .put("a", &SecretBytes::new(vec![9; 8]))
+            .await
+            .unwrap();
+
+        let envelope = writer.inner().get("a").await.unwrap();
+        let reader = KmsSecretStore::new(LocalKeyWrapper::generate(), MemorySecretStore::new());
+        reader.inner().put("a", &envelope).await.unwrap();
+        assert!(reader.get("a").await.is_err());
+    }
+}
@@ -0,0 +1,219 @@
+//! Secret storage for signing keys
+//!
+//! [`SecretStore`] abstracts where key material lives so that long-running
+//! services don't have to keep raw seeds in environment variables or config
+//! files. Wallets in the EVM and Substrate adapters load their keys from a
+//! store on demand.
+//!
+//! Backends:
+//! - [`MemorySecretStore`]: in-process map, for tests
+//! - [`FileSecretStore`]: one owner-readable file per secret
+//! - `KeychainSecretStore` (feature `keychain`): macOS Keychain, Windows
+//!   Credential Manager (DPAPI) or the Secret Service on Linux
+//! - `KmsSecretStore` (feature `kms`): envelope encryption where each secret
+//!   is encrypted with a fresh data key wrapped by a `KeyWrapper`, e.g. AWS
+//!   KMS (feature `aws-kms`)
+
+#[cfg(not(target_arch = "wasm32"))]
+mod file;
+#[cfg(feature = "keychain")]
+mod keychain;
+#[cfg(feature = "kms")]
+pub mod kms;
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use file::FileSecretStore;
+#[cfg(feature = "keychain")]
+pub use keychain::KeychainSecretStore;
+#[cfg(feature = "kms")]
+pub use kms::{KeyWrapper, KmsSecretStore};
+
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::RwLock;
+use thiserror::Error;
+use zeroize::Zeroizing;
+
+/// Secret store error
+#[derive(Error, Debug)]
+pub enum SecretError {
+    #[error("Secret not found: {0}")]
+    NotFound(String),
+
+    #[error("Invalid secret id: {0}")]
+    InvalidId(String),
+
+    #[error("Backend error: {0}")]
+    Backend(String),
+
+    #[error("Crypto error: {0}")]
+    Crypto(String),
+}
+
+/// Result type for secret store operations
+pub type Result<T> = std::result::Result<T, SecretError>;
+
+/// Secret key material that is zeroed on drop
+///
+/// `Debug` is redacted so secrets don't end up in logs.
+#[derive(Clone, PartialEq, Eq)]
+pub struct SecretBytes(Zeroizing<Vec<u8>>);
+
+impl SecretBytes {
+    /// Wrap raw key material
+    pub fn new(bytes: Vec<u8>) -> Self {
+        Self(Zeroizing::new(bytes))
+    }
+
+    /// Access the raw bytes
+    pub fn expose(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Length in bytes
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Whether the secret is empty
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl From<Vec<u8>> for SecretBytes {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self::new(bytes)
+    }
+}
+
+impl From<&[u8]> for SecretBytes {
+    fn from(bytes: &[u8]) -> Self {
+        Self::new(bytes.to_vec())
+    }
+}
+
+impl std::fmt::Debug for SecretBytes {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "SecretBytes([REDACTED; {}])", self.0.len())
+    }
+}
+
+/// Storage backend for secret key material
+///
+/// Secrets are addressed by an opaque id such as `"treasury/evm"`.
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+pub trait SecretStore: Send + Sync {
+    /// Load a secret, failing with [`SecretError::NotFound`] if absent
+    async fn get(&self, id: &str) -> Result<SecretBytes>;
+
+    /// Store a secret, replacing any existing value
+    async fn put(&self, id: &str, secret: &SecretBytes) -> Result<()>;
+
+    /// Delete a secret; deleting a missing secret is not an error
+    async fn delete(&self, id: &str) -> Result<()>;
+
+    /// Whether a secret exists
+    async fn contains(&self, id: &str) -> Result<bool> {
+        match self.get(id).await {
+            Ok(_) => Ok(true),
+            Err(SecretError::NotFound(_)) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+/// In-memory secret store, mainly for tests
+#[derive(Default)]
+pub struct MemorySecretStore {
+    secrets: RwLock<HashMap<String, SecretBytes>>,
+}
+
+impl MemorySecretStore {
+    /// Create an empty store
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl SecretStore for MemorySecretStore {
+    async fn get(&self, id: &str) -> Result<SecretBytes> {
+        self.secrets
+            .read()
+            .unwrap()
+            .get(id)
+            .cloned()
+            .ok_or_else(|| SecretError::NotFound(id.to_string()))
+    }
+
+    async fn put(&self, id: &str, secret: &SecretBytes) -> Result<()> {
+        validate_id(id)?;
+        self.secrets
+            .write()
+            .unwrap()
+            .insert(id.to_string(), secret.clone());
+        Ok(())
+    }
+
+    async fn delete(&self, id: &str) -> Result<()> {
+        self.secrets.write().unwrap().remove(id);
+        Ok(())
+    }
+}
+
+/// Reject ids that are empty or could escape a backend's namespace
+pub(crate) fn validate_id(id: &str) -> Result<()> {
+    let valid = !id.is_empty()
+        && id.len() <= 128
+        && id
+            .split('/')
+            .all(|part| !part.is_empty() && part != "." && part != "..")
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '/'));
+
+    if valid {
+        Ok(())
+    } else {
+        Err(SecretError::InvalidId(id.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_memory_store_roundtrip() {
+        let store = MemorySecretStore::new();
+        let secret = SecretBytes::from(&[7u8; 32][..]);
+
+        assert!(!store.contains("treasury/evm").await.unwrap());
+        store.put("treasury/evm", &secret).await.unwrap();
+        assert_eq!(store.get("treasury/evm").await.unwrap(), secret);
+
+        store.delete("treasury/evm").await.unwrap();
+        assert!(matches!(
+            store.get("treasury/evm").await,
+            Err(SecretError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_secret_debug_is_redacted() {
+        let secret = SecretBytes::new(vec![0xab; 4]);
+        assert_eq!(format!("{:?}", secret), "SecretBytes([REDACTED; 4])");
+    }
+
+    #[test]
+    fn test_validate_id() {
+        assert!(validate_id("treasury/evm-0").is_ok());
+        assert!(validate_id("").is_err());
+        assert!(validate_id("../etc/passwd").is_err());
+        assert!(validate_id("a//b").is_err());
+        assert!(validate_id("key with spaces").is_err());
+    }
+}
@@ -0,0 +1,187 @@
+//! Rate-limit responses from RPC endpoints
+//!
+//! Public endpoints answer bursts with HTTP 429 or JSON-RPC error `-32005`
+//! instead of serving the request. [`rate_limit`] recognizes these responses
+//! and how long the endpoint asked to be left alone, so callers can wait or
+//! rotate to another endpoint rather than surface an opaque error:
+//! [`EvmAdapter::raw_request`](crate::EvmAdapter::raw_request) waits and
+//! retries, [`ConnectionPool::raw_request`](crate::pool::ConnectionPool::raw_request)
+//! rotates to the next endpoint.
+//!
+//! The HTTP transport does not expose response headers, so the `Retry-After`
+//! hint is read from the response body, where providers repeat it as text
+//! (`Retry-After: 2`, `try again in 500ms`) or in the error data
+//! (`retry_after`, `backoff_seconds`).
+
+use ethers::providers::{ProviderError, RpcError};
+use std::time::Duration;
+
+/// JSON-RPC error codes meaning "rate limited"
+pub const RATE_LIMIT_CODES: [i64; 2] = [429, -32005];
+
+/// A rate-limit response
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    /// How long the endpoint asked callers to wait, if it said
+    pub retry_after: Option<Duration>,
+}
+
+/// The rate limit signalled by `error`, if it is a rate-limit response
+pub fn rate_limit(error: &ProviderError) -> Option<RateLimit> {
+    if let Some(response) = error.as_error_response() {
+        if !RATE_LIMIT_CODES.contains(&response.code) && !is_rate_limit_text(&response.message) {
+            return None;
+        }
+        let retry_after = response
+            .data
+            .as_ref()
+            .and_then(retry_after_in_data)
+            .or_else(|| retry_after_in_text(&response.message));
+        return Some(RateLimit { retry_after });
+    }
+
+    // Non-JSON bodies, e.g. a bare "429 Too Many Requests" page
+    let text = error.to_string();
+    is_rate_limit_text(&text).then(|| RateLimit {
+        retry_after: retry_after_in_text(&text),
+    })
+}
+
+/// Parse a `Retry-After` value in seconds, e.g. `"30"` or `"1.5"`
+///
+/// HTTP dates are not supported and give `None`.
+pub fn parse_retry_after(value: &str) -> Option<Duration> {
+    let seconds: f64 = value.trim().parse().ok()?;
+    (seconds.is_finite() && seconds >= 0.0).then(|| Duration::from_secs_f64(seconds))
+}
+
+fn is_rate_limit_text(text: &str) -> bool {
+    let text = text.to_ascii_lowercase();
+    ["too many requests", "rate limit", "rate-limit", "ratelimit"]
+        .iter()
+        .any(|phrase| text.contains(phrase))
+}
+
+/// Wait hint in error data, as `retry_after`, `retryAfter` or Infura's
+/// `rate.backoff_seconds`
+fn retry_after_in_data(data: &serde_json::Value) -> Option<Duration> {
+    let object = data.as_object()?;
+    for key in [
+        "retry_after",
+        "retryAfter",
+        "Retry-After",
+        "backoff_seconds",
+    ] {
+        let hint = match object.get(key) {
+            Some(serde_json::Value::Number(n)) => n
+                .as_f64()
+                .filter(|s| s.is_finite() && *s >= 0.0)
+                .map(Duration::from_secs_f64),
+            Some(serde_json::Value::String(s)) => parse_retry_after(s),
+            _ => None,
+        };
+        if hint.is_some() {
+            return hint;
+        }
+    }
+    object.values().find_map(retry_after_in_data)
+}
+
+/// Wait hint in a message, after "retry-after", "retry after" or
+/// "try again in", in seconds unless followed by "ms"
+fn retry_after_in_text(text: &str) -> Option<Duration> {
+    let lower = text.to_ascii_lowercase();
+    ["retry-after", "retry after", "try again in"]
+        .iter()
+        .find_map(|marker| {
+            let rest = &lower[lower.find(marker)? + marker.len()..];
+            let rest = rest.trim_start_matches([':', ' ', '"', '=']);
+            let end = rest
+                .find(|c: char| !c.is_ascii_digit() && c != '.')
+                .unwrap_or(rest.len());
+            let value = parse_retry_after(&rest[..end])?;
+            Some(if rest[end..].trim_start().starts_with("ms") {
+                value / 1000
+            } else {
+                value
+            })
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::providers::{HttpClientError, JsonRpcError};
+
+    fn rpc_error(code: i64, message: &str, data: Option<serde_json::Value>) -> ProviderError {
+        HttpClientError::JsonRpcError(JsonRpcError {
+            code,
+            message: message.to_string(),
+            data,
+        })
+        .into()
+    }
+
+    #[test]
+    fn test_detects_rate_limit_responses() {
+        // Infura
+        let infura = rpc_error(
+            -32005,
+            "daily request count exceeded, request rate limited",
+            Some(serde_json::json!({ "rate": { "allowed_rps": 1, "backoff_seconds": 30 } })),
+        );
+        assert_eq!(
+            rate_limit(&infura),
+            Some(RateLimit {
+                retry_after: Some(Duration::from_secs(30))
+            })
+        );
+
+        let alchemy = rpc_error(429, "Too many requests, try again in 250ms", None);
+        assert_eq!(
+            rate_limit(&alchemy).unwrap().retry_after,
+            Some(Duration::from_millis(250))
+        );
+
+        let plain = rpc_error(-32000, "Rate limit exceeded. Retry-After: 2", None);
+        assert_eq!(
+            rate_limit(&plain).unwrap().retry_after,
+            Some(Duration::from_secs(2))
+        );
+
+        // HTTP 429 with a non-JSON body
+        let page = ProviderError::from(HttpClientError::SerdeJson {
+            err: serde_json::from_str::<serde_json::Value>("<html>").unwrap_err(),
+            text: "429 Too Many Requests".to_string(),
+        });
+        assert_eq!(rate_limit(&page), Some(RateLimit { retry_after: None }));
+    }
+
+    #[test]
+    fn test_ignores_other_errors() {
+        assert_eq!(rate_limit(&rpc_error(3, "execution reverted", None)), None);
+        assert_eq!(
+            rate_limit(&rpc_error(
+                -32601,
+                "the method eth_foo does not exist",
+                None
+            )),
+            None
+        );
+        assert_eq!(
+            rate_limit(&ProviderError::CustomError("connection refused".into())),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_retry_after() {
+        assert_eq!(parse_retry_after("30"), Some(Duration::from_secs(30)));
+        assert_eq!(
+            parse_retry_after(" 1.5 "),
+            Some(Duration::from_millis(1500))
+        );
+        assert_eq!(parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT"), None);
+        assert_eq!(parse_retry_after("-1"), None);
+    }
+}
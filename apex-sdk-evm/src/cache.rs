@@ -9,9 +9,11 @@
 use std::collections::HashMap;
 use std::hash::Hash;
 use std::sync::Arc;
-use std::time::{Duration, Instant};
+use std::time::Duration;
 use tokio::sync::RwLock;
 
+use crate::runtime::{self, Instant};
+
 /// Cache entry with expiration
 #[derive(Clone)]
 struct CacheEntry<V> {
@@ -321,9 +323,9 @@ impl EvmCache {
         let interval = Duration::from_secs(self.config.cleanup_interval_secs);
         let interval_secs = self.config.cleanup_interval_secs;
 
-        tokio::spawn(async move {
+        runtime::spawn(async move {
             loop {
-                tokio::time::sleep(interval).await;
+                runtime::sleep(interval).await;
                 cache.cleanup().await;
             }
         });
@@ -0,0 +1,485 @@
+//! Account abstraction (ERC-4337) with paymaster sponsorship
+//!
+//! A [`SmartAccount`] sends calls as [`UserOperation`]s to a bundler instead
+//! of as transactions, so the account needs no ETH of its own when a
+//! [`Paymaster`] sponsors the gas. The account's owner key only signs the
+//! operation hash.
+//!
+//! Operations use the v0.6 format of [`ENTRY_POINT_V06`] and the
+//! `execute(address,uint256,bytes)` call of SimpleAccount-compatible
+//! accounts. [`RpcPaymaster`] requests sponsorship over the
+//! `pm_sponsorUserOperation` method offered by most paymaster services.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use apex_sdk_evm::erc4337::{BundlerClient, RpcPaymaster, SmartAccount};
+//! use apex_sdk_evm::{wallet::Wallet, EvmAdapter};
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let adapter = EvmAdapter::connect("https://eth.llamarpc.com").await?;
+//! let bundler = BundlerClient::connect("https://bundler.example.com/rpc")?;
+//! let paymaster = RpcPaymaster::connect("https://paymaster.example.com/rpc")?;
+//!
+//! let account = SmartAccount::new(
+//!     "0x742D35Cc6634C0532925A3B844bC9e7595f0bEB7".parse()?,
+//!     Wallet::new_random(),
+//!     adapter,
+//!     bundler,
+//! )
+//! .with_paymaster(paymaster);
+//!
+//! let op_hash = account
+//!     .send("0x8ba1f109551bD432803012645Ac136ddd64DBA72".parse()?, 0.into(), vec![])
+//!     .await?;
+//! println!("User operation: {:?}", op_hash);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::wallet::Wallet;
+use crate::{Error, EvmAdapter};
+use async_trait::async_trait;
+use ethers::abi::{self, ParamType, Token};
+use ethers::providers::{Http, Provider};
+use ethers::types::{Address as EthAddress, Bytes, TransactionReceipt, H256, U256};
+use ethers::utils::keccak256;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// Canonical address of the v0.6 EntryPoint contract
+pub const ENTRY_POINT_V06: &str = "0x5FF137D4b0FDCD49DcA30c7CF57E578a026d2789";
+
+/// Signature placed on operations while estimating gas, so that signature
+/// recovery in the account's validation does not revert
+const DUMMY_SIGNATURE: &str = "0xfffffffffffffffffffffffffffffff0000000000000000000000000000000007aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa1c";
+
+/// An ERC-4337 user operation (EntryPoint v0.6)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserOperation {
+    /// Smart account sending the operation
+    pub sender: EthAddress,
+    /// Anti-replay nonce from the EntryPoint
+    pub nonce: U256,
+    /// Factory call deploying the account, empty once deployed
+    pub init_code: Bytes,
+    /// Call the account executes
+    pub call_data: Bytes,
+    /// Gas for the execution phase
+    pub call_gas_limit: U256,
+    /// Gas for the validation phase
+    pub verification_gas_limit: U256,
+    /// Gas paid to the bundler for overhead
+    pub pre_verification_gas: U256,
+    /// Maximum fee per gas (EIP-1559)
+    pub max_fee_per_gas: U256,
+    /// Maximum priority fee per gas (EIP-1559)
+    pub max_priority_fee_per_gas: U256,
+    /// Paymaster address followed by its data, empty when self-funded
+    pub paymaster_and_data: Bytes,
+    /// Owner's signature over [`hash`](Self::hash)
+    pub signature: Bytes,
+}
+
+impl UserOperation {
+    /// Hash identifying the operation, signed by the account owner
+    pub fn hash(&self, entry_point: EthAddress, chain_id: u64) -> H256 {
+        let packed = abi::encode(&[
+            Token::Address(self.sender),
+            Token::Uint(self.nonce),
+            Token::FixedBytes(keccak256(&self.init_code).to_vec()),
+            Token::FixedBytes(keccak256(&self.call_data).to_vec()),
+            Token::Uint(self.call_gas_limit),
+            Token::Uint(self.verification_gas_limit),
+            Token::Uint(self.pre_verification_gas),
+            Token::Uint(self.max_fee_per_gas),
+            Token::Uint(self.max_priority_fee_per_gas),
+            Token::FixedBytes(keccak256(&self.paymaster_and_data).to_vec()),
+        ]);
+        H256(keccak256(abi::encode(&[
+            Token::FixedBytes(keccak256(packed).to_vec()),
+            Token::Address(entry_point),
+            Token::Uint(chain_id.into()),
+        ])))
+    }
+}
+
+/// Gas limits of a user operation, as estimated by a bundler or paymaster
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserOperationGas {
+    /// Gas paid to the bundler for overhead
+    pub pre_verification_gas: U256,
+    /// Gas for the validation phase
+    pub verification_gas_limit: U256,
+    /// Gas for the execution phase
+    pub call_gas_limit: U256,
+}
+
+impl UserOperationGas {
+    fn apply(&self, op: &mut UserOperation) {
+        op.pre_verification_gas = self.pre_verification_gas;
+        op.verification_gas_limit = self.verification_gas_limit;
+        op.call_gas_limit = self.call_gas_limit;
+    }
+}
+
+/// A paymaster's agreement to pay for a user operation
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Sponsorship {
+    /// Value for [`UserOperation::paymaster_and_data`]
+    pub paymaster_and_data: Bytes,
+    /// Gas limits the sponsorship was signed for, if the paymaster set them
+    #[serde(flatten)]
+    pub gas: Option<UserOperationGas>,
+}
+
+/// Outcome of an included user operation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserOperationReceipt {
+    /// Hash of the user operation
+    pub user_op_hash: H256,
+    /// Whether the account's call succeeded
+    pub success: bool,
+    /// Gas cost paid by the account or paymaster, in wei
+    pub actual_gas_cost: U256,
+    /// Receipt of the bundle transaction
+    pub receipt: TransactionReceipt,
+}
+
+/// Pays gas on behalf of smart accounts
+#[async_trait]
+pub trait Paymaster: Send + Sync {
+    /// Sponsor `op`, whose gas limits and fees are already set
+    async fn sponsor(
+        &self,
+        op: &UserOperation,
+        entry_point: EthAddress,
+    ) -> Result<Sponsorship, Error>;
+}
+
+/// A paymaster service reached over `pm_sponsorUserOperation`
+pub struct RpcPaymaster {
+    provider: Provider<Http>,
+    context: Option<serde_json::Value>,
+}
+
+impl RpcPaymaster {
+    /// Use the paymaster service at `url`
+    pub fn connect(url: &str) -> Result<Self, Error> {
+        Ok(Self {
+            provider: http_provider(url)?,
+            context: None,
+        })
+    }
+
+    /// Pass `context` with every request, e.g. `{"sponsorshipPolicyId": "..."}`
+    pub fn with_context(mut self, context: serde_json::Value) -> Self {
+        self.context = Some(context);
+        self
+    }
+}
+
+#[async_trait]
+impl Paymaster for RpcPaymaster {
+    async fn sponsor(
+        &self,
+        op: &UserOperation,
+        entry_point: EthAddress,
+    ) -> Result<Sponsorship, Error> {
+        let mut params = vec![to_json(op)?, to_json(&entry_point)?];
+        params.extend(self.context.clone());
+        rpc(&self.provider, "pm_sponsorUserOperation", params).await
+    }
+}
+
+/// Client for an ERC-4337 bundler
+pub struct BundlerClient {
+    provider: Provider<Http>,
+    entry_point: EthAddress,
+}
+
+impl BundlerClient {
+    /// Use the bundler at `url` with the v0.6 EntryPoint
+    pub fn connect(url: &str) -> Result<Self, Error> {
+        Ok(Self {
+            provider: http_provider(url)?,
+            entry_point: ENTRY_POINT_V06.parse().expect("valid EntryPoint address"),
+        })
+    }
+
+    /// Use another EntryPoint deployment
+    pub fn with_entry_point(mut self, entry_point: EthAddress) -> Self {
+        self.entry_point = entry_point;
+        self
+    }
+
+    /// EntryPoint the operations are sent to
+    pub fn entry_point(&self) -> EthAddress {
+        self.entry_point
+    }
+
+    /// EntryPoints the bundler accepts operations for
+    pub async fn supported_entry_points(&self) -> Result<Vec<EthAddress>, Error> {
+        rpc(&self.provider, "eth_supportedEntryPoints", vec![]).await
+    }
+
+    /// Estimate the gas limits of `op`
+    pub async fn estimate_user_operation_gas(
+        &self,
+        op: &UserOperation,
+    ) -> Result<UserOperationGas, Error> {
+        rpc(
+            &self.provider,
+            "eth_estimateUserOperationGas",
+            vec![to_json(op)?, to_json(&self.entry_point)?],
+        )
+        .await
+    }
+
+    /// Submit a signed `op`, returning its hash
+    pub async fn send_user_operation(&self, op: &UserOperation) -> Result<H256, Error> {
+        rpc(
+            &self.provider,
+            "eth_sendUserOperation",
+            vec![to_json(op)?, to_json(&self.entry_point)?],
+        )
+        .await
+    }
+
+    /// Get the receipt of an operation, `None` while it is not included
+    pub async fn get_user_operation_receipt(
+        &self,
+        hash: H256,
+    ) -> Result<Option<UserOperationReceipt>, Error> {
+        rpc(
+            &self.provider,
+            "eth_getUserOperationReceipt",
+            vec![to_json(&hash)?],
+        )
+        .await
+    }
+}
+
+/// A SimpleAccount-compatible smart account controlled by an owner key
+pub struct SmartAccount {
+    address: EthAddress,
+    owner: Wallet,
+    adapter: EvmAdapter,
+    bundler: BundlerClient,
+    paymaster: Option<Arc<dyn Paymaster>>,
+    init_code: Bytes,
+}
+
+impl SmartAccount {
+    /// Smart account at `address`, signed for by `owner`
+    ///
+    /// `adapter` reads the nonce and fees from the chain the bundler serves.
+    pub fn new(
+        address: EthAddress,
+        owner: Wallet,
+        adapter: EvmAdapter,
+        bundler: BundlerClient,
+    ) -> Self {
+        Self {
+            address,
+            owner,
+            adapter,
+            bundler,
+            paymaster: None,
+            init_code: Bytes::default(),
+        }
+    }
+
+    /// Have `paymaster` pay for the account's operations
+    pub fn with_paymaster(mut self, paymaster: impl Paymaster + 'static) -> Self {
+        self.paymaster = Some(Arc::new(paymaster));
+        self
+    }
+
+    /// Deploy the account with `init_code` on its first operation
+    pub fn with_init_code(mut self, init_code: impl Into<Bytes>) -> Self {
+        self.init_code = init_code.into();
+        self
+    }
+
+    /// Address of the account
+    pub fn address(&self) -> EthAddress {
+        self.address
+    }
+
+    /// Build, sponsor and sign an operation calling `to` with `value` and `data`
+    pub async fn build_user_operation(
+        &self,
+        to: EthAddress,
+        value: U256,
+        data: Vec<u8>,
+    ) -> Result<UserOperation, Error> {
+        let entry_point = self.bundler.entry_point();
+        let (max_fee_per_gas, max_priority_fee_per_gas) = self.adapter.suggested_fees().await?;
+
+        let mut op = UserOperation {
+            sender: self.address,
+            nonce: self.nonce().await?,
+            init_code: self.init_code.clone(),
+            call_data: execute_call(to, value, data).into(),
+            max_fee_per_gas,
+            max_priority_fee_per_gas,
+            signature: DUMMY_SIGNATURE.parse().expect("valid dummy signature"),
+            ..Default::default()
+        };
+
+        match &self.paymaster {
+            Some(paymaster) => {
+                // The paymaster's estimate accounts for its own validation
+                let sponsorship = paymaster.sponsor(&op, entry_point).await?;
+                op.paymaster_and_data = sponsorship.paymaster_and_data;
+                match sponsorship.gas {
+                    Some(gas) => gas.apply(&mut op),
+                    None => self
+                        .bundler
+                        .estimate_user_operation_gas(&op)
+                        .await?
+                        .apply(&mut op),
+                }
+            }
+            None => self
+                .bundler
+                .estimate_user_operation_gas(&op)
+                .await?
+                .apply(&mut op),
+        }
+
+        let hash = op.hash(entry_point, self.adapter.chain_id().await?);
+        op.signature = self
+            .owner
+            .sign_message(hash.as_bytes())
+            .await?
+            .to_vec()
+            .into();
+        Ok(op)
+    }
+
+    /// Send a call to `to` with `value` and `data` through the bundler,
+    /// returning the user operation hash
+    pub async fn send(&self, to: EthAddress, value: U256, data: Vec<u8>) -> Result<H256, Error> {
+        let op = self.build_user_operation(to, value, data).await?;
+        self.bundler.send_user_operation(&op).await
+    }
+
+    async fn nonce(&self) -> Result<U256, Error> {
+        // getNonce(address,uint192)
+        let mut data = vec![0x35, 0x56, 0x7e, 0x1a];
+        data.extend(abi::encode(&[
+            Token::Address(self.address),
+            Token::Uint(U256::zero()),
+        ]));
+        let output = self
+            .adapter
+            .call_contract(&format!("{:?}", self.bundler.entry_point()), data)
+            .await?;
+        match abi::decode(&[ParamType::Uint(256)], &output)
+            .ok()
+            .as_deref()
+        {
+            Some([Token::Uint(nonce)]) => Ok(*nonce),
+            _ => Err(Error::Contract(
+                "Unexpected EntryPoint getNonce response".to_string(),
+            )),
+        }
+    }
+}
+
+/// Call data for the account's `execute(address,uint256,bytes)`
+fn execute_call(to: EthAddress, value: U256, data: Vec<u8>) -> Vec<u8> {
+    let mut call = vec![0xb6, 0x1d, 0x27, 0xf6];
+    call.extend(abi::encode(&[
+        Token::Address(to),
+        Token::Uint(value),
+        Token::Bytes(data),
+    ]));
+    call
+}
+
+fn http_provider(url: &str) -> Result<Provider<Http>, Error> {
+    Provider::<Http>::try_from(url)
+        .map_err(|e| Error::Connection(format!("Invalid endpoint URL: {}", e)))
+}
+
+fn to_json<T: Serialize>(value: &T) -> Result<serde_json::Value, Error> {
+    serde_json::to_value(value).map_err(|e| Error::Other(e.to_string()))
+}
+
+async fn rpc<R>(
+    provider: &Provider<Http>,
+    method: &str,
+    params: Vec<serde_json::Value>,
+) -> Result<R, Error>
+where
+    R: serde::de::DeserializeOwned + Serialize + std::fmt::Debug + Send,
+{
+    provider
+        .request(method, params)
+        .await
+        .map_err(|e| crate::rpc_error(&format!("{} failed", method), e, Error::Transaction))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn op() -> UserOperation {
+        UserOperation {
+            sender: "0x742D35Cc6634C0532925A3B844bC9e7595f0bEB7"
+                .parse()
+                .unwrap(),
+            nonce: 1.into(),
+            call_data: execute_call(EthAddress::zero(), 5.into(), vec![]).into(),
+            call_gas_limit: 50_000.into(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_user_operation_hash() {
+        let entry_point: EthAddress = ENTRY_POINT_V06.parse().unwrap();
+        let hash = op().hash(entry_point, 1);
+
+        // The signature is not part of the hash; chain and fields are
+        let mut signed = op();
+        signed.signature = vec![1; 65].into();
+        assert_eq!(signed.hash(entry_point, 1), hash);
+        assert_ne!(op().hash(entry_point, 137), hash);
+
+        let mut sponsored = op();
+        sponsored.paymaster_and_data = vec![2; 20].into();
+        assert_ne!(sponsored.hash(entry_point, 1), hash);
+    }
+
+    #[test]
+    fn test_user_operation_json() {
+        let json = serde_json::to_value(op()).unwrap();
+        assert_eq!(json["callGasLimit"], "0xc350");
+        assert_eq!(json["paymasterAndData"], "0x");
+        assert_eq!(serde_json::from_value::<UserOperation>(json).unwrap(), op());
+    }
+
+    #[test]
+    fn test_sponsorship_with_and_without_gas() {
+        let sponsorship: Sponsorship = serde_json::from_value(serde_json::json!({
+            "paymasterAndData": "0x0102",
+            "preVerificationGas": "0x1",
+            "verificationGasLimit": "0x2",
+            "callGasLimit": "0x3",
+        }))
+        .unwrap();
+        assert_eq!(sponsorship.gas.unwrap().call_gas_limit, 3.into());
+
+        let sponsorship: Sponsorship =
+            serde_json::from_value(serde_json::json!({ "paymasterAndData": "0x0102" })).unwrap();
+        assert_eq!(sponsorship.gas, None);
+    }
+}
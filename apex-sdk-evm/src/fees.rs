@@ -0,0 +1,225 @@
+//! EIP-1559 fee suggestions from recent blocks
+//!
+//! A [`FeeOracle`] reads the priority fees paid in recent blocks with
+//! `eth_feeHistory` and the base fee of the pending block, and suggests
+//! `max_fee_per_gas` / `max_priority_fee_per_gas` pairs for slow, standard
+//! and fast inclusion. Each estimate can be recorded in [`GasMetrics`].
+//!
+//! ```rust,no_run
+//! use apex_sdk_evm::fees::FeeSpeed;
+//! use apex_sdk_evm::EvmAdapter;
+//!
+//! # async fn example() -> Result<(), apex_sdk_evm::Error> {
+//! let adapter = EvmAdapter::connect("https://eth.llamarpc.com").await?;
+//! let fees = adapter.fee_oracle().estimate().await?;
+//! let fast = fees.suggestion(FeeSpeed::Fast);
+//! println!("max fee {} wei, tip {} wei", fast.max_fee_per_gas, fast.max_priority_fee_per_gas);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::metrics::{GasMetrics, GasPriceSnapshot};
+use crate::{Error, ProviderType};
+use apex_sdk_core::performance::RateLimiter;
+use ethers::types::{BlockNumber, FeeHistory, U256};
+use std::sync::Arc;
+
+/// Blocks of fee history an oracle looks at by default
+pub const DEFAULT_FEE_HISTORY_BLOCKS: u64 = 20;
+
+/// Reward percentiles requested for slow, standard and fast suggestions
+pub const REWARD_PERCENTILES: [f64; 3] = [10.0, 50.0, 90.0];
+
+/// How soon a transaction should be included
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum FeeSpeed {
+    /// Tip at the 10th percentile of recent blocks; room for the base fee
+    /// to rise 25%
+    Slow,
+    /// Tip at the median of recent blocks; room for the base fee to double
+    #[default]
+    Standard,
+    /// Tip at the 90th percentile of recent blocks; room for the base fee
+    /// to double
+    Fast,
+}
+
+/// Suggested EIP-1559 fees, in wei
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FeeSuggestion {
+    /// Most paid per gas, base fee included
+    pub max_fee_per_gas: U256,
+    /// Tip paid to the block producer per gas
+    pub max_priority_fee_per_gas: U256,
+}
+
+/// Fee suggestions for every [`FeeSpeed`], in wei
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FeeEstimate {
+    /// Base fee of the pending block
+    pub base_fee_per_gas: U256,
+    /// Suggestion for [`FeeSpeed::Slow`]
+    pub slow: FeeSuggestion,
+    /// Suggestion for [`FeeSpeed::Standard`]
+    pub standard: FeeSuggestion,
+    /// Suggestion for [`FeeSpeed::Fast`]
+    pub fast: FeeSuggestion,
+}
+
+impl FeeEstimate {
+    /// Build suggestions from the pending base fee and the tips paid in
+    /// recent blocks, one `[slow, standard, fast]` percentile row per block
+    pub fn from_rewards(base_fee_per_gas: U256, rewards: &[Vec<U256>]) -> Self {
+        let tip = |tier: usize| {
+            let mut tips: Vec<U256> = rewards
+                .iter()
+                .filter_map(|row| row.get(tier).copied())
+                .collect();
+            tips.sort();
+            tips.get(tips.len() / 2).copied().unwrap_or_default()
+        };
+        let suggestion = |tip: U256, base_fee: U256| FeeSuggestion {
+            max_fee_per_gas: base_fee + tip,
+            max_priority_fee_per_gas: tip,
+        };
+
+        Self {
+            base_fee_per_gas,
+            slow: suggestion(tip(0), base_fee_per_gas * 5 / 4),
+            standard: suggestion(tip(1), base_fee_per_gas * 2),
+            fast: suggestion(tip(2).max(tip(1)), base_fee_per_gas * 2),
+        }
+    }
+
+    /// Suggestion for `speed`
+    pub fn suggestion(&self, speed: FeeSpeed) -> FeeSuggestion {
+        match speed {
+            FeeSpeed::Slow => self.slow,
+            FeeSpeed::Standard => self.standard,
+            FeeSpeed::Fast => self.fast,
+        }
+    }
+}
+
+/// Suggests EIP-1559 fees from `eth_feeHistory` and the pending block
+#[derive(Clone)]
+pub struct FeeOracle {
+    provider: ProviderType,
+    limiter: Option<Arc<RateLimiter>>,
+    gas_metrics: Option<Arc<GasMetrics>>,
+    history_blocks: u64,
+}
+
+impl FeeOracle {
+    /// Create an oracle reading from `provider`
+    pub fn new(provider: ProviderType) -> Self {
+        Self {
+            provider,
+            limiter: None,
+            gas_metrics: None,
+            history_blocks: DEFAULT_FEE_HISTORY_BLOCKS,
+        }
+    }
+
+    /// Throttle RPC requests through `limiter`
+    pub fn with_rate_limiter(mut self, limiter: Option<Arc<RateLimiter>>) -> Self {
+        self.limiter = limiter;
+        self
+    }
+
+    /// Record a snapshot of every estimate in `metrics`, e.g. the `gas`
+    /// metrics of a [`MetricsCollector`](crate::metrics::MetricsCollector)
+    pub fn with_gas_metrics(mut self, metrics: Option<Arc<GasMetrics>>) -> Self {
+        self.gas_metrics = metrics;
+        self
+    }
+
+    /// Look at the last `blocks` blocks, at least one
+    pub fn with_history_blocks(mut self, blocks: u64) -> Self {
+        self.history_blocks = blocks.max(1);
+        self
+    }
+
+    /// Suggest fees for every speed
+    ///
+    /// Fails on chains without EIP-1559.
+    pub async fn estimate(&self) -> Result<FeeEstimate, Error> {
+        crate::throttle(self.limiter.as_deref()).await?;
+        let history = self
+            .provider
+            .request(
+                "eth_feeHistory",
+                serde_json::json!([
+                    U256::from(self.history_blocks),
+                    BlockNumber::Latest,
+                    REWARD_PERCENTILES
+                ]),
+            )
+            .await?;
+        let history: FeeHistory = serde_json::from_value(history)
+            .map_err(|e| Error::Other(format!("Invalid fee history: {}", e)))?;
+
+        crate::throttle(self.limiter.as_deref()).await?;
+        let pending = self.provider.get_block(BlockNumber::Pending.into()).await?;
+        // Nodes without a pending block still report the next base fee last
+        let base_fee = pending
+            .and_then(|block| block.base_fee_per_gas)
+            .or_else(|| history.base_fee_per_gas.last().copied())
+            .ok_or_else(|| Error::Other("EIP-1559 not supported".to_string()))?;
+
+        let estimate = FeeEstimate::from_rewards(base_fee, &history.reward);
+        if let Some(metrics) = &self.gas_metrics {
+            metrics.record_snapshot(snapshot(&estimate)).await;
+        }
+        Ok(estimate)
+    }
+}
+
+/// Gas metrics snapshot of the standard suggestion
+fn snapshot(estimate: &FeeEstimate) -> GasPriceSnapshot {
+    let gwei = |wei: U256| wei.as_u128() as f64 / 1e9;
+    GasPriceSnapshot {
+        timestamp: std::time::Instant::now(),
+        base_fee_gwei: gwei(estimate.base_fee_per_gas),
+        priority_fee_gwei: gwei(estimate.standard.max_priority_fee_per_gas),
+        gas_price_gwei: gwei(
+            estimate.base_fee_per_gas + estimate.standard.max_priority_fee_per_gas,
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gwei(n: u64) -> U256 {
+        U256::from(n) * 1_000_000_000u64
+    }
+
+    #[test]
+    fn test_from_rewards() {
+        let rewards = vec![
+            vec![gwei(1), gwei(2), gwei(5)],
+            vec![gwei(1), gwei(3), gwei(9)],
+            vec![gwei(2), gwei(2), gwei(4)],
+        ];
+        let estimate = FeeEstimate::from_rewards(gwei(20), &rewards);
+
+        assert_eq!(estimate.slow.max_priority_fee_per_gas, gwei(1));
+        assert_eq!(estimate.slow.max_fee_per_gas, gwei(26));
+        assert_eq!(estimate.standard.max_priority_fee_per_gas, gwei(2));
+        assert_eq!(estimate.standard.max_fee_per_gas, gwei(42));
+        assert_eq!(estimate.fast.max_priority_fee_per_gas, gwei(5));
+        assert_eq!(
+            estimate.suggestion(FeeSpeed::Fast).max_fee_per_gas,
+            gwei(45)
+        );
+    }
+
+    #[test]
+    fn test_from_empty_rewards() {
+        let estimate = FeeEstimate::from_rewards(gwei(10), &[]);
+        assert_eq!(estimate.standard.max_priority_fee_per_gas, U256::zero());
+        assert_eq!(estimate.standard.max_fee_per_gas, gwei(20));
+    }
+}
@@ -17,8 +17,18 @@
 //! - **Transaction Management**: Send, track, and query transactions
 //! - **Smart Contract Interaction**: Call and deploy contracts
 //! - **Wallet Integration**: Built-in wallet and signing support
+//! - **Sign-In with Ethereum**: EIP-4361 challenges signed and verified by address
+//! - **Sponsored Transactions**: ERC-4337 user operations with paymasters
+//! - **NFTs**: ERC-721 ownership, metadata, transfers and wrapped-collection mint/burn
+//! - **KMS/HSM Signing**: Non-exportable keys in AWS KMS or PKCS#11 HSMs
 //! - **Connection Pooling**: Efficient resource management
 //! - **Metrics Collection**: Performance monitoring
+//! - **Security Pre-flight**: Simulate transactions and flag risky state changes
+//! - **Rate Limiting**: Per-endpoint request limits with burst and queueing
+//! - **Rate-Limit Handling**: 429/`-32005` responses honored and rotated away from
+//! - **Historical State**: Balances, code and calls at past blocks on archive nodes
+//! - **Raw JSON-RPC**: Provider-specific methods such as `eth_getProof`
+//! - **WebAssembly**: Compiles to `wasm32-unknown-unknown` for browser dApps
 //!
 //! ## Quick Start
 //!
@@ -31,26 +41,49 @@
 //!     let adapter = EvmAdapter::connect("https://eth.llamarpc.com").await?;
 //!
 //!     // Get balance
-//!     let balance = adapter.get_balance("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb7").await?;
+//!     let balance = adapter.get_balance("0x742D35Cc6634C0532925A3B844bC9e7595f0bEB7").await?;
 //!     println!("Balance: {} wei", balance);
 //!
 //!     Ok(())
 //! }
 //! ```
 
+pub mod backpressure;
 pub mod cache;
+pub mod erc4337;
+pub mod fees;
 pub mod metrics;
+pub mod nft;
+pub mod nonce;
+pub mod offline;
 pub mod pool;
+pub mod preflight;
+pub mod signer;
+pub mod siwe;
 pub mod transaction;
 pub mod wallet;
 
-use apex_sdk_types::{Address, TransactionStatus};
+mod runtime;
+
+use apex_sdk_core::performance::{RateLimitConfig, RateLimiter};
+use apex_sdk_core::redact::{redact_url, redact_urls};
+use apex_sdk_core::{AdapterError, BlockRef};
+use apex_sdk_types::{
+    Address, Amount, BlockInfo, Chain, ChainType, ConfirmationPolicy, Event, EventFilter, Finality,
+    TransactionEvent, TransactionStatus,
+};
 use async_trait::async_trait;
 use thiserror::Error;
 
-use ethers::providers::{Http, Middleware, Provider, Ws};
-use ethers::types::{Address as EthAddress, TransactionReceipt, H256, U256};
+use ethers::abi::{Abi, Detokenize, Function, Tokenize};
+use ethers::providers::{Http, Middleware, Provider, StreamExt, Ws};
+use ethers::types::{
+    Address as EthAddress, Block, BlockId, BlockNumber, Log, TransactionReceipt,
+    TransactionRequest, H256, U256,
+};
+use futures::Stream;
 use std::sync::Arc;
+use std::time::Duration;
 
 /// EVM adapter error
 #[derive(Error, Debug)]
@@ -67,6 +100,9 @@ pub enum Error {
     #[error("Invalid address: {0}")]
     InvalidAddress(String),
 
+    #[error("Rate limited: {0}")]
+    RateLimited(String),
+
     #[error("Other error: {0}")]
     Other(String),
 }
@@ -85,12 +121,12 @@ impl ProviderType {
             ProviderType::Http(p) => p
                 .get_block_number()
                 .await
-                .map_err(|e| Error::Connection(format!("Failed to get block number: {}", e)))
+                .map_err(|e| rpc_error("Failed to get block number", e, Error::Connection))
                 .map(|n| U256::from(n.as_u64())),
             ProviderType::Ws(p) => p
                 .get_block_number()
                 .await
-                .map_err(|e| Error::Connection(format!("Failed to get block number: {}", e)))
+                .map_err(|e| rpc_error("Failed to get block number", e, Error::Connection))
                 .map(|n| U256::from(n.as_u64())),
         }
     }
@@ -103,11 +139,11 @@ impl ProviderType {
             ProviderType::Http(p) => p
                 .get_transaction_receipt(hash)
                 .await
-                .map_err(|e| Error::Transaction(format!("Failed to get receipt: {}", e))),
+                .map_err(|e| rpc_error("Failed to get receipt", e, Error::Transaction)),
             ProviderType::Ws(p) => p
                 .get_transaction_receipt(hash)
                 .await
-                .map_err(|e| Error::Transaction(format!("Failed to get receipt: {}", e))),
+                .map_err(|e| rpc_error("Failed to get receipt", e, Error::Transaction)),
         }
     }
 
@@ -119,11 +155,11 @@ impl ProviderType {
             ProviderType::Http(p) => p
                 .get_transaction(hash)
                 .await
-                .map_err(|e| Error::Transaction(format!("Failed to get transaction: {}", e))),
+                .map_err(|e| rpc_error("Failed to get transaction", e, Error::Transaction)),
             ProviderType::Ws(p) => p
                 .get_transaction(hash)
                 .await
-                .map_err(|e| Error::Transaction(format!("Failed to get transaction: {}", e))),
+                .map_err(|e| rpc_error("Failed to get transaction", e, Error::Transaction)),
         }
     }
 
@@ -136,11 +172,146 @@ impl ProviderType {
             ProviderType::Http(p) => p
                 .get_balance(address, block)
                 .await
-                .map_err(|e| Error::Connection(format!("Failed to get balance: {}", e))),
+                .map_err(|e| rpc_error("Failed to get balance", e, Error::Connection)),
             ProviderType::Ws(p) => p
                 .get_balance(address, block)
                 .await
-                .map_err(|e| Error::Connection(format!("Failed to get balance: {}", e))),
+                .map_err(|e| rpc_error("Failed to get balance", e, Error::Connection)),
+        }
+    }
+
+    async fn send_raw_transaction(&self, raw: ethers::types::Bytes) -> Result<H256, Error> {
+        match self {
+            ProviderType::Http(p) => p
+                .send_raw_transaction(raw)
+                .await
+                .map(|pending| pending.tx_hash())
+                .map_err(|e| rpc_error("Failed to send transaction", e, Error::Transaction)),
+            ProviderType::Ws(p) => p
+                .send_raw_transaction(raw)
+                .await
+                .map(|pending| pending.tx_hash())
+                .map_err(|e| rpc_error("Failed to send transaction", e, Error::Transaction)),
+        }
+    }
+
+    async fn try_request(
+        &self,
+        method: &str,
+        params: serde_json::Value,
+    ) -> Result<serde_json::Value, ethers::providers::ProviderError> {
+        match self {
+            ProviderType::Http(p) => p.request(method, params).await,
+            ProviderType::Ws(p) => p.request(method, params).await,
+        }
+    }
+
+    async fn request(
+        &self,
+        method: &str,
+        params: serde_json::Value,
+    ) -> Result<serde_json::Value, Error> {
+        match self {
+            ProviderType::Http(p) => p
+                .request(method, params)
+                .await
+                .map_err(|e| rpc_error(&format!("{} failed", method), e, Error::Connection)),
+            ProviderType::Ws(p) => p
+                .request(method, params)
+                .await
+                .map_err(|e| rpc_error(&format!("{} failed", method), e, Error::Connection)),
+        }
+    }
+
+    async fn get_code(
+        &self,
+        address: EthAddress,
+        block: Option<BlockId>,
+    ) -> Result<ethers::types::Bytes, Error> {
+        match self {
+            ProviderType::Http(p) => p
+                .get_code(address, block)
+                .await
+                .map_err(|e| rpc_error("Failed to get code", e, Error::Connection)),
+            ProviderType::Ws(p) => p
+                .get_code(address, block)
+                .await
+                .map_err(|e| rpc_error("Failed to get code", e, Error::Connection)),
+        }
+    }
+
+    async fn call(
+        &self,
+        tx: &ethers::types::transaction::eip2718::TypedTransaction,
+        block: Option<BlockId>,
+    ) -> Result<ethers::types::Bytes, Error> {
+        match self {
+            ProviderType::Http(p) => p
+                .call(tx, block)
+                .await
+                .map_err(|e| rpc_error("Call reverted", e, Error::Transaction)),
+            ProviderType::Ws(p) => p
+                .call(tx, block)
+                .await
+                .map_err(|e| rpc_error("Call reverted", e, Error::Transaction)),
+        }
+    }
+
+    async fn estimate_gas(
+        &self,
+        tx: &ethers::types::transaction::eip2718::TypedTransaction,
+    ) -> Result<U256, Error> {
+        match self {
+            ProviderType::Http(p) => p
+                .estimate_gas(tx, None)
+                .await
+                .map_err(|e| rpc_error("Gas estimation failed", e, Error::Transaction)),
+            ProviderType::Ws(p) => p
+                .estimate_gas(tx, None)
+                .await
+                .map_err(|e| rpc_error("Gas estimation failed", e, Error::Transaction)),
+        }
+    }
+
+    async fn get_logs(&self, filter: &ethers::types::Filter) -> Result<Vec<Log>, Error> {
+        match self {
+            ProviderType::Http(p) => p
+                .get_logs(filter)
+                .await
+                .map_err(|e| rpc_error("Failed to get logs", e, Error::Connection)),
+            ProviderType::Ws(p) => p
+                .get_logs(filter)
+                .await
+                .map_err(|e| rpc_error("Failed to get logs", e, Error::Connection)),
+        }
+    }
+
+    async fn get_block(&self, id: BlockId) -> Result<Option<Block<H256>>, Error> {
+        match self {
+            ProviderType::Http(p) => p
+                .get_block(id)
+                .await
+                .map_err(|e| rpc_error("Failed to get block", e, Error::Connection)),
+            ProviderType::Ws(p) => p
+                .get_block(id)
+                .await
+                .map_err(|e| rpc_error("Failed to get block", e, Error::Connection)),
+        }
+    }
+
+    async fn get_block_with_txs(
+        &self,
+        id: BlockId,
+    ) -> Result<Option<Block<ethers::types::Transaction>>, Error> {
+        match self {
+            ProviderType::Http(p) => p
+                .get_block_with_txs(id)
+                .await
+                .map_err(|e| rpc_error("Failed to get block", e, Error::Connection)),
+            ProviderType::Ws(p) => p
+                .get_block_with_txs(id)
+                .await
+                .map_err(|e| rpc_error("Failed to get block", e, Error::Connection)),
         }
     }
 
@@ -149,20 +320,124 @@ impl ProviderType {
             ProviderType::Http(p) => p
                 .get_chainid()
                 .await
-                .map_err(|e| Error::Connection(format!("Failed to get chain ID: {}", e))),
+                .map_err(|e| rpc_error("Failed to get chain ID", e, Error::Connection)),
             ProviderType::Ws(p) => p
                 .get_chainid()
                 .await
-                .map_err(|e| Error::Connection(format!("Failed to get chain ID: {}", e))),
+                .map_err(|e| rpc_error("Failed to get chain ID", e, Error::Connection)),
+        }
+    }
+}
+
+/// Message for a failed provider call
+///
+/// Transport errors embed the request URL, which may carry an API key.
+pub(crate) fn provider_error(context: &str, e: impl std::fmt::Display) -> String {
+    redact_urls(&format!("{}: {}", context, e))
+}
+
+/// Error for a failed provider call: [`Error::RateLimited`] if the endpoint
+/// pushed back, otherwise made by `kind`
+pub(crate) fn rpc_error(
+    context: &str,
+    e: ethers::providers::ProviderError,
+    kind: fn(String) -> Error,
+) -> Error {
+    let message = provider_error(context, &e);
+    if backpressure::rate_limit(&e).is_some() {
+        Error::RateLimited(message)
+    } else {
+        kind(message)
+    }
+}
+
+/// Wait for a request slot from `limiter`, if one is configured
+pub(crate) async fn throttle(limiter: Option<&RateLimiter>) -> Result<(), Error> {
+    let Some(limiter) = limiter else {
+        return Ok(());
+    };
+    let wait = limiter
+        .reserve()
+        .map_err(|e| Error::RateLimited(e.to_string()))?;
+    if !wait.is_zero() {
+        tracing::trace!("Rate limited, waiting {:?}", wait);
+        runtime::sleep(wait).await;
+    }
+    Ok(())
+}
+
+/// Convert a mined log into a unified event
+fn log_event(log: &Log) -> Option<Event> {
+    Some(Event {
+        chain_type: ChainType::Evm,
+        block_number: log.block_number?.as_u64(),
+        block_hash: format!("{:?}", log.block_hash?),
+        tx_hash: log.transaction_hash.map(|hash| format!("{:?}", hash)),
+        index: log.log_index?.as_u32(),
+        origin: Some(Address::evm(format!("{:?}", log.address))),
+        event: TransactionEvent::EvmLog {
+            address: format!("{:?}", log.address),
+            topics: log.topics.iter().map(|t| format!("{:?}", t)).collect(),
+            data: format!("0x{}", hex::encode(&log.data)),
+            log_index: log.log_index.map(|i| i.as_u64()),
+        },
+    })
+}
+
+/// Convert a block into a unified header, if it has been mined
+fn block_info<TX>(block: &Block<TX>, finality: Finality) -> Option<BlockInfo> {
+    Some(BlockInfo {
+        chain_type: ChainType::Evm,
+        number: block.number?.as_u64(),
+        hash: format!("{:?}", block.hash?),
+        parent_hash: format!("{:?}", block.parent_hash),
+        timestamp: Some(block.timestamp.as_u64()),
+        finality,
+    })
+}
+
+/// Send every item of a subscription `stream` to `sender`
+///
+/// Returns once `sender` is closed, or with an error if the stream ends
+/// first.
+async fn forward<T>(
+    stream: impl Stream<Item = T>,
+    sender: &tokio::sync::mpsc::Sender<T>,
+) -> Result<(), Error> {
+    let mut stream = std::pin::pin!(stream);
+    loop {
+        let closed = std::pin::pin!(sender.closed());
+        let item = match futures::future::select(stream.next(), closed).await {
+            futures::future::Either::Left((Some(item), _)) => item,
+            futures::future::Either::Left((None, _)) => {
+                return Err(Error::Connection("Subscription closed".to_string()))
+            }
+            futures::future::Either::Right(_) => return Ok(()),
+        };
+        if sender.send(item).await.is_err() {
+            return Ok(());
         }
     }
 }
 
+/// Most blocks fetched in a single `eth_getLogs` request while watching or
+/// paginating logs
+pub const MAX_LOG_RANGE: u64 = 1_000;
+
+/// Most skipped blocks fetched individually when a block watcher falls behind
+const MAX_BLOCK_CATCH_UP: u64 = 64;
+
 /// EVM blockchain adapter
+#[derive(Clone)]
 pub struct EvmAdapter {
     endpoint: String,
     provider: ProviderType,
     connected: bool,
+    limiter: Option<Arc<RateLimiter>>,
+    rpc_retry: transaction::RetryConfig,
+    rpc_metrics: Option<Arc<metrics::RpcMetrics>>,
+    gas_metrics: Option<Arc<metrics::GasMetrics>>,
+    nonces: Arc<nonce::NonceManager>,
 }
 
 impl EvmAdapter {
@@ -170,6 +445,57 @@ impl EvmAdapter {
     pub fn endpoint(&self) -> &str {
         &self.endpoint
     }
+
+    /// Limit requests to this adapter's endpoint
+    ///
+    /// The limiter is shared with every other adapter using the same
+    /// endpoint, and is passed on to executors and pre-flight analyzers
+    /// created afterwards.
+    pub fn with_rate_limit(mut self, config: RateLimitConfig) -> Self {
+        self.limiter = Some(RateLimiter::for_endpoint(&self.endpoint, config));
+        self
+    }
+
+    /// The rate limiter applied to requests, if any
+    pub fn rate_limiter(&self) -> Option<&Arc<RateLimiter>> {
+        self.limiter.as_ref()
+    }
+
+    /// Set how [`raw_request`](Self::raw_request) retries calls that fail
+    /// to reach the node
+    pub fn with_rpc_retry(mut self, config: transaction::RetryConfig) -> Self {
+        self.rpc_retry = config;
+        self
+    }
+
+    /// Record [`raw_request`](Self::raw_request) calls in `metrics`, e.g.
+    /// the `rpc` metrics of a [`MetricsCollector`](metrics::MetricsCollector)
+    pub fn with_rpc_metrics(mut self, metrics: Arc<metrics::RpcMetrics>) -> Self {
+        self.rpc_metrics = Some(metrics);
+        self
+    }
+
+    /// Record every fee estimate of this adapter's [`fee_oracle`](Self::fee_oracle)
+    /// and executors in `metrics`, e.g. the `gas` metrics of a
+    /// [`MetricsCollector`](metrics::MetricsCollector)
+    pub fn with_gas_metrics(mut self, metrics: Arc<metrics::GasMetrics>) -> Self {
+        self.gas_metrics = Some(metrics);
+        self
+    }
+
+    /// Open a fresh connection to this adapter's endpoint, keeping its rate
+    /// limiter, retry policy, metrics and nonce reservations
+    pub async fn reconnect(&self) -> Result<Self, Error> {
+        let connected = Self::connect(&self.endpoint).await?;
+        Ok(Self {
+            provider: connected.provider,
+            ..self.clone()
+        })
+    }
+
+    async fn throttle(&self) -> Result<(), Error> {
+        throttle(self.limiter.as_deref()).await
+    }
 }
 
 impl EvmAdapter {
@@ -178,16 +504,37 @@ impl EvmAdapter {
         &self.provider
     }
 
+    /// Create a security pre-flight analyzer with this adapter's provider
+    pub fn security_preflight(&self) -> preflight::SecurityPreflight {
+        preflight::SecurityPreflight::new(self.provider.clone())
+            .with_rate_limiter(self.limiter.clone())
+    }
+
+    /// Create an EIP-1559 fee oracle with this adapter's provider
+    pub fn fee_oracle(&self) -> fees::FeeOracle {
+        fees::FeeOracle::new(self.provider.clone())
+            .with_rate_limiter(self.limiter.clone())
+            .with_gas_metrics(self.gas_metrics.clone())
+    }
+
     /// Create a transaction executor with this adapter's provider
+    ///
+    /// Executors created from the same adapter share a
+    /// [`NonceManager`](nonce::NonceManager), so they can send from one
+    /// account concurrently, and take their default priority fee from the adapter's
+    /// [`fee_oracle`](Self::fee_oracle).
     pub fn transaction_executor(&self) -> transaction::TransactionExecutor {
         transaction::TransactionExecutor::new(self.provider.clone())
+            .with_rate_limiter(self.limiter.clone())
+            .with_nonce_manager(self.nonces.clone())
+            .with_fee_oracle(self.fee_oracle())
     }
 }
 
 impl EvmAdapter {
     /// Connect to an EVM node
     pub async fn connect(endpoint: &str) -> Result<Self, Error> {
-        tracing::info!("Connecting to EVM endpoint: {}", endpoint);
+        tracing::info!("Connecting to EVM endpoint: {}", redact_url(endpoint));
 
         // Determine connection type based on URL scheme
         let provider = if endpoint.starts_with("ws://") || endpoint.starts_with("wss://") {
@@ -195,13 +542,13 @@ impl EvmAdapter {
             tracing::debug!("Using WebSocket connection");
             let ws = Ws::connect(endpoint)
                 .await
-                .map_err(|e| Error::Connection(format!("WebSocket connection failed: {}", e)))?;
+                .map_err(|e| Error::Connection(provider_error("WebSocket connection failed", e)))?;
             ProviderType::Ws(Arc::new(Provider::new(ws)))
         } else {
             // HTTP connection for basic queries
             tracing::debug!("Using HTTP connection");
             let parsed_url = url::Url::parse(endpoint)
-                .map_err(|e| Error::Connection(format!("Invalid URL: {}", e)))?;
+                .map_err(|e| Error::Connection(provider_error("Invalid URL", e)))?;
             let http = Http::new(parsed_url);
             ProviderType::Http(Arc::new(Provider::new(http)))
         };
@@ -214,6 +561,11 @@ impl EvmAdapter {
             endpoint: endpoint.to_string(),
             provider,
             connected: true,
+            limiter: None,
+            rpc_retry: transaction::RetryConfig::default(),
+            rpc_metrics: None,
+            gas_metrics: None,
+            nonces: Arc::new(nonce::NonceManager::new()),
         })
     }
 
@@ -236,9 +588,11 @@ impl EvmAdapter {
             .map_err(|e| Error::Transaction(format!("Invalid hash format: {}", e)))?;
 
         // Query transaction receipt
+        self.throttle().await?;
         match self.provider.get_transaction_receipt(hash).await? {
             Some(receipt) => {
                 // Get current block number for confirmations
+                self.throttle().await?;
                 let current_block = self.provider.get_block_number().await?;
 
                 let confirmations = if let Some(block_number) = receipt.block_number {
@@ -261,6 +615,7 @@ impl EvmAdapter {
             }
             None => {
                 // Transaction not found in a block - check if it's in mempool
+                self.throttle().await?;
                 match self.provider.get_transaction(hash).await? {
                     Some(_) => Ok(TransactionStatus::Pending),
                     None => Ok(TransactionStatus::Unknown),
@@ -269,8 +624,500 @@ impl EvmAdapter {
         }
     }
 
+    /// Get transaction status under `policy`
+    ///
+    /// A transaction included in a block is reported as `Pending` until it
+    /// satisfies the policy.
+    pub async fn confirmation_status(
+        &self,
+        tx_hash: &str,
+        policy: &ConfirmationPolicy,
+    ) -> Result<TransactionStatus, Error> {
+        let status = self.get_transaction_status(tx_hash).await?;
+        let TransactionStatus::Confirmed {
+            block_number,
+            confirmations,
+        } = status
+        else {
+            return Ok(status);
+        };
+
+        let met = match policy {
+            ConfirmationPolicy::Blocks(_) => policy.is_met(confirmations, false, None),
+            ConfirmationPolicy::Finalized => {
+                let finalized = self.finalized_block_number().await?;
+                policy.is_met(confirmations, finalized >= block_number, None)
+            }
+            ConfirmationPolicy::Elapsed(_) => {
+                let block = self.get_block_info(block_number, Finality::Latest).await?;
+                let age = block.and_then(|block| block.timestamp).map(|timestamp| {
+                    let now = std::time::SystemTime::now()
+                        .duration_since(std::time::UNIX_EPOCH)
+                        .map(|d| d.as_secs())
+                        .unwrap_or_default();
+                    Duration::from_secs(now.saturating_sub(timestamp))
+                });
+                policy.is_met(confirmations, false, age)
+            }
+        };
+        Ok(if met {
+            status
+        } else {
+            TransactionStatus::Pending
+        })
+    }
+
+    /// Send JSON-RPC method `method` with `params` and decode its result
+    ///
+    /// An escape hatch for methods the adapter does not wrap, such as
+    /// `eth_getProof` or provider extensions like `ots_*` and `alchemy_*`.
+    /// The call goes through the adapter's rate limit and is recorded in its
+    /// RPC metrics, if set. Calls that fail to reach the node or are rate
+    /// limited are retried per [`with_rpc_retry`](Self::with_rpc_retry),
+    /// waiting as long as a rate-limited endpoint asks; other errors returned
+    /// by the node are not.
+    ///
+    /// ```rust,no_run
+    /// # use apex_sdk_evm::EvmAdapter;
+    /// # async fn example(adapter: EvmAdapter) -> Result<(), apex_sdk_evm::Error> {
+    /// let proof: serde_json::Value = adapter
+    ///     .raw_request(
+    ///         "eth_getProof",
+    ///         serde_json::json!(["0x7F0d15C7FAae65896648C8273B6d7E43f58Fa842", [], "latest"]),
+    ///     )
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn raw_request<T: serde::de::DeserializeOwned>(
+        &self,
+        method: &str,
+        params: serde_json::Value,
+    ) -> Result<T, Error> {
+        use ethers::providers::RpcError;
+
+        let retry = &self.rpc_retry;
+        let mut attempts = 0;
+        let mut backoff = Duration::from_millis(retry.initial_backoff_ms);
+
+        loop {
+            self.throttle().await?;
+            let started = runtime::Instant::now();
+            let result = self.provider.try_request(method, params.clone()).await;
+            let latency_ms = started.elapsed().as_millis() as u64;
+            if let Some(metrics) = &self.rpc_metrics {
+                match &result {
+                    Ok(_) => metrics.record_success(latency_ms),
+                    Err(_) => metrics.record_failure(latency_ms),
+                }
+            }
+
+            let e = match result {
+                Ok(value) => {
+                    return serde_json::from_value(value).map_err(|e| {
+                        Error::Other(format!("Unexpected response from {}: {}", method, e))
+                    })
+                }
+                Err(e) => e,
+            };
+            let limit = backpressure::rate_limit(&e);
+            if let (Some(_), Some(metrics)) = (limit, &self.rpc_metrics) {
+                metrics.record_rate_limit();
+            }
+            if limit.is_none() {
+                if let Some(response) = e.as_error_response() {
+                    return Err(Error::Other(format!(
+                        "{} rejected: {}",
+                        method, response.message
+                    )));
+                }
+            }
+            if attempts >= retry.max_retries {
+                return Err(rpc_error(
+                    &format!("{} failed", method),
+                    e,
+                    Error::Connection,
+                ));
+            }
+
+            attempts += 1;
+            if let Some(metrics) = &self.rpc_metrics {
+                metrics.record_retry();
+            }
+            // Honor the endpoint's wait hint over our own backoff
+            let wait = limit.and_then(|l| l.retry_after).unwrap_or(backoff);
+            tracing::warn!(
+                "{} (attempt {}/{}), retrying in {:?}",
+                provider_error(&format!("{} failed", method), e),
+                attempts,
+                retry.max_retries,
+                wait
+            );
+            runtime::sleep(wait).await;
+            backoff = Duration::from_millis(
+                ((backoff.as_millis() as f64 * retry.backoff_multiplier) as u64)
+                    .min(retry.max_backoff_ms),
+            );
+        }
+    }
+
+    /// Get the chain ID reported by the connected node
+    pub async fn chain_id(&self) -> Result<u64, Error> {
+        self.throttle().await?;
+        self.provider.get_chain_id().await.map(|id| id.as_u64())
+    }
+
+    /// Check that the endpoint serves `chain`, by EIP-155 chain ID
+    ///
+    /// Passes for chains without a known chain ID.
+    pub async fn verify_chain(&self, chain: &Chain) -> Result<(), Error> {
+        chain
+            .verify_evm_chain_id(self.chain_id().await?)
+            .map_err(Error::Connection)
+    }
+
+    /// Get the suggested `(max_fee_per_gas, max_priority_fee_per_gas)` in wei
+    pub async fn suggested_fees(&self) -> Result<(U256, U256), Error> {
+        let fee = |value: serde_json::Value| {
+            serde_json::from_value::<U256>(value)
+                .map_err(|e| Error::Other(format!("Invalid fee response: {}", e)))
+        };
+
+        self.throttle().await?;
+        let gas_price = fee(self
+            .provider
+            .request("eth_gasPrice", serde_json::json!([]))
+            .await?)?;
+        self.throttle().await?;
+        let priority_fee = fee(self
+            .provider
+            .request("eth_maxPriorityFeePerGas", serde_json::json!([]))
+            .await?)?;
+        Ok((gas_price, priority_fee.min(gas_price)))
+    }
+
+    /// Estimate the gas `tx` uses, as reported by the node
+    ///
+    /// No safety margin is added; executors multiply the estimate by
+    /// [`GasConfig::gas_limit_multiplier`](transaction::GasConfig::gas_limit_multiplier).
+    /// Fails if the transaction would revert.
+    pub async fn estimate_gas(
+        &self,
+        tx: impl Into<ethers::types::transaction::eip2718::TypedTransaction>,
+    ) -> Result<U256, Error> {
+        self.throttle().await?;
+        self.provider.estimate_gas(&tx.into()).await
+    }
+
+    /// Get the number of the latest block
+    pub async fn block_number(&self) -> Result<u64, Error> {
+        self.throttle().await?;
+        self.provider.get_block_number().await.map(|n| n.as_u64())
+    }
+
+    /// Get the number of the latest finalized block
+    pub async fn finalized_block_number(&self) -> Result<u64, Error> {
+        self.get_block_info(BlockNumber::Finalized, Finality::Finalized)
+            .await?
+            .map(|block| block.number)
+            .ok_or_else(|| Error::Connection("Node reported no finalized block".to_string()))
+    }
+
+    /// Get all logs emitted in blocks `from_block..=to_block`
+    pub async fn get_logs(&self, from_block: u64, to_block: u64) -> Result<Vec<Event>, Error> {
+        let filter = ethers::types::Filter::new()
+            .from_block(from_block)
+            .to_block(to_block);
+
+        self.throttle().await?;
+        let logs = self.provider.get_logs(&filter).await?;
+        Ok(logs.iter().filter_map(log_event).collect())
+    }
+
+    /// Get the logs emitted by `contract` in blocks `from_block..=to_block`
+    pub async fn get_contract_logs(
+        &self,
+        contract: &str,
+        from_block: u64,
+        to_block: u64,
+    ) -> Result<Vec<Event>, Error> {
+        let contract: EthAddress = contract
+            .parse()
+            .map_err(|e| Error::InvalidAddress(format!("Invalid contract address: {}", e)))?;
+        let filter = ethers::types::Filter::new()
+            .address(contract)
+            .from_block(from_block)
+            .to_block(to_block);
+
+        self.throttle().await?;
+        let logs = self.provider.get_logs(&filter).await?;
+        Ok(logs.iter().filter_map(log_event).collect())
+    }
+
+    /// Get the logs matching `filter`, paginated over its block range
+    ///
+    /// The range defaults to the latest block at either end. The filter's
+    /// top-level EVM addresses and topics are sent to `eth_getLogs`; every
+    /// other condition is checked on the returned events. Blocks are queried
+    /// [`MAX_LOG_RANGE`] at a time, halving the page whenever the provider
+    /// rejects it as too large.
+    pub async fn get_events(&self, filter: &EventFilter) -> Result<Vec<Event>, Error> {
+        let Some(base) = log_filter(filter)? else {
+            return Ok(Vec::new());
+        };
+        let to_block = match filter.to_block {
+            Some(block) => block,
+            None => self.block_number().await?,
+        };
+        let mut next = filter.from_block.unwrap_or(to_block);
+        let mut page = MAX_LOG_RANGE;
+
+        let mut events = Vec::new();
+        while next <= to_block {
+            let to = to_block.min(next.saturating_add(page - 1));
+            let range = base.clone().from_block(next).to_block(to);
+            self.throttle().await?;
+            match self.provider.get_logs(&range).await {
+                Ok(logs) => {
+                    events.extend(
+                        logs.iter()
+                            .filter_map(log_event)
+                            .filter(|event| filter.matches(event)),
+                    );
+                    next = to + 1;
+                }
+                Err(e) if page > 1 && is_log_range_error(&e) => {
+                    page /= 2;
+                    tracing::debug!(
+                        "Log range {}..={} too large, retrying {} blocks at a time",
+                        next,
+                        to,
+                        page
+                    );
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(events)
+    }
+
+    /// Whether the adapter is connected over WebSocket and can subscribe
+    pub fn is_websocket(&self) -> bool {
+        matches!(self.provider, ProviderType::Ws(_))
+    }
+
+    fn pubsub(&self) -> Result<&Provider<Ws>, Error> {
+        match &self.provider {
+            ProviderType::Ws(provider) => Ok(provider),
+            ProviderType::Http(_) => Err(Error::Connection(
+                "Subscriptions require a WebSocket endpoint".to_string(),
+            )),
+        }
+    }
+
+    /// Stream logs matching `filter` as they are mined
+    ///
+    /// Requires a WebSocket endpoint. EVM addresses and topics are pushed
+    /// down to the node; the rest of the filter is applied locally. The
+    /// stream ends when the connection closes.
+    pub async fn subscribe_logs(
+        &self,
+        filter: &EventFilter,
+    ) -> Result<impl Stream<Item = Event> + '_, Error> {
+        let provider = self.pubsub()?;
+        let filter = filter.clone();
+        let log_filter = log_filter(&filter)?.unwrap_or_default();
+        self.throttle().await?;
+        let stream = provider
+            .subscribe_logs(&log_filter)
+            .await
+            .map_err(|e| rpc_error("Failed to subscribe to logs", e, Error::Connection))?;
+        Ok(stream.filter_map(move |log| {
+            let event = log_event(&log).filter(|event| filter.matches(event));
+            std::future::ready(event)
+        }))
+    }
+
+    /// Stream the headers of new blocks as the node sees them
+    ///
+    /// Requires a WebSocket endpoint. Blocks are reported at
+    /// [`Finality::Latest`]; the stream ends when the connection closes.
+    pub async fn subscribe_new_heads(&self) -> Result<impl Stream<Item = BlockInfo> + '_, Error> {
+        let provider = self.pubsub()?;
+        self.throttle().await?;
+        let stream = provider
+            .subscribe_blocks()
+            .await
+            .map_err(|e| rpc_error("Failed to subscribe to new heads", e, Error::Connection))?;
+        Ok(stream.filter_map(|block| std::future::ready(block_info(&block, Finality::Latest))))
+    }
+
+    /// Stream the hashes of transactions entering the node's mempool
+    ///
+    /// Requires a WebSocket endpoint; the stream ends when the connection
+    /// closes.
+    pub async fn subscribe_pending_txs(&self) -> Result<impl Stream<Item = H256> + '_, Error> {
+        let provider = self.pubsub()?;
+        self.throttle().await?;
+        provider.subscribe_pending_txs().await.map_err(|e| {
+            rpc_error(
+                "Failed to subscribe to pending transactions",
+                e,
+                Error::Connection,
+            )
+        })
+    }
+
+    /// Follow new blocks and send their logs to `sender`
+    ///
+    /// Over WebSocket, logs are pushed by a [`subscribe_logs`](Self::subscribe_logs)
+    /// subscription and an error is returned if it ends. Otherwise, or if
+    /// subscribing fails, polls for new blocks every `poll_interval`; failed
+    /// polls are logged and retried on the next tick. Returns once `sender`
+    /// is closed.
+    pub async fn watch_logs(
+        &self,
+        poll_interval: Duration,
+        sender: tokio::sync::mpsc::Sender<Event>,
+    ) -> Result<(), Error> {
+        if self.is_websocket() {
+            match self.subscribe_logs(&EventFilter::default()).await {
+                Ok(stream) => return forward(stream, &sender).await,
+                Err(e) => tracing::warn!("Failed to subscribe to logs, polling instead: {}", e),
+            }
+        }
+
+        let mut next = self.block_number().await? + 1;
+
+        while !sender.is_closed() {
+            runtime::sleep(poll_interval).await;
+
+            let head = match self.block_number().await {
+                Ok(head) => head,
+                Err(e) => {
+                    tracing::warn!("Failed to poll for new blocks: {}", e);
+                    continue;
+                }
+            };
+            while next <= head {
+                let to = head.min(next + MAX_LOG_RANGE - 1);
+                let events = match self.get_logs(next, to).await {
+                    Ok(events) => events,
+                    Err(e) => {
+                        tracing::warn!("Failed to get logs for blocks {}..={}: {}", next, to, e);
+                        break;
+                    }
+                };
+                for event in events {
+                    if sender.send(event).await.is_err() {
+                        return Ok(());
+                    }
+                }
+                next = to + 1;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Get the header of a block by number or tag
+    pub async fn get_block_info(
+        &self,
+        id: impl Into<BlockId>,
+        finality: Finality,
+    ) -> Result<Option<BlockInfo>, Error> {
+        self.throttle().await?;
+        let block = self.provider.get_block(id.into()).await?;
+        Ok(block.and_then(|block| block_info(&block, finality)))
+    }
+
+    /// Get a block by number or tag with its full transactions
+    pub async fn get_block_with_transactions(
+        &self,
+        id: impl Into<BlockId>,
+    ) -> Result<Option<Block<ethers::types::Transaction>>, Error> {
+        self.throttle().await?;
+        self.provider.get_block_with_txs(id.into()).await
+    }
+
+    /// Follow new blocks at the given finality and send their headers to `sender`
+    ///
+    /// Over WebSocket, latest blocks are pushed by a
+    /// [`subscribe_new_heads`](Self::subscribe_new_heads) subscription and an
+    /// error is returned if it ends. Otherwise polls the `latest` or
+    /// `finalized` block every `poll_interval`; blocks skipped between polls
+    /// are fetched individually, up to a limit. Returns once `sender` is
+    /// closed. Failed polls are logged and retried.
+    pub async fn watch_blocks(
+        &self,
+        finality: Finality,
+        poll_interval: Duration,
+        sender: tokio::sync::mpsc::Sender<BlockInfo>,
+    ) -> Result<(), Error> {
+        if finality == Finality::Latest && self.is_websocket() {
+            match self.subscribe_new_heads().await {
+                Ok(stream) => return forward(stream, &sender).await,
+                Err(e) => {
+                    tracing::warn!("Failed to subscribe to new heads, polling instead: {}", e)
+                }
+            }
+        }
+
+        let tag = match finality {
+            Finality::Latest => BlockNumber::Latest,
+            Finality::Finalized => BlockNumber::Finalized,
+        };
+        let mut next: Option<u64> = None;
+
+        while !sender.is_closed() {
+            match self.get_block_info(tag, finality).await {
+                Ok(Some(head)) if next.is_none_or(|next| head.number >= next) => {
+                    let from = next
+                        .unwrap_or(head.number)
+                        .max(head.number.saturating_sub(MAX_BLOCK_CATCH_UP));
+                    for number in from..head.number {
+                        match self.get_block_info(number, finality).await {
+                            Ok(Some(block)) => {
+                                if sender.send(block).await.is_err() {
+                                    return Ok(());
+                                }
+                            }
+                            Ok(None) => {}
+                            Err(e) => tracing::warn!("Failed to get block {}: {}", number, e),
+                        }
+                    }
+                    next = Some(head.number + 1);
+                    if sender.send(head).await.is_err() {
+                        return Ok(());
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => tracing::warn!("Failed to poll for new blocks: {}", e),
+            }
+            runtime::sleep(poll_interval).await;
+        }
+
+        Ok(())
+    }
+
     /// Get balance of an address in wei
     pub async fn get_balance(&self, address: &str) -> Result<U256, Error> {
+        self.get_balance_in(address, None).await
+    }
+
+    /// Get balance of an address in wei as of a past block, by number, tag
+    /// or hash
+    ///
+    /// Blocks older than the node's pruning window need an archive node.
+    pub async fn get_balance_at(
+        &self,
+        address: &str,
+        block: impl Into<BlockId>,
+    ) -> Result<U256, Error> {
+        self.get_balance_in(address, Some(block.into())).await
+    }
+
+    async fn get_balance_in(&self, address: &str, block: Option<BlockId>) -> Result<U256, Error> {
         if !self.connected {
             return Err(Error::Connection("Not connected".to_string()));
         }
@@ -282,8 +1129,179 @@ impl EvmAdapter {
             .parse()
             .map_err(|e| Error::InvalidAddress(format!("Invalid address format: {}", e)))?;
 
-        // Query balance at latest block
-        self.provider.get_balance(addr, None).await
+        self.throttle().await?;
+        self.provider.get_balance(addr, block).await
+    }
+
+    /// Get the deployed bytecode at an address, empty for accounts without
+    /// code
+    pub async fn get_code(&self, address: &str) -> Result<Vec<u8>, Error> {
+        self.get_code_in(address, None).await
+    }
+
+    /// Get the bytecode at an address as of a past block, by number, tag or
+    /// hash
+    ///
+    /// Blocks older than the node's pruning window need an archive node.
+    pub async fn get_code_at(
+        &self,
+        address: &str,
+        block: impl Into<BlockId>,
+    ) -> Result<Vec<u8>, Error> {
+        self.get_code_in(address, Some(block.into())).await
+    }
+
+    async fn get_code_in(&self, address: &str, block: Option<BlockId>) -> Result<Vec<u8>, Error> {
+        if !self.connected {
+            return Err(Error::Connection("Not connected".to_string()));
+        }
+
+        let addr: EthAddress = address
+            .parse()
+            .map_err(|e| Error::InvalidAddress(format!("Invalid address format: {}", e)))?;
+
+        self.throttle().await?;
+        Ok(self.provider.get_code(addr, block).await?.to_vec())
+    }
+
+    /// Execute a read-only call of `data` against the contract at `to`
+    pub async fn call_contract(&self, to: &str, data: Vec<u8>) -> Result<Vec<u8>, Error> {
+        self.call_contract_in(to, data, None).await
+    }
+
+    /// Execute a read-only call of `data` against the contract at `to`, in
+    /// the state after block `number`
+    ///
+    /// Blocks older than the node's pruning window need an archive node.
+    pub async fn call_contract_at(
+        &self,
+        to: &str,
+        data: Vec<u8>,
+        number: u64,
+    ) -> Result<Vec<u8>, Error> {
+        self.call_at(to, data, number).await
+    }
+
+    /// Execute a read-only call of `data` against the contract at `to`, in
+    /// the state after a past block, by number, tag or hash
+    ///
+    /// Blocks older than the node's pruning window need an archive node.
+    pub async fn call_at(
+        &self,
+        to: &str,
+        data: Vec<u8>,
+        block: impl Into<BlockId>,
+    ) -> Result<Vec<u8>, Error> {
+        self.call_contract_in(to, data, Some(block.into())).await
+    }
+
+    async fn call_contract_in(
+        &self,
+        to: &str,
+        data: Vec<u8>,
+        block: Option<BlockId>,
+    ) -> Result<Vec<u8>, Error> {
+        if !self.connected {
+            return Err(Error::Connection("Not connected".to_string()));
+        }
+
+        let to: EthAddress = to
+            .parse()
+            .map_err(|e| Error::InvalidAddress(format!("Invalid contract address: {}", e)))?;
+        let call = ethers::types::TransactionRequest::new()
+            .to(to)
+            .data(data)
+            .into();
+
+        self.throttle().await?;
+        Ok(self.provider.call(&call, block).await?.to_vec())
+    }
+
+    /// Get the ERC-20 `token` balance of `holder` in the token's smallest unit
+    pub async fn token_balance(&self, token: &str, holder: &str) -> Result<U256, Error> {
+        let holder: EthAddress = holder
+            .parse()
+            .map_err(|e| Error::InvalidAddress(format!("Invalid address format: {}", e)))?;
+
+        // balanceOf(address)
+        let mut data = vec![0x70, 0xa0, 0x82, 0x31];
+        data.extend_from_slice(&[0u8; 12]);
+        data.extend_from_slice(holder.as_bytes());
+
+        let output = self.call_contract(token, data).await?;
+        if output.len() < 32 {
+            return Err(Error::Transaction(format!(
+                "Unexpected balanceOf response from {}",
+                token
+            )));
+        }
+        Ok(U256::from_big_endian(&output[..32]))
+    }
+
+    /// Get the decimals of the ERC-20 `token`
+    pub async fn token_decimals(&self, token: &str) -> Result<u8, Error> {
+        // decimals()
+        let output = self
+            .call_contract(token, vec![0x31, 0x3c, 0xe5, 0x67])
+            .await?;
+        if output.len() < 32 || output[..31].iter().any(|b| *b != 0) {
+            return Err(Error::Transaction(format!(
+                "Unexpected decimals response from {}",
+                token
+            )));
+        }
+        Ok(output[31])
+    }
+
+    /// Fill in `tx` from `from` for offline signing, with this node's chain ID
+    ///
+    /// Sign [`sighash`](offline::UnsignedTransaction::sighash) externally,
+    /// attach the signature with
+    /// [`attach_signature`](offline::UnsignedTransaction::attach_signature)
+    /// and broadcast the result with
+    /// [`send_raw_transaction`](Self::send_raw_transaction).
+    pub async fn build_unsigned(
+        &self,
+        from: &str,
+        tx: impl Into<ethers::types::transaction::eip2718::TypedTransaction>,
+    ) -> Result<offline::UnsignedTransaction, Error> {
+        let from = from
+            .parse::<EthAddress>()
+            .map_err(|e| Error::InvalidAddress(format!("Invalid address format: {}", e)))?;
+        let chain_id = self.chain_id().await?;
+        self.transaction_executor()
+            .build_unsigned(from, tx, chain_id)
+            .await
+    }
+
+    /// Broadcast an already-signed, RLP-encoded transaction and return its hash
+    pub async fn send_raw_transaction(&self, raw: &[u8]) -> Result<String, Error> {
+        if !self.connected {
+            return Err(Error::Connection("Not connected".to_string()));
+        }
+
+        tracing::debug!("Sending raw transaction ({} bytes)", raw.len());
+
+        self.throttle().await?;
+        let hash = self
+            .provider
+            .send_raw_transaction(raw.to_vec().into())
+            .await?;
+        Ok(format!("{:?}", hash))
+    }
+
+    /// Get the native balance of an address as an [`Amount`]
+    ///
+    /// Symbol and decimals are those of the chain with the endpoint's chain
+    /// ID; unknown chains get 18 decimals and no symbol.
+    pub async fn get_balance_amount(&self, address: &str) -> Result<Amount, Error> {
+        let balance = self.get_balance(address).await?;
+        let value = u128::try_from(balance)
+            .map_err(|_| Error::Other(format!("Balance of {} exceeds u128", address)))?;
+        Ok(match Chain::from_evm_chain_id(self.chain_id().await?) {
+            Some(chain) => Amount::native(value, &chain),
+            None => Amount::new(value, 18),
+        })
     }
 
     /// Get balance of an address in a human-readable format (ETH)
@@ -299,18 +1317,45 @@ impl EvmAdapter {
         Ok(format!("{}.{:018}", eth_value, remainder))
     }
 
-    /// Validate an EVM address (0x + 40 hex chars)
+    /// Validate an EVM address (0x + 40 hex chars, with a valid EIP-55
+    /// checksum if mixed case)
     pub fn validate_address(&self, address: &Address) -> bool {
         match address {
-            Address::Evm(addr) => {
-                addr.starts_with("0x")
-                    && addr.len() == 42
-                    && addr[2..].chars().all(|c| c.is_ascii_hexdigit())
-            }
+            Address::Evm(addr) => apex_sdk_types::eip55::is_valid(addr),
             _ => false,
         }
     }
 
+    /// Deploy a contract from `wallet`, waiting until it is mined
+    ///
+    /// `constructor_args` are ABI-encoded and appended to the creation
+    /// `bytecode`; pass `()` for a constructor without arguments. Gas and
+    /// fees are estimated.
+    pub async fn deploy(
+        &self,
+        bytecode: Vec<u8>,
+        constructor_args: impl Tokenize,
+        wallet: &wallet::Wallet,
+    ) -> Result<Deployment, Error> {
+        let mut data = bytecode;
+        data.extend(ethers::abi::encode(&constructor_args.into_tokens()));
+
+        let receipt = self
+            .transaction_executor()
+            .send_raw(wallet, TransactionRequest::new().data(data))
+            .await?;
+        match receipt.contract_address {
+            Some(address) if receipt.status == Some(1.into()) => {
+                tracing::info!("Deployed contract at {:?}", address);
+                Ok(Deployment { address, receipt })
+            }
+            _ => Err(Error::Contract(format!(
+                "Deployment {:?} failed",
+                receipt.transaction_hash
+            ))),
+        }
+    }
+
     /// Get contract instance
     pub fn contract(&self, address: &str) -> Result<ContractInfo<'_>, Error> {
         if !self.connected {
@@ -324,15 +1369,105 @@ impl EvmAdapter {
         Ok(ContractInfo {
             address: address.to_string(),
             adapter: self,
+            abi: None,
         })
     }
 }
 
+/// A deployed contract
+#[derive(Debug, Clone)]
+pub struct Deployment {
+    /// Address of the contract
+    pub address: EthAddress,
+    /// Receipt of the creation transaction
+    pub receipt: TransactionReceipt,
+}
+
+/// The `eth_getLogs` filter for the server-side conditions of `filter`, or
+/// `None` if it can match no EVM log
+fn log_filter(filter: &EventFilter) -> Result<Option<ethers::types::Filter>, Error> {
+    let mut logs = ethers::types::Filter::new();
+
+    if let Some(addresses) = &filter.addresses {
+        let addresses: Vec<EthAddress> = addresses
+            .iter()
+            .filter_map(|address| match address {
+                Address::Evm(address) => Some(address.parse().map_err(|e| {
+                    Error::InvalidAddress(format!("Invalid address {}: {}", address, e))
+                })),
+                _ => None,
+            })
+            .collect::<Result<_, _>>()?;
+        if addresses.is_empty() {
+            return Ok(None);
+        }
+        logs = logs.address(addresses);
+    }
+
+    for (position, alternatives) in filter.topics.iter().flatten().enumerate() {
+        let Some(alternatives) = alternatives else {
+            continue;
+        };
+        if position > 3 {
+            return Ok(None);
+        }
+        let topics: Vec<H256> = alternatives
+            .iter()
+            .map(|topic| {
+                topic
+                    .parse()
+                    .map_err(|e| Error::Other(format!("Invalid topic {}: {}", topic, e)))
+            })
+            .collect::<Result<_, _>>()?;
+        logs.topics[position] = Some(topics.into());
+    }
+
+    Ok(Some(logs))
+}
+
+/// Whether `error` is a provider refusing an `eth_getLogs` range or result
+/// set as too large
+fn is_log_range_error(error: &Error) -> bool {
+    let message = match error {
+        Error::Connection(message) | Error::RateLimited(message) => message.to_ascii_lowercase(),
+        _ => return false,
+    };
+    [
+        "block range",
+        "range too large",
+        "range is too large",
+        "more than",
+        "too many",
+        "response size",
+        "query timeout",
+    ]
+    .iter()
+    .any(|phrase| message.contains(phrase))
+}
+
 /// Contract information and interaction
+///
+/// With an ABI loaded, functions can be called by name: arguments are
+/// encoded from Rust values and outputs decoded into them.
+///
+/// ```rust,no_run
+/// # use apex_sdk_evm::{wallet::Wallet, EvmAdapter, Error};
+/// # use ethers::types::{Address, U256};
+/// # async fn example(adapter: EvmAdapter, wallet: Wallet, abi_json: &str) -> Result<(), Error> {
+/// let holder: Address = "0x742D35Cc6634C0532925A3B844bC9e7595f0bEB7".parse().unwrap();
+/// let token = adapter
+///     .contract("0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48")?
+///     .with_abi_json(abi_json)?;
+///
+/// let balance: U256 = token.call("balanceOf", holder).await?;
+/// let hash = token.send("transfer", (holder, balance / 2), &wallet).await?;
+/// # Ok(())
+/// # }
+/// ```
 pub struct ContractInfo<'a> {
     address: String,
-    #[allow(dead_code)]
     adapter: &'a EvmAdapter,
+    abi: Option<Abi>,
 }
 
 impl ContractInfo<'_> {
@@ -340,14 +1475,103 @@ impl ContractInfo<'_> {
     pub fn address(&self) -> &str {
         &self.address
     }
+
+    /// Use `abi` to encode calls to the contract
+    pub fn with_abi(mut self, abi: Abi) -> Self {
+        self.abi = Some(abi);
+        self
+    }
+
+    /// Use the JSON ABI `json`, as emitted by solc or a block explorer
+    pub fn with_abi_json(self, json: &str) -> Result<Self, Error> {
+        let abi = serde_json::from_str(json)
+            .map_err(|e| Error::Contract(format!("Invalid ABI: {}", e)))?;
+        Ok(self.with_abi(abi))
+    }
+
+    /// The contract's ABI, if loaded
+    pub fn abi(&self) -> Option<&Abi> {
+        self.abi.as_ref()
+    }
+
+    /// Call the view function `method` with `args` and decode its outputs
+    ///
+    /// Several outputs decode into a tuple.
+    pub async fn call<T: Detokenize>(&self, method: &str, args: impl Tokenize) -> Result<T, Error> {
+        let (function, data) = self.encode(method, args)?;
+        let output = self.adapter.call_contract(&self.address, data).await?;
+        let tokens = function
+            .decode_output(&output)
+            .map_err(|e| Error::Contract(format!("Failed to decode {} output: {}", method, e)))?;
+        T::from_tokens(tokens)
+            .map_err(|e| Error::Contract(format!("Unexpected {} output: {}", method, e)))
+    }
+
+    /// Sign and send a transaction calling `method` with `args` from `wallet`
+    pub async fn send(
+        &self,
+        method: &str,
+        args: impl Tokenize,
+        wallet: &wallet::Wallet,
+    ) -> Result<H256, Error> {
+        let (_, data) = self.encode(method, args)?;
+        let to = self
+            .address
+            .parse()
+            .map_err(|_| Error::InvalidAddress(self.address.clone()))?;
+        self.adapter
+            .transaction_executor()
+            .send_transaction(wallet, to, U256::zero(), Some(data))
+            .await
+    }
+
+    /// Find `method` in the ABI, choosing among overloads by argument count,
+    /// and encode a call to it
+    fn encode(&self, method: &str, args: impl Tokenize) -> Result<(&Function, Vec<u8>), Error> {
+        let abi = self.abi.as_ref().ok_or_else(|| {
+            Error::Contract(format!("No ABI loaded for contract {}", self.address))
+        })?;
+        let args = args.into_tokens();
+        let function = abi
+            .functions_by_name(method)
+            .map_err(|_| Error::Contract(format!("Function {} not found in ABI", method)))?
+            .iter()
+            .find(|function| function.inputs.len() == args.len())
+            .ok_or_else(|| {
+                Error::Contract(format!(
+                    "Function {} does not take {} arguments",
+                    method,
+                    args.len()
+                ))
+            })?;
+        let data = function
+            .encode_input(&args)
+            .map_err(|e| Error::Contract(format!("Invalid arguments to {}: {}", method, e)))?;
+        Ok((function, data))
+    }
 }
 
-#[async_trait]
+impl From<Error> for AdapterError {
+    fn from(e: Error) -> Self {
+        match e {
+            Error::Connection(message) => AdapterError::Connection(message),
+            Error::Transaction(message) => AdapterError::Transaction(message),
+            Error::Contract(message) => AdapterError::Contract(message),
+            Error::InvalidAddress(message) => AdapterError::InvalidAddress(message),
+            Error::RateLimited(message) => AdapterError::RateLimited(message),
+            Error::Other(message) => AdapterError::Other(message),
+        }
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
 impl apex_sdk_core::ChainAdapter for EvmAdapter {
-    async fn get_transaction_status(&self, tx_hash: &str) -> Result<TransactionStatus, String> {
-        self.get_transaction_status(tx_hash)
-            .await
-            .map_err(|e| e.to_string())
+    async fn get_transaction_status(
+        &self,
+        tx_hash: &str,
+    ) -> Result<TransactionStatus, AdapterError> {
+        Ok(self.get_transaction_status(tx_hash).await?)
     }
 
     fn validate_address(&self, address: &Address) -> bool {
@@ -357,6 +1581,35 @@ impl apex_sdk_core::ChainAdapter for EvmAdapter {
     fn chain_name(&self) -> &str {
         "EVM"
     }
+
+    async fn get_balance(&self, address: &Address) -> Result<u128, AdapterError> {
+        self.get_balance(address.as_str())
+            .await?
+            .try_into()
+            .map_err(|_| AdapterError::Other(format!("Balance of {} exceeds u128", address)))
+    }
+
+    async fn get_block(&self, block: BlockRef) -> Result<Option<BlockInfo>, AdapterError> {
+        let id = match &block {
+            BlockRef::Number(number) => BlockId::from(*number),
+            BlockRef::Hash(hash) => {
+                BlockId::from(hash.parse::<ethers::types::H256>().map_err(|e| {
+                    AdapterError::Encoding(format!("Invalid block hash {}: {}", hash, e))
+                })?)
+            }
+        };
+        Ok(self.get_block_info(id, Finality::Latest).await?)
+    }
+
+    async fn latest_block(&self) -> Result<BlockInfo, AdapterError> {
+        self.get_block_info(BlockNumber::Latest, Finality::Latest)
+            .await?
+            .ok_or_else(|| AdapterError::NotFound("Latest block".to_string()))
+    }
+
+    async fn submit_transaction(&self, tx: &[u8]) -> Result<String, AdapterError> {
+        Ok(self.send_raw_transaction(tx).await?)
+    }
 }
 
 #[cfg(test)]
@@ -377,7 +1630,7 @@ mod tests {
             .await
             .unwrap();
 
-        let valid_addr = Address::evm("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb7");
+        let valid_addr = Address::evm("0x742D35Cc6634C0532925A3B844bC9e7595f0bEB7");
         assert!(adapter.validate_address(&valid_addr));
 
         let invalid_addr = Address::evm("invalid");
@@ -413,4 +1666,51 @@ mod tests {
         let url = url::Url::parse("not-a-valid-url");
         assert!(url.is_err(), "Expected invalid URL to fail parsing");
     }
+
+    #[test]
+    fn test_log_filter() {
+        let transfer = format!("0x{}", "dd".repeat(32));
+        let filter = EventFilter::new()
+            .address(Address::evm("0xdAC17F958D2ee523a2206206994597C13D831ec7"))
+            .address(Address::substrate(
+                "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY",
+            ))
+            .topic(0, transfer.clone())
+            .data_word_at_least(0, 1);
+        let logs = log_filter(&filter).unwrap().unwrap();
+        assert_eq!(
+            serde_json::to_value(&logs).unwrap(),
+            serde_json::json!({
+                "address": ["0xdac17f958d2ee523a2206206994597c13d831ec7"],
+                "topics": [[transfer]],
+            })
+        );
+
+        // Only Substrate emitters can never match a log
+        let substrate = EventFilter::new().address(Address::substrate(
+            "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY",
+        ));
+        assert!(log_filter(&substrate).unwrap().is_none());
+        assert!(log_filter(&EventFilter::new().topic(1, "0x12")).is_err());
+    }
+
+    #[test]
+    fn test_is_log_range_error() {
+        for message in [
+            "query exceeds max block range 2000",
+            "query returned more than 10000 results",
+            "Log response size exceeded",
+        ] {
+            assert!(is_log_range_error(&Error::Connection(message.to_string())));
+        }
+        assert!(is_log_range_error(&Error::RateLimited(
+            "query returned more than 10000 results".to_string()
+        )));
+        assert!(!is_log_range_error(&Error::Connection(
+            "connection refused".to_string()
+        )));
+        assert!(!is_log_range_error(&Error::Transaction(
+            "block range".to_string()
+        )));
+    }
 }
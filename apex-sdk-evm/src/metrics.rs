@@ -9,9 +9,10 @@
 
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
-use std::time::Instant;
 use tokio::sync::RwLock;
 
+use crate::runtime::Instant;
+
 /// Metrics for RPC calls
 #[derive(Debug, Default)]
 pub struct RpcMetrics {
@@ -25,6 +26,10 @@ pub struct RpcMetrics {
     pub total_latency_ms: AtomicU64,
     /// Number of retries
     pub retries: AtomicU64,
+    /// Number of rate-limit responses (HTTP 429, JSON-RPC -32005)
+    pub rate_limited: AtomicU64,
+    /// Number of times a pool moved off a rate-limited endpoint
+    pub endpoint_rotations: AtomicU64,
 }
 
 impl RpcMetrics {
@@ -53,6 +58,16 @@ impl RpcMetrics {
         self.retries.fetch_add(1, Ordering::Relaxed);
     }
 
+    /// Record a rate-limit response
+    pub fn record_rate_limit(&self) {
+        self.rate_limited.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Record a switch to another endpoint after a rate limit
+    pub fn record_rotation(&self) {
+        self.endpoint_rotations.fetch_add(1, Ordering::Relaxed);
+    }
+
     /// Get success rate as percentage
     pub fn success_rate(&self) -> f64 {
         let total = self.total_calls.load(Ordering::Relaxed);
@@ -292,6 +307,22 @@ impl MetricsCollector {
             self.rpc.success_rate()
         ));
 
+        output.push_str("# HELP apex_evm_rpc_rate_limited_total Rate-limit responses\n");
+        output.push_str("# TYPE apex_evm_rpc_rate_limited_total counter\n");
+        output.push_str(&format!(
+            "apex_evm_rpc_rate_limited_total {}\n",
+            self.rpc.rate_limited.load(Ordering::Relaxed)
+        ));
+
+        output.push_str(
+            "# HELP apex_evm_rpc_endpoint_rotations_total Switches away from rate-limited endpoints\n",
+        );
+        output.push_str("# TYPE apex_evm_rpc_endpoint_rotations_total counter\n");
+        output.push_str(&format!(
+            "apex_evm_rpc_endpoint_rotations_total {}\n",
+            self.rpc.endpoint_rotations.load(Ordering::Relaxed)
+        ));
+
         // Transaction metrics
         output.push_str("# HELP apex_evm_transactions_submitted Total transactions submitted\n");
         output.push_str("# TYPE apex_evm_transactions_submitted counter\n");
@@ -374,6 +405,14 @@ impl MetricsCollector {
         println!("  Success Rate: {:.2}%", self.rpc.success_rate());
         println!("  Avg Latency: {:.2}ms", self.rpc.avg_latency_ms());
         println!("  Retries: {}", self.rpc.retries.load(Ordering::Relaxed));
+        println!(
+            "  Rate Limited: {}",
+            self.rpc.rate_limited.load(Ordering::Relaxed)
+        );
+        println!(
+            "  Endpoint Rotations: {}",
+            self.rpc.endpoint_rotations.load(Ordering::Relaxed)
+        );
 
         println!("\nTransactions:");
         println!(
@@ -457,11 +496,13 @@ mod tests {
         let collector = MetricsCollector::new();
 
         collector.rpc.record_success(100);
+        collector.rpc.record_rate_limit();
         collector.transactions.record_submission();
 
         let output = collector.export_prometheus().await;
 
         assert!(output.contains("apex_evm_rpc_calls_total"));
+        assert!(output.contains("apex_evm_rpc_rate_limited_total 1\n"));
         assert!(output.contains("apex_evm_transactions_submitted"));
         assert!(output.contains("apex_evm_uptime_seconds"));
     }
@@ -0,0 +1,593 @@
+//! ERC-721 and ERC-1155 NFT collections
+//!
+//! [`Erc721`] reads owners and token URIs and transfers tokens of any
+//! ERC-721 collection; collections implementing the Enumerable extension can
+//! also be listed with [`tokens_of_owner`](Erc721::tokens_of_owner). Wrapped
+//! collections minted by a bridge additionally expose
+//! `mint(address,uint256,string)` (restricted to the bridge) and
+//! `burn(uint256)` (callable by the token owner), used by
+//! [`mint`](Erc721::mint) and [`burn`](Erc721::burn).
+//!
+//! [`Erc1155`] reads balances and metadata URIs and transfers tokens of
+//! ERC-1155 multi-token collections.
+//!
+//! Which standards a contract implements can be checked through ERC-165 with
+//! `supports_interface` and the `*_INTERFACE_ID` constants.
+
+use crate::wallet::Wallet;
+use crate::{Error, EvmAdapter};
+use ethers::abi::{self, ParamType, Token};
+use ethers::types::{Address as EthAddress, H256, U256};
+
+/// ERC-165 interface ID of ERC-721
+pub const ERC721_INTERFACE_ID: [u8; 4] = [0x80, 0xac, 0x58, 0xcd];
+
+/// ERC-165 interface ID of the ERC-721 Metadata extension
+pub const ERC721_METADATA_INTERFACE_ID: [u8; 4] = [0x5b, 0x5e, 0x13, 0x9f];
+
+/// ERC-165 interface ID of the ERC-721 Enumerable extension
+pub const ERC721_ENUMERABLE_INTERFACE_ID: [u8; 4] = [0x78, 0x0e, 0x9d, 0x63];
+
+/// ERC-165 interface ID of ERC-1155
+pub const ERC1155_INTERFACE_ID: [u8; 4] = [0xd9, 0xb6, 0x7a, 0x26];
+
+/// An ERC-721 collection
+pub struct Erc721 {
+    adapter: EvmAdapter,
+    address: EthAddress,
+}
+
+impl Erc721 {
+    /// The collection at `address`
+    pub fn new(adapter: EvmAdapter, address: &str) -> Result<Self, Error> {
+        let address = address
+            .parse()
+            .map_err(|e| Error::InvalidAddress(format!("Invalid collection address: {}", e)))?;
+        Ok(Self { adapter, address })
+    }
+
+    /// Address of the collection
+    pub fn address(&self) -> EthAddress {
+        self.address
+    }
+
+    /// Whether the collection implements the ERC-165 interface `interface_id`
+    pub async fn supports_interface(&self, interface_id: [u8; 4]) -> Result<bool, Error> {
+        supports_interface(&self.adapter, self.address, interface_id).await
+    }
+
+    /// Current owner of `token_id`
+    pub async fn owner_of(&self, token_id: U256) -> Result<EthAddress, Error> {
+        match self
+            .call(
+                "ownerOf(uint256)",
+                &[Token::Uint(token_id)],
+                ParamType::Address,
+            )
+            .await?
+        {
+            Token::Address(owner) => Ok(owner),
+            _ => unreachable!("decoded as an address"),
+        }
+    }
+
+    /// Metadata URI of `token_id`
+    pub async fn token_uri(&self, token_id: U256) -> Result<String, Error> {
+        match self
+            .call(
+                "tokenURI(uint256)",
+                &[Token::Uint(token_id)],
+                ParamType::String,
+            )
+            .await?
+        {
+            Token::String(uri) => Ok(uri),
+            _ => unreachable!("decoded as a string"),
+        }
+    }
+
+    /// Number of tokens owned by `owner`
+    pub async fn balance_of(&self, owner: EthAddress) -> Result<U256, Error> {
+        self.call_uint("balanceOf(address)", &[Token::Address(owner)])
+            .await
+    }
+
+    /// Number of tokens in the collection (Enumerable extension)
+    pub async fn total_supply(&self) -> Result<U256, Error> {
+        self.call_uint("totalSupply()", &[]).await
+    }
+
+    /// ID of the `index`th token in the collection (Enumerable extension)
+    pub async fn token_by_index(&self, index: U256) -> Result<U256, Error> {
+        self.call_uint("tokenByIndex(uint256)", &[Token::Uint(index)])
+            .await
+    }
+
+    /// ID of the `index`th token owned by `owner` (Enumerable extension)
+    pub async fn token_of_owner_by_index(
+        &self,
+        owner: EthAddress,
+        index: U256,
+    ) -> Result<U256, Error> {
+        self.call_uint(
+            "tokenOfOwnerByIndex(address,uint256)",
+            &[Token::Address(owner), Token::Uint(index)],
+        )
+        .await
+    }
+
+    /// IDs of all tokens owned by `owner` (Enumerable extension)
+    ///
+    /// Makes one call per token; the list may be inconsistent if tokens
+    /// move between calls.
+    pub async fn tokens_of_owner(&self, owner: EthAddress) -> Result<Vec<U256>, Error> {
+        let balance = self.balance_of(owner).await?;
+        let count = u64::try_from(balance).map_err(|_| {
+            Error::Contract(format!(
+                "Token count {} of {:?} exceeds u64",
+                balance, owner
+            ))
+        })?;
+        let mut tokens = Vec::new();
+        for index in 0..count {
+            tokens.push(self.token_of_owner_by_index(owner, index.into()).await?);
+        }
+        Ok(tokens)
+    }
+
+    /// Whether `operator` may transfer all of `owner`'s tokens
+    pub async fn is_approved_for_all(
+        &self,
+        owner: EthAddress,
+        operator: EthAddress,
+    ) -> Result<bool, Error> {
+        is_approved_for_all(&self.adapter, self.address, owner, operator).await
+    }
+
+    /// Allow or disallow `operator` to transfer all of `wallet`'s tokens
+    pub async fn set_approval_for_all(
+        &self,
+        wallet: &Wallet,
+        operator: EthAddress,
+        approved: bool,
+    ) -> Result<H256, Error> {
+        self.send(
+            wallet,
+            "setApprovalForAll(address,bool)",
+            &[Token::Address(operator), Token::Bool(approved)],
+        )
+        .await
+    }
+
+    /// Transfer `token_id` from `wallet` to `to`
+    pub async fn transfer(
+        &self,
+        wallet: &Wallet,
+        to: EthAddress,
+        token_id: U256,
+    ) -> Result<H256, Error> {
+        self.send(
+            wallet,
+            "transferFrom(address,address,uint256)",
+            &[
+                Token::Address(wallet.eth_address()),
+                Token::Address(to),
+                Token::Uint(token_id),
+            ],
+        )
+        .await
+    }
+
+    /// Transfer `token_id` from `wallet` to `to`, which must accept it if it
+    /// is a contract
+    pub async fn safe_transfer_from(
+        &self,
+        wallet: &Wallet,
+        to: EthAddress,
+        token_id: U256,
+    ) -> Result<H256, Error> {
+        self.send(
+            wallet,
+            "safeTransferFrom(address,address,uint256)",
+            &[
+                Token::Address(wallet.eth_address()),
+                Token::Address(to),
+                Token::Uint(token_id),
+            ],
+        )
+        .await
+    }
+
+    /// Mint `token_id` with metadata `uri` to `to` on a wrapped collection
+    pub async fn mint(
+        &self,
+        wallet: &Wallet,
+        to: EthAddress,
+        token_id: U256,
+        uri: &str,
+    ) -> Result<H256, Error> {
+        self.send(
+            wallet,
+            "mint(address,uint256,string)",
+            &[
+                Token::Address(to),
+                Token::Uint(token_id),
+                Token::String(uri.to_string()),
+            ],
+        )
+        .await
+    }
+
+    /// Burn `token_id`, owned by `wallet`, on a wrapped collection
+    pub async fn burn(&self, wallet: &Wallet, token_id: U256) -> Result<H256, Error> {
+        self.send(wallet, "burn(uint256)", &[Token::Uint(token_id)])
+            .await
+    }
+
+    async fn call(
+        &self,
+        signature: &str,
+        args: &[Token],
+        output: ParamType,
+    ) -> Result<Token, Error> {
+        call(&self.adapter, self.address, signature, args, output).await
+    }
+
+    async fn call_uint(&self, signature: &str, args: &[Token]) -> Result<U256, Error> {
+        match self.call(signature, args, ParamType::Uint(256)).await? {
+            Token::Uint(value) => Ok(value),
+            _ => unreachable!("decoded as a uint"),
+        }
+    }
+
+    async fn send(&self, wallet: &Wallet, signature: &str, args: &[Token]) -> Result<H256, Error> {
+        send(&self.adapter, self.address, wallet, signature, args).await
+    }
+}
+
+/// An ERC-1155 multi-token collection
+pub struct Erc1155 {
+    adapter: EvmAdapter,
+    address: EthAddress,
+}
+
+impl Erc1155 {
+    /// The collection at `address`
+    pub fn new(adapter: EvmAdapter, address: &str) -> Result<Self, Error> {
+        let address = address
+            .parse()
+            .map_err(|e| Error::InvalidAddress(format!("Invalid collection address: {}", e)))?;
+        Ok(Self { adapter, address })
+    }
+
+    /// Address of the collection
+    pub fn address(&self) -> EthAddress {
+        self.address
+    }
+
+    /// Whether the collection implements the ERC-165 interface `interface_id`
+    pub async fn supports_interface(&self, interface_id: [u8; 4]) -> Result<bool, Error> {
+        supports_interface(&self.adapter, self.address, interface_id).await
+    }
+
+    /// Amount of token `id` owned by `owner`
+    pub async fn balance_of(&self, owner: EthAddress, id: U256) -> Result<U256, Error> {
+        match call(
+            &self.adapter,
+            self.address,
+            "balanceOf(address,uint256)",
+            &[Token::Address(owner), Token::Uint(id)],
+            ParamType::Uint(256),
+        )
+        .await?
+        {
+            Token::Uint(balance) => Ok(balance),
+            _ => unreachable!("decoded as a uint"),
+        }
+    }
+
+    /// Amounts of token `ids[i]` owned by `owners[i]`
+    pub async fn balance_of_batch(
+        &self,
+        owners: &[EthAddress],
+        ids: &[U256],
+    ) -> Result<Vec<U256>, Error> {
+        if owners.len() != ids.len() {
+            return Err(Error::Contract(format!(
+                "{} owners but {} token IDs",
+                owners.len(),
+                ids.len()
+            )));
+        }
+        let tokens = call(
+            &self.adapter,
+            self.address,
+            "balanceOfBatch(address[],uint256[])",
+            &[
+                Token::Array(owners.iter().copied().map(Token::Address).collect()),
+                Token::Array(ids.iter().copied().map(Token::Uint).collect()),
+            ],
+            ParamType::Array(Box::new(ParamType::Uint(256))),
+        )
+        .await?;
+        Ok(tokens
+            .into_array()
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(Token::into_uint)
+            .collect())
+    }
+
+    /// Metadata URI of token `id`, with any `{id}` placeholder substituted
+    pub async fn uri(&self, id: U256) -> Result<String, Error> {
+        match call(
+            &self.adapter,
+            self.address,
+            "uri(uint256)",
+            &[Token::Uint(id)],
+            ParamType::String,
+        )
+        .await?
+        {
+            Token::String(uri) => Ok(substitute_id(&uri, id)),
+            _ => unreachable!("decoded as a string"),
+        }
+    }
+
+    /// Whether `operator` may transfer all of `owner`'s tokens
+    pub async fn is_approved_for_all(
+        &self,
+        owner: EthAddress,
+        operator: EthAddress,
+    ) -> Result<bool, Error> {
+        is_approved_for_all(&self.adapter, self.address, owner, operator).await
+    }
+
+    /// Allow or disallow `operator` to transfer all of `wallet`'s tokens
+    pub async fn set_approval_for_all(
+        &self,
+        wallet: &Wallet,
+        operator: EthAddress,
+        approved: bool,
+    ) -> Result<H256, Error> {
+        send(
+            &self.adapter,
+            self.address,
+            wallet,
+            "setApprovalForAll(address,bool)",
+            &[Token::Address(operator), Token::Bool(approved)],
+        )
+        .await
+    }
+
+    /// Transfer `amount` of token `id` from `wallet` to `to`, passing `data`
+    /// to the recipient if it is a contract
+    pub async fn safe_transfer_from(
+        &self,
+        wallet: &Wallet,
+        to: EthAddress,
+        id: U256,
+        amount: U256,
+        data: Vec<u8>,
+    ) -> Result<H256, Error> {
+        send(
+            &self.adapter,
+            self.address,
+            wallet,
+            "safeTransferFrom(address,address,uint256,uint256,bytes)",
+            &[
+                Token::Address(wallet.eth_address()),
+                Token::Address(to),
+                Token::Uint(id),
+                Token::Uint(amount),
+                Token::Bytes(data),
+            ],
+        )
+        .await
+    }
+
+    /// Transfer `amounts[i]` of token `ids[i]` from `wallet` to `to` in one
+    /// transaction
+    pub async fn safe_batch_transfer_from(
+        &self,
+        wallet: &Wallet,
+        to: EthAddress,
+        ids: &[U256],
+        amounts: &[U256],
+        data: Vec<u8>,
+    ) -> Result<H256, Error> {
+        if ids.len() != amounts.len() {
+            return Err(Error::Contract(format!(
+                "{} token IDs but {} amounts",
+                ids.len(),
+                amounts.len()
+            )));
+        }
+        send(
+            &self.adapter,
+            self.address,
+            wallet,
+            "safeBatchTransferFrom(address,address,uint256[],uint256[],bytes)",
+            &[
+                Token::Address(wallet.eth_address()),
+                Token::Address(to),
+                Token::Array(ids.iter().copied().map(Token::Uint).collect()),
+                Token::Array(amounts.iter().copied().map(Token::Uint).collect()),
+                Token::Bytes(data),
+            ],
+        )
+        .await
+    }
+}
+
+async fn call(
+    adapter: &EvmAdapter,
+    address: EthAddress,
+    signature: &str,
+    args: &[Token],
+    output: ParamType,
+) -> Result<Token, Error> {
+    let data = call_data(signature, args);
+    let output_bytes = adapter
+        .call_contract(&format!("{:?}", address), data)
+        .await?;
+    abi::decode(&[output], &output_bytes)
+        .ok()
+        .and_then(|mut tokens| tokens.pop())
+        .ok_or_else(|| {
+            Error::Contract(format!(
+                "Unexpected {} response from {:?}",
+                signature, address
+            ))
+        })
+}
+
+async fn send(
+    adapter: &EvmAdapter,
+    address: EthAddress,
+    wallet: &Wallet,
+    signature: &str,
+    args: &[Token],
+) -> Result<H256, Error> {
+    adapter
+        .transaction_executor()
+        .send_transaction(
+            wallet,
+            address,
+            U256::zero(),
+            Some(call_data(signature, args)),
+        )
+        .await
+}
+
+async fn supports_interface(
+    adapter: &EvmAdapter,
+    address: EthAddress,
+    interface_id: [u8; 4],
+) -> Result<bool, Error> {
+    let token = call(
+        adapter,
+        address,
+        "supportsInterface(bytes4)",
+        &[Token::FixedBytes(interface_id.to_vec())],
+        ParamType::Bool,
+    )
+    .await?;
+    Ok(token.into_bool().unwrap_or(false))
+}
+
+async fn is_approved_for_all(
+    adapter: &EvmAdapter,
+    address: EthAddress,
+    owner: EthAddress,
+    operator: EthAddress,
+) -> Result<bool, Error> {
+    let token = call(
+        adapter,
+        address,
+        "isApprovedForAll(address,address)",
+        &[Token::Address(owner), Token::Address(operator)],
+        ParamType::Bool,
+    )
+    .await?;
+    Ok(token.into_bool().unwrap_or(false))
+}
+
+fn call_data(signature: &str, args: &[Token]) -> Vec<u8> {
+    let mut data = ethers::utils::id(signature).to_vec();
+    data.extend(abi::encode(args));
+    data
+}
+
+/// Replace `{id}` with the token ID as 64 lowercase hex digits, as ERC-1155
+/// metadata URIs require
+fn substitute_id(uri: &str, id: U256) -> String {
+    let mut bytes = [0u8; 32];
+    id.to_big_endian(&mut bytes);
+    uri.replace("{id}", &hex::encode(bytes))
+}
+
+impl EvmAdapter {
+    /// The ERC-721 collection at `address`
+    pub fn erc721(&self, address: &str) -> Result<Erc721, Error> {
+        Erc721::new(self.clone(), address)
+    }
+
+    /// The ERC-1155 collection at `address`
+    pub fn erc1155(&self, address: &str) -> Result<Erc1155, Error> {
+        Erc1155::new(self.clone(), address)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_call_data_selector() {
+        let data = call_data("ownerOf(uint256)", &[Token::Uint(U256::from(7))]);
+        assert_eq!(&data[..4], &[0x63, 0x52, 0x21, 0x1e]);
+        assert_eq!(data.len(), 36);
+        assert_eq!(data[35], 7);
+    }
+
+    #[test]
+    fn test_interface_ids() {
+        let selectors = |signatures: &[&str]| {
+            signatures
+                .iter()
+                .map(|signature| u32::from_be_bytes(ethers::utils::id(signature)))
+                .fold(0, |id, selector| id ^ selector)
+                .to_be_bytes()
+        };
+        assert_eq!(
+            selectors(&[
+                "balanceOf(address)",
+                "ownerOf(uint256)",
+                "safeTransferFrom(address,address,uint256,bytes)",
+                "safeTransferFrom(address,address,uint256)",
+                "transferFrom(address,address,uint256)",
+                "approve(address,uint256)",
+                "setApprovalForAll(address,bool)",
+                "getApproved(uint256)",
+                "isApprovedForAll(address,address)",
+            ]),
+            ERC721_INTERFACE_ID
+        );
+        assert_eq!(
+            selectors(&[
+                "totalSupply()",
+                "tokenOfOwnerByIndex(address,uint256)",
+                "tokenByIndex(uint256)",
+            ]),
+            ERC721_ENUMERABLE_INTERFACE_ID
+        );
+        assert_eq!(
+            selectors(&["name()", "symbol()", "tokenURI(uint256)"]),
+            ERC721_METADATA_INTERFACE_ID
+        );
+        assert_eq!(
+            selectors(&[
+                "safeTransferFrom(address,address,uint256,uint256,bytes)",
+                "safeBatchTransferFrom(address,address,uint256[],uint256[],bytes)",
+                "balanceOf(address,uint256)",
+                "balanceOfBatch(address[],uint256[])",
+                "setApprovalForAll(address,bool)",
+                "isApprovedForAll(address,address)",
+            ]),
+            ERC1155_INTERFACE_ID
+        );
+    }
+
+    #[test]
+    fn test_substitute_id() {
+        assert_eq!(
+            substitute_id("https://token-cdn-domain/{id}.json", U256::from(314592)),
+            "https://token-cdn-domain/000000000000000000000000000000000000000000000000000000000004cce0.json"
+        );
+        assert_eq!(
+            substitute_id("ipfs://Qm/1.json", U256::one()),
+            "ipfs://Qm/1.json"
+        );
+    }
+}
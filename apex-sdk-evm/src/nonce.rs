@@ -0,0 +1,239 @@
+//! Nonce management for concurrent transaction submission
+//!
+//! The node only counts transactions it has seen, so transactions built in
+//! parallel from one account would all get the same nonce from it. A
+//! [`NonceManager`] hands out nonces per sender instead: each one is pending
+//! from [`reserve`](NonceManager::reserve) until the node accepts the
+//! transaction ([`confirm`](NonceManager::confirm)) or the send fails
+//! ([`release`](NonceManager::release)). A released nonce leaves a gap, which
+//! the next reservation fills so later transactions are not stuck behind it.
+//!
+//! Executors created from the same [`EvmAdapter`](crate::EvmAdapter) share a
+//! manager, so they can send from one account concurrently.
+
+use crate::Error;
+use ethers::types::{Address as EthAddress, U256};
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Mutex;
+
+#[derive(Debug, Default)]
+struct AccountNonces {
+    /// Nonce after the highest one handed out
+    next: U256,
+    /// Handed out, not yet accepted by the node or released
+    pending: BTreeSet<U256>,
+    /// Released below `next`, to be handed out again
+    gaps: BTreeSet<U256>,
+}
+
+/// Nonces handed out per sender
+#[derive(Debug, Default)]
+pub struct NonceManager {
+    accounts: Mutex<HashMap<EthAddress, AccountNonces>>,
+}
+
+impl NonceManager {
+    /// Create an empty manager
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reserve the next nonce of `address`, given the node's pending
+    /// transaction count
+    ///
+    /// The lowest gap left by a released nonce comes first, then the higher
+    /// of the node's count and the last nonce handed out plus one.
+    pub fn reserve(&self, address: EthAddress, node_pending: U256) -> U256 {
+        let mut accounts = self.accounts.lock().unwrap();
+        let account = accounts.entry(address).or_default();
+        // Nonces below the node's count were used, e.g. by another client
+        account.gaps = account.gaps.split_off(&node_pending);
+
+        let nonce = match account.gaps.pop_first() {
+            Some(gap) => gap,
+            None => {
+                let nonce = account.next.max(node_pending);
+                account.next = nonce + 1;
+                nonce
+            }
+        };
+        account.pending.insert(nonce);
+        nonce
+    }
+
+    /// Mark `nonce` of `address` as used by a transaction the node accepted
+    pub fn confirm(&self, address: EthAddress, nonce: U256) {
+        if let Some(account) = self.accounts.lock().unwrap().get_mut(&address) {
+            account.pending.remove(&nonce);
+        }
+    }
+
+    /// Give back `nonce` of `address` after its transaction failed to send
+    ///
+    /// Nonces that were not handed out by [`reserve`](Self::reserve), or were
+    /// already confirmed, are ignored.
+    pub fn release(&self, address: EthAddress, nonce: U256) {
+        let mut accounts = self.accounts.lock().unwrap();
+        let Some(account) = accounts.get_mut(&address) else {
+            return;
+        };
+        if !account.pending.remove(&nonce) {
+            return;
+        }
+        account.gaps.insert(nonce);
+        // Gaps at the top are not gaps; hand them out as new nonces
+        while let Some(&last) = account.gaps.last() {
+            if last + 1 != account.next {
+                break;
+            }
+            account.gaps.pop_last();
+            account.next = last;
+        }
+    }
+
+    /// Nonces of `address` handed out but not yet confirmed or released
+    pub fn pending(&self, address: EthAddress) -> Vec<U256> {
+        self.accounts
+            .lock()
+            .unwrap()
+            .get(&address)
+            .map(|account| account.pending.iter().copied().collect())
+            .unwrap_or_default()
+    }
+
+    /// Released nonces of `address` waiting to be handed out again
+    pub fn gaps(&self, address: EthAddress) -> Vec<U256> {
+        self.accounts
+            .lock()
+            .unwrap()
+            .get(&address)
+            .map(|account| account.gaps.iter().copied().collect())
+            .unwrap_or_default()
+    }
+
+    /// Start again from the node's pending transaction count, e.g. after
+    /// transactions were dropped from its pool
+    ///
+    /// Nonces still pending keep their reservation. Fails, leaving the
+    /// account untouched, if `node_pending` does not fit in a `u64`.
+    pub fn resync(&self, address: EthAddress, node_pending: U256) -> Result<(), Error> {
+        let to_u64 = |nonce: U256| {
+            u64::try_from(nonce)
+                .map_err(|_| Error::Other(format!("Nonce {} of {:?} exceeds u64", nonce, address)))
+        };
+        let first = to_u64(node_pending)?;
+        let mut accounts = self.accounts.lock().unwrap();
+        let account = accounts.entry(address).or_default();
+        let in_flight = account.pending.last().map(|&nonce| nonce + 1);
+        let next = in_flight.unwrap_or_default().max(node_pending);
+        let last = to_u64(next)?;
+        account.next = next;
+        account.gaps = (first..last)
+            .map(U256::from)
+            .filter(|nonce| !account.pending.contains(nonce))
+            .collect();
+        Ok(())
+    }
+
+    /// Forget everything about `address`
+    pub fn reset(&self, address: EthAddress) {
+        self.accounts.lock().unwrap().remove(&address);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn nonces(values: &[u64]) -> Vec<U256> {
+        values.iter().copied().map(U256::from).collect()
+    }
+
+    #[test]
+    fn test_reserve() {
+        let manager = NonceManager::new();
+        let alice = EthAddress::repeat_byte(1);
+        let bob = EthAddress::repeat_byte(2);
+
+        // Back-to-back sends before the node sees the first
+        assert_eq!(manager.reserve(alice, U256::from(5)), U256::from(5));
+        assert_eq!(manager.reserve(alice, U256::from(5)), U256::from(6));
+        assert_eq!(manager.reserve(bob, U256::zero()), U256::zero());
+        assert_eq!(manager.pending(alice), nonces(&[5, 6]));
+
+        // The node is ahead, e.g. transactions sent from elsewhere
+        assert_eq!(manager.reserve(alice, U256::from(9)), U256::from(9));
+
+        manager.reset(alice);
+        assert!(manager.pending(alice).is_empty());
+        assert_eq!(manager.reserve(alice, U256::from(7)), U256::from(7));
+    }
+
+    #[test]
+    fn test_release_fills_gaps() {
+        let manager = NonceManager::new();
+        let alice = EthAddress::repeat_byte(1);
+        for _ in 0..4 {
+            manager.reserve(alice, U256::zero());
+        }
+        manager.confirm(alice, U256::from(0));
+        manager.release(alice, U256::from(1));
+        manager.confirm(alice, U256::from(2));
+        assert_eq!(manager.gaps(alice), nonces(&[1]));
+        assert_eq!(manager.pending(alice), nonces(&[3]));
+
+        // Confirmed nonces cannot be released
+        manager.release(alice, U256::from(2));
+        assert_eq!(manager.gaps(alice), nonces(&[1]));
+
+        assert_eq!(manager.reserve(alice, U256::from(1)), U256::from(1));
+        assert_eq!(manager.reserve(alice, U256::from(1)), U256::from(4));
+
+        // Releasing the highest nonces hands them out again in order
+        manager.release(alice, U256::from(4));
+        manager.release(alice, U256::from(3));
+        assert!(manager.gaps(alice).is_empty());
+        assert_eq!(manager.reserve(alice, U256::from(3)), U256::from(3));
+    }
+
+    #[test]
+    fn test_gaps_below_node_count_are_dropped() {
+        let manager = NonceManager::new();
+        let alice = EthAddress::repeat_byte(1);
+        manager.reserve(alice, U256::zero());
+        manager.reserve(alice, U256::zero());
+        manager.release(alice, U256::zero());
+
+        // Nonce 0 was used by another client meanwhile
+        assert_eq!(manager.reserve(alice, U256::from(2)), U256::from(2));
+        assert!(manager.gaps(alice).is_empty());
+    }
+
+    #[test]
+    fn test_resync() {
+        let manager = NonceManager::new();
+        let alice = EthAddress::repeat_byte(1);
+        for _ in 0..5 {
+            manager.reserve(alice, U256::zero());
+        }
+        for nonce in [0u64, 1, 2, 4] {
+            manager.confirm(alice, U256::from(nonce));
+        }
+
+        // Nonces 2 and 4 were dropped from the pool; 3 is still in flight
+        manager.resync(alice, U256::from(2)).unwrap();
+        assert_eq!(manager.gaps(alice), nonces(&[2]));
+        assert_eq!(manager.reserve(alice, U256::from(2)), U256::from(2));
+        assert_eq!(manager.reserve(alice, U256::from(2)), U256::from(4));
+    }
+
+    #[test]
+    fn test_resync_rejects_out_of_range_nonce() {
+        let manager = NonceManager::new();
+        let alice = EthAddress::repeat_byte(1);
+        manager.reserve(alice, U256::from(7));
+
+        assert!(manager.resync(alice, U256::MAX).is_err());
+        assert_eq!(manager.reserve(alice, U256::zero()), U256::from(8));
+    }
+}
@@ -0,0 +1,278 @@
+//! Offline signing of EVM transactions
+//!
+//! Custodians that keep keys in an HSM or on an air-gapped machine build a
+//! transaction online with
+//! [`TransactionExecutor::build_unsigned`](crate::transaction::TransactionExecutor::build_unsigned),
+//! export the resulting [`UnsignedTransaction`] as JSON, sign its
+//! [`sighash`](UnsignedTransaction::sighash) externally, and turn the
+//! signature into a raw transaction with
+//! [`attach_signature`](UnsignedTransaction::attach_signature). The raw bytes
+//! are broadcast with [`EvmAdapter::send_raw_transaction`](crate::EvmAdapter::send_raw_transaction).
+
+use crate::Error;
+use ethers::types::transaction::eip2718::TypedTransaction;
+use ethers::types::{Address as EthAddress, Signature, H256, U256};
+use serde::{Deserialize, Serialize};
+
+/// A filled-in transaction waiting for its signature
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UnsignedTransaction {
+    transaction: TypedTransaction,
+    // Not part of the transaction's JSON form
+    chain_id: u64,
+}
+
+impl UnsignedTransaction {
+    /// Wrap `transaction`, which must have its sender and chain ID set
+    pub fn new(transaction: TypedTransaction) -> Result<Self, Error> {
+        match (transaction.from(), transaction.chain_id()) {
+            (Some(_), Some(chain_id)) => Ok(Self {
+                chain_id: chain_id.as_u64(),
+                transaction,
+            }),
+            _ => Err(Error::Transaction(
+                "Unsigned transaction needs a sender and chain ID".to_string(),
+            )),
+        }
+    }
+
+    /// Transaction with sender, nonce, gas, fees and chain ID set
+    pub fn transaction(&self) -> &TypedTransaction {
+        &self.transaction
+    }
+
+    /// Chain the transaction is for
+    pub fn chain_id(&self) -> u64 {
+        self.chain_id
+    }
+
+    /// Account expected to sign
+    pub fn from(&self) -> EthAddress {
+        self.transaction.from().copied().unwrap_or_default()
+    }
+
+    /// Hash to sign
+    pub fn sighash(&self) -> H256 {
+        self.transaction.sighash()
+    }
+
+    /// RLP encoding of the unsigned transaction, as hardware signers expect
+    pub fn unsigned_rlp(&self) -> Vec<u8> {
+        self.transaction.rlp().to_vec()
+    }
+
+    /// Serialize to pretty-printed JSON
+    pub fn to_json(&self) -> Result<String, Error> {
+        serde_json::to_string_pretty(self)
+            .map_err(|e| Error::Other(format!("Failed to serialize transaction: {}", e)))
+    }
+
+    /// Parse from JSON
+    pub fn from_json(json: &str) -> Result<Self, Error> {
+        let mut unsigned: Self = serde_json::from_str(json)
+            .map_err(|e| Error::Transaction(format!("Invalid unsigned transaction: {}", e)))?;
+        unsigned.transaction.set_chain_id(unsigned.chain_id);
+        Self::new(unsigned.transaction)
+    }
+
+    /// Combine the transaction with a signature of its sighash, returning
+    /// the signed, RLP-encoded transaction
+    ///
+    /// `signature` is `r || s` (64 bytes) or `r || s || v` (65 bytes, `v`
+    /// being 0/1, 27/28 or EIP-155 encoded). It must recover to the sender;
+    /// any other `v` is rejected.
+    pub fn attach_signature(&self, signature: &[u8]) -> Result<Vec<u8>, Error> {
+        let (rs, v) = match signature.len() {
+            64 => (signature, None),
+            65 => (&signature[..64], Some(signature[64] as u64)),
+            len => {
+                return Err(Error::Transaction(format!(
+                    "Signature must be 64 or 65 bytes, got {}",
+                    len
+                )))
+            }
+        };
+        let parities: &[u64] = match v {
+            Some(v) => &[recovery_parity(v)?],
+            None => &[0, 1],
+        };
+
+        let sighash = self.sighash();
+        let from = self.from();
+        let signature = parities
+            .iter()
+            .map(|parity| Signature {
+                r: U256::from_big_endian(&rs[..32]),
+                s: U256::from_big_endian(&rs[32..]),
+                v: parity + 27,
+            })
+            .find(|signature| signature.recover(sighash).ok() == Some(from))
+            .ok_or_else(|| Error::Transaction(format!("Signature was not made by {:?}", from)))?;
+
+        Ok(self
+            .transaction
+            .rlp_signed(&self.encoded_v(signature)?)
+            .to_vec())
+    }
+
+    /// `signature` with an EIP-155 `v`, which ethers expects for every
+    /// transaction type and normalizes for typed ones
+    fn encoded_v(&self, mut signature: Signature) -> Result<Signature, Error> {
+        signature.v = self
+            .chain_id
+            .checked_mul(2)
+            .and_then(|v| v.checked_add(35 + signature.v - 27))
+            .ok_or_else(|| {
+                Error::Transaction(format!(
+                    "Chain ID {} is too large to sign for",
+                    self.chain_id
+                ))
+            })?;
+        Ok(signature)
+    }
+}
+
+/// Y parity of a 0/1, 27/28 or EIP-155 `v`
+fn recovery_parity(v: u64) -> Result<u64, Error> {
+    match v {
+        0 | 1 => Ok(v),
+        27 | 28 => Ok(v - 27),
+        _ => v
+            .checked_sub(35)
+            .map(|v| v % 2)
+            .ok_or_else(|| Error::Transaction(format!("Invalid signature v: {}", v))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wallet::Wallet;
+    use ethers::types::{Eip1559TransactionRequest, TransactionRequest};
+    use ethers::utils::rlp::Rlp;
+
+    async fn sign(wallet: &Wallet, tx: TypedTransaction) -> (UnsignedTransaction, Vec<u8>) {
+        let unsigned = UnsignedTransaction::new(tx).unwrap();
+        let signature = wallet
+            .sign_transaction(unsigned.transaction())
+            .await
+            .unwrap();
+        (unsigned, signature.to_vec())
+    }
+
+    #[tokio::test]
+    async fn test_attach_signature_eip1559() {
+        let wallet = Wallet::new_random().with_chain_id(1);
+        let tx: TypedTransaction = Eip1559TransactionRequest::new()
+            .from(wallet.eth_address())
+            .to(EthAddress::repeat_byte(2))
+            .value(1_000u64)
+            .gas(21_000u64)
+            .max_fee_per_gas(30_000_000_000u64)
+            .max_priority_fee_per_gas(1_000_000_000u64)
+            .nonce(3u64)
+            .chain_id(1u64)
+            .into();
+        let (unsigned, signature) = sign(&wallet, tx).await;
+
+        let json = unsigned.to_json().unwrap();
+        let unsigned = UnsignedTransaction::from_json(&json).unwrap();
+        assert_eq!(unsigned.from(), wallet.eth_address());
+
+        let raw = unsigned.attach_signature(&signature).unwrap();
+        assert_eq!(raw[0], 0x02);
+        let (decoded, decoded_signature) =
+            TypedTransaction::decode_signed(&Rlp::new(&raw)).unwrap();
+        assert_eq!(decoded.nonce(), Some(&U256::from(3)));
+        assert_eq!(
+            decoded_signature.recover(unsigned.sighash()).unwrap(),
+            wallet.eth_address()
+        );
+
+        // Without v, the parity is found by recovery
+        assert_eq!(unsigned.attach_signature(&signature[..64]).unwrap(), raw);
+    }
+
+    #[tokio::test]
+    async fn test_attach_signature_legacy() {
+        let wallet = Wallet::new_random().with_chain_id(5);
+        let tx: TypedTransaction = TransactionRequest::new()
+            .from(wallet.eth_address())
+            .to(EthAddress::repeat_byte(2))
+            .value(1u64)
+            .gas(21_000u64)
+            .gas_price(1_000_000_000u64)
+            .nonce(0u64)
+            .chain_id(5u64)
+            .into();
+        let (unsigned, signature) = sign(&wallet, tx).await;
+
+        let raw = unsigned.attach_signature(&signature).unwrap();
+        let (_, decoded_signature) = TypedTransaction::decode_signed(&Rlp::new(&raw)).unwrap();
+        assert!(decoded_signature.v == 45 || decoded_signature.v == 46);
+        assert_eq!(
+            decoded_signature.recover(unsigned.sighash()).unwrap(),
+            wallet.eth_address()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rejects_foreign_signature() {
+        let wallet = Wallet::new_random().with_chain_id(1);
+        let other = Wallet::new_random().with_chain_id(1);
+        let tx: TypedTransaction = Eip1559TransactionRequest::new()
+            .from(wallet.eth_address())
+            .to(EthAddress::repeat_byte(2))
+            .nonce(0u64)
+            .chain_id(1u64)
+            .into();
+        let unsigned = UnsignedTransaction::new(tx.clone()).unwrap();
+        let signature = other.sign_transaction(&tx).await.unwrap();
+
+        assert!(unsigned.attach_signature(&signature.to_vec()).is_err());
+        assert!(unsigned.attach_signature(&[0u8; 10]).is_err());
+        assert!(UnsignedTransaction::new(TypedTransaction::Legacy(Default::default())).is_err());
+    }
+
+    #[test]
+    fn test_recovery_parity() {
+        assert_eq!(recovery_parity(1).unwrap(), 1);
+        assert_eq!(recovery_parity(27).unwrap(), 0);
+        assert_eq!(recovery_parity(38).unwrap(), 1);
+        for v in [2, 5, 26, 29, 34] {
+            assert!(recovery_parity(v).is_err());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rejects_invalid_v() {
+        let wallet = Wallet::new_random().with_chain_id(1);
+        let tx: TypedTransaction = Eip1559TransactionRequest::new()
+            .from(wallet.eth_address())
+            .to(EthAddress::repeat_byte(2))
+            .nonce(0u64)
+            .chain_id(1u64)
+            .into();
+        let (unsigned, mut signature) = sign(&wallet, tx).await;
+        signature[64] = 5;
+        assert!(matches!(
+            unsigned.attach_signature(&signature),
+            Err(Error::Transaction(_))
+        ));
+    }
+
+    #[test]
+    fn test_rejects_overflowing_chain_id() {
+        let tx: TypedTransaction = Eip1559TransactionRequest::new()
+            .from(EthAddress::repeat_byte(1))
+            .chain_id(u64::MAX)
+            .into();
+        let unsigned = UnsignedTransaction::new(tx).unwrap();
+        let signature = Signature {
+            r: U256::one(),
+            s: U256::one(),
+            v: 27,
+        };
+        assert!(unsigned.encoded_v(signature).is_err());
+    }
+}
@@ -4,12 +4,16 @@
 //! - Connection pooling with round-robin load balancing
 //! - Health checks for endpoints
 //! - Automatic failover to backup endpoints
+//! - Rotation away from rate-limited endpoints
 //! - Connection reuse
 
-use crate::{Error, EvmAdapter};
+use crate::metrics::RpcMetrics;
+use crate::runtime::{self, Instant};
+use crate::{backpressure, provider_error, rpc_error, Error, EvmAdapter};
+use apex_sdk_core::redact::redact_url;
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
-use std::time::{Duration, Instant};
+use std::time::Duration;
 use tokio::sync::RwLock;
 
 /// Health status of an endpoint
@@ -25,6 +29,18 @@ pub struct EndpointHealth {
     pub failure_count: u32,
     /// Average response time in milliseconds
     pub avg_response_time_ms: u64,
+    /// Until when the endpoint asked not to be called, after a rate limit
+    pub rate_limited_until: Option<Instant>,
+    /// Number of rate-limit responses received
+    pub rate_limit_count: u32,
+}
+
+impl EndpointHealth {
+    /// Whether the endpoint is still cooling down from a rate limit
+    pub fn is_rate_limited(&self) -> bool {
+        self.rate_limited_until
+            .is_some_and(|until| until > Instant::now())
+    }
 }
 
 impl Default for EndpointHealth {
@@ -35,6 +51,8 @@ impl Default for EndpointHealth {
             last_failure: None,
             failure_count: 0,
             avg_response_time_ms: 0,
+            rate_limited_until: None,
+            rate_limit_count: 0,
         }
     }
 }
@@ -77,6 +95,21 @@ impl PooledConnection {
         }
     }
 
+    /// Leave the endpoint alone for `cooldown` after a rate-limit response
+    ///
+    /// Rate limits say nothing about the endpoint's health, so the failure
+    /// count is untouched.
+    pub async fn mark_rate_limited(&self, cooldown: Duration) {
+        let mut health = self.health.write().await;
+        health.rate_limited_until = Some(Instant::now() + cooldown);
+        health.rate_limit_count += 1;
+        tracing::warn!(
+            "Endpoint {} rate limited, cooling down for {:?}",
+            redact_url(&self.endpoint),
+            cooldown
+        );
+    }
+
     /// Mark connection as unhealthy after failure
     pub async fn mark_unhealthy(&self) {
         let mut health = self.health.write().await;
@@ -86,7 +119,10 @@ impl PooledConnection {
         // Mark as unhealthy after 3 consecutive failures
         if health.failure_count >= 3 {
             health.is_healthy = false;
-            tracing::warn!("Endpoint {} marked as unhealthy", self.endpoint);
+            tracing::warn!(
+                "Endpoint {} marked as unhealthy",
+                redact_url(&self.endpoint)
+            );
         }
     }
 }
@@ -104,6 +140,9 @@ pub struct PoolConfig {
     pub max_failures: u32,
     /// Time to wait before retrying unhealthy endpoint (seconds)
     pub unhealthy_retry_delay_secs: u64,
+    /// Time to leave a rate-limited endpoint alone when it gives no
+    /// `Retry-After` hint (seconds)
+    pub rate_limit_cooldown_secs: u64,
 }
 
 impl Default for PoolConfig {
@@ -114,6 +153,7 @@ impl Default for PoolConfig {
             health_check_timeout_secs: 5,
             max_failures: 3,
             unhealthy_retry_delay_secs: 60,
+            rate_limit_cooldown_secs: 5,
         }
     }
 }
@@ -124,6 +164,7 @@ pub struct ConnectionPool {
     connections: Arc<RwLock<Vec<PooledConnection>>>,
     next_index: AtomicUsize,
     config: PoolConfig,
+    rpc_metrics: Option<Arc<RpcMetrics>>,
 }
 
 impl ConnectionPool {
@@ -155,10 +196,17 @@ impl ConnectionPool {
                         health: Arc::new(RwLock::new(EndpointHealth::default())),
                     };
                     connections.push(conn);
-                    tracing::info!("Successfully connected to endpoint: {}", endpoint);
+                    tracing::info!(
+                        "Successfully connected to endpoint: {}",
+                        redact_url(endpoint)
+                    );
                 }
                 Err(e) => {
-                    tracing::warn!("Failed to connect to endpoint {}: {}", endpoint, e);
+                    tracing::warn!(
+                        "Failed to connect to endpoint {}: {}",
+                        redact_url(endpoint),
+                        e
+                    );
                     // Create unhealthy connection
                     let adapter = EvmAdapter::connect(endpoint).await?;
                     let health = EndpointHealth {
@@ -182,12 +230,21 @@ impl ConnectionPool {
             connections: Arc::new(RwLock::new(connections)),
             next_index: AtomicUsize::new(0),
             config,
+            rpc_metrics: None,
         })
     }
 
+    /// Record [`raw_request`](Self::raw_request) calls, rate limits and
+    /// endpoint rotations in `metrics`
+    pub fn with_rpc_metrics(mut self, metrics: Arc<RpcMetrics>) -> Self {
+        self.rpc_metrics = Some(metrics);
+        self
+    }
+
     /// Get a connection using round-robin load balancing
     ///
-    /// This will skip unhealthy endpoints and try the next one
+    /// This will skip unhealthy and rate-limited endpoints and try the next
+    /// one
     pub async fn get_connection(&self) -> Result<Arc<PooledConnection>, Error> {
         let connections = self.connections.read().await;
 
@@ -204,6 +261,10 @@ impl ConnectionPool {
             let conn = &connections[index];
 
             let health = conn.health.read().await;
+            if health.is_rate_limited() {
+                attempts += 1;
+                continue;
+            }
             if health.is_healthy {
                 drop(health);
                 return Ok(Arc::new(PooledConnection {
@@ -217,7 +278,10 @@ impl ConnectionPool {
             if let Some(last_failure) = health.last_failure {
                 if last_failure.elapsed().as_secs() > self.config.unhealthy_retry_delay_secs {
                     drop(health);
-                    tracing::info!("Retrying previously unhealthy endpoint: {}", conn.endpoint);
+                    tracing::info!(
+                        "Retrying previously unhealthy endpoint: {}",
+                        redact_url(&conn.endpoint)
+                    );
                     return Ok(Arc::new(PooledConnection {
                         adapter: conn.adapter.clone(),
                         endpoint: conn.endpoint.clone(),
@@ -229,9 +293,22 @@ impl ConnectionPool {
             attempts += 1;
         }
 
-        // All endpoints unhealthy, return the first one and let caller handle retry
-        let conn = &connections[0];
-        tracing::warn!("All endpoints unhealthy, returning first endpoint");
+        // All endpoints unavailable: return the one whose rate limit ends
+        // first (the first one if none is rate limited) and let the caller
+        // handle retry
+        let mut conn = &connections[0];
+        let mut until = conn.health.read().await.rate_limited_until;
+        for candidate in &connections[1..] {
+            let candidate_until = candidate.health.read().await.rate_limited_until;
+            if candidate_until < until {
+                conn = candidate;
+                until = candidate_until;
+            }
+        }
+        tracing::warn!(
+            "All endpoints unavailable, returning {}",
+            redact_url(&conn.endpoint)
+        );
         Ok(Arc::new(PooledConnection {
             adapter: conn.adapter.clone(),
             endpoint: conn.endpoint.clone(),
@@ -239,6 +316,91 @@ impl ConnectionPool {
         }))
     }
 
+    /// Send JSON-RPC method `method` with `params` to a pooled endpoint and
+    /// decode its result
+    ///
+    /// An endpoint that answers with a rate limit is left alone for as long
+    /// as it asks (or [`PoolConfig::rate_limit_cooldown_secs`]) and the call
+    /// moves on to the next endpoint; an endpoint that cannot be reached
+    /// counts a failure and the call moves on too. Each endpoint is tried at
+    /// most twice. If every endpoint is rate limited, the call waits for the
+    /// first to cool down. Other errors returned by the node are not retried.
+    pub async fn raw_request<T: serde::de::DeserializeOwned>(
+        &self,
+        method: &str,
+        params: serde_json::Value,
+    ) -> Result<T, Error> {
+        use ethers::providers::RpcError;
+
+        let max_attempts = 2 * self.connections.read().await.len();
+        let mut last_error = None;
+
+        for _ in 0..max_attempts {
+            let conn = self.get_connection().await?;
+            let wait = conn
+                .health()
+                .await
+                .rate_limited_until
+                .map(|until| until.saturating_duration_since(Instant::now()));
+            if let Some(wait) = wait.filter(|wait| !wait.is_zero()) {
+                tracing::debug!("All endpoints rate limited, waiting {:?}", wait);
+                runtime::sleep(wait).await;
+            }
+
+            conn.adapter.throttle().await?;
+            let started = Instant::now();
+            let result = conn
+                .adapter
+                .provider
+                .try_request(method, params.clone())
+                .await;
+            let latency_ms = started.elapsed().as_millis() as u64;
+            if let Some(metrics) = &self.rpc_metrics {
+                match &result {
+                    Ok(_) => metrics.record_success(latency_ms),
+                    Err(_) => metrics.record_failure(latency_ms),
+                }
+            }
+
+            let e = match result {
+                Ok(value) => {
+                    conn.mark_healthy(latency_ms).await;
+                    return serde_json::from_value(value).map_err(|e| {
+                        Error::Other(format!("Unexpected response from {}: {}", method, e))
+                    });
+                }
+                Err(e) => e,
+            };
+            if let Some(limit) = backpressure::rate_limit(&e) {
+                let cooldown = limit
+                    .retry_after
+                    .unwrap_or(Duration::from_secs(self.config.rate_limit_cooldown_secs));
+                conn.mark_rate_limited(cooldown).await;
+                if let Some(metrics) = &self.rpc_metrics {
+                    metrics.record_rate_limit();
+                    metrics.record_rotation();
+                }
+            } else if let Some(response) = e.as_error_response() {
+                return Err(Error::Other(format!(
+                    "{} rejected: {}",
+                    method, response.message
+                )));
+            } else {
+                conn.mark_unhealthy().await;
+                tracing::warn!(
+                    "{}, trying next endpoint",
+                    provider_error(&format!("{} failed", method), &e)
+                );
+            }
+            last_error = Some(e);
+        }
+
+        Err(match last_error {
+            Some(e) => rpc_error(&format!("{} failed", method), e, Error::Connection),
+            None => Error::Connection("No connections available".to_string()),
+        })
+    }
+
     /// Get health status of all endpoints
     pub async fn health_status(&self) -> Vec<(String, EndpointHealth)> {
         let connections = self.connections.read().await;
@@ -266,11 +428,19 @@ impl ConnectionPool {
                 Ok(_) => {
                     let elapsed = start.elapsed().as_millis() as u64;
                     conn.mark_healthy(elapsed).await;
-                    tracing::debug!("Health check passed for {}: {}ms", conn.endpoint, elapsed);
+                    tracing::debug!(
+                        "Health check passed for {}: {}ms",
+                        redact_url(&conn.endpoint),
+                        elapsed
+                    );
                 }
                 Err(e) => {
                     conn.mark_unhealthy().await;
-                    tracing::warn!("Health check failed for {}: {}", conn.endpoint, e);
+                    tracing::warn!(
+                        "Health check failed for {}: {}",
+                        redact_url(&conn.endpoint),
+                        e
+                    );
                 }
             }
         }
@@ -284,9 +454,9 @@ impl ConnectionPool {
         let interval = Duration::from_secs(self.config.health_check_interval_secs);
         let interval_secs = self.config.health_check_interval_secs;
 
-        tokio::spawn(async move {
+        runtime::spawn(async move {
             loop {
-                tokio::time::sleep(interval).await;
+                runtime::sleep(interval).await;
 
                 if let Err(e) = pool.run_health_checks().await {
                     tracing::error!("Health check error: {}", e);
@@ -325,6 +495,13 @@ mod tests {
         let health = EndpointHealth::default();
         assert!(health.is_healthy);
         assert_eq!(health.failure_count, 0);
+        assert!(!health.is_rate_limited());
+
+        let health = EndpointHealth {
+            rate_limited_until: Some(Instant::now() + Duration::from_secs(60)),
+            ..Default::default()
+        };
+        assert!(health.is_rate_limited());
     }
 
     #[tokio::test]
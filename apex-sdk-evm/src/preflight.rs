@@ -0,0 +1,541 @@
+//! Simulation-based security pre-flight for EVM transactions
+//!
+//! [`SecurityPreflight`] simulates a transaction before it is submitted and
+//! inspects the resulting call tree for dangerous patterns:
+//! - unlimited ERC-20 approvals and `setApprovalForAll`
+//! - value or token transfers to contracts that aren't verified
+//! - `DELEGATECALL` into unverified code
+//! - reverts
+//!
+//! Simulation uses `debug_traceCall` with the `callTracer`. Nodes that don't
+//! expose the debug namespace fall back to `eth_call` plus analysis of the
+//! top-level calldata only; [`RiskReport::traced`] tells which one was used.
+//!
+//! "Verified" is decided by a [`ContractVerifier`]; the default
+//! [`KnownContracts`] treats an explicit set of addresses as verified.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use apex_sdk_evm::preflight::Severity;
+//! use apex_sdk_evm::EvmAdapter;
+//! # use ethers::types::transaction::eip2718::TypedTransaction;
+//!
+//! # async fn example(tx: TypedTransaction) -> Result<(), apex_sdk_evm::Error> {
+//! let adapter = EvmAdapter::connect("https://eth.llamarpc.com").await?;
+//! let report = adapter.security_preflight().analyze(&tx).await?;
+//! if report.exceeds(Severity::High) {
+//!     println!("Refusing to sign: {}", report);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{throttle, Error, ProviderType};
+use apex_sdk_core::performance::RateLimiter;
+use async_trait::async_trait;
+use ethers::types::transaction::eip2718::TypedTransaction;
+use ethers::types::{Address as EthAddress, CallFrame, NameOrAddress, U256};
+use serde_json::json;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::Arc;
+
+/// `approve(address,uint256)`
+const APPROVE: [u8; 4] = [0x09, 0x5e, 0xa7, 0xb3];
+/// `setApprovalForAll(address,bool)`
+const SET_APPROVAL_FOR_ALL: [u8; 4] = [0xa2, 0x2c, 0xb4, 0x65];
+/// `transfer(address,uint256)`
+const TRANSFER: [u8; 4] = [0xa9, 0x05, 0x9c, 0xbb];
+/// `transferFrom(address,address,uint256)`
+const TRANSFER_FROM: [u8; 4] = [0x23, 0xb8, 0x72, 0xdd];
+
+/// Severity of a finding
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+/// Kind of dangerous pattern found
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RiskKind {
+    /// The simulated transaction reverted
+    Reverted,
+    /// ERC-20 approval of (effectively) unlimited amount
+    UnlimitedApproval,
+    /// Operator approval over an entire NFT collection
+    ApprovalForAll,
+    /// Native or token transfer to a contract that isn't verified
+    TransferToUnverifiedContract,
+    /// `DELEGATECALL` into code that isn't verified
+    DelegateCallToUnknown,
+}
+
+/// A single finding
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RiskFinding {
+    /// What was found
+    pub kind: RiskKind,
+    /// How dangerous it is
+    pub severity: Severity,
+    /// Address involved (spender, operator, recipient or callee)
+    pub address: Option<EthAddress>,
+    /// Human-readable description
+    pub description: String,
+}
+
+/// Result of a pre-flight analysis
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RiskReport {
+    /// Findings, in call-tree order
+    pub findings: Vec<RiskFinding>,
+    /// Whether the full call tree was traced (`debug_traceCall`)
+    pub traced: bool,
+}
+
+impl RiskReport {
+    /// Highest severity among the findings
+    pub fn max_severity(&self) -> Option<Severity> {
+        self.findings.iter().map(|f| f.severity).max()
+    }
+
+    /// Whether any finding is at least `severity`
+    pub fn exceeds(&self, severity: Severity) -> bool {
+        self.max_severity().is_some_and(|max| max >= severity)
+    }
+
+    /// Whether nothing was found
+    pub fn is_clean(&self) -> bool {
+        self.findings.is_empty()
+    }
+
+    /// Findings of a given kind
+    pub fn of_kind(&self, kind: RiskKind) -> impl Iterator<Item = &RiskFinding> {
+        self.findings.iter().filter(move |f| f.kind == kind)
+    }
+}
+
+impl fmt::Display for RiskReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.findings.is_empty() {
+            return write!(f, "no findings");
+        }
+        let findings: Vec<String> = self
+            .findings
+            .iter()
+            .map(|finding| format!("[{:?}] {}", finding.severity, finding.description))
+            .collect();
+        write!(f, "{}", findings.join("; "))
+    }
+}
+
+/// Decides whether a contract is trusted
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+pub trait ContractVerifier: Send + Sync {
+    /// Whether the contract at `address` is verified
+    async fn is_verified(&self, address: EthAddress) -> bool;
+}
+
+/// Treats a fixed set of contracts as verified
+#[derive(Debug, Clone, Default)]
+pub struct KnownContracts {
+    addresses: HashSet<EthAddress>,
+}
+
+impl KnownContracts {
+    /// Create an empty set
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Mark a contract as verified
+    pub fn with(mut self, address: EthAddress) -> Self {
+        self.addresses.insert(address);
+        self
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl ContractVerifier for KnownContracts {
+    async fn is_verified(&self, address: EthAddress) -> bool {
+        self.addresses.contains(&address)
+    }
+}
+
+/// Simulates transactions and reports dangerous state changes
+pub struct SecurityPreflight {
+    provider: ProviderType,
+    verifier: Arc<dyn ContractVerifier>,
+    trace: bool,
+    limiter: Option<Arc<RateLimiter>>,
+}
+
+impl SecurityPreflight {
+    /// Create an analyzer using `provider` for simulation
+    pub fn new(provider: ProviderType) -> Self {
+        Self {
+            provider,
+            verifier: Arc::new(KnownContracts::new()),
+            trace: true,
+            limiter: None,
+        }
+    }
+
+    /// Use a custom contract verifier
+    pub fn with_verifier(mut self, verifier: impl ContractVerifier + 'static) -> Self {
+        self.verifier = Arc::new(verifier);
+        self
+    }
+
+    /// Throttle simulation requests through `limiter`
+    pub fn with_rate_limiter(mut self, limiter: Option<Arc<RateLimiter>>) -> Self {
+        self.limiter = limiter;
+        self
+    }
+
+    /// Skip `debug_traceCall` and only simulate with `eth_call`
+    pub fn without_trace(mut self) -> Self {
+        self.trace = false;
+        self
+    }
+
+    /// Simulate `tx` and analyze it
+    pub async fn analyze(&self, tx: &TypedTransaction) -> Result<RiskReport, Error> {
+        let mut report = RiskReport::default();
+
+        let root = match self.trace_call(tx).await {
+            Some(frame) => {
+                report.traced = true;
+                frame
+            }
+            None => {
+                let mut frame = top_level_frame(tx);
+                throttle(self.limiter.as_deref()).await?;
+                if let Err(e) = self.provider.call(tx, None).await {
+                    frame.error = Some(e.to_string());
+                }
+                frame
+            }
+        };
+
+        let mut frames = Vec::new();
+        flatten(&root, &mut frames);
+
+        let mut has_code = HashMap::new();
+        for frame in frames {
+            for finding in calldata_findings(frame) {
+                report.findings.push(finding);
+            }
+
+            let Some(to) = frame_target(frame) else {
+                continue;
+            };
+
+            if frame.typ.eq_ignore_ascii_case("DELEGATECALL")
+                && !self.verifier.is_verified(to).await
+            {
+                report.findings.push(RiskFinding {
+                    kind: RiskKind::DelegateCallToUnknown,
+                    severity: Severity::Critical,
+                    address: Some(to),
+                    description: format!("delegatecall into unverified code at {:?}", to),
+                });
+            }
+
+            for recipient in transfer_recipients(frame) {
+                if self
+                    .is_unverified_contract(recipient, &mut has_code)
+                    .await?
+                {
+                    report.findings.push(RiskFinding {
+                        kind: RiskKind::TransferToUnverifiedContract,
+                        severity: Severity::Medium,
+                        address: Some(recipient),
+                        description: format!("transfer to unverified contract {:?}", recipient),
+                    });
+                }
+            }
+        }
+
+        if let Some(error) = &root.error {
+            report.findings.push(RiskFinding {
+                kind: RiskKind::Reverted,
+                severity: Severity::High,
+                address: frame_target(&root),
+                description: format!("simulation reverted: {}", error),
+            });
+        }
+
+        tracing::debug!("Security pre-flight: {}", report);
+        Ok(report)
+    }
+
+    /// Trace the call tree, or `None` if tracing is disabled or unsupported
+    async fn trace_call(&self, tx: &TypedTransaction) -> Option<CallFrame> {
+        if !self.trace {
+            return None;
+        }
+        let params = json!([tx, "latest", { "tracer": "callTracer" }]);
+        throttle(self.limiter.as_deref()).await.ok()?;
+        match self.provider.request("debug_traceCall", params).await {
+            Ok(value) => serde_json::from_value(value)
+                .map_err(|e| tracing::debug!("Unexpected callTracer output: {}", e))
+                .ok(),
+            Err(e) => {
+                tracing::debug!(
+                    "debug_traceCall unavailable, falling back to eth_call: {}",
+                    e
+                );
+                None
+            }
+        }
+    }
+
+    async fn is_unverified_contract(
+        &self,
+        address: EthAddress,
+        cache: &mut HashMap<EthAddress, bool>,
+    ) -> Result<bool, Error> {
+        let has_code = match cache.get(&address) {
+            Some(has_code) => *has_code,
+            None => {
+                throttle(self.limiter.as_deref()).await?;
+                let has_code = !self.provider.get_code(address, None).await?.is_empty();
+                cache.insert(address, has_code);
+                has_code
+            }
+        };
+        Ok(has_code && !self.verifier.is_verified(address).await)
+    }
+}
+
+fn top_level_frame(tx: &TypedTransaction) -> CallFrame {
+    CallFrame {
+        typ: "CALL".to_string(),
+        from: tx.from().copied().unwrap_or_default(),
+        to: tx.to().cloned(),
+        value: tx.value().copied(),
+        input: tx.data().cloned().unwrap_or_default(),
+        ..Default::default()
+    }
+}
+
+fn flatten<'a>(frame: &'a CallFrame, out: &mut Vec<&'a CallFrame>) {
+    out.push(frame);
+    for call in frame.calls.iter().flatten() {
+        flatten(call, out);
+    }
+}
+
+fn frame_target(frame: &CallFrame) -> Option<EthAddress> {
+    match frame.to.as_ref()? {
+        NameOrAddress::Address(address) => Some(*address),
+        NameOrAddress::Name(_) => None,
+    }
+}
+
+/// ABI word `index` of the arguments (after the selector)
+fn word(input: &[u8], index: usize) -> Option<&[u8]> {
+    input.get(4 + index * 32..4 + (index + 1) * 32)
+}
+
+fn word_address(input: &[u8], index: usize) -> Option<EthAddress> {
+    word(input, index).map(|w| EthAddress::from_slice(&w[12..]))
+}
+
+/// Findings derived from a frame's calldata alone
+fn calldata_findings(frame: &CallFrame) -> Vec<RiskFinding> {
+    let input = frame.input.as_ref();
+    let token = frame_target(frame);
+    let mut findings = Vec::new();
+
+    match input.get(..4) {
+        Some(selector) if selector == APPROVE => {
+            if let (Some(spender), Some(amount)) = (word_address(input, 0), word(input, 1)) {
+                if U256::from_big_endian(amount) >= U256::MAX >> 1 {
+                    findings.push(RiskFinding {
+                        kind: RiskKind::UnlimitedApproval,
+                        severity: Severity::High,
+                        address: Some(spender),
+                        description: format!(
+                            "unlimited approval of token {:?} to {:?}",
+                            token.unwrap_or_default(),
+                            spender
+                        ),
+                    });
+                }
+            }
+        }
+        Some(selector) if selector == SET_APPROVAL_FOR_ALL => {
+            if let (Some(operator), Some(approved)) = (word_address(input, 0), word(input, 1)) {
+                if approved.iter().any(|b| *b != 0) {
+                    findings.push(RiskFinding {
+                        kind: RiskKind::ApprovalForAll,
+                        severity: Severity::High,
+                        address: Some(operator),
+                        description: format!(
+                            "approval for all of collection {:?} to {:?}",
+                            token.unwrap_or_default(),
+                            operator
+                        ),
+                    });
+                }
+            }
+        }
+        _ => {}
+    }
+    findings
+}
+
+/// Recipients of native value or ERC-20 transfers in a frame
+fn transfer_recipients(frame: &CallFrame) -> Vec<EthAddress> {
+    let mut recipients = Vec::new();
+    let input = frame.input.as_ref();
+
+    if frame.value.is_some_and(|v| !v.is_zero()) && !frame.typ.eq_ignore_ascii_case("DELEGATECALL")
+    {
+        recipients.extend(frame_target(frame));
+    }
+
+    match input.get(..4) {
+        Some(selector) if selector == TRANSFER => recipients.extend(word_address(input, 0)),
+        Some(selector) if selector == TRANSFER_FROM => recipients.extend(word_address(input, 1)),
+        _ => {}
+    }
+    recipients
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::types::Bytes;
+
+    fn address(byte: u8) -> EthAddress {
+        EthAddress::repeat_byte(byte)
+    }
+
+    fn calldata(selector: [u8; 4], words: &[[u8; 32]]) -> Bytes {
+        let mut data = selector.to_vec();
+        for w in words {
+            data.extend_from_slice(w);
+        }
+        data.into()
+    }
+
+    fn address_word(a: EthAddress) -> [u8; 32] {
+        let mut w = [0u8; 32];
+        w[12..].copy_from_slice(a.as_bytes());
+        w
+    }
+
+    fn uint_word(v: U256) -> [u8; 32] {
+        let mut w = [0u8; 32];
+        v.to_big_endian(&mut w);
+        w
+    }
+
+    fn frame(typ: &str, to: EthAddress, input: Bytes) -> CallFrame {
+        CallFrame {
+            typ: typ.to_string(),
+            to: Some(NameOrAddress::Address(to)),
+            input,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_unlimited_approval() {
+        let unlimited = frame(
+            "CALL",
+            address(1),
+            calldata(APPROVE, &[address_word(address(2)), uint_word(U256::MAX)]),
+        );
+        let findings = calldata_findings(&unlimited);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].kind, RiskKind::UnlimitedApproval);
+        assert_eq!(findings[0].address, Some(address(2)));
+
+        let bounded = frame(
+            "CALL",
+            address(1),
+            calldata(APPROVE, &[address_word(address(2)), uint_word(1000.into())]),
+        );
+        assert!(calldata_findings(&bounded).is_empty());
+    }
+
+    #[test]
+    fn test_approval_for_all() {
+        let approve = frame(
+            "CALL",
+            address(1),
+            calldata(
+                SET_APPROVAL_FOR_ALL,
+                &[address_word(address(3)), uint_word(1.into())],
+            ),
+        );
+        assert_eq!(
+            calldata_findings(&approve)[0].kind,
+            RiskKind::ApprovalForAll
+        );
+
+        let revoke = frame(
+            "CALL",
+            address(1),
+            calldata(
+                SET_APPROVAL_FOR_ALL,
+                &[address_word(address(3)), uint_word(0.into())],
+            ),
+        );
+        assert!(calldata_findings(&revoke).is_empty());
+    }
+
+    #[test]
+    fn test_transfer_recipients() {
+        let mut native = frame("CALL", address(4), Bytes::new());
+        native.value = Some(1.into());
+        assert_eq!(transfer_recipients(&native), vec![address(4)]);
+
+        let token = frame(
+            "CALL",
+            address(5),
+            calldata(TRANSFER, &[address_word(address(6)), uint_word(1.into())]),
+        );
+        assert_eq!(transfer_recipients(&token), vec![address(6)]);
+
+        let from = frame(
+            "CALL",
+            address(5),
+            calldata(
+                TRANSFER_FROM,
+                &[
+                    address_word(address(7)),
+                    address_word(address(8)),
+                    uint_word(1.into()),
+                ],
+            ),
+        );
+        assert_eq!(transfer_recipients(&from), vec![address(8)]);
+    }
+
+    #[test]
+    fn test_report_severity() {
+        let report = RiskReport {
+            findings: vec![RiskFinding {
+                kind: RiskKind::TransferToUnverifiedContract,
+                severity: Severity::Medium,
+                address: None,
+                description: "transfer".to_string(),
+            }],
+            traced: true,
+        };
+        assert!(report.exceeds(Severity::Low));
+        assert!(report.exceeds(Severity::Medium));
+        assert!(!report.exceeds(Severity::High));
+        assert!(RiskReport::default().is_clean());
+    }
+}
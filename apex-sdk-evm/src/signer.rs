@@ -0,0 +1,466 @@
+//! Signers backed by a KMS or HSM
+//!
+//! A [`DigestSigner`] signs 32-byte digests with secp256k1 ECDSA wherever the
+//! key lives. [`Wallet::from_signer`](crate::wallet::Wallet::from_signer)
+//! wraps one so it can be used anywhere a local wallet can, without the
+//! private key ever being exportable.
+//!
+//! KMS and HSM APIs return plain ECDSA signatures: no recovery id, and `s`
+//! possibly in the upper half of the curve order, which Ethereum rejects.
+//! [`recoverable_signature`] normalizes `s` and recovers `v`.
+//!
+//! Backends:
+//! - `aws::AwsKmsSigner` - AWS KMS `ECC_SECG_P256K1` keys (`aws-kms` feature)
+//! - `pkcs11::Pkcs11Signer` - PKCS#11 HSMs (`pkcs11` feature)
+//! - [`RemoteDigestSigner`] - any secp256k1
+//!   [`RemoteSigner`](apex_sdk_core::remote_signer::RemoteSigner), e.g.
+//!   Vault transit (`vault` feature of `apex-sdk-core`)
+
+use crate::Error;
+use apex_sdk_core::remote_signer::{RemoteSigner, SignatureScheme};
+use async_trait::async_trait;
+use ethers::core::k256::ecdsa::{RecoveryId, Signature as EcdsaSignature, VerifyingKey};
+use ethers::types::{Address as EthAddress, Signature, H256, U256};
+use ethers::utils::public_key_to_address;
+
+/// Signs digests with a secp256k1 key held elsewhere
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+pub trait DigestSigner: Send + Sync {
+    /// Address of the signing key
+    fn address(&self) -> EthAddress;
+
+    /// Sign a 32-byte digest, returning a signature with `v` of 27 or 28
+    async fn sign_digest(&self, digest: H256) -> Result<Signature, Error>;
+}
+
+/// Build an Ethereum signature from a raw 64-byte `r || s` ECDSA signature
+///
+/// Normalizes `s` to the lower half of the curve order and picks the
+/// recovery id that yields `address`.
+pub fn recoverable_signature(
+    rs: &[u8],
+    digest: H256,
+    address: EthAddress,
+) -> Result<Signature, Error> {
+    let signature = EcdsaSignature::from_slice(rs)
+        .map_err(|e| Error::Other(format!("Invalid ECDSA signature: {}", e)))?;
+    let signature = signature.normalize_s().unwrap_or(signature);
+
+    for id in 0..2u8 {
+        let recovery_id = RecoveryId::from_byte(id).expect("recovery id in range");
+        let recovered =
+            VerifyingKey::recover_from_prehash(digest.as_bytes(), &signature, recovery_id);
+        if matches!(recovered, Ok(key) if public_key_to_address(&key) == address) {
+            let bytes = signature.to_bytes();
+            return Ok(Signature {
+                r: U256::from_big_endian(&bytes[..32]),
+                s: U256::from_big_endian(&bytes[32..]),
+                v: 27 + id as u64,
+            });
+        }
+    }
+
+    Err(Error::Other(format!(
+        "Signature does not recover to {:?}",
+        address
+    )))
+}
+
+/// Convert a DER-encoded ECDSA signature to 64-byte `r || s`
+pub fn der_to_rs(der: &[u8]) -> Result<[u8; 64], Error> {
+    let invalid = || Error::Other("Invalid DER signature".to_string());
+
+    let body = match der {
+        [0x30, len, body @ ..] if *len as usize == body.len() => body,
+        _ => return Err(invalid()),
+    };
+    let (r, rest) = der_integer(body).ok_or_else(invalid)?;
+    let (s, rest) = der_integer(rest).ok_or_else(invalid)?;
+    if !rest.is_empty() {
+        return Err(invalid());
+    }
+
+    let mut rs = [0u8; 64];
+    rs[32 - r.len()..32].copy_from_slice(r);
+    rs[64 - s.len()..].copy_from_slice(s);
+    Ok(rs)
+}
+
+/// Split a DER `INTEGER` off `input`, without leading zero bytes
+fn der_integer(input: &[u8]) -> Option<(&[u8], &[u8])> {
+    let [0x02, len, rest @ ..] = input else {
+        return None;
+    };
+    let (int, rest) = rest.split_at_checked(*len as usize)?;
+    let start = int.iter().position(|b| *b != 0).unwrap_or(int.len());
+    let int = &int[start..];
+    (int.len() <= 32).then_some((int, rest))
+}
+
+/// Address for a secp256k1 public key, given as SEC1 bytes or a DER
+/// `SubjectPublicKeyInfo`
+pub fn address_from_public_key(public_key: &[u8]) -> Result<EthAddress, Error> {
+    // An uncompressed point is the trailing 65 bytes of the SPKI encoding
+    let sec1 = match public_key.len() {
+        len if len > 65 => &public_key[len - 65..],
+        _ => public_key,
+    };
+    VerifyingKey::from_sec1_bytes(sec1)
+        .map(|key| public_key_to_address(&key))
+        .map_err(|e| Error::Other(format!("Invalid secp256k1 public key: {}", e)))
+}
+
+/// [`DigestSigner`] for a chain-agnostic secp256k1 [`RemoteSigner`]
+pub struct RemoteDigestSigner<S> {
+    signer: S,
+    address: EthAddress,
+}
+
+impl<S: RemoteSigner> RemoteDigestSigner<S> {
+    /// Wrap `signer`, which must hold a secp256k1 key
+    pub fn new(signer: S) -> Result<Self, Error> {
+        if signer.scheme() != SignatureScheme::EcdsaSecp256k1 {
+            return Err(Error::Other(format!(
+                "Remote signer uses {:?}, not secp256k1",
+                signer.scheme()
+            )));
+        }
+        let address = address_from_public_key(signer.public_key())?;
+        Ok(Self { signer, address })
+    }
+
+    /// The wrapped signer
+    pub fn inner(&self) -> &S {
+        &self.signer
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<S: RemoteSigner> DigestSigner for RemoteDigestSigner<S> {
+    fn address(&self) -> EthAddress {
+        self.address
+    }
+
+    async fn sign_digest(&self, digest: H256) -> Result<Signature, Error> {
+        let signature = self
+            .signer
+            .sign(digest.as_bytes())
+            .await
+            .map_err(|e| Error::Transaction(e.to_string()))?;
+        let rs = match signature.len() {
+            64 => signature,
+            _ => der_to_rs(&signature)?.to_vec(),
+        };
+        recoverable_signature(&rs, digest, self.address)
+    }
+}
+
+/// AWS KMS signer
+#[cfg(all(feature = "aws-kms", not(target_arch = "wasm32")))]
+pub mod aws {
+    use super::{address_from_public_key, der_to_rs, recoverable_signature, DigestSigner};
+    use crate::Error;
+    use apex_sdk_core::remote_signer::{self, RemoteSigner, SignatureScheme, SignerError};
+    use async_trait::async_trait;
+    use aws_sdk_kms::error::DisplayErrorContext;
+    use aws_sdk_kms::primitives::Blob;
+    use aws_sdk_kms::types::{MessageType, SigningAlgorithmSpec};
+    use aws_sdk_kms::Client;
+    use ethers::types::{Address as EthAddress, Signature, H256};
+
+    /// Signs with an AWS KMS `ECC_SECG_P256K1` key
+    ///
+    /// Access is governed by the key's IAM policy; the key material never
+    /// leaves KMS.
+    pub struct AwsKmsSigner {
+        client: Client,
+        key_id: String,
+        public_key: Vec<u8>,
+        address: EthAddress,
+    }
+
+    impl AwsKmsSigner {
+        /// Use `key_id` (key ARN, id or alias), fetching its public key
+        pub async fn new(client: Client, key_id: impl Into<String>) -> Result<Self, Error> {
+            let key_id = key_id.into();
+            let output = client
+                .get_public_key()
+                .key_id(&key_id)
+                .send()
+                .await
+                .map_err(|e| {
+                    Error::Other(format!(
+                        "KMS GetPublicKey failed: {}",
+                        DisplayErrorContext(e)
+                    ))
+                })?;
+            let public_key = output
+                .public_key()
+                .ok_or_else(|| Error::Other("KMS returned no public key".to_string()))?;
+            let public_key = public_key.as_ref().to_vec();
+            let address = address_from_public_key(&public_key)?;
+
+            tracing::info!("Using KMS key {} for {:?}", key_id, address);
+
+            Ok(Self {
+                client,
+                key_id,
+                public_key,
+                address,
+            })
+        }
+
+        /// DER signature of a 32-byte digest
+        async fn sign_der(&self, digest: &[u8]) -> Result<Vec<u8>, String> {
+            let output = self
+                .client
+                .sign()
+                .key_id(&self.key_id)
+                .message(Blob::new(digest.to_vec()))
+                .message_type(MessageType::Digest)
+                .signing_algorithm(SigningAlgorithmSpec::EcdsaSha256)
+                .send()
+                .await
+                .map_err(|e| format!("KMS Sign failed: {}", DisplayErrorContext(e)))?;
+            output
+                .signature()
+                .map(|der| der.as_ref().to_vec())
+                .ok_or_else(|| "KMS returned no signature".to_string())
+        }
+    }
+
+    #[async_trait]
+    impl DigestSigner for AwsKmsSigner {
+        fn address(&self) -> EthAddress {
+            self.address
+        }
+
+        async fn sign_digest(&self, digest: H256) -> Result<Signature, Error> {
+            let der = self
+                .sign_der(digest.as_bytes())
+                .await
+                .map_err(Error::Transaction)?;
+            recoverable_signature(&der_to_rs(&der)?, digest, self.address)
+        }
+    }
+
+    #[async_trait]
+    impl RemoteSigner for AwsKmsSigner {
+        fn public_key(&self) -> &[u8] {
+            &self.public_key
+        }
+
+        fn scheme(&self) -> SignatureScheme {
+            SignatureScheme::EcdsaSecp256k1
+        }
+
+        /// Sign a 32-byte digest, returning `r || s`
+        async fn sign(&self, message: &[u8]) -> remote_signer::Result<Vec<u8>> {
+            if message.len() != 32 {
+                return Err(SignerError::InvalidInput(format!(
+                    "Expected a 32-byte digest, got {} bytes",
+                    message.len()
+                )));
+            }
+            let der = self.sign_der(message).await.map_err(SignerError::Backend)?;
+            der_to_rs(&der)
+                .map(|rs| rs.to_vec())
+                .map_err(|e| SignerError::InvalidResponse(e.to_string()))
+        }
+    }
+}
+
+/// PKCS#11 HSM signer
+#[cfg(all(feature = "pkcs11", not(target_arch = "wasm32")))]
+pub mod pkcs11 {
+    use super::{address_from_public_key, recoverable_signature, DigestSigner};
+    use crate::Error;
+    use apex_sdk_core::hsm::{Pkcs11Session, SignMechanism};
+    use async_trait::async_trait;
+    use ethers::types::{Address as EthAddress, Signature, H256};
+    use std::sync::Arc;
+
+    /// Signs with a secp256k1 key pair on a PKCS#11 token
+    pub struct Pkcs11Signer {
+        session: Arc<Pkcs11Session>,
+        label: String,
+        address: EthAddress,
+    }
+
+    impl Pkcs11Signer {
+        /// Use the key pair labelled `label`
+        pub fn new(session: Arc<Pkcs11Session>, label: impl Into<String>) -> Result<Self, Error> {
+            let label = label.into();
+            let public_key = session
+                .public_key(&label)
+                .map_err(|e| Error::Other(e.to_string()))?;
+            let address = address_from_public_key(&public_key)?;
+            Ok(Self {
+                session,
+                label,
+                address,
+            })
+        }
+    }
+
+    #[async_trait]
+    impl DigestSigner for Pkcs11Signer {
+        fn address(&self) -> EthAddress {
+            self.address
+        }
+
+        async fn sign_digest(&self, digest: H256) -> Result<Signature, Error> {
+            let session = self.session.clone();
+            let label = self.label.clone();
+            let rs = tokio::task::spawn_blocking(move || {
+                session.sign(&label, SignMechanism::Ecdsa, digest.as_bytes())
+            })
+            .await
+            .map_err(|e| Error::Transaction(format!("HSM signing task failed: {}", e)))?
+            .map_err(|e| Error::Transaction(e.to_string()))?;
+
+            recoverable_signature(&rs, digest, self.address)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::signers::{LocalWallet, Signer};
+
+    const KEY: &str = "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+    fn der(r: &[u8], s: &[u8]) -> Vec<u8> {
+        let int = |v: &[u8]| {
+            let mut out = vec![0x02];
+            let pad = v[0] & 0x80 != 0;
+            out.push(v.len() as u8 + pad as u8);
+            if pad {
+                out.push(0);
+            }
+            out.extend_from_slice(v);
+            out
+        };
+        let body = [int(r), int(s)].concat();
+        [vec![0x30, body.len() as u8], body].concat()
+    }
+
+    #[test]
+    fn test_recoverable_signature_matches_local_wallet() {
+        let wallet: LocalWallet = KEY.parse().unwrap();
+        let digest = H256::from(ethers::utils::keccak256(b"apex"));
+        let expected = wallet.sign_hash(digest).unwrap();
+
+        let (signature, _) = wallet
+            .signer()
+            .sign_prehash_recoverable(digest.as_bytes())
+            .unwrap();
+        let bytes = signature.to_bytes();
+        let rs = der_to_rs(&der(&bytes[..32], &bytes[32..])).unwrap();
+
+        assert_eq!(
+            recoverable_signature(&rs, digest, wallet.address()).unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_high_s_is_normalized() {
+        let wallet: LocalWallet = KEY.parse().unwrap();
+        let digest = H256::from(ethers::utils::keccak256(b"high s"));
+        let expected = wallet.sign_hash(digest).unwrap();
+
+        // (r, n - s) is an equally valid signature that Ethereum rejects
+        let (signature, _) = wallet
+            .signer()
+            .sign_prehash_recoverable(digest.as_bytes())
+            .unwrap();
+        let (r, s) = signature.split_scalars();
+        let high = EcdsaSignature::from_scalars(r.to_bytes(), (-*s).to_bytes()).unwrap();
+
+        let signature = recoverable_signature(&high.to_bytes(), digest, wallet.address()).unwrap();
+        assert_eq!(signature, expected);
+
+        let other = LocalWallet::new(&mut ethers::core::rand::thread_rng());
+        assert!(recoverable_signature(&high.to_bytes(), digest, other.address()).is_err());
+    }
+
+    #[test]
+    fn test_address_from_spki() {
+        let wallet: LocalWallet = KEY.parse().unwrap();
+        let point = wallet.signer().verifying_key().to_encoded_point(false);
+
+        // secp256k1 SubjectPublicKeyInfo header, as returned by KMS
+        let mut spki = hex::decode("3056301006072a8648ce3d020106052b8104000a034200").unwrap();
+        spki.extend_from_slice(point.as_bytes());
+
+        assert_eq!(address_from_public_key(&spki).unwrap(), wallet.address());
+        assert_eq!(
+            address_from_public_key(point.as_bytes()).unwrap(),
+            wallet.address()
+        );
+        assert!(der_to_rs(&[0x30, 0x02, 0x02, 0x00]).is_err());
+    }
+
+    /// Remote signer stand-in returning DER, like most KMS APIs
+    struct DerSigner {
+        wallet: LocalWallet,
+        public_key: Vec<u8>,
+        scheme: SignatureScheme,
+    }
+
+    #[async_trait]
+    impl RemoteSigner for DerSigner {
+        fn public_key(&self) -> &[u8] {
+            &self.public_key
+        }
+
+        fn scheme(&self) -> SignatureScheme {
+            self.scheme
+        }
+
+        async fn sign(&self, message: &[u8]) -> apex_sdk_core::remote_signer::Result<Vec<u8>> {
+            let (signature, _) = self
+                .wallet
+                .signer()
+                .sign_prehash_recoverable(message)
+                .unwrap();
+            let bytes = signature.to_bytes();
+            Ok(der(&bytes[..32], &bytes[32..]))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_remote_digest_signer() {
+        let wallet: LocalWallet = KEY.parse().unwrap();
+        let public_key = wallet
+            .signer()
+            .verifying_key()
+            .to_encoded_point(false)
+            .as_bytes()
+            .to_vec();
+        let digest = H256::from(ethers::utils::keccak256(b"remote"));
+
+        let signer = RemoteDigestSigner::new(DerSigner {
+            wallet: wallet.clone(),
+            public_key: public_key.clone(),
+            scheme: SignatureScheme::EcdsaSecp256k1,
+        })
+        .unwrap();
+        assert_eq!(signer.address(), wallet.address());
+        assert_eq!(
+            signer.sign_digest(digest).await.unwrap(),
+            wallet.sign_hash(digest).unwrap()
+        );
+
+        assert!(RemoteDigestSigner::new(DerSigner {
+            wallet,
+            public_key,
+            scheme: SignatureScheme::Ed25519,
+        })
+        .is_err());
+    }
+}
@@ -0,0 +1,179 @@
+//! Sign-In with Ethereum (EIP-4361)
+//!
+//! The application creates a message with [`message`] and sends its text to
+//! the user, whose wallet signs it with `personal_sign` (or [`sign`] for
+//! wallets managed by the SDK). The application then checks the returned
+//! text and signature with [`verify`], which recovers the signer and
+//! validates the domain, nonce and validity window.
+//!
+//! Only externally owned accounts are supported; contract wallets (EIP-1271)
+//! need an on-chain `isValidSignature` call.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use apex_sdk_evm::{siwe, wallet::Wallet};
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let wallet = Wallet::new_random();
+//!
+//! // Server: issue a challenge
+//! let message = siwe::message(wallet.eth_address(), "example.com", "https://example.com", 1)
+//!     .with_statement("Sign in to Example");
+//! let nonce = message.nonce.clone();
+//! let text = message.to_string();
+//!
+//! // Client: sign it
+//! let signature = siwe::sign(&wallet, &text.parse()?).await?;
+//!
+//! // Server: verify the response
+//! let session = siwe::verify(&text, &signature, "example.com", &nonce)?;
+//! println!("Signed in as {}", session.address);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::wallet::Wallet;
+use crate::Error;
+use apex_sdk_core::auth::{AuthError, SignInMessage};
+use ethers::types::{Address as EthAddress, Signature};
+use ethers::utils::to_checksum;
+
+/// Namespace named in the message preamble
+pub const NAMESPACE: &str = "Ethereum";
+
+/// Create a sign-in message for `address`, issued now with a fresh nonce
+pub fn message(
+    address: EthAddress,
+    domain: impl Into<String>,
+    uri: impl Into<String>,
+    chain_id: u64,
+) -> SignInMessage {
+    SignInMessage::new(
+        NAMESPACE,
+        domain,
+        to_checksum(&address, None),
+        uri,
+        chain_id.to_string(),
+    )
+}
+
+/// Sign a message with `wallet` (EIP-191), returning the hex signature
+pub async fn sign(wallet: &Wallet, message: &SignInMessage) -> Result<String, Error> {
+    let address: EthAddress = message
+        .address
+        .parse()
+        .map_err(|e| Error::InvalidAddress(format!("Invalid address: {}", e)))?;
+    if address != wallet.eth_address() {
+        return Err(Error::InvalidAddress(format!(
+            "Message is for {}, not {}",
+            message.address,
+            wallet.address()
+        )));
+    }
+
+    let signature = wallet.sign_message(message.to_string()).await?;
+    Ok(format!("0x{}", signature))
+}
+
+/// Verify a signed message and its claims
+///
+/// `message` must be the exact text that was signed. Returns the parsed
+/// message if the signature recovers to its address and the domain, nonce
+/// and validity window check out.
+pub fn verify(
+    message: &str,
+    signature: &str,
+    domain: &str,
+    nonce: &str,
+) -> Result<SignInMessage, AuthError> {
+    let parsed: SignInMessage = message.parse()?;
+    if parsed.namespace != NAMESPACE {
+        return Err(AuthError::Parse(format!(
+            "expected an {} account, got {}",
+            NAMESPACE, parsed.namespace
+        )));
+    }
+    if parsed.chain_id.parse::<u64>().is_err() {
+        return Err(AuthError::Parse(format!(
+            "invalid chain ID {}",
+            parsed.chain_id
+        )));
+    }
+    let address: EthAddress = parsed
+        .address
+        .parse()
+        .map_err(|e| AuthError::Parse(format!("invalid address: {}", e)))?;
+
+    let signature = signature
+        .parse::<Signature>()
+        .map_err(|e| AuthError::InvalidSignature(e.to_string()))?;
+    signature
+        .verify(message, address)
+        .map_err(|e| AuthError::InvalidSignature(e.to_string()))?;
+
+    parsed.validate(domain, nonce)?;
+    Ok(parsed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[tokio::test]
+    async fn test_sign_and_verify() {
+        let wallet = Wallet::new_random();
+        let message = message(
+            wallet.eth_address(),
+            "example.com",
+            "https://example.com",
+            1,
+        )
+        .with_statement("Sign in to Example");
+        let text = message.to_string();
+        assert!(text.contains(&to_checksum(&wallet.eth_address(), None)));
+
+        let signature = sign(&wallet, &message).await.unwrap();
+        let verified = verify(&text, &signature, "example.com", &message.nonce).unwrap();
+        assert_eq!(verified, message);
+
+        assert!(matches!(
+            verify(&text, &signature, "example.com", "00000000"),
+            Err(AuthError::NonceMismatch)
+        ));
+        let tampered = text.replace("Sign in to Example", "Sign in to Evil");
+        assert!(matches!(
+            verify(&tampered, &signature, "example.com", &message.nonce),
+            Err(AuthError::InvalidSignature(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_rejects_other_signer_and_expired() {
+        let wallet = Wallet::new_random();
+        let other = Wallet::new_random();
+        let expired = message(
+            wallet.eth_address(),
+            "example.com",
+            "https://example.com",
+            1,
+        );
+        let expired = expired
+            .clone()
+            .with_expiration_time(expired.issued_at - Duration::minutes(1));
+
+        assert!(sign(&other, &expired).await.is_err());
+
+        let signature = sign(&wallet, &expired).await.unwrap();
+        assert!(matches!(
+            verify(
+                &expired.to_string(),
+                &signature,
+                "example.com",
+                &expired.nonce
+            ),
+            Err(AuthError::Expired)
+        ));
+    }
+}
@@ -4,21 +4,41 @@
 //! - Gas estimation (EIP-1559 and legacy)
 //! - Transaction signing
 //! - Transaction submission with retry logic
-//! - Transaction monitoring
-
-use crate::{wallet::Wallet, Error, ProviderType};
+//! - Nonce tracking for transactions sent back to back
+//! - Transaction monitoring and receipt waiting
+//! - Optional security pre-flight before submission
+
+use crate::fees::FeeOracle;
+use crate::nonce::NonceManager;
+use crate::offline::UnsignedTransaction;
+use crate::preflight::{SecurityPreflight, Severity};
+use crate::{rpc_error, wallet::Wallet, Error, ProviderType};
+use apex_sdk_core::performance::RateLimiter;
+use apex_sdk_types::{Fee, Receipt, TransactionEvent};
 use ethers::prelude::*;
 use ethers::types::{
     transaction::eip2718::TypedTransaction, Address as EthAddress, TransactionReceipt,
     TransactionRequest, H256, U256,
 };
+use std::sync::Arc;
 use std::time::Duration;
 
+/// How long [`TransactionExecutor::wait_for_receipt`] waits by default
+pub const DEFAULT_RECEIPT_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// How often [`TransactionExecutor::wait_for_receipt`] polls by default
+pub const DEFAULT_RECEIPT_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Safety margin applied to estimated gas limits by default, 20%
+pub const DEFAULT_GAS_LIMIT_MULTIPLIER: f64 = 1.2;
+
 /// Configuration for gas estimation and pricing
 #[derive(Debug, Clone)]
 pub struct GasConfig {
     /// Gas limit multiplier for safety margin (default: 1.2 = 20% buffer)
     pub gas_limit_multiplier: f64,
+    /// Fixed gas limit; skips estimation and the multiplier when set
+    pub gas_limit: Option<U256>,
     /// Max priority fee per gas (EIP-1559) in gwei
     pub max_priority_fee_per_gas: Option<U256>,
     /// Max fee per gas (EIP-1559) in gwei
@@ -30,7 +50,8 @@ pub struct GasConfig {
 impl Default for GasConfig {
     fn default() -> Self {
         Self {
-            gas_limit_multiplier: 1.2,
+            gas_limit_multiplier: DEFAULT_GAS_LIMIT_MULTIPLIER,
+            gas_limit: None,
             max_priority_fee_per_gas: None,
             max_fee_per_gas: None,
             gas_price: None,
@@ -105,10 +126,17 @@ impl GasEstimate {
 }
 
 /// Transaction executor with gas estimation and retry logic
+#[derive(Clone)]
 pub struct TransactionExecutor {
     provider: ProviderType,
     gas_config: GasConfig,
     retry_config: RetryConfig,
+    preflight: Option<(Arc<SecurityPreflight>, Severity)>,
+    limiter: Option<Arc<RateLimiter>>,
+    fee_oracle: Option<FeeOracle>,
+    nonces: Arc<NonceManager>,
+    receipt_timeout: Duration,
+    receipt_poll_interval: Duration,
 }
 
 impl TransactionExecutor {
@@ -118,6 +146,12 @@ impl TransactionExecutor {
             provider,
             gas_config: GasConfig::default(),
             retry_config: RetryConfig::default(),
+            preflight: None,
+            limiter: None,
+            fee_oracle: None,
+            nonces: Arc::new(NonceManager::new()),
+            receipt_timeout: DEFAULT_RECEIPT_TIMEOUT,
+            receipt_poll_interval: DEFAULT_RECEIPT_POLL_INTERVAL,
         }
     }
 
@@ -133,6 +167,48 @@ impl TransactionExecutor {
         self
     }
 
+    /// Throttle RPC requests through `limiter`
+    pub fn with_rate_limiter(mut self, limiter: Option<Arc<RateLimiter>>) -> Self {
+        self.limiter = limiter;
+        self
+    }
+
+    /// Take the base fee and default priority fee from `oracle`'s standard
+    /// suggestion instead of the latest block and a fixed 2 gwei tip
+    pub fn with_fee_oracle(mut self, oracle: FeeOracle) -> Self {
+        self.fee_oracle = Some(oracle);
+        self
+    }
+
+    /// Share nonce reservations with other executors for the same endpoint
+    pub fn with_nonce_manager(mut self, nonces: Arc<NonceManager>) -> Self {
+        self.nonces = nonces;
+        self
+    }
+
+    /// Set how long [`wait_for_receipt`](Self::wait_for_receipt) waits
+    pub fn with_receipt_timeout(mut self, timeout: Duration) -> Self {
+        self.receipt_timeout = timeout;
+        self
+    }
+
+    /// Set how often [`wait_for_receipt`](Self::wait_for_receipt) polls
+    pub fn with_receipt_poll_interval(mut self, interval: Duration) -> Self {
+        self.receipt_poll_interval = interval;
+        self
+    }
+
+    async fn throttle(&self) -> Result<(), Error> {
+        crate::throttle(self.limiter.as_deref()).await
+    }
+
+    /// Simulate transactions before sending and refuse those with a finding
+    /// of at least `block_at` severity
+    pub fn with_preflight(mut self, preflight: SecurityPreflight, block_at: Severity) -> Self {
+        self.preflight = Some((Arc::new(preflight), block_at));
+        self
+    }
+
     /// Estimate gas for a transaction
     ///
     /// This handles both EIP-1559 (London fork) and legacy transactions
@@ -158,13 +234,8 @@ impl TransactionExecutor {
             tx = tx.data(tx_data);
         }
 
-        // Estimate gas limit
-        let estimated_gas = self.estimate_gas_limit(&tx).await?;
-
-        // Apply safety multiplier
-        let gas_limit = U256::from(
-            (estimated_gas.as_u128() as f64 * self.gas_config.gas_limit_multiplier) as u128,
-        );
+        // Estimate gas limit with a safety margin
+        let gas_limit = self.estimate_gas_limit(&tx.into()).await?;
 
         tracing::debug!(
             "Estimated gas limit: {} (with {}% buffer)",
@@ -188,20 +259,17 @@ impl TransactionExecutor {
         })
     }
 
-    /// Estimate gas limit for a transaction
-    async fn estimate_gas_limit(&self, tx: &TransactionRequest) -> Result<U256, Error> {
-        let typed_tx: TypedTransaction = tx.clone().into();
-
-        match &self.provider {
-            ProviderType::Http(p) => p
-                .estimate_gas(&typed_tx, None)
-                .await
-                .map_err(|e| Error::Transaction(format!("Gas estimation failed: {}", e))),
-            ProviderType::Ws(p) => p
-                .estimate_gas(&typed_tx, None)
-                .await
-                .map_err(|e| Error::Transaction(format!("Gas estimation failed: {}", e))),
+    /// Estimate gas limit for a transaction, including the safety multiplier,
+    /// unless a fixed limit is configured
+    async fn estimate_gas_limit(&self, tx: &TypedTransaction) -> Result<U256, Error> {
+        if let Some(limit) = self.gas_config.gas_limit {
+            return Ok(limit);
         }
+        self.throttle().await?;
+        let estimated_gas = self.provider.estimate_gas(tx).await?;
+        Ok(U256::from(
+            (estimated_gas.as_u128() as f64 * self.gas_config.gas_limit_multiplier) as u128,
+        ))
     }
 
     /// Estimate gas price (handles both EIP-1559 and legacy)
@@ -229,13 +297,26 @@ impl TransactionExecutor {
 
     /// Get EIP-1559 fee estimates
     async fn get_eip1559_fees(&self) -> Result<(U256, U256), Error> {
+        if let (None, Some(oracle)) = (self.gas_config.max_priority_fee_per_gas, &self.fee_oracle) {
+            match oracle.estimate().await {
+                Ok(estimate) => {
+                    return Ok((
+                        estimate.base_fee_per_gas,
+                        estimate.standard.max_priority_fee_per_gas,
+                    ))
+                }
+                Err(e) => tracing::debug!("Fee oracle unavailable, using latest block: {}", e),
+            }
+        }
+
         // Get base fee from latest block
+        self.throttle().await?;
         let base_fee = match &self.provider {
             ProviderType::Http(p) => {
                 let block = p
                     .get_block(BlockNumber::Latest)
                     .await
-                    .map_err(|e| Error::Connection(format!("Failed to get block: {}", e)))?
+                    .map_err(|e| rpc_error("Failed to get block", e, Error::Connection))?
                     .ok_or_else(|| Error::Connection("No latest block".to_string()))?;
 
                 block
@@ -246,7 +327,7 @@ impl TransactionExecutor {
                 let block = p
                     .get_block(BlockNumber::Latest)
                     .await
-                    .map_err(|e| Error::Connection(format!("Failed to get block: {}", e)))?
+                    .map_err(|e| rpc_error("Failed to get block", e, Error::Connection))?
                     .ok_or_else(|| Error::Connection("No latest block".to_string()))?;
 
                 block
@@ -270,15 +351,16 @@ impl TransactionExecutor {
             return Ok(price);
         }
 
+        self.throttle().await?;
         match &self.provider {
             ProviderType::Http(p) => p
                 .get_gas_price()
                 .await
-                .map_err(|e| Error::Connection(format!("Failed to get gas price: {}", e))),
+                .map_err(|e| rpc_error("Failed to get gas price", e, Error::Connection)),
             ProviderType::Ws(p) => p
                 .get_gas_price()
                 .await
-                .map_err(|e| Error::Connection(format!("Failed to get gas price: {}", e))),
+                .map_err(|e| rpc_error("Failed to get gas price", e, Error::Connection)),
         }
     }
 
@@ -301,8 +383,7 @@ impl TransactionExecutor {
                 .await?
         };
 
-        // Get nonce
-        let nonce = self.get_transaction_count(from).await?;
+        let nonce = self.next_nonce(from).await?;
 
         // Build transaction based on EIP-1559 support
         let mut tx = if gas_est.is_eip1559 {
@@ -354,18 +435,147 @@ impl TransactionExecutor {
         Ok(tx)
     }
 
-    /// Get transaction count (nonce) for an address
-    async fn get_transaction_count(&self, address: EthAddress) -> Result<U256, Error> {
-        match &self.provider {
+    /// Reserve the next nonce for `address`
+    async fn next_nonce(&self, address: EthAddress) -> Result<U256, Error> {
+        let count = self.pending_count(address).await?;
+        Ok(self.nonces.reserve(address, count))
+    }
+
+    /// The node's pending transaction count of `address`
+    async fn pending_count(&self, address: EthAddress) -> Result<U256, Error> {
+        let pending = Some(BlockNumber::Pending.into());
+        self.throttle().await?;
+        let count = match &self.provider {
             ProviderType::Http(p) => p
-                .get_transaction_count(address, None)
+                .get_transaction_count(address, pending)
                 .await
-                .map_err(|e| Error::Connection(format!("Failed to get nonce: {}", e))),
+                .map_err(|e| rpc_error("Failed to get nonce", e, Error::Connection))?,
             ProviderType::Ws(p) => p
-                .get_transaction_count(address, None)
+                .get_transaction_count(address, pending)
                 .await
-                .map_err(|e| Error::Connection(format!("Failed to get nonce: {}", e))),
+                .map_err(|e| rpc_error("Failed to get nonce", e, Error::Connection))?,
+        };
+        Ok(count)
+    }
+
+    /// The nonce manager shared by this executor
+    pub fn nonce_manager(&self) -> &Arc<NonceManager> {
+        &self.nonces
+    }
+
+    /// Release the nonce reserved for `tx` from `address`, e.g. when a
+    /// transaction from [`build_transaction`](Self::build_transaction) will
+    /// not be sent, so the next transaction takes it
+    pub fn release_nonce(&self, address: EthAddress, tx: &TypedTransaction) {
+        if let Some(&nonce) = tx.nonce() {
+            self.nonces.release(address, nonce);
+        }
+    }
+
+    /// Forget all nonces reserved for `address`
+    pub fn release_nonces(&self, address: EthAddress) {
+        self.nonces.reset(address);
+    }
+
+    /// Restart nonces of `address` from the node's pending count, e.g. after
+    /// transactions were dropped from the pool
+    pub async fn resync_nonces(&self, address: EthAddress) -> Result<(), Error> {
+        let count = self.pending_count(address).await?;
+        self.nonces.resync(address, count)?;
+        Ok(())
+    }
+
+    /// Send `value` wei to `to` and wait for the receipt
+    ///
+    /// The transaction is EIP-1559 where the chain supports it and legacy
+    /// otherwise. A reverted transaction still returns its receipt; check
+    /// `status`.
+    pub async fn send_transfer(
+        &self,
+        wallet: &Wallet,
+        to: EthAddress,
+        value: U256,
+    ) -> Result<TransactionReceipt, Error> {
+        let hash = self.send_transaction(wallet, to, value, None).await?;
+        self.wait_for_receipt(hash, 1).await
+    }
+
+    /// Fill in, sign and send a transaction, then wait for the receipt
+    ///
+    /// Fields left unset are filled in: the sender from `wallet`, the nonce,
+    /// the gas limit, the gas price of legacy and EIP-2930 transactions or
+    /// the fee caps of EIP-1559 ones, and the chain ID.
+    pub async fn send_raw(
+        &self,
+        wallet: &Wallet,
+        tx: impl Into<TypedTransaction>,
+    ) -> Result<TransactionReceipt, Error> {
+        let tx = self
+            .fill_transaction(wallet.eth_address(), wallet.chain_id(), tx.into())
+            .await?;
+        let hash = self.send_raw_transaction(wallet, tx).await?;
+        self.wait_for_receipt(hash, 1).await
+    }
+
+    /// Fill in a transaction from `from` for signing elsewhere, e.g. on an
+    /// HSM, like [`send_raw`](Self::send_raw) does
+    ///
+    /// The nonce is reserved; call [`release_nonce`](Self::release_nonce)
+    /// if the transaction will not be broadcast.
+    pub async fn build_unsigned(
+        &self,
+        from: EthAddress,
+        tx: impl Into<TypedTransaction>,
+        chain_id: u64,
+    ) -> Result<UnsignedTransaction, Error> {
+        let tx = self
+            .fill_transaction(from, Some(chain_id), tx.into())
+            .await?;
+        UnsignedTransaction::new(tx)
+    }
+
+    /// Set the sender of `tx` and fill in its unset fields
+    async fn fill_transaction(
+        &self,
+        from: EthAddress,
+        chain_id: Option<u64>,
+        mut tx: TypedTransaction,
+    ) -> Result<TypedTransaction, Error> {
+        tx.set_from(from);
+
+        if tx.gas().is_none() {
+            let gas = self.estimate_gas_limit(&tx).await?;
+            tx.set_gas(gas);
+        }
+        match &mut tx {
+            TypedTransaction::Eip1559(inner) => {
+                if inner.max_fee_per_gas.is_none() || inner.max_priority_fee_per_gas.is_none() {
+                    let (base_fee, default_priority_fee) = self.get_eip1559_fees().await?;
+                    let priority_fee = *inner
+                        .max_priority_fee_per_gas
+                        .get_or_insert(default_priority_fee);
+                    inner
+                        .max_fee_per_gas
+                        .get_or_insert(base_fee * 2 + priority_fee);
+                }
+            }
+            _ => {
+                if tx.gas_price().is_none() {
+                    let gas_price = self.get_legacy_gas_price().await?;
+                    tx.set_gas_price(gas_price);
+                }
+            }
         }
+        if tx.chain_id().is_none() {
+            if let Some(chain_id) = chain_id {
+                tx.set_chain_id(chain_id);
+            }
+        }
+        if tx.nonce().is_none() {
+            let nonce = self.next_nonce(from).await?;
+            tx.set_nonce(nonce);
+        }
+        Ok(tx)
     }
 
     /// Send a signed transaction with retry logic
@@ -389,11 +599,35 @@ impl TransactionExecutor {
         wallet: &Wallet,
         tx: TypedTransaction,
     ) -> Result<H256, Error> {
+        let result = self.send_with_retry(wallet, &tx).await;
+        if let Some(&nonce) = tx.nonce() {
+            match &result {
+                Ok(_) => self.nonces.confirm(wallet.eth_address(), nonce),
+                // The nonce was not used; let the next transaction take it
+                Err(_) => self.nonces.release(wallet.eth_address(), nonce),
+            }
+        }
+        result
+    }
+
+    /// Pre-flight `tx`, then send it, retrying failed attempts
+    async fn send_with_retry(&self, wallet: &Wallet, tx: &TypedTransaction) -> Result<H256, Error> {
+        if let Some((preflight, block_at)) = &self.preflight {
+            let report = preflight.analyze(tx).await?;
+            if report.exceeds(*block_at) {
+                tracing::warn!("Transaction blocked by security pre-flight: {}", report);
+                return Err(Error::Transaction(format!(
+                    "Blocked by security pre-flight: {}",
+                    report
+                )));
+            }
+        }
+
         let mut attempts = 0;
         let mut backoff = Duration::from_millis(self.retry_config.initial_backoff_ms);
 
         loop {
-            match self.try_send_transaction(wallet, &tx).await {
+            match self.try_send_transaction(wallet, tx).await {
                 Ok(tx_hash) => {
                     tracing::info!("Transaction sent successfully: {:?}", tx_hash);
                     return Ok(tx_hash);
@@ -416,7 +650,7 @@ impl TransactionExecutor {
                         backoff
                     };
 
-                    tokio::time::sleep(delay).await;
+                    crate::runtime::sleep(delay).await;
 
                     // Exponential backoff
                     backoff = Duration::from_millis(std::cmp::min(
@@ -432,6 +666,28 @@ impl TransactionExecutor {
         }
     }
 
+    /// Replace a pending transaction with a copy paying `percent` more in fees
+    ///
+    /// The replacement keeps the nonce of `tx`, so at most one of the two is
+    /// mined. Returns the replacement and its hash.
+    pub async fn replace_transaction(
+        &self,
+        wallet: &Wallet,
+        tx: &TypedTransaction,
+        percent: u64,
+    ) -> Result<(TypedTransaction, H256), Error> {
+        if tx.nonce().is_none() {
+            return Err(Error::Transaction(
+                "Cannot replace a transaction without a nonce".to_string(),
+            ));
+        }
+        let replacement = bump_fees(tx, percent);
+        let hash = self
+            .send_raw_transaction(wallet, replacement.clone())
+            .await?;
+        Ok((replacement, hash))
+    }
+
     /// Try to send a transaction (single attempt)
     async fn try_send_transaction(
         &self,
@@ -445,23 +701,20 @@ impl TransactionExecutor {
         let signed_tx = tx.rlp_signed(&signature);
 
         // Send raw transaction and get pending transaction
+        self.throttle().await?;
         let tx_hash = match &self.provider {
             ProviderType::Http(p) => {
                 let pending = p
                     .send_raw_transaction(signed_tx.clone())
                     .await
-                    .map_err(|e| {
-                        Error::Transaction(format!("Failed to send transaction: {}", e))
-                    })?;
+                    .map_err(|e| rpc_error("Failed to send transaction", e, Error::Transaction))?;
                 *pending
             }
             ProviderType::Ws(p) => {
                 let pending = p
                     .send_raw_transaction(signed_tx.clone())
                     .await
-                    .map_err(|e| {
-                        Error::Transaction(format!("Failed to send transaction: {}", e))
-                    })?;
+                    .map_err(|e| rpc_error("Failed to send transaction", e, Error::Transaction))?;
                 *pending
             }
         };
@@ -481,15 +734,16 @@ impl TransactionExecutor {
             tx_hash
         );
 
+        self.throttle().await?;
         let receipt = match &self.provider {
             ProviderType::Http(p) => p
                 .get_transaction_receipt(tx_hash)
                 .await
-                .map_err(|e| Error::Transaction(format!("Failed to get receipt: {}", e)))?,
+                .map_err(|e| rpc_error("Failed to get receipt", e, Error::Transaction))?,
             ProviderType::Ws(p) => p
                 .get_transaction_receipt(tx_hash)
                 .await
-                .map_err(|e| Error::Transaction(format!("Failed to get receipt: {}", e)))?,
+                .map_err(|e| rpc_error("Failed to get receipt", e, Error::Transaction))?,
         };
 
         if let Some(ref r) = receipt {
@@ -502,6 +756,96 @@ impl TransactionExecutor {
 
         Ok(receipt)
     }
+
+    /// Poll until `tx_hash` is mined with `confirmations` blocks on top,
+    /// counting its own
+    ///
+    /// Fails once the receipt timeout elapses.
+    pub async fn wait_for_receipt(
+        &self,
+        tx_hash: H256,
+        confirmations: u64,
+    ) -> Result<TransactionReceipt, Error> {
+        let deadline = crate::runtime::Instant::now() + self.receipt_timeout;
+        loop {
+            if let Some(receipt) = self.wait_for_confirmation(tx_hash, 1).await? {
+                let mined = receipt.block_number.unwrap_or_default().as_u64();
+                self.throttle().await?;
+                let head = self.provider.get_block_number().await?.as_u64();
+                if head + 1 >= mined + confirmations {
+                    return Ok(receipt);
+                }
+            }
+            if crate::runtime::Instant::now() >= deadline {
+                return Err(Error::Transaction(format!(
+                    "Transaction {:?} not confirmed within {:?}",
+                    tx_hash, self.receipt_timeout
+                )));
+            }
+            crate::runtime::sleep(self.receipt_poll_interval).await;
+        }
+    }
+}
+
+/// Effective fee paid by a mined transaction (gas used * effective gas price)
+pub fn receipt_fee(receipt: &TransactionReceipt) -> Option<Fee> {
+    let gas_used = receipt.gas_used?;
+    let gas_price = receipt.effective_gas_price?;
+    Some(Fee::from_gas(gas_used.as_u64(), gas_price.as_u128()))
+}
+
+/// Convert the logs of a receipt into unified transaction events
+pub fn receipt_events(receipt: &TransactionReceipt) -> Vec<TransactionEvent> {
+    receipt
+        .logs
+        .iter()
+        .map(|log| TransactionEvent::EvmLog {
+            address: format!("{:?}", log.address),
+            topics: log.topics.iter().map(|t| format!("{:?}", t)).collect(),
+            data: format!("0x{}", hex::encode(&log.data)),
+            log_index: log.log_index.map(|i| i.as_u64()),
+        })
+        .collect()
+}
+
+/// Convert a mined receipt into a unified [`Receipt`]
+///
+/// Receipts only record that a transaction reverted, not why, so the error
+/// of a reverted transaction is `"Transaction reverted"`.
+pub fn to_receipt(receipt: &TransactionReceipt) -> Receipt {
+    let mut unified = Receipt::new(format!("{:?}", receipt.transaction_hash));
+    unified.block_number = receipt.block_number.map(|n| n.as_u64());
+    unified.block_hash = receipt.block_hash.map(|h| format!("{:?}", h));
+    if receipt.status.is_some_and(|status| status.is_zero()) {
+        unified.error = Some("Transaction reverted".to_string());
+    }
+    unified.gas_used = receipt.gas_used.map(|g| g.as_u64());
+    unified.effective_gas_price = receipt.effective_gas_price.map(|p| p.as_u128());
+    unified.fee = receipt_fee(receipt);
+    unified.events = receipt_events(receipt);
+    unified.contract_address = receipt.contract_address.map(|a| format!("{:?}", a));
+    unified
+}
+
+/// Smallest fee increase, in percent, nodes accept for a replacement
+pub const MIN_REPLACEMENT_BUMP_PERCENT: u64 = 10;
+
+/// Copy of `tx` with its fees raised by `percent` (at least
+/// [`MIN_REPLACEMENT_BUMP_PERCENT`]), rounded up
+pub fn bump_fees(tx: &TypedTransaction, percent: u64) -> TypedTransaction {
+    let percent = percent.max(MIN_REPLACEMENT_BUMP_PERCENT);
+    let bump = |fee: U256| (fee * (100 + percent) + 99) / 100;
+
+    let mut tx = tx.clone();
+    match &mut tx {
+        TypedTransaction::Legacy(inner) => inner.gas_price = inner.gas_price.map(bump),
+        TypedTransaction::Eip2930(inner) => inner.tx.gas_price = inner.tx.gas_price.map(bump),
+        TypedTransaction::Eip1559(inner) => {
+            inner.max_fee_per_gas = inner.max_fee_per_gas.map(bump);
+            inner.max_priority_fee_per_gas = inner.max_priority_fee_per_gas.map(bump);
+        }
+    }
+    tx
 }
 
 /// Helper function to format wei to gwei
@@ -550,6 +894,30 @@ mod tests {
         assert!(config.use_jitter);
     }
 
+    #[test]
+    fn test_bump_fees() {
+        let legacy: TypedTransaction = TransactionRequest::new()
+            .gas_price(1_000_000_000u64)
+            .nonce(7)
+            .into();
+        let bumped = bump_fees(&legacy, 5);
+        assert_eq!(bumped.gas_price(), Some(U256::from(1_100_000_000u64)));
+        assert_eq!(bumped.nonce(), Some(&U256::from(7)));
+
+        let eip1559 = TypedTransaction::Eip1559(
+            Eip1559TransactionRequest::new()
+                .max_fee_per_gas(100u64)
+                .max_priority_fee_per_gas(3u64),
+        );
+        match bump_fees(&eip1559, 25) {
+            TypedTransaction::Eip1559(tx) => {
+                assert_eq!(tx.max_fee_per_gas, Some(U256::from(125)));
+                assert_eq!(tx.max_priority_fee_per_gas, Some(U256::from(4)));
+            }
+            other => panic!("Unexpected transaction type: {:?}", other),
+        }
+    }
+
     #[test]
     fn test_format_gwei() {
         let wei = U256::from(1_000_000_000u64);
@@ -562,6 +930,61 @@ mod tests {
         assert_eq!(format_gwei(wei), "2.54");
     }
 
+    #[test]
+    fn test_receipt_fee_and_events() {
+        let log = Log {
+            address: "0x742D35Cc6634C0532925A3B844bC9e7595f0bEB7"
+                .parse()
+                .unwrap(),
+            topics: vec![H256::repeat_byte(0x11)],
+            data: vec![0xde, 0xad].into(),
+            log_index: Some(U256::from(3)),
+            ..Default::default()
+        };
+        let receipt = TransactionReceipt {
+            gas_used: Some(U256::from(21_000)),
+            effective_gas_price: Some(U256::from(1_000_000_000u64)),
+            logs: vec![log],
+            ..Default::default()
+        };
+
+        let fee = receipt_fee(&receipt).unwrap();
+        assert_eq!(fee.amount, 21_000_000_000_000);
+        assert_eq!(fee.gas_used, Some(21_000));
+
+        let events = receipt_events(&receipt);
+        assert_eq!(events.len(), 1);
+        match &events[0] {
+            TransactionEvent::EvmLog {
+                address,
+                topics,
+                data,
+                log_index,
+            } => {
+                assert_eq!(address, "0x742d35cc6634c0532925a3b844bc9e7595f0beb7");
+                assert_eq!(topics[0], format!("0x{}", "11".repeat(32)));
+                assert_eq!(data, "0xdead");
+                assert_eq!(*log_index, Some(3));
+            }
+            other => panic!("Expected EVM log, got {:?}", other),
+        }
+
+        let pending = TransactionReceipt::default();
+        assert!(receipt_fee(&pending).is_none());
+
+        let unified = to_receipt(&receipt);
+        assert!(unified.is_success());
+        assert_eq!(unified.fee, Some(fee));
+        assert_eq!(unified.effective_gas_price, Some(1_000_000_000));
+        assert_eq!(unified.events, events);
+
+        let reverted = to_receipt(&TransactionReceipt {
+            status: Some(0u64.into()),
+            ..receipt
+        });
+        assert_eq!(reverted.error.as_deref(), Some("Transaction reverted"));
+    }
+
     #[test]
     fn test_format_eth() {
         let wei = U256::from(10_u64.pow(18));
@@ -5,21 +5,84 @@
 //! - Private key management
 //! - Transaction signing
 //! - Message signing (EIP-191, EIP-712)
+//! - Loading keys from a [`SecretStore`]
+//! - Importing and exporting V3 JSON keystores (geth, ethers, MetaMask)
+//! - Non-exportable keys in a KMS or HSM via [`DigestSigner`]
 
+use crate::signer::DigestSigner;
 use crate::Error;
+use apex_sdk_core::secret::{SecretBytes, SecretStore};
 use ethers::prelude::*;
 use ethers::signers::{coins_bip39::English, LocalWallet, Signer};
 use ethers::types::{
     transaction::eip2718::TypedTransaction, transaction::eip712::Eip712, Address as EthAddress,
     Signature,
 };
+use serde::{Deserialize, Serialize};
 use std::str::FromStr;
+use std::sync::Arc;
+use zeroize::Zeroizing;
+
+/// Key derivation function protecting a V3 keystore
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeystoreKdf {
+    /// scrypt with cost `2^log_n`, block size `r` and parallelism `p`
+    Scrypt { log_n: u8, r: u32, p: u32 },
+    /// PBKDF2-HMAC-SHA256 with `c` iterations
+    Pbkdf2 { c: u32 },
+}
+
+impl Default for KeystoreKdf {
+    /// geth's standard scrypt parameters (N = 2^18, r = 8, p = 1)
+    fn default() -> Self {
+        KeystoreKdf::Scrypt {
+            log_n: 18,
+            r: 8,
+            p: 1,
+        }
+    }
+}
+
+/// Largest scrypt cost accepted from a keystore file (N = 2^20, about 1 GiB
+/// at r = 8); geth's standard keystores use 2^18
+const MAX_KEYSTORE_SCRYPT_LOG_N: u8 = 20;
+
+/// Largest `r * p` accepted from a keystore file, enough for both geth's
+/// (r = 8, p = 1) and eth-keyfile's (r = 1, p = 8) defaults
+const MAX_KEYSTORE_SCRYPT_RP: u64 = 8;
+
+/// Most PBKDF2 iterations accepted from a keystore file; geth uses 262,144
+const MAX_KEYSTORE_PBKDF2_ROUNDS: u32 = 10_000_000;
+
+/// Where a wallet's key lives
+#[derive(Clone)]
+enum Key {
+    /// Private key in process memory, shared by clones and zeroed on drop
+    Local {
+        wallet: Arc<LocalWallet>,
+        chain_id: u64,
+    },
+    /// Key held by a KMS or HSM
+    Remote {
+        signer: Arc<dyn DigestSigner>,
+        chain_id: u64,
+    },
+}
+
+impl Key {
+    fn local(wallet: LocalWallet) -> Self {
+        Key::Local {
+            chain_id: wallet.chain_id(),
+            wallet: Arc::new(wallet),
+        }
+    }
+}
 
 /// Wallet for managing EVM accounts and signing transactions
 #[derive(Clone)]
 pub struct Wallet {
-    /// The underlying ethers wallet
-    inner: LocalWallet,
+    /// The underlying key
+    inner: Key,
     /// The address of this wallet
     address: EthAddress,
 }
@@ -40,7 +103,10 @@ impl Wallet {
 
         tracing::info!("Created new random wallet: {}", address);
 
-        Self { inner, address }
+        Self {
+            inner: Key::local(inner),
+            address,
+        }
     }
 
     /// Create a wallet from a private key (hex string with or without 0x prefix)
@@ -66,11 +132,17 @@ impl Wallet {
 
         tracing::info!("Loaded wallet from private key: {}", address);
 
-        Ok(Self { inner, address })
+        Ok(Self {
+            inner: Key::local(inner),
+            address,
+        })
     }
 
     /// Create a wallet from a mnemonic phrase
     ///
+    /// The key is derived at the BIP-44 path `m/44'/60'/0'/0/{index}`, as
+    /// MetaMask and hardware wallets do.
+    ///
     /// # Arguments
     /// * `mnemonic` - The BIP-39 mnemonic phrase
     /// * `index` - The account index (default 0 for first account)
@@ -101,16 +173,264 @@ impl Wallet {
         );
 
         Ok(Self {
-            inner: wallet,
+            inner: Key::local(wallet),
             address,
         })
     }
 
+    /// Load a wallet from a V3 JSON keystore, as written by geth, ethers,
+    /// MetaMask and most other Ethereum tools
+    ///
+    /// Both scrypt and PBKDF2 keystores are supported. Keystores are
+    /// rejected when scrypt's N exceeds 2^20 or r * p exceeds 8, PBKDF2
+    /// runs more than 10,000,000 rounds, or the derived key is not 32
+    /// bytes, so a crafted file cannot make the import use unbounded
+    /// memory or CPU.
+    pub fn from_keystore_v3(json: &str, password: &str) -> Result<Self, Error> {
+        let keystore: KeystoreV3 = serde_json::from_str(json)
+            .map_err(|e| Error::Other(format!("Invalid V3 keystore: {}", e)))?;
+        if keystore.version != 3 {
+            return Err(Error::Other(format!(
+                "Unsupported keystore version: {}",
+                keystore.version
+            )));
+        }
+        let crypto = &keystore.crypto;
+        if crypto.cipher != "aes-128-ctr" {
+            return Err(Error::Other(format!(
+                "Unsupported keystore cipher: {}",
+                crypto.cipher
+            )));
+        }
+
+        let kdf = match crypto.kdf.as_str() {
+            "scrypt" => match (crypto.kdfparams.n, crypto.kdfparams.r, crypto.kdfparams.p) {
+                (Some(n), Some(r), Some(p))
+                    if n.is_power_of_two()
+                        && n.trailing_zeros() <= MAX_KEYSTORE_SCRYPT_LOG_N as u32
+                        && (r as u64) * (p as u64) <= MAX_KEYSTORE_SCRYPT_RP =>
+                {
+                    KeystoreKdf::Scrypt {
+                        log_n: n.trailing_zeros() as u8,
+                        r,
+                        p,
+                    }
+                }
+                _ => {
+                    return Err(Error::Other(
+                        "Invalid keystore scrypt parameters".to_string(),
+                    ))
+                }
+            },
+            "pbkdf2" => match (crypto.kdfparams.c, crypto.kdfparams.prf.as_deref()) {
+                (Some(c), Some("hmac-sha256")) if c <= MAX_KEYSTORE_PBKDF2_ROUNDS => {
+                    KeystoreKdf::Pbkdf2 { c }
+                }
+                _ => {
+                    return Err(Error::Other(
+                        "Unsupported keystore PBKDF2 parameters".to_string(),
+                    ))
+                }
+            },
+            other => return Err(Error::Other(format!("Unsupported keystore KDF: {}", other))),
+        };
+
+        let salt = keystore_hex("salt", &crypto.kdfparams.salt)?;
+        let iv = keystore_hex("iv", &crypto.cipherparams.iv)?;
+        let mut key = Zeroizing::new(keystore_hex("ciphertext", &crypto.ciphertext)?);
+        let mac = keystore_hex("mac", &crypto.mac)?;
+        if iv.len() != 16 || crypto.kdfparams.dklen != 32 {
+            return Err(Error::Other("Invalid keystore parameters".to_string()));
+        }
+
+        let derived = derive_keystore_key(password, &salt, kdf)?;
+        if ethers::utils::keccak256([&derived[16..32], &key[..]].concat()) != mac[..] {
+            return Err(Error::Other("Wrong keystore password".to_string()));
+        }
+        aes_128_ctr(&derived[..16], &iv, &mut key);
+
+        let wallet = Self::from_private_key(&Zeroizing::new(hex::encode(&key[..])))?;
+        if let Some(address) = &keystore.address {
+            let address = EthAddress::from_str(address.trim_start_matches("0x"))
+                .map_err(|e| Error::InvalidAddress(format!("Invalid keystore address: {}", e)))?;
+            if address != wallet.address {
+                return Err(Error::Other(format!(
+                    "Keystore address {:?} does not match its key",
+                    address
+                )));
+            }
+        }
+        Ok(wallet)
+    }
+
+    /// Export the wallet as a V3 JSON keystore encrypted with `password`,
+    /// using geth's standard scrypt parameters
+    ///
+    /// Fails for wallets backed by a remote signer.
+    pub fn to_keystore_v3(&self, password: &str) -> Result<String, Error> {
+        self.to_keystore_v3_with_kdf(password, KeystoreKdf::default())
+    }
+
+    /// Export the wallet as a V3 JSON keystore protected by `kdf`
+    pub fn to_keystore_v3_with_kdf(
+        &self,
+        password: &str,
+        kdf: KeystoreKdf,
+    ) -> Result<String, Error> {
+        use rand::RngCore;
+
+        let mut ciphertext = Zeroizing::new(self.local()?.signer().to_bytes().to_vec());
+
+        let mut rng = rand::thread_rng();
+        let mut salt = [0u8; 32];
+        let mut iv = [0u8; 16];
+        let mut id = [0u8; 16];
+        rng.fill_bytes(&mut salt);
+        rng.fill_bytes(&mut iv);
+        rng.fill_bytes(&mut id);
+
+        let derived = derive_keystore_key(password, &salt, kdf)?;
+        aes_128_ctr(&derived[..16], &iv, &mut ciphertext);
+        let mac = ethers::utils::keccak256([&derived[16..32], &ciphertext[..]].concat());
+
+        let kdfparams = match kdf {
+            KeystoreKdf::Scrypt { log_n, r, p } => KdfParams {
+                dklen: 32,
+                salt: hex::encode(salt),
+                n: Some(1 << log_n),
+                r: Some(r),
+                p: Some(p),
+                c: None,
+                prf: None,
+            },
+            KeystoreKdf::Pbkdf2 { c } => KdfParams {
+                dklen: 32,
+                salt: hex::encode(salt),
+                n: None,
+                r: None,
+                p: None,
+                c: Some(c),
+                prf: Some("hmac-sha256".to_string()),
+            },
+        };
+        let keystore = KeystoreV3 {
+            version: 3,
+            id: Some(uuid_v4(id)),
+            address: Some(hex::encode(self.address)),
+            crypto: KeystoreCrypto {
+                cipher: "aes-128-ctr".to_string(),
+                ciphertext: hex::encode(&ciphertext[..]),
+                cipherparams: CipherParams {
+                    iv: hex::encode(iv),
+                },
+                kdf: match kdf {
+                    KeystoreKdf::Scrypt { .. } => "scrypt",
+                    KeystoreKdf::Pbkdf2 { .. } => "pbkdf2",
+                }
+                .to_string(),
+                kdfparams,
+                mac: hex::encode(mac),
+            },
+        };
+        serde_json::to_string(&keystore)
+            .map_err(|e| Error::Other(format!("Failed to serialize keystore: {}", e)))
+    }
+
+    /// Load a wallet whose 32-byte private key is held in a secret store
+    ///
+    /// # Example
+    /// ```no_run
+    /// use apex_sdk_core::secret::FileSecretStore;
+    /// use apex_sdk_evm::wallet::Wallet;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let store = FileSecretStore::new("/run/secrets/apex")?;
+    /// let wallet = Wallet::from_secret_store(&store, "treasury/evm").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn from_secret_store(store: &dyn SecretStore, id: &str) -> Result<Self, Error> {
+        let secret = store
+            .get(id)
+            .await
+            .map_err(|e| Error::Other(format!("Failed to load key {}: {}", id, e)))?;
+
+        let inner = LocalWallet::from_bytes(secret.expose())
+            .map_err(|e| Error::Other(format!("Invalid private key in {}: {}", id, e)))?;
+        let address = inner.address();
+
+        tracing::info!("Loaded wallet from secret store: {}", address);
+
+        Ok(Self {
+            inner: Key::local(inner),
+            address,
+        })
+    }
+
+    /// Create a wallet that signs through a KMS or HSM
+    ///
+    /// The private key is never available to the process, so
+    /// [`export_private_key`](Self::export_private_key) and
+    /// [`save_to_secret_store`](Self::save_to_secret_store) fail.
+    pub fn from_signer(signer: impl DigestSigner + 'static) -> Self {
+        let address = signer.address();
+
+        tracing::info!("Using remote signer for wallet: {:?}", address);
+
+        Self {
+            inner: Key::Remote {
+                signer: Arc::new(signer),
+                chain_id: 1,
+            },
+            address,
+        }
+    }
+
+    /// Whether the key is held outside the process
+    pub fn is_remote(&self) -> bool {
+        matches!(self.inner, Key::Remote { .. })
+    }
+
+    fn local(&self) -> Result<&LocalWallet, Error> {
+        match &self.inner {
+            Key::Local { wallet, .. } => Ok(wallet),
+            Key::Remote { .. } => Err(Error::Other(
+                "Private key is held by a remote signer".to_string(),
+            )),
+        }
+    }
+
+    /// Generate a new wallet and persist its private key in a secret store
+    pub async fn generate_in_secret_store(
+        store: &dyn SecretStore,
+        id: &str,
+    ) -> Result<Self, Error> {
+        let wallet = Self::new_random();
+        wallet.save_to_secret_store(store, id).await?;
+        Ok(wallet)
+    }
+
+    /// Persist this wallet's private key in a secret store
+    pub async fn save_to_secret_store(
+        &self,
+        store: &dyn SecretStore,
+        id: &str,
+    ) -> Result<(), Error> {
+        let secret = SecretBytes::new(self.local()?.signer().to_bytes().to_vec());
+        store
+            .put(id, &secret)
+            .await
+            .map_err(|e| Error::Other(format!("Failed to store key {}: {}", id, e)))
+    }
+
     /// Create a wallet with a specific chain ID
     ///
     /// This is important for EIP-155 replay protection
     pub fn with_chain_id(mut self, chain_id: u64) -> Self {
-        self.inner = self.inner.with_chain_id(chain_id);
+        self.inner = match self.inner {
+            Key::Local { wallet, .. } => Key::Local { wallet, chain_id },
+            Key::Remote { signer, .. } => Key::Remote { signer, chain_id },
+        };
         tracing::debug!("Set wallet chain ID to {}", chain_id);
         self
     }
@@ -133,11 +453,28 @@ impl Wallet {
     /// # Returns
     /// The signature as bytes
     pub async fn sign_transaction(&self, tx: &TypedTransaction) -> Result<Signature, Error> {
-        let signature = self
-            .inner
-            .sign_transaction(tx)
-            .await
-            .map_err(|e| Error::Transaction(format!("Failed to sign transaction: {}", e)))?;
+        let signature = match &self.inner {
+            Key::Local { wallet, chain_id } => {
+                // The shared key keeps its own chain ID; ours goes on the tx
+                let mut tx = tx.clone();
+                if tx.chain_id().is_none() {
+                    tx.set_chain_id(*chain_id);
+                }
+                wallet
+                    .sign_transaction_sync(&tx)
+                    .map_err(|e| Error::Transaction(format!("Failed to sign transaction: {}", e)))?
+            }
+            Key::Remote { signer, chain_id } => {
+                // Mirror LocalWallet: sighash and `v` use the same chain ID
+                let chain_id = tx.chain_id().map(|id| id.as_u64()).unwrap_or(*chain_id);
+                let mut tx = tx.clone();
+                tx.set_chain_id(chain_id);
+
+                let mut signature = signer.sign_digest(tx.sighash()).await?;
+                signature.v = ethers::signers::to_eip155_v(signature.v as u8 - 27, chain_id);
+                signature
+            }
+        };
 
         tracing::debug!("Signed transaction");
 
@@ -155,11 +492,17 @@ impl Wallet {
         &self,
         message: S,
     ) -> Result<Signature, Error> {
-        let signature = self
-            .inner
-            .sign_message(message)
-            .await
-            .map_err(|e| Error::Transaction(format!("Failed to sign message: {}", e)))?;
+        let signature = match &self.inner {
+            Key::Local { wallet, .. } => wallet
+                .sign_message(message)
+                .await
+                .map_err(|e| Error::Transaction(format!("Failed to sign message: {}", e)))?,
+            Key::Remote { signer, .. } => {
+                signer
+                    .sign_digest(ethers::utils::hash_message(message))
+                    .await?
+            }
+        };
 
         tracing::debug!("Signed message");
 
@@ -177,11 +520,18 @@ impl Wallet {
         &self,
         data: &T,
     ) -> Result<Signature, Error> {
-        let signature = self
-            .inner
-            .sign_typed_data(data)
-            .await
-            .map_err(|e| Error::Transaction(format!("Failed to sign typed data: {}", e)))?;
+        let signature = match &self.inner {
+            Key::Local { wallet, .. } => wallet
+                .sign_typed_data(data)
+                .await
+                .map_err(|e| Error::Transaction(format!("Failed to sign typed data: {}", e)))?,
+            Key::Remote { signer, .. } => {
+                let digest = data.encode_eip712().map_err(|e| {
+                    Error::Transaction(format!("Failed to encode typed data: {}", e))
+                })?;
+                signer.sign_digest(H256::from(digest)).await?
+            }
+        };
 
         tracing::debug!("Signed typed data");
 
@@ -190,19 +540,117 @@ impl Wallet {
 
     /// Get the chain ID configured for this wallet
     pub fn chain_id(&self) -> Option<u64> {
-        Some(self.inner.chain_id())
+        match &self.inner {
+            Key::Local { chain_id, .. } | Key::Remote { chain_id, .. } => Some(*chain_id),
+        }
     }
 
     /// Export private key (WARNING: Handle with extreme care!)
     ///
+    /// Fails for wallets backed by a remote signer.
+    ///
     /// # Security Warning
     /// This exposes the private key. Only use in secure contexts.
-    pub fn export_private_key(&self) -> String {
+    pub fn export_private_key(&self) -> Result<String, Error> {
+        let key = self.local()?.signer().to_bytes();
         tracing::warn!("Private key exported - ensure secure handling!");
-        format!("0x{}", hex::encode(self.inner.signer().to_bytes()))
+        Ok(format!("0x{}", hex::encode(key)))
     }
 }
 
+/// A Web3 Secret Storage (V3) keystore
+#[derive(Serialize, Deserialize)]
+struct KeystoreV3 {
+    version: u8,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    address: Option<String>,
+    // MyEtherWallet capitalizes the field
+    #[serde(alias = "Crypto")]
+    crypto: KeystoreCrypto,
+}
+
+#[derive(Serialize, Deserialize)]
+struct KeystoreCrypto {
+    cipher: String,
+    ciphertext: String,
+    cipherparams: CipherParams,
+    kdf: String,
+    kdfparams: KdfParams,
+    mac: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CipherParams {
+    iv: String,
+}
+
+/// Parameters of either KDF, as named in the keystore
+#[derive(Serialize, Deserialize)]
+struct KdfParams {
+    dklen: u32,
+    salt: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    n: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    r: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    p: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    c: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    prf: Option<String>,
+}
+
+fn keystore_hex(field: &str, value: &str) -> Result<Vec<u8>, Error> {
+    hex::decode(value.trim_start_matches("0x"))
+        .map_err(|e| Error::Other(format!("Invalid keystore {}: {}", field, e)))
+}
+
+/// Derive the 32-byte keystore key from `password`
+fn derive_keystore_key(
+    password: &str,
+    salt: &[u8],
+    kdf: KeystoreKdf,
+) -> Result<Zeroizing<[u8; 32]>, Error> {
+    let mut key = Zeroizing::new([0u8; 32]);
+    match kdf {
+        KeystoreKdf::Scrypt { log_n, r, p } => {
+            let params = scrypt::Params::new(log_n, r, p)
+                .map_err(|e| Error::Other(format!("Invalid keystore scrypt parameters: {}", e)))?;
+            scrypt::scrypt(password.as_bytes(), salt, &params, &mut key[..])
+                .map_err(|e| Error::Other(format!("Failed to derive keystore key: {}", e)))?;
+        }
+        KeystoreKdf::Pbkdf2 { c } => {
+            pbkdf2::pbkdf2::<hmac::Hmac<sha2::Sha256>>(password.as_bytes(), salt, c, &mut key[..]);
+        }
+    }
+    Ok(key)
+}
+
+/// Encrypt or decrypt `data` in place with AES-128-CTR
+fn aes_128_ctr(key: &[u8], iv: &[u8], data: &mut [u8]) {
+    use aes::cipher::{KeyIvInit, StreamCipher};
+
+    ctr::Ctr128BE::<aes::Aes128>::new(key.into(), iv.into()).apply_keystream(data);
+}
+
+/// Format 16 random bytes as a version 4 UUID
+fn uuid_v4(mut bytes: [u8; 16]) -> String {
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    let hex = hex::encode(bytes);
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    )
+}
+
 impl std::fmt::Debug for Wallet {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Wallet")
@@ -290,6 +738,7 @@ impl Default for WalletManager {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use apex_sdk_core::secret::MemorySecretStore;
 
     #[test]
     fn test_new_random_wallet() {
@@ -320,12 +769,133 @@ mod tests {
         assert_eq!(wallet.address().len(), 42);
     }
 
+    #[test]
+    fn test_from_mnemonic_bip44_path() {
+        // m/44'/60'/0'/0/1 of the hardhat mnemonic
+        let mnemonic = "test test test test test test test test test test test junk";
+        let wallet = Wallet::from_mnemonic(mnemonic, 1).unwrap();
+        assert_eq!(
+            wallet.address().to_lowercase(),
+            "0x70997970c51812dc3a010c7d01b50e0d17dc79c8"
+        );
+    }
+
+    #[test]
+    fn test_keystore_v3_pbkdf2_vector() {
+        // Web3 Secret Storage definition test vector
+        let json = r#"{"crypto":{"cipher":"aes-128-ctr","cipherparams":{"iv":"6087dab2f9fdbbfaddc31a909735c1e6"},"ciphertext":"5318b4d5bcd28de64ee5559e671353e16f075ecae9f99c7a79a38af5f869aa46","kdf":"pbkdf2","kdfparams":{"c":262144,"dklen":32,"prf":"hmac-sha256","salt":"ae3cd4e7013836a3df6bd7241b12db061dbe2c6785853cce422d148a624ce0bd"},"mac":"517ead924a9d0dc3124507e3393d175ce3ff7c1e96529c6c555ce9e51205e9b2"},"id":"3198bc9c-6672-5ab3-d995-4942343ae5b6","version":3}"#;
+        let wallet = Wallet::from_keystore_v3(json, "testpassword").unwrap();
+        assert_eq!(
+            wallet.export_private_key().unwrap(),
+            "0x7a28b5ba57c53603b0b07b56bba752f7784bf506fa95edc395f5cf6c7514fe9d"
+        );
+        assert!(Wallet::from_keystore_v3(json, "wrong").is_err());
+    }
+
+    #[test]
+    fn test_keystore_v3_roundtrip() {
+        let wallet = Wallet::new_random();
+        for kdf in [
+            KeystoreKdf::Scrypt {
+                log_n: 10,
+                r: 8,
+                p: 1,
+            },
+            KeystoreKdf::Pbkdf2 { c: 1000 },
+        ] {
+            let json = wallet.to_keystore_v3_with_kdf("hunter2", kdf).unwrap();
+            let keystore: serde_json::Value = serde_json::from_str(&json).unwrap();
+            assert_eq!(keystore["version"], 3);
+            assert_eq!(keystore["id"].as_str().unwrap().len(), 36);
+
+            let loaded = Wallet::from_keystore_v3(&json, "hunter2").unwrap();
+            assert_eq!(loaded.address(), wallet.address());
+
+            // The address, when present, must match the key
+            let mut tampered = keystore.clone();
+            tampered["address"] = hex::encode(Wallet::new_random().eth_address()).into();
+            assert!(Wallet::from_keystore_v3(&tampered.to_string(), "hunter2").is_err());
+        }
+    }
+
+    #[test]
+    fn test_keystore_v3_rejects_oversized_scrypt() {
+        let wallet = Wallet::new_random();
+        let kdf = KeystoreKdf::Scrypt {
+            log_n: 10,
+            r: 8,
+            p: 1,
+        };
+        let json = wallet.to_keystore_v3_with_kdf("hunter2", kdf).unwrap();
+        let keystore: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        // Refused before any key derivation, so these return immediately
+        for (n, r, p) in [
+            (1u64 << 21, 8, 1),
+            (1 << 31, 8, 1),
+            (1 << 10, 8, 2),
+            (1 << 10, 1, 16),
+        ] {
+            let mut oversized = keystore.clone();
+            oversized["crypto"]["kdfparams"]["n"] = n.into();
+            oversized["crypto"]["kdfparams"]["r"] = r.into();
+            oversized["crypto"]["kdfparams"]["p"] = p.into();
+            let err = Wallet::from_keystore_v3(&oversized.to_string(), "hunter2").unwrap_err();
+            assert!(err.to_string().contains("scrypt parameters"));
+        }
+    }
+
+    #[test]
+    fn test_keystore_v3_rejects_oversized_pbkdf2() {
+        let wallet = Wallet::new_random();
+        let json = wallet
+            .to_keystore_v3_with_kdf("hunter2", KeystoreKdf::Pbkdf2 { c: 1000 })
+            .unwrap();
+        let keystore: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        let mut oversized = keystore.clone();
+        oversized["crypto"]["kdfparams"]["c"] = u32::MAX.into();
+        let err = Wallet::from_keystore_v3(&oversized.to_string(), "hunter2").unwrap_err();
+        assert!(err.to_string().contains("PBKDF2 parameters"));
+
+        for dklen in [16u32, 64, u32::MAX] {
+            let mut oversized = keystore.clone();
+            oversized["crypto"]["kdfparams"]["dklen"] = dklen.into();
+            let err = Wallet::from_keystore_v3(&oversized.to_string(), "hunter2").unwrap_err();
+            assert!(err.to_string().contains("Invalid keystore parameters"));
+        }
+    }
+
     #[test]
     fn test_wallet_with_chain_id() {
         let wallet = Wallet::new_random().with_chain_id(1);
         assert_eq!(wallet.chain_id(), Some(1));
     }
 
+    #[test]
+    fn test_clones_share_key() {
+        let wallet = Wallet::new_random();
+        let clone = wallet.clone();
+        match (&wallet.inner, &clone.inner) {
+            (Key::Local { wallet: a, .. }, Key::Local { wallet: b, .. }) => {
+                assert!(Arc::ptr_eq(a, b))
+            }
+            _ => panic!("expected local keys"),
+        }
+
+        // Other clones keep the original chain ID and still share the key
+        let wallet = wallet.with_chain_id(10);
+        assert_eq!(wallet.chain_id(), Some(10));
+        assert_eq!(clone.chain_id(), Some(1));
+        assert_eq!(wallet.address(), clone.address());
+        match (&wallet.inner, &clone.inner) {
+            (Key::Local { wallet: a, .. }, Key::Local { wallet: b, .. }) => {
+                assert!(Arc::ptr_eq(a, b))
+            }
+            _ => panic!("expected local keys"),
+        }
+    }
+
     #[tokio::test]
     async fn test_sign_message() {
         let wallet = Wallet::new_random();
@@ -378,4 +948,69 @@ mod tests {
             "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266"
         );
     }
+
+    #[tokio::test]
+    async fn test_secret_store_roundtrip() {
+        let store = MemorySecretStore::new();
+        let wallet = Wallet::generate_in_secret_store(&store, "evm/signer")
+            .await
+            .unwrap();
+
+        let loaded = Wallet::from_secret_store(&store, "evm/signer")
+            .await
+            .unwrap();
+        assert_eq!(loaded.address(), wallet.address());
+
+        assert!(Wallet::from_secret_store(&store, "evm/missing")
+            .await
+            .is_err());
+    }
+
+    /// Signs with an in-memory key through the remote signer path
+    struct TestSigner(LocalWallet);
+
+    #[async_trait::async_trait]
+    impl DigestSigner for TestSigner {
+        fn address(&self) -> EthAddress {
+            self.0.address()
+        }
+
+        async fn sign_digest(&self, digest: H256) -> Result<Signature, Error> {
+            self.0
+                .sign_hash(digest)
+                .map_err(|e| Error::Other(e.to_string()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_remote_signer_matches_local() {
+        let key = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+        let local = Wallet::from_private_key(key).unwrap().with_chain_id(5);
+        let remote = Wallet::from_signer(TestSigner(key.parse().unwrap())).with_chain_id(5);
+
+        assert!(remote.is_remote());
+        assert_eq!(remote.address(), local.address());
+        assert!(remote.export_private_key().is_err());
+
+        let legacy: TypedTransaction = TransactionRequest::new()
+            .to(local.eth_address())
+            .value(1)
+            .nonce(0)
+            .into();
+        let eip1559: TypedTransaction = Eip1559TransactionRequest::new()
+            .to(local.eth_address())
+            .value(1)
+            .chain_id(5)
+            .into();
+        for tx in [legacy, eip1559] {
+            assert_eq!(
+                remote.sign_transaction(&tx).await.unwrap(),
+                local.sign_transaction(&tx).await.unwrap()
+            );
+        }
+        assert_eq!(
+            remote.sign_message("hello").await.unwrap(),
+            local.sign_message("hello").await.unwrap()
+        );
+    }
 }
@@ -172,7 +172,7 @@ async fn test_invalid_address_balance() {
 async fn test_concurrent_balance_queries() {
     let addresses = vec![
         "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045", // Vitalik
-        "0xDE0B295669a9FD93d5F28D9Ec85E40f4cb697BAe", // Ethereum Foundation
+        "0xde0B295669a9FD93d5F28D9Ec85E40f4cb697BAe", // Ethereum Foundation
         "0x00000000219ab540356cBB839Cbe05303d7705Fa", // ETH2 Deposit Contract
     ];
 
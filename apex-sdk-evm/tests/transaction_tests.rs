@@ -108,7 +108,7 @@ fn test_wallet_export_private_key() {
     let private_key = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
     let wallet = Wallet::from_private_key(private_key).unwrap();
 
-    let exported = wallet.export_private_key();
+    let exported = wallet.export_private_key().unwrap();
     assert_eq!(exported.to_lowercase(), private_key.to_lowercase());
 }
 
@@ -123,7 +123,7 @@ async fn test_gas_estimation() {
     let executor = TransactionExecutor::new(adapter.provider().clone());
 
     let from = EthAddress::from_str("0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045").unwrap();
-    let to = EthAddress::from_str("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb7").unwrap();
+    let to = EthAddress::from_str("0x742D35Cc6634C0532925A3B844bC9e7595f0bEB7").unwrap();
     let value = U256::from(1_000_000_000_000_000u64); // 0.001 ETH
 
     let estimate = executor
@@ -154,7 +154,7 @@ async fn test_gas_estimation_with_config() {
     let executor = TransactionExecutor::new(adapter.provider().clone()).with_gas_config(gas_config);
 
     let from = EthAddress::from_str("0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045").unwrap();
-    let to = EthAddress::from_str("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb7").unwrap();
+    let to = EthAddress::from_str("0x742D35Cc6634C0532925A3B844bC9e7595f0bEB7").unwrap();
 
     let estimate = executor
         .estimate_gas(from, Some(to), Some(U256::from(1000)), None)
@@ -175,7 +175,7 @@ async fn test_build_transaction() {
     let executor = TransactionExecutor::new(adapter.provider().clone());
 
     let wallet = Wallet::new_random().with_chain_id(1);
-    let to = EthAddress::from_str("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb7").unwrap();
+    let to = EthAddress::from_str("0x742D35Cc6634C0532925A3B844bC9e7595f0bEB7").unwrap();
     let value = U256::from(1000);
 
     let tx = executor
@@ -396,7 +396,7 @@ async fn test_full_workflow_with_all_features() {
 
     // Test gas estimation
     let from = wallet.eth_address();
-    let to = EthAddress::from_str("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb7").unwrap();
+    let to = EthAddress::from_str("0x742D35Cc6634C0532925A3B844bC9e7595f0bEB7").unwrap();
 
     let start = std::time::Instant::now();
     let estimate = executor
@@ -458,7 +458,7 @@ async fn test_concurrent_balance_queries() {
 
     let addresses = vec![
         "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045",
-        "0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb7",
+        "0x742D35Cc6634C0532925A3B844bC9e7595f0bEB7",
         "0xDA9dfA130Df4dE4673b89022EE50ff26f6EA73Cf",
     ];
 
@@ -0,0 +1,99 @@
+/*
+ * Apex SDK - C bindings
+ *
+ * All functions return an ApexErrorCode. On failure, apex_last_error_message()
+ * describes the error. Handles must be released with the matching *_free
+ * function and returned strings with apex_string_free().
+ *
+ * Amounts and balances are decimal strings in the chain's smallest unit.
+ */
+
+#ifndef APEX_SDK_H
+#define APEX_SDK_H
+
+#include <stdbool.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef enum ApexErrorCode {
+    APEX_OK = 0,
+    APEX_ERROR_NULL_POINTER = 1,
+    APEX_ERROR_INVALID_UTF8 = 2,
+    APEX_ERROR_INVALID_ARGUMENT = 3,
+    APEX_ERROR_CONFIG = 4,
+    APEX_ERROR_CONNECTION = 5,
+    APEX_ERROR_TRANSACTION = 6,
+    APEX_ERROR_UNSUPPORTED_CHAIN = 7,
+    APEX_ERROR_INVALID_ADDRESS = 8,
+    APEX_ERROR_INTERNAL = 99,
+} ApexErrorCode;
+
+typedef enum ApexChain {
+    APEX_CHAIN_POLKADOT = 0,
+    APEX_CHAIN_KUSAMA = 1,
+    APEX_CHAIN_ETHEREUM = 2,
+    APEX_CHAIN_BINANCE_SMART_CHAIN = 3,
+    APEX_CHAIN_POLYGON = 4,
+    APEX_CHAIN_AVALANCHE = 5,
+    APEX_CHAIN_MOONBEAM = 6,
+    APEX_CHAIN_ASTAR = 7,
+    APEX_CHAIN_SEPOLIA = 8,
+    APEX_CHAIN_HOLESKY = 9,
+    APEX_CHAIN_AMOY = 10,
+    APEX_CHAIN_MOONBASE_ALPHA = 11,
+    APEX_CHAIN_SHIBUYA = 12,
+    APEX_CHAIN_PASEO = 13,
+} ApexChain;
+
+/* Chain arguments are uint32_t holding an ApexChain value; any other value
+ * fails with APEX_ERROR_INVALID_ARGUMENT. */
+
+/* Opaque handles */
+typedef struct ApexSdk ApexSdk;
+typedef struct ApexTransaction ApexTransaction;
+
+/* SDK lifecycle. Either endpoint may be NULL, but not both. */
+ApexErrorCode apex_sdk_connect(const char *substrate_endpoint,
+                               const char *evm_endpoint,
+                               ApexSdk **out_sdk);
+void apex_sdk_free(ApexSdk *sdk);
+ApexErrorCode apex_sdk_is_chain_supported(const ApexSdk *sdk,
+                                          uint32_t chain,
+                                          bool *out_supported);
+
+/* Queries */
+ApexErrorCode apex_get_balance(const ApexSdk *sdk,
+                               uint32_t chain,
+                               const char *address,
+                               char **out_balance);
+ApexErrorCode apex_transaction_status(const ApexSdk *sdk,
+                                      uint32_t chain,
+                                      const char *tx_hash,
+                                      char **out_status_json);
+
+/* Transactions */
+ApexErrorCode apex_transaction_build(uint32_t chain,
+                                     const char *from,
+                                     const char *to,
+                                     const char *amount,
+                                     ApexTransaction **out_tx);
+ApexErrorCode apex_transaction_to_json(const ApexTransaction *tx,
+                                       char **out_json);
+ApexErrorCode apex_transaction_sign_and_submit(const ApexSdk *sdk,
+                                               const ApexTransaction *tx,
+                                               const char *secret,
+                                               char **out_tx_hash);
+void apex_transaction_free(ApexTransaction *tx);
+
+/* Errors and memory */
+const char *apex_last_error_message(void);
+void apex_string_free(char *s);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* APEX_SDK_H */
@@ -0,0 +1,788 @@
+//! # Apex SDK FFI
+//!
+//! C ABI bindings for the Apex SDK, for use from Swift, Kotlin, C++ and any
+//! other language that can call into a C library.
+//!
+//! ## Conventions
+//!
+//! - Every function returns an [`ApexErrorCode`]. On failure, a description of
+//!   the error can be read with [`apex_last_error_message`].
+//! - SDK instances and transactions are handed out as opaque pointers and must
+//!   be released with [`apex_sdk_free`] / [`apex_transaction_free`].
+//! - Strings returned through out-parameters are owned by the caller and must
+//!   be released with [`apex_string_free`].
+//! - Amounts and balances are passed as decimal strings in the chain's smallest
+//!   unit (planck, wei), since C has no portable 128-bit integer.
+//!
+//! The matching C declarations live in `include/apex_sdk.h`.
+//!
+//! ## Example (C)
+//!
+//! ```c
+//! ApexSdk *sdk = NULL;
+//! if (apex_sdk_connect(NULL, "https://eth.llamarpc.com", &sdk) != APEX_OK) {
+//!     fprintf(stderr, "%s\n", apex_last_error_message());
+//!     return 1;
+//! }
+//!
+//! char *balance = NULL;
+//! apex_get_balance(sdk, APEX_CHAIN_ETHEREUM,
+//!                  "0x742D35Cc6634C0532925A3B844bC9e7595f0bEB7", &balance);
+//! printf("balance: %s wei\n", balance);
+//!
+//! apex_string_free(balance);
+//! apex_sdk_free(sdk);
+//! ```
+
+use apex_sdk::evm::wallet::Wallet as EvmWallet;
+use apex_sdk::substrate::{KeyPairType, Wallet as SubstrateWallet};
+use apex_sdk::types::{Address, Chain};
+use apex_sdk::{ApexSDK, Error, Transaction, TransactionBuilder};
+use std::cell::RefCell;
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::ptr;
+use tokio::runtime::Runtime;
+
+/// Status codes returned by every FFI function
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApexErrorCode {
+    /// The call succeeded
+    Ok = 0,
+    /// A required pointer argument was null
+    NullPointer = 1,
+    /// A string argument was not valid UTF-8
+    InvalidUtf8 = 2,
+    /// An argument could not be parsed
+    InvalidArgument = 3,
+    /// The SDK is not configured for the requested operation
+    Config = 4,
+    /// Communication with the node failed
+    Connection = 5,
+    /// The transaction could not be built, signed or submitted
+    Transaction = 6,
+    /// The chain is not supported by this SDK instance
+    UnsupportedChain = 7,
+    /// An address was malformed
+    InvalidAddress = 8,
+    /// Unexpected internal error (including caught panics)
+    Internal = 99,
+}
+
+/// Chains addressable through the C API
+///
+/// Functions take the chain as a `u32` holding one of these values, since C
+/// callers may pass any integer where an enum is expected.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApexChain {
+    /// Polkadot relay chain
+    Polkadot = 0,
+    /// Kusama relay chain
+    Kusama = 1,
+    /// Ethereum mainnet
+    Ethereum = 2,
+    /// Binance Smart Chain
+    BinanceSmartChain = 3,
+    /// Polygon
+    Polygon = 4,
+    /// Avalanche C-Chain
+    Avalanche = 5,
+    /// Moonbeam
+    Moonbeam = 6,
+    /// Astar
+    Astar = 7,
+    /// Sepolia (Ethereum testnet)
+    Sepolia = 8,
+    /// Holesky (Ethereum testnet)
+    Holesky = 9,
+    /// Amoy (Polygon testnet)
+    Amoy = 10,
+    /// Moonbase Alpha (Moonbeam testnet)
+    MoonbaseAlpha = 11,
+    /// Shibuya (Astar testnet)
+    Shibuya = 12,
+    /// Paseo (Polkadot testnet)
+    Paseo = 13,
+}
+
+impl TryFrom<u32> for ApexChain {
+    type Error = ApexErrorCode;
+
+    /// Fails with [`ApexErrorCode::InvalidArgument`] for unknown values
+    fn try_from(value: u32) -> Result<Self, ApexErrorCode> {
+        Ok(match value {
+            0 => ApexChain::Polkadot,
+            1 => ApexChain::Kusama,
+            2 => ApexChain::Ethereum,
+            3 => ApexChain::BinanceSmartChain,
+            4 => ApexChain::Polygon,
+            5 => ApexChain::Avalanche,
+            6 => ApexChain::Moonbeam,
+            7 => ApexChain::Astar,
+            8 => ApexChain::Sepolia,
+            9 => ApexChain::Holesky,
+            10 => ApexChain::Amoy,
+            11 => ApexChain::MoonbaseAlpha,
+            12 => ApexChain::Shibuya,
+            13 => ApexChain::Paseo,
+            _ => return Err(ApexErrorCode::InvalidArgument),
+        })
+    }
+}
+
+impl From<ApexChain> for Chain {
+    fn from(chain: ApexChain) -> Self {
+        match chain {
+            ApexChain::Polkadot => Chain::Polkadot,
+            ApexChain::Kusama => Chain::Kusama,
+            ApexChain::Ethereum => Chain::Ethereum,
+            ApexChain::BinanceSmartChain => Chain::BinanceSmartChain,
+            ApexChain::Polygon => Chain::Polygon,
+            ApexChain::Avalanche => Chain::Avalanche,
+            ApexChain::Moonbeam => Chain::Moonbeam,
+            ApexChain::Astar => Chain::Astar,
+            ApexChain::Sepolia => Chain::Sepolia,
+            ApexChain::Holesky => Chain::Holesky,
+            ApexChain::Amoy => Chain::Amoy,
+            ApexChain::MoonbaseAlpha => Chain::MoonbaseAlpha,
+            ApexChain::Shibuya => Chain::Shibuya,
+            ApexChain::Paseo => Chain::Paseo,
+        }
+    }
+}
+
+/// Opaque SDK handle
+pub struct ApexSdk {
+    runtime: Runtime,
+    sdk: ApexSDK,
+}
+
+/// Opaque transaction handle
+pub struct ApexTransaction {
+    inner: Transaction,
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+/// Error carried across the FFI boundary
+struct FfiError {
+    code: ApexErrorCode,
+    message: String,
+}
+
+impl FfiError {
+    fn new(code: ApexErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+impl From<Error> for FfiError {
+    fn from(err: Error) -> Self {
+        let code = match &err {
+            Error::Config(_) => ApexErrorCode::Config,
+            Error::Connection(_) => ApexErrorCode::Connection,
+            Error::Transaction(_) | Error::Policy(_) => ApexErrorCode::Transaction,
+            Error::UnsupportedChain(_) => ApexErrorCode::UnsupportedChain,
+            Error::InvalidAddress(_) => ApexErrorCode::InvalidAddress,
+            Error::Substrate(_) | Error::Evm(_) => ApexErrorCode::Connection,
+            Error::Serialization(_) | Error::Other(_) => ApexErrorCode::Internal,
+        };
+        Self::new(code, err.to_string())
+    }
+}
+
+type FfiResult<T> = std::result::Result<T, FfiError>;
+
+fn set_last_error(message: &str) {
+    let message = CString::new(message.replace('\0', " ")).unwrap_or_default();
+    LAST_ERROR.with(|last| *last.borrow_mut() = Some(message));
+}
+
+fn clear_last_error() {
+    LAST_ERROR.with(|last| *last.borrow_mut() = None);
+}
+
+/// Run an FFI body, translating errors and panics into status codes
+fn run(body: impl FnOnce() -> FfiResult<()>) -> ApexErrorCode {
+    match catch_unwind(AssertUnwindSafe(body)) {
+        Ok(Ok(())) => {
+            clear_last_error();
+            ApexErrorCode::Ok
+        }
+        Ok(Err(err)) => {
+            set_last_error(&err.message);
+            err.code
+        }
+        Err(_) => {
+            set_last_error("panic while executing Apex SDK call");
+            ApexErrorCode::Internal
+        }
+    }
+}
+
+/// Read a required, NUL-terminated UTF-8 string argument
+unsafe fn str_arg<'a>(ptr: *const c_char, name: &str) -> FfiResult<&'a str> {
+    if ptr.is_null() {
+        return Err(FfiError::new(
+            ApexErrorCode::NullPointer,
+            format!("{} must not be null", name),
+        ));
+    }
+    CStr::from_ptr(ptr)
+        .to_str()
+        .map_err(|_| FfiError::new(ApexErrorCode::InvalidUtf8, format!("{} is not UTF-8", name)))
+}
+
+/// Read an optional string argument (null means "not set")
+unsafe fn opt_str_arg<'a>(ptr: *const c_char, name: &str) -> FfiResult<Option<&'a str>> {
+    if ptr.is_null() {
+        Ok(None)
+    } else {
+        str_arg(ptr, name).map(Some)
+    }
+}
+
+unsafe fn ref_arg<'a, T>(ptr: *const T, name: &str) -> FfiResult<&'a T> {
+    ptr.as_ref().ok_or_else(|| {
+        FfiError::new(
+            ApexErrorCode::NullPointer,
+            format!("{} must not be null", name),
+        )
+    })
+}
+
+fn check_out<T>(out: *mut T, name: &str) -> FfiResult<()> {
+    if out.is_null() {
+        Err(FfiError::new(
+            ApexErrorCode::NullPointer,
+            format!("{} must not be null", name),
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// Hand a string to the caller through an out-parameter
+unsafe fn write_string(out: *mut *mut c_char, value: String) -> FfiResult<()> {
+    let value = CString::new(value)
+        .map_err(|_| FfiError::new(ApexErrorCode::Internal, "string contains NUL byte"))?;
+    *out = value.into_raw();
+    Ok(())
+}
+
+fn parse_amount(value: &str) -> FfiResult<u128> {
+    value.trim().parse::<u128>().map_err(|e| {
+        FfiError::new(
+            ApexErrorCode::InvalidArgument,
+            format!("Invalid amount '{}': {}", value, e),
+        )
+    })
+}
+
+/// Read a chain argument holding an [`ApexChain`] value
+fn chain_arg(chain: u32) -> FfiResult<Chain> {
+    ApexChain::try_from(chain)
+        .map(Chain::from)
+        .map_err(|code| FfiError::new(code, format!("Unknown chain {}", chain)))
+}
+
+/// Parse an EVM or SS58 address, checking its checksum
+fn parse_address(value: &str) -> FfiResult<Address> {
+    value
+        .parse::<Address>()
+        .map_err(|e| FfiError::new(ApexErrorCode::InvalidAddress, e.to_string()))
+}
+
+/// Connect to the given endpoints and create an SDK instance.
+///
+/// Either endpoint may be null, but at least one must be provided.
+///
+/// # Safety
+///
+/// `substrate_endpoint` and `evm_endpoint` must be null or valid NUL-terminated
+/// strings, and `out_sdk` must be a valid pointer to write the handle to.
+#[no_mangle]
+pub unsafe extern "C" fn apex_sdk_connect(
+    substrate_endpoint: *const c_char,
+    evm_endpoint: *const c_char,
+    out_sdk: *mut *mut ApexSdk,
+) -> ApexErrorCode {
+    run(|| {
+        check_out(out_sdk, "out_sdk")?;
+        let substrate_endpoint = opt_str_arg(substrate_endpoint, "substrate_endpoint")?;
+        let evm_endpoint = opt_str_arg(evm_endpoint, "evm_endpoint")?;
+
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .build()
+            .map_err(|e| FfiError::new(ApexErrorCode::Internal, e.to_string()))?;
+
+        let mut builder = ApexSDK::builder();
+        if let Some(endpoint) = substrate_endpoint {
+            builder = builder.with_substrate_endpoint(endpoint);
+        }
+        if let Some(endpoint) = evm_endpoint {
+            builder = builder.with_evm_endpoint(endpoint);
+        }
+
+        let sdk = runtime.block_on(builder.build())?;
+        *out_sdk = Box::into_raw(Box::new(ApexSdk { runtime, sdk }));
+        Ok(())
+    })
+}
+
+/// Release an SDK instance. Passing null is a no-op.
+///
+/// # Safety
+///
+/// `sdk` must be null or a handle returned by [`apex_sdk_connect`] that has
+/// not been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn apex_sdk_free(sdk: *mut ApexSdk) {
+    if !sdk.is_null() {
+        drop(Box::from_raw(sdk));
+    }
+}
+
+/// Check whether `chain` can be used with this SDK instance.
+///
+/// # Safety
+///
+/// `sdk` must be a live handle and `out_supported` a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn apex_sdk_is_chain_supported(
+    sdk: *const ApexSdk,
+    chain: u32,
+    out_supported: *mut bool,
+) -> ApexErrorCode {
+    run(|| {
+        let sdk = ref_arg(sdk, "sdk")?;
+        check_out(out_supported, "out_supported")?;
+        *out_supported = sdk.sdk.is_chain_supported(&chain_arg(chain)?);
+        Ok(())
+    })
+}
+
+/// Query the balance of `address` on `chain`.
+///
+/// The balance is written to `out_balance` as a decimal string in the chain's
+/// smallest unit. On hybrid chains, `0x` addresses are queried through the EVM
+/// adapter and SS58 addresses through the Substrate adapter.
+///
+/// # Safety
+///
+/// `sdk` must be a live handle, `address` a valid NUL-terminated string and
+/// `out_balance` a valid pointer. The returned string must be released with
+/// [`apex_string_free`].
+#[no_mangle]
+pub unsafe extern "C" fn apex_get_balance(
+    sdk: *const ApexSdk,
+    chain: u32,
+    address: *const c_char,
+    out_balance: *mut *mut c_char,
+) -> ApexErrorCode {
+    run(|| {
+        let handle = ref_arg(sdk, "sdk")?;
+        let chain = chain_arg(chain)?;
+        let address = parse_address(str_arg(address, "address")?)?;
+        check_out(out_balance, "out_balance")?;
+
+        let balance = handle
+            .runtime
+            .block_on(handle.sdk.get_balance(&chain, &address))?;
+
+        write_string(out_balance, balance.value.to_string())
+    })
+}
+
+/// Query the status of a transaction.
+///
+/// The status is written to `out_status_json` as a JSON document, e.g.
+/// `"Pending"` or `{"Confirmed":{"block_number":1,"confirmations":3}}`.
+///
+/// # Safety
+///
+/// `sdk` must be a live handle, `tx_hash` a valid NUL-terminated string and
+/// `out_status_json` a valid pointer. The returned string must be released
+/// with [`apex_string_free`].
+#[no_mangle]
+pub unsafe extern "C" fn apex_transaction_status(
+    sdk: *const ApexSdk,
+    chain: u32,
+    tx_hash: *const c_char,
+    out_status_json: *mut *mut c_char,
+) -> ApexErrorCode {
+    run(|| {
+        let handle = ref_arg(sdk, "sdk")?;
+        let chain = chain_arg(chain)?;
+        let tx_hash = str_arg(tx_hash, "tx_hash")?;
+        check_out(out_status_json, "out_status_json")?;
+
+        let status = handle
+            .runtime
+            .block_on(handle.sdk.get_transaction_status(&chain, tx_hash))?;
+        let json = serde_json::to_string(&status)
+            .map_err(|e| FfiError::new(ApexErrorCode::Internal, e.to_string()))?;
+
+        write_string(out_status_json, json)
+    })
+}
+
+/// Build a native-token transfer on `chain`.
+///
+/// `from` and `to` may be `0x` EVM addresses or SS58 Substrate addresses;
+/// `amount` is a decimal string in the chain's smallest unit.
+///
+/// # Safety
+///
+/// All string arguments must be valid NUL-terminated strings and `out_tx` a
+/// valid pointer. The returned handle must be released with
+/// [`apex_transaction_free`].
+#[no_mangle]
+pub unsafe extern "C" fn apex_transaction_build(
+    chain: u32,
+    from: *const c_char,
+    to: *const c_char,
+    amount: *const c_char,
+    out_tx: *mut *mut ApexTransaction,
+) -> ApexErrorCode {
+    run(|| {
+        let chain = chain_arg(chain)?;
+        let from = parse_address(str_arg(from, "from")?)?;
+        let to = parse_address(str_arg(to, "to")?)?;
+        let amount = parse_amount(str_arg(amount, "amount")?)?;
+        check_out(out_tx, "out_tx")?;
+
+        let inner = TransactionBuilder::new()
+            .from(from)
+            .to(to)
+            .amount(amount)
+            .on_chain(chain)
+            .build()?;
+
+        *out_tx = Box::into_raw(Box::new(ApexTransaction { inner }));
+        Ok(())
+    })
+}
+
+/// Serialize a built transaction as JSON.
+///
+/// # Safety
+///
+/// `tx` must be a live transaction handle and `out_json` a valid pointer. The
+/// returned string must be released with [`apex_string_free`].
+#[no_mangle]
+pub unsafe extern "C" fn apex_transaction_to_json(
+    tx: *const ApexTransaction,
+    out_json: *mut *mut c_char,
+) -> ApexErrorCode {
+    run(|| {
+        let tx = ref_arg(tx, "tx")?;
+        check_out(out_json, "out_json")?;
+        let json = serde_json::to_string(&tx.inner)
+            .map_err(|e| FfiError::new(ApexErrorCode::Internal, e.to_string()))?;
+        write_string(out_json, json)
+    })
+}
+
+/// Sign a built transaction and submit it to its source chain.
+///
+/// `secret` is a hex private key (EVM) or a BIP-39 mnemonic (EVM account 0, or
+/// an SR25519 Substrate account). The account derived from `secret` must
+/// match the transaction's sender. The transaction hash is
+/// written to `out_tx_hash`.
+///
+/// # Safety
+///
+/// `sdk` and `tx` must be live handles, `secret` a valid NUL-terminated string
+/// and `out_tx_hash` a valid pointer. The returned string must be released
+/// with [`apex_string_free`].
+#[no_mangle]
+pub unsafe extern "C" fn apex_transaction_sign_and_submit(
+    sdk: *const ApexSdk,
+    tx: *const ApexTransaction,
+    secret: *const c_char,
+    out_tx_hash: *mut *mut c_char,
+) -> ApexErrorCode {
+    run(|| {
+        let handle = ref_arg(sdk, "sdk")?;
+        let tx = &ref_arg(tx, "tx")?.inner;
+        let secret = str_arg(secret, "secret")?;
+        check_out(out_tx_hash, "out_tx_hash")?;
+
+        let tx_hash = handle.runtime.block_on(async {
+            match &tx.from {
+                Address::Evm(from) => submit_evm(&handle.sdk, tx, from, secret).await,
+                Address::Substrate(from) => submit_substrate(&handle.sdk, tx, from, secret).await,
+            }
+        })?;
+
+        write_string(out_tx_hash, tx_hash)
+    })
+}
+
+async fn submit_evm(
+    sdk: &ApexSDK,
+    tx: &Transaction,
+    from: &str,
+    secret: &str,
+) -> FfiResult<String> {
+    let adapter = sdk.evm()?;
+    let invalid =
+        |e: apex_sdk::evm::Error| FfiError::new(ApexErrorCode::InvalidArgument, e.to_string());
+
+    let wallet = if secret.trim().contains(' ') {
+        EvmWallet::from_mnemonic(secret, 0).map_err(invalid)?
+    } else {
+        EvmWallet::from_private_key(secret).map_err(invalid)?
+    };
+    if !wallet.address().eq_ignore_ascii_case(from) {
+        return Err(FfiError::new(
+            ApexErrorCode::InvalidArgument,
+            format!("Secret does not control sender address {}", from),
+        ));
+    }
+
+    let chain_id = adapter.chain_id().await.map_err(Error::Evm)?;
+    let wallet = wallet.with_chain_id(chain_id);
+
+    let to = tx
+        .to
+        .as_str()
+        .parse::<ethers::types::Address>()
+        .map_err(|e| {
+            FfiError::new(
+                ApexErrorCode::InvalidAddress,
+                format!("Invalid recipient {}: {}", tx.to.as_str(), e),
+            )
+        })?;
+
+    let hash = adapter
+        .transaction_executor()
+        .send_transaction(
+            &wallet,
+            to,
+            ethers::types::U256::from(tx.amount),
+            tx.data.clone(),
+        )
+        .await
+        .map_err(Error::Evm)?;
+
+    Ok(format!("{:?}", hash))
+}
+
+async fn submit_substrate(
+    sdk: &ApexSDK,
+    tx: &Transaction,
+    from: &str,
+    secret: &str,
+) -> FfiResult<String> {
+    let adapter = sdk.substrate()?;
+    let wallet = SubstrateWallet::from_mnemonic(secret, KeyPairType::Sr25519)
+        .map_err(|e| FfiError::new(ApexErrorCode::InvalidArgument, e.to_string()))?;
+    if Some(wallet.public_key()) != tx.from.account_bytes() {
+        return Err(FfiError::new(
+            ApexErrorCode::InvalidArgument,
+            format!("Secret does not control sender address {}", from),
+        ));
+    }
+
+    let hash = adapter
+        .transaction_executor()
+        .transfer(&wallet, tx.to.as_str(), tx.amount)
+        .await
+        .map_err(Error::Substrate)?;
+
+    Ok(hash)
+}
+
+/// Release a transaction handle. Passing null is a no-op.
+///
+/// # Safety
+///
+/// `tx` must be null or a handle returned by [`apex_transaction_build`] that
+/// has not been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn apex_transaction_free(tx: *mut ApexTransaction) {
+    if !tx.is_null() {
+        drop(Box::from_raw(tx));
+    }
+}
+
+/// Get the message of the last error raised on the calling thread.
+///
+/// Returns null if the last call succeeded. The pointer stays valid until the
+/// next Apex SDK call on the same thread and must not be freed.
+#[no_mangle]
+pub extern "C" fn apex_last_error_message() -> *const c_char {
+    LAST_ERROR.with(|last| {
+        last.borrow()
+            .as_ref()
+            .map_or(ptr::null(), |message| message.as_ptr())
+    })
+}
+
+/// Release a string returned by this library. Passing null is a no-op.
+///
+/// # Safety
+///
+/// `s` must be null or a string returned through an out-parameter of this
+/// library that has not been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn apex_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn last_error() -> String {
+        let ptr = apex_last_error_message();
+        assert!(!ptr.is_null());
+        unsafe { CStr::from_ptr(ptr) }.to_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn test_connect_requires_an_endpoint() {
+        let mut sdk = ptr::null_mut();
+        let code = unsafe { apex_sdk_connect(ptr::null(), ptr::null(), &mut sdk) };
+        assert_eq!(code, ApexErrorCode::Config);
+        assert!(sdk.is_null());
+        assert!(last_error().contains("At least one adapter"));
+    }
+
+    #[test]
+    fn test_null_out_pointer() {
+        let code = unsafe { apex_sdk_connect(ptr::null(), ptr::null(), ptr::null_mut()) };
+        assert_eq!(code, ApexErrorCode::NullPointer);
+        assert!(last_error().contains("out_sdk"));
+    }
+
+    #[test]
+    fn test_transaction_build_and_serialize() {
+        let from = CString::new("0x742D35Cc6634C0532925A3B844bC9e7595f0bEB7").unwrap();
+        let to = CString::new("0x1234567890123456789012345678901234567890").unwrap();
+        let amount = CString::new("1000").unwrap();
+
+        let mut tx = ptr::null_mut();
+        let code = unsafe {
+            apex_transaction_build(
+                ApexChain::Polygon as u32,
+                from.as_ptr(),
+                to.as_ptr(),
+                amount.as_ptr(),
+                &mut tx,
+            )
+        };
+        assert_eq!(code, ApexErrorCode::Ok);
+        assert!(apex_last_error_message().is_null());
+
+        let mut json = ptr::null_mut();
+        let code = unsafe { apex_transaction_to_json(tx, &mut json) };
+        assert_eq!(code, ApexErrorCode::Ok);
+
+        let value: serde_json::Value =
+            serde_json::from_str(unsafe { CStr::from_ptr(json) }.to_str().unwrap()).unwrap();
+        assert_eq!(value["amount"], 1000);
+        assert_eq!(value["source_chain"], "Polygon");
+
+        unsafe {
+            apex_string_free(json);
+            apex_transaction_free(tx);
+        }
+    }
+
+    #[test]
+    fn test_transaction_build_invalid_amount() {
+        let from = CString::new("0x742D35Cc6634C0532925A3B844bC9e7595f0bEB7").unwrap();
+        let to = CString::new("0x1234567890123456789012345678901234567890").unwrap();
+        let amount = CString::new("1.5").unwrap();
+
+        let mut tx = ptr::null_mut();
+        let code = unsafe {
+            apex_transaction_build(
+                ApexChain::Ethereum as u32,
+                from.as_ptr(),
+                to.as_ptr(),
+                amount.as_ptr(),
+                &mut tx,
+            )
+        };
+        assert_eq!(code, ApexErrorCode::InvalidArgument);
+        assert!(tx.is_null());
+        assert!(last_error().contains("Invalid amount"));
+    }
+
+    #[test]
+    fn test_free_functions_accept_null() {
+        unsafe {
+            apex_sdk_free(ptr::null_mut());
+            apex_transaction_free(ptr::null_mut());
+            apex_string_free(ptr::null_mut());
+        }
+    }
+
+    #[test]
+    fn test_transaction_build_invalid_chain_and_address() {
+        let from = CString::new("0x742D35Cc6634C0532925A3B844bC9e7595f0bEB7").unwrap();
+        let to = CString::new("0x1234567890123456789012345678901234567890").unwrap();
+        let amount = CString::new("1").unwrap();
+
+        let mut tx = ptr::null_mut();
+        let code = unsafe {
+            apex_transaction_build(99, from.as_ptr(), to.as_ptr(), amount.as_ptr(), &mut tx)
+        };
+        assert_eq!(code, ApexErrorCode::InvalidArgument);
+        assert!(last_error().contains("Unknown chain 99"));
+
+        let bad = CString::new("0xabc").unwrap();
+        let code = unsafe {
+            apex_transaction_build(
+                ApexChain::Ethereum as u32,
+                from.as_ptr(),
+                bad.as_ptr(),
+                amount.as_ptr(),
+                &mut tx,
+            )
+        };
+        assert_eq!(code, ApexErrorCode::InvalidAddress);
+        assert!(tx.is_null());
+    }
+
+    #[test]
+    fn test_chain_arg() {
+        for chain in 0..=13 {
+            assert_eq!(ApexChain::try_from(chain).unwrap() as u32, chain);
+        }
+        assert!(chain_arg(14).is_err());
+        assert!(matches!(
+            chain_arg(ApexChain::Paseo as u32),
+            Ok(Chain::Paseo)
+        ));
+    }
+
+    #[test]
+    fn test_parse_address() {
+        assert!(matches!(
+            parse_address("0x1234567890123456789012345678901234567890"),
+            Ok(Address::Evm(_))
+        ));
+        assert!(matches!(
+            parse_address("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"),
+            Ok(Address::Substrate(_))
+        ));
+        for invalid in ["0xabc", "not an address", ""] {
+            assert!(parse_address(invalid).is_err());
+        }
+    }
+}
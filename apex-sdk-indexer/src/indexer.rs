@@ -0,0 +1,223 @@
+//! Following chains into a store
+
+use crate::source::ChainSource;
+use crate::store::IndexStore;
+use crate::Result;
+use apex_sdk::types::Chain;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Ingests blocks from chain sources into a store
+///
+/// Each source is followed from the block after the last one indexed for
+/// its chain. A block whose parent hash differs from the indexed parent
+/// means the chain reorganized: the indexed parent is rolled back and
+/// re-fetched, walking back until the two agree.
+pub struct Indexer<S: IndexStore> {
+    store: S,
+    sources: Vec<Arc<dyn ChainSource>>,
+    start_blocks: HashMap<Chain, u64>,
+    confirmations: u64,
+    batch_size: u64,
+    poll_interval: Duration,
+}
+
+impl<S: IndexStore> Indexer<S> {
+    /// Create an indexer writing to `store`
+    pub fn new(store: S) -> Self {
+        Self {
+            store,
+            sources: Vec::new(),
+            start_blocks: HashMap::new(),
+            confirmations: 0,
+            batch_size: 100,
+            poll_interval: Duration::from_secs(6),
+        }
+    }
+
+    /// Add a chain to index
+    pub fn with_source(mut self, source: impl ChainSource + 'static) -> Self {
+        self.sources.push(Arc::new(source));
+        self
+    }
+
+    /// Start indexing `chain` at block `number` when nothing is indexed yet,
+    /// instead of at the current head
+    pub fn with_start_block(mut self, chain: Chain, number: u64) -> Self {
+        self.start_blocks.insert(chain, number);
+        self
+    }
+
+    /// Only index blocks at least `confirmations` blocks below the head
+    pub fn with_confirmations(mut self, confirmations: u64) -> Self {
+        self.confirmations = confirmations;
+        self
+    }
+
+    /// Index at most `batch_size` blocks per chain in one pass
+    pub fn with_batch_size(mut self, batch_size: u64) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Set how long [`run`](Self::run) waits between passes
+    pub fn with_poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+
+    /// The store, for queries
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+
+    /// Index newly available blocks of every source, returning how many
+    /// were stored
+    ///
+    /// # Errors
+    ///
+    /// Stops at the first failing source; blocks already stored stay indexed.
+    pub async fn run_once(&self) -> Result<u64> {
+        let mut indexed = 0;
+        for source in &self.sources {
+            indexed += self.sync(source.as_ref()).await?;
+        }
+        Ok(indexed)
+    }
+
+    /// Index continuously, logging failed passes and retrying on the next
+    pub async fn run(&self) {
+        loop {
+            match self.run_once().await {
+                Ok(0) => {}
+                Ok(indexed) => tracing::debug!("Indexed {} blocks", indexed),
+                Err(e) => tracing::warn!("Indexing pass failed: {}", e),
+            }
+            tokio::time::sleep(self.poll_interval).await;
+        }
+    }
+
+    /// Index up to one batch of new blocks from `source`
+    async fn sync(&self, source: &dyn ChainSource) -> Result<u64> {
+        let chain = source.chain();
+        let head = source.head().await?.saturating_sub(self.confirmations);
+        let mut next = match self.store.latest_block(chain).await? {
+            Some(latest) => latest.number + 1,
+            None => self.start_blocks.get(chain).copied().unwrap_or(head),
+        };
+
+        let mut indexed = 0;
+        while next <= head && indexed < self.batch_size {
+            let Some(data) = source.block(next).await? else {
+                break;
+            };
+
+            if let Some(parent) = match next.checked_sub(1) {
+                Some(number) => self.store.block(chain, number).await?,
+                None => None,
+            } {
+                if parent.hash != data.block.parent_hash {
+                    tracing::warn!(
+                        "Reorg on {} at block {}: rolling back block {}",
+                        chain,
+                        next,
+                        parent.number
+                    );
+                    self.store.rollback(chain, parent.number).await?;
+                    next = parent.number;
+                    continue;
+                }
+            }
+
+            self.store.save(&data).await?;
+            indexed += 1;
+            next += 1;
+        }
+        Ok(indexed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::record::{BlockData, Query};
+    use crate::store::{fixtures, MemoryStore};
+    use async_trait::async_trait;
+    use std::sync::Mutex;
+
+    /// Source serving a replaceable list of blocks `1..`
+    struct FakeSource {
+        blocks: Mutex<Vec<BlockData>>,
+    }
+
+    #[async_trait]
+    impl ChainSource for Arc<FakeSource> {
+        fn chain(&self) -> &Chain {
+            &Chain::Ethereum
+        }
+
+        async fn head(&self) -> Result<u64> {
+            Ok(self.blocks.lock().unwrap().len() as u64)
+        }
+
+        async fn block(&self, number: u64) -> Result<Option<BlockData>> {
+            Ok(number
+                .checked_sub(1)
+                .and_then(|i| self.blocks.lock().unwrap().get(i as usize).cloned()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_catches_up_in_batches() {
+        let source = Arc::new(FakeSource {
+            blocks: Mutex::new(fixtures::chain(5)),
+        });
+        let indexer = Indexer::new(MemoryStore::new())
+            .with_source(source.clone())
+            .with_start_block(Chain::Ethereum, 1)
+            .with_batch_size(2)
+            .with_confirmations(1);
+
+        assert_eq!(indexer.run_once().await.unwrap(), 2);
+        assert_eq!(indexer.run_once().await.unwrap(), 2);
+        assert_eq!(indexer.run_once().await.unwrap(), 0);
+        assert_eq!(indexer.store().len(), 4);
+
+        let transactions = indexer
+            .store()
+            .transactions(&Query::new().address(fixtures::ALICE))
+            .await
+            .unwrap();
+        assert_eq!(transactions.len(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_rolls_back_reorganized_blocks() {
+        let source = Arc::new(FakeSource {
+            blocks: Mutex::new(fixtures::chain(3)),
+        });
+        let indexer = Indexer::new(MemoryStore::new())
+            .with_source(source.clone())
+            .with_start_block(Chain::Ethereum, 1);
+        assert_eq!(indexer.run_once().await.unwrap(), 3);
+
+        // Blocks 3 and 4 replace the old block 3
+        {
+            let mut blocks = source.blocks.lock().unwrap();
+            let mut fork = fixtures::block(3, &blocks[1].block.hash);
+            fork.block.hash = "0xf3".to_string();
+            let mut child = fixtures::block(4, "0xf3");
+            child.block.hash = "0xf4".to_string();
+            blocks[2] = fork;
+            blocks.push(child);
+        }
+
+        assert_eq!(indexer.run_once().await.unwrap(), 2);
+        let store = indexer.store();
+        let block = |n| async move { store.block(&Chain::Ethereum, n).await.unwrap().unwrap() };
+        assert_eq!(block(3).await.hash, "0xf3");
+        assert_eq!(block(4).await.hash, "0xf4");
+        assert_eq!(store.len(), 4);
+    }
+}
@@ -0,0 +1,88 @@
+//! # Apex SDK Indexer
+//!
+//! Continuously ingest blocks, transactions and events from chains reached
+//! through the Apex SDK into a database, and query them by address,
+//! contract or event type. Meant for applications with moderate data volumes
+//! that would rather not run a separate indexing stack.
+//!
+//! ## Components
+//!
+//! - [`ChainSource`]: fetches blocks from one chain ([`EvmSource`],
+//!   [`SubstrateSource`])
+//! - [`IndexStore`]: persists what was fetched and answers [`Query`]s
+//! - [`Indexer`]: follows each source from its last indexed block, rolling
+//!   back blocks that were reorganized away
+//!
+//! ## Stores
+//!
+//! - [`MemoryStore`]: in-process, useful for tests
+//! - `SqliteStore` (`sqlite` feature, default): a single database file
+//! - `PostgresStore` (`postgres` feature): a shared Postgres database
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use apex_sdk::evm::EvmAdapter;
+//! use apex_sdk::types::Chain;
+//! use apex_sdk_indexer::{sqlite::SqliteStore, EvmSource, IndexStore, Indexer, Query};
+//!
+//! # async fn run() -> apex_sdk_indexer::Result<()> {
+//! let adapter = EvmAdapter::connect("https://eth.llamarpc.com").await?;
+//! let indexer = Indexer::new(SqliteStore::open("chain.db")?)
+//!     .with_source(EvmSource::new(Chain::Ethereum, adapter))
+//!     .with_confirmations(12);
+//!
+//! indexer.run_once().await?;
+//! let transfers = indexer
+//!     .store()
+//!     .events(&Query::new().contract("0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48").limit(20))
+//!     .await?;
+//! println!("{} recent USDC events", transfers.len());
+//! # Ok(())
+//! # }
+//! ```
+
+pub mod indexer;
+pub mod record;
+pub mod source;
+pub mod store;
+
+#[cfg(feature = "postgres")]
+pub mod postgres;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+
+pub use indexer::Indexer;
+pub use record::{BlockData, IndexedEvent, IndexedTransaction, Query};
+pub use source::{ChainSource, EvmSource, SubstrateSource};
+pub use store::{IndexStore, MemoryStore};
+
+use thiserror::Error;
+
+/// Indexer error
+#[derive(Error, Debug)]
+pub enum IndexerError {
+    #[error("Storage error: {0}")]
+    Storage(String),
+
+    #[error("Source error: {0}")]
+    Source(String),
+
+    #[error("SDK error: {0}")]
+    Sdk(#[from] apex_sdk::Error),
+}
+
+impl From<apex_sdk::evm::Error> for IndexerError {
+    fn from(error: apex_sdk::evm::Error) -> Self {
+        IndexerError::Sdk(error.into())
+    }
+}
+
+impl From<apex_sdk::substrate::Error> for IndexerError {
+    fn from(error: apex_sdk::substrate::Error) -> Self {
+        IndexerError::Sdk(error.into())
+    }
+}
+
+/// Result type for indexer operations
+pub type Result<T> = std::result::Result<T, IndexerError>;
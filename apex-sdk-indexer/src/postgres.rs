@@ -0,0 +1,230 @@
+//! Postgres store
+//!
+//! Shares the schema of the SQLite store, so several applications can query
+//! an index written by one indexer.
+
+use crate::record::{BlockData, IndexedEvent, IndexedTransaction, Query};
+use crate::store::{
+    from_json, parse_chain, select_sql, to_json, transaction_params, IndexStore, SqlParam, SCHEMA,
+    UPSERT_TRANSACTION,
+};
+use crate::{IndexerError, Result};
+use apex_sdk::types::{BlockInfo, Chain};
+use async_trait::async_trait;
+use tokio::sync::Mutex;
+use tokio_postgres::types::ToSql;
+use tokio_postgres::{Client, NoTls};
+
+/// Store backed by a Postgres database
+pub struct PostgresStore {
+    client: Mutex<Client>,
+}
+
+impl PostgresStore {
+    /// Connect with a libpq-style connection string, e.g.
+    /// `host=localhost user=indexer dbname=chain`, and create missing tables
+    pub async fn connect(config: &str) -> Result<Self> {
+        let (client, connection) = tokio_postgres::connect(config, NoTls)
+            .await
+            .map_err(|e| IndexerError::Storage(format!("Failed to connect to Postgres: {}", e)))?;
+        tokio::spawn(async move {
+            if let Err(e) = connection.await {
+                tracing::error!("Postgres connection closed: {}", e);
+            }
+        });
+
+        client.batch_execute(SCHEMA).await.map_err(storage_error)?;
+        Ok(Self {
+            client: Mutex::new(client),
+        })
+    }
+
+    async fn block_data(
+        &self,
+        sql: &str,
+        params: &[&(dyn ToSql + Sync)],
+    ) -> Result<Option<BlockInfo>> {
+        let row = self
+            .client
+            .lock()
+            .await
+            .query_opt(sql, params)
+            .await
+            .map_err(storage_error)?;
+        row.map(|row| from_json(&row.get::<_, String>(0)))
+            .transpose()
+    }
+}
+
+#[async_trait]
+impl IndexStore for PostgresStore {
+    async fn save(&self, data: &BlockData) -> Result<()> {
+        let chain = data.chain.to_string();
+        let number = data.block.number as i64;
+        let mut client = self.client.lock().await;
+        let tx = client.transaction().await.map_err(storage_error)?;
+        delete_from(&tx, &chain, number, "=").await?;
+
+        tx.execute(
+            "INSERT INTO blocks (chain, number, hash, data) VALUES ($1, $2, $3, $4)",
+            &[&chain, &number, &data.block.hash, &to_json(&data.block)?],
+        )
+        .await
+        .map_err(storage_error)?;
+        for transaction in &data.transactions {
+            let params = sql_params(transaction_params(transaction));
+            tx.execute(UPSERT_TRANSACTION, &refs(&params))
+                .await
+                .map_err(storage_error)?;
+        }
+        for event in &data.events {
+            let indexed = IndexedEvent {
+                chain: data.chain.clone(),
+                event: event.clone(),
+            };
+            tx.execute(
+                "INSERT INTO events (chain, block_number, position, contract, event_type, data)
+                 VALUES ($1, $2, $3, $4, $5, $6)",
+                &[
+                    &chain,
+                    &number,
+                    &(event.index as i64),
+                    &indexed.contract(),
+                    &indexed.event_type(),
+                    &to_json(event)?,
+                ],
+            )
+            .await
+            .map_err(storage_error)?;
+        }
+        tx.commit().await.map_err(storage_error)
+    }
+
+    async fn rollback(&self, chain: &Chain, number: u64) -> Result<()> {
+        let mut client = self.client.lock().await;
+        let tx = client.transaction().await.map_err(storage_error)?;
+        delete_from(&tx, &chain.to_string(), number as i64, ">=").await?;
+        tx.commit().await.map_err(storage_error)
+    }
+
+    async fn latest_block(&self, chain: &Chain) -> Result<Option<BlockInfo>> {
+        self.block_data(
+            "SELECT data FROM blocks WHERE chain = $1 ORDER BY number DESC LIMIT 1",
+            &[&chain.to_string()],
+        )
+        .await
+    }
+
+    async fn block(&self, chain: &Chain, number: u64) -> Result<Option<BlockInfo>> {
+        self.block_data(
+            "SELECT data FROM blocks WHERE chain = $1 AND number = $2",
+            &[&chain.to_string(), &(number as i64)],
+        )
+        .await
+    }
+
+    async fn transactions(&self, query: &Query) -> Result<Vec<IndexedTransaction>> {
+        let (sql, params) = select_sql(
+            "transactions",
+            "chain, hash, block_number, position, sender, recipient, value, call",
+            query,
+        );
+        let params = sql_params(params);
+        let rows = self
+            .client
+            .lock()
+            .await
+            .query(&sql, &refs(&params))
+            .await
+            .map_err(storage_error)?;
+
+        rows.iter()
+            .map(|row| {
+                Ok(IndexedTransaction {
+                    chain: parse_chain(row.get(0))?,
+                    hash: row.get(1),
+                    block_number: row.get::<_, i64>(2) as u64,
+                    index: row.get::<_, i64>(3) as u32,
+                    from: row.get(4),
+                    to: row.get(5),
+                    value: row.get(6),
+                    call: row.get(7),
+                })
+            })
+            .collect()
+    }
+
+    async fn events(&self, query: &Query) -> Result<Vec<IndexedEvent>> {
+        let (sql, params) = select_sql("events", "chain, data", query);
+        let params = sql_params(params);
+        let rows = self
+            .client
+            .lock()
+            .await
+            .query(&sql, &refs(&params))
+            .await
+            .map_err(storage_error)?;
+
+        rows.iter()
+            .map(|row| {
+                Ok(IndexedEvent {
+                    chain: parse_chain(row.get(0))?,
+                    event: from_json(row.get(1))?,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Delete blocks whose number compares to `number` by `op`, with their
+/// transactions and events
+async fn delete_from(
+    tx: &tokio_postgres::Transaction<'_>,
+    chain: &str,
+    number: i64,
+    op: &str,
+) -> Result<()> {
+    for (table, column) in [
+        ("blocks", "number"),
+        ("transactions", "block_number"),
+        ("events", "block_number"),
+    ] {
+        tx.execute(
+            &format!(
+                "DELETE FROM {} WHERE chain = $1 AND {} {} $2",
+                table, column, op
+            ),
+            &[&chain, &number],
+        )
+        .await
+        .map_err(storage_error)?;
+    }
+    Ok(())
+}
+
+/// Owned Postgres parameters
+type Params = Vec<Box<dyn ToSql + Sync + Send>>;
+
+fn sql_params(params: Vec<SqlParam>) -> Params {
+    params
+        .into_iter()
+        .map(|param| -> Box<dyn ToSql + Sync + Send> {
+            match param {
+                SqlParam::Text(text) => Box::new(text),
+                SqlParam::Int(int) => Box::new(int),
+                SqlParam::Null => Box::new(None::<String>),
+            }
+        })
+        .collect()
+}
+
+fn refs(params: &Params) -> Vec<&(dyn ToSql + Sync)> {
+    params
+        .iter()
+        .map(|param| param.as_ref() as &(dyn ToSql + Sync))
+        .collect()
+}
+
+fn storage_error(error: tokio_postgres::Error) -> IndexerError {
+    IndexerError::Storage(format!("Postgres: {}", error))
+}
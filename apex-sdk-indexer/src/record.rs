@@ -0,0 +1,193 @@
+//! Indexed records and queries over them
+
+use apex_sdk::types::{BlockInfo, Chain, Event};
+
+/// A transaction or extrinsic included in an indexed block
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexedTransaction {
+    /// Chain the transaction was included on
+    pub chain: Chain,
+    /// Transaction hash
+    pub hash: String,
+    /// Number of the including block
+    pub block_number: u64,
+    /// Position within the block
+    pub index: u32,
+    /// Sender, `None` for unsigned extrinsics
+    pub from: Option<String>,
+    /// Recipient, `None` for contract creations and extrinsics
+    pub to: Option<String>,
+    /// Value transferred in the native token's smallest unit, EVM only
+    pub value: Option<String>,
+    /// Call made: `Pallet.call` for extrinsics, the 4-byte selector for
+    /// EVM contract calls
+    pub call: Option<String>,
+}
+
+/// An event emitted in an indexed block
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexedEvent {
+    /// Chain the event was emitted on
+    pub chain: Chain,
+    /// The event
+    pub event: Event,
+}
+
+impl IndexedEvent {
+    /// Contract or account that emitted the event, if known
+    pub fn contract(&self) -> Option<String> {
+        self.event
+            .origin
+            .as_ref()
+            .map(|origin| normalize(origin.as_str()))
+    }
+
+    /// Event type: `Pallet.Variant` for Substrate, the first topic for EVM logs
+    pub fn event_type(&self) -> String {
+        normalize(&self.event.name())
+    }
+}
+
+/// Everything indexed for one block
+#[derive(Debug, Clone)]
+pub struct BlockData {
+    /// Chain the block belongs to
+    pub chain: Chain,
+    /// Block header
+    pub block: BlockInfo,
+    /// Transactions in block order
+    pub transactions: Vec<IndexedTransaction>,
+    /// Events in block order
+    pub events: Vec<Event>,
+}
+
+/// Criteria for querying indexed transactions and events
+///
+/// `chain` and the block range apply to both; `address` matches the sender
+/// or recipient of transactions, `contract` and `event_type` match events.
+/// Results are ordered newest first.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Query {
+    /// Only records on this chain
+    pub chain: Option<Chain>,
+    /// Only transactions sent from or to this address
+    pub address: Option<String>,
+    /// Only events emitted by this contract or account
+    pub contract: Option<String>,
+    /// Only events of this type
+    pub event_type: Option<String>,
+    /// Only records in this block or later
+    pub from_block: Option<u64>,
+    /// Only records in this block or earlier
+    pub to_block: Option<u64>,
+    /// At most this many records
+    pub limit: Option<usize>,
+}
+
+impl Query {
+    /// Match everything
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only records on `chain`
+    pub fn chain(mut self, chain: Chain) -> Self {
+        self.chain = Some(chain);
+        self
+    }
+
+    /// Only transactions sent from or to `address`
+    pub fn address(mut self, address: impl AsRef<str>) -> Self {
+        self.address = Some(normalize(address.as_ref()));
+        self
+    }
+
+    /// Only events emitted by `contract`
+    pub fn contract(mut self, contract: impl AsRef<str>) -> Self {
+        self.contract = Some(normalize(contract.as_ref()));
+        self
+    }
+
+    /// Only events of `event_type`, e.g. `Balances.Transfer` or a topic hash
+    pub fn event_type(mut self, event_type: impl AsRef<str>) -> Self {
+        self.event_type = Some(normalize(event_type.as_ref()));
+        self
+    }
+
+    /// Only records in blocks `from..=to`
+    pub fn blocks(mut self, from: u64, to: u64) -> Self {
+        self.from_block = Some(from);
+        self.to_block = Some(to);
+        self
+    }
+
+    /// Return at most `limit` records
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Whether `tx` matches
+    pub fn matches_transaction(&self, tx: &IndexedTransaction) -> bool {
+        self.matches_block(&tx.chain, tx.block_number)
+            && self.address.as_ref().is_none_or(|address| {
+                [&tx.from, &tx.to]
+                    .into_iter()
+                    .flatten()
+                    .any(|a| normalize(a) == *address)
+            })
+    }
+
+    /// Whether `event` matches
+    pub fn matches_event(&self, event: &IndexedEvent) -> bool {
+        self.matches_block(&event.chain, event.event.block_number)
+            && self
+                .contract
+                .as_ref()
+                .is_none_or(|c| event.contract().as_ref() == Some(c))
+            && self
+                .event_type
+                .as_ref()
+                .is_none_or(|t| event.event_type() == *t)
+    }
+
+    fn matches_block(&self, chain: &Chain, number: u64) -> bool {
+        self.chain.as_ref().is_none_or(|c| c == chain)
+            && self.from_block.is_none_or(|from| number >= from)
+            && self.to_block.is_none_or(|to| number <= to)
+    }
+}
+
+/// Lowercase hex identifiers so lookups ignore checksum casing; SS58
+/// addresses are case-sensitive and kept as they are
+pub(crate) fn normalize(value: &str) -> String {
+    if value.starts_with("0x") {
+        value.to_ascii_lowercase()
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_address_query_ignores_checksum_case() {
+        let tx = IndexedTransaction {
+            chain: Chain::Ethereum,
+            hash: "0x01".to_string(),
+            block_number: 10,
+            index: 0,
+            from: Some("0xAbC0000000000000000000000000000000000001".to_string()),
+            to: None,
+            value: Some("0".to_string()),
+            call: None,
+        };
+
+        let query = Query::new().address("0xabc0000000000000000000000000000000000001");
+        assert!(query.matches_transaction(&tx));
+        assert!(!query.clone().chain(Chain::Polygon).matches_transaction(&tx));
+        assert!(!query.blocks(11, 20).matches_transaction(&tx));
+    }
+}
@@ -0,0 +1,141 @@
+//! Chains blocks are indexed from
+
+use crate::record::{BlockData, IndexedTransaction};
+use crate::Result;
+use apex_sdk::evm::EvmAdapter;
+use apex_sdk::substrate::SubstrateAdapter;
+use apex_sdk::types::{BlockInfo, Chain, ChainType, Finality};
+use async_trait::async_trait;
+
+/// A chain the indexer fetches blocks from
+#[async_trait]
+pub trait ChainSource: Send + Sync {
+    /// Chain the blocks belong to
+    fn chain(&self) -> &Chain;
+
+    /// Number of the newest block available for indexing
+    async fn head(&self) -> Result<u64>;
+
+    /// Block `number` with its transactions and events, `None` if it does
+    /// not exist yet
+    async fn block(&self, number: u64) -> Result<Option<BlockData>>;
+}
+
+/// Blocks of an EVM chain, with their transactions and logs
+pub struct EvmSource {
+    chain: Chain,
+    adapter: EvmAdapter,
+}
+
+impl EvmSource {
+    /// Index `chain` through `adapter`
+    pub fn new(chain: Chain, adapter: EvmAdapter) -> Self {
+        Self { chain, adapter }
+    }
+}
+
+#[async_trait]
+impl ChainSource for EvmSource {
+    fn chain(&self) -> &Chain {
+        &self.chain
+    }
+
+    async fn head(&self) -> Result<u64> {
+        Ok(self.adapter.block_number().await?)
+    }
+
+    async fn block(&self, number: u64) -> Result<Option<BlockData>> {
+        let Some(block) = self.adapter.get_block_with_transactions(number).await? else {
+            return Ok(None);
+        };
+        let Some(hash) = block.hash else {
+            // Still pending
+            return Ok(None);
+        };
+
+        let transactions = block
+            .transactions
+            .iter()
+            .enumerate()
+            .map(|(index, tx)| IndexedTransaction {
+                chain: self.chain.clone(),
+                hash: format!("{:?}", tx.hash),
+                block_number: number,
+                index: tx.transaction_index.map_or(index as u32, |i| i.as_u32()),
+                from: Some(format!("{:?}", tx.from)),
+                to: tx.to.map(|to| format!("{:?}", to)),
+                value: Some(tx.value.to_string()),
+                call: tx
+                    .input
+                    .get(..4)
+                    .map(|selector| format!("0x{}", hex::encode(selector))),
+            })
+            .collect();
+
+        Ok(Some(BlockData {
+            chain: self.chain.clone(),
+            block: BlockInfo {
+                chain_type: ChainType::Evm,
+                number,
+                hash: format!("{:?}", hash),
+                parent_hash: format!("{:?}", block.parent_hash),
+                timestamp: Some(block.timestamp.as_u64()),
+                finality: Finality::Latest,
+            },
+            transactions,
+            events: self.adapter.get_logs(number, number).await?,
+        }))
+    }
+}
+
+/// Finalized blocks of a Substrate chain, with their extrinsics and events
+pub struct SubstrateSource {
+    chain: Chain,
+    adapter: SubstrateAdapter,
+}
+
+impl SubstrateSource {
+    /// Index `chain` through `adapter`
+    pub fn new(chain: Chain, adapter: SubstrateAdapter) -> Self {
+        Self { chain, adapter }
+    }
+}
+
+#[async_trait]
+impl ChainSource for SubstrateSource {
+    fn chain(&self) -> &Chain {
+        &self.chain
+    }
+
+    async fn head(&self) -> Result<u64> {
+        Ok(self.adapter.finalized_block_number().await?)
+    }
+
+    async fn block(&self, number: u64) -> Result<Option<BlockData>> {
+        let Some(contents) = self.adapter.get_block(number).await? else {
+            return Ok(None);
+        };
+
+        let transactions = contents
+            .extrinsics
+            .into_iter()
+            .map(|ext| IndexedTransaction {
+                chain: self.chain.clone(),
+                hash: ext.hash,
+                block_number: number,
+                index: ext.index,
+                from: ext.signer,
+                to: None,
+                value: None,
+                call: Some(format!("{}.{}", ext.pallet, ext.call)),
+            })
+            .collect();
+
+        Ok(Some(BlockData {
+            chain: self.chain.clone(),
+            block: contents.info,
+            transactions,
+            events: contents.events,
+        }))
+    }
+}
@@ -0,0 +1,271 @@
+//! SQLite store
+//!
+//! Keeps the index in a single database file. Statements run on Tokio's
+//! blocking pool, one at a time over a shared connection, which suits the
+//! moderate write rates of a single indexer.
+
+use crate::record::{BlockData, IndexedEvent, IndexedTransaction, Query};
+use crate::store::{
+    from_json, parse_chain, select_sql, to_json, transaction_params, IndexStore, SqlParam, SCHEMA,
+    UPSERT_TRANSACTION,
+};
+use crate::{IndexerError, Result};
+use apex_sdk::types::{BlockInfo, Chain};
+use async_trait::async_trait;
+use rusqlite::types::Value;
+use rusqlite::{params, params_from_iter, Connection, OptionalExtension};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+/// Store backed by a SQLite database
+#[derive(Clone)]
+pub struct SqliteStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl SqliteStore {
+    /// Open (or create) the database at `path`
+    #[allow(clippy::result_large_err)]
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let conn = Connection::open(path).map_err(storage_error)?;
+        // WAL lets readers query while the indexer writes
+        conn.pragma_update(None, "journal_mode", "WAL")
+            .map_err(storage_error)?;
+        Self::with_connection(conn)
+    }
+
+    /// Create a database that lives only as long as the store
+    #[allow(clippy::result_large_err)]
+    pub fn in_memory() -> Result<Self> {
+        Self::with_connection(Connection::open_in_memory().map_err(storage_error)?)
+    }
+
+    #[allow(clippy::result_large_err)]
+    fn with_connection(conn: Connection) -> Result<Self> {
+        conn.execute_batch(SCHEMA).map_err(storage_error)?;
+        Ok(Self {
+            conn: Arc::new(Mutex::new(conn)),
+        })
+    }
+
+    /// Run `f` with the connection on the blocking pool
+    #[allow(clippy::result_large_err)]
+    async fn with<T, F>(&self, f: F) -> Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut Connection) -> Result<T> + Send + 'static,
+    {
+        let conn = self.conn.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut conn = conn.lock().unwrap_or_else(|e| e.into_inner());
+            f(&mut conn)
+        })
+        .await
+        .map_err(|e| IndexerError::Storage(format!("SQLite task failed: {}", e)))?
+    }
+}
+
+#[async_trait]
+impl IndexStore for SqliteStore {
+    #[allow(clippy::result_large_err)]
+    async fn save(&self, data: &BlockData) -> Result<()> {
+        let data = data.clone();
+        self.with(move |conn| {
+            let tx = conn.transaction().map_err(storage_error)?;
+            let chain = data.chain.to_string();
+            let number = data.block.number as i64;
+            delete_from(&tx, &chain, number, "=")?;
+
+            tx.execute(
+                "INSERT INTO blocks (chain, number, hash, data) VALUES ($1, $2, $3, $4)",
+                params![chain, number, data.block.hash, to_json(&data.block)?],
+            )
+            .map_err(storage_error)?;
+            for transaction in &data.transactions {
+                tx.execute(
+                    UPSERT_TRANSACTION,
+                    params_from_iter(values(transaction_params(transaction))),
+                )
+                .map_err(storage_error)?;
+            }
+            for event in &data.events {
+                let indexed = IndexedEvent {
+                    chain: data.chain.clone(),
+                    event: event.clone(),
+                };
+                tx.execute(
+                    "INSERT INTO events (chain, block_number, position, contract, event_type, data)
+                     VALUES ($1, $2, $3, $4, $5, $6)",
+                    params![
+                        chain,
+                        number,
+                        event.index as i64,
+                        indexed.contract(),
+                        indexed.event_type(),
+                        to_json(event)?
+                    ],
+                )
+                .map_err(storage_error)?;
+            }
+            tx.commit().map_err(storage_error)
+        })
+        .await
+    }
+
+    #[allow(clippy::result_large_err)]
+    async fn rollback(&self, chain: &Chain, number: u64) -> Result<()> {
+        let chain = chain.to_string();
+        self.with(move |conn| {
+            let tx = conn.transaction().map_err(storage_error)?;
+            delete_from(&tx, &chain, number as i64, ">=")?;
+            tx.commit().map_err(storage_error)
+        })
+        .await
+    }
+
+    #[allow(clippy::result_large_err)]
+    async fn latest_block(&self, chain: &Chain) -> Result<Option<BlockInfo>> {
+        let chain = chain.to_string();
+        self.with(move |conn| {
+            let data: Option<String> = conn
+                .query_row(
+                    "SELECT data FROM blocks WHERE chain = $1 ORDER BY number DESC LIMIT 1",
+                    params![chain],
+                    |row| row.get(0),
+                )
+                .optional()
+                .map_err(storage_error)?;
+            data.map(|data| from_json(&data)).transpose()
+        })
+        .await
+    }
+
+    #[allow(clippy::result_large_err)]
+    async fn block(&self, chain: &Chain, number: u64) -> Result<Option<BlockInfo>> {
+        let chain = chain.to_string();
+        self.with(move |conn| {
+            let data: Option<String> = conn
+                .query_row(
+                    "SELECT data FROM blocks WHERE chain = $1 AND number = $2",
+                    params![chain, number as i64],
+                    |row| row.get(0),
+                )
+                .optional()
+                .map_err(storage_error)?;
+            data.map(|data| from_json(&data)).transpose()
+        })
+        .await
+    }
+
+    #[allow(clippy::result_large_err)]
+    async fn transactions(&self, query: &Query) -> Result<Vec<IndexedTransaction>> {
+        let (sql, params) = select_sql(
+            "transactions",
+            "chain, hash, block_number, position, sender, recipient, value, call",
+            query,
+        );
+        self.with(move |conn| {
+            let mut statement = conn.prepare(&sql).map_err(storage_error)?;
+            let rows = statement
+                .query_map(params_from_iter(values(params)), |row| {
+                    Ok((
+                        row.get::<_, String>(0)?,
+                        row.get(1)?,
+                        row.get::<_, i64>(2)?,
+                        row.get::<_, i64>(3)?,
+                        row.get(4)?,
+                        row.get(5)?,
+                        row.get(6)?,
+                        row.get(7)?,
+                    ))
+                })
+                .map_err(storage_error)?;
+
+            let mut transactions = Vec::new();
+            for row in rows {
+                let (chain, hash, block_number, index, from, to, value, call) =
+                    row.map_err(storage_error)?;
+                transactions.push(IndexedTransaction {
+                    chain: parse_chain(&chain)?,
+                    hash,
+                    block_number: block_number as u64,
+                    index: index as u32,
+                    from,
+                    to,
+                    value,
+                    call,
+                });
+            }
+            Ok(transactions)
+        })
+        .await
+    }
+
+    #[allow(clippy::result_large_err)]
+    async fn events(&self, query: &Query) -> Result<Vec<IndexedEvent>> {
+        let (sql, params) = select_sql("events", "chain, data", query);
+        self.with(move |conn| {
+            let mut statement = conn.prepare(&sql).map_err(storage_error)?;
+            let rows = statement
+                .query_map(params_from_iter(values(params)), |row| {
+                    Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+                })
+                .map_err(storage_error)?;
+
+            let mut events = Vec::new();
+            for row in rows {
+                let (chain, data) = row.map_err(storage_error)?;
+                events.push(IndexedEvent {
+                    chain: parse_chain(&chain)?,
+                    event: from_json(&data)?,
+                });
+            }
+            Ok(events)
+        })
+        .await
+    }
+}
+
+/// Delete blocks whose number compares to `number` by `op`, with their
+/// transactions and events
+#[allow(clippy::result_large_err)]
+fn delete_from(conn: &Connection, chain: &str, number: i64, op: &str) -> Result<()> {
+    for (table, column) in [
+        ("blocks", "number"),
+        ("transactions", "block_number"),
+        ("events", "block_number"),
+    ] {
+        conn.execute(
+            &format!(
+                "DELETE FROM {} WHERE chain = $1 AND {} {} $2",
+                table, column, op
+            ),
+            params![chain, number],
+        )
+        .map_err(storage_error)?;
+    }
+    Ok(())
+}
+
+fn values(params: Vec<SqlParam>) -> impl Iterator<Item = Value> {
+    params.into_iter().map(|param| match param {
+        SqlParam::Text(text) => Value::Text(text),
+        SqlParam::Int(int) => Value::Integer(int),
+        SqlParam::Null => Value::Null,
+    })
+}
+
+fn storage_error(error: rusqlite::Error) -> IndexerError {
+    IndexerError::Storage(format!("SQLite: {}", error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::fixtures;
+
+    #[tokio::test]
+    async fn test_sqlite_store() {
+        fixtures::check_store(&SqliteStore::in_memory().unwrap()).await;
+    }
+}
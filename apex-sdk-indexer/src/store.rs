@@ -0,0 +1,481 @@
+//! Store abstraction and in-memory implementation
+
+use crate::record::{normalize, BlockData, IndexedEvent, IndexedTransaction, Query};
+use crate::Result;
+use apex_sdk::types::{BlockInfo, Chain};
+use async_trait::async_trait;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+
+/// Persistent storage for indexed chain data
+#[async_trait]
+pub trait IndexStore: Send + Sync {
+    /// Store a block with its transactions and events, replacing whatever
+    /// was stored for the same block number
+    async fn save(&self, data: &BlockData) -> Result<()>;
+
+    /// Remove block `number` and everything after it on `chain`
+    async fn rollback(&self, chain: &Chain, number: u64) -> Result<()>;
+
+    /// Highest indexed block on `chain`
+    async fn latest_block(&self, chain: &Chain) -> Result<Option<BlockInfo>>;
+
+    /// Indexed block `number` on `chain`
+    async fn block(&self, chain: &Chain, number: u64) -> Result<Option<BlockInfo>>;
+
+    /// Transactions matching `query`, newest first
+    async fn transactions(&self, query: &Query) -> Result<Vec<IndexedTransaction>>;
+
+    /// Events matching `query`, newest first
+    async fn events(&self, query: &Query) -> Result<Vec<IndexedEvent>>;
+}
+
+#[async_trait]
+impl<S: IndexStore + ?Sized> IndexStore for Arc<S> {
+    async fn save(&self, data: &BlockData) -> Result<()> {
+        (**self).save(data).await
+    }
+
+    async fn rollback(&self, chain: &Chain, number: u64) -> Result<()> {
+        (**self).rollback(chain, number).await
+    }
+
+    async fn latest_block(&self, chain: &Chain) -> Result<Option<BlockInfo>> {
+        (**self).latest_block(chain).await
+    }
+
+    async fn block(&self, chain: &Chain, number: u64) -> Result<Option<BlockInfo>> {
+        (**self).block(chain, number).await
+    }
+
+    async fn transactions(&self, query: &Query) -> Result<Vec<IndexedTransaction>> {
+        (**self).transactions(query).await
+    }
+
+    async fn events(&self, query: &Query) -> Result<Vec<IndexedEvent>> {
+        (**self).events(query).await
+    }
+}
+
+/// Indexed blocks by chain and number
+type Blocks = HashMap<Chain, BTreeMap<u64, BlockData>>;
+
+/// Store keeping indexed data in memory
+#[derive(Debug, Clone, Default)]
+pub struct MemoryStore {
+    blocks: Arc<Mutex<Blocks>>,
+}
+
+impl MemoryStore {
+    /// Create an empty store
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of indexed blocks across chains
+    pub fn len(&self) -> usize {
+        self.blocks
+            .lock()
+            .unwrap()
+            .values()
+            .map(BTreeMap::len)
+            .sum()
+    }
+
+    /// Whether nothing has been indexed yet
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Blocks matching the chain and block range of `query`, newest first
+    fn matching(&self, query: &Query) -> Vec<BlockData> {
+        let blocks = self.blocks.lock().unwrap();
+        let mut matching: Vec<BlockData> = blocks
+            .iter()
+            .filter(|(chain, _)| query.chain.as_ref().is_none_or(|c| c == *chain))
+            .flat_map(|(_, blocks)| {
+                blocks
+                    .range(query.from_block.unwrap_or(0)..=query.to_block.unwrap_or(u64::MAX))
+                    .map(|(_, data)| data.clone())
+            })
+            .collect();
+        matching.sort_by_key(|data| std::cmp::Reverse(data.block.number));
+        matching
+    }
+}
+
+#[async_trait]
+impl IndexStore for MemoryStore {
+    async fn save(&self, data: &BlockData) -> Result<()> {
+        self.blocks
+            .lock()
+            .unwrap()
+            .entry(data.chain.clone())
+            .or_default()
+            .insert(data.block.number, data.clone());
+        Ok(())
+    }
+
+    async fn rollback(&self, chain: &Chain, number: u64) -> Result<()> {
+        if let Some(blocks) = self.blocks.lock().unwrap().get_mut(chain) {
+            blocks.split_off(&number);
+        }
+        Ok(())
+    }
+
+    async fn latest_block(&self, chain: &Chain) -> Result<Option<BlockInfo>> {
+        Ok(self
+            .blocks
+            .lock()
+            .unwrap()
+            .get(chain)
+            .and_then(|blocks| blocks.last_key_value())
+            .map(|(_, data)| data.block.clone()))
+    }
+
+    async fn block(&self, chain: &Chain, number: u64) -> Result<Option<BlockInfo>> {
+        Ok(self
+            .blocks
+            .lock()
+            .unwrap()
+            .get(chain)
+            .and_then(|blocks| blocks.get(&number))
+            .map(|data| data.block.clone()))
+    }
+
+    async fn transactions(&self, query: &Query) -> Result<Vec<IndexedTransaction>> {
+        Ok(self
+            .matching(query)
+            .into_iter()
+            .flat_map(|data| data.transactions.into_iter().rev())
+            .filter(|tx| query.matches_transaction(tx))
+            .take(query.limit.unwrap_or(usize::MAX))
+            .collect())
+    }
+
+    async fn events(&self, query: &Query) -> Result<Vec<IndexedEvent>> {
+        Ok(self
+            .matching(query)
+            .into_iter()
+            .flat_map(|data| {
+                let chain = data.chain;
+                data.events
+                    .into_iter()
+                    .rev()
+                    .map(move |event| IndexedEvent {
+                        chain: chain.clone(),
+                        event,
+                    })
+            })
+            .filter(|event| query.matches_event(event))
+            .take(query.limit.unwrap_or(usize::MAX))
+            .collect())
+    }
+}
+
+/// Tables and indexes shared by the SQL stores
+#[cfg(any(feature = "sqlite", feature = "postgres"))]
+pub(crate) const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS blocks (
+    chain TEXT NOT NULL,
+    number BIGINT NOT NULL,
+    hash TEXT NOT NULL,
+    data TEXT NOT NULL,
+    PRIMARY KEY (chain, number)
+);
+CREATE TABLE IF NOT EXISTS transactions (
+    chain TEXT NOT NULL,
+    hash TEXT NOT NULL,
+    block_number BIGINT NOT NULL,
+    position BIGINT NOT NULL,
+    sender TEXT,
+    recipient TEXT,
+    value TEXT,
+    call TEXT,
+    PRIMARY KEY (chain, hash)
+);
+CREATE INDEX IF NOT EXISTS transactions_sender ON transactions (sender);
+CREATE INDEX IF NOT EXISTS transactions_recipient ON transactions (recipient);
+CREATE INDEX IF NOT EXISTS transactions_block ON transactions (chain, block_number);
+CREATE TABLE IF NOT EXISTS events (
+    chain TEXT NOT NULL,
+    block_number BIGINT NOT NULL,
+    position BIGINT NOT NULL,
+    contract TEXT,
+    event_type TEXT NOT NULL,
+    data TEXT NOT NULL,
+    PRIMARY KEY (chain, block_number, position)
+);
+CREATE INDEX IF NOT EXISTS events_contract ON events (contract);
+CREATE INDEX IF NOT EXISTS events_type ON events (event_type);
+";
+
+/// Upsert of one transaction row, `$1..$8` in column order
+#[cfg(any(feature = "sqlite", feature = "postgres"))]
+pub(crate) const UPSERT_TRANSACTION: &str = "
+INSERT INTO transactions (chain, hash, block_number, position, sender, recipient, value, call)
+VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+ON CONFLICT (chain, hash) DO UPDATE SET
+    block_number = excluded.block_number,
+    position = excluded.position,
+    sender = excluded.sender,
+    recipient = excluded.recipient,
+    value = excluded.value,
+    call = excluded.call";
+
+/// A bound SQL parameter
+#[cfg(any(feature = "sqlite", feature = "postgres"))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum SqlParam {
+    Text(String),
+    Int(i64),
+    Null,
+}
+
+/// `SELECT` for `query` over `transactions` or `events`, with `$n`
+/// placeholders understood by both SQLite and Postgres
+#[cfg(any(feature = "sqlite", feature = "postgres"))]
+pub(crate) fn select_sql(table: &str, columns: &str, query: &Query) -> (String, Vec<SqlParam>) {
+    let mut conditions = Vec::new();
+    let mut params = Vec::new();
+    let bind = |param: SqlParam, params: &mut Vec<SqlParam>| {
+        params.push(param);
+        format!("${}", params.len())
+    };
+
+    if let Some(chain) = &query.chain {
+        let p = bind(SqlParam::Text(chain.to_string()), &mut params);
+        conditions.push(format!("chain = {}", p));
+    }
+    if let Some(from) = query.from_block {
+        let p = bind(SqlParam::Int(from as i64), &mut params);
+        conditions.push(format!("block_number >= {}", p));
+    }
+    if let Some(to) = query.to_block {
+        let p = bind(SqlParam::Int(to.min(i64::MAX as u64) as i64), &mut params);
+        conditions.push(format!("block_number <= {}", p));
+    }
+    if table == "transactions" {
+        if let Some(address) = &query.address {
+            let p = bind(SqlParam::Text(address.clone()), &mut params);
+            conditions.push(format!("(sender = {} OR recipient = {})", p, p));
+        }
+    } else {
+        if let Some(contract) = &query.contract {
+            let p = bind(SqlParam::Text(contract.clone()), &mut params);
+            conditions.push(format!("contract = {}", p));
+        }
+        if let Some(event_type) = &query.event_type {
+            let p = bind(SqlParam::Text(event_type.clone()), &mut params);
+            conditions.push(format!("event_type = {}", p));
+        }
+    }
+
+    let mut sql = format!("SELECT {} FROM {}", columns, table);
+    if !conditions.is_empty() {
+        sql.push_str(" WHERE ");
+        sql.push_str(&conditions.join(" AND "));
+    }
+    sql.push_str(" ORDER BY block_number DESC, position DESC");
+    if let Some(limit) = query.limit {
+        let p = bind(
+            SqlParam::Int(limit.min(i64::MAX as usize) as i64),
+            &mut params,
+        );
+        sql.push_str(&format!(" LIMIT {}", p));
+    }
+    (sql, params)
+}
+
+/// Row of a transaction for [`UPSERT_TRANSACTION`], with hex addresses lowercased
+#[cfg(any(feature = "sqlite", feature = "postgres"))]
+pub(crate) fn transaction_params(tx: &IndexedTransaction) -> Vec<SqlParam> {
+    let text = |value: Option<String>| value.map_or(SqlParam::Null, SqlParam::Text);
+    vec![
+        SqlParam::Text(tx.chain.to_string()),
+        SqlParam::Text(tx.hash.clone()),
+        SqlParam::Int(tx.block_number as i64),
+        SqlParam::Int(tx.index as i64),
+        text(tx.from.as_deref().map(normalize)),
+        text(tx.to.as_deref().map(normalize)),
+        text(tx.value.clone()),
+        text(tx.call.clone()),
+    ]
+}
+
+/// Parse a chain stored by name
+#[cfg(any(feature = "sqlite", feature = "postgres"))]
+#[allow(clippy::result_large_err)]
+pub(crate) fn parse_chain(name: &str) -> Result<Chain> {
+    name.parse().map_err(crate::IndexerError::Storage)
+}
+
+/// Encode a record stored as JSON
+#[cfg(any(feature = "sqlite", feature = "postgres"))]
+#[allow(clippy::result_large_err)]
+pub(crate) fn to_json<T: serde::Serialize>(value: &T) -> Result<String> {
+    serde_json::to_string(value)
+        .map_err(|e| crate::IndexerError::Storage(format!("Failed to encode record: {}", e)))
+}
+
+/// Decode a record stored as JSON
+#[cfg(any(feature = "sqlite", feature = "postgres"))]
+#[allow(clippy::result_large_err)]
+pub(crate) fn from_json<T: serde::de::DeserializeOwned>(data: &str) -> Result<T> {
+    serde_json::from_str(data)
+        .map_err(|e| crate::IndexerError::Storage(format!("Failed to decode record: {}", e)))
+}
+
+#[cfg(test)]
+pub(crate) mod fixtures {
+    use super::*;
+    use apex_sdk::types::{Address, ChainType, Event, Finality, TransactionEvent};
+
+    pub const ALICE: &str = "0xA11CE00000000000000000000000000000000001";
+    pub const BOB: &str = "0xb0b0000000000000000000000000000000000002";
+    pub const TOKEN: &str = "0x70ce000000000000000000000000000000000003";
+    pub const TRANSFER: &str = "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef";
+
+    /// Block `number` on Ethereum with a transfer from Alice to Bob and its log
+    pub fn block(number: u64, parent: &str) -> BlockData {
+        let hash = format!("0x{:064x}", number);
+        BlockData {
+            chain: Chain::Ethereum,
+            block: BlockInfo {
+                chain_type: ChainType::Evm,
+                number,
+                hash: hash.clone(),
+                parent_hash: parent.to_string(),
+                timestamp: Some(1_700_000_000 + number),
+                finality: Finality::Latest,
+            },
+            transactions: vec![IndexedTransaction {
+                chain: Chain::Ethereum,
+                hash: format!("0x{:064x}", number + 1_000),
+                block_number: number,
+                index: 0,
+                from: Some(ALICE.to_string()),
+                to: Some(TOKEN.to_string()),
+                value: Some("0".to_string()),
+                call: Some("0xa9059cbb".to_string()),
+            }],
+            events: vec![Event {
+                chain_type: ChainType::Evm,
+                block_number: number,
+                block_hash: hash,
+                tx_hash: Some(format!("0x{:064x}", number + 1_000)),
+                index: 0,
+                origin: Some(Address::evm(TOKEN)),
+                event: TransactionEvent::EvmLog {
+                    address: TOKEN.to_string(),
+                    topics: vec![TRANSFER.to_string()],
+                    data: "0x".to_string(),
+                    log_index: Some(0),
+                },
+            }],
+        }
+    }
+
+    /// Blocks `1..=count`, each the child of the previous
+    pub fn chain(count: u64) -> Vec<BlockData> {
+        (1..=count)
+            .map(|n| block(n, &format!("0x{:064x}", n - 1)))
+            .collect()
+    }
+
+    /// Save three blocks and check queries and rollback against `store`
+    pub async fn check_store(store: &impl IndexStore) {
+        for data in chain(3) {
+            store.save(&data).await.unwrap();
+        }
+        // Saving a block again replaces it
+        store.save(&chain(3)[2]).await.unwrap();
+
+        let latest = store.latest_block(&Chain::Ethereum).await.unwrap().unwrap();
+        assert_eq!(latest.number, 3);
+        assert_eq!(
+            store
+                .block(&Chain::Ethereum, 2)
+                .await
+                .unwrap()
+                .unwrap()
+                .number,
+            2
+        );
+        assert!(store.latest_block(&Chain::Polygon).await.unwrap().is_none());
+
+        let sent = store
+            .transactions(&Query::new().address(ALICE.to_lowercase()))
+            .await
+            .unwrap();
+        assert_eq!(
+            sent.iter().map(|tx| tx.block_number).collect::<Vec<_>>(),
+            [3, 2, 1]
+        );
+        assert!(store
+            .transactions(&Query::new().address(BOB))
+            .await
+            .unwrap()
+            .is_empty());
+
+        let events = store
+            .events(
+                &Query::new()
+                    .contract(TOKEN)
+                    .event_type(TRANSFER)
+                    .blocks(2, 3)
+                    .limit(1),
+            )
+            .await
+            .unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event.block_number, 3);
+        assert_eq!(events[0].event, chain(3)[2].events[0]);
+
+        store.rollback(&Chain::Ethereum, 2).await.unwrap();
+        assert_eq!(
+            store
+                .latest_block(&Chain::Ethereum)
+                .await
+                .unwrap()
+                .unwrap()
+                .number,
+            1
+        );
+        assert_eq!(store.events(&Query::new()).await.unwrap().len(), 1);
+        assert_eq!(store.transactions(&Query::new()).await.unwrap().len(), 1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_memory_store() {
+        fixtures::check_store(&MemoryStore::new()).await;
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[test]
+    fn test_select_sql() {
+        let (sql, params) = select_sql(
+            "transactions",
+            "hash",
+            &Query::new().chain(Chain::Ethereum).address("0xAB").limit(5),
+        );
+        assert_eq!(
+            sql,
+            "SELECT hash FROM transactions WHERE chain = $1 AND (sender = $2 OR recipient = $2) \
+             ORDER BY block_number DESC, position DESC LIMIT $3"
+        );
+        assert_eq!(
+            params,
+            [
+                SqlParam::Text("Ethereum".to_string()),
+                SqlParam::Text("0xab".to_string()),
+                SqlParam::Int(5)
+            ]
+        );
+    }
+}
@@ -0,0 +1,21 @@
+"""Unified Substrate & EVM blockchain SDK."""
+
+from ._apex_sdk import (
+    ApexError,
+    ApexSDK,
+    ApexSDKBuilder,
+    Chain,
+    TransactionStatus,
+    TransactionWatcher,
+    __version__,
+)
+
+__all__ = [
+    "ApexError",
+    "ApexSDK",
+    "ApexSDKBuilder",
+    "Chain",
+    "TransactionStatus",
+    "TransactionWatcher",
+    "__version__",
+]
@@ -0,0 +1,63 @@
+from typing import AsyncIterator, Optional
+
+__version__: str
+
+class ApexError(Exception): ...
+
+class Chain:
+    Polkadot: "Chain"
+    Kusama: "Chain"
+    Ethereum: "Chain"
+    BinanceSmartChain: "Chain"
+    Polygon: "Chain"
+    Avalanche: "Chain"
+    Moonbeam: "Chain"
+    Astar: "Chain"
+    Sepolia: "Chain"
+    Holesky: "Chain"
+    Amoy: "Chain"
+    MoonbaseAlpha: "Chain"
+    Shibuya: "Chain"
+    Paseo: "Chain"
+    @property
+    def name(self) -> str: ...
+    @property
+    def native_symbol(self) -> str: ...
+    @property
+    def native_decimals(self) -> int: ...
+
+class TransactionStatus:
+    @property
+    def state(self) -> str: ...
+    @property
+    def block_number(self) -> Optional[int]: ...
+    @property
+    def confirmations(self) -> Optional[int]: ...
+    @property
+    def error(self) -> Optional[str]: ...
+
+class TransactionWatcher(AsyncIterator[TransactionStatus]):
+    def __aiter__(self) -> "TransactionWatcher": ...
+    async def __anext__(self) -> TransactionStatus: ...
+
+class ApexSDKBuilder:
+    def __init__(self) -> None: ...
+    def with_substrate_endpoint(self, url: str) -> "ApexSDKBuilder": ...
+    def with_evm_endpoint(self, url: str) -> "ApexSDKBuilder": ...
+    def with_timeout(self, seconds: int) -> "ApexSDKBuilder": ...
+    async def build(self) -> "ApexSDK": ...
+
+class ApexSDK:
+    @staticmethod
+    def builder() -> ApexSDKBuilder: ...
+    def is_chain_supported(self, chain: Chain) -> bool: ...
+    async def get_balance(self, chain: Chain, address: str) -> int: ...
+    async def transfer(self, chain: Chain, secret: str, to: str, amount: int) -> str: ...
+    async def get_transaction_status(self, chain: Chain, tx_hash: str) -> TransactionStatus: ...
+    def watch_transaction(
+        self,
+        chain: Chain,
+        tx_hash: str,
+        poll_interval: float = 2.0,
+        confirmations: Optional[int] = None,
+    ) -> TransactionWatcher: ...
@@ -0,0 +1,520 @@
+//! # Apex SDK Python bindings
+//!
+//! Exposes the Apex SDK to Python through PyO3. Every network call returns an
+//! awaitable driven by a shared Tokio runtime, so the API plugs directly into
+//! `asyncio`:
+//!
+//! ```python
+//! import asyncio
+//! from apex_sdk import ApexSDK, Chain
+//!
+//! async def main():
+//!     sdk = await ApexSDK.builder().with_evm_endpoint("https://eth.llamarpc.com").build()
+//!     balance = await sdk.get_balance(Chain.Ethereum, "0x742D35Cc6634C0532925A3B844bC9e7595f0bEB7")
+//!     print(balance)
+//!
+//! asyncio.run(main())
+//! ```
+//!
+//! Balances and amounts are Python `int`s in the chain's smallest unit.
+
+use apex_sdk::evm::wallet::Wallet as EvmWallet;
+use apex_sdk::substrate::{KeyPairType, Wallet as SubstrateWallet};
+use apex_sdk::types::{Address, ChainType};
+use apex_sdk::{ApexSDK, ApexSDKBuilder, Error};
+use pyo3::create_exception;
+use pyo3::exceptions::{PyException, PyStopAsyncIteration};
+use pyo3::prelude::*;
+use pyo3_async_runtimes::tokio::future_into_py;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+create_exception!(
+    apex_sdk,
+    ApexError,
+    PyException,
+    "Error raised by Apex SDK calls."
+);
+
+fn to_py_err(err: Error) -> PyErr {
+    ApexError::new_err(err.to_string())
+}
+
+/// Supported blockchain networks
+#[pyclass(eq, eq_int, frozen, module = "apex_sdk")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Chain {
+    Polkadot,
+    Kusama,
+    Ethereum,
+    BinanceSmartChain,
+    Polygon,
+    Avalanche,
+    Moonbeam,
+    Astar,
+    Sepolia,
+    Holesky,
+    Amoy,
+    MoonbaseAlpha,
+    Shibuya,
+    Paseo,
+}
+
+impl From<Chain> for apex_sdk::types::Chain {
+    fn from(chain: Chain) -> Self {
+        use apex_sdk::types::Chain as C;
+        match chain {
+            Chain::Polkadot => C::Polkadot,
+            Chain::Kusama => C::Kusama,
+            Chain::Ethereum => C::Ethereum,
+            Chain::BinanceSmartChain => C::BinanceSmartChain,
+            Chain::Polygon => C::Polygon,
+            Chain::Avalanche => C::Avalanche,
+            Chain::Moonbeam => C::Moonbeam,
+            Chain::Astar => C::Astar,
+            Chain::Sepolia => C::Sepolia,
+            Chain::Holesky => C::Holesky,
+            Chain::Amoy => C::Amoy,
+            Chain::MoonbaseAlpha => C::MoonbaseAlpha,
+            Chain::Shibuya => C::Shibuya,
+            Chain::Paseo => C::Paseo,
+        }
+    }
+}
+
+#[pymethods]
+impl Chain {
+    /// Human-readable chain name
+    #[getter]
+    fn name(&self) -> String {
+        apex_sdk::types::Chain::from(*self).name().to_string()
+    }
+
+    /// Symbol of the native token (e.g. "DOT", "ETH")
+    #[getter]
+    fn native_symbol(&self) -> String {
+        apex_sdk::types::Chain::from(*self)
+            .native_symbol()
+            .to_string()
+    }
+
+    /// Decimals of the native token
+    #[getter]
+    fn native_decimals(&self) -> u8 {
+        apex_sdk::types::Chain::from(*self).native_decimals()
+    }
+}
+
+/// Status of a submitted transaction
+#[pyclass(frozen, module = "apex_sdk")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransactionStatus {
+    inner: apex_sdk::types::TransactionStatus,
+}
+
+#[pymethods]
+impl TransactionStatus {
+    /// One of "pending", "confirmed", "failed" or "unknown"
+    #[getter]
+    fn state(&self) -> &'static str {
+        use apex_sdk::types::TransactionStatus as S;
+        match self.inner {
+            S::Pending => "pending",
+            S::Confirmed { .. } => "confirmed",
+            S::Failed { .. } => "failed",
+            S::Unknown => "unknown",
+        }
+    }
+
+    /// Block the transaction was included in, if confirmed
+    #[getter]
+    fn block_number(&self) -> Option<u64> {
+        match self.inner {
+            apex_sdk::types::TransactionStatus::Confirmed { block_number, .. } => {
+                Some(block_number)
+            }
+            _ => None,
+        }
+    }
+
+    /// Number of confirmations, if confirmed
+    #[getter]
+    fn confirmations(&self) -> Option<u32> {
+        match self.inner {
+            apex_sdk::types::TransactionStatus::Confirmed { confirmations, .. } => {
+                Some(confirmations)
+            }
+            _ => None,
+        }
+    }
+
+    /// Failure reason, if failed
+    #[getter]
+    fn error(&self) -> Option<String> {
+        match &self.inner {
+            apex_sdk::types::TransactionStatus::Failed { error } => Some(error.clone()),
+            _ => None,
+        }
+    }
+
+    fn __str__(&self) -> String {
+        self.inner.to_string()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("TransactionStatus({})", self.inner)
+    }
+}
+
+/// Builder for configuring an [`PyApexSdk`] instance
+#[pyclass(name = "ApexSDKBuilder", module = "apex_sdk")]
+#[derive(Debug, Clone, Default)]
+pub struct PyApexSdkBuilder {
+    substrate_endpoint: Option<String>,
+    evm_endpoint: Option<String>,
+    timeout_secs: Option<u64>,
+}
+
+#[pymethods]
+impl PyApexSdkBuilder {
+    #[new]
+    fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the Substrate endpoint URL
+    fn with_substrate_endpoint(mut slf: PyRefMut<'_, Self>, url: String) -> PyRefMut<'_, Self> {
+        slf.substrate_endpoint = Some(url);
+        slf
+    }
+
+    /// Set the EVM endpoint URL
+    fn with_evm_endpoint(mut slf: PyRefMut<'_, Self>, url: String) -> PyRefMut<'_, Self> {
+        slf.evm_endpoint = Some(url);
+        slf
+    }
+
+    /// Set the connection timeout in seconds
+    fn with_timeout(mut slf: PyRefMut<'_, Self>, seconds: u64) -> PyRefMut<'_, Self> {
+        slf.timeout_secs = Some(seconds);
+        slf
+    }
+
+    /// Connect to the configured endpoints; returns an awaitable `ApexSDK`
+    fn build<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let builder = self.to_builder();
+        future_into_py(py, async move {
+            let sdk = builder.build().await.map_err(to_py_err)?;
+            Ok(PyApexSdk {
+                inner: Arc::new(sdk),
+            })
+        })
+    }
+}
+
+impl PyApexSdkBuilder {
+    fn to_builder(&self) -> ApexSDKBuilder {
+        let mut builder = ApexSDK::builder();
+        if let Some(url) = &self.substrate_endpoint {
+            builder = builder.with_substrate_endpoint(url);
+        }
+        if let Some(url) = &self.evm_endpoint {
+            builder = builder.with_evm_endpoint(url);
+        }
+        if let Some(seconds) = self.timeout_secs {
+            builder = builder.with_timeout(seconds);
+        }
+        builder
+    }
+}
+
+/// Unified entry point for Substrate and EVM chains
+#[pyclass(name = "ApexSDK", frozen, module = "apex_sdk")]
+pub struct PyApexSdk {
+    inner: Arc<ApexSDK>,
+}
+
+#[pymethods]
+impl PyApexSdk {
+    /// Create a new builder
+    #[staticmethod]
+    fn builder() -> PyApexSdkBuilder {
+        PyApexSdkBuilder::default()
+    }
+
+    /// Check whether `chain` can be used with this instance
+    fn is_chain_supported(&self, chain: Chain) -> bool {
+        self.inner.is_chain_supported(&chain.into())
+    }
+
+    /// Query the balance of `address` in the chain's smallest unit
+    ///
+    /// On hybrid chains, `0x` addresses are queried through the EVM adapter
+    /// and SS58 addresses through the Substrate adapter.
+    fn get_balance<'py>(
+        &self,
+        py: Python<'py>,
+        chain: Chain,
+        address: String,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let sdk = self.inner.clone();
+        future_into_py(py, async move {
+            let address = address
+                .parse::<Address>()
+                .map_err(|e| to_py_err(Error::InvalidAddress(e.to_string())))?;
+            sdk.get_balance(&chain.into(), &address)
+                .await
+                .map(|balance| balance.value)
+                .map_err(to_py_err)
+        })
+    }
+
+    /// Transfer `amount` of the native token to `to`
+    ///
+    /// `secret` is a hex private key or mnemonic on EVM chains and an SR25519
+    /// mnemonic on Substrate chains. Returns the transaction hash.
+    fn transfer<'py>(
+        &self,
+        py: Python<'py>,
+        chain: Chain,
+        secret: String,
+        to: String,
+        amount: u128,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let sdk = self.inner.clone();
+        future_into_py(py, async move {
+            transfer(&sdk, chain.into(), &secret, &to, amount)
+                .await
+                .map_err(to_py_err)
+        })
+    }
+
+    /// Get the current status of a transaction
+    fn get_transaction_status<'py>(
+        &self,
+        py: Python<'py>,
+        chain: Chain,
+        tx_hash: String,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let sdk = self.inner.clone();
+        future_into_py(py, async move {
+            let inner = sdk
+                .get_transaction_status(&chain.into(), &tx_hash)
+                .await
+                .map_err(to_py_err)?;
+            Ok(TransactionStatus { inner })
+        })
+    }
+
+    /// Subscribe to status updates of a transaction
+    ///
+    /// Returns an async iterator yielding a `TransactionStatus` each time it
+    /// changes, and stopping once the transaction has failed or is confirmed
+    /// under the chain's confirmation policy (and, if given, has
+    /// `confirmations` confirmations).
+    #[pyo3(signature = (chain, tx_hash, poll_interval = 2.0, confirmations = None))]
+    fn watch_transaction(
+        &self,
+        chain: Chain,
+        tx_hash: String,
+        poll_interval: f64,
+        confirmations: Option<u32>,
+    ) -> PyResult<TransactionWatcher> {
+        let poll_interval = Duration::try_from_secs_f64(poll_interval)
+            .map_err(|e| ApexError::new_err(format!("Invalid poll_interval: {}", e)))?;
+
+        Ok(TransactionWatcher {
+            sdk: self.inner.clone(),
+            chain,
+            tx_hash,
+            poll_interval,
+            confirmations,
+            state: Arc::new(Mutex::new(WatchState::default())),
+        })
+    }
+}
+
+#[derive(Debug, Default)]
+struct WatchState {
+    last: Option<apex_sdk::types::TransactionStatus>,
+    done: bool,
+}
+
+/// Async iterator over transaction status changes
+#[pyclass(frozen, module = "apex_sdk")]
+pub struct TransactionWatcher {
+    sdk: Arc<ApexSDK>,
+    chain: Chain,
+    tx_hash: String,
+    poll_interval: Duration,
+    confirmations: Option<u32>,
+    state: Arc<Mutex<WatchState>>,
+}
+
+#[pymethods]
+impl TransactionWatcher {
+    fn __aiter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __anext__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let sdk = self.sdk.clone();
+        let chain = self.chain.into();
+        let tx_hash = self.tx_hash.clone();
+        let poll_interval = self.poll_interval;
+        let required = self.confirmations.unwrap_or(0);
+        let state = self.state.clone();
+
+        future_into_py(py, async move {
+            let mut state = state.lock().await;
+            if state.done {
+                return Err(PyStopAsyncIteration::new_err(()));
+            }
+
+            loop {
+                if state.last.is_some() {
+                    tokio::time::sleep(poll_interval).await;
+                }
+
+                let status = sdk
+                    .get_transaction_status(&chain, &tx_hash)
+                    .await
+                    .map_err(to_py_err)?;
+
+                if state.last.as_ref() == Some(&status) {
+                    continue;
+                }
+
+                state.done = is_final(&status, required);
+                state.last = Some(status.clone());
+                return Ok(TransactionStatus { inner: status });
+            }
+        })
+    }
+}
+
+fn is_final(status: &apex_sdk::types::TransactionStatus, required: u32) -> bool {
+    match status {
+        apex_sdk::types::TransactionStatus::Confirmed { confirmations, .. } => {
+            *confirmations >= required
+        }
+        apex_sdk::types::TransactionStatus::Failed { .. } => true,
+        _ => false,
+    }
+}
+
+/// Route hybrid chains by address format: `0x` is EVM, anything else SS58
+fn uses_evm(chain: &apex_sdk::types::Chain, address: &str) -> bool {
+    match chain.chain_type() {
+        ChainType::Evm => true,
+        ChainType::Substrate => false,
+        ChainType::Hybrid => address.starts_with("0x"),
+    }
+}
+
+async fn transfer(
+    sdk: &ApexSDK,
+    chain: apex_sdk::types::Chain,
+    secret: &str,
+    to: &str,
+    amount: u128,
+) -> apex_sdk::Result<String> {
+    if uses_evm(&chain, to) {
+        let adapter = sdk.evm()?;
+        let wallet = if secret.trim().contains(' ') {
+            EvmWallet::from_mnemonic(secret, 0)?
+        } else {
+            EvmWallet::from_private_key(secret)?
+        };
+        let wallet = wallet.with_chain_id(adapter.chain_id().await?);
+
+        let to_address = to
+            .parse::<ethers::types::Address>()
+            .map_err(|e| Error::InvalidAddress(format!("{}: {}", to, e)))?;
+        let hash = adapter
+            .transaction_executor()
+            .send_transaction(&wallet, to_address, ethers::types::U256::from(amount), None)
+            .await?;
+
+        Ok(format!("{:?}", hash))
+    } else {
+        let wallet = SubstrateWallet::from_mnemonic(secret, KeyPairType::Sr25519)?;
+        let to = Address::substrate(to);
+        Ok(sdk
+            .substrate()?
+            .transaction_executor()
+            .transfer(&wallet, to.as_str(), amount)
+            .await?)
+    }
+}
+
+/// Native module; re-exported by the `apex_sdk` Python package
+#[pymodule]
+#[pyo3(name = "_apex_sdk")]
+fn apex_sdk_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<Chain>()?;
+    m.add_class::<TransactionStatus>()?;
+    m.add_class::<PyApexSdkBuilder>()?;
+    m.add_class::<PyApexSdk>()?;
+    m.add_class::<TransactionWatcher>()?;
+    m.add("ApexError", m.py().get_type::<ApexError>())?;
+    m.add("__version__", env!("CARGO_PKG_VERSION"))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use apex_sdk::types::Chain as SdkChain;
+    use apex_sdk::types::TransactionStatus as SdkStatus;
+
+    #[test]
+    fn test_chain_conversion() {
+        assert_eq!(SdkChain::from(Chain::Polkadot), SdkChain::Polkadot);
+        assert_eq!(SdkChain::from(Chain::Moonbeam), SdkChain::Moonbeam);
+        assert_eq!(Chain::Kusama.native_symbol(), "KSM");
+    }
+
+    #[test]
+    fn test_uses_evm() {
+        assert!(uses_evm(&SdkChain::Ethereum, "anything"));
+        assert!(!uses_evm(&SdkChain::Polkadot, "0xabc"));
+        assert!(uses_evm(&SdkChain::Moonbeam, "0xabc"));
+        assert!(!uses_evm(
+            &SdkChain::Astar,
+            "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"
+        ));
+    }
+
+    #[test]
+    fn test_is_final() {
+        let confirmed = |confirmations| SdkStatus::Confirmed {
+            block_number: 1,
+            confirmations,
+        };
+        assert!(!is_final(&SdkStatus::Pending, 1));
+        assert!(!is_final(&confirmed(1), 3));
+        assert!(is_final(&confirmed(3), 3));
+        assert!(is_final(
+            &SdkStatus::Failed {
+                error: "reverted".to_string()
+            },
+            1
+        ));
+    }
+
+    #[test]
+    fn test_status_accessors() {
+        let status = TransactionStatus {
+            inner: SdkStatus::Confirmed {
+                block_number: 42,
+                confirmations: 2,
+            },
+        };
+        assert_eq!(status.state(), "confirmed");
+        assert_eq!(status.block_number(), Some(42));
+        assert_eq!(status.confirmations(), Some(2));
+        assert_eq!(status.error(), None);
+    }
+}
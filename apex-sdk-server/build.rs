@@ -0,0 +1,14 @@
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    #[cfg(feature = "grpc")]
+    {
+        // Use a bundled protoc so builds don't depend on a system install
+        if std::env::var_os("PROTOC").is_none() {
+            std::env::set_var("PROTOC", protoc_bin_vendored::protoc_bin_path()?);
+        }
+        println!("cargo:rerun-if-changed=proto/apex/v1/apex.proto");
+        tonic_build::configure()
+            .build_client(true)
+            .compile_protos(&["proto/apex/v1/apex.proto"], &["proto"])?;
+    }
+    Ok(())
+}
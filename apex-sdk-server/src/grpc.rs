@@ -0,0 +1,363 @@
+//! gRPC service exposing Apex SDK operations
+//!
+//! The protobuf definitions live in `proto/apex/v1/apex.proto`. Transactions
+//! are submitted pre-signed, so private keys never leave the client.
+
+use apex_sdk::types::{Address, Chain, TransactionStatus};
+use apex_sdk::{ApexSDK, Error};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tokio_stream::Stream;
+use tonic::{Request, Response, Status};
+
+/// Generated protobuf types and service stubs
+#[allow(missing_docs)]
+pub mod pb {
+    tonic::include_proto!("apex.v1");
+}
+
+use pb::apex_service_server::{ApexService, ApexServiceServer};
+
+/// All chains, in the order reported by `ListChains`
+const CHAINS: [Chain; 14] = [
+    Chain::Polkadot,
+    Chain::Kusama,
+    Chain::Ethereum,
+    Chain::BinanceSmartChain,
+    Chain::Polygon,
+    Chain::Avalanche,
+    Chain::Moonbeam,
+    Chain::Astar,
+    Chain::Sepolia,
+    Chain::Holesky,
+    Chain::Amoy,
+    Chain::MoonbaseAlpha,
+    Chain::Shibuya,
+    Chain::Paseo,
+];
+
+/// gRPC service backed by a shared [`ApexSDK`]
+pub struct ApexGrpcService {
+    sdk: Arc<ApexSDK>,
+    watch_interval: Duration,
+}
+
+impl ApexGrpcService {
+    /// Create a service around a connected SDK instance
+    pub fn new(sdk: Arc<ApexSDK>) -> Self {
+        Self {
+            sdk,
+            watch_interval: Duration::from_secs(2),
+        }
+    }
+
+    /// Set how often `WatchTransaction` polls for status changes
+    pub fn with_watch_interval(mut self, interval: Duration) -> Self {
+        self.watch_interval = interval;
+        self
+    }
+
+    /// Wrap the service for use with `tonic::transport::Server`
+    pub fn into_server(self) -> ApexServiceServer<Self> {
+        ApexServiceServer::new(self)
+    }
+}
+
+impl From<Chain> for pb::Chain {
+    fn from(chain: Chain) -> Self {
+        match chain {
+            Chain::Polkadot => pb::Chain::Polkadot,
+            Chain::Kusama => pb::Chain::Kusama,
+            Chain::Ethereum => pb::Chain::Ethereum,
+            Chain::BinanceSmartChain => pb::Chain::BinanceSmartChain,
+            Chain::Polygon => pb::Chain::Polygon,
+            Chain::Avalanche => pb::Chain::Avalanche,
+            Chain::Moonbeam => pb::Chain::Moonbeam,
+            Chain::Astar => pb::Chain::Astar,
+            Chain::Sepolia => pb::Chain::Sepolia,
+            Chain::Holesky => pb::Chain::Holesky,
+            Chain::Amoy => pb::Chain::Amoy,
+            Chain::MoonbaseAlpha => pb::Chain::MoonbaseAlpha,
+            Chain::Shibuya => pb::Chain::Shibuya,
+            Chain::Paseo => pb::Chain::Paseo,
+            // Registered chains have no protobuf variant
+            Chain::Custom(_) => pb::Chain::Unspecified,
+        }
+    }
+}
+
+/// Decode the `chain` field of a request
+#[allow(clippy::result_large_err)]
+fn parse_chain(value: i32) -> Result<Chain, Status> {
+    match pb::Chain::try_from(value) {
+        Ok(pb::Chain::Polkadot) => Ok(Chain::Polkadot),
+        Ok(pb::Chain::Kusama) => Ok(Chain::Kusama),
+        Ok(pb::Chain::Ethereum) => Ok(Chain::Ethereum),
+        Ok(pb::Chain::BinanceSmartChain) => Ok(Chain::BinanceSmartChain),
+        Ok(pb::Chain::Polygon) => Ok(Chain::Polygon),
+        Ok(pb::Chain::Avalanche) => Ok(Chain::Avalanche),
+        Ok(pb::Chain::Moonbeam) => Ok(Chain::Moonbeam),
+        Ok(pb::Chain::Astar) => Ok(Chain::Astar),
+        Ok(pb::Chain::Sepolia) => Ok(Chain::Sepolia),
+        Ok(pb::Chain::Holesky) => Ok(Chain::Holesky),
+        Ok(pb::Chain::Amoy) => Ok(Chain::Amoy),
+        Ok(pb::Chain::MoonbaseAlpha) => Ok(Chain::MoonbaseAlpha),
+        Ok(pb::Chain::Shibuya) => Ok(Chain::Shibuya),
+        Ok(pb::Chain::Paseo) => Ok(Chain::Paseo),
+        Ok(pb::Chain::Unspecified) | Err(_) => Err(Status::invalid_argument(format!(
+            "Unknown chain: {}",
+            value
+        ))),
+    }
+}
+
+impl From<TransactionStatus> for pb::TransactionStatus {
+    fn from(status: TransactionStatus) -> Self {
+        use pb::transaction_status::State;
+
+        let mut out = pb::TransactionStatus::default();
+        match status {
+            TransactionStatus::Pending => out.set_state(State::Pending),
+            TransactionStatus::Confirmed {
+                block_number,
+                confirmations,
+            } => {
+                out.set_state(State::Confirmed);
+                out.block_number = block_number;
+                out.confirmations = confirmations;
+            }
+            TransactionStatus::Failed { error } => {
+                out.set_state(State::Failed);
+                out.error = error;
+            }
+            TransactionStatus::Unknown => out.set_state(State::Unknown),
+        }
+        out
+    }
+}
+
+fn to_status(err: Error) -> Status {
+    match err {
+        Error::Config(msg) => Status::failed_precondition(msg),
+        Error::UnsupportedChain(msg) => Status::failed_precondition(msg),
+        Error::InvalidAddress(msg) => Status::invalid_argument(msg),
+        Error::Transaction(msg) => Status::aborted(msg),
+        Error::Policy(msg) => Status::permission_denied(msg),
+        Error::Connection(_) | Error::Substrate(_) | Error::Evm(_) => {
+            Status::unavailable(err.to_string())
+        }
+        Error::Serialization(_) | Error::Other(_) => Status::internal(err.to_string()),
+    }
+}
+
+/// Whether a watched transaction has reached a terminal state
+fn is_final(status: &TransactionStatus, confirmations: u32) -> bool {
+    match status {
+        TransactionStatus::Confirmed {
+            confirmations: have,
+            ..
+        } => *have >= confirmations,
+        TransactionStatus::Failed { .. } => true,
+        _ => false,
+    }
+}
+
+type StatusStream = Pin<Box<dyn Stream<Item = Result<pb::TransactionStatus, Status>> + Send>>;
+
+#[tonic::async_trait]
+impl ApexService for ApexGrpcService {
+    async fn list_chains(
+        &self,
+        _request: Request<pb::ListChainsRequest>,
+    ) -> Result<Response<pb::ListChainsResponse>, Status> {
+        let chains = CHAINS
+            .iter()
+            .map(|chain| pb::ChainInfo {
+                chain: pb::Chain::from(chain.clone()).into(),
+                name: chain.name().to_string(),
+                native_symbol: chain.native_symbol().to_string(),
+                native_decimals: chain.native_decimals() as u32,
+                supported: self.sdk.is_chain_supported(chain),
+            })
+            .collect();
+
+        Ok(Response::new(pb::ListChainsResponse { chains }))
+    }
+
+    async fn get_balance(
+        &self,
+        request: Request<pb::GetBalanceRequest>,
+    ) -> Result<Response<pb::GetBalanceResponse>, Status> {
+        let request = request.into_inner();
+        let chain = parse_chain(request.chain)?;
+        let address = request
+            .address
+            .parse::<Address>()
+            .map_err(|e| Status::invalid_argument(e.to_string()))?;
+
+        let balance = self
+            .sdk
+            .get_balance(&chain, &address)
+            .await
+            .map_err(to_status)?;
+
+        Ok(Response::new(pb::GetBalanceResponse {
+            balance: balance.value.to_string(),
+            decimals: balance.decimals as u32,
+            symbol: balance.symbol.unwrap_or_default(),
+        }))
+    }
+
+    async fn submit_transaction(
+        &self,
+        request: Request<pb::SubmitTransactionRequest>,
+    ) -> Result<Response<pb::SubmitTransactionResponse>, Status> {
+        let request = request.into_inner();
+        let chain = parse_chain(request.chain)?;
+        if request.signed_transaction.is_empty() {
+            return Err(Status::invalid_argument("signed_transaction is empty"));
+        }
+
+        let tx_hash = self
+            .sdk
+            .submit_raw_transaction(&chain, &request.signed_transaction)
+            .await
+            .map_err(to_status)?;
+
+        tracing::info!("Submitted transaction {} on {}", tx_hash, chain);
+        Ok(Response::new(pb::SubmitTransactionResponse { tx_hash }))
+    }
+
+    async fn get_transaction_status(
+        &self,
+        request: Request<pb::GetTransactionStatusRequest>,
+    ) -> Result<Response<pb::TransactionStatus>, Status> {
+        let request = request.into_inner();
+        let chain = parse_chain(request.chain)?;
+
+        let status = self
+            .sdk
+            .get_transaction_status(&chain, &request.tx_hash)
+            .await
+            .map_err(to_status)?;
+
+        Ok(Response::new(status.into()))
+    }
+
+    type WatchTransactionStream = StatusStream;
+
+    async fn watch_transaction(
+        &self,
+        request: Request<pb::WatchTransactionRequest>,
+    ) -> Result<Response<Self::WatchTransactionStream>, Status> {
+        let request = request.into_inner();
+        let chain = parse_chain(request.chain)?;
+        let confirmations = request.confirmations;
+        let tx_hash = request.tx_hash;
+        let sdk = self.sdk.clone();
+        let interval = self.watch_interval;
+
+        let (tx, rx) = mpsc::channel(16);
+        tokio::spawn(async move {
+            let mut last: Option<TransactionStatus> = None;
+            loop {
+                let status = match sdk.get_transaction_status(&chain, &tx_hash).await {
+                    Ok(status) => status,
+                    Err(e) => {
+                        let _ = tx.send(Err(to_status(e))).await;
+                        return;
+                    }
+                };
+
+                if last.as_ref() != Some(&status) {
+                    let done = is_final(&status, confirmations);
+                    if tx.send(Ok(status.clone().into())).await.is_err() || done {
+                        return;
+                    }
+                    last = Some(status);
+                }
+
+                tokio::time::sleep(interval).await;
+            }
+        });
+
+        Ok(Response::new(Box::pin(ReceiverStream::new(rx))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chain_round_trip() {
+        for chain in CHAINS {
+            let value: i32 = pb::Chain::from(chain.clone()).into();
+            assert_eq!(parse_chain(value).unwrap(), chain);
+        }
+    }
+
+    #[test]
+    fn test_parse_chain_rejects_unknown() {
+        let err = parse_chain(pb::Chain::Unspecified.into()).unwrap_err();
+        assert_eq!(err.code(), tonic::Code::InvalidArgument);
+        assert!(parse_chain(42).is_err());
+    }
+
+    #[test]
+    fn test_status_conversion() {
+        use pb::transaction_status::State;
+
+        let status: pb::TransactionStatus = TransactionStatus::Confirmed {
+            block_number: 100,
+            confirmations: 3,
+        }
+        .into();
+        assert_eq!(status.state(), State::Confirmed);
+        assert_eq!(status.block_number, 100);
+        assert_eq!(status.confirmations, 3);
+
+        let status: pb::TransactionStatus = TransactionStatus::Failed {
+            error: "reverted".to_string(),
+        }
+        .into();
+        assert_eq!(status.state(), State::Failed);
+        assert_eq!(status.error, "reverted");
+    }
+
+    #[test]
+    fn test_error_mapping() {
+        assert_eq!(
+            to_status(Error::Config("no adapter".to_string())).code(),
+            tonic::Code::FailedPrecondition
+        );
+        assert_eq!(
+            to_status(Error::InvalidAddress("0x".to_string())).code(),
+            tonic::Code::InvalidArgument
+        );
+        assert_eq!(
+            to_status(Error::Connection("timeout".to_string())).code(),
+            tonic::Code::Unavailable
+        );
+    }
+
+    #[test]
+    fn test_is_final() {
+        let confirmed = |confirmations| TransactionStatus::Confirmed {
+            block_number: 1,
+            confirmations,
+        };
+        assert!(!is_final(&TransactionStatus::Pending, 1));
+        assert!(!is_final(&confirmed(1), 2));
+        assert!(is_final(&confirmed(2), 2));
+        assert!(is_final(
+            &TransactionStatus::Failed {
+                error: "reverted".to_string()
+            },
+            1
+        ));
+    }
+}
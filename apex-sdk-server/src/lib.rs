@@ -0,0 +1,37 @@
+//! # Apex SDK Server
+//!
+//! Exposes the Apex SDK over the network so services written in other
+//! languages can share a single chain-access layer.
+//!
+//! ## Features
+//!
+//! - `grpc` (default): gRPC service defined in `proto/apex/v1/apex.proto`
+//! - `rest`: axum-based REST/JSON gateway with API-key authentication hooks
+//!
+//! ## Quick Start
+//!
+//! ```rust,no_run
+//! # #[cfg(feature = "grpc")]
+//! # async fn run() -> anyhow::Result<()> {
+//! use apex_sdk::ApexSDK;
+//! use apex_sdk_server::grpc::ApexGrpcService;
+//! use std::sync::Arc;
+//!
+//! let sdk = ApexSDK::builder()
+//!     .with_evm_endpoint("https://eth.llamarpc.com")
+//!     .build()
+//!     .await?;
+//!
+//! tonic::transport::Server::builder()
+//!     .add_service(ApexGrpcService::new(Arc::new(sdk)).into_server())
+//!     .serve("127.0.0.1:50051".parse()?)
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+
+#[cfg(feature = "grpc")]
+pub mod grpc;
+
+#[cfg(feature = "rest")]
+pub mod rest;
@@ -0,0 +1,132 @@
+//! Apex SDK server binary
+
+use apex_sdk::ApexSDK;
+use clap::Parser;
+use std::sync::Arc;
+use tokio::sync::watch;
+use tokio::task::JoinSet;
+
+#[derive(Parser)]
+#[command(name = "apex-sdk-server")]
+#[command(about = "Serve Apex SDK operations over gRPC and REST", long_about = None)]
+#[command(version)]
+struct Args {
+    /// Address for the gRPC service to listen on
+    #[cfg(feature = "grpc")]
+    #[arg(long, env = "APEX_LISTEN", default_value = "127.0.0.1:50051")]
+    listen: std::net::SocketAddr,
+
+    /// Polling interval for WatchTransaction streams, in seconds
+    #[cfg(feature = "grpc")]
+    #[arg(long, default_value_t = 2)]
+    watch_interval: u64,
+
+    /// Address for the REST gateway to listen on (disabled if unset)
+    #[cfg(feature = "rest")]
+    #[arg(long, env = "APEX_REST_LISTEN")]
+    rest_listen: Option<std::net::SocketAddr>,
+
+    /// API keys accepted by the REST gateway (no authentication if empty)
+    #[cfg(feature = "rest")]
+    #[arg(long = "api-key", env = "APEX_API_KEYS", value_delimiter = ',')]
+    api_keys: Vec<String>,
+
+    /// Substrate node endpoint
+    #[arg(long, env = "APEX_SUBSTRATE_ENDPOINT")]
+    substrate_endpoint: Option<String>,
+
+    /// EVM node endpoint
+    #[arg(long, env = "APEX_EVM_ENDPOINT")]
+    evm_endpoint: Option<String>,
+}
+
+/// Resolve once shutdown has been requested
+#[cfg(any(feature = "grpc", feature = "rest"))]
+async fn shutdown_signal(mut rx: watch::Receiver<bool>) {
+    let _ = rx.wait_for(|stop| *stop).await;
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    tracing_subscriber::fmt::init();
+
+    let args = Args::parse();
+
+    let mut builder = ApexSDK::builder();
+    if let Some(endpoint) = &args.substrate_endpoint {
+        builder = builder.with_substrate_endpoint(endpoint);
+    }
+    if let Some(endpoint) = &args.evm_endpoint {
+        builder = builder.with_evm_endpoint(endpoint);
+    }
+    let sdk = Arc::new(builder.build().await?);
+
+    let (stop_tx, stop_rx) = watch::channel(false);
+    let mut servers: JoinSet<anyhow::Result<()>> = JoinSet::new();
+
+    #[cfg(feature = "grpc")]
+    {
+        use apex_sdk_server::grpc::ApexGrpcService;
+        use std::time::Duration;
+
+        let service = ApexGrpcService::new(sdk.clone())
+            .with_watch_interval(Duration::from_secs(args.watch_interval.max(1)));
+        let addr = args.listen;
+        let stop = shutdown_signal(stop_rx.clone());
+
+        tracing::info!("gRPC service listening on {}", addr);
+        servers.spawn(async move {
+            tonic::transport::Server::builder()
+                .add_service(service.into_server())
+                .serve_with_shutdown(addr, stop)
+                .await
+                .map_err(anyhow::Error::from)
+        });
+    }
+
+    #[cfg(feature = "rest")]
+    if let Some(addr) = args.rest_listen {
+        use apex_sdk_server::rest::RestGateway;
+
+        let mut gateway = RestGateway::new(sdk.clone());
+        if !args.api_keys.is_empty() {
+            gateway = gateway.with_api_keys(args.api_keys.clone());
+        } else {
+            tracing::warn!("REST gateway running without API key authentication");
+        }
+        let listener = tokio::net::TcpListener::bind(addr).await?;
+        let stop = shutdown_signal(stop_rx.clone());
+
+        tracing::info!("REST gateway listening on {}", addr);
+        servers.spawn(async move {
+            axum::serve(listener, gateway.router())
+                .with_graceful_shutdown(stop)
+                .await
+                .map_err(anyhow::Error::from)
+        });
+    }
+
+    #[cfg(not(any(feature = "grpc", feature = "rest")))]
+    let _ = (sdk, stop_rx);
+
+    if servers.is_empty() {
+        anyhow::bail!("Nothing to serve: enable the `grpc` feature or pass --rest-listen");
+    }
+
+    tokio::select! {
+        _ = tokio::signal::ctrl_c() => {
+            tracing::info!("Shutting down");
+            let _ = stop_tx.send(true);
+        }
+        Some(result) = servers.join_next() => {
+            let _ = stop_tx.send(true);
+            result??;
+        }
+    }
+
+    while let Some(result) = servers.join_next().await {
+        result??;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,394 @@
+//! REST/JSON gateway exposing Apex SDK operations
+//!
+//! Routes:
+//!
+//! - `GET /health` (never authenticated)
+//! - `GET /chains`
+//! - `GET /chains/:chain/balance/:address`
+//! - `POST /tx` with `{"chain": "ethereum", "signed_transaction": "0x..."}`
+//! - `GET /tx/:hash?chain=ethereum`
+//!
+//! When API keys are configured, every other route requires either an
+//! `x-api-key` header or an `Authorization: Bearer <key>` header.
+
+use apex_sdk::types::{Address, Amount, Chain, TransactionStatus};
+use apex_sdk::{ApexSDK, Error};
+use axum::extract::{Path, Query, Request, State};
+use axum::http::{header, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::Arc;
+
+/// All chains, in the order reported by `GET /chains`
+const CHAINS: [Chain; 14] = [
+    Chain::Polkadot,
+    Chain::Kusama,
+    Chain::Ethereum,
+    Chain::BinanceSmartChain,
+    Chain::Polygon,
+    Chain::Avalanche,
+    Chain::Moonbeam,
+    Chain::Astar,
+    Chain::Sepolia,
+    Chain::Holesky,
+    Chain::Amoy,
+    Chain::MoonbaseAlpha,
+    Chain::Shibuya,
+    Chain::Paseo,
+];
+
+/// Hook deciding whether an API key may access the gateway
+pub trait ApiKeyValidator: Send + Sync + 'static {
+    /// Return `true` if `key` is allowed
+    fn validate(&self, key: &str) -> bool;
+}
+
+impl<F> ApiKeyValidator for F
+where
+    F: Fn(&str) -> bool + Send + Sync + 'static,
+{
+    fn validate(&self, key: &str) -> bool {
+        self(key)
+    }
+}
+
+/// Validator accepting a fixed set of keys
+#[derive(Debug, Clone, Default)]
+pub struct StaticApiKeys {
+    keys: HashSet<String>,
+}
+
+impl StaticApiKeys {
+    /// Create a validator from a list of keys
+    pub fn new(keys: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            keys: keys.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl ApiKeyValidator for StaticApiKeys {
+    fn validate(&self, key: &str) -> bool {
+        self.keys.contains(key)
+    }
+}
+
+/// HTTP gateway backed by a shared [`ApexSDK`]
+pub struct RestGateway {
+    sdk: Arc<ApexSDK>,
+    api_keys: Option<Arc<dyn ApiKeyValidator>>,
+}
+
+impl RestGateway {
+    /// Create a gateway around a connected SDK instance (no authentication)
+    pub fn new(sdk: Arc<ApexSDK>) -> Self {
+        Self {
+            sdk,
+            api_keys: None,
+        }
+    }
+
+    /// Require one of the given API keys on every request
+    pub fn with_api_keys(self, keys: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.with_api_key_validator(StaticApiKeys::new(keys))
+    }
+
+    /// Use a custom hook to validate API keys
+    pub fn with_api_key_validator(mut self, validator: impl ApiKeyValidator) -> Self {
+        self.api_keys = Some(Arc::new(validator));
+        self
+    }
+
+    /// Build the axum router
+    pub fn router(self) -> Router {
+        let api = Router::new()
+            .route("/chains", get(list_chains))
+            .route("/chains/:chain/balance/:address", get(get_balance))
+            .route("/tx", post(submit_transaction))
+            .route("/tx/:hash", get(get_transaction_status))
+            .with_state(self.sdk);
+
+        let api = match self.api_keys {
+            Some(validator) => {
+                api.layer(middleware::from_fn_with_state(validator, require_api_key))
+            }
+            None => api,
+        };
+
+        Router::new().route("/health", get(health)).merge(api)
+    }
+}
+
+/// Error body returned by every failing route
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ErrorBody {
+    /// Human-readable error message
+    pub error: String,
+}
+
+#[derive(Debug)]
+struct ApiError(StatusCode, String);
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.0, Json(ErrorBody { error: self.1 })).into_response()
+    }
+}
+
+impl From<Error> for ApiError {
+    fn from(err: Error) -> Self {
+        let status = match &err {
+            Error::Config(_) | Error::UnsupportedChain(_) => StatusCode::NOT_IMPLEMENTED,
+            Error::InvalidAddress(_) => StatusCode::BAD_REQUEST,
+            Error::Transaction(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            Error::Policy(_) => StatusCode::FORBIDDEN,
+            Error::Connection(_) | Error::Substrate(_) | Error::Evm(_) => StatusCode::BAD_GATEWAY,
+            Error::Serialization(_) | Error::Other(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+        ApiError(status, err.to_string())
+    }
+}
+
+fn parse_chain(value: &str) -> Result<Chain, ApiError> {
+    value
+        .parse()
+        .map_err(|e: String| ApiError(StatusCode::BAD_REQUEST, e))
+}
+
+async fn require_api_key(
+    State(validator): State<Arc<dyn ApiKeyValidator>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let headers = request.headers();
+    let key = headers
+        .get("x-api-key")
+        .and_then(|v| v.to_str().ok())
+        .or_else(|| {
+            headers
+                .get(header::AUTHORIZATION)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.strip_prefix("Bearer "))
+        });
+
+    match key {
+        Some(key) if validator.validate(key) => next.run(request).await,
+        Some(_) => ApiError(StatusCode::FORBIDDEN, "Invalid API key".to_string()).into_response(),
+        None => ApiError(StatusCode::UNAUTHORIZED, "Missing API key".to_string()).into_response(),
+    }
+}
+
+async fn health() -> &'static str {
+    "ok"
+}
+
+/// Entry of `GET /chains`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ChainInfo {
+    /// Chain identifier
+    pub chain: Chain,
+    /// Human-readable name
+    pub name: String,
+    /// Native token symbol
+    pub native_symbol: String,
+    /// Native token decimals
+    pub native_decimals: u8,
+    /// Whether the gateway has the adapters required for this chain
+    pub supported: bool,
+}
+
+async fn list_chains(State(sdk): State<Arc<ApexSDK>>) -> Json<Vec<ChainInfo>> {
+    Json(
+        CHAINS
+            .iter()
+            .map(|chain| ChainInfo {
+                chain: chain.clone(),
+                name: chain.name().to_string(),
+                native_symbol: chain.native_symbol().to_string(),
+                native_decimals: chain.native_decimals(),
+                supported: sdk.is_chain_supported(chain),
+            })
+            .collect(),
+    )
+}
+
+/// Response of `GET /chains/:chain/balance/:address`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BalanceResponse {
+    /// Chain queried
+    pub chain: Chain,
+    /// Address queried
+    pub address: String,
+    /// Balance in the smallest unit, as a decimal string
+    pub balance: String,
+    /// Native token decimals
+    pub decimals: u8,
+    /// Native token symbol
+    pub symbol: String,
+    /// Balance rendered for humans, e.g. "1.5 DOT"
+    pub formatted: String,
+}
+
+async fn get_balance(
+    State(sdk): State<Arc<ApexSDK>>,
+    Path((chain, address)): Path<(String, String)>,
+) -> Result<Json<BalanceResponse>, ApiError> {
+    let chain = parse_chain(&chain)?;
+    let parsed = address
+        .parse::<Address>()
+        .map_err(|e| Error::InvalidAddress(e.to_string()))?;
+    let balance = sdk.get_balance(&chain, &parsed).await?;
+
+    Ok(Json(BalanceResponse {
+        address,
+        balance: balance.value.to_string(),
+        decimals: balance.decimals,
+        symbol: balance.symbol.clone().unwrap_or_default(),
+        formatted: balance.to_string(),
+        chain,
+    }))
+}
+
+/// Body of `POST /tx`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SubmitRequest {
+    /// Chain to submit to, e.g. "ethereum"
+    pub chain: String,
+    /// Hex-encoded signed transaction (RLP for EVM, SCALE extrinsic for Substrate)
+    pub signed_transaction: String,
+}
+
+/// Response of `POST /tx`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SubmitResponse {
+    /// Hash of the submitted transaction
+    pub tx_hash: String,
+}
+
+async fn submit_transaction(
+    State(sdk): State<Arc<ApexSDK>>,
+    Json(body): Json<SubmitRequest>,
+) -> Result<(StatusCode, Json<SubmitResponse>), ApiError> {
+    let chain = parse_chain(&body.chain)?;
+    let raw = hex::decode(body.signed_transaction.trim_start_matches("0x")).map_err(|e| {
+        ApiError(
+            StatusCode::BAD_REQUEST,
+            format!("signed_transaction is not valid hex: {}", e),
+        )
+    })?;
+    if raw.is_empty() {
+        return Err(ApiError(
+            StatusCode::BAD_REQUEST,
+            "signed_transaction is empty".to_string(),
+        ));
+    }
+
+    let tx_hash = sdk.submit_raw_transaction(&chain, &raw).await?;
+    tracing::info!("Submitted transaction {} on {}", tx_hash, chain);
+
+    Ok((StatusCode::ACCEPTED, Json(SubmitResponse { tx_hash })))
+}
+
+/// Query string of `GET /tx/:hash`
+#[derive(Debug, Deserialize)]
+struct StatusQuery {
+    chain: String,
+}
+
+/// Response of `GET /tx/:hash`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StatusResponse {
+    /// Chain queried
+    pub chain: Chain,
+    /// Transaction hash
+    pub tx_hash: String,
+    /// Current status
+    pub status: TransactionStatus,
+}
+
+async fn get_transaction_status(
+    State(sdk): State<Arc<ApexSDK>>,
+    Path(tx_hash): Path<String>,
+    Query(query): Query<StatusQuery>,
+) -> Result<Json<StatusResponse>, ApiError> {
+    let chain = parse_chain(&query.chain)?;
+    let status = sdk.get_transaction_status(&chain, &tx_hash).await?;
+
+    Ok(Json(StatusResponse {
+        chain,
+        tx_hash,
+        status,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use tower::ServiceExt;
+
+    fn protected_router(validator: impl ApiKeyValidator) -> Router {
+        let validator: Arc<dyn ApiKeyValidator> = Arc::new(validator);
+        Router::new()
+            .route("/chains", get(|| async { "chains" }))
+            .layer(middleware::from_fn_with_state(validator, require_api_key))
+    }
+
+    async fn status_of(router: Router, request: axum::http::Request<Body>) -> StatusCode {
+        router.oneshot(request).await.unwrap().status()
+    }
+
+    #[tokio::test]
+    async fn test_api_key_required() {
+        let router = protected_router(StaticApiKeys::new(["secret"]));
+        let request = axum::http::Request::get("/chains")
+            .body(Body::empty())
+            .unwrap();
+        assert_eq!(status_of(router, request).await, StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_api_key_header() {
+        let router = protected_router(StaticApiKeys::new(["secret"]));
+        let request = axum::http::Request::get("/chains")
+            .header("x-api-key", "secret")
+            .body(Body::empty())
+            .unwrap();
+        assert_eq!(status_of(router.clone(), request).await, StatusCode::OK);
+
+        let request = axum::http::Request::get("/chains")
+            .header("x-api-key", "wrong")
+            .body(Body::empty())
+            .unwrap();
+        assert_eq!(status_of(router, request).await, StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_bearer_token_and_custom_validator() {
+        let router = protected_router(|key: &str| key.starts_with("team-"));
+        let request = axum::http::Request::get("/chains")
+            .header(header::AUTHORIZATION, "Bearer team-ops")
+            .body(Body::empty())
+            .unwrap();
+        assert_eq!(status_of(router, request).await, StatusCode::OK);
+    }
+
+    #[test]
+    fn test_parse_chain() {
+        assert_eq!(parse_chain("ethereum").unwrap(), Chain::Ethereum);
+        let err = parse_chain("solana").err().unwrap();
+        assert_eq!(err.0, StatusCode::BAD_REQUEST);
+    }
+
+    #[test]
+    fn test_error_mapping() {
+        let err = ApiError::from(Error::InvalidAddress("0x".to_string()));
+        assert_eq!(err.0, StatusCode::BAD_REQUEST);
+        let err = ApiError::from(Error::Config("EVM adapter not configured".to_string()));
+        assert_eq!(err.0, StatusCode::NOT_IMPLEMENTED);
+    }
+}
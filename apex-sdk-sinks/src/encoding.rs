@@ -0,0 +1,82 @@
+//! Serialization of event envelopes
+
+use crate::{EventEnvelope, Result, SinkError};
+
+/// Wire encoding used for published events
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Encoding {
+    /// JSON (`application/json`)
+    #[default]
+    Json,
+    /// MessagePack (`application/msgpack`), requires the `msgpack` feature
+    #[cfg(feature = "msgpack")]
+    MessagePack,
+}
+
+impl Encoding {
+    /// MIME type of the encoding, for brokers that carry content-type headers
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Encoding::Json => "application/json",
+            #[cfg(feature = "msgpack")]
+            Encoding::MessagePack => "application/msgpack",
+        }
+    }
+
+    /// Serialize an envelope
+    #[allow(clippy::result_large_err)]
+    pub fn encode(&self, envelope: &EventEnvelope) -> Result<Vec<u8>> {
+        match self {
+            Encoding::Json => {
+                serde_json::to_vec(envelope).map_err(|e| SinkError::Encoding(e.to_string()))
+            }
+            #[cfg(feature = "msgpack")]
+            Encoding::MessagePack => {
+                rmp_serde::to_vec_named(envelope).map_err(|e| SinkError::Encoding(e.to_string()))
+            }
+        }
+    }
+
+    /// Deserialize an envelope
+    #[allow(clippy::result_large_err)]
+    pub fn decode(&self, bytes: &[u8]) -> Result<EventEnvelope> {
+        match self {
+            Encoding::Json => {
+                serde_json::from_slice(bytes).map_err(|e| SinkError::Encoding(e.to_string()))
+            }
+            #[cfg(feature = "msgpack")]
+            Encoding::MessagePack => {
+                rmp_serde::from_slice(bytes).map_err(|e| SinkError::Encoding(e.to_string()))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SdkEvent;
+    use apex_sdk::types::Chain;
+
+    fn envelope() -> EventEnvelope {
+        EventEnvelope::new(SdkEvent::NewBlock {
+            chain: Chain::Ethereum,
+            number: 19_000_000,
+            hash: "0xbeef".to_string(),
+        })
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let bytes = Encoding::Json.encode(&envelope()).unwrap();
+        assert_eq!(Encoding::Json.decode(&bytes).unwrap().id, envelope().id);
+    }
+
+    #[cfg(feature = "msgpack")]
+    #[test]
+    fn test_msgpack_round_trip() {
+        let bytes = Encoding::MessagePack.encode(&envelope()).unwrap();
+        let decoded = Encoding::MessagePack.decode(&bytes).unwrap();
+        assert_eq!(decoded.event, envelope().event);
+    }
+}
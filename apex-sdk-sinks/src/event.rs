@@ -0,0 +1,222 @@
+//! Events published to sinks
+
+use apex_sdk::attestation::Attestation;
+use apex_sdk::types::{Chain, TransactionEvent, TransactionStatus};
+use apex_sdk::TransactionResult;
+use serde::{Deserialize, Serialize};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A chain event observed through the SDK
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SdkEvent {
+    /// A new block was produced
+    NewBlock {
+        chain: Chain,
+        number: u64,
+        hash: String,
+    },
+    /// A log (EVM) or runtime event (Substrate) emitted by a transaction
+    Log {
+        chain: Chain,
+        tx_hash: String,
+        block_number: Option<u64>,
+        /// Position of the event within the transaction
+        index: u32,
+        event: TransactionEvent,
+    },
+    /// A transaction moved to a new status
+    TransactionStatus {
+        chain: Chain,
+        tx_hash: String,
+        status: TransactionStatus,
+    },
+    /// A signed submission attestation
+    Attestation { attestation: Box<Attestation> },
+}
+
+impl SdkEvent {
+    /// Chain the event was observed on
+    pub fn chain(&self) -> &Chain {
+        match self {
+            SdkEvent::NewBlock { chain, .. }
+            | SdkEvent::Log { chain, .. }
+            | SdkEvent::TransactionStatus { chain, .. } => chain,
+            SdkEvent::Attestation { attestation } => &attestation.body.source_chain,
+        }
+    }
+
+    /// Short event kind, used for routing (subjects, stream fields)
+    pub fn kind(&self) -> &'static str {
+        match self {
+            SdkEvent::NewBlock { .. } => "new_block",
+            SdkEvent::Log { .. } => "log",
+            SdkEvent::TransactionStatus { .. } => "transaction_status",
+            SdkEvent::Attestation { .. } => "attestation",
+        }
+    }
+
+    /// Deterministic identifier: the same event always gets the same id, so
+    /// consumers can discard redeliveries
+    pub fn id(&self) -> String {
+        let chain = chain_slug(self.chain());
+        match self {
+            SdkEvent::NewBlock { number, hash, .. } => {
+                format!("{}:block:{}:{}", chain, number, hash)
+            }
+            SdkEvent::Log { tx_hash, index, .. } => format!("{}:log:{}:{}", chain, tx_hash, index),
+            SdkEvent::TransactionStatus {
+                tx_hash, status, ..
+            } => {
+                let state = match status {
+                    TransactionStatus::Pending => "pending".to_string(),
+                    TransactionStatus::Confirmed {
+                        block_number,
+                        confirmations,
+                    } => format!("confirmed:{}:{}", block_number, confirmations),
+                    TransactionStatus::Failed { .. } => "failed".to_string(),
+                    TransactionStatus::Unknown => "unknown".to_string(),
+                };
+                format!("{}:tx:{}:{}", chain, tx_hash, state)
+            }
+            SdkEvent::Attestation { attestation } => format!(
+                "{}:attestation:{}:{}",
+                chain, attestation.body.tx_hash, attestation.body.submitted_at
+            ),
+        }
+    }
+
+    /// Events describing a completed transaction: its logs followed by its status
+    pub fn from_result(chain: &Chain, result: &TransactionResult) -> Vec<SdkEvent> {
+        let mut events: Vec<SdkEvent> = result
+            .events
+            .iter()
+            .enumerate()
+            .map(|(index, event)| SdkEvent::Log {
+                chain: chain.clone(),
+                tx_hash: result.source_tx_hash.clone(),
+                block_number: result.block_number,
+                index: index as u32,
+                event: event.clone(),
+            })
+            .collect();
+
+        events.push(SdkEvent::TransactionStatus {
+            chain: chain.clone(),
+            tx_hash: result.source_tx_hash.clone(),
+            status: result.status.clone(),
+        });
+        events
+    }
+}
+
+/// Lowercase chain identifier used in ids and routing keys (e.g. "binancesmartchain")
+pub fn chain_slug(chain: &Chain) -> String {
+    format!("{:?}", chain).to_lowercase()
+}
+
+/// Wire format of a published event
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EventEnvelope {
+    /// Deterministic event id (see [`SdkEvent::id`])
+    pub id: String,
+    /// Event kind (see [`SdkEvent::kind`])
+    pub kind: String,
+    /// Unix timestamp (milliseconds) at which the event was queued
+    pub timestamp: u64,
+    /// The event itself
+    pub event: SdkEvent,
+}
+
+impl EventEnvelope {
+    /// Wrap an event, stamping it with the current time
+    pub fn new(event: SdkEvent) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or_default();
+
+        Self {
+            id: event.id(),
+            kind: event.kind().to_string(),
+            timestamp,
+            event,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_ids_are_deterministic() {
+        let event = SdkEvent::NewBlock {
+            chain: Chain::Polkadot,
+            number: 42,
+            hash: "0xabc".to_string(),
+        };
+        assert_eq!(event.id(), "polkadot:block:42:0xabc");
+        assert_eq!(event.id(), event.clone().id());
+        assert_eq!(event.kind(), "new_block");
+    }
+
+    #[test]
+    fn test_status_ids_distinguish_transitions() {
+        let status = |status| SdkEvent::TransactionStatus {
+            chain: Chain::BinanceSmartChain,
+            tx_hash: "0x1".to_string(),
+            status,
+        };
+        let pending = status(TransactionStatus::Pending);
+        let confirmed = status(TransactionStatus::Confirmed {
+            block_number: 7,
+            confirmations: 1,
+        });
+        assert_eq!(pending.id(), "binancesmartchain:tx:0x1:pending");
+        assert_eq!(confirmed.id(), "binancesmartchain:tx:0x1:confirmed:7:1");
+    }
+
+    #[test]
+    fn test_from_result() {
+        let result = TransactionResult {
+            source_tx_hash: "0xfeed".to_string(),
+            destination_tx_hash: None,
+            status: TransactionStatus::Confirmed {
+                block_number: 10,
+                confirmations: 1,
+            },
+            block_number: Some(10),
+            gas_used: None,
+            block_hash: None,
+            fee: None,
+            events: vec![TransactionEvent::EvmLog {
+                address: "0xtoken".to_string(),
+                topics: vec!["0xddf2".to_string()],
+                data: "0x".to_string(),
+                log_index: Some(0),
+            }],
+            contract_address: None,
+            finalized_at: None,
+            receipt: None,
+        };
+
+        let events = SdkEvent::from_result(&Chain::Ethereum, &result);
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].id(), "ethereum:log:0xfeed:0");
+        assert_eq!(events[1].kind(), "transaction_status");
+    }
+
+    #[test]
+    fn test_envelope_serialization() {
+        let envelope = EventEnvelope::new(SdkEvent::NewBlock {
+            chain: Chain::Kusama,
+            number: 1,
+            hash: "0x01".to_string(),
+        });
+        let json = serde_json::to_value(&envelope).unwrap();
+        assert_eq!(json["kind"], "new_block");
+        assert_eq!(json["event"]["type"], "new_block");
+        assert_eq!(json["event"]["chain"], "Kusama");
+    }
+}
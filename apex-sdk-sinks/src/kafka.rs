@@ -0,0 +1,77 @@
+//! Kafka sink
+
+use crate::{Encoding, EventEnvelope, EventSink, Result, SinkError};
+use async_trait::async_trait;
+use rdkafka::message::{Header, OwnedHeaders};
+use rdkafka::producer::{FutureProducer, FutureRecord};
+use rdkafka::ClientConfig;
+use std::time::Duration;
+
+/// Publishes events to a Kafka topic
+///
+/// Uses an idempotent producer with `acks=all`, keyed by event id so all
+/// transitions of an entity land on the same partition in order.
+pub struct KafkaSink {
+    producer: FutureProducer,
+    topic: String,
+    timeout: Duration,
+}
+
+impl KafkaSink {
+    /// Create a producer for `brokers` (comma-separated `host:port` list)
+    pub fn new(brokers: &str, topic: impl Into<String>) -> Result<Self> {
+        let producer = ClientConfig::new()
+            .set("bootstrap.servers", brokers)
+            .set("enable.idempotence", "true")
+            .set("acks", "all")
+            .create()
+            .map_err(|e| SinkError::Connection(format!("Kafka producer failed: {}", e)))?;
+        Ok(Self::from_producer(producer, topic))
+    }
+
+    /// Use an existing producer
+    pub fn from_producer(producer: FutureProducer, topic: impl Into<String>) -> Self {
+        Self {
+            producer,
+            topic: topic.into(),
+            timeout: Duration::from_secs(30),
+        }
+    }
+
+    /// Set how long a send may wait in the producer queue
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+}
+
+#[async_trait]
+impl EventSink for KafkaSink {
+    async fn send(
+        &self,
+        envelope: &EventEnvelope,
+        payload: &[u8],
+        encoding: Encoding,
+    ) -> Result<()> {
+        let headers = OwnedHeaders::new()
+            .insert(Header {
+                key: "content-type",
+                value: Some(encoding.content_type()),
+            })
+            .insert(Header {
+                key: "event-kind",
+                value: Some(envelope.kind.as_str()),
+            });
+
+        let record = FutureRecord::to(&self.topic)
+            .key(envelope.id.as_str())
+            .payload(payload)
+            .headers(headers);
+
+        self.producer
+            .send(record, self.timeout)
+            .await
+            .map_err(|(e, _)| SinkError::Publish(format!("Kafka send failed: {}", e)))?;
+        Ok(())
+    }
+}
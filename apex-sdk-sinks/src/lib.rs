@@ -0,0 +1,81 @@
+//! # Apex SDK Sinks
+//!
+//! Publish chain events observed through the Apex SDK (new blocks, matched
+//! logs, transaction lifecycle transitions) to message brokers, so indexing
+//! pipelines can consume chain data without bespoke glue.
+//!
+//! ## Delivery semantics
+//!
+//! [`Publisher`] provides at-least-once delivery: events are queued, sent in
+//! order, and only removed from the queue once the broker acknowledges them.
+//! Failed sends are retried with exponential backoff and stay queued for the
+//! next [`Publisher::flush`] if retries are exhausted. Every event carries a
+//! deterministic [`EventEnvelope::id`], so consumers can drop redeliveries.
+//!
+//! ## Sinks
+//!
+//! - [`MemorySink`]: in-process buffer, useful for tests
+//! - `NatsSink` (`nats` feature): JetStream publish with `Nats-Msg-Id` dedup
+//! - `KafkaSink` (`kafka` feature): idempotent producer, keyed by event id
+//! - `RedisStreamSink` (`redis` feature): `XADD` to a Redis stream
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use apex_sdk::types::{Chain, TransactionStatus};
+//! use apex_sdk_sinks::{Encoding, MemorySink, Publisher, SdkEvent};
+//!
+//! # async fn run() -> Result<(), apex_sdk_sinks::SinkError> {
+//! let publisher = Publisher::new(MemorySink::new()).with_encoding(Encoding::Json);
+//!
+//! publisher
+//!     .publish(SdkEvent::TransactionStatus {
+//!         chain: Chain::Ethereum,
+//!         tx_hash: "0xabc...".to_string(),
+//!         status: TransactionStatus::Pending,
+//!     })
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+
+pub mod encoding;
+pub mod event;
+pub mod publisher;
+pub mod sink;
+
+#[cfg(feature = "kafka")]
+pub mod kafka;
+#[cfg(feature = "nats")]
+pub mod nats;
+#[cfg(feature = "redis")]
+pub mod redis;
+
+pub use encoding::Encoding;
+pub use event::{EventEnvelope, SdkEvent};
+pub use publisher::{Publisher, RetryPolicy};
+pub use sink::{EventSink, MemorySink};
+
+use thiserror::Error;
+
+/// Sink error
+#[derive(Error, Debug)]
+pub enum SinkError {
+    #[error("Connection error: {0}")]
+    Connection(String),
+
+    #[error("Publish error: {0}")]
+    Publish(String),
+
+    #[error("Encoding error: {0}")]
+    Encoding(String),
+
+    #[error("Queue full: {0} events pending")]
+    QueueFull(usize),
+
+    #[error("SDK error: {0}")]
+    Sdk(#[from] apex_sdk::Error),
+}
+
+/// Result type for sink operations
+pub type Result<T> = std::result::Result<T, SinkError>;
@@ -0,0 +1,68 @@
+//! NATS JetStream sink
+
+use crate::event::chain_slug;
+use crate::{Encoding, EventEnvelope, EventSink, Result, SinkError};
+use async_nats::jetstream::{self, context::Publish};
+use async_trait::async_trait;
+
+/// Publishes events to NATS JetStream
+///
+/// Events go to `<prefix>.<chain>.<kind>` (e.g. `apex.events.ethereum.log`)
+/// with the event id as `Nats-Msg-Id`, so JetStream drops duplicates within
+/// the stream's deduplication window. A stream covering the subjects must
+/// already exist.
+pub struct NatsSink {
+    context: jetstream::Context,
+    subject_prefix: String,
+}
+
+impl NatsSink {
+    /// Connect to a NATS server
+    pub async fn connect(url: &str, subject_prefix: impl Into<String>) -> Result<Self> {
+        let client = async_nats::connect(url)
+            .await
+            .map_err(|e| SinkError::Connection(format!("NATS connection failed: {}", e)))?;
+        Ok(Self::from_client(client, subject_prefix))
+    }
+
+    /// Use an existing NATS client
+    pub fn from_client(client: async_nats::Client, subject_prefix: impl Into<String>) -> Self {
+        Self {
+            context: jetstream::new(client),
+            subject_prefix: subject_prefix.into(),
+        }
+    }
+
+    /// Subject an envelope is published to
+    pub fn subject(&self, envelope: &EventEnvelope) -> String {
+        format!(
+            "{}.{}.{}",
+            self.subject_prefix,
+            chain_slug(envelope.event.chain()),
+            envelope.kind
+        )
+    }
+}
+
+#[async_trait]
+impl EventSink for NatsSink {
+    async fn send(
+        &self,
+        envelope: &EventEnvelope,
+        payload: &[u8],
+        encoding: Encoding,
+    ) -> Result<()> {
+        let publish = Publish::build()
+            .payload(payload.to_vec().into())
+            .message_id(&envelope.id)
+            .header("Content-Type", encoding.content_type());
+
+        self.context
+            .send_publish(self.subject(envelope), publish)
+            .await
+            .map_err(|e| SinkError::Publish(format!("NATS publish failed: {}", e)))?
+            .await
+            .map_err(|e| SinkError::Publish(format!("NATS publish not acknowledged: {}", e)))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,359 @@
+//! At-least-once event publisher
+
+use crate::{Encoding, EventEnvelope, EventSink, Result, SdkEvent, SinkError};
+use apex_sdk::attestation::{Attestation, AttestationSink};
+use apex_sdk::types::{Chain, TransactionStatus};
+use apex_sdk::ApexSDK;
+use async_trait::async_trait;
+use std::collections::VecDeque;
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+/// Retry behaviour for failed sends
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Attempts per event before [`Publisher::flush`] gives up (minimum 1)
+    pub max_attempts: u32,
+    /// Delay before the first retry
+    pub initial_delay: Duration,
+    /// Upper bound for the delay between retries
+    pub max_delay: Duration,
+    /// Factor applied to the delay after each failed attempt
+    pub multiplier: f64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            initial_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(10),
+            multiplier: 2.0,
+        }
+    }
+}
+
+/// Publishes events to a sink with at-least-once delivery
+///
+/// Events are queued and delivered in order. An event leaves the queue only
+/// once the sink acknowledges it; if retries are exhausted it stays at the
+/// head of the queue and is redelivered by the next publish or flush.
+pub struct Publisher<S: EventSink> {
+    sink: S,
+    encoding: Encoding,
+    retry: RetryPolicy,
+    capacity: usize,
+    queue: Mutex<VecDeque<EventEnvelope>>,
+}
+
+impl<S: EventSink> Publisher<S> {
+    /// Create a publisher with JSON encoding and the default retry policy
+    pub fn new(sink: S) -> Self {
+        Self {
+            sink,
+            encoding: Encoding::default(),
+            retry: RetryPolicy::default(),
+            capacity: 10_000,
+            queue: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Set the wire encoding
+    pub fn with_encoding(mut self, encoding: Encoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    /// Set the retry policy
+    pub fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Set the maximum number of undelivered events kept in the queue
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    /// Get the underlying sink
+    pub fn sink(&self) -> &S {
+        &self.sink
+    }
+
+    /// Number of events waiting for delivery
+    pub async fn pending(&self) -> usize {
+        self.queue.lock().await.len()
+    }
+
+    /// Queue an event and deliver everything pending
+    pub async fn publish(&self, event: SdkEvent) -> Result<()> {
+        self.publish_all([event]).await
+    }
+
+    /// Queue several events and deliver everything pending
+    pub async fn publish_all(&self, events: impl IntoIterator<Item = SdkEvent>) -> Result<()> {
+        let envelopes: Vec<EventEnvelope> = events.into_iter().map(EventEnvelope::new).collect();
+        {
+            let mut queue = self.queue.lock().await;
+            if queue.len() + envelopes.len() > self.capacity {
+                return Err(SinkError::QueueFull(queue.len()));
+            }
+            queue.extend(envelopes);
+        }
+        self.flush().await
+    }
+
+    /// Deliver all pending events, in order
+    pub async fn flush(&self) -> Result<()> {
+        let mut queue = self.queue.lock().await;
+
+        while let Some(envelope) = queue.front() {
+            let payload = match self.encoding.encode(envelope) {
+                Ok(payload) => payload,
+                Err(e) => {
+                    // Encoding failures are permanent; retrying would block the queue forever
+                    tracing::error!("Dropping event {}: {}", envelope.id, e);
+                    queue.pop_front();
+                    return Err(e);
+                }
+            };
+
+            self.send_with_retry(envelope, &payload).await?;
+            queue.pop_front();
+        }
+
+        Ok(())
+    }
+
+    async fn send_with_retry(&self, envelope: &EventEnvelope, payload: &[u8]) -> Result<()> {
+        let mut attempts = 0;
+        let mut delay = self.retry.initial_delay;
+
+        loop {
+            attempts += 1;
+            match self.sink.send(envelope, payload, self.encoding).await {
+                Ok(()) => return Ok(()),
+                Err(e) if attempts >= self.retry.max_attempts.max(1) => {
+                    tracing::warn!(
+                        "Giving up on event {} after {} attempts: {}",
+                        envelope.id,
+                        attempts,
+                        e
+                    );
+                    return Err(e);
+                }
+                Err(e) => {
+                    tracing::debug!(
+                        "Send of {} failed (attempt {}): {}. Retrying in {:?}",
+                        envelope.id,
+                        attempts,
+                        e,
+                        delay
+                    );
+                    tokio::time::sleep(delay).await;
+                    delay = Duration::from_secs_f64(
+                        (delay.as_secs_f64() * self.retry.multiplier)
+                            .min(self.retry.max_delay.as_secs_f64()),
+                    );
+                }
+            }
+        }
+    }
+
+    /// Follow a transaction and publish each status transition
+    ///
+    /// Polls every `interval` until the transaction fails or satisfies the
+    /// SDK's confirmation policy for `chain`, and returns the final status.
+    pub async fn track_transaction(
+        &self,
+        sdk: &ApexSDK,
+        chain: &Chain,
+        tx_hash: &str,
+        interval: Duration,
+    ) -> Result<TransactionStatus> {
+        let mut last: Option<TransactionStatus> = None;
+
+        loop {
+            let status = sdk.get_transaction_status(chain, tx_hash).await?;
+
+            if last.as_ref() != Some(&status) {
+                self.publish(SdkEvent::TransactionStatus {
+                    chain: chain.clone(),
+                    tx_hash: tx_hash.to_string(),
+                    status: status.clone(),
+                })
+                .await?;
+
+                let done = matches!(
+                    status,
+                    TransactionStatus::Confirmed { .. } | TransactionStatus::Failed { .. }
+                );
+                if done {
+                    return Ok(status);
+                }
+                last = Some(status);
+            }
+
+            tokio::time::sleep(interval).await;
+        }
+    }
+}
+
+/// Publishes attestations as [`SdkEvent::Attestation`] events
+#[async_trait]
+impl<S: EventSink> AttestationSink for Publisher<S> {
+    async fn emit(&self, attestation: &Attestation) -> apex_sdk::Result<()> {
+        self.publish(SdkEvent::Attestation {
+            attestation: Box::new(attestation.clone()),
+        })
+        .await
+        .map_err(|e| apex_sdk::Error::Other(format!("Failed to publish attestation: {}", e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MemorySink;
+    use async_trait::async_trait;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    /// Sink failing the first `failures` sends
+    struct FlakySink {
+        failures: AtomicU32,
+        inner: MemorySink,
+    }
+
+    #[async_trait]
+    impl EventSink for FlakySink {
+        async fn send(
+            &self,
+            envelope: &EventEnvelope,
+            payload: &[u8],
+            encoding: Encoding,
+        ) -> Result<()> {
+            if self.failures.load(Ordering::SeqCst) > 0 {
+                self.failures.fetch_sub(1, Ordering::SeqCst);
+                return Err(SinkError::Publish("broker unavailable".to_string()));
+            }
+            self.inner.send(envelope, payload, encoding).await
+        }
+    }
+
+    fn block(number: u64) -> SdkEvent {
+        SdkEvent::NewBlock {
+            chain: Chain::Polkadot,
+            number,
+            hash: format!("0x{:02x}", number),
+        }
+    }
+
+    fn fast_retry(max_attempts: u32) -> RetryPolicy {
+        RetryPolicy {
+            max_attempts,
+            initial_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(2),
+            multiplier: 2.0,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_publish_in_order() {
+        let publisher = Publisher::new(MemorySink::new());
+        publisher.publish_all([block(1), block(2)]).await.unwrap();
+        publisher.publish(block(3)).await.unwrap();
+
+        let ids: Vec<String> = publisher
+            .sink()
+            .events()
+            .into_iter()
+            .map(|e| e.id)
+            .collect();
+        assert_eq!(
+            ids,
+            vec![
+                "polkadot:block:1:0x01",
+                "polkadot:block:2:0x02",
+                "polkadot:block:3:0x03"
+            ]
+        );
+        assert_eq!(publisher.pending().await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_retries_transient_failures() {
+        let sink = FlakySink {
+            failures: AtomicU32::new(2),
+            inner: MemorySink::new(),
+        };
+        let publisher = Publisher::new(sink).with_retry_policy(fast_retry(3));
+
+        publisher.publish(block(1)).await.unwrap();
+        assert_eq!(publisher.sink().inner.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_keeps_undelivered_events() {
+        let sink = FlakySink {
+            failures: AtomicU32::new(3),
+            inner: MemorySink::new(),
+        };
+        let publisher = Publisher::new(sink).with_retry_policy(fast_retry(2));
+
+        assert!(publisher.publish(block(1)).await.is_err());
+        assert_eq!(publisher.pending().await, 1);
+
+        // Third failure, then the queued event and the new one go through in order
+        assert!(publisher.publish(block(2)).await.is_ok());
+        let events = publisher.sink().inner.events();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].id, "polkadot:block:1:0x01");
+        assert_eq!(publisher.pending().await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_queue_capacity() {
+        let sink = FlakySink {
+            failures: AtomicU32::new(u32::MAX),
+            inner: MemorySink::new(),
+        };
+        let publisher = Publisher::new(sink)
+            .with_retry_policy(fast_retry(1))
+            .with_capacity(1);
+
+        assert!(publisher.publish(block(1)).await.is_err());
+        assert!(matches!(
+            publisher.publish(block(2)).await,
+            Err(SinkError::QueueFull(1))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_publishes_attestations() {
+        use apex_sdk::attestation::Attestor;
+        use apex_sdk::evm::wallet::Wallet;
+        use apex_sdk::TransactionBuilder;
+
+        let sink = MemorySink::new();
+        let attestor = Attestor::new(Wallet::new_random()).with_sink(Publisher::new(sink.clone()));
+        let tx = TransactionBuilder::new()
+            .from_evm_address("0x742D35Cc6634C0532925A3B844bC9e7595f0bEB7")
+            .to_evm_address("0x1234567890123456789012345678901234567890")
+            .amount(1)
+            .build()
+            .unwrap();
+
+        let attestation = attestor.attest(&tx, "0xabc", &[], 0).await.unwrap();
+        attestor.emit(&attestation).await;
+
+        let events = sink.events();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].kind, "attestation");
+        assert!(events[0].id.starts_with("ethereum:attestation:0xabc:"));
+        match &events[0].event {
+            SdkEvent::Attestation { attestation } => assert!(attestation.verify().unwrap()),
+            other => panic!("unexpected event {:?}", other),
+        }
+    }
+}
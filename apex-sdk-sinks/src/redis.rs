@@ -0,0 +1,75 @@
+//! Redis streams sink
+
+use crate::{Encoding, EventEnvelope, EventSink, Result, SinkError};
+use async_trait::async_trait;
+use redis::aio::MultiplexedConnection;
+use redis::streams::StreamMaxlen;
+use redis::AsyncCommands;
+
+/// Appends events to a Redis stream with `XADD`
+///
+/// Each entry has `id`, `kind`, `content_type` and `payload` fields. Consumer
+/// groups give at-least-once processing on the reading side.
+pub struct RedisStreamSink {
+    connection: MultiplexedConnection,
+    stream: String,
+    max_len: Option<usize>,
+}
+
+impl RedisStreamSink {
+    /// Connect to Redis
+    pub async fn connect(url: &str, stream: impl Into<String>) -> Result<Self> {
+        let client = redis::Client::open(url)
+            .map_err(|e| SinkError::Connection(format!("Invalid Redis URL: {}", e)))?;
+        let connection = client
+            .get_multiplexed_async_connection()
+            .await
+            .map_err(|e| SinkError::Connection(format!("Redis connection failed: {}", e)))?;
+        Ok(Self::from_connection(connection, stream))
+    }
+
+    /// Use an existing connection
+    pub fn from_connection(connection: MultiplexedConnection, stream: impl Into<String>) -> Self {
+        Self {
+            connection,
+            stream: stream.into(),
+            max_len: None,
+        }
+    }
+
+    /// Cap the stream at roughly `max_len` entries (`MAXLEN ~`)
+    pub fn with_max_len(mut self, max_len: usize) -> Self {
+        self.max_len = Some(max_len);
+        self
+    }
+}
+
+#[async_trait]
+impl EventSink for RedisStreamSink {
+    async fn send(
+        &self,
+        envelope: &EventEnvelope,
+        payload: &[u8],
+        encoding: Encoding,
+    ) -> Result<()> {
+        let fields: [(&str, &[u8]); 4] = [
+            ("id", envelope.id.as_bytes()),
+            ("kind", envelope.kind.as_bytes()),
+            ("content_type", encoding.content_type().as_bytes()),
+            ("payload", payload),
+        ];
+
+        let mut connection = self.connection.clone();
+        let result: redis::RedisResult<String> = match self.max_len {
+            Some(max_len) => {
+                connection
+                    .xadd_maxlen(&self.stream, StreamMaxlen::Approx(max_len), "*", &fields)
+                    .await
+            }
+            None => connection.xadd(&self.stream, "*", &fields).await,
+        };
+
+        result.map_err(|e| SinkError::Publish(format!("Redis XADD failed: {}", e)))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,95 @@
+//! Sink abstraction and in-memory implementation
+
+use crate::{Encoding, EventEnvelope, Result};
+use async_trait::async_trait;
+use std::sync::{Arc, Mutex};
+
+/// Destination for encoded events
+///
+/// Implementations must only return `Ok` once the broker has accepted the
+/// message; [`Publisher`](crate::Publisher) relies on this for at-least-once
+/// delivery.
+#[async_trait]
+pub trait EventSink: Send + Sync {
+    /// Deliver one encoded event
+    async fn send(
+        &self,
+        envelope: &EventEnvelope,
+        payload: &[u8],
+        encoding: Encoding,
+    ) -> Result<()>;
+}
+
+#[async_trait]
+impl<S: EventSink + ?Sized> EventSink for Arc<S> {
+    async fn send(
+        &self,
+        envelope: &EventEnvelope,
+        payload: &[u8],
+        encoding: Encoding,
+    ) -> Result<()> {
+        (**self).send(envelope, payload, encoding).await
+    }
+}
+
+/// Envelopes and encoded payloads, in delivery order
+type Delivered = Vec<(EventEnvelope, Vec<u8>)>;
+
+/// Sink keeping delivered events in memory
+#[derive(Debug, Clone, Default)]
+pub struct MemorySink {
+    delivered: Arc<Mutex<Delivered>>,
+}
+
+impl MemorySink {
+    /// Create an empty sink
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Envelopes delivered so far, in order
+    pub fn events(&self) -> Vec<EventEnvelope> {
+        self.delivered
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(envelope, _)| envelope.clone())
+            .collect()
+    }
+
+    /// Encoded payloads delivered so far, in order
+    pub fn payloads(&self) -> Vec<Vec<u8>> {
+        self.delivered
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(_, payload)| payload.clone())
+            .collect()
+    }
+
+    /// Number of delivered events
+    pub fn len(&self) -> usize {
+        self.delivered.lock().unwrap().len()
+    }
+
+    /// Whether nothing has been delivered yet
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[async_trait]
+impl EventSink for MemorySink {
+    async fn send(
+        &self,
+        envelope: &EventEnvelope,
+        payload: &[u8],
+        _encoding: Encoding,
+    ) -> Result<()> {
+        self.delivered
+            .lock()
+            .unwrap()
+            .push((envelope.clone(), payload.to_vec()));
+        Ok(())
+    }
+}
@@ -1,42 +1,52 @@
 //! Build script for apex-sdk-substrate
 //!
-//! This creates a placeholder for optionally generated typed metadata.
-//! To generate typed metadata, use the `subxt` CLI tool:
+//! With the `typed` feature, typed bindings are generated from a pinned
+//! metadata file named by `APEX_METADATA_PATH` (raw SCALE metadata or a chain
+//! descriptor JSON). Pin metadata with:
 //!
 //! ```bash
-//! # Install subxt CLI
-//! cargo install subxt-cli
-//!
-//! # Generate metadata from a chain
-//! subxt metadata --url wss://rpc.polkadot.io > metadata.scale
-//!
-//! # Generate Rust code from metadata
-//! subxt codegen --file metadata.scale | rustfmt > src/generated.rs
+//! ./scripts/generate_metadata.sh westend   # writes metadata/westend.scale
+//! APEX_METADATA_PATH=$PWD/metadata/westend.scale cargo build --features typed
 //! ```
+//!
+//! Without a metadata file the `typed::api` module is empty and only the
+//! dynamic API is available.
 
 use std::env;
 use std::fs;
 use std::path::PathBuf;
 
+#[cfg(feature = "typed")]
+#[path = "src/codegen.rs"]
+mod codegen;
+
 fn main() {
-    // Create a placeholder metadata file
+    println!("cargo:rerun-if-env-changed=APEX_METADATA_PATH");
+
     let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
-    let metadata_path = out_dir.join("metadata.rs");
+    let bindings_path = out_dir.join("typed_api.rs");
+
+    #[cfg(feature = "typed")]
+    if let Ok(metadata_path) = env::var("APEX_METADATA_PATH") {
+        if let Err(e) = codegen::write_bindings(&metadata_path, &bindings_path) {
+            panic!("Failed to generate bindings from {}: {}", metadata_path, e);
+        }
+        return;
+    }
 
-    // Create placeholder file
+    // Placeholder: an empty module so `typed::api` always resolves
     let placeholder = r#"
-// Typed metadata placeholder
-//
-// To generate typed metadata:
-// 1. Install subxt CLI: cargo install subxt-cli
-// 2. Fetch metadata: subxt metadata --url wss://rpc.polkadot.io > metadata.scale
-// 3. Generate code: subxt codegen --file metadata.scale > src/generated.rs
-// 4. Enable the "typed" feature in your Cargo.toml
-//
-// For now, use the dynamic API provided by the main modules.
+/// Typed bindings placeholder
+///
+/// Set `APEX_METADATA_PATH` to a pinned metadata file and enable the `typed`
+/// feature to generate bindings. Until then, use the dynamic API.
+pub mod api {}
 "#;
 
-    fs::write(&metadata_path, placeholder).expect("Failed to write placeholder metadata");
+    fs::write(&bindings_path, placeholder).expect("Failed to write placeholder bindings");
 
-    println!("cargo:warning=Using dynamic API. See build.rs for instructions on generating typed metadata.");
+    #[cfg(feature = "typed")]
+    println!(
+        "cargo:warning=APEX_METADATA_PATH not set; typed bindings are empty. See build.rs for instructions."
+    );
 }
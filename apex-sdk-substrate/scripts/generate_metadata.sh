@@ -30,6 +30,7 @@ WESTEND_ENDPOINT="wss://westend-rpc.polkadot.io"
 # Parse arguments
 CHAIN="${1:-westend}"
 OUTPUT_DIR="src/metadata"
+PIN_DIR="metadata"
 
 case "$CHAIN" in
     polkadot)
@@ -56,8 +57,21 @@ echo -e "Endpoint: ${GREEN}$ENDPOINT${NC}"
 echo -e "Output: ${GREEN}$OUTPUT_DIR/$OUTPUT_FILE${NC}"
 echo ""
 
-# Create output directory if it doesn't exist
-mkdir -p "$OUTPUT_DIR"
+# Create output directories if they don't exist
+mkdir -p "$OUTPUT_DIR" "$PIN_DIR"
+
+# Pin raw metadata for reproducible builds with the `typed` feature
+PIN_NAME="$CHAIN"
+[ "$OUTPUT_FILE" = "custom.rs" ] && PIN_NAME="custom"
+echo -e "${YELLOW}Pinning metadata to $PIN_DIR/$PIN_NAME.scale...${NC}"
+subxt metadata \
+    --url "$ENDPOINT" \
+    --format bytes \
+    > "$PIN_DIR/$PIN_NAME.scale" || {
+        echo -e "${RED}Failed to fetch metadata from $ENDPOINT${NC}"
+        exit 1
+    }
+echo -e "${GREEN}✓${NC} Metadata pinned"
 
 echo -e "${YELLOW}Fetching metadata and generating Rust types from $ENDPOINT...${NC}"
 
@@ -112,3 +126,6 @@ echo "     use crate::metadata::${CHAIN}::runtime_types;"
 echo ""
 echo "Example usage:"
 echo "  let tx = ${CHAIN}::tx().balances().transfer_keep_alive(dest, amount);"
+echo ""
+echo "To build typed bindings from the pinned metadata instead:"
+echo "  APEX_METADATA_PATH=\$PWD/$PIN_DIR/$PIN_NAME.scale cargo build --features typed"
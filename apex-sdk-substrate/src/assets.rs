@@ -0,0 +1,355 @@
+//! `pallet-assets` client
+//!
+//! Asset Hub and other chains with `pallet-assets` hold fungible assets
+//! identified by a `u32` ID. The client reads balances, metadata and asset
+//! details, and submits transfers and the admin calls that create assets and
+//! mint supply.
+
+use crate::{
+    account_id, collect_bytes, multi_address, Error, ExtrinsicReceipt, Metrics, Result,
+    TransactionExecutor, Wallet,
+};
+use sp_core::crypto::{AccountId32, Ss58Codec};
+use subxt::dynamic::{At as _, Value};
+use subxt::ext::scale_value::{self, ValueDef};
+use subxt::{OnlineClient, PolkadotConfig};
+use tracing::info;
+
+/// Name, symbol and decimals of an asset
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AssetMetadata {
+    /// Asset name
+    pub name: String,
+    /// Ticker symbol
+    pub symbol: String,
+    /// Decimals of the smallest unit
+    pub decimals: u8,
+    /// Whether the metadata can no longer be changed by the owner
+    pub is_frozen: bool,
+}
+
+/// Supply and roles of an asset
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AssetDetails {
+    /// Account that created the asset (SS58)
+    pub owner: String,
+    /// Account allowed to mint (SS58)
+    pub issuer: String,
+    /// Account allowed to burn and force transfers (SS58)
+    pub admin: String,
+    /// Account allowed to freeze accounts (SS58)
+    pub freezer: String,
+    /// Total supply
+    pub supply: u128,
+    /// Smallest balance an account may hold
+    pub min_balance: u128,
+    /// Number of accounts holding the asset
+    pub accounts: u32,
+    /// Whether holding the asset is enough to keep an account alive
+    pub is_sufficient: bool,
+    /// `Live`, `Frozen` or `Destroying`
+    pub status: String,
+}
+
+/// Client for `pallet-assets`
+pub struct Assets {
+    client: OnlineClient<PolkadotConfig>,
+    metrics: Metrics,
+}
+
+impl Assets {
+    /// Create a client for the pallet on the chain behind `client`
+    pub fn new(client: OnlineClient<PolkadotConfig>, metrics: Metrics) -> Self {
+        Self { client, metrics }
+    }
+
+    /// Balance of `asset_id` held by `account`, 0 if it holds none
+    pub async fn balance_of(&self, asset_id: u32, account: &str) -> Result<u128> {
+        let account = account_id(account)?;
+        let value = self
+            .fetch(
+                "Account",
+                vec![Value::u128(asset_id.into()), Value::from_bytes(account)],
+            )
+            .await?;
+        Ok(value
+            .as_ref()
+            .and_then(|value| value.at("balance"))
+            .and_then(|balance| balance.as_u128())
+            .unwrap_or(0))
+    }
+
+    /// Metadata of `asset_id`, `None` if none was set
+    #[allow(clippy::result_large_err)]
+    pub async fn metadata(&self, asset_id: u32) -> Result<Option<AssetMetadata>> {
+        let value = self
+            .fetch("Metadata", vec![Value::u128(asset_id.into())])
+            .await?;
+        value
+            .map(|value| {
+                asset_metadata(&value).ok_or_else(|| {
+                    Error::Storage(format!("Malformed metadata of asset {}", asset_id))
+                })
+            })
+            .transpose()
+    }
+
+    /// Details of `asset_id`, `None` if the asset does not exist
+    #[allow(clippy::result_large_err)]
+    pub async fn details(&self, asset_id: u32) -> Result<Option<AssetDetails>> {
+        let value = self
+            .fetch("Asset", vec![Value::u128(asset_id.into())])
+            .await?;
+        value
+            .map(|value| {
+                asset_details(&value).ok_or_else(|| {
+                    Error::Storage(format!("Malformed details of asset {}", asset_id))
+                })
+            })
+            .transpose()
+    }
+
+    /// Transfer `amount` of `asset_id` from `wallet` to `to`
+    ///
+    /// With `keep_alive` the transfer fails rather than take the sender
+    /// below the asset's minimum balance.
+    pub async fn transfer(
+        &self,
+        wallet: &Wallet,
+        asset_id: u32,
+        to: &str,
+        amount: u128,
+        keep_alive: bool,
+    ) -> Result<ExtrinsicReceipt> {
+        info!(
+            "Transferring {} of asset {} from {} to {}",
+            amount,
+            asset_id,
+            wallet.address(),
+            to
+        );
+        let call = if keep_alive {
+            "transfer_keep_alive"
+        } else {
+            "transfer"
+        };
+        self.submit(
+            wallet,
+            call,
+            vec![
+                Value::u128(asset_id.into()),
+                multi_address(to)?,
+                Value::u128(amount),
+            ],
+        )
+        .await
+    }
+
+    /// Create `asset_id` with `wallet` as owner and `admin` as admin, issuer
+    /// and freezer
+    ///
+    /// The owner reserves the pallet's asset deposit.
+    pub async fn create(
+        &self,
+        wallet: &Wallet,
+        asset_id: u32,
+        admin: &str,
+        min_balance: u128,
+    ) -> Result<ExtrinsicReceipt> {
+        info!("Creating asset {} with admin {}", asset_id, admin);
+        self.submit(
+            wallet,
+            "create",
+            vec![
+                Value::u128(asset_id.into()),
+                multi_address(admin)?,
+                Value::u128(min_balance),
+            ],
+        )
+        .await
+    }
+
+    /// Set the name, symbol and decimals of `asset_id`, as its owner
+    pub async fn set_metadata(
+        &self,
+        wallet: &Wallet,
+        asset_id: u32,
+        name: &str,
+        symbol: &str,
+        decimals: u8,
+    ) -> Result<ExtrinsicReceipt> {
+        self.submit(
+            wallet,
+            "set_metadata",
+            vec![
+                Value::u128(asset_id.into()),
+                Value::from_bytes(name.as_bytes()),
+                Value::from_bytes(symbol.as_bytes()),
+                Value::u128(decimals.into()),
+            ],
+        )
+        .await
+    }
+
+    /// Mint `amount` of `asset_id` to `beneficiary`, as its issuer
+    pub async fn mint(
+        &self,
+        wallet: &Wallet,
+        asset_id: u32,
+        beneficiary: &str,
+        amount: u128,
+    ) -> Result<ExtrinsicReceipt> {
+        info!(
+            "Minting {} of asset {} to {}",
+            amount, asset_id, beneficiary
+        );
+        self.submit(
+            wallet,
+            "mint",
+            vec![
+                Value::u128(asset_id.into()),
+                multi_address(beneficiary)?,
+                Value::u128(amount),
+            ],
+        )
+        .await
+    }
+
+    async fn submit(
+        &self,
+        wallet: &Wallet,
+        call: &str,
+        fields: Vec<Value>,
+    ) -> Result<ExtrinsicReceipt> {
+        let call = subxt::dynamic::tx("Assets", call, fields);
+        TransactionExecutor::new(self.client.clone(), self.metrics.clone())
+            .submit_typed(&call, wallet)
+            .await
+    }
+
+    #[allow(clippy::result_large_err)]
+    async fn fetch(&self, item: &str, keys: Vec<Value>) -> Result<Option<scale_value::Value<u32>>> {
+        self.metrics.record_storage_query();
+        let query = subxt::dynamic::storage("Assets", item, keys);
+        let value = self
+            .client
+            .storage()
+            .at_latest()
+            .await
+            .map_err(|e| Error::Connection(format!("Failed to fetch latest block: {}", e)))?
+            .fetch(&query)
+            .await
+            .map_err(|e| Error::Storage(format!("Failed to query Assets.{}: {}", item, e)))?;
+
+        value
+            .map(|value| {
+                value
+                    .to_value()
+                    .map_err(|e| Error::Storage(format!("Failed to decode Assets.{}: {}", item, e)))
+            })
+            .transpose()
+    }
+}
+
+/// Decode an `AssetMetadata` storage value
+fn asset_metadata<T>(value: &scale_value::Value<T>) -> Option<AssetMetadata> {
+    Some(AssetMetadata {
+        name: text(value.at("name")?),
+        symbol: text(value.at("symbol")?),
+        decimals: u8::try_from(value.at("decimals")?.as_u128()?).ok()?,
+        is_frozen: value.at("is_frozen")?.as_bool()?,
+    })
+}
+
+/// Decode an `AssetDetails` storage value
+fn asset_details<T>(value: &scale_value::Value<T>) -> Option<AssetDetails> {
+    let account = |field: &str| {
+        let mut bytes = Vec::new();
+        collect_bytes(value.at(field)?, &mut bytes);
+        let bytes: [u8; 32] = bytes.try_into().ok()?;
+        Some(AccountId32::from(bytes).to_ss58check())
+    };
+    let status = match &value.at("status")?.value {
+        ValueDef::Variant(variant) => variant.name.clone(),
+        _ => return None,
+    };
+    Some(AssetDetails {
+        owner: account("owner")?,
+        issuer: account("issuer")?,
+        admin: account("admin")?,
+        freezer: account("freezer")?,
+        supply: value.at("supply")?.as_u128()?,
+        min_balance: value.at("min_balance")?.as_u128()?,
+        accounts: u32::try_from(value.at("accounts")?.as_u128()?).ok()?,
+        is_sufficient: value.at("is_sufficient")?.as_bool()?,
+        status,
+    })
+}
+
+/// A bounded byte string as text, lossily
+fn text<T>(value: &scale_value::Value<T>) -> String {
+    let mut bytes = Vec::new();
+    collect_bytes(value, &mut bytes);
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_asset_metadata() {
+        let value = Value::named_composite([
+            ("deposit", Value::u128(0)),
+            ("name", Value::from_bytes("Tether USD")),
+            ("symbol", Value::from_bytes("USDt")),
+            ("decimals", Value::u128(6)),
+            ("is_frozen", Value::bool(false)),
+        ]);
+        assert_eq!(
+            asset_metadata(&value),
+            Some(AssetMetadata {
+                name: "Tether USD".to_string(),
+                symbol: "USDt".to_string(),
+                decimals: 6,
+                is_frozen: false,
+            })
+        );
+        assert_eq!(asset_metadata(&Value::u128(6)), None);
+    }
+
+    #[test]
+    fn test_asset_details() {
+        let alice = [1u8; 32];
+        let bob = [2u8; 32];
+        let value = Value::named_composite([
+            ("owner", Value::from_bytes(alice)),
+            ("issuer", Value::from_bytes(bob)),
+            ("admin", Value::from_bytes(bob)),
+            ("freezer", Value::from_bytes(bob)),
+            ("supply", Value::u128(1_000_000)),
+            ("deposit", Value::u128(0)),
+            ("min_balance", Value::u128(700)),
+            ("is_sufficient", Value::bool(true)),
+            ("accounts", Value::u128(12)),
+            ("sufficients", Value::u128(12)),
+            ("approvals", Value::u128(0)),
+            ("status", Value::unnamed_variant("Live", [])),
+        ]);
+        let details = asset_details(&value).unwrap();
+        assert_eq!(details.owner, AccountId32::from(alice).to_ss58check());
+        assert_eq!(details.issuer, AccountId32::from(bob).to_ss58check());
+        assert_eq!(details.supply, 1_000_000);
+        assert_eq!(details.min_balance, 700);
+        assert_eq!(details.accounts, 12);
+        assert!(details.is_sufficient);
+        assert_eq!(details.status, "Live");
+    }
+
+    #[test]
+    fn test_multi_address() {
+        assert!(multi_address("not an address").is_err());
+        let alice = AccountId32::from([1u8; 32]).to_ss58check();
+        assert!(multi_address(&alice).is_ok());
+    }
+}
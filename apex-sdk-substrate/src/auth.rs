@@ -0,0 +1,204 @@
+//! Sign-in with Substrate accounts
+//!
+//! The SS58 counterpart of Sign-In with Ethereum: the same CAIP-122 message
+//! text with a `Substrate` preamble, an SS58 address and the chain's genesis
+//! hash as the chain ID. Like the Polkadot.js extension's `signRaw`, the
+//! message is wrapped in `<Bytes>...</Bytes>` before signing, so a sign-in
+//! signature can never double as an extrinsic signature.
+//!
+//! sr25519, ed25519 and ECDSA accounts are supported; the key type does not
+//! need to be known to verify.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use apex_sdk_substrate::{auth, Wallet};
+//!
+//! # fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let wallet = Wallet::new_random();
+//! let genesis = "0x91b171bb158e2d3848fa23a9f1c25182fb8e20313b2c1eb49219da7a70ce90c3";
+//!
+//! let message = auth::message(&wallet.address(), "example.com", "https://example.com", genesis);
+//! let text = message.to_string();
+//! let signature = auth::sign(&wallet, &message)?;
+//!
+//! let session = auth::verify(&text, &signature, "example.com", &message.nonce)?;
+//! println!("Signed in as {}", session.address);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::wallet::Wallet;
+use crate::{Error, Result};
+use apex_sdk_core::auth::{AuthError, SignInMessage};
+use sp_core::crypto::{AccountId32, Ss58Codec};
+use sp_core::{ecdsa, ed25519, sr25519, Pair as PairTrait};
+
+/// Namespace named in the message preamble
+pub const NAMESPACE: &str = "Substrate";
+
+/// Create a sign-in message for an SS58 `address`, issued now with a fresh
+/// nonce
+///
+/// `chain_id` identifies the chain, conventionally its genesis hash.
+pub fn message(
+    address: &str,
+    domain: impl Into<String>,
+    uri: impl Into<String>,
+    chain_id: impl Into<String>,
+) -> SignInMessage {
+    SignInMessage::new(NAMESPACE, domain, address, uri, chain_id)
+}
+
+/// Bytes actually signed for a message: its text wrapped in `<Bytes>` tags
+pub fn signing_payload(message: &str) -> Vec<u8> {
+    format!("<Bytes>{}</Bytes>", message).into_bytes()
+}
+
+/// Sign a message with `wallet`, returning the hex signature
+#[allow(clippy::result_large_err)]
+pub fn sign(wallet: &Wallet, message: &SignInMessage) -> Result<String> {
+    let account: [u8; 32] = account_id(&message.address)
+        .map_err(|e| Error::Wallet(e.to_string()))?
+        .into();
+    if account != wallet.account_id() {
+        return Err(Error::Wallet(format!(
+            "Message is for {}, not {}",
+            message.address,
+            wallet.address()
+        )));
+    }
+
+    let signature = wallet.sign(&signing_payload(&message.to_string()));
+    Ok(format!("0x{}", hex::encode(signature)))
+}
+
+/// Verify a signed message and its claims
+///
+/// `message` must be the exact text that was signed. Returns the parsed
+/// message if the signature was made by its address (sr25519, ed25519 or
+/// ECDSA) and the domain, nonce and validity window check out.
+pub fn verify(
+    message: &str,
+    signature: &str,
+    domain: &str,
+    nonce: &str,
+) -> std::result::Result<SignInMessage, AuthError> {
+    let parsed: SignInMessage = message.parse()?;
+    if parsed.namespace != NAMESPACE {
+        return Err(AuthError::Parse(format!(
+            "expected a {} account, got {}",
+            NAMESPACE, parsed.namespace
+        )));
+    }
+    let account = account_id(&parsed.address)?;
+
+    let signature = hex::decode(signature.trim_start_matches("0x"))
+        .map_err(|e| AuthError::InvalidSignature(e.to_string()))?;
+    let payload = signing_payload(message);
+    let public: [u8; 32] = account.into();
+
+    let valid = match <[u8; 64]>::try_from(&signature[..]) {
+        Ok(signature) => {
+            sr25519::Pair::verify(
+                &sr25519::Signature::from_raw(signature),
+                &payload,
+                &sr25519::Public::from_raw(public),
+            ) || ed25519::Pair::verify(
+                &ed25519::Signature::from_raw(signature),
+                &payload,
+                &ed25519::Public::from_raw(public),
+            )
+        }
+        // ECDSA accounts are a hash of the key, so recover it and compare
+        Err(_) => {
+            let signature: [u8; 65] = signature
+                .try_into()
+                .map_err(|_| AuthError::InvalidSignature("expected 64 or 65 bytes".to_string()))?;
+            ecdsa::Signature::from_raw(signature)
+                .recover(&payload)
+                .is_some_and(|key| sp_core::blake2_256(&key.0) == public)
+        }
+    };
+    if !valid {
+        return Err(AuthError::InvalidSignature(format!(
+            "not signed by {}",
+            parsed.address
+        )));
+    }
+
+    parsed.validate(domain, nonce)?;
+    Ok(parsed)
+}
+
+fn account_id(address: &str) -> std::result::Result<AccountId32, AuthError> {
+    AccountId32::from_ss58check(address)
+        .map_err(|e| AuthError::Parse(format!("invalid SS58 address {}: {:?}", address, e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wallet::KeyPairType;
+
+    const GENESIS: &str = "0x91b171bb158e2d3848fa23a9f1c25182fb8e20313b2c1eb49219da7a70ce90c3";
+
+    #[test]
+    fn test_sign_and_verify() {
+        for key_type in [
+            KeyPairType::Sr25519,
+            KeyPairType::Ed25519,
+            KeyPairType::Ecdsa,
+        ] {
+            let wallet = Wallet::new_random_with_type(key_type);
+            let message = message(
+                &wallet.address(),
+                "example.com",
+                "https://example.com",
+                GENESIS,
+            )
+            .with_statement("Sign in to Example");
+            let text = message.to_string();
+            assert!(
+                text.starts_with("example.com wants you to sign in with your Substrate account:")
+            );
+
+            let signature = sign(&wallet, &message).unwrap();
+            assert_eq!(
+                verify(&text, &signature, "example.com", &message.nonce).unwrap(),
+                message
+            );
+
+            let tampered = text.replace("Sign in to Example", "Sign in to Evil");
+            assert!(matches!(
+                verify(&tampered, &signature, "example.com", &message.nonce),
+                Err(AuthError::InvalidSignature(_))
+            ));
+            assert!(matches!(
+                verify(&text, &signature, "evil.com", &message.nonce),
+                Err(AuthError::DomainMismatch { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_rejects_other_signer_and_unwrapped_signature() {
+        let wallet = Wallet::new_random();
+        let message = message(
+            &wallet.address(),
+            "example.com",
+            "https://example.com",
+            GENESIS,
+        );
+        let text = message.to_string();
+
+        assert!(sign(&Wallet::new_random(), &message).is_err());
+
+        // A signature over the bare text is not a sign-in signature
+        let bare = format!("0x{}", hex::encode(wallet.sign(text.as_bytes())));
+        assert!(matches!(
+            verify(&text, &bare, "example.com", &message.nonce),
+            Err(AuthError::InvalidSignature(_))
+        ));
+    }
+}
@@ -0,0 +1,121 @@
+//! Typed bindings generation from pinned metadata
+//!
+//! Regenerates subxt bindings from a metadata file checked into the repository,
+//! so builds are reproducible and don't need a node. The file can be either raw
+//! SCALE-encoded metadata (`subxt metadata --format bytes`) or a
+//! [`ChainDescriptor`](crate::ChainDescriptor) JSON export.
+//!
+//! This module has no dependencies on the rest of the crate so it can be used
+//! from build scripts:
+//!
+//! ```ignore
+//! // build.rs
+//! fn main() {
+//!     let out = std::path::PathBuf::from(std::env::var("OUT_DIR").unwrap());
+//!     apex_sdk_substrate::codegen::write_bindings("metadata/westend.scale", out.join("westend.rs"))
+//!         .expect("failed to generate bindings");
+//! }
+//!
+//! // src/lib.rs
+//! include!(concat!(env!("OUT_DIR"), "/westend.rs")); // defines `pub mod api`
+//! ```
+//!
+//! The generated code refers to `::subxt`, so the including crate must depend
+//! on the same `subxt` version as this crate.
+
+use std::fmt;
+use std::path::Path;
+
+/// Bindings generation error
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodegenError(pub String);
+
+impl fmt::Display for CodegenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Codegen error: {}", self.0)
+    }
+}
+
+impl std::error::Error for CodegenError {}
+
+/// Read SCALE-encoded metadata from a `.scale` file or a descriptor `.json` file
+pub fn load_metadata(path: impl AsRef<Path>) -> Result<Vec<u8>, CodegenError> {
+    let path = path.as_ref();
+    let bytes = std::fs::read(path)
+        .map_err(|e| CodegenError(format!("Failed to read {}: {}", path.display(), e)))?;
+
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("json") => metadata_from_descriptor(&bytes),
+        _ => Ok(bytes),
+    }
+}
+
+fn metadata_from_descriptor(json: &[u8]) -> Result<Vec<u8>, CodegenError> {
+    let descriptor: serde_json::Value = serde_json::from_slice(json)
+        .map_err(|e| CodegenError(format!("Invalid chain descriptor: {}", e)))?;
+    let metadata = descriptor
+        .get("metadata")
+        .and_then(|m| m.as_str())
+        .ok_or_else(|| CodegenError("Chain descriptor has no metadata field".to_string()))?;
+
+    hex::decode(metadata.trim_start_matches("0x"))
+        .map_err(|e| CodegenError(format!("Invalid metadata hex: {}", e)))
+}
+
+/// Generate bindings (a `pub mod api { ... }` module) from a pinned metadata file
+pub fn generate_bindings(path: impl AsRef<Path>) -> Result<String, CodegenError> {
+    use parity_scale_codec::Decode;
+
+    let bytes = load_metadata(path)?;
+    let metadata = subxt_metadata::Metadata::decode(&mut &bytes[..])
+        .map_err(|e| CodegenError(format!("Failed to decode metadata: {}", e)))?;
+
+    let code = subxt_codegen::CodegenBuilder::new()
+        .generate(metadata)
+        .map_err(|e| CodegenError(format!("Failed to generate bindings: {}", e)))?;
+
+    Ok(code.to_string())
+}
+
+/// Generate bindings and write them to `out_file`
+///
+/// Emits `cargo:rerun-if-changed` for the metadata file, so bindings are only
+/// regenerated when the pinned metadata changes.
+pub fn write_bindings(
+    metadata_path: impl AsRef<Path>,
+    out_file: impl AsRef<Path>,
+) -> Result<(), CodegenError> {
+    let metadata_path = metadata_path.as_ref();
+    println!("cargo:rerun-if-changed={}", metadata_path.display());
+
+    let code = generate_bindings(metadata_path)?;
+    std::fs::write(out_file.as_ref(), code).map_err(|e| {
+        CodegenError(format!(
+            "Failed to write {}: {}",
+            out_file.as_ref().display(),
+            e
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metadata_from_descriptor() {
+        let json = br#"{"version": 1, "metadata": "0x6d657461"}"#;
+        assert_eq!(metadata_from_descriptor(json).unwrap(), b"meta".to_vec());
+    }
+
+    #[test]
+    fn test_descriptor_without_metadata() {
+        assert!(metadata_from_descriptor(br#"{"version": 1}"#).is_err());
+        assert!(metadata_from_descriptor(b"not json").is_err());
+    }
+
+    #[test]
+    fn test_missing_file() {
+        assert!(load_metadata("does/not/exist.scale").is_err());
+    }
+}
@@ -5,24 +5,22 @@
 //!
 //! ## Features
 //!
-//! - Deploy compiled ink! contracts (Wasm)
+//! - Upload code and instantiate ink! contracts (Wasm), with dry-run gas
+//!   estimation
 //! - Call contract methods (read and write)
 //! - Parse contract metadata
-//! - Handle contract events
+//! - Decode contract events with the metadata ABI
 //! - Gas estimation for contract calls
 //!
 //! ## Example
 //!
 //! ```rust,ignore
-//! use apex_sdk_substrate::contracts::ContractClient;
+//! use apex_sdk_substrate::contracts::ContractDeployer;
 //!
-//! // Deploy a contract
-//! let contract = ContractClient::deploy(
-//!     client,
-//!     wasm_code,
-//!     metadata,
-//!     constructor_args,
-//! ).await?;
+//! // Deploy a contract, estimating its gas with a dry run
+//! let contract = ContractDeployer::new(client, metadata)
+//!     .instantiate_with_code(&wallet, wasm_code, "new", &constructor_args)
+//!     .await?;
 //!
 //! // Call a contract method
 //! let result = contract
@@ -32,8 +30,11 @@
 //!     .await?;
 //! ```
 
-use crate::{Error, Result, Sr25519Signer, Wallet};
+use crate::{collect_bytes, Error, ExtrinsicReceipt, Result, Sr25519Signer, Wallet};
+use parity_scale_codec::{Compact, Decode};
 use serde::{Deserialize, Serialize};
+use subxt::dynamic::Value;
+use subxt::ext::scale_value::{Composite, Primitive, ValueDef};
 use subxt::{OnlineClient, PolkadotConfig};
 use tracing::{debug, info};
 
@@ -112,6 +113,9 @@ pub struct EventSpec {
     pub args: Vec<EventArg>,
     /// Documentation
     pub docs: Vec<String>,
+    /// First topic identifying the event (hex), ink! 5 and later
+    #[serde(default)]
+    pub signature_topic: Option<String>,
 }
 
 /// Event argument
@@ -182,6 +186,7 @@ pub enum TypeDefVariant {
     Array { len: u32, type_param: u32 },
     Tuple { fields: Vec<u32> },
     Primitive { primitive: String },
+    Compact { type_param: u32 },
 }
 
 /// Field definition
@@ -205,7 +210,9 @@ pub struct Variant {
 }
 
 /// Gas limit for contract calls
-#[derive(Debug, Clone, Copy, parity_scale_codec::Encode, parity_scale_codec::Decode)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, parity_scale_codec::Encode, parity_scale_codec::Decode,
+)]
 pub struct GasLimit {
     /// Reference time
     pub ref_time: u64,
@@ -248,13 +255,28 @@ pub enum StorageDepositLimit {
     Limited(u128),
 }
 
+/// Outcome of a dry-run contract call
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CallDryRun {
+    /// Weight consumed by the dry run
+    pub gas_consumed: GasLimit,
+    /// Weight the call needs as its gas limit
+    pub gas_required: GasLimit,
+    /// Storage deposit charged, 0 if the call refunds
+    pub storage_deposit: u128,
+    /// Whether the contract reverted
+    pub reverted: bool,
+    /// SCALE-encoded return value
+    pub data: Vec<u8>,
+}
+
 /// Contract call builder
-#[allow(dead_code)]
 pub struct ContractCallBuilder {
     contract_address: ContractAddress,
     selector: [u8; 4],
     args: Vec<u8>,
     gas_limit: GasLimit,
+    auto_gas: bool,
     storage_deposit: StorageDepositLimit,
     value: u128,
 }
@@ -267,6 +289,7 @@ impl ContractCallBuilder {
             selector,
             args: Vec::new(),
             gas_limit: GasLimit::default_call(),
+            auto_gas: false,
             storage_deposit: StorageDepositLimit::NoLimit,
             value: 0,
         }
@@ -281,6 +304,14 @@ impl ContractCallBuilder {
     /// Set the gas limit
     pub fn gas_limit(mut self, limit: GasLimit) -> Self {
         self.gas_limit = limit;
+        self.auto_gas = false;
+        self
+    }
+
+    /// Dry-run the call before [`submit`](Self::submit) and use the weight
+    /// it required as the gas limit
+    pub fn auto_gas(mut self) -> Self {
+        self.auto_gas = true;
         self
     }
 
@@ -303,6 +334,674 @@ impl ContractCallBuilder {
         call_data.extend_from_slice(&self.args);
         call_data
     }
+
+    /// Execute the call through the `ContractsApi_call` runtime API as
+    /// `origin`, without submitting it
+    ///
+    /// With [`auto_gas`](Self::auto_gas) the dry run is not limited by the
+    /// builder's gas limit.
+    pub async fn dry_run(
+        &self,
+        client: &OnlineClient<PolkadotConfig>,
+        origin: &[u8; 32],
+    ) -> Result<CallDryRun> {
+        use parity_scale_codec::Encode;
+
+        let storage_deposit_limit = match self.storage_deposit {
+            StorageDepositLimit::NoLimit => None,
+            StorageDepositLimit::Limited(amount) => Some(amount),
+        };
+
+        // (origin, dest, value, gas_limit, storage_deposit_limit, input_data)
+        let mut params = Vec::new();
+        origin.encode_to(&mut params);
+        self.contract_address.encode_to(&mut params);
+        self.value.encode_to(&mut params);
+        if self.auto_gas {
+            0u8.encode_to(&mut params);
+        } else {
+            1u8.encode_to(&mut params);
+            encode_weight(&self.gas_limit, &mut params);
+        }
+        storage_deposit_limit.encode_to(&mut params);
+        self.build_call_data().encode_to(&mut params);
+
+        let result = client
+            .backend()
+            .call(
+                "ContractsApi_call",
+                Some(&params),
+                client.backend().latest_finalized_block_ref().await?.hash(),
+            )
+            .await
+            .map_err(|e| Error::Transaction(format!("ContractsApi_call failed: {}", e)))?;
+        decode_exec_result(&mut &result[..])
+    }
+
+    /// Sign and submit the call with `wallet`, returning the transaction hash
+    /// once it succeeded in a finalized block
+    ///
+    /// With [`auto_gas`](Self::auto_gas) the call is dry-run first and not
+    /// submitted if the contract would revert.
+    pub async fn submit(
+        &self,
+        client: &OnlineClient<PolkadotConfig>,
+        wallet: &Wallet,
+    ) -> Result<String> {
+        let gas_limit = if self.auto_gas {
+            let origin = wallet.account_id();
+            let dry_run = self.dry_run(client, &origin).await?;
+            if dry_run.reverted {
+                return Err(Error::Transaction(format!(
+                    "Contract call would revert: 0x{}",
+                    hex::encode(&dry_run.data)
+                )));
+            }
+            debug!(
+                "Estimated call gas: {:?}, storage deposit: {}",
+                dry_run.gas_required, dry_run.storage_deposit
+            );
+            dry_run.gas_required
+        } else {
+            self.gas_limit
+        };
+
+        let call = subxt::dynamic::tx(
+            "Contracts",
+            "call",
+            vec![
+                Value::unnamed_variant("Id", [Value::from_bytes(self.contract_address)]),
+                Value::u128(self.value),
+                ContractClient::encode_gas_limit(&gas_limit)?,
+                ContractClient::encode_storage_deposit(&self.storage_deposit)?,
+                Value::from_bytes(self.build_call_data()),
+            ],
+        );
+        let events = submit_and_watch(client, &call, wallet).await?;
+        let tx_hash = format!("0x{}", hex::encode(events.extrinsic_hash()));
+        info!("Contract call finalized: {}", tx_hash);
+        Ok(tx_hash)
+    }
+}
+
+/// Code to instantiate a contract from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ContractCode {
+    /// Wasm blob uploaded with the instantiation
+    Upload(Vec<u8>),
+    /// Hash of code already on chain
+    Existing([u8; 32]),
+}
+
+/// Outcome of a dry-run instantiation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InstantiateEstimate {
+    /// Weight consumed by the dry run
+    pub gas_consumed: GasLimit,
+    /// Weight the instantiation needs as its gas limit
+    pub gas_required: GasLimit,
+    /// Storage deposit charged, 0 if the instantiation refunds
+    pub storage_deposit: u128,
+    /// Address the contract would get
+    pub address: ContractAddress,
+}
+
+/// Uploads contract code and instantiates contracts
+///
+/// Without an explicit [`gas_limit`](Self::gas_limit), each instantiation is
+/// dry-run through `ContractsApi_instantiate` first and submitted with the
+/// weight it reported.
+pub struct ContractDeployer {
+    client: OnlineClient<PolkadotConfig>,
+    metadata: ContractMetadata,
+    value: u128,
+    salt: Vec<u8>,
+    gas_limit: Option<GasLimit>,
+    storage_deposit: StorageDepositLimit,
+}
+
+impl ContractDeployer {
+    /// Create a deployer for contracts described by `metadata`
+    pub fn new(client: OnlineClient<PolkadotConfig>, metadata: ContractMetadata) -> Self {
+        Self {
+            client,
+            metadata,
+            value: 0,
+            salt: vec![0u8; 32],
+            gas_limit: None,
+            storage_deposit: StorageDepositLimit::NoLimit,
+        }
+    }
+
+    /// Set the balance transferred to the new contract
+    pub fn value(mut self, value: u128) -> Self {
+        self.value = value;
+        self
+    }
+
+    /// Set the salt for the contract address (32 zero bytes by default)
+    pub fn salt(mut self, salt: Vec<u8>) -> Self {
+        self.salt = salt;
+        self
+    }
+
+    /// Use `limit` instead of a dry-run estimate
+    pub fn gas_limit(mut self, limit: GasLimit) -> Self {
+        self.gas_limit = Some(limit);
+        self
+    }
+
+    /// Set the storage deposit limit
+    pub fn storage_deposit(mut self, limit: StorageDepositLimit) -> Self {
+        self.storage_deposit = limit;
+        self
+    }
+
+    /// Upload `wasm` without instantiating it, returning its code hash
+    pub async fn upload_code(&self, wallet: &Wallet, wasm: &[u8]) -> Result<[u8; 32]> {
+        let code_hash = sp_core::blake2_256(wasm);
+        info!("Uploading contract code 0x{}", hex::encode(code_hash));
+        let call = subxt::dynamic::tx(
+            "Contracts",
+            "upload_code",
+            vec![
+                Value::from_bytes(wasm),
+                ContractClient::encode_storage_deposit(&self.storage_deposit)?,
+                Value::unnamed_variant("Enforced", []),
+            ],
+        );
+        submit_and_watch(&self.client, &call, wallet).await?;
+        Ok(code_hash)
+    }
+
+    /// Instantiate the uploaded code `code_hash` with `constructor`
+    pub async fn instantiate(
+        &self,
+        wallet: &Wallet,
+        code_hash: [u8; 32],
+        constructor: &str,
+        args: &[u8],
+    ) -> Result<ContractClient> {
+        self.submit_instantiate(wallet, ContractCode::Existing(code_hash), constructor, args)
+            .await
+    }
+
+    /// Upload `wasm` and instantiate it with `constructor` in one extrinsic
+    pub async fn instantiate_with_code(
+        &self,
+        wallet: &Wallet,
+        wasm: Vec<u8>,
+        constructor: &str,
+        args: &[u8],
+    ) -> Result<ContractClient> {
+        self.submit_instantiate(wallet, ContractCode::Upload(wasm), constructor, args)
+            .await
+    }
+
+    /// Dry-run instantiating `code` with `constructor` as `origin`
+    ///
+    /// Fails if the constructor reverts or the runtime rejects the
+    /// instantiation.
+    pub async fn estimate(
+        &self,
+        origin: &[u8; 32],
+        code: &ContractCode,
+        constructor: &str,
+        args: &[u8],
+    ) -> Result<InstantiateEstimate> {
+        use parity_scale_codec::Encode;
+
+        let data = self.constructor_data(constructor, args)?;
+        let storage_deposit_limit = match self.storage_deposit {
+            StorageDepositLimit::NoLimit => None,
+            StorageDepositLimit::Limited(amount) => Some(amount),
+        };
+
+        // (origin, value, gas_limit, storage_deposit_limit, code, data, salt)
+        let mut params = Vec::new();
+        origin.encode_to(&mut params);
+        self.value.encode_to(&mut params);
+        None::<GasLimit>.encode_to(&mut params);
+        storage_deposit_limit.encode_to(&mut params);
+        match code {
+            ContractCode::Upload(wasm) => {
+                0u8.encode_to(&mut params);
+                wasm.encode_to(&mut params);
+            }
+            ContractCode::Existing(code_hash) => {
+                1u8.encode_to(&mut params);
+                code_hash.encode_to(&mut params);
+            }
+        }
+        data.encode_to(&mut params);
+        self.salt.encode_to(&mut params);
+
+        let result = self
+            .client
+            .backend()
+            .call(
+                "ContractsApi_instantiate",
+                Some(&params),
+                self.client
+                    .backend()
+                    .latest_finalized_block_ref()
+                    .await?
+                    .hash(),
+            )
+            .await
+            .map_err(|e| Error::Transaction(format!("ContractsApi_instantiate failed: {}", e)))?;
+        decode_instantiate_result(&result)
+    }
+
+    async fn submit_instantiate(
+        &self,
+        wallet: &Wallet,
+        code: ContractCode,
+        constructor: &str,
+        args: &[u8],
+    ) -> Result<ContractClient> {
+        info!("Instantiating contract with constructor: {}", constructor);
+        let gas_limit = match self.gas_limit {
+            Some(limit) => limit,
+            None => {
+                let origin = wallet.account_id();
+                let estimate = self.estimate(&origin, &code, constructor, args).await?;
+                debug!(
+                    "Estimated instantiation gas: {:?}, storage deposit: {}",
+                    estimate.gas_required, estimate.storage_deposit
+                );
+                estimate.gas_required
+            }
+        };
+
+        let data = self.constructor_data(constructor, args)?;
+        let mut fields = vec![
+            Value::u128(self.value),
+            ContractClient::encode_gas_limit(&gas_limit)?,
+            ContractClient::encode_storage_deposit(&self.storage_deposit)?,
+        ];
+        let call = match code {
+            ContractCode::Upload(wasm) => {
+                fields.extend([
+                    Value::from_bytes(wasm),
+                    Value::from_bytes(&data),
+                    Value::from_bytes(&self.salt),
+                ]);
+                subxt::dynamic::tx("Contracts", "instantiate_with_code", fields)
+            }
+            ContractCode::Existing(code_hash) => {
+                fields.extend([
+                    Value::from_bytes(code_hash),
+                    Value::from_bytes(&data),
+                    Value::from_bytes(&self.salt),
+                ]);
+                subxt::dynamic::tx("Contracts", "instantiate", fields)
+            }
+        };
+        let events = submit_and_watch(&self.client, &call, wallet).await?;
+
+        for event in events.iter() {
+            let event =
+                event.map_err(|e| Error::Transaction(format!("Failed to decode event: {}", e)))?;
+            if event.pallet_name() != "Contracts" || event.variant_name() != "Instantiated" {
+                continue;
+            }
+            let fields = event
+                .field_values()
+                .map_err(|e| Error::Transaction(format!("Failed to decode event: {}", e)))?;
+            let mut bytes = Vec::new();
+            if let Composite::Named(fields) = &fields {
+                if let Some((_, contract)) = fields.iter().find(|(name, _)| name == "contract") {
+                    collect_bytes(contract, &mut bytes);
+                }
+            }
+            let address: ContractAddress = bytes.try_into().map_err(|_| {
+                Error::Transaction("Malformed contract address in Instantiated event".to_string())
+            })?;
+            info!("Contract instantiated at 0x{}", hex::encode(address));
+            return Ok(ContractClient::with_metadata(
+                self.client.clone(),
+                address,
+                self.metadata.clone(),
+            ));
+        }
+
+        Err(Error::Transaction(
+            "Contract deployment succeeded but address not found in events".to_string(),
+        ))
+    }
+
+    /// Selector of `constructor` followed by `args`
+    #[allow(clippy::result_large_err)]
+    fn constructor_data(&self, constructor: &str, args: &[u8]) -> Result<Vec<u8>> {
+        let constructor = self
+            .metadata
+            .spec
+            .constructors
+            .iter()
+            .find(|c| c.label == constructor)
+            .ok_or_else(|| {
+                Error::Transaction(format!("Constructor '{}' not found", constructor))
+            })?;
+        let mut data = constructor.selector.to_vec();
+        data.extend_from_slice(args);
+        Ok(data)
+    }
+}
+
+/// Sign `call` with `wallet`, submit it and wait for it to succeed in a
+/// finalized block
+async fn submit_and_watch<Call: subxt::tx::Payload>(
+    client: &OnlineClient<PolkadotConfig>,
+    call: &Call,
+    wallet: &Wallet,
+) -> Result<subxt::blocks::ExtrinsicEvents<PolkadotConfig>> {
+    let pair = wallet
+        .sr25519_pair()
+        .ok_or_else(|| Error::Transaction("Wallet does not have SR25519 key".to_string()))?;
+    let signer = Sr25519Signer::new(pair.clone());
+
+    let mut progress = client
+        .tx()
+        .sign_and_submit_then_watch_default(call, &signer)
+        .await
+        .map_err(|e| Error::Transaction(format!("Failed to submit transaction: {}", e)))?;
+
+    while let Some(event) = progress.next().await {
+        let event = event.map_err(|e| Error::Transaction(format!("Transaction error: {}", e)))?;
+        if let Some(finalized) = event.as_finalized() {
+            debug!(
+                "Transaction finalized: 0x{}",
+                hex::encode(finalized.extrinsic_hash())
+            );
+            return finalized
+                .wait_for_success()
+                .await
+                .map_err(|e| Error::Transaction(format!("Transaction failed: {}", e)));
+        }
+    }
+
+    Err(Error::Transaction(
+        "Transaction stream ended without finalization".to_string(),
+    ))
+}
+
+/// Decode a SCALE `Weight`, whose fields are compact
+#[allow(clippy::result_large_err)]
+fn decode_weight(input: &mut &[u8]) -> Result<GasLimit> {
+    let ref_time = Compact::<u64>::decode(input)
+        .map_err(|e| Error::Transaction(format!("Failed to decode weight: {}", e)))?;
+    let proof_size = Compact::<u64>::decode(input)
+        .map_err(|e| Error::Transaction(format!("Failed to decode weight: {}", e)))?;
+    Ok(GasLimit::new(ref_time.0, proof_size.0))
+}
+
+/// Decode the fields shared by `ContractExecResult` and
+/// `ContractInstantiateResult`, up to the return value
+#[allow(clippy::result_large_err)]
+fn decode_exec_result(input: &mut &[u8]) -> Result<CallDryRun> {
+    let decode_error = |e: parity_scale_codec::Error| {
+        Error::Transaction(format!("Failed to decode dry run: {}", e))
+    };
+
+    let gas_consumed = decode_weight(input)?;
+    let gas_required = decode_weight(input)?;
+    // StorageDeposit: Refund(Balance) | Charge(Balance)
+    let deposit_variant = u8::decode(input).map_err(decode_error)?;
+    let deposit = u128::decode(input).map_err(decode_error)?;
+    let storage_deposit = if deposit_variant == 1 { deposit } else { 0 };
+    let debug_message = Vec::<u8>::decode(input).map_err(decode_error)?;
+    if !debug_message.is_empty() {
+        debug!(
+            "Contract debug message: {}",
+            String::from_utf8_lossy(&debug_message)
+        );
+    }
+
+    if u8::decode(input).map_err(decode_error)? != 0 {
+        return Err(Error::Transaction(format!(
+            "Dry run failed with DispatchError 0x{}",
+            hex::encode(*input)
+        )));
+    }
+    let flags = u32::decode(input).map_err(decode_error)?;
+    let data = Vec::<u8>::decode(input).map_err(decode_error)?;
+
+    Ok(CallDryRun {
+        gas_consumed,
+        gas_required,
+        storage_deposit,
+        // ReturnFlags::REVERT
+        reverted: flags & 1 != 0,
+        data,
+    })
+}
+
+/// Decode a `ContractInstantiateResult` returned by `ContractsApi_instantiate`
+#[allow(clippy::result_large_err)]
+fn decode_instantiate_result(bytes: &[u8]) -> Result<InstantiateEstimate> {
+    let mut input = bytes;
+    let exec = decode_exec_result(&mut input)?;
+    let address = <[u8; 32]>::decode(&mut input)
+        .map_err(|e| Error::Transaction(format!("Failed to decode dry run: {}", e)))?;
+    if exec.reverted {
+        return Err(Error::Transaction(
+            "Contract constructor reverted in dry run".to_string(),
+        ));
+    }
+
+    Ok(InstantiateEstimate {
+        gas_consumed: exec.gas_consumed,
+        gas_required: exec.gas_required,
+        storage_deposit: exec.storage_deposit,
+        address,
+    })
+}
+
+/// Encode a `Weight`, whose fields are compact
+fn encode_weight(limit: &GasLimit, dest: &mut Vec<u8>) {
+    use parity_scale_codec::Encode;
+    Compact(limit.ref_time).encode_to(dest);
+    Compact(limit.proof_size).encode_to(dest);
+}
+
+/// Where to look for contract events
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EventSource {
+    /// Every extrinsic of the block with this hash
+    Block(String),
+    /// One extrinsic of a block, by hash
+    Extrinsic {
+        /// Hash of the block holding the extrinsic
+        block_hash: String,
+        /// Extrinsic hash
+        tx_hash: String,
+    },
+}
+
+impl From<&ExtrinsicReceipt> for EventSource {
+    fn from(receipt: &ExtrinsicReceipt) -> Self {
+        EventSource::Extrinsic {
+            block_hash: receipt.block_hash.clone(),
+            tx_hash: receipt.hash.clone(),
+        }
+    }
+}
+
+/// An ink! event decoded with the contract's metadata
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContractEvent {
+    /// Event label in the metadata
+    pub name: String,
+    /// Arguments by label, in declaration order
+    pub fields: Vec<(String, Value)>,
+}
+
+impl ContractEvent {
+    /// Argument `label`
+    pub fn field(&self, label: &str) -> Option<&Value> {
+        self.fields
+            .iter()
+            .find(|(name, _)| name == label)
+            .map(|(_, value)| value)
+    }
+}
+
+/// Decode the `data` of a `ContractEmitted` event with `metadata`
+///
+/// ink! 5 events are identified by their signature topic, the first of the
+/// event's `topics`; earlier versions prefix `data` with the event's index
+/// in the metadata.
+#[allow(clippy::result_large_err)]
+pub fn decode_event(
+    metadata: &ContractMetadata,
+    topics: &[[u8; 32]],
+    data: &[u8],
+) -> Result<ContractEvent> {
+    let events = &metadata.spec.events;
+    let mut input = data;
+    let spec = if events.iter().any(|event| event.signature_topic.is_some()) {
+        let topic = topics
+            .first()
+            .map(hex::encode)
+            .ok_or_else(|| Error::Encoding("Event has no signature topic".to_string()))?;
+        events
+            .iter()
+            .find(|event| {
+                event
+                    .signature_topic
+                    .as_deref()
+                    .map(|t| t.trim_start_matches("0x"))
+                    == Some(topic.as_str())
+            })
+            .ok_or_else(|| Error::Encoding(format!("Unknown event topic 0x{}", topic)))?
+    } else {
+        let index = *input
+            .first()
+            .ok_or_else(|| Error::Encoding("Empty event data".to_string()))?;
+        input = &input[1..];
+        events
+            .get(index as usize)
+            .ok_or_else(|| Error::Encoding(format!("Unknown event index {}", index)))?
+    };
+
+    let fields = spec
+        .args
+        .iter()
+        .map(|arg| {
+            Ok((
+                arg.label.clone(),
+                decode_value(&metadata.types, arg.type_ref.ty, &mut input)?,
+            ))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(ContractEvent {
+        name: spec.label.clone(),
+        fields,
+    })
+}
+
+/// Decode a value of type `ty` from `input` with the contract's type table
+#[allow(clippy::result_large_err)]
+fn decode_value(types: &[TypeDef], ty: u32, input: &mut &[u8]) -> Result<Value> {
+    let def = types
+        .iter()
+        .find(|def| def.id == ty)
+        .ok_or_else(|| Error::Encoding(format!("Unknown type {}", ty)))?;
+    let decode_error = |e: parity_scale_codec::Error| {
+        Error::Encoding(format!("Failed to decode type {}: {}", ty, e))
+    };
+
+    Ok(match &def.def {
+        TypeDefVariant::Composite { fields } => {
+            Value::without_context(ValueDef::Composite(decode_fields(types, fields, input)?))
+        }
+        TypeDefVariant::Variant { variants } => {
+            let index = u8::decode(input).map_err(decode_error)?;
+            let variant = variants
+                .iter()
+                .find(|variant| variant.index == index)
+                .ok_or_else(|| {
+                    Error::Encoding(format!("Unknown variant {} of type {}", index, ty))
+                })?;
+            let fields = decode_fields(types, &variant.fields, input)?;
+            Value::variant(variant.name.clone(), fields)
+        }
+        TypeDefVariant::Sequence { type_param } => {
+            let len = Compact::<u32>::decode(input).map_err(decode_error)?.0;
+            Value::unnamed_composite(
+                (0..len)
+                    .map(|_| decode_value(types, *type_param, input))
+                    .collect::<Result<Vec<_>>>()?,
+            )
+        }
+        TypeDefVariant::Array { len, type_param } => Value::unnamed_composite(
+            (0..*len)
+                .map(|_| decode_value(types, *type_param, input))
+                .collect::<Result<Vec<_>>>()?,
+        ),
+        TypeDefVariant::Tuple { fields } => Value::unnamed_composite(
+            fields
+                .iter()
+                .map(|field| decode_value(types, *field, input))
+                .collect::<Result<Vec<_>>>()?,
+        ),
+        TypeDefVariant::Compact { .. } => {
+            Value::u128(Compact::<u128>::decode(input).map_err(decode_error)?.0)
+        }
+        TypeDefVariant::Primitive { primitive } => match primitive.as_str() {
+            "bool" => Value::bool(bool::decode(input).map_err(decode_error)?),
+            "char" => {
+                let code = u32::decode(input).map_err(decode_error)?;
+                Value::char(
+                    char::from_u32(code)
+                        .ok_or_else(|| Error::Encoding(format!("Invalid char {}", code)))?,
+                )
+            }
+            "str" => Value::string(String::decode(input).map_err(decode_error)?),
+            "u8" => Value::u128(u8::decode(input).map_err(decode_error)?.into()),
+            "u16" => Value::u128(u16::decode(input).map_err(decode_error)?.into()),
+            "u32" => Value::u128(u32::decode(input).map_err(decode_error)?.into()),
+            "u64" => Value::u128(u64::decode(input).map_err(decode_error)?.into()),
+            "u128" => Value::u128(u128::decode(input).map_err(decode_error)?),
+            "u256" => Value::primitive(Primitive::U256(
+                <[u8; 32]>::decode(input).map_err(decode_error)?,
+            )),
+            "i8" => Value::i128(i8::decode(input).map_err(decode_error)?.into()),
+            "i16" => Value::i128(i16::decode(input).map_err(decode_error)?.into()),
+            "i32" => Value::i128(i32::decode(input).map_err(decode_error)?.into()),
+            "i64" => Value::i128(i64::decode(input).map_err(decode_error)?.into()),
+            "i128" => Value::i128(i128::decode(input).map_err(decode_error)?),
+            "i256" => Value::primitive(Primitive::I256(
+                <[u8; 32]>::decode(input).map_err(decode_error)?,
+            )),
+            other => {
+                return Err(Error::Encoding(format!(
+                    "Unsupported primitive {} in type {}",
+                    other, ty
+                )))
+            }
+        },
+    })
+}
+
+/// Decode `fields` in order, named if they all have names
+#[allow(clippy::result_large_err)]
+fn decode_fields(types: &[TypeDef], fields: &[Field], input: &mut &[u8]) -> Result<Composite<()>> {
+    let values = fields
+        .iter()
+        .map(|field| decode_value(types, field.ty, input))
+        .collect::<Result<Vec<_>>>()?;
+    if !fields.is_empty() && fields.iter().all(|field| field.name.is_some()) {
+        Ok(Composite::named(
+            fields
+                .iter()
+                .filter_map(|field| field.name.clone())
+                .zip(values),
+        ))
+    } else {
+        Ok(Composite::unnamed(values))
+    }
 }
 
 /// Contract client for interacting with deployed contracts
@@ -359,114 +1058,14 @@ impl ContractClient {
         wallet: &Wallet,
         salt: Option<Vec<u8>>,
     ) -> Result<Self> {
-        info!("Deploying contract with constructor: {}", constructor_name);
-
-        // Find the constructor
-        let constructor = metadata
-            .spec
-            .constructors
-            .iter()
-            .find(|c| c.label == constructor_name)
-            .ok_or_else(|| {
-                Error::Transaction(format!("Constructor '{}' not found", constructor_name))
-            })?;
-
-        // Build constructor call data
-        let mut call_data = Vec::new();
-        call_data.extend_from_slice(&constructor.selector);
-        call_data.extend_from_slice(constructor_args);
-
-        // Prepare salt (use default if not provided)
-        let salt = salt.unwrap_or_else(|| vec![0u8; 32]);
-
-        // Build the instantiate call
-        let gas_limit = GasLimit::default_deploy();
-        let storage_deposit = StorageDepositLimit::NoLimit;
-
-        let instantiate_call = subxt::dynamic::tx(
-            "Contracts",
-            "instantiate",
-            vec![
-                subxt::dynamic::Value::u128(0), // value
-                Self::encode_gas_limit(&gas_limit)?,
-                Self::encode_storage_deposit(&storage_deposit)?,
-                subxt::dynamic::Value::from_bytes(&wasm_code),
-                subxt::dynamic::Value::from_bytes(&call_data),
-                subxt::dynamic::Value::from_bytes(&salt),
-            ],
-        );
-
-        // Submit the transaction
-        let pair = wallet
-            .sr25519_pair()
-            .ok_or_else(|| Error::Transaction("Wallet does not have SR25519 key".to_string()))?;
-
-        let signer = Sr25519Signer::new(pair.clone());
-
-        let mut progress = client
-            .tx()
-            .sign_and_submit_then_watch_default(&instantiate_call, &signer)
-            .await
-            .map_err(|e| {
-                Error::Transaction(format!("Failed to submit deploy transaction: {}", e))
-            })?;
-
-        // Wait for finalization and extract contract address
-        while let Some(event) = progress.next().await {
-            let event = event
-                .map_err(|e| Error::Transaction(format!("Deploy transaction error: {}", e)))?;
-
-            if let Some(finalized) = event.as_finalized() {
-                info!("Contract deployment finalized");
-
-                // Extract contract address from events
-                let events = finalized
-                    .fetch_events()
-                    .await
-                    .map_err(|e| Error::Transaction(format!("Failed to get events: {}", e)))?;
-
-                for evt in events.iter() {
-                    let evt = evt.map_err(|e| {
-                        Error::Transaction(format!("Failed to decode event: {}", e))
-                    })?;
-
-                    // Look for Contracts.Instantiated event
-                    if evt.pallet_name() == "Contracts" && evt.variant_name() == "Instantiated" {
-                        // Extract contract address from event data
-                        // This is a simplified version - in production, parse the actual event fields
-                        debug!("Contract instantiated event found");
-
-                        // Extract contract address from event fields
-                        // The Instantiated event is SCALE encoded, we need to decode it
-                        // For now, use the raw bytes and try to extract the address
-                        let field_bytes = evt.field_bytes();
-
-                        // The event fields are SCALE encoded - skip the first field (deployer)
-                        // and extract the second field (contract address)
-                        // This is a simplified approach; proper decoding should use the metadata
-                        if field_bytes.len() >= 64 {
-                            let mut contract_address = [0u8; 32];
-                            // Skip first 32 bytes (deployer) and take next 32 bytes (contract)
-                            contract_address.copy_from_slice(&field_bytes[32..64]);
-                            return Ok(Self::with_metadata(client, contract_address, metadata));
-                        } else {
-                            return Err(Error::Transaction(format!(
-                                "Contract event data has unexpected length: {}",
-                                field_bytes.len()
-                            )));
-                        }
-                    }
-                }
-
-                return Err(Error::Transaction(
-                    "Contract deployment succeeded but address not found in events".to_string(),
-                ));
-            }
+        let mut deployer =
+            ContractDeployer::new(client, metadata).gas_limit(GasLimit::default_deploy());
+        if let Some(salt) = salt {
+            deployer = deployer.salt(salt);
         }
-
-        Err(Error::Transaction(
-            "Deploy transaction stream ended without finalization".to_string(),
-        ))
+        deployer
+            .instantiate_with_code(wallet, wasm_code, constructor_name, constructor_args)
+            .await
     }
 
     /// Call a contract method (mutable)
@@ -555,6 +1154,21 @@ impl ContractClient {
         ))
     }
 
+    /// Start building a call to `method_name`, by its selector in the metadata
+    #[allow(clippy::result_large_err)]
+    pub fn call_builder(&self, method_name: &str) -> Result<ContractCallBuilder> {
+        let message = self
+            .metadata
+            .as_ref()
+            .ok_or_else(|| Error::Transaction("Contract metadata not available".to_string()))?
+            .spec
+            .messages
+            .iter()
+            .find(|m| m.label == method_name)
+            .ok_or_else(|| Error::Transaction(format!("Method '{}' not found", method_name)))?;
+        Ok(ContractCallBuilder::new(self.address, message.selector))
+    }
+
     /// Read contract state (dry-run, doesn't modify state)
     ///
     /// This method performs a read-only call to a smart contract method without
@@ -660,8 +1274,6 @@ impl ContractClient {
     /// Decode ContractExecResult from SCALE-encoded bytes
     #[allow(clippy::result_large_err)]
     fn decode_contract_result(bytes: &[u8]) -> Result<Vec<u8>> {
-        use parity_scale_codec::Decode;
-
         // ContractExecResult structure (simplified):
         // struct ContractExecResult {
         //     gas_consumed: Weight,
@@ -734,6 +1346,103 @@ impl ContractClient {
         }
     }
 
+    /// Decode the events this contract emitted in a block or extrinsic
+    ///
+    /// Needs the contract metadata. Events of other contracts are skipped.
+    #[allow(clippy::result_large_err)]
+    pub async fn decode_events(&self, source: EventSource) -> Result<Vec<ContractEvent>> {
+        let metadata = self
+            .metadata
+            .as_ref()
+            .ok_or_else(|| Error::Metadata("Contract metadata not available".to_string()))?;
+        let (block_hash, tx_hash) = match &source {
+            EventSource::Block(block_hash) => (block_hash, None),
+            EventSource::Extrinsic {
+                block_hash,
+                tx_hash,
+            } => (block_hash, Some(tx_hash.trim_start_matches("0x"))),
+        };
+        let hash_bytes = hex::decode(block_hash.trim_start_matches("0x"))
+            .ok()
+            .filter(|bytes| bytes.len() == 32)
+            .ok_or_else(|| Error::Transaction(format!("Invalid block hash {}", block_hash)))?;
+        let block = self
+            .client
+            .blocks()
+            .at(subxt::utils::H256::from_slice(&hash_bytes))
+            .await
+            .map_err(|e| Error::Connection(format!("Failed to get block: {}", e)))?;
+
+        let mut emitted = Vec::new();
+        let mut collect = |event: subxt::events::EventDetails<PolkadotConfig>| -> Result<()> {
+            if event.pallet_name() != "Contracts" || event.variant_name() != "ContractEmitted" {
+                return Ok(());
+            }
+            let fields = event
+                .field_values()
+                .map_err(|e| Error::Encoding(format!("Failed to decode event: {}", e)))?;
+            let Composite::Named(fields) = fields else {
+                return Ok(());
+            };
+            let field = |label: &str| {
+                let mut bytes = Vec::new();
+                if let Some((_, value)) = fields.iter().find(|(name, _)| name == label) {
+                    collect_bytes(value, &mut bytes);
+                }
+                bytes
+            };
+            if field("contract") != self.address {
+                return Ok(());
+            }
+            let topics: Vec<[u8; 32]> = event.topics().iter().map(|topic| topic.0).collect();
+            emitted.push(decode_event(metadata, &topics, &field("data"))?);
+            Ok(())
+        };
+
+        match tx_hash {
+            None => {
+                let events = block
+                    .events()
+                    .await
+                    .map_err(|e| Error::Connection(format!("Failed to fetch events: {}", e)))?;
+                for event in events.iter() {
+                    collect(
+                        event.map_err(|e| {
+                            Error::Encoding(format!("Failed to decode event: {}", e))
+                        })?,
+                    )?;
+                }
+            }
+            Some(tx_hash) => {
+                let extrinsics = block
+                    .extrinsics()
+                    .await
+                    .map_err(|e| Error::Connection(format!("Failed to fetch extrinsics: {}", e)))?;
+                let extrinsic = extrinsics
+                    .iter()
+                    .find(|ext| hex::encode(ext.hash()).eq_ignore_ascii_case(tx_hash))
+                    .ok_or_else(|| {
+                        Error::Transaction(format!(
+                            "Extrinsic 0x{} not found in block {}",
+                            tx_hash, block_hash
+                        ))
+                    })?;
+                let events = extrinsic
+                    .events()
+                    .await
+                    .map_err(|e| Error::Connection(format!("Failed to fetch events: {}", e)))?;
+                for event in events.iter() {
+                    collect(
+                        event.map_err(|e| {
+                            Error::Encoding(format!("Failed to decode event: {}", e))
+                        })?,
+                    )?;
+                }
+            }
+        }
+        Ok(emitted)
+    }
+
     /// Get the contract address
     pub fn address(&self) -> &ContractAddress {
         &self.address
@@ -744,6 +1453,10 @@ impl ContractClient {
         self.metadata.as_ref()
     }
 
+    pub(crate) fn client(&self) -> &OnlineClient<PolkadotConfig> {
+        &self.client
+    }
+
     // Helper methods
 
     #[allow(clippy::result_large_err)]
@@ -792,6 +1505,180 @@ mod tests {
         assert!(limit.proof_size > 0);
     }
 
+    fn event_metadata(signature_topic: Option<&str>) -> ContractMetadata {
+        let arg = |label: &str, ty: u32| EventArg {
+            label: label.to_string(),
+            type_ref: TypeRef {
+                ty,
+                display_name: vec![],
+            },
+            indexed: false,
+        };
+        let ty = |id: u32, def: TypeDefVariant| TypeDef {
+            id,
+            path: vec![],
+            params: vec![],
+            def,
+        };
+        ContractMetadata {
+            spec: ContractSpec {
+                constructors: vec![],
+                messages: vec![],
+                events: vec![
+                    EventSpec {
+                        label: "Approval".to_string(),
+                        args: vec![arg("value", 1)],
+                        docs: vec![],
+                        signature_topic: signature_topic.map(|_| "11".repeat(32)),
+                    },
+                    EventSpec {
+                        label: "Transfer".to_string(),
+                        args: vec![arg("from", 2), arg("value", 1)],
+                        docs: vec![],
+                        signature_topic: signature_topic.map(str::to_string),
+                    },
+                ],
+            },
+            storage: StorageLayout {
+                root: LayoutKey {
+                    key: "0x00000000".to_string(),
+                    ty: 0,
+                },
+            },
+            types: vec![
+                ty(
+                    0,
+                    TypeDefVariant::Primitive {
+                        primitive: "u8".to_string(),
+                    },
+                ),
+                ty(
+                    1,
+                    TypeDefVariant::Primitive {
+                        primitive: "u128".to_string(),
+                    },
+                ),
+                // Option<[u8; 32]>
+                ty(
+                    2,
+                    TypeDefVariant::Variant {
+                        variants: vec![
+                            Variant {
+                                name: "None".to_string(),
+                                fields: vec![],
+                                index: 0,
+                            },
+                            Variant {
+                                name: "Some".to_string(),
+                                fields: vec![Field { name: None, ty: 3 }],
+                                index: 1,
+                            },
+                        ],
+                    },
+                ),
+                ty(
+                    3,
+                    TypeDefVariant::Array {
+                        len: 32,
+                        type_param: 0,
+                    },
+                ),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_decode_indexed_event() {
+        let metadata = event_metadata(None);
+        let mut data = vec![1u8, 1];
+        data.extend_from_slice(&[7u8; 32]);
+        data.extend_from_slice(&500u128.to_le_bytes());
+
+        let event = decode_event(&metadata, &[], &data).unwrap();
+        assert_eq!(event.name, "Transfer");
+        assert_eq!(event.field("value"), Some(&Value::u128(500)));
+        assert_eq!(
+            event.field("from"),
+            Some(&Value::unnamed_variant(
+                "Some",
+                [Value::from_bytes([7u8; 32])]
+            ))
+        );
+
+        assert!(decode_event(&metadata, &[], &[5]).is_err());
+    }
+
+    #[test]
+    fn test_decode_topic_event() {
+        let topic = "22".repeat(32);
+        let metadata = event_metadata(Some(&topic));
+        let mut data = vec![0u8];
+        data.extend_from_slice(&9u128.to_le_bytes());
+
+        let event = decode_event(&metadata, &[[0x22; 32]], &data).unwrap();
+        assert_eq!(event.name, "Transfer");
+        assert_eq!(
+            event.field("from"),
+            Some(&Value::unnamed_variant("None", []))
+        );
+        assert_eq!(event.field("value"), Some(&Value::u128(9)));
+
+        assert!(decode_event(&metadata, &[[0x33; 32]], &data).is_err());
+        assert!(decode_event(&metadata, &[], &data).is_err());
+    }
+
+    #[test]
+    fn test_decode_instantiate_result() {
+        use parity_scale_codec::Encode;
+
+        let mut bytes = Vec::new();
+        (
+            Compact(10u64),
+            Compact(20u64),
+            Compact(30u64),
+            Compact(40u64),
+        )
+            .encode_to(&mut bytes);
+        (1u8, 500u128, b"ok".to_vec()).encode_to(&mut bytes);
+        (0u8, 0u32, Vec::<u8>::new(), [9u8; 32]).encode_to(&mut bytes);
+
+        assert_eq!(
+            decode_instantiate_result(&bytes).unwrap(),
+            InstantiateEstimate {
+                gas_consumed: GasLimit::new(10, 20),
+                gas_required: GasLimit::new(30, 40),
+                storage_deposit: 500,
+                address: [9u8; 32],
+            }
+        );
+
+        // Reverted constructor
+        let reverted = bytes.len() - 32 - 1 - 4;
+        bytes[reverted] = 1;
+        assert!(decode_instantiate_result(&bytes).is_err());
+    }
+
+    #[test]
+    fn test_decode_call_dry_run() {
+        use parity_scale_codec::Encode;
+
+        let mut bytes = Vec::new();
+        (Compact(1u64), Compact(2u64), Compact(3u64), Compact(4u64)).encode_to(&mut bytes);
+        (0u8, 70u128, Vec::<u8>::new()).encode_to(&mut bytes);
+        (0u8, 1u32, vec![0xaau8]).encode_to(&mut bytes);
+
+        let dry_run = decode_exec_result(&mut &bytes[..]).unwrap();
+        assert_eq!(dry_run.gas_required, GasLimit::new(3, 4));
+        // Refunds are not charged
+        assert_eq!(dry_run.storage_deposit, 0);
+        assert!(dry_run.reverted);
+        assert_eq!(dry_run.data, vec![0xaa]);
+
+        let mut weight = Vec::new();
+        encode_weight(&GasLimit::new(3, 4), &mut weight);
+        assert_eq!(weight, bytes[2..4].to_vec());
+    }
+
     #[test]
     fn test_contract_call_builder() {
         let address = [1u8; 32];
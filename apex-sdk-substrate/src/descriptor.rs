@@ -0,0 +1,834 @@
+//! Exportable chain descriptors
+//!
+//! A [`ChainDescriptor`] is a versioned JSON snapshot of everything needed to
+//! build transactions for a chain without a node connection: genesis hash,
+//! runtime versions, chain properties, pallet/call indices and the full
+//! SCALE-encoded metadata.
+//!
+//! Export one from a connected [`SubstrateAdapter`](crate::SubstrateAdapter),
+//! commit it next to your code, and load it later with
+//! [`OfflineSubstrateAdapter`] to sign extrinsics offline. The signed bytes
+//! can be broadcast with [`SubstrateAdapter::submit_raw_extrinsic`](crate::SubstrateAdapter::submit_raw_extrinsic).
+//!
+//! When the key never touches the machine that builds the transaction,
+//! [`build_unsigned`](OfflineSubstrateAdapter::build_unsigned) exports an
+//! [`UnsignedExtrinsic`] as JSON. It is signed with
+//! [`UnsignedExtrinsic::sign`] on the air-gapped machine, turned into the
+//! extrinsic with [`assemble`](OfflineSubstrateAdapter::assemble), and
+//! broadcast with [`SubstrateAdapter::submit_signed`](crate::SubstrateAdapter::submit_signed).
+//! Keys held by a KMS or signing service sign with
+//! [`UnsignedExtrinsic::sign_remote`] instead.
+
+use crate::signer::key_pair_type;
+use crate::{account_id, ApexSigner, ChainConfig, Error, KeyPairType, Result, Wallet};
+use apex_sdk_core::remote_signer::RemoteSigner;
+use parity_scale_codec::Decode;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use subxt::client::RuntimeVersion;
+use subxt::config::DefaultExtrinsicParamsBuilder;
+use subxt::ext::scale_value::{Composite, ValueDef};
+use subxt::tx::DynamicPayload;
+use subxt::utils::{AccountId32, MultiSignature};
+use subxt::{Metadata, OfflineClient, PolkadotConfig};
+
+/// Current descriptor format version
+pub const DESCRIPTOR_VERSION: u32 = 1;
+
+/// Call exposed by a pallet
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CallDescriptor {
+    /// Call name (e.g. "transfer_keep_alive")
+    pub name: String,
+    /// Call index within the pallet
+    pub index: u8,
+}
+
+/// Pallet and its call indices
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PalletDescriptor {
+    /// Pallet name (e.g. "Balances")
+    pub name: String,
+    /// Pallet index in the runtime
+    pub index: u8,
+    /// Calls, ordered by index
+    pub calls: Vec<CallDescriptor>,
+}
+
+/// Versioned snapshot of a chain's metadata and properties
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChainDescriptor {
+    /// Descriptor format version
+    pub version: u32,
+    /// Chain name
+    pub chain_name: String,
+    /// Genesis hash (0x-prefixed hex)
+    pub genesis_hash: String,
+    /// Runtime spec version
+    pub spec_version: u32,
+    /// Runtime transaction version
+    pub transaction_version: u32,
+    /// SS58 address prefix
+    pub ss58_prefix: u16,
+    /// Token symbol
+    pub token_symbol: String,
+    /// Token decimals
+    pub token_decimals: u8,
+    /// Pallets and call indices, ordered by pallet index
+    pub pallets: Vec<PalletDescriptor>,
+    /// SCALE-encoded runtime metadata (0x-prefixed hex)
+    pub metadata: String,
+}
+
+impl ChainDescriptor {
+    /// Build a descriptor from SCALE-encoded metadata, as returned by
+    /// `state_getMetadata`, and the chain's runtime information
+    #[allow(clippy::result_large_err)]
+    pub fn from_metadata(
+        genesis_hash: subxt::utils::H256,
+        runtime_version: RuntimeVersion,
+        metadata: &[u8],
+        config: &ChainConfig,
+    ) -> Result<Self> {
+        let raw = metadata;
+        let metadata = Metadata::decode(&mut &raw[..])
+            .map_err(|e| Error::Metadata(format!("Failed to decode metadata: {}", e)))?;
+
+        let mut pallets: Vec<PalletDescriptor> = metadata
+            .pallets()
+            .map(|pallet| {
+                let mut calls: Vec<CallDescriptor> = pallet
+                    .call_variants()
+                    .unwrap_or_default()
+                    .iter()
+                    .map(|variant| CallDescriptor {
+                        name: variant.name.clone(),
+                        index: variant.index,
+                    })
+                    .collect();
+                calls.sort_by_key(|call| call.index);
+
+                PalletDescriptor {
+                    name: pallet.name().to_string(),
+                    index: pallet.index(),
+                    calls,
+                }
+            })
+            .collect();
+        pallets.sort_by_key(|pallet| pallet.index);
+
+        Ok(Self {
+            version: DESCRIPTOR_VERSION,
+            chain_name: config.name.clone(),
+            genesis_hash: format!("0x{}", hex::encode(genesis_hash)),
+            spec_version: runtime_version.spec_version,
+            transaction_version: runtime_version.transaction_version,
+            ss58_prefix: config.ss58_prefix,
+            token_symbol: config.token_symbol.clone(),
+            token_decimals: config.token_decimals,
+            pallets,
+            metadata: format!("0x{}", hex::encode(raw)),
+        })
+    }
+
+    /// Serialize to pretty-printed JSON
+    #[allow(clippy::result_large_err)]
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self)
+            .map_err(|e| Error::Encoding(format!("Failed to serialize descriptor: {}", e)))
+    }
+
+    /// Parse from JSON, rejecting unsupported format versions
+    #[allow(clippy::result_large_err)]
+    pub fn from_json(json: &str) -> Result<Self> {
+        let descriptor: Self = serde_json::from_str(json)
+            .map_err(|e| Error::Encoding(format!("Invalid chain descriptor: {}", e)))?;
+        descriptor.check_version()?;
+        Ok(descriptor)
+    }
+
+    /// Write the descriptor to a JSON file
+    #[allow(clippy::result_large_err)]
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        std::fs::write(path.as_ref(), self.to_json()?).map_err(|e| {
+            Error::Other(format!(
+                "Failed to write {}: {}",
+                path.as_ref().display(),
+                e
+            ))
+        })
+    }
+
+    /// Read a descriptor from a JSON file
+    #[allow(clippy::result_large_err)]
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let json = std::fs::read_to_string(path.as_ref()).map_err(|e| {
+            Error::Other(format!("Failed to read {}: {}", path.as_ref().display(), e))
+        })?;
+        Self::from_json(&json)
+    }
+
+    #[allow(clippy::result_large_err)]
+    fn check_version(&self) -> Result<()> {
+        if self.version == 0 || self.version > DESCRIPTOR_VERSION {
+            return Err(Error::Metadata(format!(
+                "Unsupported descriptor version {} (supported: 1..={})",
+                self.version, DESCRIPTOR_VERSION
+            )));
+        }
+        Ok(())
+    }
+
+    /// Look up a pallet by name
+    pub fn pallet(&self, name: &str) -> Option<&PalletDescriptor> {
+        self.pallets.iter().find(|pallet| pallet.name == name)
+    }
+
+    /// Look up the `(pallet index, call index)` pair of a call
+    pub fn call_index(&self, pallet: &str, call: &str) -> Option<(u8, u8)> {
+        let pallet = self.pallet(pallet)?;
+        pallet
+            .calls
+            .iter()
+            .find(|c| c.name == call)
+            .map(|c| (pallet.index, c.index))
+    }
+
+    /// Chain configuration described by this snapshot (without an endpoint)
+    pub fn chain_config(&self) -> ChainConfig {
+        ChainConfig {
+            name: self.chain_name.clone(),
+            endpoint: String::new(),
+            ss58_prefix: self.ss58_prefix,
+            token_symbol: self.token_symbol.clone(),
+            token_decimals: self.token_decimals,
+            rate_limit: None,
+            genesis_hash: Some(self.genesis_hash.clone()),
+        }
+    }
+
+    /// Decode the embedded metadata
+    #[allow(clippy::result_large_err)]
+    pub fn decode_metadata(&self) -> Result<Metadata> {
+        let bytes = decode_hex(&self.metadata, "metadata")?;
+        Metadata::decode(&mut &bytes[..])
+            .map_err(|e| Error::Metadata(format!("Failed to decode metadata: {}", e)))
+    }
+
+    /// Build an offline client from the snapshot
+    #[allow(clippy::result_large_err)]
+    pub fn offline_client(&self) -> Result<OfflineClient<PolkadotConfig>> {
+        self.check_version()?;
+
+        let genesis = decode_hex(&self.genesis_hash, "genesis hash")?;
+        if genesis.len() != 32 {
+            return Err(Error::Metadata(format!(
+                "Genesis hash must be 32 bytes, got {}",
+                genesis.len()
+            )));
+        }
+
+        Ok(OfflineClient::new(
+            subxt::utils::H256::from_slice(&genesis),
+            RuntimeVersion {
+                spec_version: self.spec_version,
+                transaction_version: self.transaction_version,
+            },
+            self.decode_metadata()?,
+        ))
+    }
+}
+
+#[allow(clippy::result_large_err)]
+fn decode_hex(value: &str, what: &str) -> Result<Vec<u8>> {
+    hex::decode(value.trim_start_matches("0x"))
+        .map_err(|e| Error::Encoding(format!("Invalid {} hex: {}", what, e)))
+}
+
+/// Block an extrinsic's validity period starts at
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Mortality {
+    /// Number of blocks the extrinsic stays valid for
+    pub period: u64,
+    /// Number of the starting block
+    pub block_number: u64,
+    /// Hash of the starting block (0x-prefixed hex)
+    pub block_hash: String,
+}
+
+/// Extension parameters of an extrinsic built for offline signing
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OfflineParams {
+    /// Account nonce
+    pub nonce: u64,
+    /// Tip for the block author
+    pub tip: u128,
+    /// Validity period, immortal if `None`
+    pub mortality: Option<Mortality>,
+}
+
+/// An extrinsic waiting for its signature, exportable as JSON
+///
+/// Everything the signature covers is included, so the signing machine only
+/// needs this and the key.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UnsignedExtrinsic {
+    /// Account expected to sign (SS58)
+    pub signer: String,
+    /// SCALE-encoded call (0x-prefixed hex)
+    pub call_data: String,
+    /// Extension parameters
+    pub params: OfflineParams,
+    /// Genesis hash of the chain (0x-prefixed hex)
+    pub genesis_hash: String,
+    /// Runtime spec version the extrinsic is built for
+    pub spec_version: u32,
+    /// Runtime transaction version the extrinsic is built for
+    pub transaction_version: u32,
+    /// Bytes to sign (0x-prefixed hex)
+    pub signer_payload: String,
+}
+
+impl UnsignedExtrinsic {
+    /// Serialize to pretty-printed JSON
+    #[allow(clippy::result_large_err)]
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self)
+            .map_err(|e| Error::Encoding(format!("Failed to serialize extrinsic: {}", e)))
+    }
+
+    /// Parse from JSON
+    #[allow(clippy::result_large_err)]
+    pub fn from_json(json: &str) -> Result<Self> {
+        serde_json::from_str(json)
+            .map_err(|e| Error::Encoding(format!("Invalid unsigned extrinsic: {}", e)))
+    }
+
+    /// Bytes to sign
+    #[allow(clippy::result_large_err)]
+    pub fn payload(&self) -> Result<Vec<u8>> {
+        decode_hex(&self.signer_payload, "signer payload")
+    }
+
+    /// Sign the payload with `wallet`, which must be the expected signer
+    #[allow(clippy::result_large_err)]
+    pub fn sign(&self, wallet: &Wallet) -> Result<Vec<u8>> {
+        if account_id(&wallet.address())? != account_id(&self.signer)? {
+            return Err(Error::Wallet(format!(
+                "Extrinsic must be signed by {}",
+                self.signer
+            )));
+        }
+        Ok(wallet.sign(&self.payload()?))
+    }
+
+    /// Sign the payload with `signer`, which must hold the expected signer's
+    /// key, returning the signature and the key type to
+    /// [`assemble`](OfflineSubstrateAdapter::assemble) it with
+    pub async fn sign_remote(&self, signer: &dyn RemoteSigner) -> Result<(Vec<u8>, KeyPairType)> {
+        let key_type = key_pair_type(signer.scheme())?;
+        if signer.public_key() != account_id(&self.signer)?.as_slice() {
+            return Err(Error::Wallet(format!(
+                "Extrinsic must be signed by {}",
+                self.signer
+            )));
+        }
+        let signature = signer
+            .sign(&self.payload()?)
+            .await
+            .map_err(|e| Error::Signature(e.to_string()))?;
+        Ok((signature, key_type))
+    }
+}
+
+/// Adapter built from a [`ChainDescriptor`], able to sign extrinsics offline
+pub struct OfflineSubstrateAdapter {
+    descriptor: ChainDescriptor,
+    client: OfflineClient<PolkadotConfig>,
+}
+
+impl OfflineSubstrateAdapter {
+    /// Load an adapter from a descriptor
+    #[allow(clippy::result_large_err)]
+    pub fn from_descriptor(descriptor: ChainDescriptor) -> Result<Self> {
+        let client = descriptor.offline_client()?;
+        Ok(Self { descriptor, client })
+    }
+
+    /// Load an adapter from a descriptor file
+    #[allow(clippy::result_large_err)]
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        Self::from_descriptor(ChainDescriptor::load(path)?)
+    }
+
+    /// Get the descriptor
+    pub fn descriptor(&self) -> &ChainDescriptor {
+        &self.descriptor
+    }
+
+    /// Get the offline client
+    pub fn client(&self) -> &OfflineClient<PolkadotConfig> {
+        &self.client
+    }
+
+    /// Chain configuration described by the descriptor
+    pub fn config(&self) -> ChainConfig {
+        self.descriptor.chain_config()
+    }
+
+    /// Sign an arbitrary dynamic call, returning the SCALE-encoded extrinsic
+    ///
+    /// The account nonce must be supplied since it can't be queried offline.
+    /// The extrinsic is immortal.
+    #[allow(clippy::result_large_err)]
+    pub fn sign_call(
+        &self,
+        pallet: &str,
+        call: &str,
+        args: Vec<subxt::dynamic::Value>,
+        signer: &Wallet,
+        nonce: u64,
+    ) -> Result<Vec<u8>> {
+        if self.descriptor.call_index(pallet, call).is_none() {
+            return Err(Error::Metadata(format!(
+                "Call {}::{} not found in descriptor",
+                pallet, call
+            )));
+        }
+
+        let signer = match signer.key_type() {
+            KeyPairType::Sr25519 => signer.sr25519_pair().cloned().map(ApexSigner::from_sr25519),
+            KeyPairType::Ed25519 => signer.ed25519_pair().cloned().map(ApexSigner::from_ed25519),
+            KeyPairType::Ecdsa => signer.ecdsa_pair().cloned().map(ApexSigner::from_ecdsa),
+        }
+        .ok_or_else(|| Error::Wallet("Wallet has no key pair".to_string()))?;
+
+        let tx = subxt::dynamic::tx(pallet, call, args);
+        let params = subxt::config::DefaultExtrinsicParamsBuilder::<PolkadotConfig>::new()
+            .nonce(nonce)
+            .build();
+
+        let mut partial = self
+            .client
+            .tx()
+            .create_partial_offline(&tx, params)
+            .map_err(|e| Error::Transaction(format!("Failed to build extrinsic: {}", e)))?;
+
+        Ok(partial.sign(&signer).into_encoded())
+    }
+
+    /// Build `pallet::call` for `signer` (SS58) to sign elsewhere
+    ///
+    /// The genesis hash and runtime versions come from the descriptor.
+    #[allow(clippy::result_large_err)]
+    pub fn build_unsigned(
+        &self,
+        pallet: &str,
+        call: &str,
+        args: Vec<subxt::dynamic::Value>,
+        signer: &str,
+        params: OfflineParams,
+    ) -> Result<UnsignedExtrinsic> {
+        if self.descriptor.call_index(pallet, call).is_none() {
+            return Err(Error::Metadata(format!(
+                "Call {}::{} not found in descriptor",
+                pallet, call
+            )));
+        }
+        account_id(signer)?;
+
+        let tx = subxt::dynamic::tx(pallet, call, args);
+        let call_data = self
+            .client
+            .tx()
+            .call_data(&tx)
+            .map_err(|e| Error::Transaction(format!("Failed to encode call: {}", e)))?;
+        let partial = self.partial(&tx, &params)?;
+
+        Ok(UnsignedExtrinsic {
+            signer: signer.to_string(),
+            call_data: format!("0x{}", hex::encode(call_data)),
+            params,
+            genesis_hash: self.descriptor.genesis_hash.clone(),
+            spec_version: self.descriptor.spec_version,
+            transaction_version: self.descriptor.transaction_version,
+            signer_payload: format!("0x{}", hex::encode(partial.signer_payload())),
+        })
+    }
+
+    /// Combine `unsigned` with its `signature`, returning the SCALE-encoded
+    /// extrinsic
+    ///
+    /// Fails if `unsigned` was built for another chain or runtime, or its
+    /// payload does not match the call and parameters.
+    #[allow(clippy::result_large_err)]
+    pub fn assemble(
+        &self,
+        unsigned: &UnsignedExtrinsic,
+        signature: &[u8],
+        key_type: KeyPairType,
+    ) -> Result<Vec<u8>> {
+        if !unsigned
+            .genesis_hash
+            .eq_ignore_ascii_case(&self.descriptor.genesis_hash)
+            || unsigned.spec_version != self.descriptor.spec_version
+            || unsigned.transaction_version != self.descriptor.transaction_version
+        {
+            return Err(Error::Transaction(format!(
+                "Extrinsic was built for {} at runtime {}/{}",
+                unsigned.genesis_hash, unsigned.spec_version, unsigned.transaction_version
+            )));
+        }
+
+        let wrong_length = |expected: usize| {
+            Error::Transaction(format!(
+                "Signature must be {} bytes, got {}",
+                expected,
+                signature.len()
+            ))
+        };
+        let signature = match key_type {
+            KeyPairType::Sr25519 => {
+                MultiSignature::Sr25519(signature.try_into().map_err(|_| wrong_length(64))?)
+            }
+            KeyPairType::Ed25519 => {
+                MultiSignature::Ed25519(signature.try_into().map_err(|_| wrong_length(64))?)
+            }
+            KeyPairType::Ecdsa => {
+                MultiSignature::Ecdsa(signature.try_into().map_err(|_| wrong_length(65))?)
+            }
+        };
+
+        let tx = payload_from_call_data(
+            &self.client.metadata(),
+            &decode_hex(&unsigned.call_data, "call data")?,
+        )?;
+        let mut partial = self.partial(&tx, &unsigned.params)?;
+        if partial.signer_payload() != unsigned.payload()? {
+            return Err(Error::Transaction(
+                "Signer payload does not match the call and parameters".to_string(),
+            ));
+        }
+
+        let signer = AccountId32(account_id(&unsigned.signer)?);
+        Ok(partial
+            .sign_with_account_and_signature(&signer, &signature)
+            .into_encoded())
+    }
+
+    #[allow(clippy::result_large_err)]
+    fn partial(
+        &self,
+        tx: &DynamicPayload,
+        params: &OfflineParams,
+    ) -> Result<subxt::tx::PartialTransaction<PolkadotConfig, OfflineClient<PolkadotConfig>>> {
+        let mut builder = DefaultExtrinsicParamsBuilder::<PolkadotConfig>::new()
+            .nonce(params.nonce)
+            .tip(params.tip);
+        if let Some(mortality) = &params.mortality {
+            let hash = decode_hex(&mortality.block_hash, "block hash")?;
+            if hash.len() != 32 {
+                return Err(Error::Transaction(format!(
+                    "Block hash must be 32 bytes, got {}",
+                    hash.len()
+                )));
+            }
+            builder = builder.mortal_from_unchecked(
+                mortality.period,
+                mortality.block_number,
+                subxt::utils::H256::from_slice(&hash),
+            );
+        }
+
+        self.client
+            .tx()
+            .create_partial_offline(tx, builder.build())
+            .map_err(|e| Error::Transaction(format!("Failed to build extrinsic: {}", e)))
+    }
+
+    /// Sign a `Balances::transfer_keep_alive`, returning the SCALE-encoded extrinsic
+    #[allow(clippy::result_large_err)]
+    pub fn sign_transfer(
+        &self,
+        from: &Wallet,
+        to: &str,
+        amount: u128,
+        nonce: u64,
+    ) -> Result<Vec<u8>> {
+        use sp_core::crypto::Ss58Codec;
+        use subxt::dynamic::Value;
+
+        let dest = sp_core::crypto::AccountId32::from_ss58check(to)
+            .map_err(|e| Error::Transaction(format!("Invalid destination address: {}", e)))?;
+        let dest_bytes: &[u8] = dest.as_ref();
+
+        self.sign_call(
+            "Balances",
+            "transfer_keep_alive",
+            vec![
+                Value::unnamed_variant("Id", vec![Value::from_bytes(dest_bytes)]),
+                Value::u128(amount),
+            ],
+            from,
+            nonce,
+        )
+    }
+}
+
+/// Rebuild a dynamic call from its SCALE encoding
+#[allow(clippy::result_large_err)]
+fn payload_from_call_data(metadata: &Metadata, call_data: &[u8]) -> Result<DynamicPayload> {
+    let value = subxt::ext::scale_value::scale::decode_as_type(
+        &mut &call_data[..],
+        metadata.outer_enums().call_enum_ty(),
+        metadata.types(),
+    )
+    .map_err(|e| Error::Encoding(format!("Failed to decode call: {}", e)))?
+    .remove_context();
+
+    let malformed = || Error::Encoding("Call data is not a pallet call".to_string());
+    let ValueDef::Variant(pallet) = value.value else {
+        return Err(malformed());
+    };
+    let call = match pallet.values {
+        Composite::Unnamed(mut calls) if calls.len() == 1 => calls.remove(0),
+        _ => return Err(malformed()),
+    };
+    let ValueDef::Variant(call) = call.value else {
+        return Err(malformed());
+    };
+    Ok(subxt::dynamic::tx(pallet.name, call.name, call.values))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn descriptor() -> ChainDescriptor {
+        ChainDescriptor {
+            version: DESCRIPTOR_VERSION,
+            chain_name: "Westend".to_string(),
+            genesis_hash: format!("0x{}", "e1".repeat(32)),
+            spec_version: 1_016_000,
+            transaction_version: 26,
+            ss58_prefix: 42,
+            token_symbol: "WND".to_string(),
+            token_decimals: 12,
+            pallets: vec![PalletDescriptor {
+                name: "Balances".to_string(),
+                index: 4,
+                calls: vec![
+                    CallDescriptor {
+                        name: "transfer_allow_death".to_string(),
+                        index: 0,
+                    },
+                    CallDescriptor {
+                        name: "transfer_keep_alive".to_string(),
+                        index: 3,
+                    },
+                ],
+            }],
+            metadata: "0x".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let json = descriptor().to_json().unwrap();
+        assert_eq!(ChainDescriptor::from_json(&json).unwrap(), descriptor());
+    }
+
+    #[test]
+    fn test_rejects_unknown_version() {
+        let mut future = descriptor();
+        future.version = DESCRIPTOR_VERSION + 1;
+        let json = serde_json::to_string(&future).unwrap();
+        assert!(matches!(
+            ChainDescriptor::from_json(&json),
+            Err(Error::Metadata(_))
+        ));
+    }
+
+    #[test]
+    fn test_call_index() {
+        let descriptor = descriptor();
+        assert_eq!(
+            descriptor.call_index("Balances", "transfer_keep_alive"),
+            Some((4, 3))
+        );
+        assert_eq!(descriptor.call_index("Balances", "burn"), None);
+        assert_eq!(descriptor.chain_config().ss58_prefix, 42);
+    }
+
+    #[test]
+    fn test_unsigned_extrinsic_json() {
+        let wallet = Wallet::from_seed(&[7u8; 32], KeyPairType::Sr25519).unwrap();
+        let unsigned = UnsignedExtrinsic {
+            signer: wallet.address(),
+            call_data: "0x0403".to_string(),
+            params: OfflineParams {
+                nonce: 4,
+                tip: 0,
+                mortality: Some(Mortality {
+                    period: 64,
+                    block_number: 100,
+                    block_hash: format!("0x{}", "ab".repeat(32)),
+                }),
+            },
+            genesis_hash: descriptor().genesis_hash,
+            spec_version: 1_016_000,
+            transaction_version: 26,
+            signer_payload: "0x010203".to_string(),
+        };
+        let json = unsigned.to_json().unwrap();
+        assert_eq!(UnsignedExtrinsic::from_json(&json).unwrap(), unsigned);
+
+        let signature = unsigned.sign(&wallet).unwrap();
+        assert!(wallet.verify(&[1, 2, 3], &signature));
+
+        let other = Wallet::from_seed(&[8u8; 32], KeyPairType::Sr25519).unwrap();
+        assert!(matches!(unsigned.sign(&other), Err(Error::Wallet(_))));
+    }
+
+    /// Remote signer stand-in backed by a local wallet
+    struct WalletSigner(Wallet, Vec<u8>);
+
+    #[async_trait::async_trait]
+    impl RemoteSigner for WalletSigner {
+        fn public_key(&self) -> &[u8] {
+            &self.1
+        }
+
+        fn scheme(&self) -> apex_sdk_core::remote_signer::SignatureScheme {
+            apex_sdk_core::remote_signer::SignatureScheme::Ed25519
+        }
+
+        async fn sign(&self, message: &[u8]) -> apex_sdk_core::remote_signer::Result<Vec<u8>> {
+            Ok(self.0.sign(message))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sign_remote() {
+        let wallet = Wallet::from_seed(&[9u8; 32], KeyPairType::Ed25519).unwrap();
+        let unsigned = UnsignedExtrinsic {
+            signer: wallet.address(),
+            call_data: "0x0403".to_string(),
+            params: OfflineParams::default(),
+            genesis_hash: descriptor().genesis_hash,
+            spec_version: 1_016_000,
+            transaction_version: 26,
+            signer_payload: "0x040506".to_string(),
+        };
+
+        let remote = WalletSigner(wallet.clone(), wallet.public_key());
+        let (signature, key_type) = unsigned.sign_remote(&remote).await.unwrap();
+        assert_eq!(key_type, KeyPairType::Ed25519);
+        assert!(wallet.verify(&[4, 5, 6], &signature));
+
+        let other = Wallet::from_seed(&[10u8; 32], KeyPairType::Ed25519).unwrap();
+        let remote = WalletSigner(other.clone(), other.public_key());
+        assert!(matches!(
+            unsigned.sign_remote(&remote).await,
+            Err(Error::Wallet(_))
+        ));
+    }
+
+    #[test]
+    fn test_invalid_metadata() {
+        assert!(descriptor().offline_client().is_err());
+    }
+
+    /// Polkadot runtime 9180 metadata, as returned by `state_getMetadata`
+    ///
+    /// Subxt finds the outer enums of V14 metadata by their current names, so
+    /// the runtime's `Call` and `Event` are renamed to `RuntimeCall` and
+    /// `RuntimeEvent`.
+    fn polkadot_metadata() -> Vec<u8> {
+        use frame_metadata::{RuntimeMetadata, RuntimeMetadataPrefixed};
+
+        let raw = include_bytes!("../tests/fixtures/polkadot_9180_metadata.scale");
+        let mut prefixed = RuntimeMetadataPrefixed::decode(&mut &raw[..]).unwrap();
+        let RuntimeMetadata::V14(metadata) = &mut prefixed.1 else {
+            panic!("fixture is not V14 metadata");
+        };
+        for ty in &mut metadata.types.types {
+            let segments = &mut ty.ty.path.segments;
+            if segments.len() == 2 && segments[0] == "polkadot_runtime" {
+                match segments[1].as_str() {
+                    "Call" => segments[1] = "RuntimeCall".to_string(),
+                    "Event" => segments[1] = "RuntimeEvent".to_string(),
+                    _ => {}
+                }
+            }
+        }
+        parity_scale_codec::Encode::encode(&prefixed)
+    }
+
+    #[test]
+    fn test_export_and_sign_offline() {
+        let raw = polkadot_metadata();
+        let exported = ChainDescriptor::from_metadata(
+            subxt::utils::H256::repeat_byte(0x91),
+            RuntimeVersion {
+                spec_version: 9180,
+                transaction_version: 12,
+            },
+            &raw,
+            &ChainConfig::polkadot(),
+        )
+        .unwrap();
+        assert_eq!(exported.metadata, format!("0x{}", hex::encode(&raw)));
+        assert_eq!(
+            exported.call_index("Balances", "transfer_keep_alive"),
+            Some((5, 3))
+        );
+
+        let json = exported.to_json().unwrap();
+        let adapter =
+            OfflineSubstrateAdapter::from_descriptor(ChainDescriptor::from_json(&json).unwrap())
+                .unwrap();
+
+        let from = Wallet::from_seed(&[7u8; 32], KeyPairType::Sr25519).unwrap();
+        let to = Wallet::from_seed(&[8u8; 32], KeyPairType::Sr25519).unwrap();
+        let signed = adapter
+            .sign_transfer(&from, &to.address(), 1_000, 3)
+            .unwrap();
+        let unsigned = adapter
+            .build_unsigned(
+                "Balances",
+                "transfer_keep_alive",
+                vec![
+                    subxt::dynamic::Value::unnamed_variant(
+                        "Id",
+                        [subxt::dynamic::Value::from_bytes(to.public_key())],
+                    ),
+                    subxt::dynamic::Value::u128(1_000),
+                ],
+                &from.address(),
+                OfflineParams {
+                    nonce: 3,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+
+        // A signed v4 extrinsic: signer, signature, extensions, then the call
+        let call_data = decode_hex(&unsigned.call_data, "call data").unwrap();
+        assert!(signed.ends_with(&call_data));
+        assert!(signed
+            .windows(32)
+            .any(|window| window == from.public_key().as_slice()));
+
+        let signature = unsigned.sign(&from).unwrap();
+        let assembled = adapter
+            .assemble(&unsigned, &signature, KeyPairType::Sr25519)
+            .unwrap();
+        assert!(assembled.ends_with(&call_data));
+
+        assert!(matches!(
+            adapter.sign_call("Balances", "burn", vec![], &from, 3),
+            Err(Error::Metadata(_))
+        ));
+    }
+}
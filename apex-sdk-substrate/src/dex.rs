@@ -0,0 +1,353 @@
+//! Asset Conversion (Uniswap V2 style DEX pallet) client
+//!
+//! Asset Hub and other chains with `pallet-asset-conversion` swap assets
+//! identified by XCM locations: the relay token is `{ parents: 1, Here }`
+//! and a `pallet-assets` asset is `{ parents: 0, X2(PalletInstance(50),
+//! GeneralIndex(id)) }`.
+
+use crate::{
+    Error, Junction, Metrics, MultiLocation, Result, TransactionExecutor, Wallet, XcmExecutor,
+};
+use parity_scale_codec::{Compact, Decode, Encode};
+use subxt::{OnlineClient, PolkadotConfig};
+use tracing::debug;
+
+/// Pallet index of `pallet-assets` on Asset Hub
+pub const ASSETS_PALLET_INSTANCE: u8 = 50;
+
+/// Quotes and swaps through `pallet-asset-conversion`
+pub struct AssetConversion {
+    client: OnlineClient<PolkadotConfig>,
+    metrics: Metrics,
+}
+
+impl AssetConversion {
+    /// Create a client for the pallet on the chain behind `client`
+    pub fn new(client: OnlineClient<PolkadotConfig>, metrics: Metrics) -> Self {
+        Self { client, metrics }
+    }
+
+    /// Location of the relay chain's native token
+    pub fn native() -> MultiLocation {
+        MultiLocation::parent()
+    }
+
+    /// Location of `pallet-assets` asset `id`
+    pub fn asset(id: u32) -> MultiLocation {
+        MultiLocation::new(
+            0,
+            vec![
+                Junction::PalletInstance(ASSETS_PALLET_INSTANCE),
+                Junction::GeneralIndex(id as u128),
+            ],
+        )
+    }
+
+    /// Amount of `asset_out` received for exactly `amount_in` of `asset_in`,
+    /// after pool fees
+    ///
+    /// Returns `None` if there is no pool for the pair or it lacks liquidity.
+    pub async fn quote_exact_in(
+        &self,
+        asset_in: &MultiLocation,
+        asset_out: &MultiLocation,
+        amount_in: u128,
+    ) -> Result<Option<u128>> {
+        debug!(
+            "Quoting {} of {:?} for {:?}",
+            amount_in, asset_in, asset_out
+        );
+
+        let mut call_data = encode_location(asset_in)?;
+        call_data.extend(encode_location(asset_out)?);
+        (amount_in, true).encode_to(&mut call_data);
+
+        let result = self
+            .client
+            .runtime_api()
+            .at_latest()
+            .await
+            .map_err(|e| Error::Connection(format!("Failed to get latest block: {}", e)))?
+            .call_raw(
+                "AssetConversionApi_quote_price_exact_tokens_for_tokens",
+                Some(&call_data),
+            )
+            .await
+            .map_err(|e| Error::Transaction(format!("Failed to quote swap: {}", e)))?;
+
+        Option::<u128>::decode(&mut &result[..])
+            .map_err(|e| Error::Encoding(format!("Invalid swap quote: {}", e)))
+    }
+
+    /// Swap exactly `amount_in` along `path` into the wallet's own account,
+    /// failing on-chain if less than `amount_out_min` would be received
+    ///
+    /// `path` starts with the asset paid and ends with the asset received.
+    /// Returns the extrinsic hash once finalized.
+    pub async fn swap_exact_in(
+        &self,
+        wallet: &Wallet,
+        path: &[MultiLocation],
+        amount_in: u128,
+        amount_out_min: u128,
+        keep_alive: bool,
+    ) -> Result<String> {
+        if path.len() < 2 {
+            return Err(Error::Transaction(
+                "Swap path needs at least two assets".to_string(),
+            ));
+        }
+
+        let path = path
+            .iter()
+            .map(XcmExecutor::encode_multilocation)
+            .collect::<Result<Vec<_>>>()?;
+        let call = subxt::dynamic::tx(
+            "AssetConversion",
+            "swap_exact_tokens_for_tokens",
+            vec![
+                subxt::dynamic::Value::unnamed_composite(path),
+                subxt::dynamic::Value::u128(amount_in),
+                subxt::dynamic::Value::u128(amount_out_min),
+                subxt::dynamic::Value::from_bytes(wallet.account_id()),
+                subxt::dynamic::Value::bool(keep_alive),
+            ],
+        );
+
+        let receipt = TransactionExecutor::new(self.client.clone(), self.metrics.clone())
+            .submit_typed(&call, wallet)
+            .await?;
+        Ok(receipt.hash)
+    }
+}
+
+/// SCALE-encode `location` as an XCM v4 `Location`, as taken by runtime APIs
+#[allow(clippy::result_large_err)]
+pub fn encode_location(location: &MultiLocation) -> Result<Vec<u8>> {
+    if location.interior.len() > 8 {
+        return Err(Error::Encoding("Too many junctions (max 8)".to_string()));
+    }
+
+    // Junctions::Here is variant 0, X1..X8 are 1..8
+    let mut out = vec![location.parents, location.interior.len() as u8];
+    for junction in &location.interior {
+        match junction {
+            Junction::Parachain(id) => {
+                out.push(0);
+                Compact(*id).encode_to(&mut out);
+            }
+            Junction::AccountId32 { network, id } => {
+                out.push(1);
+                encode_network(network.is_some(), &mut out)?;
+                out.extend_from_slice(id);
+            }
+            Junction::AccountId20 { network, key } => {
+                out.push(3);
+                encode_network(network.is_some(), &mut out)?;
+                out.extend_from_slice(key);
+            }
+            Junction::PalletInstance(instance) => out.extend([4, *instance]),
+            Junction::GeneralIndex(index) => {
+                out.push(5);
+                Compact(*index).encode_to(&mut out);
+            }
+            Junction::GeneralKey { data } => {
+                if data.len() > 32 {
+                    return Err(Error::Encoding(
+                        "General key longer than 32 bytes".to_string(),
+                    ));
+                }
+                let mut key = [0u8; 32];
+                key[..data.len()].copy_from_slice(data);
+                out.extend([6, data.len() as u8]);
+                out.extend_from_slice(&key);
+            }
+        }
+    }
+    Ok(out)
+}
+
+/// Decode an XCM v4 `Location` built by [`encode_location`]
+///
+/// Only the canonical encoding is accepted, so decoding and re-encoding
+/// gives back the same bytes. Junction networks are rejected, as they are
+/// when encoding.
+#[allow(clippy::result_large_err)]
+pub fn decode_location(mut bytes: &[u8]) -> Result<MultiLocation> {
+    let input = &mut bytes;
+    let parents = u8::decode(input).map_err(invalid_location)?;
+    let count = u8::decode(input).map_err(invalid_location)?;
+    if count > 8 {
+        return Err(Error::Encoding(format!(
+            "Unsupported junctions variant {}",
+            count
+        )));
+    }
+
+    let mut interior = Vec::with_capacity(count as usize);
+    for _ in 0..count {
+        let junction = match u8::decode(input).map_err(invalid_location)? {
+            0 => Junction::Parachain(Compact::<u32>::decode(input).map_err(invalid_location)?.0),
+            1 => {
+                decode_network(input)?;
+                Junction::AccountId32 {
+                    network: None,
+                    id: <[u8; 32]>::decode(input).map_err(invalid_location)?,
+                }
+            }
+            3 => {
+                decode_network(input)?;
+                Junction::AccountId20 {
+                    network: None,
+                    key: <[u8; 20]>::decode(input).map_err(invalid_location)?,
+                }
+            }
+            4 => Junction::PalletInstance(u8::decode(input).map_err(invalid_location)?),
+            5 => {
+                Junction::GeneralIndex(Compact::<u128>::decode(input).map_err(invalid_location)?.0)
+            }
+            6 => {
+                let length = u8::decode(input).map_err(invalid_location)? as usize;
+                let key = <[u8; 32]>::decode(input).map_err(invalid_location)?;
+                if length > 32 || key[length..].iter().any(|byte| *byte != 0) {
+                    return Err(Error::Encoding("Invalid general key".to_string()));
+                }
+                Junction::GeneralKey {
+                    data: key[..length].to_vec(),
+                }
+            }
+            variant => {
+                return Err(Error::Encoding(format!(
+                    "Unsupported junction variant {}",
+                    variant
+                )))
+            }
+        };
+        interior.push(junction);
+    }
+
+    if !input.is_empty() {
+        return Err(Error::Encoding("Trailing bytes after location".to_string()));
+    }
+    Ok(MultiLocation::new(parents, interior))
+}
+
+fn invalid_location(e: parity_scale_codec::Error) -> Error {
+    Error::Encoding(format!("Invalid location: {}", e))
+}
+
+#[allow(clippy::result_large_err)]
+fn decode_network(input: &mut &[u8]) -> Result<()> {
+    match u8::decode(input).map_err(invalid_location)? {
+        0 => Ok(()),
+        _ => Err(Error::Encoding(
+            "Junction networks are not supported in runtime API calls".to_string(),
+        )),
+    }
+}
+
+#[allow(clippy::result_large_err)]
+fn encode_network(present: bool, out: &mut Vec<u8>) -> Result<()> {
+    if present {
+        return Err(Error::Encoding(
+            "Junction networks are not supported in runtime API calls".to_string(),
+        ));
+    }
+    out.push(0);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn junction() -> impl Strategy<Value = Junction> {
+        prop_oneof![
+            any::<u32>().prop_map(Junction::Parachain),
+            any::<[u8; 32]>().prop_map(|id| Junction::AccountId32 { network: None, id }),
+            any::<[u8; 20]>().prop_map(|key| Junction::AccountId20 { network: None, key }),
+            any::<u8>().prop_map(Junction::PalletInstance),
+            any::<u128>().prop_map(Junction::GeneralIndex),
+            prop::collection::vec(any::<u8>(), 0..=32)
+                .prop_map(|data| Junction::GeneralKey { data }),
+        ]
+    }
+
+    fn location() -> impl Strategy<Value = MultiLocation> {
+        (any::<u8>(), prop::collection::vec(junction(), 0..=8))
+            .prop_map(|(parents, interior)| MultiLocation::new(parents, interior))
+    }
+
+    #[test]
+    fn test_encode_native_location() {
+        assert_eq!(encode_location(&AssetConversion::native()).unwrap(), [1, 0]);
+    }
+
+    #[test]
+    fn test_encode_asset_location() {
+        // X2(PalletInstance(50), GeneralIndex(Compact(1984)))
+        assert_eq!(
+            encode_location(&AssetConversion::asset(1984)).unwrap(),
+            [0, 2, 4, 50, 5, 0x01, 0x1f]
+        );
+    }
+
+    #[test]
+    fn test_encode_location_rejects_network() {
+        let location = MultiLocation::new(
+            0,
+            vec![Junction::AccountId32 {
+                network: Some(crate::NetworkId::Polkadot),
+                id: [0; 32],
+            }],
+        );
+        assert!(encode_location(&location).is_err());
+    }
+
+    #[test]
+    fn test_decode_location_rejects_non_canonical() {
+        // General key with a non-zero byte past its length
+        let mut bytes = vec![0, 1, 6, 1, 0xaa];
+        bytes.extend([0; 30]);
+        bytes.push(1);
+        assert!(decode_location(&bytes).is_err());
+        // Trailing bytes, too many junctions, unknown junction
+        assert!(decode_location(&[1, 0, 0]).is_err());
+        assert!(decode_location(&[0, 9]).is_err());
+        assert!(decode_location(&[0, 1, 2]).is_err());
+    }
+
+    proptest! {
+        #[test]
+        fn prop_location_roundtrip(location in location()) {
+            let bytes = encode_location(&location).unwrap();
+            prop_assert_eq!(decode_location(&bytes).unwrap(), location);
+        }
+
+        #[test]
+        fn prop_decode_location_is_canonical(bytes in prop::collection::vec(any::<u8>(), 0..128)) {
+            if let Ok(location) = decode_location(&bytes) {
+                prop_assert_eq!(encode_location(&location).unwrap(), bytes);
+            }
+        }
+
+        #[test]
+        fn prop_encoders_reject_the_same_locations(
+            parents in any::<u8>(),
+            interior in prop::collection::vec(junction(), 0..=10),
+            long_key in prop::collection::vec(any::<u8>(), 33..64),
+            with_long_key in any::<bool>(),
+        ) {
+            let mut interior = interior;
+            if with_long_key {
+                interior.push(Junction::GeneralKey { data: long_key });
+            }
+            let location = MultiLocation::new(parents, interior);
+            prop_assert_eq!(
+                XcmExecutor::encode_multilocation(&location).is_ok(),
+                encode_location(&location).is_ok()
+            );
+        }
+    }
+}
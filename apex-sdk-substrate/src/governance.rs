@@ -0,0 +1,559 @@
+//! OpenGov referenda and conviction voting
+//!
+//! Reads referenda, tracks and decision queues from `pallet-referenda`, and
+//! votes or delegates votes through `pallet-conviction-voting`, as deployed
+//! on Polkadot and Kusama.
+
+use crate::{collect_bytes, multi_address, Error, Metrics, Result, TransactionExecutor, Wallet};
+use subxt::dynamic::{At as _, Value};
+use subxt::ext::scale_value::{self, ValueDef};
+use subxt::{OnlineClient, PolkadotConfig};
+use tracing::debug;
+
+/// Lifecycle state of a referendum
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReferendumStatus {
+    /// Waiting for a decision deposit or a free decision slot
+    Preparing,
+    /// Being decided
+    Deciding,
+    /// Passed
+    Approved,
+    /// Failed
+    Rejected,
+    /// Cancelled by the cancel origin
+    Cancelled,
+    /// Never entered the decision period in time
+    TimedOut,
+    /// Killed by the kill origin
+    Killed,
+}
+
+/// Votes cast on a referendum, in conviction-weighted Planck
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReferendumTally {
+    /// Weighted aye votes
+    pub ayes: u128,
+    /// Weighted nay votes
+    pub nays: u128,
+    /// Unweighted aye and abstain balance backing the referendum
+    pub support: u128,
+}
+
+/// A referendum of `pallet-referenda`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Referendum {
+    /// Referendum index
+    pub index: u32,
+    /// Current state
+    pub status: ReferendumStatus,
+    /// Track the referendum runs on, while ongoing
+    pub track: Option<u16>,
+    /// Current tally, while ongoing
+    pub tally: Option<ReferendumTally>,
+    /// Block the referendum was submitted, or concluded once finished
+    pub since: Option<u32>,
+}
+
+/// A referendum track (origin class) and its timings
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Track {
+    /// Track ID, the `class` of votes and delegations
+    pub id: u16,
+    /// Track name, e.g. `root` or `small_spender`
+    pub name: String,
+    /// Referenda decided at once; the rest wait in the track queue
+    pub max_deciding: u32,
+    /// Deposit to place before a referendum can be decided, in Planck
+    pub decision_deposit: u128,
+    /// Blocks a referendum waits before it can be decided
+    pub prepare_period: u32,
+    /// Blocks a referendum has to pass once deciding
+    pub decision_period: u32,
+    /// Blocks a referendum has to keep passing to be approved
+    pub confirm_period: u32,
+    /// Blocks between approval and enactment, at least
+    pub min_enactment_period: u32,
+}
+
+/// Lock multiplier applied to a vote
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Conviction {
+    /// 0.1x votes, no lock
+    #[default]
+    None,
+    /// 1x votes, locked for one period
+    Locked1x,
+    /// 2x votes, locked for two periods
+    Locked2x,
+    /// 3x votes, locked for four periods
+    Locked3x,
+    /// 4x votes, locked for eight periods
+    Locked4x,
+    /// 5x votes, locked for 16 periods
+    Locked5x,
+    /// 6x votes, locked for 32 periods
+    Locked6x,
+}
+
+impl Conviction {
+    fn to_value(self) -> Value {
+        let name = match self {
+            Conviction::None => "None",
+            Conviction::Locked1x => "Locked1x",
+            Conviction::Locked2x => "Locked2x",
+            Conviction::Locked3x => "Locked3x",
+            Conviction::Locked4x => "Locked4x",
+            Conviction::Locked5x => "Locked5x",
+            Conviction::Locked6x => "Locked6x",
+        };
+        Value::unnamed_variant(name, [])
+    }
+}
+
+/// A vote on a referendum
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccountVote {
+    /// Aye or nay with `balance` at `conviction`
+    Standard {
+        aye: bool,
+        conviction: Conviction,
+        balance: u128,
+    },
+    /// Balance split across aye, nay and abstain, without conviction
+    SplitAbstain { aye: u128, nay: u128, abstain: u128 },
+}
+
+impl AccountVote {
+    fn to_value(self) -> Value {
+        match self {
+            AccountVote::Standard {
+                aye,
+                conviction,
+                balance,
+            } => {
+                // Vote(u8): the aye flag in the top bit, conviction below
+                let vote = ((aye as u8) << 7) | conviction as u8;
+                Value::named_variant(
+                    "Standard",
+                    [
+                        (
+                            "vote",
+                            Value::unnamed_composite([Value::u128(vote as u128)]),
+                        ),
+                        ("balance", Value::u128(balance)),
+                    ],
+                )
+            }
+            AccountVote::SplitAbstain { aye, nay, abstain } => Value::named_variant(
+                "SplitAbstain",
+                [
+                    ("aye", Value::u128(aye)),
+                    ("nay", Value::u128(nay)),
+                    ("abstain", Value::u128(abstain)),
+                ],
+            ),
+        }
+    }
+}
+
+/// Client for OpenGov referenda and voting
+pub struct OpenGov {
+    client: OnlineClient<PolkadotConfig>,
+    metrics: Metrics,
+}
+
+impl OpenGov {
+    /// Create a client for the chain behind `client`
+    pub fn new(client: OnlineClient<PolkadotConfig>, metrics: Metrics) -> Self {
+        Self { client, metrics }
+    }
+
+    /// Get referendum `index`, `None` if it does not exist
+    pub async fn referendum(&self, index: u32) -> Result<Option<Referendum>> {
+        self.metrics.record_storage_query();
+        let query = subxt::dynamic::storage(
+            "Referenda",
+            "ReferendumInfoFor",
+            vec![Value::u128(index as u128)],
+        );
+
+        let value = self
+            .client
+            .storage()
+            .at_latest()
+            .await
+            .map_err(|e| Error::Connection(format!("Failed to fetch latest block: {}", e)))?
+            .fetch(&query)
+            .await
+            .map_err(|e| Error::Storage(format!("Failed to query referendum: {}", e)))?;
+
+        match value {
+            Some(value) => {
+                let value = value
+                    .to_value()
+                    .map_err(|e| Error::Storage(format!("Failed to decode referendum: {}", e)))?;
+                parse_referendum(index, &value).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Get all referenda, newest first
+    pub async fn referenda(&self) -> Result<Vec<Referendum>> {
+        self.metrics.record_storage_query();
+        let query = subxt::dynamic::storage("Referenda", "ReferendumInfoFor", Vec::<Value>::new());
+
+        let storage = self
+            .client
+            .storage()
+            .at_latest()
+            .await
+            .map_err(|e| Error::Connection(format!("Failed to fetch latest block: {}", e)))?;
+        let mut iter = storage
+            .iter(query)
+            .await
+            .map_err(|e| Error::Storage(format!("Failed to iterate referenda: {}", e)))?;
+
+        let mut referenda = Vec::new();
+        while let Some(entry) = iter.next().await {
+            let entry =
+                entry.map_err(|e| Error::Storage(format!("Failed to fetch referendum: {}", e)))?;
+            // Keys are Blake2_128Concat-hashed, ending in the SCALE-encoded u32 index
+            let index = entry
+                .key_bytes
+                .last_chunk::<4>()
+                .map(|bytes| u32::from_le_bytes(*bytes))
+                .ok_or_else(|| Error::Storage("Malformed referendum key".to_string()))?;
+            let value = entry
+                .value
+                .to_value()
+                .map_err(|e| Error::Storage(format!("Failed to decode referendum: {}", e)))?;
+            referenda.push(parse_referendum(index, &value)?);
+        }
+
+        debug!("Found {} referenda", referenda.len());
+        referenda.sort_by_key(|r| std::cmp::Reverse(r.index));
+        Ok(referenda)
+    }
+
+    /// Get the referendum tracks of the runtime
+    #[allow(clippy::result_large_err)]
+    pub fn tracks(&self) -> Result<Vec<Track>> {
+        let address = subxt::dynamic::constant("Referenda", "Tracks");
+        let value = self
+            .client
+            .constants()
+            .at(&address)
+            .map_err(|e| Error::Metadata(format!("Failed to read Tracks: {}", e)))?
+            .to_value()
+            .map_err(|e| Error::Metadata(format!("Failed to decode Tracks: {}", e)))?;
+        parse_tracks(&value).ok_or_else(|| Error::Metadata("Malformed Tracks".to_string()))
+    }
+
+    /// Number of referenda being decided on `track`
+    pub async fn deciding_count(&self, track: u16) -> Result<u32> {
+        let value = self
+            .fetch("DecidingCount", vec![Value::u128(track as u128)])
+            .await?;
+        Ok(value
+            .and_then(|v| v.as_u128())
+            .map_or(0, |count| count as u32))
+    }
+
+    /// Referenda of `track` waiting for a decision slot, with their
+    /// support, in queue order
+    pub async fn track_queue(&self, track: u16) -> Result<Vec<(u32, u128)>> {
+        let value = self
+            .fetch("TrackQueue", vec![Value::u128(track as u128)])
+            .await?;
+        let Some(value) = value else {
+            return Ok(Vec::new());
+        };
+        parse_track_queue(&value)
+            .ok_or_else(|| Error::Storage(format!("Malformed queue of track {}", track)))
+    }
+
+    /// Vote on referendum `index`, returning the extrinsic hash once finalized
+    pub async fn vote(&self, wallet: &Wallet, index: u32, vote: AccountVote) -> Result<String> {
+        self.submit(
+            wallet,
+            "vote",
+            vec![Value::u128(index as u128), vote.to_value()],
+        )
+        .await
+    }
+
+    /// Delegate the votes of `wallet` on `track` to `to`, with `balance` at
+    /// `conviction`, returning the extrinsic hash once finalized
+    ///
+    /// Fails while `wallet` has direct votes on the track.
+    pub async fn delegate(
+        &self,
+        wallet: &Wallet,
+        track: u16,
+        to: &str,
+        conviction: Conviction,
+        balance: u128,
+    ) -> Result<String> {
+        self.submit(
+            wallet,
+            "delegate",
+            vec![
+                Value::u128(track as u128),
+                multi_address(to)?,
+                conviction.to_value(),
+                Value::u128(balance),
+            ],
+        )
+        .await
+    }
+
+    /// Stop delegating the votes of `wallet` on `track`, returning the
+    /// extrinsic hash once finalized
+    ///
+    /// The delegated balance stays locked for the conviction period.
+    pub async fn undelegate(&self, wallet: &Wallet, track: u16) -> Result<String> {
+        self.submit(wallet, "undelegate", vec![Value::u128(track as u128)])
+            .await
+    }
+
+    async fn submit(&self, wallet: &Wallet, call: &str, fields: Vec<Value>) -> Result<String> {
+        let call = subxt::dynamic::tx("ConvictionVoting", call, fields);
+        let receipt = TransactionExecutor::new(self.client.clone(), self.metrics.clone())
+            .submit_typed(&call, wallet)
+            .await?;
+        Ok(receipt.hash)
+    }
+
+    #[allow(clippy::result_large_err)]
+    async fn fetch(&self, item: &str, keys: Vec<Value>) -> Result<Option<scale_value::Value<u32>>> {
+        self.metrics.record_storage_query();
+        let query = subxt::dynamic::storage("Referenda", item, keys);
+        let value = self
+            .client
+            .storage()
+            .at_latest()
+            .await
+            .map_err(|e| Error::Connection(format!("Failed to fetch latest block: {}", e)))?
+            .fetch(&query)
+            .await
+            .map_err(|e| Error::Storage(format!("Failed to query Referenda.{}: {}", item, e)))?;
+
+        value
+            .map(|value| {
+                value.to_value().map_err(|e| {
+                    Error::Storage(format!("Failed to decode Referenda.{}: {}", item, e))
+                })
+            })
+            .transpose()
+    }
+}
+
+/// Read the decoded `Tracks` constant, a list of `(id, TrackInfo)`
+fn parse_tracks<T>(value: &scale_value::Value<T>) -> Option<Vec<Track>> {
+    let ValueDef::Composite(tracks) = &value.value else {
+        return None;
+    };
+    tracks
+        .values()
+        .map(|entry| {
+            let info = entry.at(1)?;
+            let number = |name: &str| info.at(name).and_then(|v| v.as_u128());
+            Some(Track {
+                id: entry.at(0)?.as_u128()? as u16,
+                name: track_name(info.at("name")?),
+                max_deciding: number("max_deciding")? as u32,
+                decision_deposit: number("decision_deposit")?,
+                prepare_period: number("prepare_period")? as u32,
+                decision_period: number("decision_period")? as u32,
+                confirm_period: number("confirm_period")? as u32,
+                min_enactment_period: number("min_enactment_period")? as u32,
+            })
+        })
+        .collect()
+}
+
+/// A track name, a string or a zero-padded byte array depending on the
+/// runtime
+fn track_name<T>(value: &scale_value::Value<T>) -> String {
+    if let Some(name) = value.as_str() {
+        return name.to_string();
+    }
+    let mut bytes = Vec::new();
+    collect_bytes(value, &mut bytes);
+    String::from_utf8_lossy(&bytes)
+        .trim_end_matches('\0')
+        .to_string()
+}
+
+/// Read a decoded `TrackQueue`, a list of `(index, support)`
+fn parse_track_queue<T>(value: &scale_value::Value<T>) -> Option<Vec<(u32, u128)>> {
+    let ValueDef::Composite(entries) = &value.value else {
+        return None;
+    };
+    entries
+        .values()
+        .map(|entry| Some((entry.at(0)?.as_u128()? as u32, entry.at(1)?.as_u128()?)))
+        .collect()
+}
+
+/// Read a decoded `ReferendumInfo`
+#[allow(clippy::result_large_err)]
+pub(crate) fn parse_referendum<T>(index: u32, value: &scale_value::Value<T>) -> Result<Referendum> {
+    let ValueDef::Variant(info) = &value.value else {
+        return Err(Error::Storage(format!(
+            "Referendum {} is not a ReferendumInfo",
+            index
+        )));
+    };
+    let fields = &info.values;
+    let block =
+        |value: Option<&scale_value::Value<T>>| value.and_then(|v| v.as_u128()).map(|n| n as u32);
+
+    let referendum = |status| Referendum {
+        index,
+        status,
+        track: None,
+        tally: None,
+        since: block(fields.at(0)),
+    };
+
+    Ok(match info.name.as_str() {
+        "Ongoing" => {
+            let ongoing = fields.at(0);
+            let amount = |name: &str| {
+                ongoing
+                    .at("tally")
+                    .at(name)
+                    .and_then(|v| v.as_u128())
+                    .unwrap_or_default()
+            };
+            let deciding = ongoing
+                .at("deciding")
+                .is_some_and(|d| matches!(&d.value, ValueDef::Variant(v) if v.name == "Some"));
+
+            Referendum {
+                index,
+                status: if deciding {
+                    ReferendumStatus::Deciding
+                } else {
+                    ReferendumStatus::Preparing
+                },
+                track: ongoing
+                    .at("track")
+                    .and_then(|v| v.as_u128())
+                    .map(|t| t as u16),
+                tally: Some(ReferendumTally {
+                    ayes: amount("ayes"),
+                    nays: amount("nays"),
+                    support: amount("support"),
+                }),
+                since: block(ongoing.at("submitted")),
+            }
+        }
+        "Approved" => referendum(ReferendumStatus::Approved),
+        "Rejected" => referendum(ReferendumStatus::Rejected),
+        "Cancelled" => referendum(ReferendumStatus::Cancelled),
+        "TimedOut" => referendum(ReferendumStatus::TimedOut),
+        "Killed" => referendum(ReferendumStatus::Killed),
+        other => {
+            return Err(Error::Storage(format!(
+                "Unknown referendum state {} for {}",
+                other, index
+            )))
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ongoing_referendum() {
+        let value = Value::unnamed_variant(
+            "Ongoing",
+            [Value::named_composite([
+                ("track", Value::u128(33)),
+                ("submitted", Value::u128(1_000)),
+                (
+                    "deciding",
+                    Value::unnamed_variant("Some", [Value::u128(1_010)]),
+                ),
+                (
+                    "tally",
+                    Value::named_composite([
+                        ("ayes", Value::u128(70)),
+                        ("nays", Value::u128(30)),
+                        ("support", Value::u128(50)),
+                    ]),
+                ),
+            ])],
+        );
+
+        let referendum = parse_referendum(7, &value).unwrap();
+        assert_eq!(referendum.status, ReferendumStatus::Deciding);
+        assert_eq!(referendum.track, Some(33));
+        assert_eq!(referendum.since, Some(1_000));
+        assert_eq!(referendum.tally.unwrap().ayes, 70);
+    }
+
+    #[test]
+    fn test_parse_tracks() {
+        let track = |id: u128, name: Value| {
+            Value::unnamed_composite([
+                Value::u128(id),
+                Value::named_composite([
+                    ("name", name),
+                    ("max_deciding", Value::u128(10)),
+                    ("decision_deposit", Value::u128(1_000)),
+                    ("prepare_period", Value::u128(200)),
+                    ("decision_period", Value::u128(40_000)),
+                    ("confirm_period", Value::u128(300)),
+                    ("min_enactment_period", Value::u128(100)),
+                ]),
+            ])
+        };
+        let mut padded = b"small_tipper".to_vec();
+        padded.resize(25, 0);
+        let value = Value::unnamed_composite([
+            track(0, Value::string("root")),
+            track(30, Value::from_bytes(padded)),
+        ]);
+
+        let tracks = parse_tracks(&value).unwrap();
+        assert_eq!(tracks.len(), 2);
+        assert_eq!(tracks[0].name, "root");
+        assert_eq!(tracks[1].id, 30);
+        assert_eq!(tracks[1].name, "small_tipper");
+        assert_eq!(tracks[1].decision_period, 40_000);
+        assert_eq!(parse_tracks(&Value::u128(0)), None);
+    }
+
+    #[test]
+    fn test_parse_track_queue() {
+        let value = Value::unnamed_composite([
+            Value::unnamed_composite([Value::u128(12), Value::u128(500)]),
+            Value::unnamed_composite([Value::u128(9), Value::u128(100)]),
+        ]);
+        assert_eq!(parse_track_queue(&value), Some(vec![(12, 500), (9, 100)]));
+    }
+
+    #[test]
+    fn test_parse_finished_referendum() {
+        let value = Value::unnamed_variant(
+            "Approved",
+            [
+                Value::u128(2_000),
+                Value::unnamed_variant("None", []),
+                Value::unnamed_variant("None", []),
+            ],
+        );
+
+        let referendum = parse_referendum(7, &value).unwrap();
+        assert_eq!(referendum.status, ReferendumStatus::Approved);
+        assert_eq!(referendum.since, Some(2_000));
+        assert_eq!(referendum.tally, None);
+    }
+}
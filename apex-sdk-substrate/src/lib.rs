@@ -4,25 +4,64 @@
 //! It includes support for:
 //! - Connection management via WebSocket
 //! - Account and wallet management (SR25519, ED25519)
+//! - Sign-in challenges for SS58 accounts
 //! - Transaction execution (extrinsics)
-//! - Storage queries
+//! - Storage queries and storage-change subscriptions
+//! - Staking: bonding, nominations and rewards from era history
+//! - `Assets` pallet balances, metadata, transfers and minting
+//! - OpenGov referenda, tracks, voting and delegation
+//! - Multisig accounts and approvals
+//! - Proxy accounts and submission as a proxy
+//! - NFTs of `pallet-nfts` and `pallet-uniques`
+//! - PSP22 and PSP34 ink! tokens
+//! - Raw RPC calls for chain-specific methods
+//! - Transaction status from an index of finalized extrinsics
 //! - Connection pooling
+//! - Per-endpoint rate limiting
 //! - Caching
 //! - Metrics collection
 
-use apex_sdk_types::{Address, TransactionStatus};
+use apex_sdk_core::performance::{RateLimitConfig, RateLimiter};
+use apex_sdk_core::redact::{redact_url, redact_urls};
+use apex_sdk_core::{AdapterError, BlockRef};
+use apex_sdk_types::{
+    genesis, Address, Amount, BlockInfo, Chain, ChainType, ConfirmationPolicy, Event, Finality,
+    TransactionEvent, TransactionStatus,
+};
 use async_trait::async_trait;
+use std::sync::Arc;
+use subxt::backend::legacy::LegacyRpcMethods;
+use subxt::backend::rpc::RpcClient;
+use subxt::blocks::Block;
+use subxt::ext::scale_value::{Composite, Primitive, Value, ValueDef};
+use subxt::ext::subxt_rpcs::client::rpc_params;
 use subxt::{OnlineClient, PolkadotConfig};
 use thiserror::Error;
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
 
+pub mod assets;
+pub mod auth;
 pub mod cache;
+#[cfg(feature = "typed")]
+pub mod codegen;
 pub mod contracts;
+pub mod descriptor;
+pub mod dex;
+pub mod governance;
 pub mod metrics;
+pub mod multisig;
+pub mod nfts;
 pub mod pool;
+pub mod proxy;
+pub mod psp;
+pub mod rate_limit;
 pub mod signer;
+pub mod staking;
 pub mod storage;
 pub mod transaction;
+pub mod tx_index;
+#[cfg(feature = "typed")]
+pub mod typed;
 pub mod wallet;
 pub mod xcm;
 
@@ -33,18 +72,41 @@ pub mod xcm;
 ))]
 pub mod metadata;
 
+pub use assets::{AssetDetails, AssetMetadata, Assets};
 pub use cache::{Cache, CacheConfig};
 pub use contracts::{
-    parse_metadata, ContractCallBuilder, ContractClient, ContractMetadata, GasLimit,
+    decode_event, parse_metadata, CallDryRun, ContractCallBuilder, ContractClient, ContractCode,
+    ContractDeployer, ContractEvent, ContractMetadata, EventSource, GasLimit, InstantiateEstimate,
     StorageDepositLimit,
 };
+pub use descriptor::{
+    CallDescriptor, ChainDescriptor, Mortality, OfflineParams, OfflineSubstrateAdapter,
+    PalletDescriptor, UnsignedExtrinsic, DESCRIPTOR_VERSION,
+};
+pub use dex::{decode_location, encode_location, AssetConversion};
+pub use governance::{
+    AccountVote, Conviction, OpenGov, Referendum, ReferendumStatus, ReferendumTally, Track,
+};
 pub use metrics::{Metrics, MetricsSnapshot};
+pub use multisig::{
+    multisig_account, Multisig, PendingMultisig, Timepoint, DEFAULT_MULTISIG_MAX_WEIGHT,
+};
+pub use nfts::{NftCollection, NftPallet, Nfts};
 pub use pool::{ConnectionPool, PoolConfig};
-pub use signer::{ApexSigner, Ed25519Signer, Sr25519Signer};
-pub use storage::{StorageClient, StorageQuery};
+pub use proxy::{Proxy, ProxyDefinition, ProxyType};
+pub use psp::{Psp22, Psp34, Psp34Id};
+#[cfg(feature = "pkcs11")]
+pub use signer::Pkcs11Ed25519Signer;
+pub use signer::{ApexSigner, EcdsaSigner, Ed25519Signer, JsonRpcSigner, Sr25519Signer};
+pub use staking::{ActiveEra, EraReward, RewardDestination, Staking, StakingLedger, UnlockChunk};
+pub use storage::{
+    StorageChange, StorageClient, StoragePage, StorageQuery, DEFAULT_STORAGE_PAGE_SIZE,
+};
 pub use transaction::{
-    BatchCall, BatchMode, ExtrinsicBuilder, FeeConfig, RetryConfig, TransactionExecutor,
+    decode_transfer_args, encode_transfer_args, BatchCall, BatchMode, ExtrinsicBuilder,
+    ExtrinsicReceipt, FeeConfig, RetryConfig, TransactionExecutor,
 };
+pub use tx_index::{ExtrinsicOutcome, IndexedExtrinsic, TxIndex, DEFAULT_TX_INDEX_CAPACITY};
 pub use wallet::{KeyPairType, Wallet, WalletManager};
 pub use xcm::{
     AssetId, Fungibility, Junction, MultiLocation, NetworkId, WeightLimit, XcmAsset, XcmConfig,
@@ -85,19 +147,59 @@ pub enum Error {
 /// Type alias for Result with our Error type
 pub type Result<T> = std::result::Result<T, Error>;
 
+/// Message for a failed RPC call, with endpoint credentials masked
+pub(crate) fn rpc_error(context: &str, e: impl std::fmt::Display) -> String {
+    redact_urls(&format!("{}: {}", context, e))
+}
+
+/// Whether `e` means the node could not be reached, rather than that it
+/// answered with an error
+pub(crate) fn is_unreachable(e: &subxt::Error) -> bool {
+    use subxt::error::RpcError;
+    use subxt::ext::subxt_rpcs::Error as ClientError;
+
+    matches!(
+        e,
+        subxt::Error::Rpc(
+            RpcError::SubscriptionDropped
+                | RpcError::ClientError(
+                    ClientError::Client(_) | ClientError::DisconnectedWillReconnect(_)
+                )
+        )
+    )
+}
+
+/// Error for a failed storage query: [`Error::Connection`] if the node could
+/// not be reached, so pooled reads fail over
+fn storage_error(context: &str, e: subxt::Error) -> Error {
+    if is_unreachable(&e) {
+        Error::Connection(rpc_error(context, e))
+    } else {
+        Error::Storage(format!("{}: {}", context, e))
+    }
+}
+
+/// Genesis block hash of Westend, which has no [`Chain`] variant
+const WESTEND_GENESIS: &str = "0xe143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e";
+
 /// Chain configuration for different Substrate chains
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct ChainConfig {
     /// Chain name
     pub name: String,
     /// WebSocket endpoint
     pub endpoint: String,
-    /// SS58 address prefix
+    /// SS58 address prefix, replaced on connect by the runtime's
+    /// `System.SS58Prefix` when it declares one
     pub ss58_prefix: u16,
     /// Token symbol
     pub token_symbol: String,
     /// Token decimals
     pub token_decimals: u8,
+    /// Request rate limit for the endpoint
+    pub rate_limit: Option<RateLimitConfig>,
+    /// Genesis hash the endpoint must serve, checked on connect
+    pub genesis_hash: Option<String>,
 }
 
 impl ChainConfig {
@@ -109,6 +211,8 @@ impl ChainConfig {
             ss58_prefix: 0,
             token_symbol: "DOT".to_string(),
             token_decimals: 10,
+            rate_limit: None,
+            genesis_hash: Some(genesis::POLKADOT.to_string()),
         }
     }
 
@@ -120,6 +224,8 @@ impl ChainConfig {
             ss58_prefix: 2,
             token_symbol: "KSM".to_string(),
             token_decimals: 12,
+            rate_limit: None,
+            genesis_hash: Some(genesis::KUSAMA.to_string()),
         }
     }
 
@@ -131,6 +237,8 @@ impl ChainConfig {
             ss58_prefix: 42,
             token_symbol: "WND".to_string(),
             token_decimals: 12,
+            rate_limit: None,
+            genesis_hash: Some(WESTEND_GENESIS.to_string()),
         }
     }
 
@@ -142,52 +250,734 @@ impl ChainConfig {
             ss58_prefix,
             token_symbol: "UNIT".to_string(),
             token_decimals: 12,
+            rate_limit: None,
+            genesis_hash: None,
         }
     }
+
+    /// Refuse to connect to endpoints not serving the chain with genesis
+    /// block hash `hash`
+    pub fn with_genesis_hash(mut self, hash: impl Into<String>) -> Self {
+        self.genesis_hash = Some(hash.into());
+        self
+    }
+
+    /// Limit requests to the endpoint
+    ///
+    /// Adapters connecting to the same endpoint share one limiter.
+    pub fn with_rate_limit(mut self, config: RateLimitConfig) -> Self {
+        self.rate_limit = Some(config);
+        self
+    }
+}
+
+impl std::fmt::Debug for ChainConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ChainConfig")
+            .field("name", &self.name)
+            .field("endpoint", &redact_url(&self.endpoint))
+            .field("ss58_prefix", &self.ss58_prefix)
+            .field("token_symbol", &self.token_symbol)
+            .field("token_decimals", &self.token_decimals)
+            .field("rate_limit", &self.rate_limit)
+            .field("genesis_hash", &self.genesis_hash)
+            .finish()
+    }
+}
+
+/// An extrinsic included in a block
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExtrinsicInfo {
+    /// Position of the extrinsic within the block
+    pub index: u32,
+    /// Extrinsic hash
+    pub hash: String,
+    /// Pallet of the call
+    pub pallet: String,
+    /// Call name
+    pub call: String,
+    /// SS58 address of the signer, `None` for unsigned extrinsics
+    pub signer: Option<String>,
+}
+
+/// A block's header, extrinsics and events
+#[derive(Debug, Clone)]
+pub struct BlockContents {
+    /// Block header
+    pub info: BlockInfo,
+    /// Extrinsics in block order
+    pub extrinsics: Vec<ExtrinsicInfo>,
+    /// Events in block order
+    pub events: Vec<Event>,
+}
+
+/// Decode the events of `block`, skipping (and logging) undecodable ones
+async fn block_events(
+    block: &Block<PolkadotConfig, OnlineClient<PolkadotConfig>>,
+) -> Result<Vec<Event>> {
+    let block_number = block.number() as u64;
+    let block_hash = format!("{:?}", block.hash());
+    let events = block
+        .events()
+        .await
+        .map_err(|e| Error::Connection(rpc_error("Failed to fetch events", e)))?;
+
+    let mut decoded = Vec::new();
+    for (index, event) in events.iter().enumerate() {
+        let event = match event {
+            Ok(event) => event,
+            Err(e) => {
+                warn!(
+                    "Failed to decode event {} in block {}: {}",
+                    index, block_number, e
+                );
+                continue;
+            }
+        };
+        let fields = event.field_values().ok();
+        decoded.push(Event {
+            chain_type: ChainType::Substrate,
+            block_number,
+            block_hash: block_hash.clone(),
+            tx_hash: None,
+            index: index as u32,
+            origin: fields
+                .as_ref()
+                .and_then(|fields| contract_origin(event.pallet_name(), fields)),
+            event: TransactionEvent::Substrate {
+                pallet: event.pallet_name().to_string(),
+                variant: event.variant_name().to_string(),
+                fields: fields.map(|f| f.to_string()).unwrap_or_default(),
+            },
+        });
+    }
+    Ok(decoded)
+}
+
+/// Contract that emitted a `Contracts` pallet event, from its `contract` field
+fn contract_origin<T>(pallet: &str, fields: &Composite<T>) -> Option<Address> {
+    use sp_core::crypto::{AccountId32, Ss58Codec};
+
+    if pallet != "Contracts" {
+        return None;
+    }
+    let Composite::Named(fields) = fields else {
+        return None;
+    };
+    let (_, contract) = fields.iter().find(|(name, _)| name == "contract")?;
+
+    let mut bytes = Vec::new();
+    collect_bytes(contract, &mut bytes);
+    let account: [u8; 32] = bytes.try_into().ok()?;
+    Some(Address::substrate(
+        AccountId32::from(account).to_ss58check(),
+    ))
+}
+
+/// Whether `extrinsic`, SCALE-encoded with its length prefix, carries a
+/// signature
+fn is_signed_extrinsic(extrinsic: &[u8]) -> bool {
+    use parity_scale_codec::{Compact, Decode};
+
+    let mut input = extrinsic;
+    let Ok(Compact(len)) = Compact::<u32>::decode(&mut input) else {
+        return false;
+    };
+    // The version byte's top bit marks signed extrinsics
+    len as usize == input.len() && input.first().is_some_and(|version| version & 0x80 != 0)
+}
+
+/// The runtime's `System.SS58Prefix` constant, if its metadata has one
+fn runtime_ss58_prefix(client: &OnlineClient<PolkadotConfig>) -> Option<u16> {
+    let address = subxt::dynamic::constant("System", "SS58Prefix");
+    let value = client.constants().at(&address).ok()?.to_value().ok()?;
+    value
+        .as_u128()
+        .and_then(|prefix| u16::try_from(prefix).ok())
+}
+
+/// The 32 account ID bytes of the SS58 `address`
+#[allow(clippy::result_large_err)]
+pub(crate) fn account_id(address: &str) -> Result<[u8; 32]> {
+    use sp_core::crypto::{AccountId32, Ss58Codec};
+    AccountId32::from_ss58check(address)
+        .map(Into::into)
+        .map_err(|e| Error::Transaction(format!("Invalid SS58 address {}: {}", address, e)))
+}
+
+/// `MultiAddress::Id` of the SS58 `address`, as taken by dispatchables
+#[allow(clippy::result_large_err)]
+pub(crate) fn multi_address(address: &str) -> Result<Value> {
+    Ok(Value::unnamed_variant(
+        "Id",
+        vec![Value::from_bytes(account_id(address)?)],
+    ))
+}
+
+/// Flatten a decoded byte array (such as an `AccountId32`) into `bytes`
+pub(crate) fn collect_bytes<T>(value: &Value<T>, bytes: &mut Vec<u8>) {
+    match &value.value {
+        ValueDef::Primitive(Primitive::U128(byte)) => bytes.push(*byte as u8),
+        ValueDef::Composite(composite) => {
+            for value in composite.values() {
+                collect_bytes(value, bytes);
+            }
+        }
+        _ => {}
+    }
 }
 
 /// Substrate blockchain adapter
+#[derive(Clone)]
 pub struct SubstrateAdapter {
     /// WebSocket endpoint
     endpoint: String,
     /// Subxt client
     client: OnlineClient<PolkadotConfig>,
+    /// RPC client the subxt client runs on
+    rpc: RpcClient,
     /// Chain configuration
     config: ChainConfig,
     /// Connection status
     connected: bool,
     /// Metrics collector
     metrics: Metrics,
+    /// Retry policy for raw RPC calls
+    rpc_retry: RetryConfig,
+    /// Pool serving reads and submissions, if connected with
+    /// [`connect_pooled`](Self::connect_pooled)
+    pool: Option<Arc<ConnectionPool>>,
+    /// Index of finalized extrinsics, if enabled with
+    /// [`with_tx_index`](Self::with_tx_index)
+    tx_index: Option<Arc<TxIndex>>,
 }
 
 impl SubstrateAdapter {
-    /// Connect to a Substrate node using default Polkadot configuration
+    /// Connect to a Substrate node using default configuration
+    ///
+    /// The SS58 prefix addresses are validated against is read from the
+    /// runtime, so this works for Polkadot, Kusama and other chains alike.
     pub async fn connect(endpoint: &str) -> Result<Self> {
         Self::connect_with_config(ChainConfig::custom("Substrate", endpoint, 42)).await
     }
 
     /// Connect to a Substrate node with specific chain configuration
-    pub async fn connect_with_config(config: ChainConfig) -> Result<Self> {
-        info!("Connecting to {} at {}", config.name, config.endpoint);
+    pub async fn connect_with_config(mut config: ChainConfig) -> Result<Self> {
+        info!(
+            "Connecting to {} at {}",
+            config.name,
+            redact_url(&config.endpoint)
+        );
 
-        // Create subxt client
-        let client = OnlineClient::<PolkadotConfig>::from_url(&config.endpoint)
+        // Create subxt client, throttled if a rate limit is configured
+        let rpc = RpcClient::from_url(&config.endpoint)
             .await
-            .map_err(|e| Error::Connection(format!("Failed to connect: {}", e)))?;
+            .map_err(|e| Error::Connection(rpc_error("Failed to connect", e)))?;
+        let rpc = match config.rate_limit {
+            Some(limit) => {
+                let limiter = RateLimiter::for_endpoint(&config.endpoint, limit);
+                RpcClient::new(rate_limit::RateLimitedRpcClient::new(rpc, limiter))
+            }
+            None => rpc,
+        };
+        let client = OnlineClient::<PolkadotConfig>::from_rpc_client(rpc.clone())
+            .await
+            .map_err(|e| Error::Connection(rpc_error("Failed to connect", e)))?;
 
         // Verify connection by fetching metadata
         let _metadata = client.metadata();
+
+        // Refuse endpoints serving another chain than the configured one
+        let genesis_hash = format!("0x{}", hex::encode(client.genesis_hash()));
+        if let Some(expected) = &config.genesis_hash {
+            if !expected.eq_ignore_ascii_case(&genesis_hash) {
+                return Err(Error::Connection(format!(
+                    "{} serves genesis {}, expected {} for {}",
+                    redact_url(&config.endpoint),
+                    genesis_hash,
+                    expected,
+                    config.name
+                )));
+            }
+        }
+        if let Some(prefix) = runtime_ss58_prefix(&client) {
+            if prefix != config.ss58_prefix {
+                debug!(
+                    "{} runtime uses SS58 prefix {}, not {}",
+                    config.name, prefix, config.ss58_prefix
+                );
+                config.ss58_prefix = prefix;
+            }
+        }
         debug!("Connected to {}", config.name);
 
         Ok(Self {
             endpoint: config.endpoint.clone(),
             client,
+            rpc,
             config,
             connected: true,
             metrics: Metrics::new(),
+            rpc_retry: RetryConfig::default(),
+            pool: None,
+            tx_index: None,
         })
     }
 
+    /// Connect to several endpoints of the same chain through a
+    /// [`ConnectionPool`], using default Substrate configuration
+    ///
+    /// `endpoints` replace those in `config`. See
+    /// [`connect_pooled_with_config`](Self::connect_pooled_with_config).
+    pub async fn connect_pooled(endpoints: Vec<String>, config: PoolConfig) -> Result<Self> {
+        let first = endpoints.first().cloned().unwrap_or_default();
+        Self::connect_pooled_with_config(
+            ChainConfig::custom("Substrate", first, 42),
+            PoolConfig {
+                endpoints,
+                ..config
+            },
+        )
+        .await
+    }
+
+    /// Connect to the endpoints in `pool` of the chain described by `config`
+    ///
+    /// Balance and storage reads are spread round-robin over the healthy
+    /// endpoints; submissions and the executors stick to one healthy
+    /// endpoint. Either moves on to the next endpoint when one cannot be
+    /// reached, and the pool's health checker reconnects it. Other calls,
+    /// such as [`rpc_call`](Self::rpc_call) and subscriptions, use the
+    /// endpoint first pinned.
+    ///
+    /// ```rust,no_run
+    /// # use apex_sdk_substrate::{PoolConfig, SubstrateAdapter};
+    /// # async fn example() -> apex_sdk_substrate::Result<()> {
+    /// let adapter = SubstrateAdapter::connect_pooled(
+    ///     vec![
+    ///         "wss://rpc.polkadot.io".to_string(),
+    ///         "wss://polkadot-rpc.dwellir.com".to_string(),
+    ///     ],
+    ///     PoolConfig::new(vec![]),
+    /// )
+    /// .await?;
+    /// let balance = adapter
+    ///     .get_balance("15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5")
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn connect_pooled_with_config(config: ChainConfig, pool: PoolConfig) -> Result<Self> {
+        let pool = Arc::new(ConnectionPool::new(pool, config).await?);
+        let mut adapter = (*pool.pinned_connection()?).clone();
+        adapter.pool = Some(pool);
+        Ok(adapter)
+    }
+
+    /// Open a fresh connection to this adapter's endpoint, keeping its
+    /// configuration, metrics and retry policy
+    ///
+    /// Pooled adapters move to the pool's currently pinned connection.
+    pub async fn reconnect(&self) -> Result<Self> {
+        let mut adapter = match &self.pool {
+            Some(pool) => (*pool.pinned_connection()?).clone(),
+            None => Self::connect_with_config(self.config.clone()).await?,
+        };
+        adapter.metrics = self.metrics.clone();
+        adapter.rpc_retry = self.rpc_retry.clone();
+        adapter.pool = self.pool.clone();
+        adapter.tx_index = self.tx_index.clone();
+        Ok(adapter)
+    }
+
+    /// Index finalized extrinsics in the background, keeping up to
+    /// `capacity` of the most recent ones
+    ///
+    /// [`get_transaction_status`](Self::get_transaction_status) answers
+    /// indexed extrinsics from memory. The index follows finalized blocks
+    /// until every adapter sharing it is dropped. Must be called within a
+    /// Tokio runtime.
+    pub fn with_tx_index(mut self, capacity: usize) -> Self {
+        let index = Arc::new(TxIndex::new(capacity));
+        index.start(self.client.clone());
+        self.tx_index = Some(index);
+        self
+    }
+
+    /// The index of finalized extrinsics, if enabled
+    pub fn tx_index(&self) -> Option<&Arc<TxIndex>> {
+        self.tx_index.as_ref()
+    }
+
+    /// The pool behind an adapter connected with
+    /// [`connect_pooled`](Self::connect_pooled)
+    pub fn pool(&self) -> Option<&Arc<ConnectionPool>> {
+        self.pool.as_ref()
+    }
+
+    /// Client and RPC client for the next storage read: round-robin over a
+    /// pool's healthy connections, or this adapter's own
+    fn read_connection(&self) -> (OnlineClient<PolkadotConfig>, RpcClient) {
+        self.pool
+            .as_ref()
+            .and_then(|pool| pool.get_connection().ok())
+            .map_or_else(
+                || (self.client.clone(), self.rpc.clone()),
+                |adapter| (adapter.client.clone(), adapter.rpc.clone()),
+            )
+    }
+
+    /// Client for submissions: a pool's pinned connection, or this
+    /// adapter's own
+    fn submit_client(&self) -> OnlineClient<PolkadotConfig> {
+        self.pool
+            .as_ref()
+            .and_then(|pool| pool.pinned_connection().ok())
+            .map_or_else(|| self.client.clone(), |adapter| adapter.client.clone())
+    }
+
+    /// Follow finalized blocks and send their events to `sender`
+    ///
+    /// Returns once `sender` is closed, or with an error if the block
+    /// subscription fails or ends.
+    pub async fn watch_finalized_events(
+        &self,
+        sender: tokio::sync::mpsc::Sender<Event>,
+    ) -> Result<()> {
+        let mut blocks = self
+            .client
+            .blocks()
+            .subscribe_finalized()
+            .await
+            .map_err(|e| {
+                Error::Connection(rpc_error("Failed to subscribe to finalized blocks", e))
+            })?;
+
+        while let Some(block) = blocks.next().await {
+            let block = block.map_err(|e| {
+                Error::Connection(rpc_error("Finalized block subscription failed", e))
+            })?;
+            for event in block_events(&block).await? {
+                if sender.send(event).await.is_err() {
+                    return Ok(());
+                }
+            }
+        }
+
+        if sender.is_closed() {
+            Ok(())
+        } else {
+            Err(Error::Connection(
+                "Finalized block subscription ended".to_string(),
+            ))
+        }
+    }
+
+    /// Get the number of the latest finalized block
+    pub async fn finalized_block_number(&self) -> Result<u64> {
+        let block = self
+            .client
+            .blocks()
+            .at_latest()
+            .await
+            .map_err(|e| Error::Connection(rpc_error("Failed to get latest block", e)))?;
+        Ok(block.number() as u64)
+    }
+
+    /// Get the events emitted in blocks `from_block..=to_block`, in chain order
+    ///
+    /// Scans block by block, so each block costs a few RPC requests. Stops
+    /// early at the chain head.
+    pub async fn scan_events(&self, from_block: u64, to_block: u64) -> Result<Vec<Event>> {
+        let rpc = LegacyRpcMethods::<PolkadotConfig>::new(self.rpc.clone());
+        let mut events = Vec::new();
+
+        for number in from_block..=to_block {
+            let hash = rpc
+                .chain_get_block_hash(Some(number.into()))
+                .await
+                .map_err(|e| Error::Connection(rpc_error("Failed to get block hash", e)))?;
+            let Some(hash) = hash else {
+                break;
+            };
+            let block = self
+                .client
+                .blocks()
+                .at(hash)
+                .await
+                .map_err(|e| Error::Connection(rpc_error("Failed to get block", e)))?;
+            events.extend(block_events(&block).await?);
+        }
+
+        Ok(events)
+    }
+
+    /// Get finalized block `number` with its extrinsics and events, `None`
+    /// beyond the chain head
+    pub async fn get_block(&self, number: u64) -> Result<Option<BlockContents>> {
+        use sp_core::crypto::{AccountId32, Ss58AddressFormat, Ss58Codec};
+
+        let rpc = LegacyRpcMethods::<PolkadotConfig>::new(self.rpc.clone());
+        let hash = rpc
+            .chain_get_block_hash(Some(number.into()))
+            .await
+            .map_err(|e| Error::Connection(rpc_error("Failed to get block hash", e)))?;
+        let Some(hash) = hash else {
+            return Ok(None);
+        };
+        let block = self
+            .client
+            .blocks()
+            .at(hash)
+            .await
+            .map_err(|e| Error::Connection(rpc_error("Failed to get block", e)))?;
+        let extrinsics = block
+            .extrinsics()
+            .await
+            .map_err(|e| Error::Connection(rpc_error("Failed to fetch extrinsics", e)))?;
+
+        let format = Ss58AddressFormat::custom(self.config.ss58_prefix);
+        let extrinsics = extrinsics
+            .iter()
+            .map(|ext| ExtrinsicInfo {
+                index: ext.index(),
+                hash: format!("{:?}", ext.hash()),
+                pallet: ext.pallet_name().unwrap_or_default().to_string(),
+                call: ext.variant_name().unwrap_or_default().to_string(),
+                // MultiAddress::Id: variant byte followed by the 32-byte account
+                signer: ext
+                    .address_bytes()
+                    .and_then(|bytes| bytes.strip_prefix(&[0]))
+                    .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+                    .map(|account| AccountId32::from(account).to_ss58check_with_version(format)),
+            })
+            .collect();
+
+        Ok(Some(BlockContents {
+            info: BlockInfo {
+                chain_type: ChainType::Substrate,
+                number,
+                hash: format!("{:?}", block.hash()),
+                parent_hash: format!("{:?}", block.header().parent_hash),
+                timestamp: None,
+                finality: Finality::Finalized,
+            },
+            extrinsics,
+            events: block_events(&block).await?,
+        }))
+    }
+
+    /// Get the header of a block by number or hash, `None` if the node does
+    /// not know it
+    ///
+    /// Numbers are resolved on the best chain. Blocks up to the latest
+    /// finalized one are reported as [`Finality::Finalized`].
+    pub async fn get_block_info(&self, block: BlockRef) -> Result<Option<BlockInfo>> {
+        let rpc = LegacyRpcMethods::<PolkadotConfig>::new(self.rpc.clone());
+        let hash = match &block {
+            BlockRef::Number(number) => rpc
+                .chain_get_block_hash(Some((*number).into()))
+                .await
+                .map_err(|e| Error::Connection(rpc_error("Failed to get block hash", e)))?,
+            BlockRef::Hash(hash) => {
+                let bytes = hex::decode(hash.trim_start_matches("0x"))
+                    .ok()
+                    .filter(|bytes| bytes.len() == 32)
+                    .ok_or_else(|| Error::Encoding(format!("Invalid block hash: {}", hash)))?;
+                Some(subxt::utils::H256::from_slice(&bytes))
+            }
+        };
+        let Some(hash) = hash else {
+            return Ok(None);
+        };
+        let header = rpc
+            .chain_get_header(Some(hash))
+            .await
+            .map_err(|e| Error::Connection(rpc_error("Failed to get block header", e)))?;
+        let Some(header) = header else {
+            return Ok(None);
+        };
+
+        let number = header.number as u64;
+        let finality = if number <= self.finalized_block_number().await? {
+            Finality::Finalized
+        } else {
+            Finality::Latest
+        };
+        Ok(Some(BlockInfo {
+            chain_type: ChainType::Substrate,
+            number,
+            hash: format!("{:?}", hash),
+            parent_hash: format!("{:?}", header.parent_hash),
+            timestamp: None,
+            finality,
+        }))
+    }
+
+    /// Follow new blocks at the given finality and send their headers to `sender`
+    ///
+    /// [`Finality::Latest`] follows the best block. Returns once `sender` is
+    /// closed, or with an error if the block subscription fails or ends.
+    pub async fn watch_blocks(
+        &self,
+        finality: Finality,
+        sender: tokio::sync::mpsc::Sender<BlockInfo>,
+    ) -> Result<()> {
+        let blocks = self.client.blocks();
+        let mut blocks = match finality {
+            Finality::Latest => blocks.subscribe_best().await,
+            Finality::Finalized => blocks.subscribe_finalized().await,
+        }
+        .map_err(|e| Error::Connection(rpc_error("Failed to subscribe to blocks", e)))?;
+
+        while let Some(block) = blocks.next().await {
+            let block =
+                block.map_err(|e| Error::Connection(rpc_error("Block subscription failed", e)))?;
+            let info = BlockInfo {
+                chain_type: ChainType::Substrate,
+                number: block.number() as u64,
+                hash: format!("{:?}", block.hash()),
+                parent_hash: format!("{:?}", block.header().parent_hash),
+                timestamp: None,
+                finality,
+            };
+            if sender.send(info).await.is_err() {
+                return Ok(());
+            }
+        }
+
+        if sender.is_closed() {
+            Ok(())
+        } else {
+            Err(Error::Connection("Block subscription ended".to_string()))
+        }
+    }
+
+    /// Follow changes to the storage entries at `keys` and send them to `sender`
+    ///
+    /// Keys are raw storage keys, e.g. from [`StorageClient::storage_key`].
+    /// The first notification carries the current value of every key, later
+    /// ones the keys changed by each new block. Returns once `sender` is
+    /// closed, or with an error if the subscription fails or ends.
+    pub async fn subscribe_storage(
+        &self,
+        keys: Vec<Vec<u8>>,
+        sender: tokio::sync::mpsc::Sender<StorageChange>,
+    ) -> Result<()> {
+        let keys: Vec<String> = keys
+            .iter()
+            .map(|key| format!("0x{}", hex::encode(key)))
+            .collect();
+        let mut changes = self
+            .rpc
+            .subscribe::<storage::StorageChangeSet>(
+                "state_subscribeStorage",
+                rpc_params![keys],
+                "state_unsubscribeStorage",
+            )
+            .await
+            .map_err(|e| Error::Connection(rpc_error("Failed to subscribe to storage", e)))?;
+
+        while let Some(set) = changes.next().await {
+            let set =
+                set.map_err(|e| Error::Connection(rpc_error("Storage subscription failed", e)))?;
+            for change in set.into_changes()? {
+                if sender.send(change).await.is_err() {
+                    return Ok(());
+                }
+            }
+        }
+
+        if sender.is_closed() {
+            Ok(())
+        } else {
+            Err(Error::Connection("Storage subscription ended".to_string()))
+        }
+    }
+
+    /// Set how [`rpc_call`](Self::rpc_call) retries calls that fail to
+    /// reach the node
+    pub fn with_rpc_retry(mut self, config: RetryConfig) -> Self {
+        self.rpc_retry = config;
+        self
+    }
+
+    /// Call RPC method `method` with positional `params` and decode its result
+    ///
+    /// An escape hatch for chain-specific methods the adapter does not wrap,
+    /// such as `babe_epochAuthorship` or a parachain's custom RPCs. The call
+    /// shares the adapter's connection and rate limit and is recorded in its
+    /// metrics. Calls that fail to reach the node are retried per
+    /// [`with_rpc_retry`](Self::with_rpc_retry); errors returned by the node
+    /// are not.
+    ///
+    /// ```rust,no_run
+    /// # use apex_sdk_substrate::SubstrateAdapter;
+    /// # async fn example(adapter: SubstrateAdapter) -> apex_sdk_substrate::Result<()> {
+    /// let health: serde_json::Value = adapter.rpc_call("system_health", vec![]).await?;
+    /// let authorship: serde_json::Value = adapter
+    ///     .rpc_call("babe_epochAuthorship", vec![])
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn rpc_call<T: serde::de::DeserializeOwned>(
+        &self,
+        method: &str,
+        params: Vec<serde_json::Value>,
+    ) -> Result<T> {
+        use subxt::ext::subxt_rpcs::{client::RpcParams, Error as RpcError};
+
+        let mut attempts = 0;
+        let mut delay = self.rpc_retry.initial_delay;
+        loop {
+            let mut rpc_params = RpcParams::new();
+            for param in &params {
+                rpc_params.push(param).map_err(|e| {
+                    Error::Encoding(format!("Invalid parameter for {}: {}", method, e))
+                })?;
+            }
+
+            attempts += 1;
+            let started = std::time::Instant::now();
+            let result = self.rpc.request::<T>(method, rpc_params).await;
+            self.metrics.record_rpc_call_time(method, started.elapsed());
+
+            match result {
+                Ok(value) => return Ok(value),
+                Err(e @ (RpcError::Client(_) | RpcError::DisconnectedWillReconnect(_)))
+                    if attempts <= self.rpc_retry.max_retries =>
+                {
+                    warn!(
+                        "{}. Retrying in {:?}",
+                        rpc_error(&format!("RPC call {} failed", method), e),
+                        delay
+                    );
+                    tokio::time::sleep(delay).await;
+                    delay = std::time::Duration::from_secs_f64(
+                        (delay.as_secs_f64() * self.rpc_retry.backoff_multiplier)
+                            .min(self.rpc_retry.max_delay.as_secs_f64()),
+                    );
+                }
+                Err(RpcError::User(e)) => {
+                    return Err(Error::Other(format!("{} rejected: {}", method, e)))
+                }
+                Err(RpcError::Deserialization(e)) => {
+                    return Err(Error::Encoding(format!(
+                        "Unexpected response from {}: {}",
+                        method, e
+                    )))
+                }
+                Err(e) => {
+                    return Err(Error::Connection(rpc_error(
+                        &format!("RPC call {} failed", method),
+                        e,
+                    )))
+                }
+            }
+        }
+    }
+
     /// Get reference to the subxt client
     pub fn client(&self) -> &OnlineClient<PolkadotConfig> {
         &self.client
@@ -203,9 +993,27 @@ impl SubstrateAdapter {
         &self.config
     }
 
-    /// Check if connected
+    /// Genesis block hash of the connected chain, as lowercase 0x-prefixed hex
+    pub fn genesis_hash(&self) -> String {
+        format!("0x{}", hex::encode(self.client.genesis_hash()))
+    }
+
+    /// Check that the endpoint serves `chain`, by genesis hash
+    ///
+    /// Passes for chains without a known genesis hash.
+    #[allow(clippy::result_large_err)]
+    pub fn verify_chain(&self, chain: &Chain) -> Result<()> {
+        chain
+            .verify_genesis_hash(&self.genesis_hash())
+            .map_err(Error::Connection)
+    }
+
+    /// Check if connected, to at least one healthy endpoint if pooled
     pub fn is_connected(&self) -> bool {
-        self.connected
+        match &self.pool {
+            Some(pool) => pool.stats().healthy_endpoints > 0,
+            None => self.connected,
+        }
     }
 
     /// Get metrics snapshot
@@ -214,6 +1022,9 @@ impl SubstrateAdapter {
     }
 
     /// Get transaction status by extrinsic hash
+    ///
+    /// With a [`TxIndex`] enabled, indexed extrinsics are answered from
+    /// memory. Otherwise, or on a miss, the last 100 blocks are searched.
     pub async fn get_transaction_status(&self, tx_hash: &str) -> Result<TransactionStatus> {
         if !self.connected {
             return Err(Error::Connection("Not connected".to_string()));
@@ -235,17 +1046,18 @@ impl SubstrateAdapter {
         let mut hash_array = [0u8; 32];
         hash_array.copy_from_slice(&hash_bytes);
 
-        // Try to subscribe to finalized blocks and check recent history
-        // Note: This is a simplified implementation that checks recent finalized blocks
-        // For production, consider maintaining a transaction pool and using event subscriptions
+        if let Some(status) = self.tx_index.as_ref().and_then(|i| i.status(&hash_array)) {
+            return Ok(status);
+        }
 
+        // Not indexed: search recent finalized blocks
         // Get the latest finalized block
         let latest_block = self
             .client
             .blocks()
             .at_latest()
             .await
-            .map_err(|e| Error::Connection(format!("Failed to get latest block: {}", e)))?;
+            .map_err(|e| Error::Connection(rpc_error("Failed to get latest block", e)))?;
 
         let latest_number = latest_block.number();
 
@@ -277,7 +1089,7 @@ impl SubstrateAdapter {
                 .blocks()
                 .at(block_hash)
                 .await
-                .map_err(|e| Error::Connection(format!("Failed to get block: {}", e)))?;
+                .map_err(|e| Error::Connection(rpc_error("Failed to get block", e)))?;
 
             // Get extrinsics from the block
             let extrinsics = block
@@ -293,47 +1105,20 @@ impl SubstrateAdapter {
                 let computed_hash = sp_core::blake2_256(ext_bytes);
 
                 if computed_hash == hash_array {
-                    // Found the transaction! Get the extrinsic index
-                    let ext_index = ext_details.index();
-
-                    // Check events for this extrinsic
-                    let events = ext_details
-                        .events()
-                        .await
-                        .map_err(|e| Error::Transaction(format!("Failed to get events: {}", e)))?;
-
-                    let mut success = false;
-                    let mut error_msg = None;
-
-                    for event in events.iter() {
-                        let event = event.map_err(|e| {
-                            Error::Transaction(format!("Failed to decode event: {}", e))
-                        })?;
-
-                        // Check for ExtrinsicSuccess or ExtrinsicFailed
-                        if event.pallet_name() == "System" {
-                            if event.variant_name() == "ExtrinsicSuccess" {
-                                success = true;
-                            } else if event.variant_name() == "ExtrinsicFailed" {
-                                // Try to extract error details from event
-                                error_msg = Some(format!("Extrinsic {} failed", ext_index));
-                            }
-                        }
-                    }
-
                     let confirmations = latest_number - block_num;
-
-                    return if success {
-                        Ok(TransactionStatus::Confirmed {
-                            block_number: block_num as u64,
-                            confirmations,
-                        })
-                    } else if let Some(error) = error_msg {
-                        Ok(TransactionStatus::Failed { error })
-                    } else {
-                        // Transaction found but status unclear
-                        Ok(TransactionStatus::Unknown)
-                    };
+                    return Ok(
+                        match tx_index::extrinsic_outcome(&ext_details, &self.client.metadata())
+                            .await?
+                        {
+                            ExtrinsicOutcome::Success => TransactionStatus::Confirmed {
+                                block_number: block_num as u64,
+                                confirmations,
+                            },
+                            ExtrinsicOutcome::Failed(error) => TransactionStatus::Failed { error },
+                            // Transaction found but status unclear
+                            ExtrinsicOutcome::Unknown => TransactionStatus::Unknown,
+                        },
+                    );
                 }
             }
         }
@@ -342,21 +1127,144 @@ impl SubstrateAdapter {
         Ok(TransactionStatus::Unknown)
     }
 
-    /// Validate a Substrate address (SS58 format)
-    pub fn validate_address(&self, address: &Address) -> bool {
-        match address {
-            Address::Substrate(addr) => {
-                // Use sp_core to validate SS58 address
-                use sp_core::crypto::Ss58Codec;
-                sp_core::sr25519::Public::from_ss58check(addr).is_ok()
-                    || sp_core::ed25519::Public::from_ss58check(addr).is_ok()
+    /// Get the receipt of extrinsic `tx_hash` from finalized block
+    /// `block_number`, with the events it emitted and its fee
+    ///
+    /// Returns `None` if the block does not contain the extrinsic.
+    pub async fn extrinsic_receipt(
+        &self,
+        tx_hash: &str,
+        block_number: u64,
+    ) -> Result<Option<ExtrinsicReceipt>> {
+        let rpc = LegacyRpcMethods::<PolkadotConfig>::new(self.rpc.clone());
+        let block_hash = rpc
+            .chain_get_block_hash(Some(block_number.into()))
+            .await
+            .map_err(|e| Error::Connection(rpc_error("Failed to get block hash", e)))?;
+        let Some(block_hash) = block_hash else {
+            return Ok(None);
+        };
+        let block = self
+            .client
+            .blocks()
+            .at(block_hash)
+            .await
+            .map_err(|e| Error::Connection(rpc_error("Failed to get block", e)))?;
+        let extrinsics = block
+            .extrinsics()
+            .await
+            .map_err(|e| Error::Connection(rpc_error("Failed to fetch extrinsics", e)))?;
+
+        let wanted = tx_hash.trim_start_matches("0x");
+        for ext in extrinsics.iter() {
+            let hash = format!("{:?}", ext.hash());
+            if !hash.trim_start_matches("0x").eq_ignore_ascii_case(wanted) {
+                continue;
             }
-            _ => false,
+            let events = ext
+                .events()
+                .await
+                .map_err(|e| Error::Transaction(format!("Failed to get events: {}", e)))?;
+            return Ok(Some(ExtrinsicReceipt::from_events(
+                hash,
+                format!("{:?}", block_hash),
+                block_number,
+                &events,
+                &self.client.metadata(),
+            )));
         }
+        Ok(None)
+    }
+
+    /// Get transaction status under `policy`
+    ///
+    /// Transactions are only found in finalized blocks, so `Finalized` is
+    /// always met; other policies report an included transaction as
+    /// `Pending` until they are satisfied.
+    pub async fn confirmation_status(
+        &self,
+        tx_hash: &str,
+        policy: &ConfirmationPolicy,
+    ) -> Result<TransactionStatus> {
+        let status = self.get_transaction_status(tx_hash).await?;
+        let TransactionStatus::Confirmed {
+            block_number,
+            confirmations,
+        } = status
+        else {
+            return Ok(status);
+        };
+
+        let age = match policy {
+            ConfirmationPolicy::Elapsed(_) => {
+                self.block_timestamp(block_number).await?.map(|timestamp| {
+                    let now = std::time::SystemTime::now()
+                        .duration_since(std::time::UNIX_EPOCH)
+                        .map(|d| d.as_secs())
+                        .unwrap_or_default();
+                    std::time::Duration::from_secs(now.saturating_sub(timestamp))
+                })
+            }
+            _ => None,
+        };
+        Ok(if policy.is_met(confirmations, true, age) {
+            status
+        } else {
+            TransactionStatus::Pending
+        })
+    }
+
+    /// Get the timestamp (seconds since the Unix epoch) of block `number`
+    /// from `Timestamp.Now`, `None` beyond the chain head
+    pub async fn block_timestamp(&self, number: u64) -> Result<Option<u64>> {
+        let rpc = LegacyRpcMethods::<PolkadotConfig>::new(self.rpc.clone());
+        let hash = rpc
+            .chain_get_block_hash(Some(number.into()))
+            .await
+            .map_err(|e| Error::Connection(rpc_error("Failed to get block hash", e)))?;
+        let Some(hash) = hash else {
+            return Ok(None);
+        };
+        let value = self
+            .client
+            .storage()
+            .at(hash)
+            .fetch(&subxt::dynamic::storage("Timestamp", "Now", vec![]))
+            .await
+            .map_err(|e| Error::Storage(format!("Failed to query storage: {}", e)))?;
+        let Some(value) = value else {
+            return Ok(None);
+        };
+        let millis = value
+            .to_value()
+            .map_err(|e| Error::Storage(format!("Failed to decode storage: {}", e)))?
+            .as_u128();
+        Ok(millis.map(|ms| (ms / 1000) as u64))
+    }
+
+    /// Validate a Substrate address for this chain
+    ///
+    /// SS58 addresses must use the chain's prefix, so an address meant for
+    /// another network is rejected. 0x-hex account IDs are accepted.
+    pub fn validate_address(&self, address: &Address) -> bool {
+        address
+            .validate_ss58_prefix(self.config.ss58_prefix)
+            .is_ok()
     }
 
     /// Get account balance using dynamic storage queries
     pub async fn get_balance(&self, address: &str) -> Result<u128> {
+        match &self.pool {
+            Some(pool) => {
+                pool.read(|adapter| Box::pin(async move { adapter.own_balance(address).await }))
+                    .await
+            }
+            None => self.own_balance(address).await,
+        }
+    }
+
+    /// [`get_balance`](Self::get_balance) over this adapter's own connection
+    async fn own_balance(&self, address: &str) -> Result<u128> {
         if !self.connected {
             return Err(Error::Connection("Not connected".to_string()));
         }
@@ -382,10 +1290,10 @@ impl SubstrateAdapter {
             .storage()
             .at_latest()
             .await
-            .map_err(|e| Error::Storage(format!("Failed to get latest block: {}", e)))?
+            .map_err(|e| storage_error("Failed to get latest block", e))?
             .fetch(&storage_query)
             .await
-            .map_err(|e| Error::Storage(format!("Failed to query storage: {}", e)))?;
+            .map_err(|e| storage_error("Failed to query storage", e))?;
 
         if let Some(account_data) = result {
             // Decode the storage value
@@ -412,6 +1320,189 @@ impl SubstrateAdapter {
         }
     }
 
+    /// Get the balance of `asset_id` in the `Assets` pallet held by `address`
+    pub async fn get_asset_balance(&self, asset_id: u32, address: &str) -> Result<u128> {
+        use sp_core::crypto::{AccountId32, Ss58Codec};
+        use subxt::dynamic::Value;
+
+        let account_id = AccountId32::from_ss58check(address)
+            .map_err(|e| Error::Storage(format!("Invalid SS58 address: {}", e)))?;
+        self.metrics.record_rpc_call("get_asset_balance");
+
+        let account_bytes: &[u8] = account_id.as_ref();
+        let keys = vec![
+            Value::u128(asset_id.into()),
+            Value::from_bytes(account_bytes),
+        ];
+        self.fetch_u128_field("Assets", "Account", keys, "balance")
+            .await
+    }
+
+    /// Get the decimals of `asset_id` from its `Assets` pallet metadata, 0 if
+    /// none is set
+    pub async fn get_asset_decimals(&self, asset_id: u32) -> Result<u8> {
+        self.metrics.record_rpc_call("get_asset_decimals");
+        let keys = vec![subxt::dynamic::Value::u128(asset_id.into())];
+        let decimals = self
+            .fetch_u128_field("Assets", "Metadata", keys, "decimals")
+            .await?;
+        u8::try_from(decimals)
+            .map_err(|_| Error::Storage(format!("Invalid decimals for asset {}", asset_id)))
+    }
+
+    /// Get the actively bonded `Staking` balance of the stash `address`
+    pub async fn get_staked_balance(&self, address: &str) -> Result<u128> {
+        use sp_core::crypto::{AccountId32, Ss58Codec};
+        use subxt::dynamic::Value;
+
+        let account_id = AccountId32::from_ss58check(address)
+            .map_err(|e| Error::Storage(format!("Invalid SS58 address: {}", e)))?;
+        self.metrics.record_rpc_call("get_staked_balance");
+
+        let account_bytes: &[u8] = account_id.as_ref();
+        let keys = vec![Value::from_bytes(account_bytes)];
+        self.fetch_u128_field("Staking", "Ledger", keys, "active")
+            .await
+    }
+
+    /// Read the `field` of a storage entry, 0 if the entry is empty
+    async fn fetch_u128_field(
+        &self,
+        pallet: &str,
+        item: &str,
+        keys: Vec<subxt::dynamic::Value>,
+        field: &str,
+    ) -> Result<u128> {
+        match &self.pool {
+            Some(pool) => {
+                pool.read(|adapter| {
+                    let keys = keys.clone();
+                    Box::pin(async move {
+                        adapter
+                            .fetch_own_u128_field(pallet, item, keys, field)
+                            .await
+                    })
+                })
+                .await
+            }
+            None => self.fetch_own_u128_field(pallet, item, keys, field).await,
+        }
+    }
+
+    /// [`fetch_u128_field`](Self::fetch_u128_field) over this adapter's own
+    /// connection
+    async fn fetch_own_u128_field(
+        &self,
+        pallet: &str,
+        item: &str,
+        keys: Vec<subxt::dynamic::Value>,
+        field: &str,
+    ) -> Result<u128> {
+        use subxt::dynamic::At as _;
+
+        let address = subxt::dynamic::storage(pallet, item, keys);
+        let value = self
+            .client
+            .storage()
+            .at_latest()
+            .await
+            .map_err(|e| storage_error("Failed to get latest block", e))?
+            .fetch(&address)
+            .await
+            .map_err(|e| storage_error("Failed to query storage", e))?;
+
+        match value {
+            Some(value) => {
+                let decoded = value
+                    .to_value()
+                    .map_err(|e| Error::Storage(format!("Failed to decode storage: {}", e)))?;
+                Ok(decoded
+                    .at(field)
+                    .and_then(|value| value.as_u128())
+                    .unwrap_or(0))
+            }
+            None => Ok(0),
+        }
+    }
+
+    /// Submit an already-signed, SCALE-encoded extrinsic and return its hash
+    ///
+    /// Returns as soon as the node accepts the extrinsic into its pool; use
+    /// [`get_transaction_status`](Self::get_transaction_status) to follow it.
+    pub async fn submit_raw_extrinsic(&self, extrinsic: Vec<u8>) -> Result<String> {
+        match &self.pool {
+            Some(pool) => {
+                pool.pinned(|adapter| {
+                    let extrinsic = extrinsic.clone();
+                    Box::pin(async move { adapter.submit_own_extrinsic(extrinsic).await })
+                })
+                .await
+            }
+            None => self.submit_own_extrinsic(extrinsic).await,
+        }
+    }
+
+    /// Broadcast an extrinsic signed offline, e.g. one assembled with
+    /// [`OfflineSubstrateAdapter::assemble`]
+    ///
+    /// Rejects unsigned extrinsics before they reach the node, then behaves
+    /// like [`submit_raw_extrinsic`](Self::submit_raw_extrinsic).
+    pub async fn submit_signed(&self, extrinsic: &[u8]) -> Result<String> {
+        if !is_signed_extrinsic(extrinsic) {
+            return Err(Error::Transaction("Extrinsic is not signed".to_string()));
+        }
+        self.submit_raw_extrinsic(extrinsic.to_vec()).await
+    }
+
+    /// [`submit_raw_extrinsic`](Self::submit_raw_extrinsic) over this
+    /// adapter's own connection
+    async fn submit_own_extrinsic(&self, extrinsic: Vec<u8>) -> Result<String> {
+        if !self.connected {
+            return Err(Error::Connection("Not connected".to_string()));
+        }
+
+        debug!("Submitting raw extrinsic ({} bytes)", extrinsic.len());
+        self.metrics.record_rpc_call("submit_raw_extrinsic");
+
+        let hash = subxt::tx::SubmittableTransaction::from_bytes(self.client.clone(), extrinsic)
+            .submit()
+            .await
+            .map_err(|e| {
+                if is_unreachable(&e) {
+                    Error::Connection(rpc_error("Failed to submit extrinsic", e))
+                } else {
+                    Error::Transaction(format!("Failed to submit extrinsic: {}", e))
+                }
+            })?;
+
+        Ok(format!("0x{}", hex::encode(hash)))
+    }
+
+    /// Export the connected chain's metadata and properties as a descriptor
+    ///
+    /// The metadata is fetched with `state_getMetadata` and embedded as the
+    /// node returned it. See [`ChainDescriptor`] for loading it back offline.
+    pub async fn export_descriptor(&self) -> Result<ChainDescriptor> {
+        let rpc = LegacyRpcMethods::<PolkadotConfig>::new(self.rpc.clone());
+        let metadata = rpc
+            .state_get_metadata(None)
+            .await
+            .map_err(|e| Error::Connection(rpc_error("Failed to fetch metadata", e)))?;
+        ChainDescriptor::from_metadata(
+            self.client.genesis_hash(),
+            self.client.runtime_version(),
+            &metadata.into_raw(),
+            &self.config,
+        )
+    }
+
+    /// Get the free balance of an address as an [`Amount`] of the chain's
+    /// token
+    pub async fn get_balance_amount(&self, address: &str) -> Result<Amount> {
+        let value = self.get_balance(address).await?;
+        Ok(Amount::new(value, self.config.token_decimals).with_symbol(&self.config.token_symbol))
+    }
+
     /// Get formatted balance (with decimals)
     pub async fn get_balance_formatted(&self, address: &str) -> Result<String> {
         let balance = self.get_balance(address).await?;
@@ -439,12 +1530,71 @@ impl SubstrateAdapter {
 
     /// Create a storage client for querying chain storage
     pub fn storage(&self) -> StorageClient {
-        StorageClient::new(self.client.clone(), self.metrics.clone())
+        let (client, rpc) = self.read_connection();
+        StorageClient::new(client, self.metrics.clone()).with_rpc(rpc)
     }
 
     /// Create a transaction executor
     pub fn transaction_executor(&self) -> TransactionExecutor {
-        TransactionExecutor::new(self.client.clone(), self.metrics.clone())
+        TransactionExecutor::new(self.submit_client(), self.metrics.clone())
+    }
+
+    /// Create an XCM executor for cross-chain transfers
+    pub fn xcm_executor(&self) -> XcmExecutor {
+        XcmExecutor::new(self.submit_client())
+    }
+
+    /// Create a client for `Assets` pallet balances, metadata and admin calls
+    pub fn assets(&self) -> Assets {
+        Assets::new(self.submit_client(), self.metrics.clone())
+    }
+
+    /// Create a client for the NFTs of `pallet`
+    pub fn nfts(&self, pallet: NftPallet) -> Nfts {
+        Nfts::new(self.submit_client(), self.metrics.clone(), pallet)
+    }
+
+    /// The PSP22 token contract at `address`
+    #[allow(clippy::result_large_err)]
+    pub fn psp22(&self, address: &str) -> Result<Psp22> {
+        Ok(Psp22::new(self.submit_client(), account_id(address)?))
+    }
+
+    /// The PSP34 collection contract at `address`
+    #[allow(clippy::result_large_err)]
+    pub fn psp34(&self, address: &str) -> Result<Psp34> {
+        Ok(Psp34::new(self.submit_client(), account_id(address)?))
+    }
+
+    /// Create a client for registering proxies and submitting calls as one
+    pub fn proxy(&self) -> Proxy {
+        Proxy::new(self.submit_client(), self.metrics.clone())
+    }
+
+    /// Create a client for the multisig of `signatories` with `threshold`
+    #[allow(clippy::result_large_err)]
+    pub fn multisig(&self, signatories: &[&str], threshold: u16) -> Result<Multisig> {
+        Multisig::new(
+            self.submit_client(),
+            self.metrics.clone(),
+            signatories,
+            threshold,
+        )
+    }
+
+    /// Create an Asset Conversion client for swapping assets
+    pub fn asset_conversion(&self) -> AssetConversion {
+        AssetConversion::new(self.submit_client(), self.metrics.clone())
+    }
+
+    /// Create an OpenGov client for referenda and voting
+    pub fn open_gov(&self) -> OpenGov {
+        OpenGov::new(self.submit_client(), self.metrics.clone())
+    }
+
+    /// Create a client for staking calls, ledgers and rewards
+    pub fn staking(&self) -> Staking {
+        Staking::new(self.submit_client(), self.metrics.clone())
     }
 
     /// Get runtime version
@@ -458,15 +1608,32 @@ impl SubstrateAdapter {
     }
 }
 
+impl From<Error> for AdapterError {
+    fn from(e: Error) -> Self {
+        match e {
+            Error::Connection(message) => AdapterError::Connection(message),
+            Error::Transaction(message) => AdapterError::Transaction(message),
+            Error::Metadata(message) => AdapterError::Metadata(message),
+            Error::Storage(message) => AdapterError::Storage(message),
+            Error::Wallet(message) => AdapterError::Wallet(message),
+            Error::Signature(message) => AdapterError::Signature(message),
+            Error::Encoding(message) => AdapterError::Encoding(message),
+            Error::Subxt(e) if is_unreachable(&e) => {
+                AdapterError::Connection(redact_urls(&e.to_string()))
+            }
+            Error::Subxt(e) => AdapterError::Other(redact_urls(&e.to_string())),
+            Error::Other(message) => AdapterError::Other(message),
+        }
+    }
+}
+
 #[async_trait]
 impl apex_sdk_core::ChainAdapter for SubstrateAdapter {
     async fn get_transaction_status(
         &self,
         tx_hash: &str,
-    ) -> std::result::Result<TransactionStatus, String> {
-        self.get_transaction_status(tx_hash)
-            .await
-            .map_err(|e| e.to_string())
+    ) -> std::result::Result<TransactionStatus, AdapterError> {
+        Ok(self.get_transaction_status(tx_hash).await?)
     }
 
     fn validate_address(&self, address: &Address) -> bool {
@@ -476,6 +1643,30 @@ impl apex_sdk_core::ChainAdapter for SubstrateAdapter {
     fn chain_name(&self) -> &str {
         self.chain_name()
     }
+
+    async fn get_balance(&self, address: &Address) -> std::result::Result<u128, AdapterError> {
+        Ok(self.get_balance(address.as_str()).await?)
+    }
+
+    async fn get_block(
+        &self,
+        block: BlockRef,
+    ) -> std::result::Result<Option<BlockInfo>, AdapterError> {
+        Ok(self.get_block_info(block).await?)
+    }
+
+    /// The latest finalized block; Substrate transactions are only looked up
+    /// in finalized blocks
+    async fn latest_block(&self) -> std::result::Result<BlockInfo, AdapterError> {
+        let number = self.finalized_block_number().await?;
+        self.get_block_info(BlockRef::Number(number))
+            .await?
+            .ok_or_else(|| AdapterError::NotFound(format!("Finalized block #{}", number)))
+    }
+
+    async fn submit_transaction(&self, tx: &[u8]) -> std::result::Result<String, AdapterError> {
+        Ok(self.submit_raw_extrinsic(tx.to_vec()).await?)
+    }
 }
 
 #[cfg(test)]
@@ -512,6 +1703,35 @@ mod tests {
         assert!(adapter.is_ok());
     }
 
+    #[tokio::test]
+    #[ignore] // Requires network connection
+    async fn test_rpc_call() {
+        let adapter = SubstrateAdapter::connect_with_config(ChainConfig::polkadot())
+            .await
+            .unwrap();
+        let chain: String = adapter.rpc_call("system_chain", vec![]).await.unwrap();
+        assert_eq!(chain, "Polkadot");
+        assert!(adapter
+            .rpc_call::<String>("no_suchMethod", vec![])
+            .await
+            .is_err());
+        assert_eq!(
+            adapter.metrics().rpc_calls_by_method.get("system_chain"),
+            Some(&1)
+        );
+    }
+
+    #[test]
+    fn test_is_signed_extrinsic() {
+        // Length 3, signed v4 version byte
+        assert!(is_signed_extrinsic(&[0x0c, 0x84, 0x00, 0x01]));
+        // Unsigned (bare) v4
+        assert!(!is_signed_extrinsic(&[0x0c, 0x04, 0x00, 0x01]));
+        // Length prefix disagrees with the payload
+        assert!(!is_signed_extrinsic(&[0x10, 0x84, 0x00, 0x01]));
+        assert!(!is_signed_extrinsic(&[]));
+    }
+
     #[test]
     fn test_address_validation() {
         // We'll need a connected adapter for proper validation
@@ -0,0 +1,478 @@
+//! `pallet-multisig` accounts and approvals
+//!
+//! A multisig account is derived from its sorted signatories and threshold;
+//! it has no key. A call is dispatched from it once `threshold` signatories
+//! approved it: the first approval opens the operation at a timepoint
+//! (block height and extrinsic index) and reserves a deposit, later ones
+//! must quote that timepoint, and the last one carries the full call.
+//!
+//! [`Multisig`] derives the account, hashes calls, looks up timepoints of
+//! open operations and lists their approvals.
+//!
+//! ```rust,no_run
+//! use apex_sdk_substrate::{SubstrateAdapter, Wallet};
+//! use subxt::dynamic::Value;
+//!
+//! # async fn example(adapter: SubstrateAdapter, alice: Wallet, bob: Wallet) -> apex_sdk_substrate::Result<()> {
+//! let charlie = "5FLSigC9HGRKVhB9FiEo4Y3koPsNmBmLJbpXg2mp1hXcS59Y";
+//! let multisig = adapter.multisig(&[&alice.address(), &bob.address(), charlie], 2)?;
+//!
+//! let call = || {
+//!     subxt::dynamic::tx(
+//!         "System",
+//!         "remark",
+//!         vec![Value::from_bytes(b"paid by the multisig")],
+//!     )
+//! };
+//! let call_hash = multisig.call_hash(&call())?;
+//!
+//! // First approval opens the operation, the second dispatches the call
+//! multisig.approve_as_multi(&alice, call_hash).await?;
+//! multisig.as_multi(&bob, call()).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{
+    account_id, collect_bytes, Error, ExtrinsicReceipt, Metrics, Result, TransactionExecutor,
+    Wallet,
+};
+use parity_scale_codec::Encode;
+use sp_core::crypto::{AccountId32, Ss58Codec};
+use subxt::dynamic::{At as _, Value};
+use subxt::ext::scale_value::{self, ValueDef};
+use subxt::tx::DynamicPayload;
+use subxt::{OnlineClient, PolkadotConfig};
+use tracing::info;
+
+/// Prefix hashed with the signatories and threshold into a multisig account
+const MULTISIG_ENTROPY_PREFIX: &[u8; 16] = b"modlpy/utilisuba";
+
+/// Most weight the final approval may spend dispatching the call by default:
+/// `(ref_time, proof_size)`
+pub const DEFAULT_MULTISIG_MAX_WEIGHT: (u64, u64) = (10_000_000_000, 1_000_000);
+
+/// Block height and extrinsic index of the first approval of an operation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timepoint {
+    /// Block number
+    pub height: u32,
+    /// Index of the extrinsic in the block
+    pub index: u32,
+}
+
+/// A multisig operation awaiting approvals
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingMultisig {
+    /// Hash of the call being approved
+    pub call_hash: [u8; 32],
+    /// First approval, quoted by later approvals and cancellation
+    pub when: Timepoint,
+    /// Deposit reserved from the depositor
+    pub deposit: u128,
+    /// Signatory that opened the operation (SS58)
+    pub depositor: String,
+    /// Signatories that approved so far (SS58)
+    pub approvals: Vec<String>,
+}
+
+/// Address of the multisig account of `signatories` with `threshold`
+///
+/// The order of `signatories` does not matter.
+#[allow(clippy::result_large_err)]
+pub fn multisig_account(signatories: &[&str], threshold: u16) -> Result<String> {
+    let signatories = sorted_signatories(signatories)?;
+    Ok(AccountId32::from(derive_account(&signatories, threshold)).to_ss58check())
+}
+
+fn derive_account(sorted: &[[u8; 32]], threshold: u16) -> [u8; 32] {
+    let entropy = (MULTISIG_ENTROPY_PREFIX, sorted, threshold).encode();
+    sp_core::blake2_256(&entropy)
+}
+
+#[allow(clippy::result_large_err)]
+fn sorted_signatories(signatories: &[&str]) -> Result<Vec<[u8; 32]>> {
+    let mut accounts = signatories
+        .iter()
+        .map(|address| account_id(address))
+        .collect::<Result<Vec<_>>>()?;
+    accounts.sort();
+    accounts.dedup();
+    Ok(accounts)
+}
+
+/// Client for one multisig account
+pub struct Multisig {
+    client: OnlineClient<PolkadotConfig>,
+    metrics: Metrics,
+    signatories: Vec<[u8; 32]>,
+    threshold: u16,
+    account: [u8; 32],
+    max_weight: (u64, u64),
+}
+
+impl Multisig {
+    /// Create a client for the multisig of `signatories` with `threshold`
+    ///
+    /// Fails unless `threshold` is between 2 and the number of distinct
+    /// signatories.
+    #[allow(clippy::result_large_err)]
+    pub fn new(
+        client: OnlineClient<PolkadotConfig>,
+        metrics: Metrics,
+        signatories: &[&str],
+        threshold: u16,
+    ) -> Result<Self> {
+        let signatories = sorted_signatories(signatories)?;
+        if threshold < 2 || threshold as usize > signatories.len() {
+            return Err(Error::Transaction(format!(
+                "Invalid multisig threshold {} for {} signatories",
+                threshold,
+                signatories.len()
+            )));
+        }
+        Ok(Self {
+            client,
+            metrics,
+            account: derive_account(&signatories, threshold),
+            signatories,
+            threshold,
+            max_weight: DEFAULT_MULTISIG_MAX_WEIGHT,
+        })
+    }
+
+    /// Set the most weight the final approval may spend on the call
+    ///
+    /// The final approval pays fees for this weight; the unused part is
+    /// refunded.
+    pub fn with_max_weight(mut self, ref_time: u64, proof_size: u64) -> Self {
+        self.max_weight = (ref_time, proof_size);
+        self
+    }
+
+    /// SS58 address of the multisig account
+    pub fn address(&self) -> String {
+        AccountId32::from(self.account).to_ss58check()
+    }
+
+    /// Approvals needed to dispatch a call
+    pub fn threshold(&self) -> u16 {
+        self.threshold
+    }
+
+    /// Hash identifying `call` in approvals
+    #[allow(clippy::result_large_err)]
+    pub fn call_hash(&self, call: &DynamicPayload) -> Result<[u8; 32]> {
+        let call_data = self
+            .client
+            .tx()
+            .call_data(call)
+            .map_err(|e| Error::Encoding(format!("Failed to encode call: {}", e)))?;
+        Ok(sp_core::blake2_256(&call_data))
+    }
+
+    /// Open operation for `call_hash`, `None` if there is none
+    pub async fn pending(&self, call_hash: [u8; 32]) -> Result<Option<PendingMultisig>> {
+        self.metrics.record_storage_query();
+        let query = subxt::dynamic::storage(
+            "Multisig",
+            "Multisigs",
+            vec![
+                Value::from_bytes(self.account),
+                Value::from_bytes(call_hash),
+            ],
+        );
+        let value = self
+            .client
+            .storage()
+            .at_latest()
+            .await
+            .map_err(|e| Error::Connection(format!("Failed to fetch latest block: {}", e)))?
+            .fetch(&query)
+            .await
+            .map_err(|e| Error::Storage(format!("Failed to query multisig: {}", e)))?;
+
+        let Some(value) = value else {
+            return Ok(None);
+        };
+        let value = value
+            .to_value()
+            .map_err(|e| Error::Storage(format!("Failed to decode multisig: {}", e)))?;
+        parse_multisig(call_hash, &value)
+            .map(Some)
+            .ok_or_else(|| Error::Storage("Malformed multisig".to_string()))
+    }
+
+    /// Every open operation of the multisig account
+    pub async fn pending_calls(&self) -> Result<Vec<PendingMultisig>> {
+        self.metrics.record_storage_query();
+        let query = subxt::dynamic::storage(
+            "Multisig",
+            "Multisigs",
+            vec![Value::from_bytes(self.account)],
+        );
+        let storage = self
+            .client
+            .storage()
+            .at_latest()
+            .await
+            .map_err(|e| Error::Connection(format!("Failed to fetch latest block: {}", e)))?;
+        let mut iter = storage
+            .iter(query)
+            .await
+            .map_err(|e| Error::Storage(format!("Failed to iterate multisigs: {}", e)))?;
+
+        let mut pending = Vec::new();
+        while let Some(entry) = iter.next().await {
+            let entry =
+                entry.map_err(|e| Error::Storage(format!("Failed to fetch multisig: {}", e)))?;
+            // Keys end in the Blake2_128Concat-hashed call hash, stored in full
+            let call_hash = entry
+                .key_bytes
+                .last_chunk::<32>()
+                .copied()
+                .ok_or_else(|| Error::Storage("Malformed multisig key".to_string()))?;
+            let value = entry
+                .value
+                .to_value()
+                .map_err(|e| Error::Storage(format!("Failed to decode multisig: {}", e)))?;
+            pending.push(
+                parse_multisig(call_hash, &value)
+                    .ok_or_else(|| Error::Storage("Malformed multisig".to_string()))?,
+            );
+        }
+        Ok(pending)
+    }
+
+    /// Approve the call with `call_hash` from `wallet`, opening the operation
+    /// if it is the first approval
+    ///
+    /// Cannot be the final approval; use [`as_multi`](Self::as_multi) for it.
+    pub async fn approve_as_multi(
+        &self,
+        wallet: &Wallet,
+        call_hash: [u8; 32],
+    ) -> Result<ExtrinsicReceipt> {
+        let others = self.other_signatories(wallet)?;
+        let timepoint = self.timepoint(call_hash).await?;
+        info!(
+            "Approving call 0x{} for multisig {} from {}",
+            hex::encode(call_hash),
+            self.address(),
+            wallet.address()
+        );
+        self.submit(
+            wallet,
+            "approve_as_multi",
+            vec![
+                Value::u128(self.threshold as u128),
+                others,
+                option(timepoint),
+                Value::from_bytes(call_hash),
+                weight((0, 0)),
+            ],
+        )
+        .await
+    }
+
+    /// Approve `call` from `wallet`, dispatching it from the multisig
+    /// account if this is the final approval
+    pub async fn as_multi(
+        &self,
+        wallet: &Wallet,
+        call: DynamicPayload,
+    ) -> Result<ExtrinsicReceipt> {
+        let others = self.other_signatories(wallet)?;
+        let call_hash = self.call_hash(&call)?;
+        let timepoint = self.timepoint(call_hash).await?;
+        info!(
+            "Approving call 0x{} with data for multisig {} from {}",
+            hex::encode(call_hash),
+            self.address(),
+            wallet.address()
+        );
+        self.submit(
+            wallet,
+            "as_multi",
+            vec![
+                Value::u128(self.threshold as u128),
+                others,
+                option(timepoint),
+                call.into_value(),
+                weight(self.max_weight),
+            ],
+        )
+        .await
+    }
+
+    /// Cancel the open operation for `call_hash`, returning the deposit
+    ///
+    /// Only the depositor can cancel.
+    pub async fn cancel_as_multi(
+        &self,
+        wallet: &Wallet,
+        call_hash: [u8; 32],
+    ) -> Result<ExtrinsicReceipt> {
+        let others = self.other_signatories(wallet)?;
+        let timepoint = self.timepoint(call_hash).await?.ok_or_else(|| {
+            Error::Transaction(format!(
+                "No open multisig operation for call 0x{}",
+                hex::encode(call_hash)
+            ))
+        })?;
+        self.submit(
+            wallet,
+            "cancel_as_multi",
+            vec![
+                Value::u128(self.threshold as u128),
+                others,
+                timepoint_value(timepoint),
+                Value::from_bytes(call_hash),
+            ],
+        )
+        .await
+    }
+
+    /// Timepoint of the open operation for `call_hash`
+    async fn timepoint(&self, call_hash: [u8; 32]) -> Result<Option<Timepoint>> {
+        Ok(self.pending(call_hash).await?.map(|pending| pending.when))
+    }
+
+    /// The sorted signatories other than `wallet`, as the pallet expects
+    #[allow(clippy::result_large_err)]
+    fn other_signatories(&self, wallet: &Wallet) -> Result<Value> {
+        let signer = account_id(&wallet.address())?;
+        if !self.signatories.contains(&signer) {
+            return Err(Error::Transaction(format!(
+                "{} is not a signatory of multisig {}",
+                wallet.address(),
+                self.address()
+            )));
+        }
+        Ok(Value::unnamed_composite(
+            self.signatories
+                .iter()
+                .filter(|account| **account != signer)
+                .map(Value::from_bytes),
+        ))
+    }
+
+    async fn submit(
+        &self,
+        wallet: &Wallet,
+        call: &str,
+        fields: Vec<Value>,
+    ) -> Result<ExtrinsicReceipt> {
+        let call = subxt::dynamic::tx("Multisig", call, fields);
+        TransactionExecutor::new(self.client.clone(), self.metrics.clone())
+            .submit_typed(&call, wallet)
+            .await
+    }
+}
+
+fn timepoint_value(timepoint: Timepoint) -> Value {
+    Value::named_composite([
+        ("height", Value::u128(timepoint.height as u128)),
+        ("index", Value::u128(timepoint.index as u128)),
+    ])
+}
+
+fn option(timepoint: Option<Timepoint>) -> Value {
+    match timepoint {
+        Some(timepoint) => Value::unnamed_variant("Some", [timepoint_value(timepoint)]),
+        None => Value::unnamed_variant("None", []),
+    }
+}
+
+fn weight((ref_time, proof_size): (u64, u64)) -> Value {
+    Value::named_composite([
+        ("ref_time", Value::u128(ref_time as u128)),
+        ("proof_size", Value::u128(proof_size as u128)),
+    ])
+}
+
+/// Read a decoded `Multisig` storage value
+fn parse_multisig<T>(
+    call_hash: [u8; 32],
+    value: &scale_value::Value<T>,
+) -> Option<PendingMultisig> {
+    let address = |value: &scale_value::Value<T>| {
+        let mut bytes = Vec::new();
+        collect_bytes(value, &mut bytes);
+        let bytes: [u8; 32] = bytes.try_into().ok()?;
+        Some(AccountId32::from(bytes).to_ss58check())
+    };
+    let when = value.at("when")?;
+    let approvals = match &value.at("approvals")?.value {
+        ValueDef::Composite(approvals) => approvals
+            .values()
+            .map(address)
+            .collect::<Option<Vec<_>>>()?,
+        _ => return None,
+    };
+    Some(PendingMultisig {
+        call_hash,
+        when: Timepoint {
+            height: when.at("height")?.as_u128()? as u32,
+            index: when.at("index")?.as_u128()? as u32,
+        },
+        deposit: value.at("deposit")?.as_u128()?,
+        depositor: address(value.at("depositor")?)?,
+        approvals,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALICE: &str = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+    const BOB: &str = "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty";
+    const CHARLIE: &str = "5FLSigC9HGRKVhB9FiEo4Y3koPsNmBmLJbpXg2mp1hXcS59Y";
+
+    #[test]
+    fn test_multisig_account() {
+        let expected = "5DjYJStmdZ2rcqXbXGX7TW85JsrW6uG4y9MUcLq2BoPMpRA7";
+        assert_eq!(
+            multisig_account(&[ALICE, BOB, CHARLIE], 2).unwrap(),
+            expected
+        );
+        // Order and duplicates do not matter
+        assert_eq!(
+            multisig_account(&[CHARLIE, ALICE, BOB, ALICE], 2).unwrap(),
+            expected
+        );
+        assert_ne!(
+            multisig_account(&[ALICE, BOB, CHARLIE], 3).unwrap(),
+            expected
+        );
+        assert!(multisig_account(&[ALICE, "not an address"], 2).is_err());
+    }
+
+    #[test]
+    fn test_parse_multisig() {
+        let value = Value::named_composite([
+            (
+                "when",
+                Value::named_composite([("height", Value::u128(100)), ("index", Value::u128(2))]),
+            ),
+            ("deposit", Value::u128(5_000)),
+            ("depositor", Value::from_bytes(account_id(ALICE).unwrap())),
+            (
+                "approvals",
+                Value::unnamed_composite([Value::from_bytes(account_id(ALICE).unwrap())]),
+            ),
+        ]);
+        let pending = parse_multisig([7; 32], &value).unwrap();
+        assert_eq!(
+            pending.when,
+            Timepoint {
+                height: 100,
+                index: 2
+            }
+        );
+        assert_eq!(pending.deposit, 5_000);
+        assert_eq!(pending.depositor, ALICE);
+        assert_eq!(pending.approvals, vec![ALICE.to_string()]);
+    }
+}
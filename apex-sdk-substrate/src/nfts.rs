@@ -0,0 +1,315 @@
+//! `pallet-nfts` and `pallet-uniques` clients
+//!
+//! Both pallets hold non-fungible items identified by a `u32` collection ID
+//! and a `u32` item ID, as on Asset Hub. `pallet-nfts` superseded
+//! `pallet-uniques`; the call and storage names differ, and [`Nfts`] picks
+//! them by [`NftPallet`].
+
+use crate::{
+    collect_bytes, multi_address, Error, ExtrinsicReceipt, Metrics, Result, TransactionExecutor,
+    Wallet,
+};
+use sp_core::crypto::{AccountId32, Ss58Codec};
+use subxt::dynamic::{At as _, Value};
+use subxt::ext::scale_value;
+use subxt::{OnlineClient, PolkadotConfig};
+use tracing::info;
+
+/// NFT pallet a client talks to
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum NftPallet {
+    /// `pallet-nfts`
+    #[default]
+    Nfts,
+    /// `pallet-uniques`
+    Uniques,
+}
+
+impl NftPallet {
+    /// Pallet name in the runtime
+    pub fn name(self) -> &'static str {
+        match self {
+            NftPallet::Nfts => "Nfts",
+            NftPallet::Uniques => "Uniques",
+        }
+    }
+
+    fn collection_storage(self) -> &'static str {
+        match self {
+            NftPallet::Nfts => "Collection",
+            NftPallet::Uniques => "Class",
+        }
+    }
+
+    fn item_storage(self) -> &'static str {
+        match self {
+            NftPallet::Nfts => "Item",
+            NftPallet::Uniques => "Asset",
+        }
+    }
+
+    fn metadata_storage(self) -> &'static str {
+        match self {
+            NftPallet::Nfts => "ItemMetadataOf",
+            NftPallet::Uniques => "InstanceMetadataOf",
+        }
+    }
+}
+
+/// Owner and item counts of a collection
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NftCollection {
+    /// Collection owner (SS58)
+    pub owner: String,
+    /// Items in the collection
+    pub items: u32,
+    /// Items with metadata set
+    pub item_metadatas: u32,
+}
+
+/// Client for `pallet-nfts` or `pallet-uniques`
+pub struct Nfts {
+    client: OnlineClient<PolkadotConfig>,
+    metrics: Metrics,
+    pallet: NftPallet,
+}
+
+impl Nfts {
+    /// Create a client for `pallet` on the chain behind `client`
+    pub fn new(client: OnlineClient<PolkadotConfig>, metrics: Metrics, pallet: NftPallet) -> Self {
+        Self {
+            client,
+            metrics,
+            pallet,
+        }
+    }
+
+    /// Pallet the client talks to
+    pub fn pallet(&self) -> NftPallet {
+        self.pallet
+    }
+
+    /// Details of `collection`, `None` if it does not exist
+    pub async fn collection(&self, collection: u32) -> Result<Option<NftCollection>> {
+        let Some(value) = self
+            .fetch(
+                self.pallet.collection_storage(),
+                vec![Value::u128(collection.into())],
+            )
+            .await?
+        else {
+            return Ok(None);
+        };
+        parse_collection(&value)
+            .map(Some)
+            .ok_or_else(|| Error::Storage(format!("Malformed collection {}", collection)))
+    }
+
+    /// Owner of `item` in `collection`, `None` if it does not exist
+    #[allow(clippy::result_large_err)]
+    pub async fn owner_of(&self, collection: u32, item: u32) -> Result<Option<String>> {
+        let value = self
+            .fetch(self.pallet.item_storage(), item_keys(collection, item))
+            .await?;
+        value
+            .map(|value| {
+                value.at("owner").and_then(address).ok_or_else(|| {
+                    Error::Storage(format!("Malformed item {}/{}", collection, item))
+                })
+            })
+            .transpose()
+    }
+
+    /// Metadata of `item` in `collection`, usually a URI, `None` if none was
+    /// set
+    pub async fn item_metadata(&self, collection: u32, item: u32) -> Result<Option<String>> {
+        let value = self
+            .fetch(self.pallet.metadata_storage(), item_keys(collection, item))
+            .await?;
+        Ok(value.as_ref().and_then(|value| value.at("data")).map(text))
+    }
+
+    /// Mint `item` in `collection` to `owner`, as the collection's issuer
+    pub async fn mint(
+        &self,
+        wallet: &Wallet,
+        collection: u32,
+        item: u32,
+        owner: &str,
+    ) -> Result<ExtrinsicReceipt> {
+        info!("Minting item {}/{} to {}", collection, item, owner);
+        let mut fields = vec![
+            Value::u128(collection.into()),
+            Value::u128(item.into()),
+            multi_address(owner)?,
+        ];
+        if self.pallet == NftPallet::Nfts {
+            // No witness: minting as the issuer
+            fields.push(Value::unnamed_variant("None", []));
+        }
+        self.submit(wallet, "mint", fields).await
+    }
+
+    /// Transfer `item` in `collection` from `wallet` to `dest`
+    pub async fn transfer(
+        &self,
+        wallet: &Wallet,
+        collection: u32,
+        item: u32,
+        dest: &str,
+    ) -> Result<ExtrinsicReceipt> {
+        info!(
+            "Transferring item {}/{} from {} to {}",
+            collection,
+            item,
+            wallet.address(),
+            dest
+        );
+        self.submit(
+            wallet,
+            "transfer",
+            vec![
+                Value::u128(collection.into()),
+                Value::u128(item.into()),
+                multi_address(dest)?,
+            ],
+        )
+        .await
+    }
+
+    /// Set the metadata of `item` in `collection`, as the collection's admin
+    pub async fn set_metadata(
+        &self,
+        wallet: &Wallet,
+        collection: u32,
+        item: u32,
+        data: &str,
+    ) -> Result<ExtrinsicReceipt> {
+        let mut fields = vec![
+            Value::u128(collection.into()),
+            Value::u128(item.into()),
+            Value::from_bytes(data.as_bytes()),
+        ];
+        if self.pallet == NftPallet::Uniques {
+            // is_frozen
+            fields.push(Value::bool(false));
+        }
+        self.submit(wallet, "set_metadata", fields).await
+    }
+
+    /// Burn `item` in `collection`, as its owner
+    pub async fn burn(
+        &self,
+        wallet: &Wallet,
+        collection: u32,
+        item: u32,
+    ) -> Result<ExtrinsicReceipt> {
+        info!("Burning item {}/{}", collection, item);
+        let mut fields = vec![Value::u128(collection.into()), Value::u128(item.into())];
+        if self.pallet == NftPallet::Uniques {
+            // check_owner
+            fields.push(Value::unnamed_variant("None", []));
+        }
+        self.submit(wallet, "burn", fields).await
+    }
+
+    async fn submit(
+        &self,
+        wallet: &Wallet,
+        call: &str,
+        fields: Vec<Value>,
+    ) -> Result<ExtrinsicReceipt> {
+        let call = subxt::dynamic::tx(self.pallet.name(), call, fields);
+        TransactionExecutor::new(self.client.clone(), self.metrics.clone())
+            .submit_typed(&call, wallet)
+            .await
+    }
+
+    #[allow(clippy::result_large_err)]
+    async fn fetch(&self, item: &str, keys: Vec<Value>) -> Result<Option<scale_value::Value<u32>>> {
+        self.metrics.record_storage_query();
+        let pallet = self.pallet.name();
+        let query = subxt::dynamic::storage(pallet, item, keys);
+        let value = self
+            .client
+            .storage()
+            .at_latest()
+            .await
+            .map_err(|e| Error::Connection(format!("Failed to fetch latest block: {}", e)))?
+            .fetch(&query)
+            .await
+            .map_err(|e| Error::Storage(format!("Failed to query {}.{}: {}", pallet, item, e)))?;
+
+        value
+            .map(|value| {
+                value.to_value().map_err(|e| {
+                    Error::Storage(format!("Failed to decode {}.{}: {}", pallet, item, e))
+                })
+            })
+            .transpose()
+    }
+}
+
+fn item_keys(collection: u32, item: u32) -> Vec<Value> {
+    vec![Value::u128(collection.into()), Value::u128(item.into())]
+}
+
+/// Read a decoded `CollectionDetails` of either pallet
+fn parse_collection<T>(value: &scale_value::Value<T>) -> Option<NftCollection> {
+    Some(NftCollection {
+        owner: address(value.at("owner")?)?,
+        items: value.at("items")?.as_u128()? as u32,
+        item_metadatas: value.at("item_metadatas")?.as_u128()? as u32,
+    })
+}
+
+/// SS58 address of a decoded `AccountId32`
+fn address<T>(value: &scale_value::Value<T>) -> Option<String> {
+    let mut bytes = Vec::new();
+    collect_bytes(value, &mut bytes);
+    let bytes: [u8; 32] = bytes.try_into().ok()?;
+    Some(AccountId32::from(bytes).to_ss58check())
+}
+
+/// A bounded byte string as text, lossily
+fn text<T>(value: &scale_value::Value<T>) -> String {
+    let mut bytes = Vec::new();
+    collect_bytes(value, &mut bytes);
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pallet_names() {
+        assert_eq!(NftPallet::default().name(), "Nfts");
+        assert_eq!(NftPallet::Uniques.collection_storage(), "Class");
+        assert_eq!(NftPallet::Uniques.metadata_storage(), "InstanceMetadataOf");
+    }
+
+    #[test]
+    fn test_parse_collection() {
+        let owner = [5u8; 32];
+        // pallet-nfts layout; pallet-uniques has more fields around the same ones
+        let value = Value::named_composite([
+            ("owner", Value::from_bytes(owner)),
+            ("owner_deposit", Value::u128(100)),
+            ("items", Value::u128(3)),
+            ("item_metadatas", Value::u128(2)),
+            ("item_configs", Value::u128(3)),
+            ("attributes", Value::u128(0)),
+        ]);
+        assert_eq!(
+            parse_collection(&value),
+            Some(NftCollection {
+                owner: AccountId32::from(owner).to_ss58check(),
+                items: 3,
+                item_metadatas: 2,
+            })
+        );
+        assert_eq!(parse_collection(&Value::u128(0)), None);
+        assert_eq!(text(&Value::from_bytes("ipfs://cid")), "ipfs://cid");
+    }
+}
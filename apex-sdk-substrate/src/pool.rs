@@ -5,16 +5,21 @@
 //! - Round-robin load balancing
 //! - Health checking
 //! - Automatic failover
+//!
+//! [`SubstrateAdapter::connect_pooled`] builds an adapter on top of a pool.
 
 use crate::{ChainConfig, Error, Result, SubstrateAdapter};
+use apex_sdk_core::redact::redact_url;
 use parking_lot::RwLock;
+use std::future::Future;
+use std::pin::Pin;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::time::sleep;
 use tracing::{debug, info, warn};
 
 /// Configuration for connection pool
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct PoolConfig {
     /// Endpoints to connect to
     pub endpoints: Vec<String>,
@@ -28,6 +33,25 @@ pub struct PoolConfig {
     pub auto_health_check: bool,
 }
 
+impl std::fmt::Debug for PoolConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PoolConfig")
+            .field(
+                "endpoints",
+                &self
+                    .endpoints
+                    .iter()
+                    .map(|e| redact_url(e))
+                    .collect::<Vec<_>>(),
+            )
+            .field("health_check_interval", &self.health_check_interval)
+            .field("connection_timeout", &self.connection_timeout)
+            .field("max_retries", &self.max_retries)
+            .field("auto_health_check", &self.auto_health_check)
+            .finish()
+    }
+}
+
 impl PoolConfig {
     /// Create a new pool configuration
     pub fn new(endpoints: Vec<String>) -> Self {
@@ -91,9 +115,14 @@ pub struct ConnectionPool {
     config: PoolConfig,
     connections: Arc<RwLock<Vec<PooledConnection>>>,
     current_index: Arc<RwLock<usize>>,
+    /// Endpoint submissions go to while it stays healthy
+    pinned: RwLock<Option<String>>,
     chain_config: ChainConfig,
 }
 
+/// An operation run on a pooled connection
+pub type PooledOperation<'a, T> = Pin<Box<dyn Future<Output = Result<T>> + Send + 'a>>;
+
 impl ConnectionPool {
     /// Create a new connection pool
     pub async fn new(config: PoolConfig, chain_config: ChainConfig) -> Result<Self> {
@@ -112,18 +141,18 @@ impl ConnectionPool {
 
         // Initialize connections
         for endpoint in &config.endpoints {
-            debug!("Initializing connection to {}", endpoint);
+            debug!("Initializing connection to {}", redact_url(endpoint));
 
             let mut chain_cfg = chain_config.clone();
             chain_cfg.endpoint = endpoint.clone();
 
             let adapter = match SubstrateAdapter::connect_with_config(chain_cfg).await {
                 Ok(adapter) => {
-                    info!("Successfully connected to {}", endpoint);
+                    info!("Successfully connected to {}", redact_url(endpoint));
                     Some(Arc::new(adapter))
                 }
                 Err(e) => {
-                    warn!("Failed to connect to {}: {}", endpoint, e);
+                    warn!("Failed to connect to {}: {}", redact_url(endpoint), e);
                     None
                 }
             };
@@ -147,6 +176,7 @@ impl ConnectionPool {
             config,
             connections: Arc::new(RwLock::new(connections)),
             current_index: Arc::new(RwLock::new(0)),
+            pinned: RwLock::new(None),
             chain_config,
         };
 
@@ -195,6 +225,100 @@ impl ConnectionPool {
         ))
     }
 
+    /// Get the connection submissions go to
+    ///
+    /// The same endpoint is returned while it stays healthy, so a sender's
+    /// transactions reach one transaction pool; once it fails, the next
+    /// healthy endpoint is pinned instead.
+    #[allow(clippy::result_large_err)]
+    pub fn pinned_connection(&self) -> Result<Arc<SubstrateAdapter>> {
+        let pinned = self.pinned.read().clone();
+        if let Some(endpoint) = pinned {
+            let connections = self.connections.read();
+            let adapter = connections
+                .iter()
+                .find(|c| c.endpoint == endpoint && c.health_status == HealthStatus::Healthy)
+                .and_then(|c| c.adapter.clone());
+            if let Some(adapter) = adapter {
+                return Ok(adapter);
+            }
+        }
+
+        let adapter = self.get_connection()?;
+        debug!("Pinning submissions to {}", redact_url(adapter.endpoint()));
+        *self.pinned.write() = Some(adapter.endpoint().to_string());
+        Ok(adapter)
+    }
+
+    /// Mark the connection to `endpoint` as failed after it could not reach
+    /// its node
+    ///
+    /// The connection is dropped, taken out of rotation and unpinned; the
+    /// health checker reconnects it later.
+    pub fn report_failure(&self, endpoint: &str) {
+        warn!("Connection to {} failed", redact_url(endpoint));
+        if let Some(conn) = self
+            .connections
+            .write()
+            .iter_mut()
+            .find(|c| c.endpoint == endpoint)
+        {
+            conn.adapter = None;
+            conn.health_status = HealthStatus::Unhealthy;
+        }
+        let mut pinned = self.pinned.write();
+        if pinned.as_deref() == Some(endpoint) {
+            *pinned = None;
+        }
+    }
+
+    /// Run a read on the next healthy connection, round-robin, failing over
+    /// to the others while connections fail with [`Error::Connection`]
+    pub async fn read<'a, T, F>(&self, operation: F) -> Result<T>
+    where
+        F: Fn(Arc<SubstrateAdapter>) -> PooledOperation<'a, T>,
+    {
+        self.failover(Self::get_connection, operation).await
+    }
+
+    /// Run an operation on the [pinned connection](Self::pinned_connection),
+    /// failing over to the next healthy one while connections fail with
+    /// [`Error::Connection`]
+    pub async fn pinned<'a, T, F>(&self, operation: F) -> Result<T>
+    where
+        F: Fn(Arc<SubstrateAdapter>) -> PooledOperation<'a, T>,
+    {
+        self.failover(Self::pinned_connection, operation).await
+    }
+
+    async fn failover<'a, T, F>(
+        &self,
+        connection: fn(&Self) -> Result<Arc<SubstrateAdapter>>,
+        operation: F,
+    ) -> Result<T>
+    where
+        F: Fn(Arc<SubstrateAdapter>) -> PooledOperation<'a, T>,
+    {
+        let mut last_error = None;
+        for _ in 0..self.endpoint_count() {
+            let adapter = match connection(self) {
+                Ok(adapter) => adapter,
+                Err(e) => return Err(last_error.unwrap_or(e)),
+            };
+            let endpoint = adapter.endpoint().to_string();
+            match operation(adapter).await {
+                Err(Error::Connection(e)) => {
+                    warn!("{}, failing over", e);
+                    self.report_failure(&endpoint);
+                    last_error = Some(Error::Connection(e));
+                }
+                result => return result,
+            }
+        }
+        Err(last_error
+            .unwrap_or_else(|| Error::Connection("No healthy connections available".to_string())))
+    }
+
     /// Get all available connections
     pub fn get_all_connections(&self) -> Vec<Arc<SubstrateAdapter>> {
         self.connections
@@ -257,20 +381,24 @@ impl ConnectionPool {
 
                 // Try to reconnect if failures are below threshold
                 if conn.failure_count <= self.config.max_retries {
-                    debug!("Attempting to reconnect to {}", conn.endpoint);
+                    debug!("Attempting to reconnect to {}", redact_url(&conn.endpoint));
 
                     let mut chain_cfg = self.chain_config.clone();
                     chain_cfg.endpoint = conn.endpoint.clone();
 
                     match SubstrateAdapter::connect_with_config(chain_cfg).await {
                         Ok(adapter) => {
-                            info!("Successfully reconnected to {}", conn.endpoint);
+                            info!("Successfully reconnected to {}", redact_url(&conn.endpoint));
                             conn.adapter = Some(Arc::new(adapter));
                             conn.health_status = HealthStatus::Healthy;
                             conn.failure_count = 0;
                         }
                         Err(e) => {
-                            warn!("Failed to reconnect to {}: {}", conn.endpoint, e);
+                            warn!(
+                                "Failed to reconnect to {}: {}",
+                                redact_url(&conn.endpoint),
+                                e
+                            );
                         }
                     }
                 }
@@ -351,7 +479,7 @@ impl ConnectionPool {
 
     /// Add a new endpoint to the pool
     pub async fn add_endpoint(&self, endpoint: String) -> Result<()> {
-        info!("Adding new endpoint to pool: {}", endpoint);
+        info!("Adding new endpoint to pool: {}", redact_url(&endpoint));
 
         let mut chain_cfg = self.chain_config.clone();
         chain_cfg.endpoint = endpoint.clone();
@@ -359,7 +487,11 @@ impl ConnectionPool {
         let adapter = match SubstrateAdapter::connect_with_config(chain_cfg).await {
             Ok(adapter) => Some(Arc::new(adapter)),
             Err(e) => {
-                warn!("Failed to connect to new endpoint {}: {}", endpoint, e);
+                warn!(
+                    "Failed to connect to new endpoint {}: {}",
+                    redact_url(&endpoint),
+                    e
+                );
                 None
             }
         };
@@ -385,7 +517,7 @@ impl ConnectionPool {
     /// Remove an endpoint from the pool
     #[allow(clippy::result_large_err)]
     pub fn remove_endpoint(&self, endpoint: &str) -> Result<()> {
-        info!("Removing endpoint from pool: {}", endpoint);
+        info!("Removing endpoint from pool: {}", redact_url(endpoint));
 
         let mut connections = self.connections.write();
         let initial_len = connections.len();
@@ -486,4 +618,38 @@ mod tests {
         let stats = pool.stats();
         assert!(stats.total_endpoints > 0);
     }
+
+    #[tokio::test]
+    #[ignore] // Requires network
+    async fn test_connect_pooled_fails_over() {
+        let adapter = SubstrateAdapter::connect_pooled(
+            vec![
+                "wss://westend-rpc.polkadot.io".to_string(),
+                "wss://westend-rpc.dwellir.com".to_string(),
+            ],
+            PoolConfig::new(vec![]).with_auto_health_check(false),
+        )
+        .await
+        .unwrap();
+        let pool = adapter.pool().unwrap();
+        assert_eq!(pool.endpoint_count(), 2);
+
+        // Submissions stay on one endpoint until it fails
+        let pinned = pool.pinned_connection().unwrap();
+        assert_eq!(
+            pool.pinned_connection().unwrap().endpoint(),
+            pinned.endpoint()
+        );
+        pool.report_failure(pinned.endpoint());
+        assert_ne!(
+            pool.pinned_connection().unwrap().endpoint(),
+            pinned.endpoint()
+        );
+
+        assert!(adapter.is_connected());
+        adapter
+            .get_balance("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY")
+            .await
+            .unwrap();
+    }
 }
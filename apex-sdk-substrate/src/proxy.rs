@@ -0,0 +1,286 @@
+//! `pallet-proxy` delegation
+//!
+//! A proxy account can dispatch calls on behalf of a real account,
+//! restricted by a proxy type and optionally delayed by a number of blocks.
+//! [`Proxy`] registers and removes proxies, lists them, and submits calls
+//! through `Proxy.proxy`. To send everything from a
+//! [`TransactionExecutor`] as a proxy, configure it with
+//! [`with_proxy`](TransactionExecutor::with_proxy).
+
+use crate::{
+    account_id, collect_bytes, multi_address, Error, ExtrinsicReceipt, Metrics, Result,
+    TransactionExecutor, Wallet,
+};
+use sp_core::crypto::{AccountId32, Ss58Codec};
+use subxt::dynamic::{At as _, Value};
+use subxt::ext::scale_value::{self, ValueDef};
+use subxt::tx::DynamicPayload;
+use subxt::{OnlineClient, PolkadotConfig};
+use tracing::info;
+
+/// What a proxy may do on behalf of the real account
+///
+/// Proxy types are defined by each runtime; [`Other`](Self::Other) names
+/// those not listed here.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ProxyType {
+    /// Any call
+    Any,
+    /// Any call except balance transfers
+    NonTransfer,
+    /// Governance and treasury calls
+    Governance,
+    /// Staking calls
+    Staking,
+    /// Only rejecting announced proxy calls
+    CancelProxy,
+    /// A runtime-specific proxy type, by variant name
+    Other(String),
+}
+
+impl ProxyType {
+    /// Variant name in the runtime
+    pub fn name(&self) -> &str {
+        match self {
+            ProxyType::Any => "Any",
+            ProxyType::NonTransfer => "NonTransfer",
+            ProxyType::Governance => "Governance",
+            ProxyType::Staking => "Staking",
+            ProxyType::CancelProxy => "CancelProxy",
+            ProxyType::Other(name) => name,
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        Value::unnamed_variant(self.name(), [])
+    }
+}
+
+impl std::fmt::Display for ProxyType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// A proxy registered for a real account
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProxyDefinition {
+    /// Proxy account (SS58)
+    pub delegate: String,
+    /// Proxy type variant name
+    pub proxy_type: String,
+    /// Blocks a call must be announced in advance, 0 for none
+    pub delay: u32,
+}
+
+/// Client for `pallet-proxy`
+pub struct Proxy {
+    client: OnlineClient<PolkadotConfig>,
+    metrics: Metrics,
+}
+
+impl Proxy {
+    /// Create a client for the chain behind `client`
+    pub fn new(client: OnlineClient<PolkadotConfig>, metrics: Metrics) -> Self {
+        Self { client, metrics }
+    }
+
+    /// Proxies registered for `real`
+    pub async fn proxies(&self, real: &str) -> Result<Vec<ProxyDefinition>> {
+        self.metrics.record_storage_query();
+        let query = subxt::dynamic::storage(
+            "Proxy",
+            "Proxies",
+            vec![Value::from_bytes(account_id(real)?)],
+        );
+        let value = self
+            .client
+            .storage()
+            .at_latest()
+            .await
+            .map_err(|e| Error::Connection(format!("Failed to fetch latest block: {}", e)))?
+            .fetch(&query)
+            .await
+            .map_err(|e| Error::Storage(format!("Failed to query proxies: {}", e)))?;
+
+        let Some(value) = value else {
+            return Ok(Vec::new());
+        };
+        let value = value
+            .to_value()
+            .map_err(|e| Error::Storage(format!("Failed to decode proxies: {}", e)))?;
+        parse_proxies(&value)
+            .ok_or_else(|| Error::Storage(format!("Malformed proxies of {}", real)))
+    }
+
+    /// Register `delegate` as a `proxy_type` proxy of `wallet`
+    ///
+    /// `wallet` reserves the proxy deposit. With a `delay`, the proxy has to
+    /// announce calls that many blocks in advance.
+    pub async fn add_proxy(
+        &self,
+        wallet: &Wallet,
+        delegate: &str,
+        proxy_type: ProxyType,
+        delay: u32,
+    ) -> Result<ExtrinsicReceipt> {
+        info!(
+            "Adding {} proxy {} for {}",
+            proxy_type,
+            delegate,
+            wallet.address()
+        );
+        self.submit(
+            wallet,
+            "add_proxy",
+            vec![
+                multi_address(delegate)?,
+                proxy_type.to_value(),
+                Value::u128(delay as u128),
+            ],
+        )
+        .await
+    }
+
+    /// Unregister the `proxy_type` proxy `delegate` of `wallet`
+    pub async fn remove_proxy(
+        &self,
+        wallet: &Wallet,
+        delegate: &str,
+        proxy_type: ProxyType,
+        delay: u32,
+    ) -> Result<ExtrinsicReceipt> {
+        info!(
+            "Removing {} proxy {} for {}",
+            proxy_type,
+            delegate,
+            wallet.address()
+        );
+        self.submit(
+            wallet,
+            "remove_proxy",
+            vec![
+                multi_address(delegate)?,
+                proxy_type.to_value(),
+                Value::u128(delay as u128),
+            ],
+        )
+        .await
+    }
+
+    /// Unregister every proxy of `wallet`, returning the deposit
+    pub async fn remove_proxies(&self, wallet: &Wallet) -> Result<ExtrinsicReceipt> {
+        info!("Removing all proxies for {}", wallet.address());
+        self.submit(wallet, "remove_proxies", vec![]).await
+    }
+
+    /// Dispatch `call` as `real`, signed by its proxy `wallet`
+    ///
+    /// With `force_proxy_type`, only a proxy of that type is used.
+    pub async fn proxy(
+        &self,
+        wallet: &Wallet,
+        real: &str,
+        force_proxy_type: Option<ProxyType>,
+        call: DynamicPayload,
+    ) -> Result<ExtrinsicReceipt> {
+        info!("Submitting call for {} as proxy {}", real, wallet.address());
+        let call = proxy_call(real, force_proxy_type.as_ref(), call.into_value())?;
+        TransactionExecutor::new(self.client.clone(), self.metrics.clone())
+            .submit_typed(&call, wallet)
+            .await
+    }
+
+    async fn submit(
+        &self,
+        wallet: &Wallet,
+        call: &str,
+        fields: Vec<Value>,
+    ) -> Result<ExtrinsicReceipt> {
+        let call = subxt::dynamic::tx("Proxy", call, fields);
+        TransactionExecutor::new(self.client.clone(), self.metrics.clone())
+            .submit_typed(&call, wallet)
+            .await
+    }
+}
+
+/// `Proxy.proxy` dispatching `call` as `real`
+#[allow(clippy::result_large_err)]
+pub(crate) fn proxy_call(
+    real: &str,
+    force_proxy_type: Option<&ProxyType>,
+    call: Value,
+) -> Result<DynamicPayload> {
+    let force_proxy_type = match force_proxy_type {
+        Some(proxy_type) => Value::unnamed_variant("Some", [proxy_type.to_value()]),
+        None => Value::unnamed_variant("None", []),
+    };
+    Ok(subxt::dynamic::tx(
+        "Proxy",
+        "proxy",
+        vec![multi_address(real)?, force_proxy_type, call],
+    ))
+}
+
+/// Read a decoded `Proxies` entry, a list of definitions and the deposit
+fn parse_proxies<T>(value: &scale_value::Value<T>) -> Option<Vec<ProxyDefinition>> {
+    let ValueDef::Composite(definitions) = &value.at(0)?.value else {
+        return None;
+    };
+    definitions
+        .values()
+        .map(|definition| {
+            let mut delegate = Vec::new();
+            collect_bytes(definition.at("delegate")?, &mut delegate);
+            let delegate: [u8; 32] = delegate.try_into().ok()?;
+            let ValueDef::Variant(proxy_type) = &definition.at("proxy_type")?.value else {
+                return None;
+            };
+            Some(ProxyDefinition {
+                delegate: AccountId32::from(delegate).to_ss58check(),
+                proxy_type: proxy_type.name.clone(),
+                delay: definition.at("delay")?.as_u128()? as u32,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_proxy_type_name() {
+        assert_eq!(ProxyType::NonTransfer.name(), "NonTransfer");
+        assert_eq!(ProxyType::Other("Auction".to_string()).name(), "Auction");
+        assert_eq!(ProxyType::Staking.to_string(), "Staking");
+    }
+
+    #[test]
+    fn test_parse_proxies() {
+        let delegate = [3u8; 32];
+        let value = Value::unnamed_composite([
+            Value::unnamed_composite([Value::named_composite([
+                ("delegate", Value::from_bytes(delegate)),
+                ("proxy_type", Value::unnamed_variant("Staking", [])),
+                ("delay", Value::u128(10)),
+            ])]),
+            Value::u128(20_000),
+        ]);
+        assert_eq!(
+            parse_proxies(&value),
+            Some(vec![ProxyDefinition {
+                delegate: AccountId32::from(delegate).to_ss58check(),
+                proxy_type: "Staking".to_string(),
+                delay: 10,
+            }])
+        );
+    }
+
+    #[test]
+    fn test_proxy_call_rejects_invalid_real() {
+        assert!(proxy_call("not an address", None, Value::u128(0)).is_err());
+        let real = AccountId32::from([1u8; 32]).to_ss58check();
+        assert!(proxy_call(&real, Some(&ProxyType::Any), Value::u128(0)).is_ok());
+    }
+}
@@ -0,0 +1,275 @@
+//! PSP22 and PSP34 ink! token standards
+//!
+//! [`Psp22`] (fungible) and [`Psp34`] (non-fungible) wrap a
+//! [`ContractClient`] with the standard messages, the Substrate counterparts
+//! of ERC-20 and ERC-721. No metadata file is needed: message selectors are
+//! derived from the trait-qualified names the standards fix, such as
+//! `PSP22::transfer`. Reads are dry runs; transactions are dry-run first to
+//! find their gas limit and are not submitted if the contract would revert.
+
+use crate::contracts::{ContractAddress, ContractCallBuilder, ContractClient};
+use crate::{account_id, Error, Result, Wallet};
+use parity_scale_codec::{Decode, Encode};
+use sp_core::crypto::{AccountId32, Ss58Codec};
+use subxt::{OnlineClient, PolkadotConfig};
+use tracing::info;
+
+/// Token ID of a PSP34 collection
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Encode, Decode)]
+pub enum Psp34Id {
+    /// `u8` ID
+    U8(u8),
+    /// `u16` ID
+    U16(u16),
+    /// `u32` ID
+    U32(u32),
+    /// `u64` ID
+    U64(u64),
+    /// `u128` ID
+    U128(u128),
+    /// Arbitrary bytes
+    Bytes(Vec<u8>),
+}
+
+/// A PSP22 fungible token contract
+pub struct Psp22 {
+    contract: ContractClient,
+}
+
+impl Psp22 {
+    /// The token at `address`
+    pub fn new(client: OnlineClient<PolkadotConfig>, address: ContractAddress) -> Self {
+        Self {
+            contract: ContractClient::new(client, address),
+        }
+    }
+
+    /// Underlying contract client
+    pub fn contract(&self) -> &ContractClient {
+        &self.contract
+    }
+
+    /// Total token supply
+    pub async fn total_supply(&self) -> Result<u128> {
+        query(&self.contract, "PSP22::total_supply", ()).await
+    }
+
+    /// Balance of `owner`
+    pub async fn balance_of(&self, owner: &str) -> Result<u128> {
+        query(&self.contract, "PSP22::balance_of", account_id(owner)?).await
+    }
+
+    /// Amount `spender` may transfer on behalf of `owner`
+    pub async fn allowance(&self, owner: &str, spender: &str) -> Result<u128> {
+        query(
+            &self.contract,
+            "PSP22::allowance",
+            (account_id(owner)?, account_id(spender)?),
+        )
+        .await
+    }
+
+    /// Transfer `value` from `wallet` to `to`, returning the transaction hash
+    pub async fn transfer(&self, wallet: &Wallet, to: &str, value: u128) -> Result<String> {
+        info!("Transferring {} PSP22 tokens to {}", value, to);
+        submit(
+            &self.contract,
+            wallet,
+            "PSP22::transfer",
+            (account_id(to)?, value, Vec::<u8>::new()),
+        )
+        .await
+    }
+
+    /// Allow `spender` to transfer up to `value` on behalf of `wallet`
+    pub async fn approve(&self, wallet: &Wallet, spender: &str, value: u128) -> Result<String> {
+        submit(
+            &self.contract,
+            wallet,
+            "PSP22::approve",
+            (account_id(spender)?, value),
+        )
+        .await
+    }
+}
+
+/// A PSP34 non-fungible token contract
+pub struct Psp34 {
+    contract: ContractClient,
+}
+
+impl Psp34 {
+    /// The collection at `address`
+    pub fn new(client: OnlineClient<PolkadotConfig>, address: ContractAddress) -> Self {
+        Self {
+            contract: ContractClient::new(client, address),
+        }
+    }
+
+    /// Underlying contract client
+    pub fn contract(&self) -> &ContractClient {
+        &self.contract
+    }
+
+    /// Number of tokens in the collection
+    pub async fn total_supply(&self) -> Result<u128> {
+        query(&self.contract, "PSP34::total_supply", ()).await
+    }
+
+    /// Number of tokens owned by `owner`
+    pub async fn balance_of(&self, owner: &str) -> Result<u32> {
+        query(&self.contract, "PSP34::balance_of", account_id(owner)?).await
+    }
+
+    /// Owner of `id` (SS58), `None` if it does not exist
+    pub async fn owner_of(&self, id: &Psp34Id) -> Result<Option<String>> {
+        let owner: Option<[u8; 32]> = query(&self.contract, "PSP34::owner_of", id).await?;
+        Ok(owner.map(|owner| AccountId32::from(owner).to_ss58check()))
+    }
+
+    /// Attribute `key` of `id` (PSP34Metadata), `None` if it is not set
+    pub async fn attribute(&self, id: &Psp34Id, key: &[u8]) -> Result<Option<Vec<u8>>> {
+        query(
+            &self.contract,
+            "PSP34Metadata::get_attribute",
+            (id, key.to_vec()),
+        )
+        .await
+    }
+
+    /// Metadata URI of `id`, from its `uri` attribute
+    pub async fn token_uri(&self, id: &Psp34Id) -> Result<Option<String>> {
+        Ok(self
+            .attribute(id, b"uri")
+            .await?
+            .map(|uri| String::from_utf8_lossy(&uri).into_owned()))
+    }
+
+    /// Transfer `id` from `wallet` to `to`, returning the transaction hash
+    pub async fn transfer(&self, wallet: &Wallet, to: &str, id: Psp34Id) -> Result<String> {
+        info!("Transferring PSP34 token {:?} to {}", id, to);
+        submit(
+            &self.contract,
+            wallet,
+            "PSP34::transfer",
+            (account_id(to)?, id, Vec::<u8>::new()),
+        )
+        .await
+    }
+
+    /// Allow or disallow `operator` to transfer `id` on behalf of `wallet`,
+    /// or every token of `wallet` without an `id`
+    pub async fn approve(
+        &self,
+        wallet: &Wallet,
+        operator: &str,
+        id: Option<Psp34Id>,
+        approved: bool,
+    ) -> Result<String> {
+        submit(
+            &self.contract,
+            wallet,
+            "PSP34::approve",
+            (account_id(operator)?, id, approved),
+        )
+        .await
+    }
+}
+
+/// Selector of the ink! trait message `name`, e.g. `PSP22::transfer`
+pub fn selector(name: &str) -> [u8; 4] {
+    let hash = sp_core::blake2_256(name.as_bytes());
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
+/// Dry-run `message` with `args` and decode its return value
+async fn query<T: Decode>(
+    contract: &ContractClient,
+    message: &str,
+    args: impl Encode,
+) -> Result<T> {
+    let dry_run = ContractCallBuilder::new(*contract.address(), selector(message))
+        .args(&args.encode())
+        .auto_gas()
+        .dry_run(contract.client(), &[0u8; 32])
+        .await?;
+    if dry_run.reverted {
+        return Err(Error::Transaction(format!(
+            "{} reverted: 0x{}",
+            message,
+            hex::encode(&dry_run.data)
+        )));
+    }
+    decode_return(message, &dry_run.data)
+}
+
+/// Submit `message` with `args` signed by `wallet`
+async fn submit(
+    contract: &ContractClient,
+    wallet: &Wallet,
+    message: &str,
+    args: impl Encode,
+) -> Result<String> {
+    ContractCallBuilder::new(*contract.address(), selector(message))
+        .args(&args.encode())
+        .auto_gas()
+        .submit(contract.client(), wallet)
+        .await
+}
+
+/// Decode a message's return value, wrapped by ink! in
+/// `Result<T, LangError>`
+#[allow(clippy::result_large_err)]
+fn decode_return<T: Decode>(message: &str, data: &[u8]) -> Result<T> {
+    let mut input = data;
+    match input.first() {
+        Some(0) => input = &input[1..],
+        _ => {
+            return Err(Error::Transaction(format!(
+                "{} failed to dispatch: 0x{}",
+                message,
+                hex::encode(data)
+            )))
+        }
+    }
+    T::decode(&mut input)
+        .map_err(|e| Error::Encoding(format!("Failed to decode {} result: {}", message, e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_selector() {
+        assert_eq!(selector("PSP22::balance_of"), [0x65, 0x68, 0x38, 0x2f]);
+        assert_eq!(selector("PSP22::transfer"), [0xdb, 0x20, 0xf9, 0xf5]);
+        assert_eq!(selector("PSP34::owner_of"), [0x11, 0x68, 0x62, 0x4d]);
+    }
+
+    #[test]
+    fn test_decode_return() {
+        let mut data = vec![0u8];
+        500u128.encode_to(&mut data);
+        assert_eq!(
+            decode_return::<u128>("PSP22::balance_of", &data).unwrap(),
+            500
+        );
+
+        let owner = Some([4u8; 32]).encode();
+        let data = [&[0u8][..], &owner].concat();
+        assert_eq!(
+            decode_return::<Option<[u8; 32]>>("PSP34::owner_of", &data).unwrap(),
+            Some([4u8; 32])
+        );
+
+        // LangError::CouldNotReadInput
+        assert!(decode_return::<u128>("PSP22::balance_of", &[1, 1]).is_err());
+    }
+
+    #[test]
+    fn test_psp34_id_encoding() {
+        assert_eq!(Psp34Id::U8(7).encode(), vec![0, 7]);
+        assert_eq!(Psp34Id::Bytes(vec![1, 2]).encode(), vec![5, 8, 1, 2]);
+    }
+}
@@ -0,0 +1,65 @@
+//! Rate-limited RPC client
+//!
+//! Wraps a subxt RPC client so every request and subscription goes through a
+//! shared [`RateLimiter`] before reaching the node.
+
+use apex_sdk_core::performance::RateLimiter;
+use std::sync::Arc;
+use subxt::backend::rpc::{RawRpcFuture, RawRpcSubscription, RawValue, RpcClient, RpcClientT};
+use subxt::ext::subxt_rpcs;
+use tracing::trace;
+
+/// RPC client that throttles requests through a [`RateLimiter`]
+pub struct RateLimitedRpcClient {
+    inner: RpcClient,
+    limiter: Arc<RateLimiter>,
+}
+
+impl RateLimitedRpcClient {
+    /// Throttle requests made through `inner`
+    pub fn new(inner: RpcClient, limiter: Arc<RateLimiter>) -> Self {
+        Self { inner, limiter }
+    }
+
+    /// The limiter applied to requests
+    pub fn limiter(&self) -> &Arc<RateLimiter> {
+        &self.limiter
+    }
+
+    async fn throttle(&self) -> Result<(), subxt_rpcs::Error> {
+        let wait = self
+            .limiter
+            .reserve()
+            .map_err(|e| subxt_rpcs::Error::Client(Box::new(e)))?;
+        if !wait.is_zero() {
+            trace!("Rate limited, waiting {:?}", wait);
+            tokio::time::sleep(wait).await;
+        }
+        Ok(())
+    }
+}
+
+impl RpcClientT for RateLimitedRpcClient {
+    fn request_raw<'a>(
+        &'a self,
+        method: &'a str,
+        params: Option<Box<RawValue>>,
+    ) -> RawRpcFuture<'a, Box<RawValue>> {
+        Box::pin(async move {
+            self.throttle().await?;
+            self.inner.request_raw(method, params).await
+        })
+    }
+
+    fn subscribe_raw<'a>(
+        &'a self,
+        sub: &'a str,
+        params: Option<Box<RawValue>>,
+        unsub: &'a str,
+    ) -> RawRpcFuture<'a, RawRpcSubscription> {
+        Box::pin(async move {
+            self.throttle().await?;
+            self.inner.subscribe_raw(sub, params, unsub).await
+        })
+    }
+}
@@ -1,10 +1,21 @@
 //! Custom signer implementation for Substrate transactions
 //!
 //! This module provides a custom signer that replaces the deprecated PairSigner
-//! from substrate-compat. It implements the subxt::tx::Signer trait for SR25519
-//! and ED25519 key pairs.
+//! from substrate-compat. It implements the subxt::tx::Signer trait for SR25519,
+//! ED25519 and ECDSA key pairs, and (with the `pkcs11` feature) for ED25519
+//! keys held in a PKCS#11 HSM.
+//!
+//! [`JsonRpcSigner`] is a [`RemoteSigner`] for keys held by a JSON-RPC signing
+//! service. Remote signers can't sign synchronously, so they sign
+//! [`UnsignedExtrinsic`](crate::descriptor::UnsignedExtrinsic)s through
+//! [`sign_remote`](crate::descriptor::UnsignedExtrinsic::sign_remote).
 
-use sp_core::{ed25519, sr25519, Pair as _};
+use crate::{rpc_error, KeyPairType};
+use apex_sdk_core::remote_signer::{self, RemoteSigner, SignatureScheme, SignerError};
+use async_trait::async_trait;
+use sp_core::{ecdsa, ed25519, sr25519, Pair as _};
+use subxt::backend::rpc::RpcClient;
+use subxt::ext::subxt_rpcs::client::rpc_params;
 use subxt::tx::Signer;
 
 // Import AccountId32 and MultiSignature from subxt instead of sp_runtime
@@ -89,12 +100,51 @@ impl Signer<subxt::PolkadotConfig> for Ed25519Signer {
     }
 }
 
-/// Enum to hold either SR25519 or ED25519 signer
+/// Custom signer for ECDSA key pairs
+///
+/// The account ID is the blake2-256 hash of the compressed public key.
+#[derive(Clone)]
+pub struct EcdsaSigner {
+    pair: ecdsa::Pair,
+    account_id: AccountId32,
+}
+
+impl EcdsaSigner {
+    /// Create a new ECDSA signer from a key pair
+    pub fn new(pair: ecdsa::Pair) -> Self {
+        let account_id = AccountId32::from(sp_core::blake2_256(&pair.public().0));
+        Self { pair, account_id }
+    }
+
+    /// Get the account ID
+    pub fn account_id(&self) -> &AccountId32 {
+        &self.account_id
+    }
+
+    /// Get the public key
+    pub fn public_key(&self) -> ecdsa::Public {
+        self.pair.public()
+    }
+}
+
+impl Signer<subxt::PolkadotConfig> for EcdsaSigner {
+    fn account_id(&self) -> AccountId32 {
+        self.account_id.clone()
+    }
+
+    fn sign(&self, payload: &[u8]) -> <subxt::PolkadotConfig as subxt::Config>::Signature {
+        let signature = self.pair.sign(payload);
+        MultiSignature::Ecdsa(signature.0)
+    }
+}
+
+/// Enum to hold an SR25519, ED25519 or ECDSA signer
 #[derive(Clone)]
 #[allow(clippy::large_enum_variant)]
 pub enum ApexSigner {
     Sr25519(Sr25519Signer),
     Ed25519(Ed25519Signer),
+    Ecdsa(EcdsaSigner),
 }
 
 impl ApexSigner {
@@ -107,6 +157,11 @@ impl ApexSigner {
     pub fn from_ed25519(pair: ed25519::Pair) -> Self {
         Self::Ed25519(Ed25519Signer::new(pair))
     }
+
+    /// Create from ECDSA pair
+    pub fn from_ecdsa(pair: ecdsa::Pair) -> Self {
+        Self::Ecdsa(EcdsaSigner::new(pair))
+    }
 }
 
 impl Signer<subxt::PolkadotConfig> for ApexSigner {
@@ -114,6 +169,7 @@ impl Signer<subxt::PolkadotConfig> for ApexSigner {
         match self {
             Self::Sr25519(signer) => signer.account_id().clone(),
             Self::Ed25519(signer) => signer.account_id().clone(),
+            Self::Ecdsa(signer) => signer.account_id().clone(),
         }
     }
 
@@ -121,10 +177,186 @@ impl Signer<subxt::PolkadotConfig> for ApexSigner {
         match self {
             Self::Sr25519(signer) => signer.sign(payload),
             Self::Ed25519(signer) => signer.sign(payload),
+            Self::Ecdsa(signer) => signer.sign(payload),
         }
     }
 }
 
+/// Key type of a [`RemoteSigner`] holding a Substrate key
+#[allow(clippy::result_large_err)]
+pub fn key_pair_type(scheme: SignatureScheme) -> crate::Result<KeyPairType> {
+    match scheme {
+        SignatureScheme::Sr25519 => Ok(KeyPairType::Sr25519),
+        SignatureScheme::Ed25519 => Ok(KeyPairType::Ed25519),
+        SignatureScheme::EcdsaSecp256k1 => Err(crate::Error::Signature(
+            "Remote secp256k1 signers return no recovery id, which Substrate ECDSA \
+             signatures need"
+                .to_string(),
+        )),
+    }
+}
+
+/// Signer whose key is held by a JSON-RPC signing service
+///
+/// Each signature is a call to `method` with the account's SS58 address and
+/// the 0x-prefixed payload as positional params, answered with the
+/// 0x-prefixed 64-byte signature.
+pub struct JsonRpcSigner {
+    rpc: RpcClient,
+    method: String,
+    address: String,
+    public_key: [u8; 32],
+    scheme: SignatureScheme,
+}
+
+impl JsonRpcSigner {
+    /// Connect to the signing service at `url`
+    pub async fn new(
+        url: &str,
+        method: impl Into<String>,
+        address: &str,
+        key_type: KeyPairType,
+    ) -> crate::Result<Self> {
+        let rpc = RpcClient::from_url(url)
+            .await
+            .map_err(|e| crate::Error::Connection(rpc_error("Failed to connect to signer", e)))?;
+        Self::with_client(rpc, method, address, key_type)
+    }
+
+    /// Use an existing client for the signing service
+    #[allow(clippy::result_large_err)]
+    pub fn with_client(
+        rpc: RpcClient,
+        method: impl Into<String>,
+        address: &str,
+        key_type: KeyPairType,
+    ) -> crate::Result<Self> {
+        let public_key = crate::account_id(address)?;
+        let scheme = match key_type {
+            KeyPairType::Sr25519 => SignatureScheme::Sr25519,
+            KeyPairType::Ed25519 => SignatureScheme::Ed25519,
+            // The address only carries a hash of an ECDSA public key
+            KeyPairType::Ecdsa => {
+                return Err(crate::Error::Signature(
+                    "JSON-RPC signing supports sr25519 and ed25519 keys".to_string(),
+                ))
+            }
+        };
+        Ok(Self {
+            rpc,
+            method: method.into(),
+            address: address.to_string(),
+            public_key,
+            scheme,
+        })
+    }
+
+    /// Get the account ID
+    pub fn account_id(&self) -> AccountId32 {
+        AccountId32::from(self.public_key)
+    }
+}
+
+#[async_trait]
+impl RemoteSigner for JsonRpcSigner {
+    fn public_key(&self) -> &[u8] {
+        &self.public_key
+    }
+
+    fn scheme(&self) -> SignatureScheme {
+        self.scheme
+    }
+
+    async fn sign(&self, message: &[u8]) -> remote_signer::Result<Vec<u8>> {
+        let signature: String = self
+            .rpc
+            .request(
+                &self.method,
+                rpc_params![&self.address, format!("0x{}", hex::encode(message))],
+            )
+            .await
+            .map_err(|e| SignerError::Backend(rpc_error("Remote signing failed", e)))?;
+        let signature = hex::decode(signature.trim_start_matches("0x"))
+            .map_err(|e| SignerError::InvalidResponse(format!("Invalid signature hex: {}", e)))?;
+        if signature.len() != 64 {
+            return Err(SignerError::InvalidResponse(format!(
+                "Expected a 64-byte signature, got {} bytes",
+                signature.len()
+            )));
+        }
+        Ok(signature)
+    }
+}
+
+/// ED25519 signer whose key never leaves a PKCS#11 HSM
+///
+/// AWS KMS has no key spec usable for Substrate accounts, so only PKCS#11
+/// tokens are supported.
+#[cfg(feature = "pkcs11")]
+pub struct Pkcs11Ed25519Signer {
+    session: std::sync::Arc<apex_sdk_core::hsm::Pkcs11Session>,
+    label: String,
+    account_id: AccountId32,
+}
+
+#[cfg(feature = "pkcs11")]
+impl Pkcs11Ed25519Signer {
+    /// Use the ED25519 key pair labelled `label`
+    pub fn new(
+        session: std::sync::Arc<apex_sdk_core::hsm::Pkcs11Session>,
+        label: impl Into<String>,
+    ) -> crate::Result<Self> {
+        let label = label.into();
+        let public: [u8; 32] = session
+            .public_key(&label)
+            .map_err(|e| crate::Error::Signature(e.to_string()))?
+            .try_into()
+            .map_err(|_| crate::Error::Signature(format!("{} is not an ED25519 key", label)))?;
+
+        Ok(Self {
+            session,
+            label,
+            account_id: AccountId32::from(public),
+        })
+    }
+
+    /// Get the account ID
+    pub fn account_id(&self) -> &AccountId32 {
+        &self.account_id
+    }
+
+    /// Sign `payload`, reporting HSM failures
+    pub fn try_sign(&self, payload: &[u8]) -> crate::Result<MultiSignature> {
+        let signature: [u8; 64] = self
+            .session
+            .sign(
+                &self.label,
+                apex_sdk_core::hsm::SignMechanism::Eddsa,
+                payload,
+            )
+            .map_err(|e| crate::Error::Signature(e.to_string()))?
+            .try_into()
+            .map_err(|_| crate::Error::Signature("Unexpected signature length".to_string()))?;
+        Ok(MultiSignature::Ed25519(signature))
+    }
+}
+
+#[cfg(feature = "pkcs11")]
+impl Signer<subxt::PolkadotConfig> for Pkcs11Ed25519Signer {
+    fn account_id(&self) -> AccountId32 {
+        self.account_id.clone()
+    }
+
+    /// `Signer::sign` can't fail, so an HSM error is logged and an empty
+    /// signature returned, which the node rejects
+    fn sign(&self, payload: &[u8]) -> <subxt::PolkadotConfig as subxt::Config>::Signature {
+        self.try_sign(payload).unwrap_or_else(|e| {
+            tracing::error!("HSM signing failed: {}", e);
+            MultiSignature::Ed25519([0; 64])
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -160,6 +392,23 @@ mod tests {
         assert_eq!(signer.account_id().0.len(), 32);
     }
 
+    #[test]
+    fn test_apex_signer_ecdsa() {
+        let (pair, _) = ecdsa::Pair::generate();
+        let signer = ApexSigner::from_ecdsa(pair.clone());
+
+        let message = b"test message";
+        let MultiSignature::Ecdsa(signature) = signer.sign(message) else {
+            panic!("expected an ECDSA signature");
+        };
+        assert!(ecdsa::Pair::verify(
+            &ecdsa::Signature::from_raw(signature),
+            message,
+            &pair.public()
+        ));
+        assert_eq!(signer.account_id().0, sp_core::blake2_256(&pair.public().0));
+    }
+
     #[test]
     fn test_apex_signer_ed25519() {
         let (pair, _) = ed25519::Pair::generate();
@@ -0,0 +1,682 @@
+//! `pallet-staking` bonding, nominations and rewards
+//!
+//! The client submits the calls a nominator needs (bond, nominate, unbond,
+//! withdraw, payout) and reads ledgers and era history.
+//!
+//! Rewards are computed the way the pallet pays them out: the era payout is
+//! split between validators by reward points, the validator takes its
+//! commission, and the rest is shared pro rata among the stake exposed
+//! behind it. Paged exposures (`ErasStakersOverview` / `ErasStakersPaged`)
+//! are read, as deployed on Polkadot and Kusama.
+
+use crate::{
+    account_id, collect_bytes, multi_address, Error, ExtrinsicReceipt, Metrics, Result,
+    TransactionExecutor, Wallet,
+};
+use sp_core::crypto::{AccountId32, Ss58Codec};
+use sp_core::U256;
+use subxt::dynamic::{At as _, Value};
+use subxt::ext::scale_value::{self, ValueDef};
+use subxt::{OnlineClient, PolkadotConfig};
+use tracing::info;
+
+/// Parts per billion of a `Perbill`
+const PERBILL: u128 = 1_000_000_000;
+
+/// The era currently being rewarded
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ActiveEra {
+    /// Era index
+    pub index: u32,
+    /// Unix timestamp (milliseconds) the era started at, once known
+    pub start: Option<u64>,
+}
+
+/// Reward of one stash for one era
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EraReward {
+    /// Era index
+    pub era: u32,
+    /// Reward in Planck
+    pub amount: u128,
+    /// Whether every payout page holding the stash was claimed
+    pub claimed: bool,
+}
+
+/// Where staking rewards are paid
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RewardDestination {
+    /// Paid to the stash and added to the bond
+    Staked,
+    /// Paid to the stash, not bonded
+    Stash,
+    /// Paid to another account (SS58)
+    Account(String),
+    /// Not paid out
+    None,
+}
+
+impl RewardDestination {
+    #[allow(clippy::result_large_err)]
+    fn to_value(&self) -> Result<Value> {
+        Ok(match self {
+            Self::Staked => Value::unnamed_variant("Staked", []),
+            Self::Stash => Value::unnamed_variant("Stash", []),
+            Self::Account(address) => {
+                Value::unnamed_variant("Account", [Value::from_bytes(account_id(address)?)])
+            }
+            Self::None => Value::unnamed_variant("None", []),
+        })
+    }
+}
+
+/// Funds being unbonded, withdrawable from `era`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnlockChunk {
+    /// Amount in Planck
+    pub value: u128,
+    /// Era from which the chunk can be withdrawn
+    pub era: u32,
+}
+
+/// Bonded funds of a stash
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StakingLedger {
+    /// Stash account (SS58)
+    pub stash: String,
+    /// Bonded amount, including chunks being unbonded, in Planck
+    pub total: u128,
+    /// Amount earning rewards, in Planck
+    pub active: u128,
+    /// Chunks being unbonded, oldest first
+    pub unlocking: Vec<UnlockChunk>,
+}
+
+impl StakingLedger {
+    /// Amount `withdraw_unbonded` releases in `current_era`
+    pub fn withdrawable(&self, current_era: u32) -> u128 {
+        self.unlocking
+            .iter()
+            .filter(|chunk| chunk.era <= current_era)
+            .map(|chunk| chunk.value)
+            .sum()
+    }
+}
+
+/// Reward of `stake` out of `exposure` behind a validator paid
+/// `validator_payout` with `commission` (parts per billion)
+///
+/// The validator's own stake also earns the commission.
+pub fn stake_reward(
+    validator_payout: u128,
+    commission: u32,
+    exposure: u128,
+    stake: u128,
+    is_validator: bool,
+) -> u128 {
+    let commission = mul_div(validator_payout, (commission as u128).min(PERBILL), PERBILL);
+    let shared = validator_payout - commission;
+    let share = if exposure == 0 {
+        0
+    } else {
+        mul_div(shared, stake, exposure)
+    };
+    if is_validator {
+        commission + share
+    } else {
+        share
+    }
+}
+
+/// `a * b / c` in 256 bits, for `b <= c`
+fn mul_div(a: u128, b: u128, c: u128) -> u128 {
+    (U256::from(a) * U256::from(b) / U256::from(c)).low_u128()
+}
+
+/// Client for `pallet-staking`
+pub struct Staking {
+    client: OnlineClient<PolkadotConfig>,
+    metrics: Metrics,
+}
+
+impl Staking {
+    /// Create a client for the chain behind `client`
+    pub fn new(client: OnlineClient<PolkadotConfig>, metrics: Metrics) -> Self {
+        Self { client, metrics }
+    }
+
+    /// Get the active era, `None` before staking started
+    pub async fn active_era(&self) -> Result<Option<ActiveEra>> {
+        let Some(value) = self.fetch("ActiveEra", vec![]).await? else {
+            return Ok(None);
+        };
+        let index = value
+            .at("index")
+            .and_then(number)
+            .ok_or_else(|| Error::Storage("Malformed ActiveEra".to_string()))?;
+        Ok(Some(ActiveEra {
+            index: index as u32,
+            start: value.at("start").and_then(number).map(|ms| ms as u64),
+        }))
+    }
+
+    /// Number of past eras whose rewards can still be claimed
+    #[allow(clippy::result_large_err)]
+    pub fn history_depth(&self) -> Result<u32> {
+        let address = subxt::dynamic::constant("Staking", "HistoryDepth");
+        let value = self
+            .client
+            .constants()
+            .at(&address)
+            .map_err(|e| Error::Metadata(format!("Failed to read HistoryDepth: {}", e)))?
+            .to_value()
+            .map_err(|e| Error::Metadata(format!("Failed to decode HistoryDepth: {}", e)))?;
+        number(&value)
+            .map(|depth| depth as u32)
+            .ok_or_else(|| Error::Metadata("Malformed HistoryDepth".to_string()))
+    }
+
+    /// Ledger of `stash`, `None` if it is not bonded
+    pub async fn ledger(&self, stash: &str) -> Result<Option<StakingLedger>> {
+        let stash_bytes = account_id(stash)?;
+        // Ledgers are keyed by controller, which is the stash itself on
+        // current runtimes
+        let controller = self
+            .fetch("Bonded", vec![Value::from_bytes(stash_bytes)])
+            .await?
+            .as_ref()
+            .and_then(account_bytes)
+            .unwrap_or(stash_bytes);
+        let Some(value) = self
+            .fetch("Ledger", vec![Value::from_bytes(controller)])
+            .await?
+        else {
+            return Ok(None);
+        };
+        parse_ledger(&value)
+            .map(Some)
+            .ok_or_else(|| Error::Storage(format!("Malformed ledger of {}", stash)))
+    }
+
+    /// Rewards of `stash` in eras still claimable and not yet paid out,
+    /// oldest first
+    pub async fn pending_rewards(&self, stash: &str) -> Result<Vec<EraReward>> {
+        let Some(active) = self.active_era().await? else {
+            return Ok(Vec::new());
+        };
+        let oldest = active.index.saturating_sub(self.history_depth()?);
+
+        let mut rewards = Vec::new();
+        for era in oldest..active.index {
+            if let Some(reward) = self.era_reward(era, stash).await? {
+                if !reward.claimed && reward.amount > 0 {
+                    rewards.push(reward);
+                }
+            }
+        }
+        Ok(rewards)
+    }
+
+    /// Bond `value` from `wallet` as a new stash, paying rewards to `payee`
+    pub async fn bond(
+        &self,
+        wallet: &Wallet,
+        value: u128,
+        payee: RewardDestination,
+    ) -> Result<ExtrinsicReceipt> {
+        info!("Bonding {} from {}", value, wallet.address());
+        self.submit(wallet, "bond", vec![Value::u128(value), payee.to_value()?])
+            .await
+    }
+
+    /// Add `value` from `wallet`'s free balance to its bond
+    pub async fn bond_extra(&self, wallet: &Wallet, value: u128) -> Result<ExtrinsicReceipt> {
+        info!("Bonding {} more from {}", value, wallet.address());
+        self.submit(wallet, "bond_extra", vec![Value::u128(value)])
+            .await
+    }
+
+    /// Nominate the validator stashes `targets`, replacing any previous
+    /// nominations
+    #[allow(clippy::result_large_err)]
+    pub async fn nominate(&self, wallet: &Wallet, targets: &[&str]) -> Result<ExtrinsicReceipt> {
+        let targets = targets
+            .iter()
+            .map(|target| multi_address(target))
+            .collect::<Result<Vec<_>>>()?;
+        info!(
+            "Nominating {} validators from {}",
+            targets.len(),
+            wallet.address()
+        );
+        self.submit(wallet, "nominate", vec![Value::unnamed_composite(targets)])
+            .await
+    }
+
+    /// Start unbonding `value`, withdrawable after the bonding duration
+    pub async fn unbond(&self, wallet: &Wallet, value: u128) -> Result<ExtrinsicReceipt> {
+        info!("Unbonding {} from {}", value, wallet.address());
+        self.submit(wallet, "unbond", vec![Value::u128(value)])
+            .await
+    }
+
+    /// Withdraw every unbonded chunk whose era has come
+    ///
+    /// `num_slashing_spans` is only needed, as the stash's slashing span
+    /// count, when the withdrawal empties the ledger; 0 otherwise.
+    pub async fn withdraw_unbonded(
+        &self,
+        wallet: &Wallet,
+        num_slashing_spans: u32,
+    ) -> Result<ExtrinsicReceipt> {
+        self.submit(
+            wallet,
+            "withdraw_unbonded",
+            vec![Value::u128(num_slashing_spans as u128)],
+        )
+        .await
+    }
+
+    /// Pay out the rewards of `validator` and its nominators for `era`
+    ///
+    /// Anyone can submit it; each call pays one exposure page.
+    pub async fn payout_stakers(
+        &self,
+        wallet: &Wallet,
+        validator: &str,
+        era: u32,
+    ) -> Result<ExtrinsicReceipt> {
+        info!("Paying out {} for era {}", validator, era);
+        self.submit(
+            wallet,
+            "payout_stakers",
+            vec![
+                Value::from_bytes(account_id(validator)?),
+                Value::u128(era as u128),
+            ],
+        )
+        .await
+    }
+
+    /// Reward of `stash` for `era`, as a validator or through the validators
+    /// it currently nominates
+    ///
+    /// `None` while the era is ongoing or once its history was pruned.
+    pub async fn era_reward(&self, era: u32, stash: &str) -> Result<Option<EraReward>> {
+        let account = AccountId32::from_ss58check(stash)
+            .map_err(|e| Error::Storage(format!("Invalid SS58 address: {}", e)))?;
+        let account: [u8; 32] = account.into();
+        let era_key = || Value::u128(era as u128);
+
+        let Some(era_payout) = self
+            .fetch("ErasValidatorReward", vec![era_key()])
+            .await?
+            .as_ref()
+            .and_then(number)
+        else {
+            return Ok(None);
+        };
+        let Some(points) = self.fetch("ErasRewardPoints", vec![era_key()]).await? else {
+            return Ok(None);
+        };
+        let total_points = points.at("total").and_then(number).unwrap_or(0);
+        let validator_points = reward_points(&points);
+        let points_of = |validator: &[u8; 32]| {
+            validator_points
+                .iter()
+                .find(|(account, _)| account == validator)
+                .map(|(_, points)| *points)
+        };
+
+        let mut reward = EraReward {
+            era,
+            amount: 0,
+            claimed: true,
+        };
+
+        // A validator of the era
+        if let Some(points) = points_of(&account) {
+            let payout = mul_div(era_payout, points, total_points.max(1));
+            let exposure = self.exposure_overview(era, &account).await?;
+            let commission = self.commission(era, &account).await?;
+            reward.amount = stake_reward(payout, commission, exposure.0, exposure.1, true);
+            reward.claimed = !self.claimed_pages(era, &account).await?.is_empty();
+            return Ok(Some(reward));
+        }
+
+        // A nominator, through each validator it backed
+        for target in self.nominations(&account).await? {
+            let Some(points) = points_of(&target) else {
+                continue;
+            };
+            let (exposure, _, page_count) = self.exposure_overview(era, &target).await?;
+            let payout = mul_div(era_payout, points, total_points.max(1));
+            let commission = self.commission(era, &target).await?;
+            let claimed = self.claimed_pages(era, &target).await?;
+
+            for page in 0..page_count {
+                let Some(stake) = self.paged_stake(era, &target, page, &account).await? else {
+                    continue;
+                };
+                reward.amount += stake_reward(payout, commission, exposure, stake, false);
+                reward.claimed &= claimed.contains(&page);
+            }
+        }
+        Ok(Some(reward))
+    }
+
+    /// `(total, own, page_count)` exposure of `validator` in `era`
+    async fn exposure_overview(&self, era: u32, validator: &[u8; 32]) -> Result<(u128, u128, u32)> {
+        let value = self
+            .fetch(
+                "ErasStakersOverview",
+                vec![Value::u128(era as u128), Value::from_bytes(validator)],
+            )
+            .await?;
+        let field = |name: &str| value.as_ref().and_then(|v| v.at(name)).and_then(number);
+        Ok((
+            field("total").unwrap_or(0),
+            field("own").unwrap_or(0),
+            field("page_count").unwrap_or(0) as u32,
+        ))
+    }
+
+    /// Stake of `nominator` on exposure page `page` of `validator`
+    async fn paged_stake(
+        &self,
+        era: u32,
+        validator: &[u8; 32],
+        page: u32,
+        nominator: &[u8; 32],
+    ) -> Result<Option<u128>> {
+        let Some(value) = self
+            .fetch(
+                "ErasStakersPaged",
+                vec![
+                    Value::u128(era as u128),
+                    Value::from_bytes(validator),
+                    Value::u128(page as u128),
+                ],
+            )
+            .await?
+        else {
+            return Ok(None);
+        };
+        let stake = exposed_stake(&value, nominator);
+        Ok(stake)
+    }
+
+    /// Commission of `validator` in `era`, in parts per billion
+    async fn commission(&self, era: u32, validator: &[u8; 32]) -> Result<u32> {
+        let value = self
+            .fetch(
+                "ErasValidatorPrefs",
+                vec![Value::u128(era as u128), Value::from_bytes(validator)],
+            )
+            .await?;
+        Ok(value
+            .as_ref()
+            .and_then(|v| v.at("commission"))
+            .and_then(number)
+            .unwrap_or(0) as u32)
+    }
+
+    /// Exposure pages of `validator` already paid out for `era`
+    async fn claimed_pages(&self, era: u32, validator: &[u8; 32]) -> Result<Vec<u32>> {
+        let value = self
+            .fetch(
+                "ClaimedRewards",
+                vec![Value::u128(era as u128), Value::from_bytes(validator)],
+            )
+            .await?;
+        Ok(match value.as_ref().map(|v| &v.value) {
+            Some(ValueDef::Composite(pages)) => pages
+                .values()
+                .filter_map(number)
+                .map(|page| page as u32)
+                .collect(),
+            _ => Vec::new(),
+        })
+    }
+
+    /// Validators `nominator` currently nominates
+    async fn nominations(&self, nominator: &[u8; 32]) -> Result<Vec<[u8; 32]>> {
+        let value = self
+            .fetch("Nominators", vec![Value::from_bytes(nominator)])
+            .await?;
+        Ok(match value.as_ref().and_then(|v| v.at("targets")) {
+            Some(targets) => match &targets.value {
+                ValueDef::Composite(targets) => {
+                    targets.values().filter_map(account_bytes).collect()
+                }
+                _ => Vec::new(),
+            },
+            None => Vec::new(),
+        })
+    }
+
+    async fn submit(
+        &self,
+        wallet: &Wallet,
+        call: &str,
+        fields: Vec<Value>,
+    ) -> Result<ExtrinsicReceipt> {
+        let call = subxt::dynamic::tx("Staking", call, fields);
+        TransactionExecutor::new(self.client.clone(), self.metrics.clone())
+            .submit_typed(&call, wallet)
+            .await
+    }
+
+    #[allow(clippy::result_large_err)]
+    async fn fetch(&self, item: &str, keys: Vec<Value>) -> Result<Option<scale_value::Value<u32>>> {
+        self.metrics.record_storage_query();
+        let query = subxt::dynamic::storage("Staking", item, keys);
+        let value = self
+            .client
+            .storage()
+            .at_latest()
+            .await
+            .map_err(|e| Error::Connection(format!("Failed to fetch latest block: {}", e)))?
+            .fetch(&query)
+            .await
+            .map_err(|e| Error::Storage(format!("Failed to query Staking.{}: {}", item, e)))?;
+
+        value
+            .map(|value| {
+                value.to_value().map_err(|e| {
+                    Error::Storage(format!("Failed to decode Staking.{}: {}", item, e))
+                })
+            })
+            .transpose()
+    }
+}
+
+/// Read a decoded `StakingLedger`
+fn parse_ledger<T>(value: &scale_value::Value<T>) -> Option<StakingLedger> {
+    let unlocking = match &value.at("unlocking")?.value {
+        ValueDef::Composite(chunks) => chunks
+            .values()
+            .map(|chunk| {
+                Some(UnlockChunk {
+                    value: number(chunk.at("value")?)?,
+                    era: number(chunk.at("era")?)? as u32,
+                })
+            })
+            .collect::<Option<Vec<_>>>()?,
+        _ => return None,
+    };
+    Some(StakingLedger {
+        stash: AccountId32::from(account_bytes(value.at("stash")?)?).to_ss58check(),
+        total: number(value.at("total")?)?,
+        active: number(value.at("active")?)?,
+        unlocking,
+    })
+}
+
+/// Validators and their points from a decoded `EraRewardPoints`
+fn reward_points<T>(points: &scale_value::Value<T>) -> Vec<([u8; 32], u128)> {
+    let Some(individual) = points.at("individual") else {
+        return Vec::new();
+    };
+    let ValueDef::Composite(entries) = &individual.value else {
+        return Vec::new();
+    };
+    entries
+        .values()
+        .filter_map(|entry| Some((account_bytes(entry.at(0)?)?, number(entry.at(1)?)?)))
+        .collect()
+}
+
+/// Stake of `nominator` among the `others` of a decoded exposure
+/// (`Exposure` or `ExposurePage`)
+fn exposed_stake<T>(exposure: &scale_value::Value<T>, nominator: &[u8; 32]) -> Option<u128> {
+    let ValueDef::Composite(others) = &exposure.at("others")?.value else {
+        return None;
+    };
+    others.values().find_map(|other| {
+        (account_bytes(other.at("who")?)? == *nominator)
+            .then(|| other.at("value").and_then(number))
+            .flatten()
+    })
+}
+
+/// A number, possibly wrapped in single-field composites (`Perbill`,
+/// `Compact`) or an `Option`'s `Some`
+fn number<T>(value: &scale_value::Value<T>) -> Option<u128> {
+    match &value.value {
+        ValueDef::Primitive(_) => value.as_u128(),
+        ValueDef::Composite(composite) if composite.len() == 1 => {
+            number(composite.values().next()?)
+        }
+        ValueDef::Variant(variant) if variant.name == "Some" => {
+            number(variant.values.values().next()?)
+        }
+        _ => None,
+    }
+}
+
+/// The 32 bytes of a decoded `AccountId32`
+fn account_bytes<T>(value: &scale_value::Value<T>) -> Option<[u8; 32]> {
+    let mut bytes = Vec::new();
+    collect_bytes(value, &mut bytes);
+    bytes.try_into().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stake_reward() {
+        // 10% commission on a 1000 payout leaves 900 shared over 100 staked
+        assert_eq!(stake_reward(1_000, 100_000_000, 100, 25, false), 225);
+        assert_eq!(stake_reward(1_000, 100_000_000, 100, 25, true), 325);
+        assert_eq!(stake_reward(1_000, 0, 0, 25, false), 0);
+        // Large balances do not overflow
+        let planck = 10u128.pow(10);
+        assert_eq!(
+            stake_reward(
+                100_000 * planck,
+                0,
+                u64::MAX as u128 * planck,
+                u64::MAX as u128 * planck,
+                false
+            ),
+            100_000 * planck
+        );
+    }
+
+    #[test]
+    fn test_parse_ledger() {
+        let chunk = |value: u128, era: u128| {
+            Value::named_composite([("value", Value::u128(value)), ("era", Value::u128(era))])
+        };
+        let value = Value::named_composite([
+            ("stash", Value::from_bytes([1u8; 32])),
+            ("total", Value::u128(1_000)),
+            ("active", Value::u128(700)),
+            (
+                "unlocking",
+                Value::unnamed_composite([chunk(100, 10), chunk(200, 12)]),
+            ),
+            ("legacy_claimed_rewards", Value::unnamed_composite([])),
+        ]);
+        let ledger = parse_ledger(&value).unwrap();
+        assert_eq!(ledger.stash, AccountId32::from([1u8; 32]).to_ss58check());
+        assert_eq!(ledger.total, 1_000);
+        assert_eq!(ledger.active, 700);
+        assert_eq!(ledger.unlocking.len(), 2);
+        assert_eq!(ledger.withdrawable(9), 0);
+        assert_eq!(ledger.withdrawable(11), 100);
+        assert_eq!(ledger.withdrawable(12), 300);
+    }
+
+    #[test]
+    fn test_reward_destination_value() {
+        assert!(RewardDestination::Staked.to_value().is_ok());
+        assert!(RewardDestination::Account("not an address".to_string())
+            .to_value()
+            .is_err());
+        let alice = AccountId32::from([1u8; 32]).to_ss58check();
+        assert!(RewardDestination::Account(alice).to_value().is_ok());
+    }
+
+    #[test]
+    fn test_reward_points() {
+        let account = |byte: u8| Value::unnamed_composite([Value::from_bytes([byte; 32])]);
+        let points = Value::named_composite([
+            ("total", Value::u128(30)),
+            (
+                "individual",
+                Value::unnamed_composite([
+                    Value::unnamed_composite([account(1), Value::u128(10)]),
+                    Value::unnamed_composite([account(2), Value::u128(20)]),
+                ]),
+            ),
+        ]);
+        assert_eq!(reward_points(&points), vec![([1; 32], 10), ([2; 32], 20)]);
+        assert_eq!(
+            number(&Value::unnamed_variant("Some", [Value::u128(7)])),
+            Some(7)
+        );
+    }
+    #[test]
+    fn test_exposed_stake_from_erasstakers() {
+        use parity_scale_codec::{Compact, Decode, Encode};
+
+        use frame_metadata::v14::StorageEntryType;
+        use frame_metadata::{RuntimeMetadata, RuntimeMetadataPrefixed};
+
+        // Polkadot runtime 9180 metadata, which still stores full exposures
+        let raw = include_bytes!("../tests/fixtures/polkadot_9180_metadata.scale");
+        let RuntimeMetadata::V14(metadata) =
+            RuntimeMetadataPrefixed::decode(&mut &raw[..]).unwrap().1
+        else {
+            panic!("fixture is not V14 metadata");
+        };
+        let entry = metadata
+            .pallets
+            .iter()
+            .find(|pallet| pallet.name == "Staking")
+            .and_then(|pallet| pallet.storage.as_ref())
+            .and_then(|storage| storage.entries.iter().find(|e| e.name == "ErasStakers"))
+            .unwrap();
+        let StorageEntryType::Map { value, .. } = &entry.ty else {
+            panic!("ErasStakers is not a map");
+        };
+
+        // Exposure { total, own, others: [IndividualExposure { who, value }] }
+        let bytes = (
+            Compact(1_500u128),
+            Compact(1_000u128),
+            vec![([1u8; 32], Compact(200u128)), ([2u8; 32], Compact(300u128))],
+        )
+            .encode();
+        let exposure =
+            scale_value::scale::decode_as_type(&mut &bytes[..], value.id, &metadata.types).unwrap();
+
+        assert_eq!(exposure.at("total").and_then(number), Some(1_500));
+        assert_eq!(exposed_stake(&exposure, &[2; 32]), Some(300));
+        assert_eq!(exposed_stake(&exposure, &[3; 32]), None);
+    }
+}
@@ -3,24 +3,41 @@
 //! This module provides functionality for querying chain storage including:
 //! - Account information and balances
 //! - Storage item queries
+//! - Paged iteration over storage maps
 //! - Runtime constants
 //! - Metadata inspection
 
 use crate::{Error, Metrics, Result};
+use subxt::backend::legacy::LegacyRpcMethods;
+use subxt::backend::rpc::RpcClient;
 use subxt::dynamic::At as _;
 use subxt::{OnlineClient, PolkadotConfig};
 use tracing::debug;
 
+/// Entries per page of storage iteration by default, the most nodes return
+pub const DEFAULT_STORAGE_PAGE_SIZE: u32 = 1000;
+
 /// Storage query client for accessing chain storage
 pub struct StorageClient {
     client: OnlineClient<PolkadotConfig>,
+    rpc: Option<RpcClient>,
     metrics: Metrics,
 }
 
 impl StorageClient {
     /// Create a new storage client
     pub fn new(client: OnlineClient<PolkadotConfig>, metrics: Metrics) -> Self {
-        Self { client, metrics }
+        Self {
+            client,
+            rpc: None,
+            metrics,
+        }
+    }
+
+    /// Use `rpc`, the client `client` runs on, for paged iteration
+    pub fn with_rpc(mut self, rpc: RpcClient) -> Self {
+        self.rpc = Some(rpc);
+        self
     }
 
     /// Query account information including balance and nonce
@@ -139,6 +156,66 @@ impl StorageClient {
         Ok(result.map(|v| v.encoded().to_vec()))
     }
 
+    /// Raw key of a storage entry, e.g. to watch it with
+    /// [`SubstrateAdapter::subscribe_storage`](crate::SubstrateAdapter::subscribe_storage)
+    #[allow(clippy::result_large_err)]
+    pub fn storage_key(
+        &self,
+        pallet: &str,
+        item: &str,
+        keys: Vec<subxt::dynamic::Value>,
+    ) -> Result<Vec<u8>> {
+        self.client
+            .storage()
+            .address_bytes(&subxt::dynamic::storage(pallet, item, keys))
+            .map_err(|e| {
+                Error::Storage(format!(
+                    "Failed to encode storage key {}::{}: {}",
+                    pallet, item, e
+                ))
+            })
+    }
+
+    /// Fetch a typed storage value at the latest block
+    ///
+    /// Accepts any subxt storage address, typically one built from the
+    /// generated bindings in [`typed::api`](crate::typed) (`typed` feature).
+    pub async fn fetch_typed<Addr>(&self, address: &Addr) -> Result<Option<Addr::Target>>
+    where
+        Addr: subxt::storage::Address<IsFetchable = subxt::utils::Yes>,
+    {
+        self.metrics.record_storage_query();
+
+        self.client
+            .storage()
+            .at_latest()
+            .await
+            .map_err(|e| Error::Connection(format!("Failed to fetch latest block: {}", e)))?
+            .fetch(address)
+            .await
+            .map_err(|e| Error::Storage(format!("Failed to query typed storage: {}", e)))
+    }
+
+    /// Fetch a typed storage value, falling back to its default if unset
+    pub async fn fetch_typed_or_default<Addr>(&self, address: &Addr) -> Result<Addr::Target>
+    where
+        Addr: subxt::storage::Address<
+            IsFetchable = subxt::utils::Yes,
+            IsDefaultable = subxt::utils::Yes,
+        >,
+    {
+        self.metrics.record_storage_query();
+
+        self.client
+            .storage()
+            .at_latest()
+            .await
+            .map_err(|e| Error::Connection(format!("Failed to fetch latest block: {}", e)))?
+            .fetch_or_default(address)
+            .await
+            .map_err(|e| Error::Storage(format!("Failed to query typed storage: {}", e)))
+    }
+
     /// Get a runtime constant (returns raw bytes)
     #[allow(clippy::result_large_err)]
     pub fn get_constant(&self, pallet: &str, constant: &str) -> Result<Vec<u8>> {
@@ -235,6 +312,103 @@ impl StorageClient {
         Ok(results)
     }
 
+    /// One page of the raw keys matching `query`
+    ///
+    /// The query's keys are a prefix of the map's keys, so none lists the
+    /// whole map. Needs an RPC client (see [`with_rpc`](Self::with_rpc)).
+    pub async fn iter_keys(&self, query: &StorageQuery) -> Result<StoragePage<Vec<u8>>> {
+        debug!("Iterating storage keys: {}::{}", query.pallet, query.item);
+        self.metrics.record_storage_query();
+
+        let (prefix, at) = self.page_context(query).await?;
+        let keys = self
+            .legacy_rpc()?
+            .state_get_keys_paged(
+                &prefix,
+                query.page_size,
+                query.start_key.as_deref(),
+                Some(at),
+            )
+            .await
+            .map_err(|e| {
+                Error::Storage(format!(
+                    "Failed to iterate storage {}::{}: {}",
+                    query.pallet, query.item, e
+                ))
+            })?;
+
+        Ok(StoragePage {
+            next_key: next_key(&keys, query.page_size),
+            at: format!("{:?}", at),
+            items: keys,
+        })
+    }
+
+    /// One page of the raw keys and SCALE-encoded values matching `query`
+    ///
+    /// Keys and values are read at the same block. Needs an RPC client (see
+    /// [`with_rpc`](Self::with_rpc)).
+    pub async fn iter(&self, query: &StorageQuery) -> Result<StoragePage<(Vec<u8>, Vec<u8>)>> {
+        let keys = self.iter_keys(query).await?;
+        if keys.items.is_empty() {
+            return Ok(StoragePage {
+                items: Vec::new(),
+                next_key: None,
+                at: keys.at,
+            });
+        }
+
+        self.metrics.record_storage_query();
+        let at = parse_block_hash(&keys.at)?;
+        let change_sets = self
+            .legacy_rpc()?
+            .state_query_storage_at(keys.items.iter().map(Vec::as_slice), Some(at))
+            .await
+            .map_err(|e| {
+                Error::Storage(format!(
+                    "Failed to query storage {}::{}: {}",
+                    query.pallet, query.item, e
+                ))
+            })?;
+
+        let items = change_sets
+            .into_iter()
+            .flat_map(|set| set.changes)
+            .filter_map(|(key, value)| Some((key.0, value?.0)))
+            .collect();
+        Ok(StoragePage {
+            items,
+            next_key: keys.next_key,
+            at: keys.at,
+        })
+    }
+
+    #[allow(clippy::result_large_err)]
+    fn legacy_rpc(&self) -> Result<LegacyRpcMethods<PolkadotConfig>> {
+        self.rpc.clone().map(LegacyRpcMethods::new).ok_or_else(|| {
+            Error::Connection("Paged storage iteration needs an RPC client".to_string())
+        })
+    }
+
+    /// Key prefix and block hash of a page of `query`
+    async fn page_context(
+        &self,
+        query: &StorageQuery,
+    ) -> Result<(Vec<u8>, subxt::config::substrate::H256)> {
+        let prefix = self.storage_key(&query.pallet, &query.item, query.keys.clone())?;
+        let at = match &query.at {
+            Some(hash) => parse_block_hash(hash)?,
+            None => self
+                .client
+                .backend()
+                .latest_finalized_block_ref()
+                .await
+                .map_err(|e| Error::Connection(format!("Failed to fetch latest block: {}", e)))?
+                .hash(),
+        };
+        Ok((prefix, at))
+    }
+
     /// Get metadata about a pallet
     #[allow(clippy::result_large_err)]
     pub fn get_pallet_metadata(&self, pallet: &str) -> Result<PalletMetadata> {
@@ -336,11 +510,26 @@ pub struct PalletMetadata {
     pub error_count: usize,
 }
 
+/// A page of storage entries
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoragePage<T> {
+    /// Entries in key order
+    pub items: Vec<T>,
+    /// Key to pass as [`start_key`](StorageQuery::start_key) for the next
+    /// page, `None` on the last page
+    pub next_key: Option<Vec<u8>>,
+    /// Hash of the block the page was read at
+    pub at: String,
+}
+
 /// Storage query helper
 pub struct StorageQuery {
     pallet: String,
     item: String,
     keys: Vec<subxt::dynamic::Value>,
+    at: Option<String>,
+    page_size: u32,
+    start_key: Option<Vec<u8>>,
 }
 
 impl StorageQuery {
@@ -350,6 +539,9 @@ impl StorageQuery {
             pallet: pallet.into(),
             item: item.into(),
             keys: Vec::new(),
+            at: None,
+            page_size: DEFAULT_STORAGE_PAGE_SIZE,
+            start_key: None,
         }
     }
 
@@ -365,12 +557,100 @@ impl StorageQuery {
         self
     }
 
+    /// Iterate at the block with hash `block_hash` instead of the latest
+    /// finalized one
+    pub fn at(mut self, block_hash: impl Into<String>) -> Self {
+        self.at = Some(block_hash.into());
+        self
+    }
+
+    /// Set the number of entries per page
+    pub fn page_size(mut self, page_size: u32) -> Self {
+        self.page_size = page_size;
+        self
+    }
+
+    /// Start iterating after the raw key `key`
+    pub fn start_key(mut self, key: Vec<u8>) -> Self {
+        self.start_key = Some(key);
+        self
+    }
+
+    /// Continue after `page`, at the block it was read at
+    pub fn after<T>(self, page: &StoragePage<T>) -> Option<Self> {
+        let next_key = page.next_key.clone()?;
+        Some(self.at(page.at.clone()).start_key(next_key))
+    }
+
+    /// Raw key of the queried entry
+    #[allow(clippy::result_large_err)]
+    pub fn storage_key(&self, client: &StorageClient) -> Result<Vec<u8>> {
+        client.storage_key(&self.pallet, &self.item, self.keys.clone())
+    }
+
     /// Execute the query (returns raw bytes)
     pub async fn execute(&self, client: &StorageClient) -> Result<Option<Vec<u8>>> {
         client
             .query_storage(&self.pallet, &self.item, self.keys.clone())
             .await
     }
+
+    /// Fetch one page of the matching keys and values
+    pub async fn iter(&self, client: &StorageClient) -> Result<StoragePage<(Vec<u8>, Vec<u8>)>> {
+        client.iter(self).await
+    }
+
+    /// Fetch one page of the matching keys
+    pub async fn iter_keys(&self, client: &StorageClient) -> Result<StoragePage<Vec<u8>>> {
+        client.iter_keys(self).await
+    }
+}
+
+/// Start key of the page after `keys`, `None` if the page was not full
+fn next_key(keys: &[Vec<u8>], page_size: u32) -> Option<Vec<u8>> {
+    if keys.len() < page_size as usize {
+        return None;
+    }
+    keys.last().cloned()
+}
+
+/// A new value of a watched storage entry
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StorageChange {
+    /// Hash of the block the value was read at
+    pub block_hash: String,
+    /// Raw storage key
+    pub key: Vec<u8>,
+    /// SCALE-encoded value, `None` if the entry does not exist
+    pub value: Option<Vec<u8>>,
+}
+
+/// Notification of `state_subscribeStorage`
+#[derive(Debug, serde::Deserialize)]
+pub(crate) struct StorageChangeSet {
+    block: String,
+    changes: Vec<(String, Option<String>)>,
+}
+
+impl StorageChangeSet {
+    /// Decode the hex-encoded keys and values
+    #[allow(clippy::result_large_err)]
+    pub(crate) fn into_changes(self) -> Result<Vec<StorageChange>> {
+        let decode = |hex_str: &str| {
+            hex::decode(hex_str.trim_start_matches("0x"))
+                .map_err(|e| Error::Storage(format!("Invalid storage change {}: {}", hex_str, e)))
+        };
+        self.changes
+            .iter()
+            .map(|(key, value)| {
+                Ok(StorageChange {
+                    block_hash: self.block.clone(),
+                    key: decode(key)?,
+                    value: value.as_deref().map(decode).transpose()?,
+                })
+            })
+            .collect()
+    }
 }
 
 // Helper function for parsing block hash from hex string
@@ -447,4 +727,49 @@ mod tests {
         assert_eq!(query.item, "Account");
         assert_eq!(query.keys.len(), 1);
     }
+
+    #[test]
+    fn test_storage_query_pagination() {
+        let query = StorageQuery::new("Staking", "Nominators").page_size(2);
+        assert_eq!(query.page_size, 2);
+        assert_eq!(query.start_key, None);
+
+        let keys = vec![vec![1], vec![2]];
+        assert_eq!(next_key(&keys, 2), Some(vec![2]));
+        assert_eq!(next_key(&keys, 3), None);
+        assert_eq!(next_key(&[], 2), None);
+
+        let page = StoragePage {
+            items: keys,
+            next_key: Some(vec![2]),
+            at: "0xabcd".to_string(),
+        };
+        let next = query.after(&page).unwrap();
+        assert_eq!(next.start_key, Some(vec![2]));
+        assert_eq!(next.at.as_deref(), Some("0xabcd"));
+
+        let last = StoragePage::<Vec<u8>> {
+            items: vec![],
+            next_key: None,
+            at: "0xabcd".to_string(),
+        };
+        assert!(StorageQuery::new("Staking", "Nominators")
+            .after(&last)
+            .is_none());
+    }
+
+    #[test]
+    fn test_storage_change_set() {
+        let set: StorageChangeSet = serde_json::from_str(
+            r#"{"block":"0xabcd","changes":[["0x26aa","0x0102"],["0x26ab",null]]}"#,
+        )
+        .unwrap();
+
+        let changes = set.into_changes().unwrap();
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0].block_hash, "0xabcd");
+        assert_eq!(changes[0].key, vec![0x26, 0xaa]);
+        assert_eq!(changes[0].value, Some(vec![1, 2]));
+        assert_eq!(changes[1].value, None);
+    }
 }
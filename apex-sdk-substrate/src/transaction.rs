@@ -6,10 +6,14 @@
 //! - Transaction signing
 //! - Retry logic with exponential backoff
 //! - Transaction confirmation tracking
+//! - Submission as a proxy of another account
 
+use crate::proxy::{proxy_call, ProxyType};
 use crate::{Error, Metrics, Result, Sr25519Signer, Wallet};
-use std::time::Duration;
-use subxt::{OnlineClient, PolkadotConfig};
+use apex_sdk_types::{Fee, Receipt, TransactionEvent};
+use parity_scale_codec::{Compact, Decode, Encode};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use subxt::{Metadata, OnlineClient, PolkadotConfig};
 use tokio::time::sleep;
 use tracing::{debug, info, warn};
 
@@ -139,12 +143,103 @@ impl RetryConfig {
     }
 }
 
+/// Details of a finalized extrinsic
+#[derive(Debug, Clone)]
+pub struct ExtrinsicReceipt {
+    /// Extrinsic hash (0x-prefixed hex)
+    pub hash: String,
+    /// Hash of the finalized block containing the extrinsic
+    pub block_hash: String,
+    /// Number of the finalized block containing the extrinsic
+    pub block_number: u64,
+    /// Events emitted by the extrinsic
+    pub events: Vec<TransactionEvent>,
+    /// Fee paid, from `TransactionPayment::TransactionFeePaid` when available
+    pub fee: Option<Fee>,
+    /// Dispatch error, from `System::ExtrinsicFailed`; `None` on success
+    pub error: Option<String>,
+    /// Unix timestamp (seconds) at which finalization was observed
+    pub finalized_at: u64,
+}
+
+impl ExtrinsicReceipt {
+    pub(crate) fn from_events(
+        hash: String,
+        block_hash: String,
+        block_number: u64,
+        events: &subxt::blocks::ExtrinsicEvents<PolkadotConfig>,
+        metadata: &Metadata,
+    ) -> Self {
+        use subxt::dynamic::At as _;
+
+        let mut decoded = Vec::new();
+        let mut fee = None;
+        let mut error = None;
+
+        for event in events.iter().flatten() {
+            let fields = event.field_values().ok();
+
+            if event.pallet_name() == "TransactionPayment"
+                && event.variant_name() == "TransactionFeePaid"
+            {
+                fee = fields
+                    .as_ref()
+                    .and_then(|f| f.at("actual_fee"))
+                    .and_then(|v| v.as_u128())
+                    .map(Fee::new);
+            }
+
+            if event.pallet_name() == "System" && event.variant_name() == "ExtrinsicFailed" {
+                error = Some(
+                    fields
+                        .as_ref()
+                        .and_then(|f| crate::tx_index::dispatch_error(f, metadata))
+                        .unwrap_or_else(|| "Extrinsic failed".to_string()),
+                );
+            }
+
+            decoded.push(TransactionEvent::Substrate {
+                pallet: event.pallet_name().to_string(),
+                variant: event.variant_name().to_string(),
+                fields: fields.map(|f| f.to_string()).unwrap_or_default(),
+            });
+        }
+
+        let finalized_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+
+        Self {
+            hash,
+            block_hash,
+            block_number,
+            events: decoded,
+            fee,
+            error,
+            finalized_at,
+        }
+    }
+
+    /// The receipt in the chain-independent [`Receipt`] form
+    pub fn to_receipt(&self) -> Receipt {
+        let mut receipt = Receipt::new(self.hash.clone())
+            .with_block(self.block_number, self.block_hash.clone())
+            .with_events(self.events.clone());
+        receipt.fee = self.fee.clone();
+        receipt.error = self.error.clone();
+        receipt
+    }
+}
+
 /// Transaction executor for building and submitting extrinsics
 pub struct TransactionExecutor {
     client: OnlineClient<PolkadotConfig>,
     fee_config: FeeConfig,
     retry_config: RetryConfig,
     metrics: Metrics,
+    /// Real account and forced proxy type to submit as a proxy for
+    proxy: Option<(String, Option<ProxyType>)>,
 }
 
 impl TransactionExecutor {
@@ -155,6 +250,7 @@ impl TransactionExecutor {
             fee_config: FeeConfig::default(),
             retry_config: RetryConfig::default(),
             metrics,
+            proxy: None,
         }
     }
 
@@ -170,8 +266,36 @@ impl TransactionExecutor {
         self
     }
 
+    /// Submit every call as a proxy of the SS58 account `real`
+    ///
+    /// Transfers, batches and [`submit_typed`](Self::submit_typed) calls are
+    /// wrapped in `Proxy.proxy` and dispatched with `real` as their origin;
+    /// the signing wallet must be a proxy of `real` (see
+    /// [`Proxy::add_proxy`](crate::Proxy::add_proxy)). With
+    /// `force_proxy_type`, only a proxy of that type is used.
+    pub fn with_proxy(
+        mut self,
+        real: impl Into<String>,
+        force_proxy_type: Option<ProxyType>,
+    ) -> Self {
+        self.proxy = Some((real.into(), force_proxy_type));
+        self
+    }
+
     /// Submit a balance transfer transaction
     pub async fn transfer(&self, from: &Wallet, to: &str, amount: u128) -> Result<String> {
+        self.transfer_with_receipt(from, to, amount)
+            .await
+            .map(|receipt| receipt.hash)
+    }
+
+    /// Submit a balance transfer and return the receipt of the finalized extrinsic
+    pub async fn transfer_with_receipt(
+        &self,
+        from: &Wallet,
+        to: &str,
+        amount: u128,
+    ) -> Result<ExtrinsicReceipt> {
         info!(
             "Submitting transfer from {} to {} of {} units",
             from.address(),
@@ -199,12 +323,257 @@ impl TransactionExecutor {
         self.submit_extrinsic_with_retry(&transfer_call, from).await
     }
 
-    /// Submit an extrinsic with retry logic
+    /// Submit an `Assets.transfer_keep_alive` of `asset_id` and return the
+    /// receipt of the finalized extrinsic
+    pub async fn transfer_asset_with_receipt(
+        &self,
+        from: &Wallet,
+        asset_id: u32,
+        to: &str,
+        amount: u128,
+    ) -> Result<ExtrinsicReceipt> {
+        use sp_core::crypto::Ss58Codec;
+        use subxt::dynamic::Value;
+
+        info!(
+            "Submitting transfer of asset {} from {} to {} of {} units",
+            asset_id,
+            from.address(),
+            to,
+            amount
+        );
+
+        let dest = sp_core::sr25519::Public::from_ss58check(to)
+            .map_err(|e| Error::Transaction(format!("Invalid destination address: {}", e)))?;
+        let transfer_call = subxt::dynamic::tx(
+            "Assets",
+            "transfer_keep_alive",
+            vec![
+                Value::u128(asset_id.into()),
+                Value::unnamed_variant("Id", vec![Value::from_bytes(dest.0)]),
+                Value::u128(amount),
+            ],
+        );
+        self.submit_extrinsic_with_retry(&transfer_call, from).await
+    }
+
+    /// Submit several transfers as one `Utility` batch and return the receipt
+    /// of the finalized extrinsic
+    ///
+    /// Transfers use `Balances.transfer_keep_alive`, or
+    /// `Assets.transfer_keep_alive` of `asset_id` when given. `batch_mode`
+    /// selects how a failing transfer affects the others.
+    #[allow(clippy::result_large_err)]
+    pub async fn batch_transfer_with_receipt(
+        &self,
+        from: &Wallet,
+        transfers: &[(String, u128)],
+        asset_id: Option<u32>,
+        batch_mode: BatchMode,
+    ) -> Result<ExtrinsicReceipt> {
+        use sp_core::crypto::Ss58Codec;
+        use subxt::dynamic::Value;
+
+        if transfers.is_empty() {
+            return Err(Error::Transaction("Cannot execute empty batch".to_string()));
+        }
+        info!(
+            "Submitting batch of {} transfers from {}",
+            transfers.len(),
+            from.address()
+        );
+
+        let calls = transfers
+            .iter()
+            .map(|(to, amount)| {
+                let dest = sp_core::sr25519::Public::from_ss58check(to).map_err(|e| {
+                    Error::Transaction(format!("Invalid destination address {}: {}", to, e))
+                })?;
+                let dest_value = Value::unnamed_variant("Id", vec![Value::from_bytes(dest.0)]);
+                let call = match asset_id {
+                    Some(id) => subxt::dynamic::tx(
+                        "Assets",
+                        "transfer_keep_alive",
+                        vec![Value::u128(id.into()), dest_value, Value::u128(*amount)],
+                    ),
+                    None => subxt::dynamic::tx(
+                        "Balances",
+                        "transfer_keep_alive",
+                        vec![dest_value, Value::u128(*amount)],
+                    ),
+                };
+                Ok(call.into_value())
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let batch_call_name = match batch_mode {
+            BatchMode::Optimistic => "batch",
+            BatchMode::AllOrNothing => "batch_all",
+            BatchMode::Force => "force_batch",
+        };
+        let batch = subxt::dynamic::tx(
+            "Utility",
+            batch_call_name,
+            vec![Value::unnamed_composite(calls)],
+        );
+        self.submit_extrinsic_with_retry(&batch, from).await
+    }
+
+    /// Submit a typed call payload and wait for finalization
+    ///
+    /// Accepts any subxt payload, typically one built from the generated
+    /// bindings in [`typed::api`](crate::typed) (`typed` feature). Uses the same
+    /// retry policy as [`transfer`](Self::transfer).
+    pub async fn submit_typed<Call>(
+        &self,
+        payload: &Call,
+        signer: &Wallet,
+    ) -> Result<ExtrinsicReceipt>
+    where
+        Call: subxt::tx::Payload,
+    {
+        self.submit_extrinsic_with_retry(payload, signer).await
+    }
+
+    /// Submit `call` on behalf of `real`, with `sponsor` signing and paying the fees
+    ///
+    /// The call is wrapped in `Proxy.proxy` and dispatched with `real` as its
+    /// origin, so `real` needs no balance for fees. `sponsor` must first be
+    /// registered as a proxy of `real` (see [`add_sponsor`](Self::add_sponsor)).
+    pub async fn submit_sponsored(
+        &self,
+        sponsor: &Wallet,
+        real: &str,
+        call: subxt::tx::DynamicPayload,
+    ) -> Result<ExtrinsicReceipt> {
+        info!(
+            "Submitting call for {} sponsored by {}",
+            real,
+            sponsor.address()
+        );
+        let proxied = proxy_call(real, None, call.into_value())?;
+        self.submit_unwrapped_with_retry(&proxied, sponsor).await
+    }
+
+    /// Register `sponsor` as a `proxy_type` proxy of `user`, allowing it to
+    /// [`submit_sponsored`](Self::submit_sponsored) calls for `user`
+    ///
+    /// `user` pays this call's fee and the proxy deposit once. A restrictive
+    /// `proxy_type` (e.g. `"NonTransfer"`) limits what the sponsor can do.
+    pub async fn add_sponsor(
+        &self,
+        user: &Wallet,
+        sponsor: &str,
+        proxy_type: &str,
+    ) -> Result<ExtrinsicReceipt> {
+        use sp_core::crypto::{AccountId32, Ss58Codec};
+        use subxt::dynamic::Value;
+
+        let sponsor_account = AccountId32::from_ss58check(sponsor)
+            .map_err(|e| Error::Transaction(format!("Invalid sponsor address: {}", e)))?;
+        let sponsor_bytes: &[u8] = sponsor_account.as_ref();
+
+        let call = subxt::dynamic::tx(
+            "Proxy",
+            "add_proxy",
+            vec![
+                Value::unnamed_variant("Id", vec![Value::from_bytes(sponsor_bytes)]),
+                Value::unnamed_variant(proxy_type, vec![]),
+                Value::u128(0),
+            ],
+        );
+        self.submit_unwrapped_with_retry(&call, user).await
+    }
+
+    /// Sign `payload` with an explicit nonce and tip and submit it without waiting
+    ///
+    /// Re-submitting the same call with the nonce of a pending extrinsic and a
+    /// higher tip replaces it in the transaction pool. Returns the extrinsic hash.
+    pub async fn submit_with_tip<Call>(
+        &self,
+        payload: &Call,
+        signer: &Wallet,
+        nonce: u64,
+        tip: u128,
+    ) -> Result<String>
+    where
+        Call: subxt::tx::Payload,
+    {
+        let pair = signer
+            .sr25519_pair()
+            .ok_or_else(|| Error::Transaction("Wallet does not have SR25519 key".to_string()))?;
+        let apex_signer = Sr25519Signer::new(pair.clone());
+
+        let params = subxt::config::DefaultExtrinsicParamsBuilder::<PolkadotConfig>::new()
+            .nonce(nonce)
+            .tip(tip)
+            .build();
+
+        self.metrics.record_transaction_attempt();
+        let hash = self
+            .client
+            .tx()
+            .sign_and_submit(payload, &apex_signer, params)
+            .await
+            .map_err(|e| Error::Transaction(format!("Failed to submit transaction: {}", e)))?;
+        Ok(format!("0x{}", hex::encode(hash)))
+    }
+
+    /// Submit an extrinsic with retry logic, as a proxy if configured
     async fn submit_extrinsic_with_retry<Call>(
         &self,
         call: &Call,
         signer: &Wallet,
-    ) -> Result<String>
+    ) -> Result<ExtrinsicReceipt>
+    where
+        Call: subxt::tx::Payload,
+    {
+        match &self.proxy {
+            Some((real, force_proxy_type)) => {
+                let call = self.as_proxy(call, real, force_proxy_type.as_ref())?;
+                self.submit_unwrapped_with_retry(&call, signer).await
+            }
+            None => self.submit_unwrapped_with_retry(call, signer).await,
+        }
+    }
+
+    /// `Proxy.proxy` dispatching `call` as `real`
+    ///
+    /// The call is encoded and decoded again as a runtime call value, so any
+    /// payload can be wrapped.
+    #[allow(clippy::result_large_err)]
+    fn as_proxy<Call>(
+        &self,
+        call: &Call,
+        real: &str,
+        force_proxy_type: Option<&ProxyType>,
+    ) -> Result<subxt::tx::DynamicPayload>
+    where
+        Call: subxt::tx::Payload,
+    {
+        let call_data = self
+            .client
+            .tx()
+            .call_data(call)
+            .map_err(|e| Error::Encoding(format!("Failed to encode call: {}", e)))?;
+        let metadata = self.client.metadata();
+        let call = subxt::ext::scale_value::scale::decode_as_type(
+            &mut &call_data[..],
+            metadata.outer_enums().call_enum_ty(),
+            metadata.types(),
+        )
+        .map_err(|e| Error::Encoding(format!("Failed to decode call: {}", e)))?
+        .remove_context();
+        info!("Submitting call as proxy of {}", real);
+        proxy_call(real, force_proxy_type, call)
+    }
+
+    /// Submit an extrinsic with retry logic
+    async fn submit_unwrapped_with_retry<Call>(
+        &self,
+        call: &Call,
+        signer: &Wallet,
+    ) -> Result<ExtrinsicReceipt>
     where
         Call: subxt::tx::Payload,
     {
@@ -216,9 +585,9 @@ impl TransactionExecutor {
             self.metrics.record_transaction_attempt();
 
             match self.submit_extrinsic(call, signer).await {
-                Ok(hash) => {
+                Ok(receipt) => {
                     self.metrics.record_transaction_success();
-                    return Ok(hash);
+                    return Ok(receipt);
                 }
                 Err(e) => {
                     if attempts >= self.retry_config.max_retries {
@@ -244,7 +613,7 @@ impl TransactionExecutor {
     }
 
     /// Submit an extrinsic and wait for it to be included in a block
-    async fn submit_extrinsic<Call>(&self, call: &Call, signer: &Wallet) -> Result<String>
+    async fn submit_extrinsic<Call>(&self, call: &Call, signer: &Wallet) -> Result<ExtrinsicReceipt>
     where
         Call: subxt::tx::Payload,
     {
@@ -278,14 +647,28 @@ impl TransactionExecutor {
             if let Some(finalized) = event.as_finalized() {
                 let tx_hash = format!("0x{}", hex::encode(finalized.extrinsic_hash()));
                 info!("Transaction finalized: {}", tx_hash);
+                let block_hash = finalized.block_hash();
+                let block_number = self
+                    .client
+                    .blocks()
+                    .at(block_hash)
+                    .await
+                    .map_err(|e| Error::Connection(format!("Failed to get block: {}", e)))?
+                    .number() as u64;
 
                 // Wait for success
-                finalized
+                let events = finalized
                     .wait_for_success()
                     .await
                     .map_err(|e| Error::Transaction(format!("Transaction failed: {}", e)))?;
 
-                return Ok(tx_hash);
+                return Ok(ExtrinsicReceipt::from_events(
+                    tx_hash,
+                    format!("0x{}", hex::encode(block_hash)),
+                    block_number,
+                    &events,
+                    &self.client.metadata(),
+                ));
             }
         }
 
@@ -329,7 +712,6 @@ impl TransactionExecutor {
         // Query fee details using state_call RPC
         // The TransactionPaymentApi_query_info runtime call provides fee information
         let call_data = {
-            use parity_scale_codec::Encode;
             // Prepare the runtime API call parameters
             // query_info(extrinsic: Vec<u8>, len: u32) -> RuntimeDispatchInfo
             let params = (encoded, encoded.len() as u32);
@@ -520,26 +902,14 @@ impl TransactionExecutor {
         wallet: &Wallet,
         batch_mode: BatchMode,
     ) -> Result<String> {
-        use sp_core::crypto::{AccountId32, Ss58Codec};
-
         // Convert transfers to BatchCalls
         let mut calls = Vec::new();
 
         for (recipient, amount) in transfers {
-            let to_account = AccountId32::from_ss58check(&recipient).map_err(|e| {
-                Error::Transaction(format!("Invalid recipient {}: {}", recipient, e))
-            })?;
-
-            let to_bytes: &[u8] = to_account.as_ref();
-
-            // Encode the transfer call arguments
-            use parity_scale_codec::Encode;
-            let args = (to_bytes, amount).encode();
-
             calls.push(BatchCall {
                 pallet_index: 5, // Balances pallet (typical index, may vary by chain)
                 call_index: 3,   // transfer_keep_alive (typical index, may vary by chain)
-                args_encoded: args,
+                args_encoded: encode_transfer_args(&recipient, amount)?,
             });
         }
 
@@ -547,6 +917,53 @@ impl TransactionExecutor {
     }
 }
 
+/// SCALE-encode the arguments of `Balances::transfer_keep_alive`
+///
+/// The recipient is an SS58 address, encoded as `MultiAddress::Id`, and the
+/// amount a compact balance.
+#[allow(clippy::result_large_err)]
+pub fn encode_transfer_args(recipient: &str, amount: u128) -> Result<Vec<u8>> {
+    use sp_core::crypto::{AccountId32, Ss58Codec};
+
+    let to_account = AccountId32::from_ss58check(recipient)
+        .map_err(|e| Error::Transaction(format!("Invalid recipient {}: {}", recipient, e)))?;
+    let to_bytes: &[u8; 32] = to_account.as_ref();
+
+    // MultiAddress::Id is variant 0
+    let mut args = vec![0];
+    args.extend_from_slice(to_bytes);
+    Compact(amount).encode_to(&mut args);
+    Ok(args)
+}
+
+/// Decode arguments built by [`encode_transfer_args`] into the recipient's
+/// account ID and the amount
+///
+/// Only the canonical encoding is accepted, so decoding and re-encoding
+/// gives back the same bytes.
+#[allow(clippy::result_large_err)]
+pub fn decode_transfer_args(mut bytes: &[u8]) -> Result<([u8; 32], u128)> {
+    let invalid = |e: parity_scale_codec::Error| {
+        Error::Encoding(format!("Invalid transfer arguments: {}", e))
+    };
+
+    let variant = u8::decode(&mut bytes).map_err(invalid)?;
+    if variant != 0 {
+        return Err(Error::Encoding(format!(
+            "Unsupported MultiAddress variant {}",
+            variant
+        )));
+    }
+    let account = <[u8; 32]>::decode(&mut bytes).map_err(invalid)?;
+    let amount = Compact::<u128>::decode(&mut bytes).map_err(invalid)?.0;
+    if !bytes.is_empty() {
+        return Err(Error::Encoding(
+            "Trailing bytes after transfer arguments".to_string(),
+        ));
+    }
+    Ok((account, amount))
+}
+
 /// Builder for constructing extrinsics
 #[allow(dead_code)]
 pub struct ExtrinsicBuilder {
@@ -608,6 +1025,8 @@ impl ExtrinsicBuilder {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
+    use sp_core::crypto::{AccountId32, Ss58AddressFormat, Ss58Codec};
 
     #[test]
     fn test_fee_config() {
@@ -631,6 +1050,53 @@ mod tests {
         assert_eq!(config.initial_delay, Duration::from_secs(1));
     }
 
+    #[test]
+    fn test_encode_transfer_args() {
+        // Alice, 1 DOT
+        let args = encode_transfer_args(
+            "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY",
+            10_000_000_000,
+        )
+        .unwrap();
+        assert_eq!(args[0], 0);
+        assert_eq!(
+            hex::encode(&args[1..33]),
+            "d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d"
+        );
+        assert_eq!(args[33..], [0x07, 0x00, 0xe4, 0x0b, 0x54, 0x02]);
+
+        assert!(encode_transfer_args("not an address", 1).is_err());
+        assert!(decode_transfer_args(&[1; 34]).is_err());
+        assert!(decode_transfer_args(&[args.as_slice(), &[0]].concat()).is_err());
+    }
+
+    proptest! {
+        #[test]
+        fn prop_transfer_args_roundtrip(
+            account in any::<[u8; 32]>(),
+            amount in any::<u128>(),
+            prefix in prop::sample::select(vec![0u16, 2, 42]),
+        ) {
+            let recipient = AccountId32::from(account)
+                .to_ss58check_with_version(Ss58AddressFormat::custom(prefix));
+            let args = encode_transfer_args(&recipient, amount).unwrap();
+            prop_assert_eq!(decode_transfer_args(&args).unwrap(), (account, amount));
+        }
+
+        #[test]
+        fn prop_decode_transfer_args_is_canonical(bytes in prop::collection::vec(any::<u8>(), 0..64)) {
+            if let Ok((account, amount)) = decode_transfer_args(&bytes) {
+                let recipient = AccountId32::from(account).to_ss58check();
+                prop_assert_eq!(encode_transfer_args(&recipient, amount).unwrap(), bytes);
+            }
+        }
+
+        #[test]
+        fn prop_encode_transfer_args_rejects_garbage(recipient in "\\PC*", amount in any::<u128>()) {
+            let _ = encode_transfer_args(&recipient, amount);
+        }
+    }
+
     #[test]
     fn test_extrinsic_builder() {
         // We can't test the full build without a client, but we can test the builder pattern
@@ -0,0 +1,441 @@
+//! In-memory index of finalized extrinsics
+//!
+//! A [`TxIndex`] follows finalized blocks in the background and records
+//! every extrinsic's hash, block and outcome, so transaction status lookups
+//! are answered from memory instead of walking back through recent blocks.
+//! The index only knows extrinsics finalized since it was started; older
+//! ones fall back to RPC.
+
+use crate::{collect_bytes, rpc_error, Error, Result};
+use apex_sdk_types::TransactionStatus;
+use parking_lot::RwLock;
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Weak};
+use std::time::Duration;
+use subxt::blocks::{Block, ExtrinsicDetails};
+use subxt::ext::scale_value::{Composite, Value, ValueDef};
+use subxt::{Metadata, OnlineClient, PolkadotConfig};
+use tracing::{debug, warn};
+
+/// Default number of extrinsics kept in a [`TxIndex`]
+pub const DEFAULT_TX_INDEX_CAPACITY: usize = 100_000;
+
+/// Delay before resubscribing after the finalized block subscription fails
+const RESUBSCRIBE_DELAY: Duration = Duration::from_secs(5);
+
+/// Result of an extrinsic, from its `System` events
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExtrinsicOutcome {
+    /// `System.ExtrinsicSuccess` was emitted
+    Success,
+    /// `System.ExtrinsicFailed` was emitted, with its decoded dispatch error
+    Failed(String),
+    /// Neither event was found
+    Unknown,
+}
+
+/// An extrinsic recorded by a [`TxIndex`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexedExtrinsic {
+    /// Finalized block containing the extrinsic
+    pub block_number: u64,
+    /// Position of the extrinsic in its block
+    pub index: u32,
+    /// What the extrinsic did
+    pub outcome: ExtrinsicOutcome,
+}
+
+/// Index of finalized extrinsics by hash, bounded to the most recent ones
+pub struct TxIndex {
+    capacity: usize,
+    entries: RwLock<Entries>,
+    head: AtomicU64,
+}
+
+#[derive(Default)]
+struct Entries {
+    by_hash: HashMap<[u8; 32], IndexedExtrinsic>,
+    order: VecDeque<[u8; 32]>,
+}
+
+impl TxIndex {
+    /// Create an empty index keeping up to `capacity` extrinsics
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            entries: RwLock::new(Entries::default()),
+            head: AtomicU64::new(0),
+        }
+    }
+
+    /// Most extrinsics kept; the oldest are evicted first
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Number of indexed extrinsics
+    pub fn len(&self) -> usize {
+        self.entries.read().by_hash.len()
+    }
+
+    /// Whether nothing has been indexed yet
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Highest finalized block indexed, 0 before the first one
+    pub fn head(&self) -> u64 {
+        self.head.load(Ordering::SeqCst)
+    }
+
+    /// Look up an extrinsic by hash
+    pub fn get(&self, hash: &[u8; 32]) -> Option<IndexedExtrinsic> {
+        self.entries.read().by_hash.get(hash).cloned()
+    }
+
+    /// Status of the extrinsic with `hash`, if it has been indexed
+    ///
+    /// Confirmations are counted from the indexed head.
+    pub fn status(&self, hash: &[u8; 32]) -> Option<TransactionStatus> {
+        let extrinsic = self.get(hash)?;
+        Some(match extrinsic.outcome {
+            ExtrinsicOutcome::Success => TransactionStatus::Confirmed {
+                block_number: extrinsic.block_number,
+                confirmations: self.head().saturating_sub(extrinsic.block_number) as u32,
+            },
+            ExtrinsicOutcome::Failed(error) => TransactionStatus::Failed { error },
+            ExtrinsicOutcome::Unknown => TransactionStatus::Unknown,
+        })
+    }
+
+    /// Record the extrinsics of finalized block `block_number`
+    pub fn insert_block(
+        &self,
+        block_number: u64,
+        extrinsics: impl IntoIterator<Item = ([u8; 32], IndexedExtrinsic)>,
+    ) {
+        let mut entries = self.entries.write();
+        for (hash, extrinsic) in extrinsics {
+            if entries.by_hash.insert(hash, extrinsic).is_none() {
+                entries.order.push_back(hash);
+            }
+        }
+        while entries.order.len() > self.capacity {
+            if let Some(oldest) = entries.order.pop_front() {
+                entries.by_hash.remove(&oldest);
+            }
+        }
+        self.head.fetch_max(block_number, Ordering::SeqCst);
+    }
+
+    /// Follow finalized blocks on `client` until the index is dropped
+    ///
+    /// Must be called within a Tokio runtime.
+    pub(crate) fn start(self: &Arc<Self>, client: OnlineClient<PolkadotConfig>) {
+        let index = Arc::downgrade(self);
+        tokio::spawn(async move {
+            loop {
+                match follow(&index, &client).await {
+                    Ok(()) => return,
+                    Err(e) => warn!("Transaction index follower failed, resubscribing: {}", e),
+                }
+                tokio::time::sleep(RESUBSCRIBE_DELAY).await;
+                if index.strong_count() == 0 {
+                    return;
+                }
+            }
+        });
+    }
+}
+
+/// Index finalized blocks until the index is dropped or the subscription fails
+async fn follow(index: &Weak<TxIndex>, client: &OnlineClient<PolkadotConfig>) -> Result<()> {
+    let mut blocks =
+        client.blocks().subscribe_finalized().await.map_err(|e| {
+            Error::Connection(rpc_error("Failed to subscribe to finalized blocks", e))
+        })?;
+
+    while let Some(block) = blocks.next().await {
+        let block = block
+            .map_err(|e| Error::Connection(rpc_error("Finalized block subscription failed", e)))?;
+        let extrinsics = block_extrinsics(&block, &client.metadata()).await?;
+        let Some(index) = index.upgrade() else {
+            return Ok(());
+        };
+        debug!(
+            "Indexed {} extrinsics in block {}",
+            extrinsics.len(),
+            block.number()
+        );
+        index.insert_block(block.number() as u64, extrinsics);
+    }
+
+    Err(Error::Connection(
+        "Finalized block subscription ended".to_string(),
+    ))
+}
+
+/// Hash and outcome of every extrinsic in `block`
+async fn block_extrinsics(
+    block: &Block<PolkadotConfig, OnlineClient<PolkadotConfig>>,
+    metadata: &Metadata,
+) -> Result<Vec<([u8; 32], IndexedExtrinsic)>> {
+    let extrinsics = block
+        .extrinsics()
+        .await
+        .map_err(|e| Error::Transaction(format!("Failed to get extrinsics: {}", e)))?;
+
+    let mut indexed = Vec::new();
+    for details in extrinsics.iter() {
+        indexed.push((
+            sp_core::blake2_256(details.bytes()),
+            IndexedExtrinsic {
+                block_number: block.number() as u64,
+                index: details.index(),
+                outcome: extrinsic_outcome(&details, metadata).await?,
+            },
+        ));
+    }
+    Ok(indexed)
+}
+
+/// Outcome of an extrinsic, from the `System` events it emitted
+///
+/// Dispatch errors are described using the pallet errors in `metadata`.
+pub(crate) async fn extrinsic_outcome(
+    details: &ExtrinsicDetails<PolkadotConfig, OnlineClient<PolkadotConfig>>,
+    metadata: &Metadata,
+) -> Result<ExtrinsicOutcome> {
+    let events = details
+        .events()
+        .await
+        .map_err(|e| Error::Transaction(format!("Failed to get events: {}", e)))?;
+
+    let mut outcome = ExtrinsicOutcome::Unknown;
+    for event in events.iter() {
+        let event =
+            event.map_err(|e| Error::Transaction(format!("Failed to decode event: {}", e)))?;
+        if event.pallet_name() == "System" {
+            match event.variant_name() {
+                "ExtrinsicSuccess" => outcome = ExtrinsicOutcome::Success,
+                "ExtrinsicFailed" => {
+                    let error = event
+                        .field_values()
+                        .ok()
+                        .and_then(|fields| dispatch_error(&fields, metadata))
+                        .unwrap_or_else(|| format!("Extrinsic {} failed", details.index()));
+                    outcome = ExtrinsicOutcome::Failed(error);
+                }
+                _ => {}
+            }
+        }
+    }
+    Ok(outcome)
+}
+
+/// Describe the `DispatchError` in the fields of an `ExtrinsicFailed` event,
+/// naming module errors after the pallet errors in `metadata`
+pub(crate) fn dispatch_error<T>(fields: &Composite<T>, metadata: &Metadata) -> Option<String> {
+    dispatch_error_message(fields, |pallet, error| {
+        let pallet = metadata.pallet_by_index(pallet)?;
+        let variant = pallet.error_variant_by_index(error)?;
+        Some(module_error_message(
+            pallet.name(),
+            &variant.name,
+            &variant.docs,
+        ))
+    })
+}
+
+/// Describe the `DispatchError` in the fields of an `ExtrinsicFailed` event
+///
+/// Module errors are described by `lookup(pallet_index, error_index)`;
+/// other errors by their variant names, e.g. `Token::FundsUnavailable`.
+fn dispatch_error_message<T>(
+    fields: &Composite<T>,
+    lookup: impl Fn(u8, u8) -> Option<String>,
+) -> Option<String> {
+    let error = field(fields, "dispatch_error")?;
+    let ValueDef::Variant(variant) = &error.value else {
+        return None;
+    };
+
+    if variant.name == "Module" {
+        let index = field(&variant.values, "index")?.as_u128()?;
+        let mut error = Vec::new();
+        collect_bytes(field(&variant.values, "error")?, &mut error);
+        let (index, error) = (u8::try_from(index).ok()?, *error.first()?);
+        return Some(
+            lookup(index, error)
+                .unwrap_or_else(|| format!("Module error {} in pallet {}", error, index)),
+        );
+    }
+
+    match variant.values.values().next().map(|value| &value.value) {
+        Some(ValueDef::Variant(inner)) => Some(format!("{}::{}", variant.name, inner.name)),
+        _ => Some(variant.name.clone()),
+    }
+}
+
+/// `Pallet::Error: docs`, or just `Pallet::Error` without docs
+fn module_error_message(pallet: &str, error: &str, docs: &[String]) -> String {
+    let docs = docs
+        .iter()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+    if docs.is_empty() {
+        format!("{}::{}", pallet, error)
+    } else {
+        format!("{}::{}: {}", pallet, error, docs)
+    }
+}
+
+/// Named field `name` of `composite`, looking inside a single unnamed wrapper
+fn field<'a, T>(composite: &'a Composite<T>, name: &str) -> Option<&'a Value<T>> {
+    match composite {
+        Composite::Named(fields) => fields
+            .iter()
+            .find(|(field, _)| field == name)
+            .map(|(_, value)| value),
+        Composite::Unnamed(values) => match values.as_slice() {
+            [Value {
+                value: ValueDef::Composite(inner),
+                ..
+            }] => field(inner, name),
+            _ => None,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn failed(dispatch_error: Value<()>) -> Composite<()> {
+        Composite::Named(vec![
+            ("dispatch_error".to_string(), dispatch_error),
+            (
+                "dispatch_info".to_string(),
+                Value::unnamed_composite(vec![]),
+            ),
+        ])
+    }
+
+    #[test]
+    fn test_module_dispatch_error() {
+        let fields = failed(Value::unnamed_variant(
+            "Module",
+            vec![Value::named_composite(vec![
+                ("index", Value::u128(5)),
+                (
+                    "error",
+                    Value::unnamed_composite(vec![
+                        Value::u128(2),
+                        Value::u128(0),
+                        Value::u128(0),
+                        Value::u128(0),
+                    ]),
+                ),
+            ])],
+        ));
+
+        let message = dispatch_error_message(&fields, |pallet, error| {
+            assert_eq!((pallet, error), (5, 2));
+            Some(module_error_message(
+                "Balances",
+                "InsufficientBalance",
+                &[" Balance too low to send value.".to_string()],
+            ))
+        });
+        assert_eq!(
+            message.as_deref(),
+            Some("Balances::InsufficientBalance: Balance too low to send value.")
+        );
+
+        let unknown = dispatch_error_message(&fields, |_, _| None);
+        assert_eq!(unknown.as_deref(), Some("Module error 2 in pallet 5"));
+    }
+
+    #[test]
+    fn test_other_dispatch_errors() {
+        let bad_origin = failed(Value::unnamed_variant("BadOrigin", vec![]));
+        assert_eq!(
+            dispatch_error_message(&bad_origin, |_, _| None).as_deref(),
+            Some("BadOrigin")
+        );
+
+        let token = failed(Value::unnamed_variant(
+            "Token",
+            vec![Value::unnamed_variant("FundsUnavailable", vec![])],
+        ));
+        assert_eq!(
+            dispatch_error_message(&token, |_, _| None).as_deref(),
+            Some("Token::FundsUnavailable")
+        );
+
+        assert_eq!(
+            dispatch_error_message(&Composite::<()>::Named(vec![]), |_, _| None),
+            None
+        );
+    }
+
+    fn extrinsic(block_number: u64, outcome: ExtrinsicOutcome) -> IndexedExtrinsic {
+        IndexedExtrinsic {
+            block_number,
+            index: 1,
+            outcome,
+        }
+    }
+
+    #[test]
+    fn test_status_lookup() {
+        let index = TxIndex::new(10);
+        assert!(index.is_empty());
+        index.insert_block(
+            5,
+            vec![
+                ([1; 32], extrinsic(5, ExtrinsicOutcome::Success)),
+                (
+                    [2; 32],
+                    extrinsic(5, ExtrinsicOutcome::Failed("Extrinsic 1 failed".into())),
+                ),
+            ],
+        );
+        index.insert_block(8, Vec::new());
+
+        assert_eq!(index.head(), 8);
+        assert_eq!(
+            index.status(&[1; 32]),
+            Some(TransactionStatus::Confirmed {
+                block_number: 5,
+                confirmations: 3,
+            })
+        );
+        assert!(matches!(
+            index.status(&[2; 32]),
+            Some(TransactionStatus::Failed { .. })
+        ));
+        assert_eq!(index.status(&[3; 32]), None);
+    }
+
+    #[test]
+    fn test_evicts_oldest() {
+        let index = TxIndex::new(2);
+        for block in 1..=3u8 {
+            index.insert_block(
+                block as u64,
+                vec![(
+                    [block; 32],
+                    extrinsic(block as u64, ExtrinsicOutcome::Success),
+                )],
+            );
+        }
+
+        assert_eq!(index.len(), 2);
+        assert!(index.get(&[1; 32]).is_none());
+        assert!(index.get(&[3; 32]).is_some());
+    }
+}
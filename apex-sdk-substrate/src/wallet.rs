@@ -1,19 +1,25 @@
 //! Substrate wallet and account management
 //!
 //! This module provides comprehensive wallet functionality including:
-//! - Key pair generation (SR25519, ED25519)
+//! - Key pair generation (SR25519, ED25519, ECDSA)
 //! - Mnemonic phrase support (BIP-39)
 //! - SS58 address encoding
 //! - Message and transaction signing
 //! - Multi-wallet management
+//! - Loading seeds from a [`SecretStore`]
+//! - Importing and exporting polkadot-js JSON keystores
 
 use crate::{Error, Result};
+use apex_sdk_core::secret::{SecretBytes, SecretError, SecretStore};
+use base64::Engine as _;
 use parking_lot::RwLock;
-use sp_core::crypto::{Ss58AddressFormat, Ss58Codec};
-use sp_core::{ed25519, sr25519, Pair as PairTrait};
+use serde::{Deserialize, Serialize};
+use sp_core::crypto::{AccountId32, Ss58AddressFormat, Ss58Codec};
+use sp_core::{ecdsa, ed25519, sr25519, Pair as PairTrait};
 use std::collections::HashMap;
 use std::sync::Arc;
 use tracing::{debug, info};
+use zeroize::Zeroizing;
 
 /// Supported key pair types
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -23,22 +29,50 @@ pub enum KeyPairType {
     Sr25519,
     /// ED25519 - Alternative signing algorithm
     Ed25519,
+    /// ECDSA over secp256k1
+    ///
+    /// The account is the blake2-256 hash of the compressed public key, as
+    /// with `MultiSigner::Ecdsa`. Chains with 20-byte Ethereum-style accounts
+    /// derive addresses differently.
+    Ecdsa,
 }
 
-/// A unified wallet that can hold either SR25519 or ED25519 keys
+/// Key pair of a [`Wallet`], scrubbed from memory when dropped
+///
+/// Wallets hold it behind an [`Arc`], so clones share one copy of the key
+/// material instead of duplicating it.
+pub struct SecretWallet {
+    /// SR25519 pair (if applicable)
+    sr25519: Option<sr25519::Pair>,
+    /// ED25519 pair (if applicable)
+    ed25519: Option<ed25519::Pair>,
+    /// ECDSA pair (if applicable), which wipes its secret on drop
+    ecdsa: Option<ecdsa::Pair>,
+}
+
+impl Drop for SecretWallet {
+    fn drop(&mut self) {
+        // schnorrkel zeroes sr25519 secrets itself. ed25519 pairs are plain
+        // `Copy` data, so overwrite ours with a pair from an all-zero seed.
+        if let Some(pair) = self.ed25519.as_mut() {
+            *pair = ed25519::Pair::from_seed(&[0u8; 32]);
+            std::hint::black_box(pair);
+        }
+    }
+}
+
+/// A unified wallet that can hold SR25519, ED25519 or ECDSA keys
 ///
 /// # Security
-/// This struct implements `Clone` to support wallet management operations.
-/// Be aware that cloning duplicates private key material in memory.
-/// For shared access without duplication, consider wrapping in `Arc<Wallet>`.
+/// Clones share the key material through an `Arc<SecretWallet>`, which is
+/// scrubbed when the last clone is dropped. Seeds and mnemonics passed to
+/// the constructors are only copied into buffers that are zeroed after use.
 #[derive(Clone)]
 pub struct Wallet {
     /// The key pair type
     key_type: KeyPairType,
-    /// SR25519 pair (if applicable)
-    sr25519_pair: Option<sr25519::Pair>,
-    /// ED25519 pair (if applicable)
-    ed25519_pair: Option<ed25519::Pair>,
+    /// The key pair, shared by clones
+    keys: Arc<SecretWallet>,
     /// SS58 address format (network prefix)
     ss58_format: Ss58AddressFormat,
 }
@@ -55,26 +89,59 @@ impl Wallet {
 
         match key_type {
             KeyPairType::Sr25519 => {
-                let (pair, _seed) = sr25519::Pair::generate();
-                Self {
-                    key_type,
-                    sr25519_pair: Some(pair),
-                    ed25519_pair: None,
-                    ss58_format: Ss58AddressFormat::custom(42), // Default to generic
-                }
+                let (pair, seed) = sr25519::Pair::generate();
+                drop(Zeroizing::new(seed));
+                Self::from_sr25519(pair)
             }
             KeyPairType::Ed25519 => {
-                let (pair, _seed) = ed25519::Pair::generate();
-                Self {
-                    key_type,
-                    sr25519_pair: None,
-                    ed25519_pair: Some(pair),
-                    ss58_format: Ss58AddressFormat::custom(42),
-                }
+                let (pair, seed) = ed25519::Pair::generate();
+                drop(Zeroizing::new(seed));
+                Self::from_ed25519(pair)
+            }
+            KeyPairType::Ecdsa => {
+                let (pair, seed) = ecdsa::Pair::generate();
+                drop(Zeroizing::new(seed));
+                Self::from_ecdsa(pair)
             }
         }
     }
 
+    fn from_sr25519(pair: sr25519::Pair) -> Self {
+        Self {
+            key_type: KeyPairType::Sr25519,
+            keys: Arc::new(SecretWallet {
+                sr25519: Some(pair),
+                ed25519: None,
+                ecdsa: None,
+            }),
+            ss58_format: Ss58AddressFormat::custom(42),
+        }
+    }
+
+    fn from_ed25519(pair: ed25519::Pair) -> Self {
+        Self {
+            key_type: KeyPairType::Ed25519,
+            keys: Arc::new(SecretWallet {
+                sr25519: None,
+                ed25519: Some(pair),
+                ecdsa: None,
+            }),
+            ss58_format: Ss58AddressFormat::custom(42),
+        }
+    }
+
+    fn from_ecdsa(pair: ecdsa::Pair) -> Self {
+        Self {
+            key_type: KeyPairType::Ecdsa,
+            keys: Arc::new(SecretWallet {
+                sr25519: None,
+                ed25519: None,
+                ecdsa: Some(pair),
+            }),
+            ss58_format: Ss58AddressFormat::custom(42),
+        }
+    }
+
     /// Create wallet from mnemonic phrase
     #[allow(clippy::result_large_err)]
     pub fn from_mnemonic(mnemonic: &str, key_type: KeyPairType) -> Result<Self> {
@@ -95,34 +162,30 @@ impl Wallet {
             .map_err(|e| Error::Wallet(format!("Invalid mnemonic: {}", e)))?;
 
         // Create derivation path string
-        let full_path = if let Some(p) = path {
+        let full_path = Zeroizing::new(if let Some(p) = path {
             format!("{}//{}", mnemonic, p)
         } else {
             mnemonic.to_string()
-        };
+        });
 
         match key_type {
             KeyPairType::Sr25519 => {
                 let pair = sr25519::Pair::from_string(&full_path, None)
                     .map_err(|e| Error::Wallet(format!("Failed to derive key: {:?}", e)))?;
 
-                Ok(Self {
-                    key_type,
-                    sr25519_pair: Some(pair),
-                    ed25519_pair: None,
-                    ss58_format: Ss58AddressFormat::custom(42),
-                })
+                Ok(Self::from_sr25519(pair))
             }
             KeyPairType::Ed25519 => {
                 let pair = ed25519::Pair::from_string(&full_path, None)
                     .map_err(|e| Error::Wallet(format!("Failed to derive key: {:?}", e)))?;
 
-                Ok(Self {
-                    key_type,
-                    sr25519_pair: None,
-                    ed25519_pair: Some(pair),
-                    ss58_format: Ss58AddressFormat::custom(42),
-                })
+                Ok(Self::from_ed25519(pair))
+            }
+            KeyPairType::Ecdsa => {
+                let pair = ecdsa::Pair::from_string(&full_path, None)
+                    .map_err(|e| Error::Wallet(format!("Failed to derive key: {:?}", e)))?;
+
+                Ok(Self::from_ecdsa(pair))
             }
         }
     }
@@ -136,40 +199,197 @@ impl Wallet {
             return Err(Error::Wallet("Seed must be 32 bytes".to_string()));
         }
 
-        let mut seed_array = [0u8; 32];
+        let mut seed_array = Zeroizing::new([0u8; 32]);
         seed_array.copy_from_slice(seed);
 
         match key_type {
             KeyPairType::Sr25519 => {
                 let pair = sr25519::Pair::from_seed(&seed_array);
-                Ok(Self {
-                    key_type,
-                    sr25519_pair: Some(pair),
-                    ed25519_pair: None,
-                    ss58_format: Ss58AddressFormat::custom(42),
-                })
+                Ok(Self::from_sr25519(pair))
             }
             KeyPairType::Ed25519 => {
                 let pair = ed25519::Pair::from_seed(&seed_array);
-                Ok(Self {
-                    key_type,
-                    sr25519_pair: None,
-                    ed25519_pair: Some(pair),
-                    ss58_format: Ss58AddressFormat::custom(42),
-                })
+                Ok(Self::from_ed25519(pair))
+            }
+            KeyPairType::Ecdsa => {
+                // Not every 32-byte string is a valid secp256k1 secret
+                let pair = ecdsa::Pair::from_seed_slice(&seed_array[..])
+                    .map_err(|e| Error::Wallet(format!("Invalid ECDSA seed: {:?}", e)))?;
+                Ok(Self::from_ecdsa(pair))
             }
         }
     }
 
+    /// Load a wallet whose 32-byte seed is held in a secret store
+    pub async fn from_secret_store(
+        store: &dyn SecretStore,
+        id: &str,
+        key_type: KeyPairType,
+    ) -> Result<Self> {
+        let seed = store
+            .get(id)
+            .await
+            .map_err(|e| Error::Wallet(format!("Failed to load seed {}: {}", id, e)))?;
+        Self::from_seed(seed.expose(), key_type)
+    }
+
+    /// Generate a new wallet and persist its seed in a secret store
+    ///
+    /// Unlike [`new_random_with_type`](Self::new_random_with_type), the seed is
+    /// kept (in the store) so the wallet can be reloaded with
+    /// [`from_secret_store`](Self::from_secret_store).
+    pub async fn generate_in_secret_store(
+        store: &dyn SecretStore,
+        id: &str,
+        key_type: KeyPairType,
+    ) -> Result<Self> {
+        use rand::RngCore;
+
+        let mut seed = vec![0u8; 32];
+        rand::rng().fill_bytes(&mut seed);
+        let seed = SecretBytes::new(seed);
+
+        store
+            .put(id, &seed)
+            .await
+            .map_err(|e| Error::Wallet(format!("Failed to store seed {}: {}", id, e)))?;
+        Self::from_seed(seed.expose(), key_type)
+    }
+
+    /// Load a wallet from a polkadot-js JSON keystore, as exported by the
+    /// browser extension or `@polkadot/keyring`
+    ///
+    /// Only scrypt-protected (version 3) sr25519 and ed25519 keystores are
+    /// supported. The SS58 format is taken from the keystore's address.
+    #[allow(clippy::result_large_err)]
+    pub fn from_polkadotjs_json(json: &str, password: &str) -> Result<Self> {
+        let keystore: PolkadotJsKeystore = serde_json::from_str(json)
+            .map_err(|e| Error::Wallet(format!("Invalid polkadot-js keystore: {}", e)))?;
+        let key_type = match keystore.encoding.content.get(1).map(String::as_str) {
+            Some("sr25519") => KeyPairType::Sr25519,
+            Some("ed25519") => KeyPairType::Ed25519,
+            other => {
+                return Err(Error::Wallet(format!(
+                    "Unsupported keystore key type: {:?}",
+                    other
+                )))
+            }
+        };
+        if keystore.encoding.version != "3"
+            || keystore.encoding.kind != ["scrypt", "xsalsa20-poly1305"]
+        {
+            return Err(Error::Wallet(format!(
+                "Unsupported keystore encoding: version {} {:?}",
+                keystore.encoding.version, keystore.encoding.kind
+            )));
+        }
+
+        let encoded = base64::engine::general_purpose::STANDARD
+            .decode(&keystore.encoded)
+            .map_err(|e| Error::Wallet(format!("Invalid keystore encoding: {}", e)))?;
+        let pkcs8 = decrypt_keystore(&encoded, password)?;
+        let (secret, public) = split_pkcs8(&pkcs8)?;
+
+        let wallet = match key_type {
+            KeyPairType::Sr25519 => {
+                let secret = schnorrkel::SecretKey::from_ed25519_bytes(secret)
+                    .map_err(|e| Error::Wallet(format!("Invalid sr25519 secret: {}", e)))?;
+                let pair = sr25519::Pair::from_seed_slice(&*Zeroizing::new(secret.to_bytes()))
+                    .map_err(|e| Error::Wallet(format!("Invalid sr25519 secret: {:?}", e)))?;
+                Self::from_sr25519(pair)
+            }
+            KeyPairType::Ed25519 => {
+                let pair = ed25519::Pair::from_seed_slice(&secret[..32])
+                    .map_err(|e| Error::Wallet(format!("Invalid ed25519 secret: {:?}", e)))?;
+                Self::from_ed25519(pair)
+            }
+            KeyPairType::Ecdsa => unreachable!("ecdsa keystores are rejected above"),
+        };
+        if wallet.public_key() != public {
+            return Err(Error::Wallet(
+                "Keystore public key does not match its secret".to_string(),
+            ));
+        }
+
+        match AccountId32::from_ss58check_with_version(&keystore.address) {
+            Ok((account, format)) if AsRef::<[u8]>::as_ref(&account) == public => {
+                Ok(wallet.with_ss58_format(format.prefix()))
+            }
+            Ok(_) => Err(Error::Wallet(format!(
+                "Keystore address {} does not match its key",
+                keystore.address
+            ))),
+            // Some tools leave the address out or store it as hex
+            Err(_) => Ok(wallet),
+        }
+    }
+
+    /// Export the wallet as a polkadot-js JSON keystore encrypted with
+    /// `password`, importable by the browser extension and `@polkadot/keyring`
+    #[allow(clippy::result_large_err)]
+    pub fn export_polkadotjs_json(&self, password: &str) -> Result<String> {
+        let (secret, content) = match self.key_type {
+            KeyPairType::Sr25519 => {
+                let raw = Zeroizing::new(self.keys.sr25519.as_ref().unwrap().to_raw_vec());
+                let secret = schnorrkel::SecretKey::from_bytes(&raw)
+                    .map_err(|e| Error::Wallet(format!("Invalid sr25519 secret: {}", e)))?;
+                let secret = Zeroizing::new(secret.to_ed25519_bytes());
+                (Zeroizing::new(secret.to_vec()), "sr25519")
+            }
+            KeyPairType::Ed25519 => {
+                // polkadot-js keeps the seed followed by the public key
+                let seed = Zeroizing::new(self.keys.ed25519.as_ref().unwrap().to_raw_vec());
+                (
+                    Zeroizing::new([&seed[..], &self.public_key()].concat()),
+                    "ed25519",
+                )
+            }
+            KeyPairType::Ecdsa => {
+                return Err(Error::Wallet(
+                    "polkadot-js keystores are only supported for sr25519 and ed25519 keys"
+                        .to_string(),
+                ))
+            }
+        };
+        let pkcs8 = Zeroizing::new(
+            [
+                &PKCS8_HEADER[..],
+                &secret[..],
+                &PKCS8_DIVIDER[..],
+                &self.public_key(),
+            ]
+            .concat(),
+        );
+        let encoded = encrypt_keystore(&pkcs8, password)?;
+
+        let keystore = PolkadotJsKeystore {
+            encoded: base64::engine::general_purpose::STANDARD.encode(encoded),
+            encoding: KeystoreEncoding {
+                content: vec!["pkcs8".to_string(), content.to_string()],
+                kind: vec!["scrypt".to_string(), "xsalsa20-poly1305".to_string()],
+                version: "3".to_string(),
+            },
+            address: self.address(),
+            meta: serde_json::json!({
+                "whenCreated": std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .map(|elapsed| elapsed.as_millis() as u64)
+                    .unwrap_or_default(),
+            }),
+        };
+        serde_json::to_string(&keystore)
+            .map_err(|e| Error::Wallet(format!("Failed to serialize keystore: {}", e)))
+    }
+
     /// Generate a new mnemonic phrase
     pub fn generate_mnemonic() -> String {
         use bip39::{Language, Mnemonic};
         use rand::RngCore;
 
-        let mut entropy = [0u8; 32];
-        rand::rng().fill_bytes(&mut entropy);
+        let mut entropy = Zeroizing::new([0u8; 32]);
+        rand::rng().fill_bytes(&mut entropy[..]);
 
-        Mnemonic::from_entropy_in(Language::English, &entropy)
+        Mnemonic::from_entropy_in(Language::English, &entropy[..])
             .expect("Failed to generate mnemonic")
             .to_string()
     }
@@ -181,27 +401,35 @@ impl Wallet {
     }
 
     /// Get the public key as bytes
+    ///
+    /// 33 bytes (compressed) for ECDSA, 32 bytes otherwise.
     pub fn public_key(&self) -> Vec<u8> {
         match self.key_type {
-            KeyPairType::Sr25519 => self.sr25519_pair.as_ref().unwrap().public().0.to_vec(),
-            KeyPairType::Ed25519 => self.ed25519_pair.as_ref().unwrap().public().0.to_vec(),
+            KeyPairType::Sr25519 => self.keys.sr25519.as_ref().unwrap().public().0.to_vec(),
+            KeyPairType::Ed25519 => self.keys.ed25519.as_ref().unwrap().public().0.to_vec(),
+            KeyPairType::Ecdsa => self.keys.ecdsa.as_ref().unwrap().public().0.to_vec(),
         }
     }
 
-    /// Get the SS58-encoded address
-    pub fn address(&self) -> String {
+    /// Get the 32-byte account ID
+    ///
+    /// The public key itself, except for ECDSA where it is the blake2-256
+    /// hash of the compressed public key.
+    pub fn account_id(&self) -> [u8; 32] {
         match self.key_type {
-            KeyPairType::Sr25519 => {
-                let public = self.sr25519_pair.as_ref().unwrap().public();
-                public.to_ss58check_with_version(self.ss58_format)
-            }
-            KeyPairType::Ed25519 => {
-                let public = self.ed25519_pair.as_ref().unwrap().public();
-                public.to_ss58check_with_version(self.ss58_format)
+            KeyPairType::Sr25519 => self.keys.sr25519.as_ref().unwrap().public().0,
+            KeyPairType::Ed25519 => self.keys.ed25519.as_ref().unwrap().public().0,
+            KeyPairType::Ecdsa => {
+                sp_core::blake2_256(&self.keys.ecdsa.as_ref().unwrap().public().0)
             }
         }
     }
 
+    /// Get the SS58-encoded address
+    pub fn address(&self) -> String {
+        AccountId32::from(self.account_id()).to_ss58check_with_version(self.ss58_format)
+    }
+
     /// Get the key pair type
     pub fn key_type(&self) -> KeyPairType {
         self.key_type
@@ -211,11 +439,17 @@ impl Wallet {
     pub fn sign(&self, message: &[u8]) -> Vec<u8> {
         match self.key_type {
             KeyPairType::Sr25519 => {
-                let pair = self.sr25519_pair.as_ref().unwrap();
+                let pair = self.keys.sr25519.as_ref().unwrap();
                 pair.sign(message).0.to_vec()
             }
             KeyPairType::Ed25519 => {
-                let pair = self.ed25519_pair.as_ref().unwrap();
+                let pair = self.keys.ed25519.as_ref().unwrap();
+                pair.sign(message).0.to_vec()
+            }
+            KeyPairType::Ecdsa => {
+                // 65 bytes: r, s and the recovery id, over the blake2-256
+                // hash of the message
+                let pair = self.keys.ecdsa.as_ref().unwrap();
                 pair.sign(message).0.to_vec()
             }
         }
@@ -231,7 +465,7 @@ impl Wallet {
                 let mut sig_array = [0u8; 64];
                 sig_array.copy_from_slice(signature);
                 let sig = sr25519::Signature::from_raw(sig_array);
-                let public = self.sr25519_pair.as_ref().unwrap().public();
+                let public = self.keys.sr25519.as_ref().unwrap().public();
                 sr25519::Pair::verify(&sig, message, &public)
             }
             KeyPairType::Ed25519 => {
@@ -241,9 +475,17 @@ impl Wallet {
                 let mut sig_array = [0u8; 64];
                 sig_array.copy_from_slice(signature);
                 let sig = ed25519::Signature::from_raw(sig_array);
-                let public = self.ed25519_pair.as_ref().unwrap().public();
+                let public = self.keys.ed25519.as_ref().unwrap().public();
                 ed25519::Pair::verify(&sig, message, &public)
             }
+            KeyPairType::Ecdsa => {
+                let Ok(sig_array) = <[u8; 65]>::try_from(signature) else {
+                    return false;
+                };
+                let sig = ecdsa::Signature::from_raw(sig_array);
+                let public = self.keys.ecdsa.as_ref().unwrap().public();
+                ecdsa::Pair::verify(&sig, message, &public)
+            }
         }
     }
 
@@ -259,18 +501,146 @@ impl Wallet {
                 // ED25519 also doesn't expose seed directly
                 None
             }
+            KeyPairType::Ecdsa => None,
         }
     }
 
     /// Get the SR25519 pair for signing (if this is an SR25519 wallet)
     pub fn sr25519_pair(&self) -> Option<&sr25519::Pair> {
-        self.sr25519_pair.as_ref()
+        self.keys.sr25519.as_ref()
     }
 
     /// Get the ED25519 pair for signing (if this is an ED25519 wallet)
     pub fn ed25519_pair(&self) -> Option<&ed25519::Pair> {
-        self.ed25519_pair.as_ref()
+        self.keys.ed25519.as_ref()
     }
+
+    /// Get the ECDSA pair for signing (if this is an ECDSA wallet)
+    pub fn ecdsa_pair(&self) -> Option<&ecdsa::Pair> {
+        self.keys.ecdsa.as_ref()
+    }
+}
+
+/// scrypt parameters polkadot-js encrypts keystores with (N = 2^15, p = 1,
+/// r = 8); keystores declaring anything else are rejected rather than
+/// letting the file pick the work factor
+const POLKADOTJS_SCRYPT_LOG_N: u8 = 15;
+const POLKADOTJS_SCRYPT_P: u32 = 1;
+const POLKADOTJS_SCRYPT_R: u32 = 8;
+
+/// PKCS#8 framing polkadot-js puts around the secret and public key
+const PKCS8_HEADER: [u8; 16] = [48, 83, 2, 1, 1, 48, 5, 6, 3, 43, 101, 112, 4, 34, 4, 32];
+const PKCS8_DIVIDER: [u8; 5] = [161, 35, 3, 33, 0];
+
+/// A polkadot-js JSON keystore
+#[derive(Serialize, Deserialize)]
+struct PolkadotJsKeystore {
+    encoded: String,
+    encoding: KeystoreEncoding,
+    #[serde(default)]
+    address: String,
+    #[serde(default)]
+    meta: serde_json::Value,
+}
+
+#[derive(Serialize, Deserialize)]
+struct KeystoreEncoding {
+    content: Vec<String>,
+    #[serde(rename = "type")]
+    kind: Vec<String>,
+    version: String,
+}
+
+/// Encrypt `plaintext` the way polkadot-js does: scrypt parameters, then
+/// the xsalsa20-poly1305 nonce and ciphertext
+#[allow(clippy::result_large_err)]
+fn encrypt_keystore(plaintext: &[u8], password: &str) -> Result<Vec<u8>> {
+    use crypto_secretbox::aead::{Aead, KeyInit};
+    use rand::RngCore;
+
+    let mut salt = [0u8; 32];
+    let mut nonce = [0u8; 24];
+    rand::rng().fill_bytes(&mut salt);
+    rand::rng().fill_bytes(&mut nonce);
+
+    let key = keystore_key(password, &salt)?;
+    let ciphertext = crypto_secretbox::XSalsa20Poly1305::new(&(*key).into())
+        .encrypt(&nonce.into(), plaintext)
+        .map_err(|_| Error::Wallet("Failed to encrypt keystore".to_string()))?;
+
+    let mut encoded = salt.to_vec();
+    for param in [
+        1u32 << POLKADOTJS_SCRYPT_LOG_N,
+        POLKADOTJS_SCRYPT_P,
+        POLKADOTJS_SCRYPT_R,
+    ] {
+        encoded.extend_from_slice(&param.to_le_bytes());
+    }
+    encoded.extend_from_slice(&nonce);
+    encoded.extend_from_slice(&ciphertext);
+    Ok(encoded)
+}
+
+/// Reverse [`encrypt_keystore`]
+#[allow(clippy::result_large_err)]
+fn decrypt_keystore(encoded: &[u8], password: &str) -> Result<Zeroizing<Vec<u8>>> {
+    use crypto_secretbox::aead::{Aead, KeyInit};
+
+    if encoded.len() < 32 + 12 + 24 {
+        return Err(Error::Wallet("Keystore is too short".to_string()));
+    }
+    let param = |at: usize| u32::from_le_bytes(encoded[at..at + 4].try_into().unwrap());
+    let (n, p, r) = (param(32), param(36), param(40));
+    if (n, p, r)
+        != (
+            1 << POLKADOTJS_SCRYPT_LOG_N,
+            POLKADOTJS_SCRYPT_P,
+            POLKADOTJS_SCRYPT_R,
+        )
+    {
+        return Err(Error::Wallet(format!(
+            "Unsupported keystore scrypt parameters: N={}, p={}, r={}",
+            n, p, r
+        )));
+    }
+
+    let key = keystore_key(password, &encoded[..32])?;
+    let nonce: [u8; 24] = encoded[44..68].try_into().unwrap();
+    crypto_secretbox::XSalsa20Poly1305::new(&(*key).into())
+        .decrypt(&nonce.into(), &encoded[68..])
+        .map(Zeroizing::new)
+        .map_err(|_| Error::Wallet("Wrong keystore password".to_string()))
+}
+
+#[allow(clippy::result_large_err)]
+fn keystore_key(password: &str, salt: &[u8]) -> Result<Zeroizing<[u8; 32]>> {
+    let params = scrypt::Params::new(
+        POLKADOTJS_SCRYPT_LOG_N,
+        POLKADOTJS_SCRYPT_R,
+        POLKADOTJS_SCRYPT_P,
+    )
+    .map_err(|e| Error::Wallet(format!("Invalid keystore scrypt parameters: {}", e)))?;
+    let mut key = Zeroizing::new([0u8; 32]);
+    scrypt::scrypt(password.as_bytes(), salt, &params, &mut key[..])
+        .map_err(|e| Error::Wallet(format!("Failed to derive keystore key: {}", e)))?;
+    Ok(key)
+}
+
+/// Split a decrypted polkadot-js PKCS#8 key into the 64-byte secret and the
+/// public key
+#[allow(clippy::result_large_err)]
+fn split_pkcs8(pkcs8: &[u8]) -> Result<(&[u8], &[u8])> {
+    let divider = PKCS8_HEADER.len() + 64;
+    if pkcs8.len() != divider + PKCS8_DIVIDER.len() + 32
+        || pkcs8[..PKCS8_HEADER.len()] != PKCS8_HEADER
+        || pkcs8[divider..divider + PKCS8_DIVIDER.len()] != PKCS8_DIVIDER
+    {
+        return Err(Error::Wallet("Malformed keystore key".to_string()));
+    }
+    Ok((
+        &pkcs8[PKCS8_HEADER.len()..divider],
+        &pkcs8[divider + PKCS8_DIVIDER.len()..],
+    ))
 }
 
 impl std::fmt::Debug for Wallet {
@@ -284,18 +654,25 @@ impl std::fmt::Debug for Wallet {
 }
 
 /// Manager for multiple wallets
+///
+/// With a [`SecretStore`] attached, wallets can be persisted as
+/// password-encrypted polkadot-js keystores and reloaded on the next run.
+/// [`load`](Self::load) only reads the keystores; each wallet is decrypted
+/// when [`unlock`](Self::unlock)ed. To encrypt everything under one master
+/// key as well, attach a `KmsSecretStore`.
 pub struct WalletManager {
     wallets: Arc<RwLock<HashMap<String, Wallet>>>,
     default_key_type: KeyPairType,
+    store: Option<Arc<dyn SecretStore>>,
+    namespace: String,
+    /// Keystore JSON of persisted wallets, by name
+    keystores: RwLock<HashMap<String, String>>,
 }
 
 impl WalletManager {
     /// Create a new wallet manager
     pub fn new() -> Self {
-        Self {
-            wallets: Arc::new(RwLock::new(HashMap::new())),
-            default_key_type: KeyPairType::Sr25519,
-        }
+        Self::with_key_type(KeyPairType::Sr25519)
     }
 
     /// Create a new wallet manager with default key type
@@ -303,9 +680,145 @@ impl WalletManager {
         Self {
             wallets: Arc::new(RwLock::new(HashMap::new())),
             default_key_type: key_type,
+            store: None,
+            namespace: "wallets".to_string(),
+            keystores: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Persist wallets in `store`, under ids starting with `namespace`
+    pub fn with_store(mut self, store: Arc<dyn SecretStore>, namespace: impl Into<String>) -> Self {
+        self.store = Some(store);
+        self.namespace = namespace.into();
+        self
+    }
+
+    /// Encrypt wallet `name` with `password` and write it to the store
+    pub async fn save(&self, name: &str, password: &str) -> Result<()> {
+        let wallet = self
+            .get_wallet(name)
+            .ok_or_else(|| Error::Wallet(format!("Unknown wallet: {}", name)))?;
+        let json = wallet.export_polkadotjs_json(password)?;
+        self.store()?
+            .put(
+                &self.keystore_id(name),
+                &SecretBytes::new(json.clone().into_bytes()),
+            )
+            .await
+            .map_err(|e| Error::Wallet(format!("Failed to save wallet {}: {}", name, e)))?;
+        self.keystores.write().insert(name.to_string(), json);
+        self.write_index().await
+    }
+
+    /// Save every unlocked wallet, all encrypted with one master `password`
+    pub async fn save_all(&self, password: &str) -> Result<()> {
+        for name in self.list_wallets() {
+            self.save(&name, password).await?;
+        }
+        Ok(())
+    }
+
+    /// Read the persisted wallets from the store, returning their names
+    ///
+    /// The wallets stay locked until [`unlock`](Self::unlock)ed.
+    pub async fn load(&self) -> Result<Vec<String>> {
+        let store = self.store()?;
+        let names = match store.get(&self.index_id()).await {
+            Ok(index) => serde_json::from_slice::<Vec<String>>(index.expose())
+                .map_err(|e| Error::Wallet(format!("Corrupt wallet index: {}", e)))?,
+            Err(SecretError::NotFound(_)) => Vec::new(),
+            Err(e) => return Err(Error::Wallet(format!("Failed to read wallet index: {}", e))),
+        };
+
+        let mut keystores = HashMap::new();
+        for name in &names {
+            let json = store
+                .get(&self.keystore_id(name))
+                .await
+                .map_err(|e| Error::Wallet(format!("Failed to load wallet {}: {}", name, e)))?;
+            let json = String::from_utf8(json.expose().to_vec())
+                .map_err(|_| Error::Wallet(format!("Corrupt keystore for wallet {}", name)))?;
+            keystores.insert(name.clone(), json);
+        }
+        debug!("Loaded {} persisted wallets", keystores.len());
+        *self.keystores.write() = keystores;
+        Ok(names)
+    }
+
+    /// Decrypt persisted wallet `name` with `password` and add it to the
+    /// manager
+    #[allow(clippy::result_large_err)]
+    pub fn unlock(&self, name: &str, password: &str) -> Result<Wallet> {
+        let json = self
+            .keystores
+            .read()
+            .get(name)
+            .cloned()
+            .ok_or_else(|| Error::Wallet(format!("No persisted wallet {}", name)))?;
+        let wallet = Wallet::from_polkadotjs_json(&json, password)?;
+        debug!("Unlocked wallet '{}' at address {}", name, wallet.address());
+        self.wallets
+            .write()
+            .insert(name.to_string(), wallet.clone());
+        Ok(wallet)
+    }
+
+    /// Drop the decrypted keys of persisted wallet `name`; it can be
+    /// unlocked again
+    pub fn lock(&self, name: &str) {
+        if self.keystores.read().contains_key(name) {
+            self.wallets.write().remove(name);
         }
     }
 
+    /// Names of persisted wallets that are not unlocked
+    pub fn locked_wallets(&self) -> Vec<String> {
+        let wallets = self.wallets.read();
+        self.keystores
+            .read()
+            .keys()
+            .filter(|name| !wallets.contains_key(*name))
+            .cloned()
+            .collect()
+    }
+
+    /// Remove wallet `name` from the manager and the store
+    pub async fn delete(&self, name: &str) -> Result<()> {
+        self.wallets.write().remove(name);
+        self.keystores.write().remove(name);
+        self.store()?
+            .delete(&self.keystore_id(name))
+            .await
+            .map_err(|e| Error::Wallet(format!("Failed to delete wallet {}: {}", name, e)))?;
+        self.write_index().await
+    }
+
+    #[allow(clippy::result_large_err)]
+    fn store(&self) -> Result<&Arc<dyn SecretStore>> {
+        self.store
+            .as_ref()
+            .ok_or_else(|| Error::Wallet("No wallet store configured".to_string()))
+    }
+
+    fn index_id(&self) -> String {
+        format!("{}/index", self.namespace)
+    }
+
+    fn keystore_id(&self, name: &str) -> String {
+        format!("{}/keystores/{}", self.namespace, name)
+    }
+
+    async fn write_index(&self) -> Result<()> {
+        let mut names: Vec<String> = self.keystores.read().keys().cloned().collect();
+        names.sort();
+        let index = serde_json::to_vec(&names)
+            .map_err(|e| Error::Wallet(format!("Failed to serialize wallet index: {}", e)))?;
+        self.store()?
+            .put(&self.index_id(), &SecretBytes::new(index))
+            .await
+            .map_err(|e| Error::Wallet(format!("Failed to write wallet index: {}", e)))
+    }
+
     /// Create and add a new random wallet
     pub fn create_wallet(&self, name: impl Into<String>) -> Wallet {
         let wallet = Wallet::new_random_with_type(self.default_key_type);
@@ -361,6 +874,7 @@ impl Default for WalletManager {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use apex_sdk_core::secret::MemorySecretStore;
 
     #[test]
     fn test_create_random_wallet() {
@@ -467,4 +981,158 @@ mod tests {
         // resulting in distinct key pairs and thus different addresses.
         assert_ne!(sr25519_wallet.address(), ed25519_wallet.address());
     }
+
+    #[test]
+    fn test_ecdsa_wallet() {
+        let mnemonic = "bottom drive obey lake curtain smoke basket hold race lonely fit walk";
+        let alice =
+            Wallet::from_mnemonic_with_path(mnemonic, Some("Alice"), KeyPairType::Ecdsa).unwrap();
+
+        assert_eq!(
+            hex::encode(alice.public_key()),
+            "020a1091341fe5664bfa1782d5e04779689068c916b04cb365ec3153755684d9a1"
+        );
+        assert_eq!(
+            alice.address(),
+            "5C7C2Z5sWbytvHpuLTvzKunnnRwQxft1jiqrLD5rhucQ5S9X"
+        );
+
+        let signature = alice.sign(b"Hello, Substrate!");
+        assert_eq!(signature.len(), 65);
+        assert!(alice.verify(b"Hello, Substrate!", &signature));
+        assert!(!alice.verify(b"Different message", &signature));
+        assert!(!alice.verify(b"Hello, Substrate!", &signature[..64]));
+
+        assert!(Wallet::from_seed(&[0u8; 32], KeyPairType::Ecdsa).is_err());
+        assert!(alice.export_polkadotjs_json("password").is_err());
+    }
+
+    #[test]
+    fn test_clones_share_key_material() {
+        let wallet = Wallet::new_random_with_type(KeyPairType::Ed25519);
+        let clone = wallet.clone().with_ss58_format(0);
+        assert!(Arc::ptr_eq(&wallet.keys, &clone.keys));
+        assert_eq!(wallet.public_key(), clone.public_key());
+    }
+
+    #[test]
+    fn test_polkadotjs_json_roundtrip() {
+        for key_type in [KeyPairType::Sr25519, KeyPairType::Ed25519] {
+            let wallet = Wallet::new_random_with_type(key_type).with_ss58_format(0);
+            let json = wallet.export_polkadotjs_json("correct horse").unwrap();
+
+            let keystore: serde_json::Value = serde_json::from_str(&json).unwrap();
+            assert_eq!(keystore["address"], wallet.address());
+            assert_eq!(keystore["encoding"]["version"], "3");
+
+            let imported = Wallet::from_polkadotjs_json(&json, "correct horse").unwrap();
+            assert_eq!(imported.key_type(), key_type);
+            assert_eq!(imported.address(), wallet.address());
+            let signature = imported.sign(b"message");
+            assert!(wallet.verify(b"message", &signature));
+
+            assert!(Wallet::from_polkadotjs_json(&json, "wrong").is_err());
+        }
+    }
+
+    #[test]
+    fn test_polkadotjs_json_rejects_unsupported() {
+        let wallet = Wallet::new_random();
+        let json = wallet.export_polkadotjs_json("pw").unwrap();
+
+        let mut keystore: serde_json::Value = serde_json::from_str(&json).unwrap();
+        keystore["encoding"]["content"][1] = "ethereum".into();
+        assert!(Wallet::from_polkadotjs_json(&keystore.to_string(), "pw").is_err());
+
+        let mut keystore: serde_json::Value = serde_json::from_str(&json).unwrap();
+        keystore["address"] = Wallet::new_random().address().into();
+        assert!(Wallet::from_polkadotjs_json(&keystore.to_string(), "pw").is_err());
+
+        assert!(split_pkcs8(&[0u8; 117]).is_err());
+    }
+
+    #[test]
+    fn test_polkadotjs_json_fixture() {
+        // Encrypted outside this crate with polkadot-js's parameters
+        // (scrypt N = 2^15, p = 1, r = 8) from a known sr25519 key
+        let json = include_str!("../tests/fixtures/polkadotjs_sr25519.json");
+        let wallet = Wallet::from_polkadotjs_json(json, "apex-fixture").unwrap();
+        assert_eq!(wallet.key_type(), KeyPairType::Sr25519);
+        assert_eq!(
+            hex::encode(wallet.public_key()),
+            "46ebddef8cd9bb167dc30878d7113b7e168e6f0646beffd77d69d39bad76b47a"
+        );
+        assert_eq!(
+            wallet.address(),
+            "12bzRJfh7arnnfPPUZHeJUaE62QLEwhK48QnH9LXeK2m1iZU"
+        );
+        assert!(Wallet::from_polkadotjs_json(json, "wrong").is_err());
+
+        // Any other scrypt cost is refused before deriving a key
+        let mut keystore: serde_json::Value = serde_json::from_str(json).unwrap();
+        let engine = base64::engine::general_purpose::STANDARD;
+        let mut encoded = engine
+            .decode(keystore["encoded"].as_str().unwrap())
+            .unwrap();
+        encoded[32..36].copy_from_slice(&(1u32 << 30).to_le_bytes());
+        keystore["encoded"] = engine.encode(&encoded).into();
+        let err = Wallet::from_polkadotjs_json(&keystore.to_string(), "apex-fixture").unwrap_err();
+        assert!(err.to_string().contains("scrypt parameters"));
+    }
+
+    #[tokio::test]
+    async fn test_wallet_manager_persistence() {
+        let store: Arc<dyn SecretStore> = Arc::new(MemorySecretStore::new());
+        let manager = WalletManager::new().with_store(store.clone(), "cli");
+        let alice = manager.create_wallet("alice");
+        let bob = manager.create_wallet("bob");
+        manager.save("alice", "alice-pw").await.unwrap();
+        manager.save("bob", "bob-pw").await.unwrap();
+        assert!(manager.save("carol", "pw").await.is_err());
+
+        // A fresh run sees both wallets, locked until unlocked
+        let manager = WalletManager::new().with_store(store.clone(), "cli");
+        let mut names = manager.load().await.unwrap();
+        names.sort();
+        assert_eq!(names, vec!["alice", "bob"]);
+        assert_eq!(manager.wallet_count(), 0);
+        assert!(manager.get_wallet("alice").is_none());
+
+        assert!(manager.unlock("alice", "bob-pw").is_err());
+        let unlocked = manager.unlock("alice", "alice-pw").unwrap();
+        assert_eq!(unlocked.address(), alice.address());
+        assert_eq!(manager.locked_wallets(), vec!["bob".to_string()]);
+
+        manager.lock("alice");
+        assert!(manager.get_wallet("alice").is_none());
+        assert_eq!(manager.locked_wallets().len(), 2);
+
+        manager.delete("alice").await.unwrap();
+        let manager = WalletManager::new().with_store(store, "cli");
+        assert_eq!(manager.load().await.unwrap(), vec!["bob".to_string()]);
+        assert_eq!(
+            manager.unlock("bob", "bob-pw").unwrap().address(),
+            bob.address()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_secret_store_roundtrip() {
+        let store = MemorySecretStore::new();
+        let wallet =
+            Wallet::generate_in_secret_store(&store, "substrate/signer", KeyPairType::Sr25519)
+                .await
+                .unwrap();
+
+        let loaded = Wallet::from_secret_store(&store, "substrate/signer", KeyPairType::Sr25519)
+            .await
+            .unwrap();
+        assert_eq!(loaded.address(), wallet.address());
+
+        assert!(
+            Wallet::from_secret_store(&store, "substrate/missing", KeyPairType::Sr25519)
+                .await
+                .is_err()
+        );
+    }
 }
@@ -30,7 +30,7 @@
 //!     .await?;
 //! ```
 
-use crate::{Error, Result, Sr25519Signer, Wallet};
+use crate::{Error, Metrics, Result, Sr25519Signer, TransactionExecutor, Wallet};
 use subxt::{OnlineClient, PolkadotConfig};
 use tracing::{debug, info};
 
@@ -101,6 +101,14 @@ impl MultiLocation {
         }
     }
 
+    /// Create a MultiLocation for a 20-byte (EVM) account on the current chain
+    pub fn account20(key: [u8; 20]) -> Self {
+        Self {
+            parents: 0,
+            interior: vec![Junction::AccountId20 { network: None, key }],
+        }
+    }
+
     /// Create a MultiLocation for an account on a specific parachain
     pub fn parachain_account(para_id: u32, account_id: [u8; 32]) -> Self {
         Self {
@@ -314,29 +322,67 @@ impl XcmExecutor {
         beneficiary: [u8; 32],
         assets: Vec<XcmAsset>,
     ) -> Result<String> {
-        info!("Executing reserve transfer to {:?} for beneficiary", dest);
+        self.reserve_transfer_to(wallet, dest, MultiLocation::account(beneficiary), assets)
+            .await
+    }
 
-        // Build the reserve transfer call using dynamic API
-        let dest_value = self.encode_multilocation(&dest)?;
-        let beneficiary_value = self.encode_multilocation(&MultiLocation::account(beneficiary))?;
-        let assets_value = self.encode_assets(&assets)?;
-        let fee_index = 0u32; // Use first asset for fees
+    /// Execute a reserve transfer to any beneficiary location
+    ///
+    /// Like [`reserve_transfer`](Self::reserve_transfer), but the beneficiary
+    /// may be e.g. a 20-byte account ([`MultiLocation::account20`]).
+    pub async fn reserve_transfer_to(
+        &self,
+        wallet: &Wallet,
+        dest: MultiLocation,
+        beneficiary: MultiLocation,
+        assets: Vec<XcmAsset>,
+    ) -> Result<String> {
+        info!("Executing reserve transfer to {:?} for beneficiary", dest);
 
         let call = subxt::dynamic::tx(
             "XcmPallet",
             "limited_reserve_transfer_assets",
-            vec![
-                dest_value,
-                beneficiary_value,
-                assets_value,
-                subxt::dynamic::Value::u128(fee_index as u128),
-                self.encode_weight_limit()?,
-            ],
+            self.reserve_transfer_args(&dest, &beneficiary, &assets)?,
         );
 
         self.submit_xcm_call(&call, wallet).await
     }
 
+    /// Estimate the source-chain fee of a reserve transfer
+    pub async fn estimate_reserve_transfer_fee(
+        &self,
+        wallet: &Wallet,
+        dest: MultiLocation,
+        beneficiary: MultiLocation,
+        assets: Vec<XcmAsset>,
+    ) -> Result<u128> {
+        TransactionExecutor::new(self.client.clone(), Metrics::new())
+            .estimate_fee(
+                "XcmPallet",
+                "limited_reserve_transfer_assets",
+                self.reserve_transfer_args(&dest, &beneficiary, &assets)?,
+                wallet,
+            )
+            .await
+    }
+
+    #[allow(clippy::result_large_err)]
+    fn reserve_transfer_args(
+        &self,
+        dest: &MultiLocation,
+        beneficiary: &MultiLocation,
+        assets: &[XcmAsset],
+    ) -> Result<Vec<subxt::dynamic::Value>> {
+        let fee_index = 0u32; // Use first asset for fees
+        Ok(vec![
+            Self::encode_multilocation(dest)?,
+            Self::encode_multilocation(beneficiary)?,
+            Self::encode_assets(assets)?,
+            subxt::dynamic::Value::u128(fee_index as u128),
+            self.encode_weight_limit()?,
+        ])
+    }
+
     /// Execute a teleport transfer to another chain
     ///
     /// # Arguments
@@ -358,9 +404,9 @@ impl XcmExecutor {
     ) -> Result<String> {
         info!("Executing teleport to {:?} for beneficiary", dest);
 
-        let dest_value = self.encode_multilocation(&dest)?;
-        let beneficiary_value = self.encode_multilocation(&MultiLocation::account(beneficiary))?;
-        let assets_value = self.encode_assets(&assets)?;
+        let dest_value = Self::encode_multilocation(&dest)?;
+        let beneficiary_value = Self::encode_multilocation(&MultiLocation::account(beneficiary))?;
+        let assets_value = Self::encode_assets(&assets)?;
         let fee_index = 0u32;
 
         let call = subxt::dynamic::tx(
@@ -422,11 +468,11 @@ impl XcmExecutor {
     // Helper methods for encoding XCM types
 
     #[allow(clippy::result_large_err)]
-    fn encode_multilocation(&self, location: &MultiLocation) -> Result<subxt::dynamic::Value> {
+    pub(crate) fn encode_multilocation(location: &MultiLocation) -> Result<subxt::dynamic::Value> {
         // Encode MultiLocation as composite value
         // Structure: { parents: u8, interior: Junctions }
 
-        let interior = self.encode_junctions(&location.interior)?;
+        let interior = Self::encode_junctions(&location.interior)?;
 
         Ok(subxt::dynamic::Value::named_composite([
             (
@@ -438,7 +484,7 @@ impl XcmExecutor {
     }
 
     #[allow(clippy::result_large_err)]
-    fn encode_junctions(&self, junctions: &[Junction]) -> Result<subxt::dynamic::Value> {
+    fn encode_junctions(junctions: &[Junction]) -> Result<subxt::dynamic::Value> {
         if junctions.is_empty() {
             // X0 (Here) variant
             return Ok(subxt::dynamic::Value::unnamed_variant("Here", vec![]));
@@ -447,7 +493,7 @@ impl XcmExecutor {
         // Encode junctions as nested X1, X2, etc.
         let encoded_junctions: Vec<subxt::dynamic::Value> = junctions
             .iter()
-            .map(|j| self.encode_junction(j))
+            .map(Self::encode_junction)
             .collect::<Result<Vec<_>>>()?;
 
         // Use appropriate variant based on number of junctions
@@ -460,7 +506,7 @@ impl XcmExecutor {
             6 => "X6",
             7 => "X7",
             8 => "X8",
-            _ => return Err(Error::Transaction("Too many junctions (max 8)".to_string())),
+            _ => return Err(Error::Encoding("Too many junctions (max 8)".to_string())),
         };
 
         Ok(subxt::dynamic::Value::unnamed_variant(
@@ -470,7 +516,7 @@ impl XcmExecutor {
     }
 
     #[allow(clippy::result_large_err)]
-    fn encode_junction(&self, junction: &Junction) -> Result<subxt::dynamic::Value> {
+    fn encode_junction(junction: &Junction) -> Result<subxt::dynamic::Value> {
         match junction {
             Junction::Parachain(id) => Ok(subxt::dynamic::Value::unnamed_variant(
                 "Parachain",
@@ -505,10 +551,23 @@ impl XcmExecutor {
                 "GeneralIndex",
                 vec![subxt::dynamic::Value::u128(*index)],
             )),
-            Junction::GeneralKey { data } => Ok(subxt::dynamic::Value::unnamed_variant(
-                "GeneralKey",
-                vec![subxt::dynamic::Value::from_bytes(data)],
-            )),
+            Junction::GeneralKey { data } => {
+                // { length, data } with the key zero-padded to 32 bytes
+                if data.len() > 32 {
+                    return Err(Error::Encoding(
+                        "General key longer than 32 bytes".to_string(),
+                    ));
+                }
+                let mut key = [0u8; 32];
+                key[..data.len()].copy_from_slice(data);
+                Ok(subxt::dynamic::Value::named_variant(
+                    "GeneralKey",
+                    [
+                        ("length", subxt::dynamic::Value::u128(data.len() as u128)),
+                        ("data", subxt::dynamic::Value::from_bytes(key)),
+                    ],
+                ))
+            }
             Junction::PalletInstance(instance) => Ok(subxt::dynamic::Value::unnamed_variant(
                 "PalletInstance",
                 vec![subxt::dynamic::Value::u128(*instance as u128)],
@@ -517,13 +576,13 @@ impl XcmExecutor {
     }
 
     #[allow(clippy::result_large_err)]
-    fn encode_assets(&self, assets: &[XcmAsset]) -> Result<subxt::dynamic::Value> {
+    fn encode_assets(assets: &[XcmAsset]) -> Result<subxt::dynamic::Value> {
         let encoded_assets: Vec<subxt::dynamic::Value> = assets
             .iter()
             .map(|asset| {
                 let id_value = match &asset.id {
                     AssetId::Concrete(location) => {
-                        let loc = self.encode_multilocation(location)?;
+                        let loc = Self::encode_multilocation(location)?;
                         subxt::dynamic::Value::unnamed_variant("Concrete", vec![loc])
                     }
                     AssetId::Abstract(data) => subxt::dynamic::Value::unnamed_variant(
@@ -645,6 +704,16 @@ mod tests {
         assert_eq!(location.interior.len(), 1);
     }
 
+    #[test]
+    fn test_multilocation_account20() {
+        let location = MultiLocation::account20([7u8; 20]);
+        assert_eq!(location.parents, 0);
+        assert!(matches!(
+            location.interior[..],
+            [Junction::AccountId20 { key, .. }] if key == [7u8; 20]
+        ));
+    }
+
     #[test]
     fn test_xcm_asset_native() {
         let asset = XcmAsset::native(1000);
@@ -169,6 +169,14 @@ async fn test_address_validation() {
         "Valid address should pass"
     );
 
+    // Alice again, but encoded for Polkadot
+    let polkadot_address =
+        Address::Substrate("15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5".to_string());
+    assert!(
+        !adapter.validate_address(&polkadot_address),
+        "Address for another network should fail"
+    );
+
     // Invalid address
     let invalid_address = Address::Substrate("invalid".to_string());
     assert!(
@@ -177,7 +185,7 @@ async fn test_address_validation() {
     );
 
     // EVM address (should fail on Substrate adapter)
-    let evm_address = Address::Evm("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb7".to_string());
+    let evm_address = Address::Evm("0x742D35Cc6634C0532925A3B844bC9e7595f0bEB7".to_string());
     assert!(
         !adapter.validate_address(&evm_address),
         "EVM address should fail on Substrate"
@@ -0,0 +1,251 @@
+//! Anvil (EVM) devnet
+
+use crate::process::{self, wait_ready};
+use crate::{DevnetError, Result};
+use apex_sdk::core::redact::Redacted;
+use apex_sdk::evm::wallet::Wallet;
+use apex_sdk::ApexSDK;
+use ethers::providers::{Http, Middleware, Provider};
+use ethers::types::U256;
+use std::time::Duration;
+use tokio::process::Child;
+
+/// Anvil's default mnemonic; its accounts are pre-funded with 10,000 ETH
+pub const ANVIL_MNEMONIC: &str = "test test test test test test test test test test test junk";
+
+/// Anvil launch configuration
+#[derive(Clone)]
+pub struct AnvilConfig {
+    /// Binary to run
+    pub binary: String,
+    /// Chain ID reported by the node
+    pub chain_id: u64,
+    /// Mnemonic for the pre-funded accounts
+    pub mnemonic: String,
+    /// Number of pre-funded accounts
+    pub accounts: u32,
+    /// Seconds between blocks; `None` mines a block per transaction
+    pub block_time: Option<u64>,
+    /// How long to wait for the RPC to come up
+    pub startup_timeout: Duration,
+}
+
+impl Default for AnvilConfig {
+    fn default() -> Self {
+        Self {
+            binary: process::binary("APEX_ANVIL_BIN", "anvil"),
+            chain_id: 31337,
+            mnemonic: ANVIL_MNEMONIC.to_string(),
+            accounts: 10,
+            block_time: None,
+            startup_timeout: Duration::from_secs(30),
+        }
+    }
+}
+
+impl AnvilConfig {
+    /// Set the chain ID
+    pub fn with_chain_id(mut self, chain_id: u64) -> Self {
+        self.chain_id = chain_id;
+        self
+    }
+
+    /// Set the block time in seconds
+    pub fn with_block_time(mut self, seconds: u64) -> Self {
+        self.block_time = Some(seconds);
+        self
+    }
+
+    /// Set the mnemonic for pre-funded accounts
+    pub fn with_mnemonic(mut self, mnemonic: impl Into<String>) -> Self {
+        self.mnemonic = mnemonic.into();
+        self
+    }
+
+    fn args(&self, port: u16) -> Vec<String> {
+        let mut args = vec![
+            "--port".to_string(),
+            port.to_string(),
+            "--chain-id".to_string(),
+            self.chain_id.to_string(),
+            "--mnemonic".to_string(),
+            self.mnemonic.clone(),
+            "--accounts".to_string(),
+            self.accounts.to_string(),
+            "--silent".to_string(),
+        ];
+        if let Some(block_time) = self.block_time {
+            args.push("--block-time".to_string());
+            args.push(block_time.to_string());
+        }
+        args
+    }
+}
+
+impl std::fmt::Debug for AnvilConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AnvilConfig")
+            .field("binary", &self.binary)
+            .field("chain_id", &self.chain_id)
+            .field("mnemonic", &Redacted(&self.mnemonic))
+            .field("accounts", &self.accounts)
+            .field("block_time", &self.block_time)
+            .field("startup_timeout", &self.startup_timeout)
+            .finish()
+    }
+}
+
+/// Running (or attached) Anvil node
+///
+/// A spawned node is killed when the handle is dropped.
+pub struct AnvilNode {
+    endpoint: String,
+    chain_id: u64,
+    mnemonic: String,
+    provider: Provider<Http>,
+    _child: Option<Child>,
+}
+
+impl std::fmt::Debug for AnvilNode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AnvilNode")
+            .field("endpoint", &self.endpoint)
+            .field("chain_id", &self.chain_id)
+            .field("mnemonic", &Redacted(&self.mnemonic))
+            .field("spawned", &self._child.is_some())
+            .finish()
+    }
+}
+
+impl AnvilNode {
+    /// Attach to `APEX_ANVIL_URL` if set, otherwise spawn with the default config
+    pub async fn start() -> Result<Self> {
+        match std::env::var("APEX_ANVIL_URL") {
+            Ok(url) => Self::attach(&url).await,
+            Err(_) => Self::spawn(AnvilConfig::default()).await,
+        }
+    }
+
+    /// Spawn a new node on a free local port
+    pub async fn spawn(config: AnvilConfig) -> Result<Self> {
+        let port = process::free_port()?;
+        let mut child = process::spawn(&config.binary, &config.args(port))?;
+
+        let endpoint = format!("http://127.0.0.1:{}", port);
+        let provider = provider(&endpoint)?;
+        let chain_id = wait_ready("anvil", Some(&mut child), config.startup_timeout, || {
+            chain_id(&provider)
+        })
+        .await?;
+
+        tracing::info!("Anvil running at {} (chain {})", endpoint, chain_id);
+
+        Ok(Self {
+            endpoint,
+            chain_id,
+            mnemonic: config.mnemonic,
+            provider,
+            _child: Some(child),
+        })
+    }
+
+    /// Attach to an already running node using the default mnemonic
+    pub async fn attach(endpoint: &str) -> Result<Self> {
+        let provider = provider(endpoint)?;
+        let chain_id = wait_ready("anvil", None, Duration::from_secs(10), || {
+            chain_id(&provider)
+        })
+        .await?;
+
+        Ok(Self {
+            endpoint: endpoint.to_string(),
+            chain_id,
+            mnemonic: ANVIL_MNEMONIC.to_string(),
+            provider,
+            _child: None,
+        })
+    }
+
+    /// HTTP RPC endpoint
+    pub fn endpoint(&self) -> &str {
+        &self.endpoint
+    }
+
+    /// Chain ID reported by the node
+    pub fn chain_id(&self) -> u64 {
+        self.chain_id
+    }
+
+    /// Pre-funded account at `index`
+    #[allow(clippy::result_large_err)]
+    pub fn account(&self, index: u32) -> Result<Wallet> {
+        Wallet::from_mnemonic(&self.mnemonic, index)
+            .map(|wallet| wallet.with_chain_id(self.chain_id))
+            .map_err(|e| DevnetError::Startup(format!("Failed to derive account: {}", e)))
+    }
+
+    /// Set the balance of `address` (in wei)
+    pub async fn fund(&self, address: &str, wei: u128) -> Result<()> {
+        self.provider
+            .request::<_, ()>("anvil_setBalance", (address, U256::from(wei)))
+            .await
+            .map_err(|e| DevnetError::Rpc(format!("anvil_setBalance failed: {}", e)))
+    }
+
+    /// Create a fresh random account funded with `wei`
+    pub async fn funded_wallet(&self, wei: u128) -> Result<Wallet> {
+        let wallet = Wallet::new_random().with_chain_id(self.chain_id);
+        self.fund(&wallet.address(), wei).await?;
+        Ok(wallet)
+    }
+
+    /// Mine `blocks` blocks immediately
+    pub async fn mine(&self, blocks: u64) -> Result<()> {
+        self.provider
+            .request::<_, ()>("anvil_mine", [U256::from(blocks)])
+            .await
+            .map_err(|e| DevnetError::Rpc(format!("anvil_mine failed: {}", e)))
+    }
+
+    /// SDK connected to this node
+    pub async fn sdk(&self) -> Result<ApexSDK> {
+        Ok(ApexSDK::builder()
+            .with_evm_endpoint(&self.endpoint)
+            .build()
+            .await?)
+    }
+}
+
+#[allow(clippy::result_large_err)]
+fn provider(endpoint: &str) -> Result<Provider<Http>> {
+    Provider::<Http>::try_from(endpoint)
+        .map_err(|e| DevnetError::Startup(format!("Invalid endpoint {}: {}", endpoint, e)))
+}
+
+async fn chain_id(provider: &Provider<Http>) -> Result<u64> {
+    provider
+        .get_chainid()
+        .await
+        .map(|id| id.as_u64())
+        .map_err(|e| DevnetError::Rpc(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_args() {
+        let args = AnvilConfig::default().with_block_time(2).args(8545);
+        assert_eq!(&args[..4], ["--port", "8545", "--chain-id", "31337"]);
+        assert!(args.ends_with(&["--block-time".to_string(), "2".to_string()]));
+    }
+
+    #[test]
+    fn test_default_account() {
+        let wallet = Wallet::from_mnemonic(ANVIL_MNEMONIC, 0).unwrap();
+        assert!(wallet
+            .address()
+            .eq_ignore_ascii_case("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266"));
+    }
+}
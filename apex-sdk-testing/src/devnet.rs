@@ -0,0 +1,59 @@
+//! Combined EVM + Substrate fixture
+
+use crate::{AnvilNode, Result, SubstrateNode};
+use apex_sdk::ApexSDK;
+
+/// EVM and/or Substrate devnets, with an SDK connected to all of them
+pub struct Devnet {
+    anvil: Option<AnvilNode>,
+    substrate: Option<SubstrateNode>,
+}
+
+impl Devnet {
+    /// Only an EVM devnet
+    pub async fn evm() -> Result<Self> {
+        Ok(Self {
+            anvil: Some(AnvilNode::start().await?),
+            substrate: None,
+        })
+    }
+
+    /// Only a Substrate devnet
+    pub async fn substrate() -> Result<Self> {
+        Ok(Self {
+            anvil: None,
+            substrate: Some(SubstrateNode::start().await?),
+        })
+    }
+
+    /// Both devnets, started concurrently
+    pub async fn hybrid() -> Result<Self> {
+        let (anvil, substrate) = tokio::try_join!(AnvilNode::start(), SubstrateNode::start())?;
+        Ok(Self {
+            anvil: Some(anvil),
+            substrate: Some(substrate),
+        })
+    }
+
+    /// The EVM devnet, if started
+    pub fn anvil(&self) -> Option<&AnvilNode> {
+        self.anvil.as_ref()
+    }
+
+    /// The Substrate devnet, if started
+    pub fn substrate_node(&self) -> Option<&SubstrateNode> {
+        self.substrate.as_ref()
+    }
+
+    /// SDK connected to every running devnet
+    pub async fn sdk(&self) -> Result<ApexSDK> {
+        let mut builder = ApexSDK::builder();
+        if let Some(anvil) = &self.anvil {
+            builder = builder.with_evm_endpoint(anvil.endpoint());
+        }
+        if let Some(substrate) = &self.substrate {
+            builder = builder.with_substrate_endpoint(substrate.endpoint());
+        }
+        Ok(builder.build().await?)
+    }
+}
@@ -0,0 +1,213 @@
+//! Round-trip invariants for encoders and parsers that handle funds
+//!
+//! Each `assert_*` helper checks one invariant and panics with the offending
+//! input, so it works inside `proptest!` blocks and fuzz targets alike: what
+//! is encoded decodes back to the input, and whatever decodes re-encodes to
+//! the same bytes, so no two inputs alias. The strategies generate
+//! well-formed inputs for the first kind of check; raw bytes or strings
+//! exercise the second.
+//!
+//! ```rust
+//! use apex_sdk_testing::invariants;
+//! use proptest::prelude::*;
+//! use proptest::test_runner::TestRunner;
+//!
+//! TestRunner::default()
+//!     .run(&(any::<u128>(), 0u8..=18), |(value, decimals)| {
+//!         invariants::assert_units_roundtrip(value, decimals);
+//!         Ok(())
+//!     })
+//!     .unwrap();
+//! ```
+
+use apex_sdk::substrate::{
+    decode_location, decode_transfer_args, encode_location, encode_transfer_args, Junction,
+    MultiLocation,
+};
+use apex_sdk::types::{eip55, format_units, parse_units, ss58, Address};
+use proptest::prelude::*;
+
+/// SS58 prefixes generated by [`substrate_address`]: Polkadot, Kusama and
+/// the generic Substrate prefix
+pub const SS58_PREFIXES: [u16; 3] = [ss58::POLKADOT, ss58::KUSAMA, ss58::SUBSTRATE];
+
+/// EVM addresses in lowercase, uppercase or EIP-55 checksummed hex, with
+/// their 20 account bytes
+pub fn evm_address() -> impl Strategy<Value = (Address, [u8; 20])> {
+    (any::<[u8; 20]>(), 0..3u8).prop_map(|(key, case)| {
+        let address = match case {
+            0 => format!("0x{}", hex::encode(key)),
+            1 => format!("0x{}", hex::encode_upper(key)),
+            _ => eip55::to_checksum(&key),
+        };
+        (Address::evm(address), key)
+    })
+}
+
+/// SS58 addresses under one of [`SS58_PREFIXES`], with their account ID
+pub fn substrate_address() -> impl Strategy<Value = (Address, [u8; 32])> {
+    (
+        prop::sample::select(SS58_PREFIXES.to_vec()),
+        any::<[u8; 32]>(),
+    )
+        .prop_map(|(prefix, account)| {
+            (Address::from_public_key(prefix, &account).unwrap(), account)
+        })
+}
+
+/// XCM junctions that [`encode_location`] supports (no networks, keys of
+/// at most 32 bytes)
+pub fn junction() -> impl Strategy<Value = Junction> {
+    prop_oneof![
+        any::<u32>().prop_map(Junction::Parachain),
+        any::<[u8; 32]>().prop_map(|id| Junction::AccountId32 { network: None, id }),
+        any::<[u8; 20]>().prop_map(|key| Junction::AccountId20 { network: None, key }),
+        any::<u8>().prop_map(Junction::PalletInstance),
+        any::<u128>().prop_map(Junction::GeneralIndex),
+        prop::collection::vec(any::<u8>(), 0..=32).prop_map(|data| Junction::GeneralKey { data }),
+    ]
+}
+
+/// XCM locations that [`encode_location`] supports
+pub fn location() -> impl Strategy<Value = MultiLocation> {
+    (any::<u8>(), prop::collection::vec(junction(), 0..=8))
+        .prop_map(|(parents, interior)| MultiLocation::new(parents, interior))
+}
+
+/// Formatting `value` and parsing it back gives `value`
+pub fn assert_units_roundtrip(value: u128, decimals: u8) {
+    let formatted = format_units(value, decimals);
+    assert_eq!(
+        parse_units(&formatted, decimals),
+        Ok(value),
+        "{} with {} decimals formatted as {:?}",
+        value,
+        decimals,
+        formatted
+    );
+}
+
+/// Parsing `input` either fails or gives a value that formats and parses
+/// back to itself
+pub fn assert_parse_units_canonical(input: &str, decimals: u8) {
+    if let Ok(value) = parse_units(input, decimals) {
+        assert_units_roundtrip(value, decimals);
+    }
+}
+
+/// `address` decodes to `account`, and to nothing else
+pub fn assert_account_bytes(address: &Address, account: &[u8]) {
+    assert_eq!(
+        address.account_bytes().as_deref(),
+        Some(account),
+        "{:?} decoded to the wrong account",
+        address
+    );
+}
+
+/// `address` either fails to decode or decodes to an account of the
+/// chain's length, without panicking
+pub fn assert_account_bytes_well_formed(address: &Address) {
+    let expected = match address {
+        Address::Evm(_) => 20,
+        Address::Substrate(_) => 32,
+    };
+    if let Some(bytes) = address.account_bytes() {
+        assert_eq!(
+            bytes.len(),
+            expected,
+            "{:?} decoded to {:?}",
+            address,
+            bytes
+        );
+    }
+}
+
+/// `address` formats and parses back to itself, in the same variant
+pub fn assert_address_parse_roundtrip(address: &Address) {
+    let parsed: Address = address
+        .to_string()
+        .parse()
+        .unwrap_or_else(|e| panic!("{:?} did not parse: {}", address, e));
+    assert_eq!(
+        (&parsed, parsed.as_str()),
+        (address, address.as_str()),
+        "{:?} round-tripped wrongly",
+        address
+    );
+    assert_eq!(
+        std::mem::discriminant(&parsed),
+        std::mem::discriminant(address)
+    );
+}
+
+/// Transfer arguments for `address` and `amount` decode back to `account`
+/// and `amount`
+pub fn assert_transfer_args_roundtrip(address: &Address, account: &[u8; 32], amount: u128) {
+    let args = encode_transfer_args(address.as_str(), amount)
+        .unwrap_or_else(|e| panic!("{:?} did not encode: {}", address, e));
+    let decoded = decode_transfer_args(&args)
+        .unwrap_or_else(|e| panic!("{} did not decode: {}", hex::encode(&args), e));
+    assert_eq!(
+        decoded,
+        (*account, amount),
+        "{:?} round-tripped wrongly",
+        address
+    );
+}
+
+/// `bytes` either fail to decode as transfer arguments or re-encode to
+/// themselves
+pub fn assert_decode_transfer_args_canonical(bytes: &[u8]) {
+    if let Ok((account, amount)) = decode_transfer_args(bytes) {
+        let args = encode_transfer_args(&ss58::encode(ss58::SUBSTRATE, &account).unwrap(), amount)
+            .unwrap_or_else(|e| panic!("{} did not re-encode: {}", hex::encode(bytes), e));
+        assert_eq!(args, bytes, "{} is not canonical", hex::encode(bytes));
+    }
+}
+
+/// `location` encodes and decodes back to itself
+pub fn assert_location_roundtrip(location: &MultiLocation) {
+    let bytes = encode_location(location)
+        .unwrap_or_else(|e| panic!("{:?} did not encode: {}", location, e));
+    let decoded = decode_location(&bytes)
+        .unwrap_or_else(|e| panic!("{} did not decode: {}", hex::encode(&bytes), e));
+    assert_eq!(&decoded, location);
+}
+
+/// `bytes` either fail to decode as a location or re-encode to themselves
+pub fn assert_decode_location_canonical(bytes: &[u8]) {
+    if let Ok(location) = decode_location(bytes) {
+        let encoded = encode_location(&location)
+            .unwrap_or_else(|e| panic!("{:?} did not re-encode: {}", location, e));
+        assert_eq!(encoded, bytes, "{} is not canonical", hex::encode(bytes));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    proptest! {
+        #[test]
+        fn prop_units(value in any::<u128>(), decimals in 0u8..=40, input in "[0-9]{1,40}(\\.[0-9]{1,40})?") {
+            assert_units_roundtrip(value, decimals);
+            assert_parse_units_canonical(&input, decimals);
+        }
+
+        #[test]
+        fn prop_addresses(
+            (evm, key) in evm_address(),
+            (substrate, account) in substrate_address(),
+            garbage in "\\PC*",
+        ) {
+            assert_account_bytes(&evm, &key);
+            assert_account_bytes(&substrate, &account);
+            assert_address_parse_roundtrip(&evm);
+            assert_address_parse_roundtrip(&substrate);
+            let _ = garbage.parse::<Address>();
+            assert_account_bytes_well_formed(&Address::evm(garbage.clone()));
+            assert_account_bytes_well_formed(&Address::substrate(garbage));
+        }
+    }
+}
@@ -0,0 +1,548 @@
+//! Canned EVM JSON-RPC node
+
+use super::{param_str, MockRpcServer, RpcError};
+use crate::Result;
+use ethers::types::transaction::eip2718::TypedTransaction;
+use ethers::types::{Address, H256, U256, U64};
+use ethers::utils::rlp::Rlp;
+use ethers::utils::{get_contract_address, keccak256};
+use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet};
+use std::ops::Deref;
+use std::sync::{Arc, Mutex};
+
+const ZERO_ADDRESS: &str = "0x0000000000000000000000000000000000000000";
+
+#[derive(Debug)]
+struct EvmState {
+    chain_id: u64,
+    block_number: u64,
+    gas_price: u128,
+    balances: HashMap<String, u128>,
+    nonces: HashMap<String, u64>,
+    code: HashMap<String, String>,
+    tokens: HashMap<(String, String), u128>,
+    call_results: HashMap<(String, String), String>,
+    receipts: HashMap<String, Value>,
+    pending: HashSet<String>,
+    pending_logs: Vec<Value>,
+    logs: Vec<Value>,
+    automine: bool,
+    max_log_range: Option<u64>,
+}
+
+/// Mock EVM node answering the `eth_*` methods used by the EVM adapter
+///
+/// Dereferences to [`MockRpcServer`] for failure injection and call counting.
+/// `eth_subscribe` is supported over WebSocket for `newHeads`, `logs` and
+/// `newPendingTransactions`: [`mine`](Self::mine) pushes the new head and
+/// its logs, and transactions left pending are pushed as they are sent. Log
+/// subscriptions receive every log, regardless of their filter.
+pub struct EvmMock {
+    server: MockRpcServer,
+    state: Arc<Mutex<EvmState>>,
+}
+
+impl EvmMock {
+    /// Start a mock node reporting `chain_id`
+    pub async fn start(chain_id: u64) -> Result<Self> {
+        let server = MockRpcServer::start().await?;
+        let state = Arc::new(Mutex::new(EvmState {
+            chain_id,
+            block_number: 1,
+            gas_price: 1_000_000_000,
+            balances: HashMap::new(),
+            nonces: HashMap::new(),
+            code: HashMap::new(),
+            tokens: HashMap::new(),
+            call_results: HashMap::new(),
+            receipts: HashMap::new(),
+            pending: HashSet::new(),
+            pending_logs: Vec::new(),
+            max_log_range: None,
+            logs: Vec::new(),
+            automine: false,
+        }));
+
+        let mock = Self { server, state };
+        mock.register();
+        Ok(mock)
+    }
+
+    fn register(&self) {
+        let state = self.state.clone();
+        self.server.handle("eth_chainId", move |_| {
+            Ok(json!(U64::from(state.lock().unwrap().chain_id)))
+        });
+
+        let state = self.state.clone();
+        self.server.handle("net_version", move |_| {
+            Ok(json!(state.lock().unwrap().chain_id.to_string()))
+        });
+
+        let state = self.state.clone();
+        self.server.handle("eth_blockNumber", move |_| {
+            Ok(json!(U64::from(state.lock().unwrap().block_number)))
+        });
+
+        let state = self.state.clone();
+        self.server.handle("eth_gasPrice", move |_| {
+            Ok(json!(U256::from(state.lock().unwrap().gas_price)))
+        });
+
+        let state = self.state.clone();
+        self.server.handle("eth_getBalance", move |params| {
+            let address = param_str(params, 0)?.to_lowercase();
+            let balance = state
+                .lock()
+                .unwrap()
+                .balances
+                .get(&address)
+                .copied()
+                .unwrap_or(0);
+            Ok(json!(U256::from(balance)))
+        });
+
+        let state = self.state.clone();
+        self.server
+            .handle("eth_getTransactionCount", move |params| {
+                let address = param_str(params, 0)?.to_lowercase();
+                let nonce = state
+                    .lock()
+                    .unwrap()
+                    .nonces
+                    .get(&address)
+                    .copied()
+                    .unwrap_or(0);
+                Ok(json!(U256::from(nonce)))
+            });
+
+        let state = self.state.clone();
+        self.server.handle("eth_getCode", move |params| {
+            let address = param_str(params, 0)?.to_lowercase();
+            let code = state.lock().unwrap().code.get(&address).cloned();
+            Ok(json!(code.unwrap_or_else(|| "0x".to_string())))
+        });
+
+        let state = self.state.clone();
+        self.server.handle("eth_call", move |params| {
+            let call = params.get(0).cloned().unwrap_or(Value::Null);
+            let to = call["to"].as_str().unwrap_or_default().to_lowercase();
+            let data = call["data"]
+                .as_str()
+                .or_else(|| call["input"].as_str())
+                .unwrap_or_default()
+                .to_lowercase();
+            let state = state.lock().unwrap();
+            // balanceOf(address) of a token set with `set_token_balance`
+            if let Some(holder) = data.strip_prefix("0x70a08231") {
+                let holder = format!("0x{}", holder.get(24..64).unwrap_or_default());
+                if let Some(balance) = state.tokens.get(&(to.clone(), holder)) {
+                    return Ok(json!(format!("0x{:064x}", balance)));
+                }
+            }
+            let selector = data.get(..10).unwrap_or_default().to_string();
+            Ok(json!(state
+                .call_results
+                .get(&(to, selector))
+                .cloned()
+                .unwrap_or_else(|| "0x".to_string())))
+        });
+        self.server
+            .respond("eth_estimateGas", json!(U256::from(21_000)));
+        self.server
+            .respond("eth_maxPriorityFeePerGas", json!(U256::from(1_000_000_000)));
+
+        // Constant 1 gwei base fee; blocks tip 0.02 gwei per percentile
+        let state = self.state.clone();
+        self.server.handle("eth_feeHistory", move |params| {
+            let head = state.lock().unwrap().block_number;
+            let count = params
+                .get(0)
+                .and_then(block_param)
+                .unwrap_or(1)
+                .clamp(1, head + 1);
+            let tips: Vec<U256> = params
+                .get(2)
+                .and_then(Value::as_array)
+                .map(|percentiles| {
+                    percentiles
+                        .iter()
+                        .filter_map(Value::as_f64)
+                        .map(|p| U256::from((p * 20_000_000.0) as u64))
+                        .collect()
+                })
+                .unwrap_or_default();
+            Ok(json!({
+                "oldestBlock": U64::from(head + 1 - count),
+                "baseFeePerGas": vec![U256::from(1_000_000_000); count as usize + 1],
+                "gasUsedRatio": vec![0.5; count as usize],
+                "reward": vec![tips; count as usize],
+            }))
+        });
+
+        let state = self.state.clone();
+        self.server
+            .handle("eth_getTransactionReceipt", move |params| {
+                let hash = param_str(params, 0)?.to_lowercase();
+                Ok(state
+                    .lock()
+                    .unwrap()
+                    .receipts
+                    .get(&hash)
+                    .cloned()
+                    .unwrap_or(Value::Null))
+            });
+
+        let state = self.state.clone();
+        self.server
+            .handle("eth_getTransactionByHash", move |params| {
+                let hash = param_str(params, 0)?.to_lowercase();
+                let state = state.lock().unwrap();
+                if let Some(receipt) = state.receipts.get(&hash) {
+                    Ok(transaction_json(
+                        &hash,
+                        state.chain_id,
+                        Some(&receipt["blockNumber"]),
+                    ))
+                } else if state.pending.contains(&hash) {
+                    Ok(transaction_json(&hash, state.chain_id, None))
+                } else {
+                    Ok(Value::Null)
+                }
+            });
+
+        let state = self.state.clone();
+        let inner = self.server.inner.clone();
+        self.server.handle("eth_sendRawTransaction", move |params| {
+            let raw = param_str(params, 0)?;
+            let bytes = hex::decode(raw.trim_start_matches("0x"))
+                .map_err(|e| RpcError::invalid_params(format!("Invalid hex: {}", e)))?;
+            let hash = format!("{:?}", H256::from(keccak256(&bytes)));
+            let mut state = state.lock().unwrap();
+            if state.automine {
+                state.block_number += 1;
+                let mut receipt = receipt_json(&hash, state.block_number, true);
+                if let Some(address) = created_contract(&bytes) {
+                    receipt["to"] = Value::Null;
+                    receipt["contractAddress"] = json!(address);
+                }
+                state.receipts.insert(hash.clone(), receipt);
+            } else {
+                state.pending.insert(hash.clone());
+                inner.notify(
+                    "eth_subscribe",
+                    Some("newPendingTransactions".to_string()),
+                    json!(hash),
+                );
+            }
+            Ok(json!(hash))
+        });
+
+        let state = self.state.clone();
+        self.server.handle("eth_getBlockByNumber", move |params| {
+            let head = state.lock().unwrap().block_number;
+            // Tags such as "latest" and "finalized" resolve to the head
+            match params.get(0).and_then(block_param) {
+                Some(number) if number > head => Ok(Value::Null),
+                Some(number) => Ok(block_json(number)),
+                None => Ok(block_json(head)),
+            }
+        });
+
+        let state = self.state.clone();
+        self.server.handle("eth_getBlockByHash", move |params| {
+            let head = state.lock().unwrap().block_number;
+            let hash: H256 = param_str(params, 0)?
+                .parse()
+                .map_err(|e| RpcError::invalid_params(format!("Invalid hash: {}", e)))?;
+            // Block hashes are their numbers, see `block_hash`
+            let number = hash.to_low_u64_be();
+            if block_hash(number) == format!("{:?}", hash) && number <= head {
+                Ok(block_json(number))
+            } else {
+                Ok(Value::Null)
+            }
+        });
+
+        let state = self.state.clone();
+        self.server.handle("eth_getLogs", move |params| {
+            let filter = params.get(0).cloned().unwrap_or(Value::Null);
+            let state = state.lock().unwrap();
+            let from = block_param(&filter["fromBlock"]).unwrap_or(state.block_number);
+            let to = block_param(&filter["toBlock"]).unwrap_or(state.block_number);
+            if let Some(max) = state.max_log_range {
+                if to >= from && to - from + 1 > max {
+                    return Err(RpcError::new(
+                        -32000,
+                        format!("query exceeds max block range {}", max),
+                    ));
+                }
+            }
+            let logs: Vec<Value> = state
+                .logs
+                .iter()
+                .filter(|log| {
+                    block_param(&log["blockNumber"]).is_some_and(|n| from <= n && n <= to)
+                        && matches_any(&filter["address"], &log["address"])
+                        && filter["topics"]
+                            .as_array()
+                            .into_iter()
+                            .flatten()
+                            .enumerate()
+                            .all(|(i, topic)| matches_any(topic, &log["topics"][i]))
+                })
+                .cloned()
+                .collect();
+            Ok(json!(logs))
+        });
+
+        self.server
+            .subscription("eth_subscribe", "eth_subscription", "eth_unsubscribe");
+    }
+
+    /// Set the balance of `address` in wei
+    pub fn set_balance(&self, address: &str, wei: u128) {
+        self.state
+            .lock()
+            .unwrap()
+            .balances
+            .insert(address.to_lowercase(), wei);
+    }
+
+    /// Set the next nonce of `address`
+    pub fn set_nonce(&self, address: &str, nonce: u64) {
+        self.state
+            .lock()
+            .unwrap()
+            .nonces
+            .insert(address.to_lowercase(), nonce);
+    }
+
+    /// Deploy (fake) contract code at `address`
+    pub fn set_code(&self, address: &str, code: &[u8]) {
+        self.state
+            .lock()
+            .unwrap()
+            .code
+            .insert(address.to_lowercase(), format!("0x{}", hex::encode(code)));
+    }
+
+    /// Set the ERC-20 `token` balance of `holder`, answered to `balanceOf` calls
+    pub fn set_token_balance(&self, token: &str, holder: &str, amount: u128) {
+        self.state
+            .lock()
+            .unwrap()
+            .tokens
+            .insert((token.to_lowercase(), holder.to_lowercase()), amount);
+    }
+
+    /// Answer `eth_call`s to `to` whose data starts with the 4-byte `selector` with `output`
+    pub fn set_call_result(&self, to: &str, selector: [u8; 4], output: &[u8]) {
+        self.state.lock().unwrap().call_results.insert(
+            (to.to_lowercase(), format!("0x{}", hex::encode(selector))),
+            format!("0x{}", hex::encode(output)),
+        );
+    }
+
+    /// Set the gas price in wei
+    pub fn set_gas_price(&self, wei: u128) {
+        self.state.lock().unwrap().gas_price = wei;
+    }
+
+    /// Set the current block number
+    pub fn set_block_number(&self, number: u64) {
+        self.state.lock().unwrap().block_number = number;
+    }
+
+    /// Mark a transaction as known but not yet mined
+    pub fn add_pending(&self, tx_hash: &str) {
+        self.state
+            .lock()
+            .unwrap()
+            .pending
+            .insert(tx_hash.to_lowercase());
+    }
+
+    /// Record a mined transaction with the given outcome
+    pub fn add_receipt(&self, tx_hash: &str, block_number: u64, success: bool) {
+        let receipt = receipt_json(tx_hash, block_number, success);
+        let mut state = self.state.lock().unwrap();
+        state.pending.remove(&tx_hash.to_lowercase());
+        state.receipts.insert(tx_hash.to_lowercase(), receipt);
+    }
+
+    /// Emit a log from `address` in the next mined block
+    pub fn add_log(&self, address: &str, topics: &[&str], data: &[u8]) {
+        self.state.lock().unwrap().pending_logs.push(json!({
+            "address": address.to_lowercase(),
+            "topics": topics,
+            "data": format!("0x{}", hex::encode(data)),
+        }));
+    }
+
+    /// Refuse `eth_getLogs` requests spanning more than `blocks` blocks, as
+    /// hosted providers do
+    pub fn set_max_log_range(&self, blocks: Option<u64>) {
+        self.state.lock().unwrap().max_log_range = blocks;
+    }
+
+    /// Mine every sent transaction (successfully) into its own block right
+    /// away, instead of leaving it pending until [`mine`](Self::mine)
+    ///
+    /// Automined blocks are not pushed to `newHeads` subscribers.
+    pub fn set_automine(&self, automine: bool) {
+        self.state.lock().unwrap().automine = automine;
+    }
+
+    /// Mine pending transactions (successfully) and logs into a new block and notify subscribers
+    ///
+    /// Returns the new block number.
+    pub fn mine(&self) -> u64 {
+        let (number, logs) = {
+            let mut state = self.state.lock().unwrap();
+            state.block_number += 1;
+            let number = state.block_number;
+            let pending: Vec<String> = state.pending.drain().collect();
+            for hash in pending {
+                state
+                    .receipts
+                    .insert(hash.clone(), receipt_json(&hash, number, true));
+            }
+            let pending_logs: Vec<Value> = state.pending_logs.drain(..).collect();
+            let first_log = state.logs.len();
+            for (index, mut log) in pending_logs.into_iter().enumerate() {
+                log["blockNumber"] = json!(U64::from(number));
+                log["blockHash"] = json!(block_hash(number));
+                log["logIndex"] = json!(U256::from(index));
+                log["transactionIndex"] = json!("0x0");
+                log["transactionHash"] = json!(format!("{:?}", H256::zero()));
+                log["removed"] = json!(false);
+                state.logs.push(log);
+            }
+            (number, state.logs[first_log..].to_vec())
+        };
+
+        self.server
+            .notify_kind("eth_subscribe", "newHeads", block_json(number));
+        for log in logs {
+            self.server.notify_kind("eth_subscribe", "logs", log);
+        }
+        number
+    }
+}
+
+impl Deref for EvmMock {
+    type Target = MockRpcServer;
+
+    fn deref(&self) -> &MockRpcServer {
+        &self.server
+    }
+}
+
+fn block_param(value: &Value) -> Option<u64> {
+    u64::from_str_radix(value.as_str()?.trim_start_matches("0x"), 16).ok()
+}
+
+fn block_hash(number: u64) -> String {
+    format!("{:?}", H256::from_low_u64_be(number))
+}
+
+fn block_json(number: u64) -> Value {
+    json!({
+        "hash": block_hash(number),
+        "parentHash": block_hash(number.saturating_sub(1)),
+        "sha3Uncles": format!("{:?}", H256::zero()),
+        "miner": ZERO_ADDRESS,
+        "stateRoot": format!("{:?}", H256::zero()),
+        "transactionsRoot": format!("{:?}", H256::zero()),
+        "receiptsRoot": format!("{:?}", H256::zero()),
+        "logsBloom": format!("0x{}", "0".repeat(512)),
+        "difficulty": "0x0",
+        "number": U64::from(number),
+        "gasLimit": U256::from(30_000_000),
+        "gasUsed": "0x0",
+        "timestamp": U256::from(1_700_000_000 + number * 12),
+        "extraData": "0x",
+        "mixHash": format!("{:?}", H256::zero()),
+        "nonce": "0x0000000000000000",
+        "baseFeePerGas": U256::from(1_000_000_000),
+        "uncles": [],
+        "transactions": [],
+    })
+}
+
+/// Whether `value` matches a log filter condition: `null`, one value or a
+/// list of alternatives, compared case-insensitively
+fn matches_any(condition: &Value, value: &Value) -> bool {
+    let value = value.as_str().map(str::to_lowercase);
+    match condition {
+        Value::Null => true,
+        Value::String(expected) => value == Some(expected.to_lowercase()),
+        Value::Array(alternatives) => alternatives
+            .iter()
+            .any(|expected| matches_any(expected, &json!(value))),
+        _ => false,
+    }
+}
+
+/// Address of the contract created by the signed transaction `raw`, if it
+/// is a contract creation
+fn created_contract(raw: &[u8]) -> Option<Address> {
+    let (tx, signature) = TypedTransaction::decode_signed(&Rlp::new(raw)).ok()?;
+    if tx.to().is_some() {
+        return None;
+    }
+    let from = signature.recover(tx.sighash()).ok()?;
+    Some(get_contract_address(
+        from,
+        tx.nonce().copied().unwrap_or_default(),
+    ))
+}
+
+fn receipt_json(tx_hash: &str, block_number: u64, success: bool) -> Value {
+    json!({
+        "transactionHash": tx_hash,
+        "transactionIndex": "0x0",
+        "blockHash": block_hash(block_number),
+        "blockNumber": U64::from(block_number),
+        "from": ZERO_ADDRESS,
+        "to": ZERO_ADDRESS,
+        "cumulativeGasUsed": U256::from(21_000),
+        "gasUsed": U256::from(21_000),
+        "effectiveGasPrice": U256::from(1_000_000_000),
+        "contractAddress": null,
+        "logs": [],
+        "logsBloom": format!("0x{}", "0".repeat(512)),
+        "status": if success { "0x1" } else { "0x0" },
+        "type": "0x2",
+    })
+}
+
+fn transaction_json(tx_hash: &str, chain_id: u64, block_number: Option<&Value>) -> Value {
+    let block_number = block_number.cloned().unwrap_or(Value::Null);
+    let mined_in = block_number
+        .as_str()
+        .and_then(|n| u64::from_str_radix(n.trim_start_matches("0x"), 16).ok())
+        .map(|n| json!(block_hash(n)))
+        .unwrap_or(Value::Null);
+
+    json!({
+        "hash": tx_hash,
+        "nonce": "0x0",
+        "blockHash": mined_in,
+        "blockNumber": block_number,
+        "transactionIndex": if block_number.is_null() { Value::Null } else { json!("0x0") },
+        "from": ZERO_ADDRESS,
+        "to": ZERO_ADDRESS,
+        "value": "0x0",
+        "gasPrice": U256::from(1_000_000_000),
+        "gas": U256::from(21_000),
+        "input": "0x",
+        "v": "0x0",
+        "r": "0x1",
+        "s": "0x1",
+        "type": "0x0",
+        "chainId": U256::from(chain_id),
+    })
+}
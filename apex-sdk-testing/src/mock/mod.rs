@@ -0,0 +1,541 @@
+//! In-process mock JSON-RPC servers
+//!
+//! [`MockRpcServer`] answers JSON-RPC 2.0 over HTTP and WebSocket on a local
+//! port, with canned responses, failure injection and push subscriptions, so
+//! adapter connection, retry and decoding logic can be tested without a
+//! network. [`EvmMock`] and [`SubstrateMock`] pre-register the methods the
+//! adapters use and keep simple chain state (balances, receipts, storage).
+//!
+//! ```rust,no_run
+//! use apex_sdk::evm::EvmAdapter;
+//! use apex_sdk_testing::mock::EvmMock;
+//!
+//! # async fn run() {
+//! let mock = EvmMock::start(1).await.unwrap();
+//! mock.set_balance("0x742D35CC6634c0532925A3b844BC9E7595F0BEb0", 42);
+//!
+//! let adapter = EvmAdapter::connect(&mock.http_url()).await.unwrap();
+//! mock.fail_next("eth_getBalance", 1); // next balance query returns an RPC error
+//! # }
+//! ```
+
+mod evm;
+mod substrate;
+
+pub use evm::EvmMock;
+pub use substrate::{account_storage_key, SubstrateMock};
+
+use crate::{DevnetError, Result};
+use axum::body::Bytes;
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::any;
+use axum::Router;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+
+/// JSON-RPC error returned by a handler
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RpcError {
+    pub code: i64,
+    pub message: String,
+}
+
+impl RpcError {
+    /// Create an error with an arbitrary code
+    pub fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+
+    /// Invalid params (-32602)
+    pub fn invalid_params(message: impl Into<String>) -> Self {
+        Self::new(-32602, message)
+    }
+}
+
+/// A request received by the mock
+#[derive(Debug, Clone, PartialEq)]
+pub struct RpcCall {
+    pub method: String,
+    pub params: Value,
+}
+
+type Handler = Arc<dyn Fn(&Value) -> std::result::Result<Value, RpcError> + Send + Sync>;
+
+/// Subscription registered with [`MockRpcServer::subscription`]
+#[derive(Debug, Clone)]
+struct Subscription {
+    notification: String,
+    unsubscribe: String,
+}
+
+/// Value pushed to the subscribers of a method, optionally only to those
+/// whose first subscribe param is `kind`
+#[derive(Debug, Clone)]
+struct Notification {
+    subscribe: String,
+    kind: Option<String>,
+    result: Value,
+}
+
+struct Inner {
+    handlers: RwLock<HashMap<String, Handler>>,
+    subscriptions: RwLock<HashMap<String, Subscription>>,
+    failures: Mutex<HashMap<String, u32>>,
+    calls: Mutex<Vec<RpcCall>>,
+    unavailable: AtomicBool,
+    rate_limit: Mutex<Option<u64>>,
+    next_subscription: AtomicU64,
+    notifications: broadcast::Sender<Notification>,
+    disconnects: broadcast::Sender<()>,
+}
+
+/// Mock JSON-RPC server listening on HTTP and WebSocket
+///
+/// The server stops when the handle is dropped.
+pub struct MockRpcServer {
+    addr: SocketAddr,
+    inner: Arc<Inner>,
+    task: JoinHandle<()>,
+}
+
+impl MockRpcServer {
+    /// Start an empty server on a free local port
+    pub async fn start() -> Result<Self> {
+        let (notifications, _) = broadcast::channel(256);
+        let (disconnects, _) = broadcast::channel(1);
+        let inner = Arc::new(Inner {
+            handlers: RwLock::new(HashMap::new()),
+            subscriptions: RwLock::new(HashMap::new()),
+            failures: Mutex::new(HashMap::new()),
+            calls: Mutex::new(Vec::new()),
+            unavailable: AtomicBool::new(false),
+            rate_limit: Mutex::new(None),
+            next_subscription: AtomicU64::new(1),
+            notifications,
+            disconnects,
+        });
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .map_err(|e| DevnetError::Startup(format!("Failed to bind mock server: {}", e)))?;
+        let addr = listener
+            .local_addr()
+            .map_err(|e| DevnetError::Startup(e.to_string()))?;
+
+        let app = Router::new()
+            .route("/", any(entry))
+            .with_state(inner.clone());
+        let task = tokio::spawn(async move {
+            if let Err(e) = axum::serve(listener, app).await {
+                tracing::error!("Mock RPC server stopped: {}", e);
+            }
+        });
+
+        Ok(Self { addr, inner, task })
+    }
+
+    /// HTTP endpoint
+    pub fn http_url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// WebSocket endpoint
+    pub fn ws_url(&self) -> String {
+        format!("ws://{}", self.addr)
+    }
+
+    /// Always answer `method` with `result`
+    pub fn respond(&self, method: &str, result: Value) {
+        self.handle(method, move |_| Ok(result.clone()));
+    }
+
+    /// Answer `method` with a handler computing the result from the params
+    pub fn handle<F>(&self, method: &str, handler: F)
+    where
+        F: Fn(&Value) -> std::result::Result<Value, RpcError> + Send + Sync + 'static,
+    {
+        self.inner
+            .handlers
+            .write()
+            .unwrap()
+            .insert(method.to_string(), Arc::new(handler));
+    }
+
+    /// Register a subscription method pushing `notification` messages
+    ///
+    /// Values passed to [`notify`](Self::notify) for `subscribe` are sent to
+    /// every live subscriber on any WebSocket connection.
+    pub fn subscription(&self, subscribe: &str, notification: &str, unsubscribe: &str) {
+        self.inner.subscriptions.write().unwrap().insert(
+            subscribe.to_string(),
+            Subscription {
+                notification: notification.to_string(),
+                unsubscribe: unsubscribe.to_string(),
+            },
+        );
+    }
+
+    /// Push `result` to all subscribers of `subscribe`
+    pub fn notify(&self, subscribe: &str, result: Value) {
+        self.send_notification(subscribe, None, result);
+    }
+
+    /// Push `result` to the subscribers of `subscribe` whose first param is
+    /// `kind`, e.g. `eth_subscribe("logs")`
+    pub fn notify_kind(&self, subscribe: &str, kind: &str, result: Value) {
+        self.send_notification(subscribe, Some(kind.to_string()), result);
+    }
+
+    fn send_notification(&self, subscribe: &str, kind: Option<String>, result: Value) {
+        self.inner.notify(subscribe, kind, result);
+    }
+
+    /// Close every open WebSocket connection, dropping its subscriptions
+    ///
+    /// The server keeps accepting new connections.
+    pub fn drop_connections(&self) {
+        let _ = self.inner.disconnects.send(());
+    }
+
+    /// Make the next `times` calls to `method` fail with a JSON-RPC error
+    pub fn fail_next(&self, method: &str, times: u32) {
+        self.inner
+            .failures
+            .lock()
+            .unwrap()
+            .insert(method.to_string(), times);
+    }
+
+    /// Answer every HTTP request with `503 Service Unavailable` while set
+    pub fn set_unavailable(&self, unavailable: bool) {
+        self.inner.unavailable.store(unavailable, Ordering::SeqCst);
+    }
+
+    /// Answer every HTTP request with `429 Too Many Requests` while set,
+    /// asking callers to retry after the given number of seconds
+    ///
+    /// The body is a JSON-RPC `-32005` error repeating the wait in its
+    /// data, as Infura-style endpoints do. Rejected calls are still recorded.
+    pub fn set_rate_limited(&self, retry_after_secs: Option<u64>) {
+        *self.inner.rate_limit.lock().unwrap() = retry_after_secs;
+    }
+
+    /// Number of calls received for `method`
+    pub fn calls(&self, method: &str) -> usize {
+        self.inner
+            .calls
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|call| call.method == method)
+            .count()
+    }
+
+    /// All calls received, in order
+    pub fn requests(&self) -> Vec<RpcCall> {
+        self.inner.calls.lock().unwrap().clone()
+    }
+}
+
+impl Drop for MockRpcServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+async fn entry(
+    State(inner): State<Arc<Inner>>,
+    ws: Option<WebSocketUpgrade>,
+    body: Bytes,
+) -> Response {
+    if let Some(ws) = ws {
+        return ws.on_upgrade(move |socket| serve_socket(inner, socket));
+    }
+
+    if inner.unavailable.load(Ordering::SeqCst) {
+        return StatusCode::SERVICE_UNAVAILABLE.into_response();
+    }
+
+    let rate_limit = *inner.rate_limit.lock().unwrap();
+    if let Some(retry_after) = rate_limit {
+        if let Ok(request) = serde_json::from_slice::<Value>(&body) {
+            inner.record(&request);
+        }
+        let body = json!({
+            "jsonrpc": "2.0",
+            "id": null,
+            "error": {
+                "code": -32005,
+                "message": "Too many requests",
+                "data": { "retry_after": retry_after },
+            },
+        });
+        return (
+            StatusCode::TOO_MANY_REQUESTS,
+            [
+                (
+                    axum::http::header::CONTENT_TYPE,
+                    "application/json".to_string(),
+                ),
+                (axum::http::header::RETRY_AFTER, retry_after.to_string()),
+            ],
+            body.to_string(),
+        )
+            .into_response();
+    }
+
+    let request: Value = match serde_json::from_slice(&body) {
+        Ok(request) => request,
+        Err(e) => return json_response(error_response(Value::Null, -32700, e.to_string())),
+    };
+
+    let response = match request {
+        Value::Array(batch) => Value::Array(
+            batch
+                .into_iter()
+                .map(|request| inner.call(&request))
+                .collect(),
+        ),
+        request => inner.call(&request),
+    };
+
+    json_response(response)
+}
+
+fn json_response(value: Value) -> Response {
+    (
+        [(axum::http::header::CONTENT_TYPE, "application/json")],
+        value.to_string(),
+    )
+        .into_response()
+}
+
+async fn serve_socket(inner: Arc<Inner>, mut socket: WebSocket) {
+    let mut notifications = inner.notifications.subscribe();
+    let mut disconnects = inner.disconnects.subscribe();
+    // subscription id -> subscribe method and kind, for this connection
+    let mut active: HashMap<String, (String, Option<String>)> = HashMap::new();
+
+    loop {
+        tokio::select! {
+            message = socket.recv() => {
+                let text = match message {
+                    Some(Ok(Message::Text(text))) => text,
+                    Some(Ok(Message::Close(_))) | None | Some(Err(_)) => break,
+                    Some(Ok(_)) => continue,
+                };
+
+                let response = match serde_json::from_str::<Value>(&text) {
+                    Ok(Value::Array(batch)) => Value::Array(
+                        batch
+                            .iter()
+                            .map(|request| inner.call_ws(request, &mut active))
+                            .collect(),
+                    ),
+                    Ok(request) => inner.call_ws(&request, &mut active),
+                    Err(e) => error_response(Value::Null, -32700, e.to_string()),
+                };
+
+                if socket.send(Message::Text(response.to_string())).await.is_err() {
+                    break;
+                }
+            }
+            _ = disconnects.recv() => {
+                let _ = socket.send(Message::Close(None)).await;
+                break;
+            }
+            notification = notifications.recv() => {
+                let notification = match notification {
+                    Ok(notification) => notification,
+                    Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(broadcast::error::RecvError::Closed) => break,
+                };
+                let Some(method) = inner.notification_method(&notification.subscribe) else {
+                    continue;
+                };
+
+                let subscribers = active.iter().filter(|(_, (subscribe, kind))| {
+                    *subscribe == notification.subscribe
+                        && (notification.kind.is_none() || *kind == notification.kind)
+                });
+                for (id, _) in subscribers {
+                    let message = json!({
+                        "jsonrpc": "2.0",
+                        "method": method,
+                        "params": { "subscription": id, "result": notification.result },
+                    });
+                    if socket.send(Message::Text(message.to_string())).await.is_err() {
+                        return;
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl Inner {
+    fn notify(&self, subscribe: &str, kind: Option<String>, result: Value) {
+        // No receivers just means nobody is subscribed yet
+        let _ = self.notifications.send(Notification {
+            subscribe: subscribe.to_string(),
+            kind,
+            result,
+        });
+    }
+
+    fn notification_method(&self, subscribe: &str) -> Option<String> {
+        self.subscriptions
+            .read()
+            .unwrap()
+            .get(subscribe)
+            .map(|s| s.notification.clone())
+    }
+
+    /// Handle a request arriving over WebSocket, including (un)subscriptions
+    fn call_ws(
+        &self,
+        request: &Value,
+        active: &mut HashMap<String, (String, Option<String>)>,
+    ) -> Value {
+        let id = request.get("id").cloned().unwrap_or(Value::Null);
+        let method = request.get("method").and_then(Value::as_str).unwrap_or("");
+
+        if self.subscriptions.read().unwrap().contains_key(method) {
+            self.record(request);
+            let subscription = format!(
+                "0x{:016x}",
+                self.next_subscription.fetch_add(1, Ordering::SeqCst)
+            );
+            let kind = request
+                .get("params")
+                .and_then(|p| p.get(0))
+                .and_then(Value::as_str)
+                .map(str::to_string);
+            active.insert(subscription.clone(), (method.to_string(), kind));
+            return result_response(id, json!(subscription));
+        }
+
+        let is_unsubscribe = self
+            .subscriptions
+            .read()
+            .unwrap()
+            .values()
+            .any(|s| s.unsubscribe == method);
+        if is_unsubscribe {
+            self.record(request);
+            let removed = request
+                .get("params")
+                .and_then(|p| p.get(0))
+                .and_then(Value::as_str)
+                .map(|sub| active.remove(sub).is_some())
+                .unwrap_or(false);
+            return result_response(id, json!(removed));
+        }
+
+        self.call(request)
+    }
+
+    fn record(&self, request: &Value) {
+        self.calls.lock().unwrap().push(RpcCall {
+            method: request
+                .get("method")
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string(),
+            params: request.get("params").cloned().unwrap_or(Value::Null),
+        });
+    }
+
+    /// Handle a single JSON-RPC request
+    fn call(&self, request: &Value) -> Value {
+        let id = request.get("id").cloned().unwrap_or(Value::Null);
+        let Some(method) = request.get("method").and_then(Value::as_str) else {
+            return error_response(id, -32600, "Invalid request".to_string());
+        };
+        let params = request.get("params").cloned().unwrap_or(Value::Null);
+        self.record(request);
+
+        {
+            let mut failures = self.failures.lock().unwrap();
+            if let Some(remaining) = failures.get_mut(method) {
+                if *remaining > 0 {
+                    *remaining -= 1;
+                    return error_response(id, -32000, format!("Injected failure for {}", method));
+                }
+            }
+        }
+
+        let handler = self.handlers.read().unwrap().get(method).cloned();
+        match handler {
+            Some(handler) => match handler(&params) {
+                Ok(result) => result_response(id, result),
+                Err(e) => error_response(id, e.code, e.message),
+            },
+            None => error_response(id, -32601, format!("Method not found: {}", method)),
+        }
+    }
+}
+
+fn result_response(id: Value, result: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "result": result })
+}
+
+fn error_response(id: Value, code: i64, message: String) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "error": { "code": code, "message": message } })
+}
+
+/// Parse the `index`-th param as a string
+pub(crate) fn param_str(params: &Value, index: usize) -> std::result::Result<&str, RpcError> {
+    params
+        .get(index)
+        .and_then(Value::as_str)
+        .ok_or_else(|| RpcError::invalid_params(format!("Expected string param {}", index)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::providers::{Http, Provider};
+
+    #[tokio::test]
+    async fn test_canned_response_and_failures() {
+        let mock = MockRpcServer::start().await.unwrap();
+        mock.respond("system_name", json!("mock"));
+        let client = Provider::<Http>::try_from(mock.http_url()).unwrap();
+
+        let name: String = client.request("system_name", ()).await.unwrap();
+        assert_eq!(name, "mock");
+
+        mock.fail_next("system_name", 1);
+        assert!(client
+            .request::<_, String>("system_name", ())
+            .await
+            .is_err());
+        assert!(client.request::<_, String>("system_name", ()).await.is_ok());
+        assert_eq!(mock.calls("system_name"), 3);
+    }
+
+    #[tokio::test]
+    async fn test_unknown_method_and_unavailable() {
+        let mock = MockRpcServer::start().await.unwrap();
+        let client = Provider::<Http>::try_from(mock.http_url()).unwrap();
+        assert!(client.request::<_, Value>("nope", ()).await.is_err());
+
+        mock.respond("ping", json!(true));
+        mock.set_unavailable(true);
+        assert!(client.request::<_, bool>("ping", ()).await.is_err());
+        mock.set_unavailable(false);
+        assert!(client.request::<_, bool>("ping", ()).await.unwrap());
+    }
+}
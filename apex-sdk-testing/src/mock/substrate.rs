@@ -0,0 +1,377 @@
+//! Canned Substrate JSON-RPC node
+
+use super::{param_str, MockRpcServer, RpcError};
+use crate::Result;
+use parity_scale_codec::Encode;
+use serde_json::{json, Value};
+use sp_crypto_hashing::{blake2_128, blake2_256, twox_128};
+use std::collections::HashMap;
+use std::ops::Deref;
+use std::sync::{Arc, Mutex};
+
+#[derive(Debug)]
+struct SubstrateState {
+    chain: String,
+    genesis_hash: [u8; 32],
+    spec_version: u32,
+    transaction_version: u32,
+    block_number: u32,
+    metadata: Option<Vec<u8>>,
+    properties: Value,
+    storage: HashMap<String, String>,
+}
+
+/// Mock Substrate node answering the legacy RPC methods used by subxt
+///
+/// Dereferences to [`MockRpcServer`] for failure injection and call counting.
+/// Runtime metadata is not synthesized: load a pinned metadata file with
+/// [`set_metadata`](Self::set_metadata) before connecting an adapter.
+/// `chain_subscribeNewHeads` and `chain_subscribeFinalizedHeads` are supported
+/// over WebSocket; [`produce_block`](Self::produce_block) notifies subscribers.
+pub struct SubstrateMock {
+    server: MockRpcServer,
+    state: Arc<Mutex<SubstrateState>>,
+}
+
+impl SubstrateMock {
+    /// Start a mock development chain
+    pub async fn start() -> Result<Self> {
+        let server = MockRpcServer::start().await?;
+        let state = Arc::new(Mutex::new(SubstrateState {
+            chain: "Development".to_string(),
+            genesis_hash: [0x11; 32],
+            spec_version: 100,
+            transaction_version: 1,
+            block_number: 1,
+            metadata: None,
+            properties: json!({ "ss58Format": 42, "tokenDecimals": 12, "tokenSymbol": "UNIT" }),
+            storage: HashMap::new(),
+        }));
+
+        let mock = Self { server, state };
+        mock.register();
+        Ok(mock)
+    }
+
+    fn register(&self) {
+        let state = self.state.clone();
+        self.server.handle("system_chain", move |_| {
+            Ok(json!(state.lock().unwrap().chain))
+        });
+        self.server.respond("system_name", json!("apex-mock-node"));
+        self.server.respond("system_version", json!("1.0.0"));
+        self.server.respond(
+            "system_health",
+            json!({ "peers": 0, "isSyncing": false, "shouldHavePeers": false }),
+        );
+
+        let state = self.state.clone();
+        self.server.handle("system_properties", move |_| {
+            Ok(state.lock().unwrap().properties.clone())
+        });
+
+        self.server
+            .handle("system_accountNextIndex", |_| Ok(json!(0)));
+
+        let state = self.state.clone();
+        self.server.handle("chain_getBlockHash", move |params| {
+            let state = state.lock().unwrap();
+            let number = match params.get(0) {
+                Some(Value::Number(n)) => n.as_u64().unwrap_or(0) as u32,
+                Some(Value::String(n)) => u32::from_str_radix(n.trim_start_matches("0x"), 16)
+                    .map_err(|e| RpcError::invalid_params(e.to_string()))?,
+                _ => state.block_number,
+            };
+            if number > state.block_number {
+                return Ok(Value::Null);
+            }
+            Ok(json!(hex_bytes(&block_hash(&state, number))))
+        });
+
+        let state = self.state.clone();
+        self.server.handle("chain_getFinalizedHead", move |_| {
+            let state = state.lock().unwrap();
+            Ok(json!(hex_bytes(&block_hash(&state, state.block_number))))
+        });
+
+        let state = self.state.clone();
+        self.server.handle("chain_getHeader", move |params| {
+            let state = state.lock().unwrap();
+            let number = block_number_of(&state, params.get(0))?;
+            Ok(header_json(&state, number))
+        });
+
+        let state = self.state.clone();
+        self.server.handle("chain_getBlock", move |params| {
+            let state = state.lock().unwrap();
+            let number = block_number_of(&state, params.get(0))?;
+            Ok(json!({
+                "block": { "header": header_json(&state, number), "extrinsics": [] },
+                "justifications": null,
+            }))
+        });
+
+        let state = self.state.clone();
+        self.server.handle("state_getRuntimeVersion", move |_| {
+            let state = state.lock().unwrap();
+            Ok(json!({
+                "specName": "apex-mock",
+                "implName": "apex-mock",
+                "authoringVersion": 1,
+                "specVersion": state.spec_version,
+                "implVersion": 1,
+                "apis": [],
+                "transactionVersion": state.transaction_version,
+                "stateVersion": 1,
+            }))
+        });
+
+        let state = self.state.clone();
+        self.server.handle("state_getMetadata", move |_| {
+            state
+                .lock()
+                .unwrap()
+                .metadata
+                .as_ref()
+                .map(|metadata| json!(hex_bytes(metadata)))
+                .ok_or_else(|| RpcError::new(-32000, "No metadata loaded in mock"))
+        });
+
+        let state = self.state.clone();
+        self.server.handle("state_call", move |params| {
+            let function = param_str(params, 0)?;
+            let state = state.lock().unwrap();
+            let metadata = state
+                .metadata
+                .as_ref()
+                .ok_or_else(|| RpcError::new(-32000, "No metadata loaded in mock"))?;
+            // RuntimeMetadataPrefixed: "meta" magic followed by the version byte
+            let version = metadata.get(4).copied().unwrap_or_default() as u32;
+
+            match function {
+                "Metadata_metadata_versions" => Ok(json!(hex_bytes(&vec![version].encode()))),
+                "Metadata_metadata_at_version" => {
+                    let requested = hex::decode(param_str(params, 1)?.trim_start_matches("0x"))
+                        .ok()
+                        .and_then(|bytes| bytes.get(..4).map(|b| b.to_vec()))
+                        .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]));
+                    let result = (requested == Some(version)).then(|| metadata.clone());
+                    Ok(json!(hex_bytes(&result.encode())))
+                }
+                "Metadata_metadata" => Ok(json!(hex_bytes(&metadata.encode()))),
+                other => Err(RpcError::new(
+                    -32000,
+                    format!("Runtime API {} not mocked", other),
+                )),
+            }
+        });
+
+        let state = self.state.clone();
+        self.server.handle("state_getStorage", move |params| {
+            let key = param_str(params, 0)?.to_lowercase();
+            Ok(state
+                .lock()
+                .unwrap()
+                .storage
+                .get(&key)
+                .map(|value| json!(value))
+                .unwrap_or(Value::Null))
+        });
+
+        self.server.handle("author_submitExtrinsic", |params| {
+            let extrinsic = param_str(params, 0)?;
+            let bytes = hex::decode(extrinsic.trim_start_matches("0x"))
+                .map_err(|e| RpcError::invalid_params(format!("Invalid hex: {}", e)))?;
+            Ok(json!(hex_bytes(&blake2_256(&bytes))))
+        });
+
+        self.server.subscription(
+            "chain_subscribeNewHeads",
+            "chain_newHead",
+            "chain_unsubscribeNewHeads",
+        );
+        self.server.subscription(
+            "chain_subscribeFinalizedHeads",
+            "chain_finalizedHead",
+            "chain_unsubscribeFinalizedHeads",
+        );
+    }
+
+    /// Serve SCALE-encoded runtime metadata (e.g. a pinned `.scale` file)
+    pub fn set_metadata(&self, metadata: Vec<u8>) {
+        self.state.lock().unwrap().metadata = Some(metadata);
+    }
+
+    /// Set the runtime versions reported by `state_getRuntimeVersion`
+    pub fn set_runtime_version(&self, spec_version: u32, transaction_version: u32) {
+        let mut state = self.state.lock().unwrap();
+        state.spec_version = spec_version;
+        state.transaction_version = transaction_version;
+    }
+
+    /// Set the genesis hash
+    pub fn set_genesis_hash(&self, genesis_hash: [u8; 32]) {
+        self.state.lock().unwrap().genesis_hash = genesis_hash;
+    }
+
+    /// Set the chain properties (`ss58Format`, `tokenDecimals`, `tokenSymbol`)
+    pub fn set_properties(&self, properties: Value) {
+        self.state.lock().unwrap().properties = properties;
+    }
+
+    /// Set a raw storage value
+    pub fn set_storage(&self, key: &[u8], value: &[u8]) {
+        self.state
+            .lock()
+            .unwrap()
+            .storage
+            .insert(hex_bytes(key), hex_bytes(value));
+    }
+
+    /// Set `System::Account` for `account_id` with a free balance and nonce
+    pub fn set_account(&self, account_id: [u8; 32], free: u128, nonce: u32) {
+        self.set_storage(
+            &account_storage_key(&account_id),
+            &account_info(free, nonce),
+        );
+    }
+
+    /// Produce a new block and notify head subscribers
+    ///
+    /// Returns the new block number.
+    pub fn produce_block(&self) -> u32 {
+        let (number, header) = {
+            let mut state = self.state.lock().unwrap();
+            state.block_number += 1;
+            (state.block_number, header_json(&state, state.block_number))
+        };
+
+        self.server
+            .notify("chain_subscribeNewHeads", header.clone());
+        self.server.notify("chain_subscribeFinalizedHeads", header);
+        number
+    }
+}
+
+impl Deref for SubstrateMock {
+    type Target = MockRpcServer;
+
+    fn deref(&self) -> &MockRpcServer {
+        &self.server
+    }
+}
+
+/// Storage key of `System::Account` for an account id
+pub fn account_storage_key(account_id: &[u8; 32]) -> Vec<u8> {
+    let mut key = Vec::with_capacity(80);
+    key.extend_from_slice(&twox_128(b"System"));
+    key.extend_from_slice(&twox_128(b"Account"));
+    key.extend_from_slice(&blake2_128(account_id));
+    key.extend_from_slice(account_id);
+    key
+}
+
+/// SCALE-encoded `AccountInfo` with the given free balance and nonce
+fn account_info(free: u128, nonce: u32) -> Vec<u8> {
+    // nonce, consumers, providers, sufficients, data { free, reserved, frozen, flags }
+    (nonce, 0u32, 1u32, 0u32, free, 0u128, 0u128, 0u128).encode()
+}
+
+fn hex_bytes(bytes: &[u8]) -> String {
+    format!("0x{}", hex::encode(bytes))
+}
+
+fn block_hash(state: &SubstrateState, number: u32) -> [u8; 32] {
+    if number == 0 {
+        return state.genesis_hash;
+    }
+    let mut hash = [0u8; 32];
+    hash[28..].copy_from_slice(&number.to_be_bytes());
+    hash
+}
+
+fn block_number_of(
+    state: &SubstrateState,
+    hash: Option<&Value>,
+) -> std::result::Result<u32, RpcError> {
+    let Some(hash) = hash.and_then(Value::as_str) else {
+        return Ok(state.block_number);
+    };
+    let bytes = hex::decode(hash.trim_start_matches("0x"))
+        .map_err(|e| RpcError::invalid_params(format!("Invalid block hash: {}", e)))?;
+    (0..=state.block_number)
+        .find(|n| block_hash(state, *n).as_slice() == bytes.as_slice())
+        .ok_or_else(|| RpcError::new(-32000, "Unknown block"))
+}
+
+fn header_json(state: &SubstrateState, number: u32) -> Value {
+    json!({
+        "parentHash": hex_bytes(&block_hash(state, number.saturating_sub(1))),
+        "number": format!("0x{:x}", number),
+        "stateRoot": hex_bytes(&[0u8; 32]),
+        "extrinsicsRoot": hex_bytes(&[0u8; 32]),
+        "digest": { "logs": [] },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::providers::{Http, Provider};
+
+    #[test]
+    fn test_account_storage_key_prefix() {
+        let key = account_storage_key(&[1; 32]);
+        assert_eq!(
+            hex::encode(&key[..32]),
+            "26aa394eea5630e07c48ae0c9558cef7b99d880ec681799c0cf30e8886371da9"
+        );
+        assert_eq!(key.len(), 80);
+    }
+
+    #[tokio::test]
+    async fn test_storage_and_blocks() {
+        let mock = SubstrateMock::start().await.unwrap();
+        mock.set_account([7; 32], 1_000, 3);
+        let client = Provider::<Http>::try_from(mock.http_url()).unwrap();
+
+        let key = hex_bytes(&account_storage_key(&[7; 32]));
+        let value: Option<String> = client.request("state_getStorage", [key]).await.unwrap();
+        assert_eq!(value.unwrap(), hex_bytes(&account_info(1_000, 3)));
+
+        let genesis: String = client.request("chain_getBlockHash", [0]).await.unwrap();
+        assert_eq!(genesis, hex_bytes(&[0x11; 32]));
+
+        assert_eq!(mock.produce_block(), 2);
+        let header: Value = client.request("chain_getHeader", ()).await.unwrap();
+        assert_eq!(header["number"], "0x2");
+    }
+
+    #[tokio::test]
+    async fn test_metadata_runtime_calls() {
+        let mock = SubstrateMock::start().await.unwrap();
+        let client = Provider::<Http>::try_from(mock.http_url()).unwrap();
+        assert!(client
+            .request::<_, String>("state_getMetadata", ())
+            .await
+            .is_err());
+
+        let metadata = b"meta\x0fpayload".to_vec();
+        mock.set_metadata(metadata.clone());
+
+        let versions: String = client
+            .request("state_call", ("Metadata_metadata_versions", "0x"))
+            .await
+            .unwrap();
+        assert_eq!(versions, hex_bytes(&vec![15u32].encode()));
+
+        let at_version: String = client
+            .request(
+                "state_call",
+                ("Metadata_metadata_at_version", hex_bytes(&15u32.encode())),
+            )
+            .await
+            .unwrap();
+        assert_eq!(at_version, hex_bytes(&Some(metadata).encode()));
+    }
+}
@@ -0,0 +1,110 @@
+//! Node process helpers
+
+use crate::{DevnetError, Result};
+use std::future::Future;
+use std::process::Stdio;
+use std::time::{Duration, Instant};
+use tokio::process::{Child, Command};
+
+/// Pick a free local TCP port
+#[allow(clippy::result_large_err)]
+pub(crate) fn free_port() -> Result<u16> {
+    std::net::TcpListener::bind("127.0.0.1:0")
+        .and_then(|listener| listener.local_addr())
+        .map(|addr| addr.port())
+        .map_err(|e| DevnetError::Startup(format!("Failed to allocate port: {}", e)))
+}
+
+/// Resolve a binary from an environment override, falling back to `default`
+pub(crate) fn binary(env_var: &str, default: &str) -> String {
+    std::env::var(env_var).unwrap_or_else(|_| default.to_string())
+}
+
+/// Spawn a node process that is killed when the handle is dropped
+#[allow(clippy::result_large_err)]
+pub(crate) fn spawn(binary: &str, args: &[String]) -> Result<Child> {
+    tracing::debug!("Spawning {} {}", binary, args.join(" "));
+
+    Command::new(binary)
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => {
+                DevnetError::Unavailable(format!("{} not found on PATH", binary))
+            }
+            _ => DevnetError::Startup(format!("Failed to spawn {}: {}", binary, e)),
+        })
+}
+
+/// Poll `probe` until it succeeds, the child exits, or `timeout` elapses
+pub(crate) async fn wait_ready<F, Fut, T>(
+    what: &str,
+    child: Option<&mut Child>,
+    timeout: Duration,
+    mut probe: F,
+) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let deadline = Instant::now() + timeout;
+    let mut child = child;
+
+    loop {
+        let last_error = match probe().await {
+            Ok(value) => return Ok(value),
+            Err(e) => e,
+        };
+
+        if let Some(child) = child.as_deref_mut() {
+            if let Ok(Some(status)) = child.try_wait() {
+                return Err(DevnetError::Startup(format!(
+                    "{} exited during startup ({})",
+                    what, status
+                )));
+            }
+        }
+
+        if Instant::now() >= deadline {
+            return Err(DevnetError::Startup(format!(
+                "{} not ready after {:?}: {}",
+                what, timeout, last_error
+            )));
+        }
+
+        tokio::time::sleep(Duration::from_millis(200)).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_binary_is_unavailable() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let _guard = runtime.enter();
+        assert!(matches!(
+            spawn("apex-definitely-not-installed", &[]),
+            Err(DevnetError::Unavailable(_))
+        ));
+    }
+
+    #[test]
+    fn test_free_port() {
+        assert_ne!(free_port().unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_wait_ready_times_out() {
+        let result: Result<()> = wait_ready("probe", None, Duration::from_millis(50), || async {
+            Err(DevnetError::Rpc("connection refused".to_string()))
+        })
+        .await;
+        assert!(matches!(result, Err(DevnetError::Startup(_))));
+    }
+}
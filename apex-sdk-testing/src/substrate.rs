@@ -0,0 +1,223 @@
+//! Substrate devnet (substrate-contracts-node or zombienet)
+
+use crate::process::{self, wait_ready};
+use crate::{DevnetError, Result};
+use apex_sdk::substrate::{KeyPairType, SubstrateAdapter, Wallet};
+use apex_sdk::ApexSDK;
+use std::path::Path;
+use std::time::Duration;
+use tokio::process::Child;
+
+/// Development phrase from which the well-known dev accounts are derived
+pub const DEV_PHRASE: &str =
+    "bottom drive obey lake curtain smoke basket hold race lonely fit walk";
+
+/// Well-known dev accounts, pre-funded on `--dev` chains
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DevAccount {
+    Alice,
+    Bob,
+    Charlie,
+    Dave,
+    Eve,
+    Ferdie,
+}
+
+impl DevAccount {
+    /// Derivation name (e.g. "Alice" for `//Alice`)
+    pub fn name(&self) -> &'static str {
+        match self {
+            DevAccount::Alice => "Alice",
+            DevAccount::Bob => "Bob",
+            DevAccount::Charlie => "Charlie",
+            DevAccount::Dave => "Dave",
+            DevAccount::Eve => "Eve",
+            DevAccount::Ferdie => "Ferdie",
+        }
+    }
+
+    /// SR25519 wallet for the account
+    #[allow(clippy::result_large_err)]
+    pub fn wallet(&self) -> Result<Wallet> {
+        Wallet::from_mnemonic_with_path(DEV_PHRASE, Some(self.name()), KeyPairType::Sr25519)
+            .map_err(|e| DevnetError::Startup(format!("Failed to derive {}: {}", self.name(), e)))
+    }
+}
+
+/// Substrate node launch configuration
+#[derive(Debug, Clone)]
+pub struct SubstrateNodeConfig {
+    /// Binary to run
+    pub binary: String,
+    /// Arguments passed to the binary
+    pub args: Vec<String>,
+    /// WebSocket endpoint to wait for; `None` allocates a port and passes `--rpc-port`
+    pub endpoint: Option<String>,
+    /// How long to wait for the RPC to come up
+    pub startup_timeout: Duration,
+}
+
+impl Default for SubstrateNodeConfig {
+    fn default() -> Self {
+        Self::contracts_node()
+    }
+}
+
+impl SubstrateNodeConfig {
+    /// Single `substrate-contracts-node --dev` instance with temporary storage
+    pub fn contracts_node() -> Self {
+        Self {
+            binary: process::binary("APEX_SUBSTRATE_NODE_BIN", "substrate-contracts-node"),
+            args: vec!["--dev".to_string(), "--tmp".to_string()],
+            endpoint: None,
+            startup_timeout: Duration::from_secs(60),
+        }
+    }
+
+    /// Zombienet network from a config file, reachable at `endpoint`
+    ///
+    /// `endpoint` must match the `rpc_port` of a node declared in the config.
+    pub fn zombienet(network_config: impl AsRef<Path>, endpoint: impl Into<String>) -> Self {
+        Self {
+            binary: process::binary("APEX_ZOMBIENET_BIN", "zombienet"),
+            args: vec![
+                "--provider".to_string(),
+                "native".to_string(),
+                "spawn".to_string(),
+                network_config.as_ref().display().to_string(),
+            ],
+            endpoint: Some(endpoint.into()),
+            startup_timeout: Duration::from_secs(300),
+        }
+    }
+
+    /// Set the startup timeout
+    pub fn with_startup_timeout(mut self, timeout: Duration) -> Self {
+        self.startup_timeout = timeout;
+        self
+    }
+}
+
+/// Running (or attached) Substrate node
+///
+/// A spawned node is killed when the handle is dropped.
+pub struct SubstrateNode {
+    endpoint: String,
+    adapter: SubstrateAdapter,
+    _child: Option<Child>,
+}
+
+impl SubstrateNode {
+    /// Attach to `APEX_SUBSTRATE_URL` if set, otherwise spawn `substrate-contracts-node`
+    pub async fn start() -> Result<Self> {
+        match std::env::var("APEX_SUBSTRATE_URL") {
+            Ok(url) => Self::attach(&url).await,
+            Err(_) => Self::spawn(SubstrateNodeConfig::default()).await,
+        }
+    }
+
+    /// Spawn a node (or zombienet network) and wait for its RPC
+    pub async fn spawn(config: SubstrateNodeConfig) -> Result<Self> {
+        let mut args = config.args.clone();
+        let endpoint = match config.endpoint {
+            Some(endpoint) => endpoint,
+            None => {
+                let port = process::free_port()?;
+                args.push("--rpc-port".to_string());
+                args.push(port.to_string());
+                format!("ws://127.0.0.1:{}", port)
+            }
+        };
+
+        let mut child = process::spawn(&config.binary, &args)?;
+        let adapter = wait_ready(
+            &config.binary,
+            Some(&mut child),
+            config.startup_timeout,
+            || connect(&endpoint),
+        )
+        .await?;
+
+        tracing::info!("Substrate node running at {}", endpoint);
+
+        Ok(Self {
+            endpoint,
+            adapter,
+            _child: Some(child),
+        })
+    }
+
+    /// Attach to an already running node
+    pub async fn attach(endpoint: &str) -> Result<Self> {
+        let adapter = wait_ready("substrate node", None, Duration::from_secs(10), || {
+            connect(endpoint)
+        })
+        .await?;
+
+        Ok(Self {
+            endpoint: endpoint.to_string(),
+            adapter,
+            _child: None,
+        })
+    }
+
+    /// WebSocket RPC endpoint
+    pub fn endpoint(&self) -> &str {
+        &self.endpoint
+    }
+
+    /// Adapter connected to the node
+    pub fn adapter(&self) -> &SubstrateAdapter {
+        &self.adapter
+    }
+
+    /// Transfer `amount` from Alice to `address`
+    pub async fn fund(&self, address: &str, amount: u128) -> Result<()> {
+        let alice = DevAccount::Alice.wallet()?;
+        self.adapter
+            .transaction_executor()
+            .transfer(&alice, address, amount)
+            .await
+            .map(|_| ())
+            .map_err(|e| DevnetError::Rpc(format!("Funding {} failed: {}", address, e)))
+    }
+
+    /// Create a fresh random account funded with `amount` by Alice
+    pub async fn funded_wallet(&self, amount: u128) -> Result<Wallet> {
+        let wallet = Wallet::new_random();
+        self.fund(&wallet.address(), amount).await?;
+        Ok(wallet)
+    }
+
+    /// SDK connected to this node
+    pub async fn sdk(&self) -> Result<ApexSDK> {
+        Ok(ApexSDK::builder()
+            .with_substrate_endpoint(&self.endpoint)
+            .build()
+            .await?)
+    }
+}
+
+async fn connect(endpoint: &str) -> Result<SubstrateAdapter> {
+    SubstrateAdapter::connect(endpoint)
+        .await
+        .map_err(|e| DevnetError::Rpc(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_zombienet_config() {
+        let config = SubstrateNodeConfig::zombienet("network.toml", "ws://127.0.0.1:9944");
+        assert_eq!(config.args.last().unwrap(), "network.toml");
+        assert_eq!(config.endpoint.as_deref(), Some("ws://127.0.0.1:9944"));
+    }
+
+    #[test]
+    fn test_dev_account_names() {
+        assert_eq!(DevAccount::Alice.name(), "Alice");
+        assert_eq!(DevAccount::Ferdie.name(), "Ferdie");
+    }
+}
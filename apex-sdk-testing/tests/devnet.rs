@@ -0,0 +1,52 @@
+//! Devnet harness integration tests
+//!
+//! These spawn `anvil` / `substrate-contracts-node` when available and are
+//! skipped otherwise. Set `APEX_REQUIRE_DEVNET=1` to make missing nodes fail.
+
+use apex_sdk::types::{Address, Chain};
+use apex_sdk_testing::{require_devnet, AnvilNode, Devnet, SubstrateNode};
+
+#[tokio::test]
+async fn test_anvil_funding() {
+    let anvil = require_devnet!(AnvilNode::start().await);
+    let wallet = anvil.funded_wallet(5 * 10u128.pow(18)).await.unwrap();
+
+    let sdk = anvil.sdk().await.unwrap();
+    let balance = sdk
+        .get_balance(&Chain::Ethereum, &Address::evm(wallet.address()))
+        .await
+        .unwrap();
+    assert_eq!(balance.value, 5 * 10u128.pow(18));
+}
+
+#[tokio::test]
+async fn test_anvil_prefunded_accounts() {
+    let anvil = require_devnet!(AnvilNode::start().await);
+    anvil.mine(3).await.unwrap();
+
+    let account = anvil.account(0).unwrap();
+    let sdk = anvil.sdk().await.unwrap();
+    let balance = sdk
+        .get_balance(&Chain::Ethereum, &Address::evm(account.address()))
+        .await
+        .unwrap();
+    assert!(balance.value > 0);
+}
+
+#[tokio::test]
+async fn test_substrate_funding() {
+    let node = require_devnet!(SubstrateNode::start().await);
+    let wallet = node.funded_wallet(10u128.pow(12)).await.unwrap();
+
+    let balance = node.adapter().get_balance(&wallet.address()).await.unwrap();
+    assert_eq!(balance, 10u128.pow(12));
+}
+
+#[tokio::test]
+async fn test_hybrid_devnet_sdk() {
+    let devnet = require_devnet!(Devnet::hybrid().await);
+    let sdk = devnet.sdk().await.unwrap();
+
+    assert!(sdk.is_chain_supported(&Chain::Ethereum));
+    assert!(sdk.is_chain_supported(&Chain::Polkadot));
+}
@@ -0,0 +1,1948 @@
+//! Hermetic adapter tests against the mock RPC servers
+
+use apex_sdk::advanced::EventItem;
+use apex_sdk::core::performance::RateLimitConfig;
+use apex_sdk::core::{AdapterError, BlockRef, ChainAdapter};
+use apex_sdk::evm::fees::FeeSpeed;
+use apex_sdk::evm::metrics::{GasMetrics, RpcMetrics};
+use apex_sdk::evm::nft::ERC721_ENUMERABLE_INTERFACE_ID;
+use apex_sdk::evm::pool::ConnectionPool;
+use apex_sdk::evm::preflight::{KnownContracts, RiskKind, Severity};
+use apex_sdk::evm::signer::DigestSigner;
+use apex_sdk::evm::transaction::RetryConfig;
+use apex_sdk::evm::wallet::Wallet;
+use apex_sdk::evm::EvmAdapter;
+use apex_sdk::policy::{AddressListPolicy, PolicyEngine, SpendLimitPolicy};
+use apex_sdk::types::{
+    Address as ApexAddress, Chain, ChainSpec, ChainType, ConfirmationPolicy, EventFilter, Finality,
+    Token, TransactionStatus,
+};
+use apex_sdk::watchdog::{EvmRepricer, TransactionWatchdog, WatchdogConfig};
+use apex_sdk::{ApexSDK, Error, TokenTransfer, WaitConfig};
+use apex_sdk_testing::mock::{EvmMock, SubstrateMock};
+use ethers::providers::{Middleware, Provider, StreamExt, Ws};
+use ethers::signers::{LocalWallet, Signer};
+use ethers::types::transaction::eip2718::TypedTransaction;
+use ethers::types::{Address, BlockNumber, Signature, TransactionRequest, H256, U256};
+use ethers::utils::rlp::Rlp;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+
+const ADDRESS: &str = "0x742D35CC6634c0532925A3b844BC9E7595F0BEb0";
+const TX_HASH: &str = "0x1111111111111111111111111111111111111111111111111111111111111111";
+const TRANSFER_TOPIC: &str = "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef";
+const APPROVAL_TOPIC: &str = "0x8c5be1e5ebec7d5bd14f71427d1e84f3dd0314c0f7b2291e5b200ac8c7c3b925";
+
+#[tokio::test]
+async fn test_connect_and_balance_over_http_and_ws() {
+    let mock = EvmMock::start(31337).await.unwrap();
+    mock.set_balance(ADDRESS, 1_500_000_000_000_000_000);
+
+    for endpoint in [mock.http_url(), mock.ws_url()] {
+        let adapter = EvmAdapter::connect(&endpoint).await.unwrap();
+        assert_eq!(adapter.chain_id().await.unwrap(), 31337);
+        assert_eq!(
+            adapter.get_balance(ADDRESS).await.unwrap(),
+            U256::from(1_500_000_000_000_000_000u128)
+        );
+    }
+    assert_eq!(mock.calls("eth_getBalance"), 2);
+}
+
+#[tokio::test]
+async fn test_balance_amount() {
+    let mock = EvmMock::start(1284).await.unwrap();
+    mock.set_balance(ADDRESS, 1_500_000_000_000_000_000);
+    let adapter = EvmAdapter::connect(&mock.http_url()).await.unwrap();
+    let amount = adapter.get_balance_amount(ADDRESS).await.unwrap();
+    assert_eq!(amount.to_string(), "1.5 GLMR");
+
+    let mock = EvmMock::start(31337).await.unwrap();
+    mock.set_balance(ADDRESS, 2_000_000_000_000_000_000);
+    let adapter = EvmAdapter::connect(&mock.http_url()).await.unwrap();
+    assert_eq!(
+        adapter
+            .get_balance_amount(ADDRESS)
+            .await
+            .unwrap()
+            .to_string(),
+        "2"
+    );
+}
+
+#[tokio::test]
+async fn test_chain_adapter_operations() {
+    let mock = EvmMock::start(1).await.unwrap();
+    mock.set_balance(ADDRESS, 5);
+    mock.set_block_number(42);
+    let adapter = EvmAdapter::connect(&mock.http_url()).await.unwrap();
+    let chain: &dyn ChainAdapter = &adapter;
+
+    let address = apex_sdk::types::Address::evm(ADDRESS);
+    assert_eq!(chain.get_balance(&address).await.unwrap(), 5);
+
+    let latest = chain.latest_block().await.unwrap();
+    assert_eq!(latest.number, 42);
+    let by_hash = chain
+        .get_block(BlockRef::Hash(latest.hash.clone()))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(by_hash, latest);
+    let parent = chain
+        .get_block(BlockRef::Number(41))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(parent.hash, latest.parent_hash);
+    assert!(chain.get_block(43.into()).await.unwrap().is_none());
+    assert!(matches!(
+        chain
+            .get_block(BlockRef::Hash("0xnot-a-hash".to_string()))
+            .await,
+        Err(AdapterError::Encoding(_))
+    ));
+
+    let hash = chain.submit_transaction(&[0xde, 0xad]).await.unwrap();
+    assert_eq!(
+        hash,
+        format!("{:?}", H256::from(ethers::utils::keccak256([0xde, 0xad])))
+    );
+
+    // Adapter errors keep their kind, so callers know what to retry
+    let strict = adapter.with_rate_limit(RateLimitConfig::new(1.0).with_max_queue(0));
+    let chain: &dyn ChainAdapter = &strict;
+    chain.latest_block().await.unwrap();
+    let err = chain.latest_block().await.unwrap_err();
+    assert!(matches!(err, AdapterError::RateLimited(_)));
+    assert!(err.is_retryable());
+
+    // A node without the block answered; asking again will not help
+    mock.respond("eth_getBlockByNumber", serde_json::Value::Null);
+    let adapter = EvmAdapter::connect(&mock.http_url()).await.unwrap();
+    let chain: &dyn ChainAdapter = &adapter;
+    let err = chain.latest_block().await.unwrap_err();
+    assert!(matches!(err, AdapterError::NotFound(_)));
+    assert!(!err.is_retryable());
+}
+
+#[tokio::test]
+async fn test_historical_state_queries() {
+    let mock = EvmMock::start(1).await.unwrap();
+    mock.set_balance(ADDRESS, 42);
+    mock.set_code(ADDRESS, &[0x60, 0x80]);
+    mock.set_call_result(ADDRESS, [0x70, 0xa0, 0x82, 0x31], &[7u8; 32]);
+
+    let adapter = EvmAdapter::connect(&mock.http_url()).await.unwrap();
+    assert_eq!(
+        adapter.get_balance_at(ADDRESS, 100u64).await.unwrap(),
+        U256::from(42)
+    );
+    assert_eq!(
+        adapter
+            .get_code_at(ADDRESS, BlockNumber::Finalized)
+            .await
+            .unwrap(),
+        vec![0x60, 0x80]
+    );
+    assert_eq!(
+        adapter
+            .call_at(ADDRESS, vec![0x70, 0xa0, 0x82, 0x31], 100u64)
+            .await
+            .unwrap(),
+        vec![7u8; 32]
+    );
+
+    // The block goes out as the last parameter
+    let blocks: Vec<_> = mock
+        .requests()
+        .into_iter()
+        .filter(|call| {
+            ["eth_getBalance", "eth_getCode", "eth_call"].contains(&call.method.as_str())
+        })
+        .map(|call| call.params[call.params.as_array().unwrap().len() - 1].clone())
+        .collect();
+    assert_eq!(blocks, ["0x64", "finalized", "0x64"]);
+}
+
+#[tokio::test]
+async fn test_rate_limit_queues_and_rejects() {
+    let mock = EvmMock::start(1).await.unwrap();
+
+    let adapter = EvmAdapter::connect(&mock.http_url())
+        .await
+        .unwrap()
+        .with_rate_limit(RateLimitConfig::new(20.0).with_burst(2).with_max_queue(2));
+    let start = std::time::Instant::now();
+    for _ in 0..4 {
+        adapter.get_balance(ADDRESS).await.unwrap();
+    }
+    // Two requests go out immediately, the other two wait for refills
+    assert!(start.elapsed() >= std::time::Duration::from_millis(80));
+
+    let strict = EvmAdapter::connect(&mock.ws_url())
+        .await
+        .unwrap()
+        .with_rate_limit(RateLimitConfig::new(1.0).with_max_queue(0));
+    strict.get_balance(ADDRESS).await.unwrap();
+    assert!(matches!(
+        strict.get_balance(ADDRESS).await,
+        Err(apex_sdk::evm::Error::RateLimited(_))
+    ));
+    assert_eq!(mock.calls("eth_getBalance"), 5);
+}
+
+#[tokio::test]
+async fn test_connection_failure() {
+    let mock = EvmMock::start(1).await.unwrap();
+    mock.set_unavailable(true);
+    assert!(EvmAdapter::connect(&mock.http_url()).await.is_err());
+
+    mock.set_unavailable(false);
+    assert!(EvmAdapter::connect(&mock.http_url()).await.is_ok());
+}
+
+#[tokio::test]
+async fn test_transaction_status_decoding() {
+    let mock = EvmMock::start(1).await.unwrap();
+    let adapter = EvmAdapter::connect(&mock.http_url()).await.unwrap();
+
+    assert_eq!(
+        adapter.get_transaction_status(TX_HASH).await.unwrap(),
+        TransactionStatus::Unknown
+    );
+
+    mock.add_pending(TX_HASH);
+    assert_eq!(
+        adapter.get_transaction_status(TX_HASH).await.unwrap(),
+        TransactionStatus::Pending
+    );
+
+    mock.set_block_number(12);
+    mock.add_receipt(TX_HASH, 10, true);
+    assert_eq!(
+        adapter.get_transaction_status(TX_HASH).await.unwrap(),
+        TransactionStatus::Confirmed {
+            block_number: 10,
+            confirmations: 2
+        }
+    );
+
+    mock.add_receipt(TX_HASH, 10, false);
+    assert!(matches!(
+        adapter.get_transaction_status(TX_HASH).await.unwrap(),
+        TransactionStatus::Failed { .. }
+    ));
+}
+
+#[tokio::test]
+async fn test_confirmation_policy_status() {
+    let mock = EvmMock::start(1).await.unwrap();
+    let adapter = EvmAdapter::connect(&mock.http_url()).await.unwrap();
+    let policy = ConfirmationPolicy::Blocks(3);
+
+    mock.set_block_number(12);
+    mock.add_receipt(TX_HASH, 10, true);
+    assert_eq!(
+        adapter.confirmation_status(TX_HASH, &policy).await.unwrap(),
+        TransactionStatus::Pending
+    );
+
+    mock.set_block_number(13);
+    assert_eq!(
+        adapter.confirmation_status(TX_HASH, &policy).await.unwrap(),
+        TransactionStatus::Confirmed {
+            block_number: 10,
+            confirmations: 3
+        }
+    );
+
+    mock.add_receipt(TX_HASH, 10, false);
+    assert!(matches!(
+        adapter.confirmation_status(TX_HASH, &policy).await.unwrap(),
+        TransactionStatus::Failed { .. }
+    ));
+}
+
+#[tokio::test]
+async fn test_wait_for_transaction() {
+    let mock = EvmMock::start(1).await.unwrap();
+    mock.set_block_number(10);
+    mock.add_receipt(TX_HASH, 10, true);
+    let sdk = ApexSDK::builder()
+        .with_evm_endpoint(mock.http_url())
+        .build()
+        .await
+        .unwrap();
+
+    let config = WaitConfig::new()
+        .with_confirmations(2)
+        .with_timeout(Duration::from_secs(5))
+        .with_poll_interval(Duration::from_millis(20));
+    let miner = async {
+        for _ in 0..2 {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            mock.mine();
+        }
+    };
+    let (result, ()) = tokio::join!(
+        sdk.wait_for_transaction(&Chain::Ethereum, TX_HASH, &config),
+        miner
+    );
+    let result = result.unwrap();
+    assert_eq!(
+        result.status,
+        TransactionStatus::Confirmed {
+            block_number: 10,
+            confirmations: 2,
+        }
+    );
+    assert!(result.status.is_final(2));
+    assert_eq!(result.block_number, Some(10));
+
+    let unknown = "0x2222222222222222222222222222222222222222222222222222222222222222";
+    let config = config.with_timeout(Duration::from_millis(100));
+    assert!(matches!(
+        sdk.wait_for_transaction(&Chain::Ethereum, unknown, &config)
+            .await,
+        Err(Error::Evm(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_raw_request() {
+    let mock = EvmMock::start(1).await.unwrap();
+    let metrics = Arc::new(RpcMetrics::new());
+    let adapter = EvmAdapter::connect(&mock.http_url())
+        .await
+        .unwrap()
+        .with_rpc_retry(RetryConfig {
+            max_retries: 2,
+            initial_backoff_ms: 1,
+            use_jitter: false,
+            ..Default::default()
+        })
+        .with_rpc_metrics(metrics.clone());
+
+    mock.respond("ots_getApiLevel", serde_json::json!(8));
+    let level: u64 = adapter
+        .raw_request("ots_getApiLevel", serde_json::json!([]))
+        .await
+        .unwrap();
+    assert_eq!(level, 8);
+
+    // errors returned by the node are not retried
+    let unknown = adapter
+        .raw_request::<u64>("alchemy_getTokenBalances", serde_json::json!([ADDRESS]))
+        .await;
+    assert!(matches!(unknown, Err(apex_sdk::evm::Error::Other(_))));
+    assert_eq!(mock.calls("alchemy_getTokenBalances"), 1);
+
+    mock.set_unavailable(true);
+    let unavailable = adapter
+        .raw_request::<u64>("ots_getApiLevel", serde_json::json!([]))
+        .await;
+    assert!(matches!(
+        unavailable,
+        Err(apex_sdk::evm::Error::Connection(_))
+    ));
+    assert_eq!(metrics.retries.load(Ordering::Relaxed), 2);
+    assert_eq!(metrics.successful_calls.load(Ordering::Relaxed), 1);
+    assert_eq!(metrics.failed_calls.load(Ordering::Relaxed), 4);
+}
+
+#[tokio::test]
+async fn test_fee_oracle() {
+    let mock = EvmMock::start(1).await.unwrap();
+    let gas_metrics = Arc::new(GasMetrics::new(10));
+    let adapter = EvmAdapter::connect(&mock.http_url())
+        .await
+        .unwrap()
+        .with_gas_metrics(gas_metrics.clone());
+    let gwei = |n: f64| U256::from((n * 1e9) as u64);
+
+    let fees = adapter.fee_oracle().estimate().await.unwrap();
+    assert_eq!(fees.base_fee_per_gas, gwei(1.0));
+    assert_eq!(
+        fees.suggestion(FeeSpeed::Slow).max_priority_fee_per_gas,
+        gwei(0.2)
+    );
+    assert_eq!(fees.slow.max_fee_per_gas, gwei(1.45));
+    assert_eq!(fees.standard.max_priority_fee_per_gas, gwei(1.0));
+    assert_eq!(fees.standard.max_fee_per_gas, gwei(3.0));
+    assert_eq!(fees.fast.max_priority_fee_per_gas, gwei(1.8));
+    assert_eq!(mock.calls("eth_feeHistory"), 1);
+
+    // executors take their tip from the oracle and record it too
+    let estimate = adapter
+        .transaction_executor()
+        .estimate_gas(ADDRESS.parse().unwrap(), Some(Address::zero()), None, None)
+        .await
+        .unwrap();
+    assert_eq!(estimate.max_priority_fee_per_gas, Some(gwei(1.0)));
+    assert_eq!(mock.calls("eth_feeHistory"), 2);
+    assert_eq!(gas_metrics.avg_base_fee_gwei().await, 1.0);
+    assert_eq!(gas_metrics.avg_priority_fee_gwei().await, 1.0);
+}
+
+#[tokio::test]
+async fn test_rate_limited_endpoint() {
+    let mock = EvmMock::start(1).await.unwrap();
+    let metrics = Arc::new(RpcMetrics::new());
+    let adapter = EvmAdapter::connect(&mock.http_url())
+        .await
+        .unwrap()
+        .with_rpc_retry(RetryConfig {
+            max_retries: 2,
+            initial_backoff_ms: 1,
+            use_jitter: false,
+            ..Default::default()
+        })
+        .with_rpc_metrics(metrics.clone());
+    mock.respond("ots_getApiLevel", serde_json::json!(8));
+
+    // Retried after the endpoint's wait, then reported as a rate limit
+    mock.set_rate_limited(Some(0));
+    let limited = adapter
+        .raw_request::<u64>("ots_getApiLevel", serde_json::json!([]))
+        .await;
+    assert!(matches!(limited, Err(apex_sdk::evm::Error::RateLimited(_))));
+    assert_eq!(metrics.rate_limited.load(Ordering::Relaxed), 3);
+    assert_eq!(metrics.retries.load(Ordering::Relaxed), 2);
+    assert!(matches!(
+        adapter.get_balance(ADDRESS).await,
+        Err(apex_sdk::evm::Error::RateLimited(_))
+    ));
+
+    mock.set_rate_limited(None);
+    let level: u64 = adapter
+        .raw_request("ots_getApiLevel", serde_json::json!([]))
+        .await
+        .unwrap();
+    assert_eq!(level, 8);
+}
+
+#[tokio::test]
+async fn test_pool_rotates_off_rate_limited_endpoint() {
+    let limited = EvmMock::start(1).await.unwrap();
+    let spare = EvmMock::start(1).await.unwrap();
+    let metrics = Arc::new(RpcMetrics::new());
+    let pool = ConnectionPool::new(vec![limited.http_url(), spare.http_url()])
+        .await
+        .unwrap()
+        .with_rpc_metrics(metrics.clone());
+    limited.set_rate_limited(Some(60));
+    spare.respond("ots_getApiLevel", serde_json::json!(8));
+
+    for _ in 0..3 {
+        let level: u64 = pool
+            .raw_request("ots_getApiLevel", serde_json::json!([]))
+            .await
+            .unwrap();
+        assert_eq!(level, 8);
+    }
+
+    // Left alone for the requested minute after the first rate limit
+    assert_eq!(limited.calls("ots_getApiLevel"), 1);
+    assert_eq!(spare.calls("ots_getApiLevel"), 3);
+    assert_eq!(metrics.rate_limited.load(Ordering::Relaxed), 1);
+    assert_eq!(metrics.endpoint_rotations.load(Ordering::Relaxed), 1);
+
+    let health = pool.health_status().await;
+    assert!(health[0].1.is_rate_limited());
+    assert_eq!(health[0].1.rate_limit_count, 1);
+    assert!(health[0].1.is_healthy);
+}
+
+#[tokio::test]
+async fn test_send_retries_transient_errors() {
+    let mock = EvmMock::start(31337).await.unwrap();
+    let adapter = EvmAdapter::connect(&mock.http_url()).await.unwrap();
+    let executor = adapter
+        .transaction_executor()
+        .with_retry_config(RetryConfig {
+            max_retries: 3,
+            initial_backoff_ms: 1,
+            max_backoff_ms: 5,
+            backoff_multiplier: 2.0,
+            use_jitter: false,
+        });
+
+    let wallet = Wallet::new_random().with_chain_id(31337);
+    let tx = TransactionRequest::new()
+        .to(ADDRESS.parse::<ethers::types::Address>().unwrap())
+        .value(1)
+        .gas(21_000)
+        .gas_price(1_000_000_000u64)
+        .nonce(0)
+        .chain_id(31337u64)
+        .into();
+
+    mock.fail_next("eth_sendRawTransaction", 2);
+    let hash = executor.send_raw_transaction(&wallet, tx).await.unwrap();
+    assert_eq!(mock.calls("eth_sendRawTransaction"), 3);
+
+    mock.mine();
+    let status = adapter
+        .get_transaction_status(&format!("{:?}", hash))
+        .await
+        .unwrap();
+    assert!(matches!(status, TransactionStatus::Confirmed { .. }));
+}
+
+#[tokio::test]
+async fn test_new_heads_subscription() {
+    let mock = EvmMock::start(1).await.unwrap();
+    let provider = Provider::<Ws>::connect(mock.ws_url()).await.unwrap();
+    let mut blocks = provider.subscribe_blocks().await.unwrap();
+
+    let mined = mock.mine();
+    let block = blocks.next().await.unwrap();
+    assert_eq!(block.number.unwrap().as_u64(), mined);
+}
+
+#[tokio::test]
+async fn test_adapter_subscriptions() {
+    let mock = EvmMock::start(1).await.unwrap();
+    let http = EvmAdapter::connect(&mock.http_url()).await.unwrap();
+    assert!(!http.is_websocket());
+    assert!(matches!(
+        http.subscribe_new_heads().await,
+        Err(apex_sdk::evm::Error::Connection(_))
+    ));
+
+    let adapter = EvmAdapter::connect(&mock.ws_url()).await.unwrap();
+    assert!(adapter.is_websocket());
+    let mut heads = adapter.subscribe_new_heads().await.unwrap().boxed();
+    let mut transfers = adapter
+        .subscribe_logs(&EventFilter {
+            event_names: Some(vec![TRANSFER_TOPIC.to_string()]),
+            ..Default::default()
+        })
+        .await
+        .unwrap()
+        .boxed();
+    let mut pending = adapter.subscribe_pending_txs().await.unwrap().boxed();
+
+    let provider = Provider::<Ws>::connect(mock.ws_url()).await.unwrap();
+    let raw = vec![0xc0];
+    provider
+        .send_raw_transaction(raw.clone().into())
+        .await
+        .unwrap();
+    let hash = tokio::time::timeout(Duration::from_secs(5), pending.next())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(hash, H256::from(ethers::utils::keccak256(&raw)));
+
+    // Logs not matching the filter are dropped from the stream
+    mock.add_log(ADDRESS, &[APPROVAL_TOPIC], &[]);
+    mock.add_log(ADDRESS, &[TRANSFER_TOPIC], &[1]);
+    let mined = mock.mine();
+
+    let head = tokio::time::timeout(Duration::from_secs(5), heads.next())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(head.number, mined);
+    assert_eq!(head.chain_type, ChainType::Evm);
+    assert_eq!(head.finality, Finality::Latest);
+
+    let event = tokio::time::timeout(Duration::from_secs(5), transfers.next())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(event.block_number, mined);
+    assert_eq!(event.index, 1);
+    assert_eq!(event.name(), TRANSFER_TOPIC);
+}
+
+#[tokio::test]
+async fn test_send_transfer_and_raw() {
+    let mock = EvmMock::start(1337).await.unwrap();
+    mock.set_automine(true);
+    let wallet = Wallet::new_random().with_chain_id(1337);
+    let adapter = EvmAdapter::connect(&mock.http_url()).await.unwrap();
+    let executor = adapter
+        .transaction_executor()
+        .with_receipt_poll_interval(Duration::from_millis(10));
+    let to: Address = ADDRESS.parse().unwrap();
+
+    let receipt = executor
+        .send_transfer(&wallet, to, U256::from(1000))
+        .await
+        .unwrap();
+    assert_eq!(receipt.status, Some(1.into()));
+
+    // Unset fields of a legacy request are filled in
+    let receipt = executor
+        .send_raw(&wallet, TransactionRequest::new().to(to).value(5))
+        .await
+        .unwrap();
+    assert_eq!(receipt.block_number, Some(3.into()));
+
+    let sent: Vec<_> = mock
+        .requests()
+        .into_iter()
+        .filter(|call| call.method == "eth_sendRawTransaction")
+        .map(|call| {
+            let raw =
+                hex::decode(call.params[0].as_str().unwrap().trim_start_matches("0x")).unwrap();
+            TypedTransaction::decode_signed(&Rlp::new(&raw)).unwrap().0
+        })
+        .collect();
+    assert!(matches!(sent[0], TypedTransaction::Eip1559(_)));
+    assert!(matches!(sent[1], TypedTransaction::Legacy(_)));
+    // The mock never advances nonces; the executor does
+    assert_eq!(sent[0].nonce(), Some(&U256::zero()));
+    assert_eq!(sent[1].nonce(), Some(&U256::one()));
+    assert!(sent[1].gas_price().is_some());
+    assert_eq!(sent[1].chain_id(), Some(1337.into()));
+
+    // Unmined transactions time out
+    mock.set_automine(false);
+    let result = executor
+        .with_receipt_timeout(Duration::from_millis(50))
+        .send_transfer(&wallet, to, U256::one())
+        .await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn test_concurrent_sends_get_distinct_nonces() {
+    let mock = EvmMock::start(1337).await.unwrap();
+    let wallet = Wallet::new_random().with_chain_id(1337);
+    let adapter = EvmAdapter::connect(&mock.http_url()).await.unwrap();
+    let to: Address = ADDRESS.parse().unwrap();
+    let send = || {
+        let executor = adapter
+            .transaction_executor()
+            .with_retry_config(RetryConfig {
+                max_retries: 0,
+                ..Default::default()
+            });
+        let wallet = wallet.clone();
+        async move {
+            executor
+                .send_transaction(&wallet, to, U256::one(), None)
+                .await
+        }
+    };
+
+    let (a, b, c) = tokio::join!(send(), send(), send());
+    assert!(a.is_ok() && b.is_ok() && c.is_ok());
+
+    // The nonce of a failed send goes to the next transaction
+    mock.fail_next("eth_sendRawTransaction", 1);
+    assert!(send().await.is_err());
+    let manager = adapter.transaction_executor().nonce_manager().clone();
+    assert!(manager.pending(wallet.eth_address()).is_empty());
+    send().await.unwrap();
+    send().await.unwrap();
+    assert!(manager.pending(wallet.eth_address()).is_empty());
+
+    let mut nonces: Vec<U256> = mock
+        .requests()
+        .into_iter()
+        .filter(|call| call.method == "eth_sendRawTransaction")
+        .map(|call| {
+            let raw =
+                hex::decode(call.params[0].as_str().unwrap().trim_start_matches("0x")).unwrap();
+            *TypedTransaction::decode_signed(&Rlp::new(&raw))
+                .unwrap()
+                .0
+                .nonce()
+                .unwrap()
+        })
+        .collect();
+    // the failed attempt and its replacement both used nonce 3
+    nonces.sort();
+    assert_eq!(nonces, [0, 1, 2, 3, 3, 4].map(U256::from));
+}
+
+#[tokio::test]
+async fn test_sdk_against_mock() {
+    let mock = EvmMock::start(1).await.unwrap();
+    mock.set_balance(ADDRESS, 7);
+
+    let sdk = ApexSDK::builder()
+        .with_evm_endpoint(mock.http_url())
+        .build()
+        .await
+        .unwrap();
+    let balance = sdk
+        .get_balance(&Chain::Ethereum, &ApexAddress::evm(ADDRESS))
+        .await
+        .unwrap();
+    assert_eq!(balance.value, 7);
+    assert_eq!(balance.decimals, 18);
+    assert_eq!(balance.symbol.as_deref(), Some("ETH"));
+}
+
+#[tokio::test]
+async fn test_balance_cache() {
+    let mock = EvmMock::start(1).await.unwrap();
+    mock.set_automine(true);
+    mock.set_balance(ADDRESS, 7);
+    let wallet = Wallet::new_random();
+    let sdk = ApexSDK::builder()
+        .with_evm_endpoint(mock.http_url())
+        .with_evm_wallet(wallet.clone())
+        .with_balance_cache_ttl(Duration::from_secs(60))
+        .build()
+        .await
+        .unwrap();
+
+    // Repeated and differently-cased lookups are served from the cache
+    sdk.get_balance(&Chain::Ethereum, &ApexAddress::evm(ADDRESS))
+        .await
+        .unwrap();
+    mock.set_balance(ADDRESS, 8);
+    let cached = sdk
+        .get_balance(&Chain::Ethereum, &ApexAddress::evm(ADDRESS.to_lowercase()))
+        .await
+        .unwrap();
+    assert_eq!(cached.value, 7);
+    assert_eq!(mock.calls("eth_getBalance"), 1);
+
+    // Addresses that are not accounts on the chain never reach the node
+    let alice = ApexAddress::substrate("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY");
+    assert!(matches!(
+        sdk.get_balance(&Chain::Ethereum, &alice).await,
+        Err(Error::InvalidAddress(_))
+    ));
+    assert_eq!(mock.calls("eth_getBalance"), 1);
+
+    // Executing a transfer drops the recipient's cached balance
+    let tx = sdk
+        .transaction()
+        .from_evm_address(wallet.address())
+        .to_evm_address(ADDRESS)
+        .amount(1)
+        .build()
+        .unwrap();
+    sdk.execute(tx).await.unwrap();
+    let balance = sdk
+        .get_balance(&Chain::Ethereum, &ApexAddress::evm(ADDRESS))
+        .await
+        .unwrap();
+    assert_eq!(balance.value, 8);
+
+    mock.set_balance(ADDRESS, 9);
+    sdk.clear_balance_cache();
+    let balance = sdk
+        .get_balance(&Chain::Ethereum, &ApexAddress::evm(ADDRESS))
+        .await
+        .unwrap();
+    assert_eq!(balance.value, 9);
+}
+
+#[tokio::test]
+async fn test_builder_verifies_chain_id() {
+    let mock = EvmMock::start(56).await.unwrap();
+    let adapter = EvmAdapter::connect(&mock.http_url()).await.unwrap();
+    adapter
+        .verify_chain(&Chain::BinanceSmartChain)
+        .await
+        .unwrap();
+    assert!(adapter.verify_chain(&Chain::Ethereum).await.is_err());
+
+    let result = ApexSDK::builder()
+        .with_chain(&Chain::Ethereum)
+        .with_evm_endpoint(mock.http_url())
+        .build()
+        .await;
+    match result {
+        Err(Error::Evm(e)) => assert!(e.to_string().contains("chain ID 56"), "{}", e),
+        Err(e) => panic!("unexpected error: {}", e),
+        Ok(_) => panic!("connected to the wrong chain"),
+    }
+
+    ApexSDK::builder()
+        .with_chain(&Chain::BinanceSmartChain)
+        .with_evm_endpoint(mock.http_url())
+        .build()
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_custom_chain_end_to_end() {
+    let mock = EvmMock::start(777_001).await.unwrap();
+    let appchain = ChainSpec::new("Mock Appchain", ChainType::Evm, "MAP", 18)
+        .with_evm_chain_id(777_001)
+        .with_endpoint(mock.http_url())
+        .register()
+        .unwrap();
+    assert_eq!("mock-appchain".parse::<Chain>().unwrap(), appchain);
+
+    let wallet = Wallet::new_random();
+    let sdk = ApexSDK::builder()
+        .with_chain(&appchain)
+        .with_evm_wallet(wallet.clone())
+        .build()
+        .await
+        .unwrap();
+    assert!(sdk.is_chain_supported(&appchain));
+    assert_eq!(
+        Chain::from_evm_chain_id(sdk.evm().unwrap().chain_id().await.unwrap()),
+        Some(appchain.clone())
+    );
+
+    // Custom EVM chains wait for 12 confirmations
+    mock.add_receipt(TX_HASH, 10, true);
+    mock.set_block_number(15);
+    assert!(matches!(
+        sdk.get_transaction_status(&appchain, TX_HASH)
+            .await
+            .unwrap(),
+        TransactionStatus::Pending
+    ));
+    mock.set_block_number(30);
+    assert!(matches!(
+        sdk.get_transaction_status(&appchain, TX_HASH)
+            .await
+            .unwrap(),
+        TransactionStatus::Confirmed {
+            block_number: 10,
+            ..
+        }
+    ));
+
+    mock.set_automine(true);
+    let tx = sdk
+        .transaction()
+        .from_evm_address(wallet.address())
+        .to_evm_address("0x1234567890123456789012345678901234567890")
+        .amount(1)
+        .on_chain(appchain.clone())
+        .build()
+        .unwrap();
+    assert!(!tx.is_cross_chain());
+    let result = sdk.execute(tx).await.unwrap();
+    assert_eq!(result.block_number, Some(31));
+    assert_eq!(mock.calls("eth_sendRawTransaction"), 1);
+}
+
+#[tokio::test]
+async fn test_execute_estimates_gas_limit() {
+    let mock = EvmMock::start(1).await.unwrap();
+    mock.set_automine(true);
+    let wallet = Wallet::new_random();
+    let sdk = ApexSDK::builder()
+        .with_evm_endpoint(mock.http_url())
+        .with_evm_wallet(wallet.clone())
+        .with_gas_limit_multiplier(1.5)
+        .build()
+        .await
+        .unwrap();
+    let recipient = "0x1234567890123456789012345678901234567890";
+
+    // the mock estimates 21000 gas for everything
+    let request = TransactionRequest::new()
+        .from(wallet.eth_address())
+        .to(recipient.parse::<Address>().unwrap());
+    assert_eq!(
+        sdk.evm().unwrap().estimate_gas(request).await.unwrap(),
+        U256::from(21_000)
+    );
+
+    let transfer = |gas_limit: Option<u64>| {
+        let builder = sdk
+            .transaction()
+            .from_evm_address(wallet.address())
+            .to_evm_address(recipient)
+            .amount(1);
+        match gas_limit {
+            Some(limit) => builder.with_gas_limit(limit),
+            None => builder,
+        }
+        .build()
+        .unwrap()
+    };
+    sdk.execute(transfer(None)).await.unwrap();
+    sdk.execute(transfer(Some(50_000))).await.unwrap();
+    assert_eq!(mock.calls("eth_estimateGas"), 2);
+
+    let sent: Vec<TypedTransaction> = mock
+        .requests()
+        .into_iter()
+        .filter(|call| call.method == "eth_sendRawTransaction")
+        .map(|call| {
+            let raw =
+                hex::decode(call.params[0].as_str().unwrap().trim_start_matches("0x")).unwrap();
+            TypedTransaction::decode_signed(&Rlp::new(&raw)).unwrap().0
+        })
+        .collect();
+    assert_eq!(sent[0].gas(), Some(&U256::from(31_500)));
+    assert_eq!(sent[1].gas(), Some(&U256::from(50_000)));
+}
+
+#[tokio::test]
+async fn test_token_amounts_use_contract_decimals() {
+    let mock = EvmMock::start(1).await.unwrap();
+    let mut decimals = [0u8; 32];
+    decimals[31] = 6;
+    mock.set_call_result(ADDRESS, [0x31, 0x3c, 0xe5, 0x67], &decimals);
+
+    let wallet = Wallet::new_random();
+    let sdk = ApexSDK::builder()
+        .with_evm_endpoint(mock.http_url())
+        .with_evm_wallet(wallet.clone())
+        .build()
+        .await
+        .unwrap();
+    let token = Token::erc20(ADDRESS);
+    assert_eq!(
+        sdk.token_decimals(&Chain::Ethereum, &token).await.unwrap(),
+        6
+    );
+    assert_eq!(
+        sdk.parse_amount(&Chain::Ethereum, &token, "1.5")
+            .await
+            .unwrap(),
+        1_500_000
+    );
+    // Known decimals are not read again
+    assert_eq!(
+        sdk.parse_amount(&Chain::Ethereum, &token.clone().with_decimals(2), "1.5")
+            .await
+            .unwrap(),
+        150
+    );
+    assert_eq!(mock.calls("eth_call"), 2);
+
+    mock.set_automine(true);
+    let recipient = "0x1234567890123456789012345678901234567890";
+    let tx = sdk
+        .transaction()
+        .from_evm_address(wallet.address())
+        .to_evm_address(recipient)
+        .amount(1_500_000)
+        .token(token)
+        .build()
+        .unwrap();
+    let result = sdk.execute(tx).await.unwrap();
+    assert!(matches!(result.status, TransactionStatus::Confirmed { .. }));
+    // Executing sends the raw amount without looking up decimals
+    assert_eq!(mock.calls("eth_call"), 2);
+
+    // Sent to the token contract as transfer(recipient, amount)
+    let raw = mock
+        .requests()
+        .into_iter()
+        .find(|call| call.method == "eth_sendRawTransaction")
+        .unwrap();
+    let raw = hex::decode(raw.params[0].as_str().unwrap().trim_start_matches("0x")).unwrap();
+    let (sent, _) = TypedTransaction::decode_signed(&Rlp::new(&raw)).unwrap();
+    assert_eq!(sent.to_addr(), Some(&ADDRESS.parse().unwrap()));
+    assert_eq!(sent.value().copied().unwrap_or_default(), U256::zero());
+    let data = sent.data().unwrap();
+    assert_eq!(data[..4], [0xa9, 0x05, 0x9c, 0xbb]);
+    assert_eq!(data[16..36], recipient.parse::<Address>().unwrap()[..]);
+    assert_eq!(U256::from_big_endian(&data[36..68]), U256::from(1_500_000));
+}
+
+#[tokio::test]
+async fn test_transfer_routes_by_token() {
+    let mock = EvmMock::start(1).await.unwrap();
+    mock.set_automine(true);
+    let wallet = Wallet::new_random();
+    let sdk = ApexSDK::builder()
+        .with_evm_endpoint(mock.http_url())
+        .with_evm_wallet(wallet.clone())
+        .build()
+        .await
+        .unwrap();
+    let recipient = "0x1234567890123456789012345678901234567890";
+    let transfer = |token: Token| TokenTransfer {
+        chain: Chain::Ethereum,
+        token,
+        from: apex_sdk::types::Address::evm(wallet.address()),
+        to: apex_sdk::types::Address::evm(recipient),
+        amount: 7,
+    };
+
+    sdk.transfer(transfer(Token::Native)).await.unwrap();
+    sdk.transfer(transfer(Token::erc20(ADDRESS).with_decimals(6)))
+        .await
+        .unwrap();
+    // Assets pallet tokens need a Substrate sender
+    assert!(sdk.transfer(transfer(Token::asset(1984))).await.is_err());
+
+    let sent: Vec<TypedTransaction> = mock
+        .requests()
+        .into_iter()
+        .filter(|call| call.method == "eth_sendRawTransaction")
+        .map(|call| {
+            let raw =
+                hex::decode(call.params[0].as_str().unwrap().trim_start_matches("0x")).unwrap();
+            TypedTransaction::decode_signed(&Rlp::new(&raw)).unwrap().0
+        })
+        .collect();
+    assert_eq!(sent.len(), 2);
+    assert_eq!(sent[0].to_addr(), Some(&recipient.parse().unwrap()));
+    assert_eq!(sent[0].value(), Some(&U256::from(7)));
+    assert_eq!(sent[1].to_addr(), Some(&ADDRESS.parse().unwrap()));
+    assert_eq!(sent[1].data().unwrap()[..4], [0xa9, 0x05, 0x9c, 0xbb]);
+}
+
+#[tokio::test]
+async fn test_event_subscription_fed_by_evm_logs() {
+    let mock = EvmMock::start(1).await.unwrap();
+    let sdk = ApexSDK::builder()
+        .with_evm_endpoint(mock.http_url())
+        .with_event_poll_interval(Duration::from_millis(20))
+        .build()
+        .await
+        .unwrap();
+
+    let transfers = sdk.subscribe_events(EventFilter {
+        event_names: Some(vec![TRANSFER_TOPIC.to_string()]),
+        ..Default::default()
+    });
+    let mut receiver = transfers.subscribe();
+    // Let the follower record the current head before anything is mined
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    mock.add_log(ADDRESS, &[APPROVAL_TOPIC], &[]);
+    mock.add_log(ADDRESS, &[TRANSFER_TOPIC], &[1]);
+    let mined = mock.mine();
+
+    let Some(EventItem::Event(event)) =
+        tokio::time::timeout(Duration::from_secs(5), receiver.recv())
+            .await
+            .unwrap()
+    else {
+        panic!("expected an event");
+    };
+    assert_eq!(event.block_number, mined);
+    assert_eq!(event.index, 1);
+    assert_eq!(event.name(), TRANSFER_TOPIC);
+}
+
+#[tokio::test]
+async fn test_subscriptions_fed_over_websocket() {
+    let mock = EvmMock::start(1).await.unwrap();
+    // Polling would not pick anything up within the test
+    let sdk = ApexSDK::builder()
+        .with_evm_endpoint(mock.ws_url())
+        .with_event_poll_interval(Duration::from_secs(60))
+        .build()
+        .await
+        .unwrap();
+
+    let transfers = sdk.subscribe_events(EventFilter {
+        event_names: Some(vec![TRANSFER_TOPIC.to_string()]),
+        ..Default::default()
+    });
+    let mut events = transfers.subscribe();
+    let blocks = sdk.subscribe_blocks(Some(ChainType::Evm));
+    let mut heads = blocks.subscribe();
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    mock.add_log(ADDRESS, &[TRANSFER_TOPIC], &[1]);
+    let mined = mock.mine();
+
+    let head = tokio::time::timeout(Duration::from_secs(5), heads.recv())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(head.number, mined);
+    let Some(EventItem::Event(event)) = tokio::time::timeout(Duration::from_secs(5), events.recv())
+        .await
+        .unwrap()
+    else {
+        panic!("expected an event");
+    };
+    assert_eq!(event.block_number, mined);
+    assert!(mock
+        .requests()
+        .iter()
+        .all(|call| call.method != "eth_getLogs"));
+}
+
+#[tokio::test]
+async fn test_block_subscription_survives_dropped_connection() {
+    let mock = EvmMock::start(1).await.unwrap();
+    let sdk = ApexSDK::builder()
+        .with_evm_endpoint(mock.ws_url())
+        .with_event_poll_interval(Duration::from_secs(60))
+        .build()
+        .await
+        .unwrap();
+
+    let blocks = sdk.subscribe_blocks(Some(ChainType::Evm));
+    let mut heads = blocks.subscribe();
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    let before = mock.mine();
+    let head = tokio::time::timeout(Duration::from_secs(5), heads.recv())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(head.number, before);
+
+    mock.drop_connections();
+    // Keep mining until a block arrives over the new connection
+    let head = tokio::time::timeout(Duration::from_secs(20), async {
+        loop {
+            mock.mine();
+            tokio::select! {
+                head = heads.recv() => break head.unwrap(),
+                _ = tokio::time::sleep(Duration::from_millis(200)) => {}
+            }
+        }
+    })
+    .await
+    .unwrap();
+    assert!(head.number > before);
+}
+
+#[tokio::test]
+async fn test_event_backfill_then_live() {
+    let mock = EvmMock::start(1).await.unwrap();
+    mock.add_log(ADDRESS, &[TRANSFER_TOPIC], &[1]);
+    mock.mine();
+    mock.add_log(ADDRESS, &[TRANSFER_TOPIC], &[2]);
+    let head = mock.mine();
+
+    let sdk = ApexSDK::builder()
+        .with_evm_endpoint(mock.http_url())
+        .with_event_poll_interval(Duration::from_millis(20))
+        .build()
+        .await
+        .unwrap();
+    let transfers = sdk.subscribe_events(EventFilter::default());
+    let mut replay = transfers.backfill(0, head).await.unwrap();
+    assert_eq!(replay.remaining_history(), 2);
+
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    mock.add_log(ADDRESS, &[TRANSFER_TOPIC], &[3]);
+    let live = mock.mine();
+
+    let mut blocks = Vec::new();
+    for _ in 0..3 {
+        let Some(EventItem::Event(event)) =
+            tokio::time::timeout(Duration::from_secs(5), replay.recv())
+                .await
+                .unwrap()
+        else {
+            panic!("expected an event");
+        };
+        blocks.push(event.block_number);
+    }
+    assert_eq!(blocks, vec![head - 1, head, live]);
+}
+
+#[tokio::test]
+async fn test_block_subscription_follows_evm_heads() {
+    let mock = EvmMock::start(1).await.unwrap();
+    let sdk = ApexSDK::builder()
+        .with_evm_endpoint(mock.http_url())
+        .with_event_poll_interval(Duration::from_millis(20))
+        .with_block_finality(Finality::Finalized)
+        .build()
+        .await
+        .unwrap();
+
+    let blocks = sdk.subscribe_blocks(Some(ChainType::Evm));
+    let mut receiver = blocks.subscribe();
+    let mut numbers = Vec::new();
+    for mined in 0..3 {
+        if mined > 0 {
+            mock.mine();
+        }
+        let block = tokio::time::timeout(Duration::from_secs(5), receiver.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(block.chain_type, ChainType::Evm);
+        assert_eq!(block.finality, Finality::Finalized);
+        numbers.push(block.number);
+    }
+    assert_eq!(numbers, vec![1, 2, 3]);
+
+    // Blocks mined between polls are caught up in order
+    mock.mine();
+    mock.mine();
+    for expected in [4, 5] {
+        let block = tokio::time::timeout(Duration::from_secs(5), receiver.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(block.number, expected);
+    }
+}
+
+#[tokio::test]
+async fn test_execute_enforces_policy() {
+    let mock = EvmMock::start(1).await.unwrap();
+    let wallet = Wallet::new_random();
+    mock.set_automine(true);
+    let sdk = ApexSDK::builder()
+        .with_evm_endpoint(mock.http_url())
+        .with_evm_wallet(wallet.clone())
+        .with_policy_engine(
+            PolicyEngine::new()
+                .with_policy(SpendLimitPolicy::new().with_default_limit(100))
+                .with_policy(AddressListPolicy::new().deny(ADDRESS)),
+        )
+        .build()
+        .await
+        .unwrap();
+
+    let transfer = |to: &str, amount| {
+        sdk.transaction()
+            .from_evm_address(wallet.address())
+            .to_evm_address(to)
+            .amount(amount)
+            .build()
+            .unwrap()
+    };
+    let other = "0x0000000000000000000000000000000000000001";
+
+    sdk.execute(transfer(other, 70)).await.unwrap();
+    assert!(matches!(
+        sdk.execute(transfer(other, 70)).await,
+        Err(Error::Policy(_))
+    ));
+    assert!(matches!(
+        sdk.execute(transfer(ADDRESS, 1)).await,
+        Err(Error::Policy(_))
+    ));
+}
+
+/// Signs with an in-memory key through the remote signer path
+struct TestSigner(LocalWallet);
+
+#[async_trait::async_trait]
+impl DigestSigner for TestSigner {
+    fn address(&self) -> Address {
+        self.0.address()
+    }
+
+    async fn sign_digest(&self, digest: H256) -> Result<Signature, apex_sdk::evm::Error> {
+        self.0
+            .sign_hash(digest)
+            .map_err(|e| apex_sdk::evm::Error::Other(e.to_string()))
+    }
+}
+
+#[tokio::test]
+async fn test_execute_with_remote_signer() {
+    let mock = EvmMock::start(5).await.unwrap();
+    mock.set_automine(true);
+    let key: LocalWallet = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80"
+        .parse()
+        .unwrap();
+    let sender = format!("{:?}", key.address());
+    let sdk = ApexSDK::builder()
+        .with_evm_endpoint(mock.http_url())
+        .with_evm_signer(TestSigner(key.clone()))
+        .build()
+        .await
+        .unwrap();
+
+    assert_eq!(sdk.signer_address(&Chain::Ethereum), Some(sender.clone()));
+    assert_eq!(sdk.signer_address(&Chain::Polkadot), None);
+
+    let tx = sdk
+        .transaction()
+        .from_evm_address(&sender)
+        .to_evm_address(ADDRESS)
+        .amount(42)
+        .build()
+        .unwrap();
+    sdk.execute(tx).await.unwrap();
+
+    let raw = mock
+        .requests()
+        .into_iter()
+        .find(|call| call.method == "eth_sendRawTransaction")
+        .unwrap();
+    let raw = hex::decode(raw.params[0].as_str().unwrap().trim_start_matches("0x")).unwrap();
+    let (tx, signature) = TypedTransaction::decode_signed(&Rlp::new(&raw)).unwrap();
+    assert_eq!(tx.chain_id(), Some(5.into()));
+    assert_eq!(signature.recover(tx.sighash()).unwrap(), key.address());
+}
+
+#[tokio::test]
+async fn test_nft_queries() {
+    let mock = EvmMock::start(1).await.unwrap();
+    let collection = "0x00000000000000000000000000000000000000c1";
+    let selector = |signature: &str| ethers::utils::id(signature);
+    let uint = |value: u64| ethers::abi::encode(&[ethers::abi::Token::Uint(value.into())]);
+    mock.set_call_result(collection, selector("balanceOf(address)"), &uint(2));
+    mock.set_call_result(
+        collection,
+        selector("tokenOfOwnerByIndex(address,uint256)"),
+        &uint(42),
+    );
+    mock.set_call_result(
+        collection,
+        selector("supportsInterface(bytes4)"),
+        &ethers::abi::encode(&[ethers::abi::Token::Bool(true)]),
+    );
+    mock.set_call_result(
+        collection,
+        selector("balanceOfBatch(address[],uint256[])"),
+        &ethers::abi::encode(&[ethers::abi::Token::Array(vec![
+            ethers::abi::Token::Uint(5.into()),
+            ethers::abi::Token::Uint(0.into()),
+        ])]),
+    );
+    mock.set_call_result(
+        collection,
+        selector("uri(uint256)"),
+        &ethers::abi::encode(&[ethers::abi::Token::String(
+            "ipfs://meta/{id}.json".to_string(),
+        )]),
+    );
+
+    let adapter = EvmAdapter::connect(&mock.http_url()).await.unwrap();
+    let owner: Address = ADDRESS.parse().unwrap();
+
+    let erc721 = adapter.erc721(collection).unwrap();
+    assert!(erc721
+        .supports_interface(ERC721_ENUMERABLE_INTERFACE_ID)
+        .await
+        .unwrap());
+    assert_eq!(
+        erc721.tokens_of_owner(owner).await.unwrap(),
+        vec![U256::from(42); 2]
+    );
+    // A count no contract could hold is an error, not a panic
+    mock.set_call_result(
+        collection,
+        selector("balanceOf(address)"),
+        &ethers::abi::encode(&[ethers::abi::Token::Uint(U256::MAX)]),
+    );
+    assert!(matches!(
+        erc721.tokens_of_owner(owner).await,
+        Err(apex_sdk::evm::Error::Contract(_))
+    ));
+
+    let erc1155 = adapter.erc1155(collection).unwrap();
+    assert_eq!(
+        erc1155
+            .balance_of_batch(&[owner, owner], &[1.into(), 2.into()])
+            .await
+            .unwrap(),
+        vec![U256::from(5), U256::zero()]
+    );
+    assert!(erc1155.balance_of_batch(&[owner], &[]).await.is_err());
+    assert_eq!(
+        erc1155.uri(U256::from(255)).await.unwrap(),
+        format!("ipfs://meta/{:0>64}.json", "ff")
+    );
+}
+
+#[tokio::test]
+async fn test_abi_contract_calls() {
+    const ABI: &str = r#"[
+        {"type":"function","name":"decimals","stateMutability":"view","inputs":[],"outputs":[{"name":"","type":"uint8"}]},
+        {"type":"function","name":"getReserves","stateMutability":"view","inputs":[],"outputs":[{"name":"reserve0","type":"uint112"},{"name":"reserve1","type":"uint112"}]},
+        {"type":"function","name":"transfer","stateMutability":"nonpayable","inputs":[{"name":"to","type":"address"},{"name":"amount","type":"uint256"}],"outputs":[{"name":"","type":"bool"}]}
+    ]"#;
+    let mock = EvmMock::start(1).await.unwrap();
+    mock.set_automine(true);
+    let token = "0x00000000000000000000000000000000000000d1";
+    mock.set_call_result(
+        token,
+        ethers::utils::id("decimals()"),
+        &ethers::abi::encode(&[ethers::abi::Token::Uint(6.into())]),
+    );
+    mock.set_call_result(
+        token,
+        ethers::utils::id("getReserves()"),
+        &ethers::abi::encode(&[
+            ethers::abi::Token::Uint(100.into()),
+            ethers::abi::Token::Uint(200.into()),
+        ]),
+    );
+
+    let adapter = EvmAdapter::connect(&mock.http_url()).await.unwrap();
+    let contract = adapter.contract(token).unwrap().with_abi_json(ABI).unwrap();
+
+    let decimals: u8 = contract.call("decimals", ()).await.unwrap();
+    assert_eq!(decimals, 6);
+    let reserves: (U256, U256) = contract.call("getReserves", ()).await.unwrap();
+    assert_eq!(reserves, (100.into(), 200.into()));
+
+    let to: Address = ADDRESS.parse().unwrap();
+    let wallet = Wallet::new_random();
+    contract
+        .send("transfer", (to, U256::from(25)), &wallet)
+        .await
+        .unwrap();
+    let raw = mock
+        .requests()
+        .into_iter()
+        .find(|call| call.method == "eth_sendRawTransaction")
+        .unwrap();
+    let raw = hex::decode(raw.params[0].as_str().unwrap().trim_start_matches("0x")).unwrap();
+    let (sent, _) = TypedTransaction::decode_signed(&Rlp::new(&raw)).unwrap();
+    let data = sent.data().unwrap();
+    assert_eq!(&data[..4], &ethers::utils::id("transfer(address,uint256)"));
+    assert_eq!(
+        ethers::abi::decode(
+            &[
+                ethers::abi::ParamType::Address,
+                ethers::abi::ParamType::Uint(256)
+            ],
+            &data[4..]
+        )
+        .unwrap(),
+        vec![
+            ethers::abi::Token::Address(to),
+            ethers::abi::Token::Uint(25.into())
+        ]
+    );
+
+    // Unknown functions, wrong arity and missing ABIs are rejected
+    assert!(contract.call::<u8>("symbol", ()).await.is_err());
+    assert!(contract.call::<u8>("decimals", to).await.is_err());
+    assert!(adapter
+        .contract(token)
+        .unwrap()
+        .call::<u8>("decimals", ())
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn test_deploy_contract() {
+    let mock = EvmMock::start(1).await.unwrap();
+    mock.set_automine(true);
+    let adapter = EvmAdapter::connect(&mock.http_url()).await.unwrap();
+    let wallet = Wallet::new_random();
+    let bytecode = vec![0x60, 0x80, 0x60, 0x40, 0x52];
+
+    let deployment = adapter
+        .deploy(
+            bytecode.clone(),
+            (U256::from(42), wallet.eth_address()),
+            &wallet,
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        deployment.address,
+        ethers::utils::get_contract_address(wallet.eth_address(), 0)
+    );
+    assert_eq!(
+        deployment.receipt.contract_address,
+        Some(deployment.address)
+    );
+
+    let raw = mock
+        .requests()
+        .into_iter()
+        .find(|call| call.method == "eth_sendRawTransaction")
+        .unwrap();
+    let raw = hex::decode(raw.params[0].as_str().unwrap().trim_start_matches("0x")).unwrap();
+    let (sent, _) = TypedTransaction::decode_signed(&Rlp::new(&raw)).unwrap();
+    assert!(sent.to().is_none());
+    let data = sent.data().unwrap();
+    assert_eq!(&data[..bytecode.len()], &bytecode[..]);
+    assert_eq!(
+        ethers::abi::decode(
+            &[
+                ethers::abi::ParamType::Uint(256),
+                ethers::abi::ParamType::Address
+            ],
+            &data[bytecode.len()..]
+        )
+        .unwrap(),
+        vec![
+            ethers::abi::Token::Uint(42.into()),
+            ethers::abi::Token::Address(wallet.eth_address())
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_get_events_paginates() {
+    let mock = EvmMock::start(1).await.unwrap();
+    let token = "0x00000000000000000000000000000000000000e1";
+    let other = "0x00000000000000000000000000000000000000e2";
+    let amount = |value: u64| ethers::abi::encode(&[ethers::abi::Token::Uint(value.into())]);
+    mock.add_log(token, &[TRANSFER_TOPIC], &amount(10));
+    mock.mine();
+    mock.add_log(other, &[TRANSFER_TOPIC], &amount(10));
+    mock.mine();
+    mock.add_log(token, &[APPROVAL_TOPIC], &amount(10));
+    mock.mine();
+    mock.add_log(token, &[TRANSFER_TOPIC], &amount(5_000));
+    let last = mock.mine();
+    mock.set_max_log_range(Some(2));
+
+    let adapter = EvmAdapter::connect(&mock.http_url()).await.unwrap();
+    let transfers = EventFilter::new()
+        .address(apex_sdk::types::Address::evm(token))
+        .topic(0, TRANSFER_TOPIC)
+        .from_block(1)
+        .to_block(last);
+
+    let events = adapter.get_events(&transfers).await.unwrap();
+    assert_eq!(
+        events.iter().map(|e| e.block_number).collect::<Vec<_>>(),
+        vec![2, last]
+    );
+
+    // Data conditions are checked on the returned logs
+    let large = transfers.clone().data_word_at_least(0, 1_000);
+    let events = adapter.get_events(&large).await.unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].block_number, last);
+
+    // The range defaults to the latest block
+    let latest = adapter
+        .get_events(&EventFilter::new().topic(0, TRANSFER_TOPIC))
+        .await
+        .unwrap();
+    assert_eq!(latest.len(), 1);
+}
+
+#[tokio::test]
+async fn test_security_preflight() {
+    let mock = EvmMock::start(1).await.unwrap();
+    let router = "0x00000000000000000000000000000000000000aa";
+    let implementation = "0x00000000000000000000000000000000000000bb";
+    mock.set_code(router, &[0x60, 0x80]);
+    mock.respond(
+        "debug_traceCall",
+        serde_json::json!({
+            "type": "CALL",
+            "from": ADDRESS,
+            "to": router,
+            "value": "0x1",
+            "input": "0x",
+            "calls": [{
+                "type": "DELEGATECALL",
+                "from": router,
+                "to": implementation,
+                "input": "0x",
+            }],
+        }),
+    );
+
+    let adapter = EvmAdapter::connect(&mock.http_url()).await.unwrap();
+    let tx = TransactionRequest::new()
+        .from(ADDRESS.parse::<Address>().unwrap())
+        .to(router.parse::<Address>().unwrap())
+        .value(1)
+        .into();
+
+    let report = adapter.security_preflight().analyze(&tx).await.unwrap();
+    assert!(report.traced);
+    assert_eq!(report.of_kind(RiskKind::DelegateCallToUnknown).count(), 1);
+    assert_eq!(
+        report
+            .of_kind(RiskKind::TransferToUnverifiedContract)
+            .count(),
+        1
+    );
+    assert_eq!(report.max_severity(), Some(Severity::Critical));
+
+    let verified = KnownContracts::new()
+        .with(router.parse().unwrap())
+        .with(implementation.parse().unwrap());
+    let report = adapter
+        .security_preflight()
+        .with_verifier(verified)
+        .analyze(&tx)
+        .await
+        .unwrap();
+    assert!(report.is_clean());
+
+    // Without tracing only the top-level call is analyzed
+    let report = adapter
+        .security_preflight()
+        .without_trace()
+        .analyze(&tx)
+        .await
+        .unwrap();
+    assert!(!report.traced);
+    assert_eq!(report.max_severity(), Some(Severity::Medium));
+
+    let wallet = Wallet::new_random().with_chain_id(1);
+    let executor = adapter
+        .transaction_executor()
+        .with_preflight(adapter.security_preflight(), Severity::High);
+    let blocked = TransactionRequest::new()
+        .to(router.parse::<Address>().unwrap())
+        .value(1)
+        .gas(21_000)
+        .gas_price(1_000_000_000u64)
+        .nonce(0)
+        .chain_id(1u64)
+        .into();
+    assert!(executor
+        .send_raw_transaction(&wallet, blocked)
+        .await
+        .is_err());
+    assert_eq!(mock.calls("eth_sendRawTransaction"), 0);
+}
+
+#[tokio::test]
+async fn test_watchdog_replaces_stuck_transaction() {
+    let mock = EvmMock::start(1).await.unwrap();
+    let sdk = Arc::new(
+        ApexSDK::builder()
+            .with_evm_endpoint(mock.http_url())
+            .build()
+            .await
+            .unwrap(),
+    );
+    let executor = sdk.evm().unwrap().transaction_executor();
+    let wallet = Wallet::new_random().with_chain_id(1);
+    let tx: ethers::types::transaction::eip2718::TypedTransaction = TransactionRequest::new()
+        .to(ADDRESS.parse::<Address>().unwrap())
+        .value(1)
+        .gas(21_000)
+        .gas_price(1_000_000_000u64)
+        .nonce(0)
+        .chain_id(1u64)
+        .into();
+    let hash = executor
+        .send_raw_transaction(&wallet, tx.clone())
+        .await
+        .unwrap();
+    let hash = format!("{:?}", hash);
+
+    let watchdog = TransactionWatchdog::new(sdk.clone()).with_config(WatchdogConfig {
+        pending_threshold: Duration::ZERO,
+        max_replacements: 1,
+        ..Default::default()
+    });
+    watchdog
+        .track_with_repricer(
+            Chain::Ethereum,
+            hash.clone(),
+            EvmRepricer::new(executor, wallet, tx),
+        )
+        .await;
+
+    let report = watchdog.check().await;
+    assert_eq!(report.replaced.len(), 1);
+    assert_ne!(report.replaced[0].latest_hash, hash);
+    assert_eq!(mock.calls("eth_sendRawTransaction"), 2);
+
+    // replacements exhausted: escalated once
+    assert_eq!(watchdog.check().await.escalated.len(), 1);
+    assert!(watchdog.check().await.escalated.is_empty());
+    assert_eq!(mock.calls("eth_sendRawTransaction"), 2);
+
+    mock.mine();
+    assert_eq!(watchdog.check().await.settled, vec![hash]);
+    assert!(watchdog.tracked().await.is_empty());
+}
+
+#[tokio::test]
+async fn test_pending_queue_resubmits_and_alerts() {
+    use apex_sdk::pending::PendingTxQueue;
+    use apex_sdk::watchdog::StuckTransaction;
+
+    let mock = EvmMock::start(1).await.unwrap();
+    let sdk = Arc::new(
+        ApexSDK::builder()
+            .with_evm_endpoint(mock.http_url())
+            .build()
+            .await
+            .unwrap(),
+    );
+    let wallet = Wallet::new_random().with_chain_id(1);
+    let alerts = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let queue = {
+        let alerts = alerts.clone();
+        PendingTxQueue::new(sdk.clone(), Chain::Ethereum, wallet.clone())
+            .unwrap()
+            .with_config(WatchdogConfig {
+                pending_threshold: Duration::ZERO,
+                max_replacements: 1,
+                ..Default::default()
+            })
+            .with_alert(move |stuck: &StuckTransaction| {
+                alerts.lock().unwrap().push(stuck.tx_hash.clone())
+            })
+    };
+    let to: Address = ADDRESS.parse().unwrap();
+
+    let hash = format!("{:?}", queue.send(to, U256::one(), None).await.unwrap());
+    assert_eq!(queue.pending().await, vec![hash.clone()]);
+
+    // resubmitted once with higher fees, then reported
+    assert_eq!(queue.check().await.replaced.len(), 1);
+    assert_eq!(mock.calls("eth_sendRawTransaction"), 2);
+    assert_eq!(queue.check().await.escalated.len(), 1);
+    assert_eq!(*alerts.lock().unwrap(), vec![hash.clone()]);
+
+    mock.mine();
+    assert_eq!(queue.check().await.settled, vec![hash]);
+    let metrics = queue.metrics();
+    assert_eq!(metrics.submitted.load(Ordering::Relaxed), 1);
+    assert_eq!(metrics.resubmitted.load(Ordering::Relaxed), 1);
+    assert_eq!(metrics.alerted.load(Ordering::Relaxed), 1);
+    assert_eq!(metrics.pending(), 0);
+
+    // without auto-resubmission stuck transactions are only reported
+    let queue = PendingTxQueue::new(sdk, Chain::Ethereum, wallet)
+        .unwrap()
+        .with_config(WatchdogConfig {
+            pending_threshold: Duration::ZERO,
+            ..Default::default()
+        })
+        .with_auto_resubmit(false);
+    queue.send(to, U256::one(), None).await.unwrap();
+    assert_eq!(queue.check().await.escalated.len(), 1);
+    assert_eq!(mock.calls("eth_sendRawTransaction"), 3);
+}
+
+#[tokio::test]
+async fn test_token_balance() {
+    let mock = EvmMock::start(1284).await.unwrap();
+    let adapter = EvmAdapter::connect(&mock.http_url()).await.unwrap();
+    let xcdot = apex_sdk::bridge::MOONBEAM_XCDOT;
+
+    mock.set_token_balance(xcdot, ADDRESS, 25_000_000_000);
+    assert_eq!(
+        adapter.token_balance(xcdot, ADDRESS).await.unwrap(),
+        U256::from(25_000_000_000u64)
+    );
+    // Calls to anything else return no data
+    assert!(adapter.token_balance(ADDRESS, ADDRESS).await.is_err());
+}
+
+#[tokio::test]
+async fn test_uniswap_v2_swap() {
+    use apex_sdk::swap::{SwapClient, SwapRequest, UniswapV2Venue};
+    use ethers::abi::{encode, Token};
+
+    const ROUTER: &str = "0x7a250d5630b4cf539739df2c5dacb4c659f2488d";
+    const USDC: &str = "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48";
+    const WETH: &str = "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2";
+
+    let mock = EvmMock::start(1).await.unwrap();
+    let adapter = EvmAdapter::connect(&mock.http_url()).await.unwrap();
+    let wallet = Wallet::new_random().with_chain_id(1);
+    let client = SwapClient::new().with_venue(UniswapV2Venue::new(
+        Chain::Ethereum,
+        adapter,
+        ROUTER,
+        wallet,
+    ));
+    let request = SwapRequest::new(Chain::Ethereum, USDC, WETH, 1_000_000);
+
+    // getAmountsOut -> [1_000_000, 400_000]
+    mock.set_call_result(
+        ROUTER,
+        [0xd0, 0x6c, 0xa6, 0x1f],
+        &encode(&[Token::Array(vec![
+            Token::Uint(1_000_000.into()),
+            Token::Uint(400_000.into()),
+        ])]),
+    );
+    let quotes = client.quotes(&request).await;
+    assert_eq!(quotes[0].amount_out, 400_000);
+
+    // Without an allowance nothing is sent
+    mock.set_call_result(
+        USDC,
+        [0xdd, 0x62, 0xed, 0x3e],
+        &encode(&[Token::Uint(0.into())]),
+    );
+    assert!(matches!(
+        client.swap(&request).await,
+        Err(Error::Transaction(_))
+    ));
+    assert_eq!(mock.calls("eth_sendRawTransaction"), 0);
+
+    mock.set_call_result(
+        USDC,
+        [0xdd, 0x62, 0xed, 0x3e],
+        &encode(&[Token::Uint(U256::MAX)]),
+    );
+    let result = client.swap(&request).await.unwrap();
+    assert_eq!(result.expected_amount_out, 400_000);
+    assert_eq!(result.min_amount_out, 398_000);
+    assert_eq!(mock.calls("eth_sendRawTransaction"), 1);
+}
+
+#[tokio::test]
+async fn test_sponsored_user_operation() {
+    use apex_sdk::evm::erc4337::{BundlerClient, RpcPaymaster, SmartAccount, ENTRY_POINT_V06};
+    use ethers::abi::{encode, Token};
+    use serde_json::json;
+
+    // One mock serves the chain, the bundler and the paymaster
+    let mock = EvmMock::start(1).await.unwrap();
+    mock.set_call_result(
+        ENTRY_POINT_V06,
+        [0x35, 0x56, 0x7e, 0x1a],
+        &encode(&[Token::Uint(7.into())]),
+    );
+    mock.respond(
+        "pm_sponsorUserOperation",
+        json!({
+            "paymasterAndData": "0x0101010101010101010101010101010101010101ff",
+            "preVerificationGas": "0xc350",
+            "verificationGasLimit": "0x186a0",
+            "callGasLimit": "0x7530",
+        }),
+    );
+    mock.respond(
+        "eth_sendUserOperation",
+        json!(format!("0x{}", "ab".repeat(32))),
+    );
+
+    let account = SmartAccount::new(
+        ADDRESS.parse().unwrap(),
+        Wallet::new_random(),
+        EvmAdapter::connect(&mock.http_url()).await.unwrap(),
+        BundlerClient::connect(&mock.http_url()).unwrap(),
+    )
+    .with_paymaster(RpcPaymaster::connect(&mock.http_url()).unwrap());
+
+    let hash = account
+        .send(ADDRESS.parse().unwrap(), U256::zero(), vec![])
+        .await
+        .unwrap();
+    assert_eq!(format!("{:?}", hash), format!("0x{}", "ab".repeat(32)));
+
+    // The paymaster's gas limits are used without a bundler estimate
+    assert_eq!(mock.calls("eth_estimateUserOperationGas"), 0);
+    let sent = mock
+        .requests()
+        .into_iter()
+        .find(|call| call.method == "eth_sendUserOperation")
+        .unwrap();
+    let op = &sent.params[0];
+    assert_eq!(op["nonce"], "0x7");
+    assert_eq!(op["callGasLimit"], "0x7530");
+    assert_eq!(
+        op["paymasterAndData"],
+        "0x0101010101010101010101010101010101010101ff"
+    );
+    assert_eq!(op["signature"].as_str().unwrap().len(), 2 + 65 * 2);
+}
+
+#[tokio::test]
+async fn test_governor_proposals_and_vote() {
+    use apex_sdk::governance::{Governance, GovernorSource, ProposalStatus, VoteChoice};
+    use ethers::abi::{encode, Token};
+    use ethers::utils::{id, keccak256};
+
+    const GOVERNOR: &str = "0x408ed6354d4973f66138c91495f2f2fcbd8724c3";
+
+    let mock = EvmMock::start(1).await.unwrap();
+    let topic = format!(
+        "0x{}",
+        hex::encode(keccak256(
+            "ProposalCreated(uint256,address,address[],uint256[],string[],bytes[],uint256,uint256,string)"
+        ))
+    );
+    let data = encode(&[
+        Token::Uint(42.into()),
+        Token::Address(ADDRESS.parse().unwrap()),
+        Token::Array(vec![]),
+        Token::Array(vec![]),
+        Token::Array(vec![]),
+        Token::Array(vec![]),
+        Token::Uint(10.into()),
+        Token::Uint(100.into()),
+        Token::String("Raise the quorum\nto 5%".to_string()),
+    ]);
+    mock.add_log(GOVERNOR, &[&topic], &data);
+    // Logs of other contracts are not proposals
+    mock.add_log(ADDRESS, &[&topic], &data);
+    mock.mine();
+
+    mock.set_call_result(
+        GOVERNOR,
+        id("state(uint256)"),
+        &encode(&[Token::Uint(1.into())]),
+    );
+    mock.set_call_result(
+        GOVERNOR,
+        id("proposalVotes(uint256)"),
+        &encode(&[
+            Token::Uint(5.into()),
+            Token::Uint(20.into()),
+            Token::Uint(1.into()),
+        ]),
+    );
+
+    let adapter = EvmAdapter::connect(&mock.http_url()).await.unwrap();
+    let governance = Governance::new().with_source(
+        GovernorSource::new(Chain::Ethereum, adapter, GOVERNOR)
+            .with_voter(Wallet::new_random().with_chain_id(1)),
+    );
+
+    let proposals = governance.active_proposals().await;
+    assert_eq!(proposals.len(), 1);
+    let proposal = &proposals[0];
+    assert_eq!(proposal.id, "42");
+    assert_eq!(proposal.title.as_deref(), Some("Raise the quorum"));
+    assert_eq!(proposal.status, ProposalStatus::Active);
+    assert_eq!((proposal.tally.ayes, proposal.tally.nays), (20, 5));
+    assert_eq!(proposal.voting_ends, Some(100));
+
+    governance.vote(proposal, VoteChoice::Aye).await.unwrap();
+    assert_eq!(mock.calls("eth_sendRawTransaction"), 1);
+}
+
+/// Polkadot runtime 9180 metadata, with its `Call` and `Event` types renamed
+/// to the `RuntimeCall` and `RuntimeEvent` subxt looks for
+fn polkadot_metadata() -> Vec<u8> {
+    use frame_metadata::{RuntimeMetadata, RuntimeMetadataPrefixed};
+    use parity_scale_codec::{Decode, Encode};
+
+    let raw =
+        include_bytes!("../../apex-sdk-substrate/tests/fixtures/polkadot_9180_metadata.scale");
+    let mut prefixed = RuntimeMetadataPrefixed::decode(&mut &raw[..]).unwrap();
+    let RuntimeMetadata::V14(metadata) = &mut prefixed.1 else {
+        panic!("fixture is not V14 metadata");
+    };
+    for ty in &mut metadata.types.types {
+        let segments = &mut ty.ty.path.segments;
+        if segments.len() == 2 && segments[0] == "polkadot_runtime" {
+            match segments[1].as_str() {
+                "Call" => segments[1] = "RuntimeCall".to_string(),
+                "Event" => segments[1] = "RuntimeEvent".to_string(),
+                _ => {}
+            }
+        }
+    }
+    prefixed.encode()
+}
+
+#[tokio::test]
+async fn test_substrate_address_prefix_from_runtime() {
+    use apex_sdk::substrate::{ChainConfig, SubstrateAdapter};
+
+    let mock = SubstrateMock::start().await.unwrap();
+    mock.set_metadata(polkadot_metadata());
+    let genesis = hex::decode(apex_sdk::types::genesis::POLKADOT.trim_start_matches("0x")).unwrap();
+    mock.set_genesis_hash(genesis.try_into().unwrap());
+
+    let polkadot = ApexAddress::substrate("15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5");
+    let generic = ApexAddress::substrate("5GrwvaEF5zXb26Fz9rcQpDWS55tNwr9ZsEu8CPoXG3Ac7fQW");
+
+    // Connected as Polkadot, and through the generic default config the
+    // builder uses: both take the prefix from the runtime
+    let config = ChainConfig {
+        endpoint: mock.ws_url(),
+        ..ChainConfig::polkadot()
+    };
+    let adapters = [
+        SubstrateAdapter::connect_with_config(config).await.unwrap(),
+        SubstrateAdapter::connect(&mock.ws_url()).await.unwrap(),
+    ];
+    for adapter in adapters {
+        assert!(adapter.validate_address(&polkadot));
+        assert!(!adapter.validate_address(&generic));
+    }
+}
@@ -0,0 +1,525 @@
+//! Token amounts and transaction fees
+//!
+//! Amounts are always stored in the chain's smallest unit (planck, wei, ...)
+//! together with the number of decimals needed to render them for humans.
+//! Values are `u128`, like every balance in the SDK: up to about 3.4e20
+//! whole tokens at 18 decimals.
+//!
+//! ```rust
+//! use apex_sdk_types::Amount;
+//!
+//! let amount = Amount::from_human("1.5 DOT").unwrap();
+//! assert_eq!(amount.value, 15_000_000_000);
+//!
+//! let fee: Amount = "0.0021 DOT".parse().unwrap();
+//! let total = amount.checked_add(&fee).unwrap();
+//! assert_eq!(total.to_string(), "1.5021 DOT");
+//! ```
+
+use crate::Chain;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// A token amount expressed in base units with its display precision
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Amount {
+    /// Value in the smallest unit of the token
+    pub value: u128,
+    /// Number of decimals used to render the value
+    pub decimals: u8,
+    /// Optional token symbol (e.g. "DOT", "ETH")
+    pub symbol: Option<String>,
+}
+
+impl Amount {
+    /// Create an amount with the given precision and no symbol
+    pub fn new(value: u128, decimals: u8) -> Self {
+        Self {
+            value,
+            decimals,
+            symbol: None,
+        }
+    }
+
+    /// Create an amount denominated in the native token of `chain`
+    pub fn native(value: u128, chain: &Chain) -> Self {
+        Self {
+            value,
+            decimals: chain.native_decimals(),
+            symbol: Some(chain.native_symbol().to_string()),
+        }
+    }
+
+    /// Attach a token symbol
+    pub fn with_symbol(mut self, symbol: impl Into<String>) -> Self {
+        self.symbol = Some(symbol.into());
+        self
+    }
+
+    /// Parse a human-readable amount of a native token, e.g. `"1.5 DOT"`
+    ///
+    /// The decimals are those of the built-in or registered chain whose
+    /// native token has the symbol (matched case-insensitively). For other
+    /// tokens use [`parse`](Self::parse) with their decimals.
+    pub fn from_human(input: &str) -> Result<Self, String> {
+        let (value, symbol) = split_symbol(input);
+        let symbol = symbol.ok_or_else(|| format!("Amount {} has no token symbol", input))?;
+        let chain =
+            native_chain(symbol).ok_or_else(|| format!("Unknown token symbol: {}", symbol))?;
+        let value = parse_units(value, chain.native_decimals())?;
+        Ok(Self::native(value, &chain))
+    }
+
+    /// Parse `"1.5"` or `"1.5 USDC"` with the given decimals
+    pub fn parse(input: &str, decimals: u8) -> Result<Self, String> {
+        let (value, symbol) = split_symbol(input);
+        let amount = Self::new(parse_units(value, decimals)?, decimals);
+        Ok(match symbol {
+            Some(symbol) => amount.with_symbol(symbol),
+            None => amount,
+        })
+    }
+
+    /// Whether the amount is zero
+    pub fn is_zero(&self) -> bool {
+        self.value == 0
+    }
+
+    /// Whether both amounts have the same decimals and symbol
+    pub fn same_unit(&self, other: &Amount) -> bool {
+        self.decimals == other.decimals && self.symbol == other.symbol
+    }
+
+    /// `self + other`, or `None` on overflow or if the units differ
+    pub fn checked_add(&self, other: &Amount) -> Option<Self> {
+        self.combine(other, u128::checked_add)
+    }
+
+    /// `self - other`, or `None` on underflow or if the units differ
+    pub fn checked_sub(&self, other: &Amount) -> Option<Self> {
+        self.combine(other, u128::checked_sub)
+    }
+
+    /// `self * factor`, or `None` on overflow
+    pub fn checked_mul(&self, factor: u128) -> Option<Self> {
+        self.with_value(self.value.checked_mul(factor)?)
+    }
+
+    /// `self / divisor`, rounded down, or `None` if `divisor` is zero
+    pub fn checked_div(&self, divisor: u128) -> Option<Self> {
+        self.with_value(self.value.checked_div(divisor)?)
+    }
+
+    /// The same amount with `decimals` decimals, or `None` if it does not
+    /// fit or would lose precision
+    pub fn rescale(&self, decimals: u8) -> Option<Self> {
+        let value = if decimals >= self.decimals {
+            let factor = 10u128.checked_pow((decimals - self.decimals) as u32)?;
+            self.value.checked_mul(factor)?
+        } else {
+            let factor = 10u128.checked_pow((self.decimals - decimals) as u32)?;
+            if self.value % factor != 0 {
+                return None;
+            }
+            self.value / factor
+        };
+        Some(Self {
+            value,
+            decimals,
+            symbol: self.symbol.clone(),
+        })
+    }
+
+    fn combine(&self, other: &Amount, op: fn(u128, u128) -> Option<u128>) -> Option<Self> {
+        if !self.same_unit(other) {
+            return None;
+        }
+        self.with_value(op(self.value, other.value)?)
+    }
+
+    fn with_value(&self, value: u128) -> Option<Self> {
+        Some(Self {
+            value,
+            ..self.clone()
+        })
+    }
+}
+
+impl std::str::FromStr for Amount {
+    type Err = String;
+
+    /// Parse with [`Amount::from_human`]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Amount::from_human(s)
+    }
+}
+
+/// Split `"1.5 DOT"` into the number and the symbol, if any
+fn split_symbol(input: &str) -> (&str, Option<&str>) {
+    let input = input.trim();
+    match input.split_once(char::is_whitespace) {
+        Some((value, symbol)) => (value, Some(symbol.trim())),
+        None => (input, None),
+    }
+}
+
+/// Built-in or registered chain whose native token is `symbol`
+fn native_chain(symbol: &str) -> Option<Chain> {
+    [
+        Chain::Polkadot,
+        Chain::Kusama,
+        Chain::Ethereum,
+        Chain::BinanceSmartChain,
+        Chain::Polygon,
+        Chain::Avalanche,
+        Chain::Moonbeam,
+        Chain::Astar,
+        Chain::Amoy,
+        Chain::MoonbaseAlpha,
+        Chain::Shibuya,
+        Chain::Paseo,
+    ]
+    .into_iter()
+    .chain(crate::registry::registered())
+    .find(|chain| chain.native_symbol().eq_ignore_ascii_case(symbol))
+}
+
+impl fmt::Display for Amount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&format_units(self.value, self.decimals))?;
+        if let Some(symbol) = &self.symbol {
+            write!(f, " {}", symbol)?;
+        }
+        Ok(())
+    }
+}
+
+/// Asset moved by a transfer
+///
+/// Token decimals left as `None` are read from the chain when needed.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Token {
+    /// Native token of the chain (ETH, DOT, ...)
+    #[default]
+    Native,
+    /// ERC-20 token on an EVM chain
+    Erc20 {
+        /// Contract address
+        address: String,
+        /// Token decimals
+        decimals: Option<u8>,
+    },
+    /// Asset of the `Assets` pallet on a Substrate chain
+    Asset {
+        /// Asset ID
+        id: u32,
+        /// Asset decimals
+        decimals: Option<u8>,
+    },
+}
+
+impl Token {
+    /// ERC-20 token at contract `address`
+    pub fn erc20(address: impl Into<String>) -> Self {
+        Token::Erc20 {
+            address: address.into(),
+            decimals: None,
+        }
+    }
+
+    /// Asset `id` of the `Assets` pallet
+    pub fn asset(id: u32) -> Self {
+        Token::Asset { id, decimals: None }
+    }
+
+    /// Set the token decimals instead of reading them from the chain
+    ///
+    /// Has no effect on [`Token::Native`], whose decimals are the chain's.
+    pub fn with_decimals(mut self, value: u8) -> Self {
+        match &mut self {
+            Token::Native => {}
+            Token::Erc20 { decimals, .. } | Token::Asset { decimals, .. } => {
+                *decimals = Some(value)
+            }
+        }
+        self
+    }
+
+    /// Whether this is the chain's native token
+    pub fn is_native(&self) -> bool {
+        matches!(self, Token::Native)
+    }
+
+    /// Decimals of the token on `chain`, if known without querying it
+    pub fn decimals(&self, chain: &Chain) -> Option<u8> {
+        match self {
+            Token::Native => Some(chain.native_decimals()),
+            Token::Erc20 { decimals, .. } | Token::Asset { decimals, .. } => *decimals,
+        }
+    }
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Native => f.write_str("native"),
+            Token::Erc20 { address, .. } => write!(f, "ERC-20 {}", address),
+            Token::Asset { id, .. } => write!(f, "asset {}", id),
+        }
+    }
+}
+
+/// Fee paid for a transaction, in the smallest unit of the fee token
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Fee {
+    /// Total fee paid
+    pub amount: u128,
+    /// Gas consumed (EVM only)
+    pub gas_used: Option<u64>,
+    /// Effective price paid per unit of gas (EVM only)
+    pub gas_price: Option<u128>,
+}
+
+impl Fee {
+    /// Create a fee from a flat amount (e.g. a Substrate partial fee)
+    pub fn new(amount: u128) -> Self {
+        Self {
+            amount,
+            gas_used: None,
+            gas_price: None,
+        }
+    }
+
+    /// Create a fee from gas consumption and an effective gas price
+    pub fn from_gas(gas_used: u64, gas_price: u128) -> Self {
+        Self {
+            amount: (gas_used as u128).saturating_mul(gas_price),
+            gas_used: Some(gas_used),
+            gas_price: Some(gas_price),
+        }
+    }
+
+    /// The fee as an [`Amount`] of the native token of `chain`
+    pub fn to_amount(&self, chain: &Chain) -> Amount {
+        Amount::native(self.amount, chain)
+    }
+
+    /// Render the fee in the native token of `chain`, e.g. "0.0021 DOT"
+    pub fn format_with(&self, chain: &Chain) -> String {
+        self.to_amount(chain).to_string()
+    }
+}
+
+impl fmt::Display for Fee {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.gas_used, self.gas_price) {
+            (Some(gas), Some(price)) => {
+                write!(f, "{} ({} gas @ {})", self.amount, gas, price)
+            }
+            _ => write!(f, "{}", self.amount),
+        }
+    }
+}
+
+/// Render a base-unit value with the given number of decimals,
+/// trimming trailing zeros from the fractional part.
+pub fn format_units(value: u128, decimals: u8) -> String {
+    if decimals == 0 {
+        return value.to_string();
+    }
+
+    let digits = value.to_string();
+    let decimals = decimals as usize;
+    let (whole, fraction) = if digits.len() > decimals {
+        let split = digits.len() - decimals;
+        (digits[..split].to_string(), digits[split..].to_string())
+    } else {
+        (
+            "0".to_string(),
+            format!("{:0>width$}", digits, width = decimals),
+        )
+    };
+
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.is_empty() {
+        whole
+    } else {
+        format!("{}.{}", whole, fraction)
+    }
+}
+
+/// Parse a decimal string such as `"1.5"` into base units with the given
+/// number of decimals; the inverse of [`format_units`].
+///
+/// Rejects signs, exponents, more fractional digits than `decimals` and
+/// values that do not fit in a `u128`, rather than rounding or saturating.
+pub fn parse_units(value: &str, decimals: u8) -> Result<u128, String> {
+    let trimmed = value.trim();
+    let (whole, fraction) = match trimmed.split_once('.') {
+        Some((whole, fraction)) if !fraction.is_empty() => (whole, fraction),
+        Some(_) => return Err(format!("Invalid amount: {}", value)),
+        None => (trimmed, ""),
+    };
+    let is_digits = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+    if whole.is_empty() || !is_digits(whole) || !is_digits(fraction) {
+        return Err(format!("Invalid amount: {}", value));
+    }
+    if fraction.len() > decimals as usize {
+        return Err(format!(
+            "Amount {} has more than {} decimals",
+            value, decimals
+        ));
+    }
+
+    let digits = format!("{}{:0<width$}", whole, fraction, width = decimals as usize);
+    let digits = digits.trim_start_matches('0');
+    if digits.is_empty() {
+        return Ok(0);
+    }
+    digits
+        .parse()
+        .map_err(|_| format!("Amount {} does not fit in 128 bits", value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_format_units() {
+        assert_eq!(format_units(0, 18), "0");
+        assert_eq!(format_units(1, 0), "1");
+        assert_eq!(format_units(21_000_000, 10), "0.0021");
+        assert_eq!(format_units(15_000_000_000, 10), "1.5");
+        assert_eq!(format_units(1_000_000_000_000_000_000, 18), "1");
+        assert_eq!(
+            format_units(u128::MAX, 38),
+            "3.40282366920938463463374607431768211455"
+        );
+    }
+
+    #[test]
+    fn test_amount_display() {
+        let amount = Amount::native(21_000_000, &Chain::Polkadot);
+        assert_eq!(amount.to_string(), "0.0021 DOT");
+
+        let amount = Amount::new(1_500, 3);
+        assert_eq!(amount.to_string(), "1.5");
+
+        let amount = Amount::new(1_500, 3).with_symbol("USDC");
+        assert_eq!(amount.to_string(), "1.5 USDC");
+    }
+
+    #[test]
+    fn test_amount_from_human() {
+        let amount = Amount::from_human("1.5 DOT").unwrap();
+        assert_eq!(amount, Amount::native(15_000_000_000, &Chain::Polkadot));
+        assert_eq!(amount.to_string().parse::<Amount>().unwrap(), amount);
+
+        let amount: Amount = " 0.25  glmr ".parse().unwrap();
+        assert_eq!(amount.value, 250_000_000_000_000_000);
+        assert_eq!(amount.symbol.as_deref(), Some("GLMR"));
+
+        assert!(Amount::from_human("1.5").is_err());
+        assert!(Amount::from_human("1.5 XYZ").is_err());
+        assert!(Amount::from_human("1.00000000001 DOT").is_err());
+
+        let usdc = Amount::parse("12.34 USDC", 6).unwrap();
+        assert_eq!(usdc, Amount::new(12_340_000, 6).with_symbol("USDC"));
+        assert_eq!(Amount::parse("7", 0).unwrap(), Amount::new(7, 0));
+    }
+
+    #[test]
+    fn test_amount_checked_math() {
+        let dot = |value| Amount::native(value, &Chain::Polkadot);
+        assert_eq!(dot(2).checked_add(&dot(3)), Some(dot(5)));
+        assert_eq!(dot(3).checked_sub(&dot(2)), Some(dot(1)));
+        assert_eq!(dot(2).checked_sub(&dot(3)), None);
+        assert_eq!(dot(u128::MAX).checked_add(&dot(1)), None);
+        assert_eq!(dot(2).checked_add(&Amount::native(3, &Chain::Kusama)), None);
+        assert_eq!(dot(7).checked_mul(3), Some(dot(21)));
+        assert_eq!(dot(u128::MAX).checked_mul(2), None);
+        assert_eq!(dot(7).checked_div(2), Some(dot(3)));
+        assert_eq!(dot(7).checked_div(0), None);
+        assert!(dot(0).is_zero());
+
+        let amount = Amount::new(1_500, 3);
+        assert_eq!(amount.rescale(6), Some(Amount::new(1_500_000, 6)));
+        assert_eq!(amount.rescale(1), Some(Amount::new(15, 1)));
+        assert_eq!(amount.rescale(0), None);
+        assert_eq!(Amount::new(u128::MAX, 0).rescale(1), None);
+
+        let fee = Fee::new(21_000_000).to_amount(&Chain::Polkadot);
+        assert_eq!(fee, dot(21_000_000));
+    }
+
+    #[test]
+    fn test_token_decimals() {
+        assert_eq!(Token::Native.decimals(&Chain::Polkadot), Some(10));
+        assert_eq!(Token::asset(1984).decimals(&Chain::Polkadot), None);
+        assert_eq!(
+            Token::erc20("0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48")
+                .with_decimals(6)
+                .decimals(&Chain::Ethereum),
+            Some(6)
+        );
+        assert_eq!(Token::Native.with_decimals(6), Token::Native);
+
+        let json = serde_json::to_string(&Token::asset(1984).with_decimals(6)).unwrap();
+        assert_eq!(json, r#"{"asset":{"id":1984,"decimals":6}}"#);
+        assert_eq!(
+            serde_json::from_str::<Token>(&json).unwrap(),
+            Token::asset(1984).with_decimals(6)
+        );
+    }
+
+    #[test]
+    fn test_fee_display() {
+        let fee = Fee::new(21_000_000);
+        assert_eq!(fee.to_string(), "21000000");
+        assert_eq!(fee.format_with(&Chain::Polkadot), "0.0021 DOT");
+
+        let fee = Fee::from_gas(21_000, 1_000_000_000);
+        assert_eq!(fee.amount, 21_000_000_000_000);
+        assert_eq!(fee.to_string(), "21000000000000 (21000 gas @ 1000000000)");
+        assert_eq!(fee.format_with(&Chain::Ethereum), "0.000021 ETH");
+    }
+
+    #[test]
+    fn test_parse_units() {
+        assert_eq!(parse_units("0", 18), Ok(0));
+        assert_eq!(parse_units("0.0021", 10), Ok(21_000_000));
+        assert_eq!(parse_units(" 1.5 ", 10), Ok(15_000_000_000));
+        assert_eq!(parse_units("007", 0), Ok(7));
+        assert_eq!(
+            parse_units("3.40282366920938463463374607431768211455", 38),
+            Ok(u128::MAX)
+        );
+
+        assert!(parse_units("3.40282366920938463463374607431768211456", 38).is_err());
+        assert!(parse_units("1.5", 0).is_err());
+        assert!(parse_units("0.00000000001", 10).is_err());
+        for invalid in ["", ".5", "1.", "-1", "+1", "1e18", "1,5", "1.2.3", "0x10"] {
+            assert!(parse_units(invalid, 18).is_err(), "{:?} parsed", invalid);
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn prop_units_roundtrip(value in any::<u128>(), decimals in 0u8..=40) {
+            prop_assert_eq!(parse_units(&format_units(value, decimals), decimals), Ok(value));
+        }
+
+        #[test]
+        fn prop_parse_units_is_canonical(
+            value in "[0-9]{0,45}(\\.[0-9]{0,45})?|\\PC*",
+            decimals in 0u8..=40,
+        ) {
+            if let Ok(parsed) = parse_units(&value, decimals) {
+                prop_assert_eq!(parse_units(&format_units(parsed, decimals), decimals), Ok(parsed));
+            }
+        }
+    }
+}
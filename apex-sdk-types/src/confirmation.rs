@@ -0,0 +1,163 @@
+//! Confirmation policies
+//!
+//! A [`ConfirmationPolicy`] says when a transaction included in a block
+//! counts as confirmed: after a number of blocks built on top of it, once
+//! its block is finalized, or once its block is old enough. Each chain has a
+//! default suited to its consensus, see [`ConfirmationPolicy::for_chain`].
+//!
+//! ```rust
+//! use apex_sdk_types::{Chain, ConfirmationPolicy};
+//! use std::time::Duration;
+//!
+//! assert_eq!(
+//!     ConfirmationPolicy::for_chain(&Chain::Polkadot),
+//!     ConfirmationPolicy::Finalized
+//! );
+//!
+//! let policy: ConfirmationPolicy = "12".parse().unwrap();
+//! assert!(policy.is_met(12, false, None));
+//! assert!(!policy.is_met(3, false, None));
+//!
+//! let policy = ConfirmationPolicy::Elapsed(Duration::from_secs(60));
+//! assert!(policy.is_met(0, false, Some(Duration::from_secs(90))));
+//! ```
+
+use crate::{Chain, ChainType};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::time::Duration;
+
+/// When a transaction included in a block counts as confirmed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ConfirmationPolicy {
+    /// At least this many blocks built on top of the including block;
+    /// `Blocks(0)` accepts any included transaction
+    Blocks(u32),
+    /// Including block finalized by the chain's consensus
+    Finalized,
+    /// Including block produced at least this long ago
+    Elapsed(Duration),
+}
+
+impl ConfirmationPolicy {
+    /// Default policy for `chain`
+    ///
+    /// Chains with deterministic finality (Polkadot, Kusama and their
+    /// parachains) wait for finalization; probabilistic EVM chains wait for
+    /// a number of blocks matching their reorg depth, 12 for registered ones.
+    pub fn for_chain(chain: &Chain) -> Self {
+        match chain {
+            Chain::Polkadot
+            | Chain::Kusama
+            | Chain::Moonbeam
+            | Chain::Astar
+            | Chain::MoonbaseAlpha
+            | Chain::Shibuya
+            | Chain::Paseo => Self::Finalized,
+            Chain::Ethereum | Chain::Sepolia | Chain::Holesky => Self::Blocks(12),
+            Chain::BinanceSmartChain => Self::Blocks(15),
+            Chain::Polygon | Chain::Amoy => Self::Blocks(64),
+            Chain::Avalanche => Self::Blocks(1),
+            Chain::Custom(spec) => match spec.chain_type {
+                ChainType::Evm => Self::Blocks(12),
+                ChainType::Substrate | ChainType::Hybrid => Self::Finalized,
+            },
+        }
+    }
+
+    /// Whether a transaction with `confirmations` blocks on top of it, in a
+    /// block that is `finalized` and `age` old (if known), is confirmed
+    pub fn is_met(&self, confirmations: u32, finalized: bool, age: Option<Duration>) -> bool {
+        match self {
+            Self::Blocks(required) => confirmations >= *required,
+            Self::Finalized => finalized,
+            Self::Elapsed(required) => age.is_some_and(|age| age >= *required),
+        }
+    }
+}
+
+impl Default for ConfirmationPolicy {
+    /// Any included transaction
+    fn default() -> Self {
+        Self::Blocks(0)
+    }
+}
+
+impl fmt::Display for ConfirmationPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Blocks(blocks) => write!(
+                f,
+                "{} confirmation{}",
+                blocks,
+                if *blocks == 1 { "" } else { "s" }
+            ),
+            Self::Finalized => f.write_str("finalized"),
+            Self::Elapsed(age) => write!(f, "{}s elapsed", age.as_secs()),
+        }
+    }
+}
+
+impl std::str::FromStr for ConfirmationPolicy {
+    type Err = String;
+
+    /// Parse `finalized`, a block count such as `12`, or an age in seconds
+    /// such as `90s`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.eq_ignore_ascii_case("finalized") {
+            return Ok(Self::Finalized);
+        }
+        if let Some(secs) = s.strip_suffix('s') {
+            return secs
+                .parse()
+                .map(|secs| Self::Elapsed(Duration::from_secs(secs)))
+                .map_err(|_| format!("Invalid confirmation policy: {}", s));
+        }
+        s.parse()
+            .map(Self::Blocks)
+            .map_err(|_| format!("Invalid confirmation policy: {}", s))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_met() {
+        assert!(ConfirmationPolicy::default().is_met(0, false, None));
+        assert!(!ConfirmationPolicy::Blocks(12).is_met(11, true, None));
+        assert!(ConfirmationPolicy::Finalized.is_met(0, true, None));
+        assert!(!ConfirmationPolicy::Finalized.is_met(100, false, None));
+
+        let elapsed = ConfirmationPolicy::Elapsed(Duration::from_secs(60));
+        assert!(!elapsed.is_met(100, true, None));
+        assert!(!elapsed.is_met(0, false, Some(Duration::from_secs(59))));
+        assert!(elapsed.is_met(0, false, Some(Duration::from_secs(60))));
+    }
+
+    #[test]
+    fn test_parse_and_display() {
+        assert_eq!(ConfirmationPolicy::Blocks(1).to_string(), "1 confirmation");
+        assert_eq!(
+            ConfirmationPolicy::Blocks(12).to_string(),
+            "12 confirmations"
+        );
+        assert_eq!(ConfirmationPolicy::Finalized.to_string(), "finalized");
+        assert_eq!(
+            ConfirmationPolicy::Elapsed(Duration::from_secs(90)).to_string(),
+            "90s elapsed"
+        );
+        assert_eq!(
+            "Finalized".parse::<ConfirmationPolicy>(),
+            Ok(ConfirmationPolicy::Finalized)
+        );
+        assert_eq!("12".parse(), Ok(ConfirmationPolicy::Blocks(12)));
+        assert_eq!(
+            "90s".parse(),
+            Ok(ConfirmationPolicy::Elapsed(Duration::from_secs(90)))
+        );
+        assert!("soon".parse::<ConfirmationPolicy>().is_err());
+    }
+}
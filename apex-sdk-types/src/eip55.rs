@@ -0,0 +1,93 @@
+//! EIP-55 checksummed EVM addresses
+//!
+//! Mixed-case hex carries a checksum: a letter is uppercase when the matching
+//! nibble of the keccak-256 hash of the lowercase address is 8 or more.
+//! All-lowercase and all-uppercase addresses carry no checksum and are
+//! accepted as is.
+//!
+//! ```rust
+//! use apex_sdk_types::eip55;
+//!
+//! let address = "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed";
+//! assert!(eip55::is_valid(address));
+//! assert!(!eip55::is_valid("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD"));
+//!
+//! let bytes = eip55::parse(&address.to_lowercase()).unwrap();
+//! assert_eq!(eip55::to_checksum(&bytes), address);
+//! ```
+
+use sha3::{Digest, Keccak256};
+
+/// Checksummed, 0x-prefixed form of a 20-byte address
+pub fn to_checksum(address: &[u8; 20]) -> String {
+    let lower = hex::encode(address);
+    let hash = Keccak256::digest(lower.as_bytes());
+
+    let mut checksummed = String::with_capacity(42);
+    checksummed.push_str("0x");
+    for (i, c) in lower.chars().enumerate() {
+        let nibble = (hash[i / 2] >> (4 * (1 - i % 2))) & 0x0f;
+        checksummed.push(if nibble >= 8 {
+            c.to_ascii_uppercase()
+        } else {
+            c
+        });
+    }
+    checksummed
+}
+
+/// Decode a 0x-prefixed hex address, verifying its checksum if it is mixed
+/// case
+pub fn parse(address: &str) -> Result<[u8; 20], String> {
+    let hex_str = address
+        .strip_prefix("0x")
+        .ok_or_else(|| format!("Missing 0x prefix: {}", address))?;
+    let bytes: [u8; 20] = hex::decode(hex_str)
+        .map_err(|e| format!("Invalid hex address {}: {}", address, e))?
+        .try_into()
+        .map_err(|_| format!("Address must be 20 bytes: {}", address))?;
+
+    let has_lower = hex_str.chars().any(|c| c.is_ascii_lowercase());
+    let has_upper = hex_str.chars().any(|c| c.is_ascii_uppercase());
+    if has_lower && has_upper && to_checksum(&bytes)[2..] != *hex_str {
+        return Err(format!("Invalid EIP-55 checksum: {}", address));
+    }
+    Ok(bytes)
+}
+
+/// Whether `address` is a 20-byte hex address with a valid (or no) checksum
+pub fn is_valid(address: &str) -> bool {
+    parse(address).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Test vectors from EIP-55
+    const CHECKSUMMED: [&str; 4] = [
+        "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+        "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+        "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+        "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+    ];
+
+    #[test]
+    fn test_checksum_vectors() {
+        for address in CHECKSUMMED {
+            let bytes = parse(address).unwrap();
+            assert_eq!(to_checksum(&bytes), address);
+            assert!(is_valid(&address.to_lowercase()));
+            assert!(is_valid(&format!("0x{}", address[2..].to_uppercase())));
+        }
+    }
+
+    #[test]
+    fn test_rejects_bad_checksum_and_format() {
+        // One letter's case flipped
+        assert!(!is_valid("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD"));
+        assert!(!is_valid("5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"));
+        assert!(!is_valid("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeA"));
+        assert!(!is_valid("0xzzAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"));
+    }
+}
@@ -0,0 +1,391 @@
+//! Event filters
+//!
+//! An [`EventFilter`] combines conditions on the event name, emitter, block
+//! range, EVM topics and log data, and Substrate pallet and variant. All
+//! conditions that are set must hold; nested filters add AND/OR composition.
+//!
+//! ```rust
+//! use apex_sdk_types::{Address, EventFilter};
+//!
+//! const TRANSFER: &str = "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef";
+//! let token = Address::evm("0xdAC17F958D2ee523a2206206994597C13D831ec7");
+//!
+//! // USDT transfers of more than 1,000 USDT to either of two wallets
+//! let recipients = [
+//!     "0x742D35Cc6634C0532925A3B844bC9e7595f0bEB7",
+//!     "0x0000000000000000000000000000000000000001",
+//! ];
+//! let filter = EventFilter::new()
+//!     .address(token)
+//!     .topic(0, TRANSFER)
+//!     .topic_any(2, recipients.iter().filter_map(|a| Address::evm(*a).to_topic()))
+//!     .data_word_at_least(0, 1_000_000_000);
+//! ```
+
+use crate::{Address, Event, TransactionEvent};
+use serde::{Deserialize, Serialize};
+
+/// Criteria selecting events for a subscription
+///
+/// Unset conditions match anything. Conditions specific to one kind of
+/// chain (topics and data words for EVM logs, pallets and variants for
+/// Substrate events) never match events from the other kind.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EventFilter {
+    /// Event names to match (see [`Event::name`]); `None` matches any
+    pub event_names: Option<Vec<String>>,
+    /// Emitting contracts or accounts to match (see [`Event::origin`]); `None` matches any
+    pub addresses: Option<Vec<Address>>,
+    /// First block to match
+    pub from_block: Option<u64>,
+    /// Last block to match
+    pub to_block: Option<u64>,
+    /// EVM log topics by position, each `None` or a list of alternatives
+    #[serde(default)]
+    pub topics: Option<Vec<Option<Vec<String>>>>,
+    /// Conditions on 32-byte words of EVM log data
+    #[serde(default)]
+    pub data_words: Vec<DataWord>,
+    /// Substrate pallets to match (e.g. "Balances")
+    #[serde(default)]
+    pub pallets: Option<Vec<String>>,
+    /// Substrate event variants to match (e.g. "Transfer")
+    #[serde(default)]
+    pub variants: Option<Vec<String>>,
+    /// Filters that must all match as well
+    #[serde(default)]
+    pub all_of: Vec<EventFilter>,
+    /// Filters of which at least one must match as well, if any are given
+    #[serde(default)]
+    pub any_of: Vec<EventFilter>,
+}
+
+/// Bounds on one 32-byte word of EVM log data, read as a big-endian integer
+///
+/// Unindexed `uint` parameters, such as an ERC-20 transfer amount, each
+/// occupy one word.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DataWord {
+    /// Position of the word in the data
+    pub index: usize,
+    /// Smallest matching value
+    pub min: Option<u128>,
+    /// Largest matching value
+    pub max: Option<u128>,
+}
+
+impl EventFilter {
+    /// Create a filter matching every event
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Match any of `filters`
+    pub fn any(filters: impl IntoIterator<Item = EventFilter>) -> Self {
+        Self {
+            any_of: filters.into_iter().collect(),
+            ..Default::default()
+        }
+    }
+
+    /// Also match event `name` (see [`Event::name`])
+    pub fn event(mut self, name: impl Into<String>) -> Self {
+        self.event_names
+            .get_or_insert_with(Vec::new)
+            .push(name.into());
+        self
+    }
+
+    /// Also match events emitted by `address`
+    pub fn address(mut self, address: Address) -> Self {
+        self.addresses.get_or_insert_with(Vec::new).push(address);
+        self
+    }
+
+    /// Match blocks from `block` on
+    pub fn from_block(mut self, block: u64) -> Self {
+        self.from_block = Some(block);
+        self
+    }
+
+    /// Match blocks up to and including `block`
+    pub fn to_block(mut self, block: u64) -> Self {
+        self.to_block = Some(block);
+        self
+    }
+
+    /// Require EVM topic `position` to be `topic`
+    pub fn topic(self, position: usize, topic: impl Into<String>) -> Self {
+        self.topic_any(position, [topic.into()])
+    }
+
+    /// Require EVM topic `position` to be any of `topics`
+    pub fn topic_any(
+        mut self,
+        position: usize,
+        topics: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        let positions = self.topics.get_or_insert_with(Vec::new);
+        if positions.len() <= position {
+            positions.resize(position + 1, None);
+        }
+        positions[position] = Some(topics.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Require EVM data word `index` to be at least `min`
+    pub fn data_word_at_least(mut self, index: usize, min: u128) -> Self {
+        self.data_words.push(DataWord {
+            index,
+            min: Some(min),
+            max: None,
+        });
+        self
+    }
+
+    /// Require EVM data word `index` to be at most `max`
+    pub fn data_word_at_most(mut self, index: usize, max: u128) -> Self {
+        self.data_words.push(DataWord {
+            index,
+            min: None,
+            max: Some(max),
+        });
+        self
+    }
+
+    /// Also match Substrate events from `pallet`
+    pub fn pallet(mut self, pallet: impl Into<String>) -> Self {
+        self.pallets
+            .get_or_insert_with(Vec::new)
+            .push(pallet.into());
+        self
+    }
+
+    /// Also match Substrate event `variant`
+    pub fn variant(mut self, variant: impl Into<String>) -> Self {
+        self.variants
+            .get_or_insert_with(Vec::new)
+            .push(variant.into());
+        self
+    }
+
+    /// Require `other` to match as well
+    pub fn and(mut self, other: EventFilter) -> Self {
+        self.all_of.push(other);
+        self
+    }
+
+    /// Match if either this filter or `other` matches
+    pub fn or(self, other: EventFilter) -> Self {
+        Self::any([self, other])
+    }
+
+    /// Whether `event` satisfies every condition of the filter
+    ///
+    /// Names, pallets, variants and topics compare case-insensitively;
+    /// addresses compare as accounts (see [`Address::is_same_account`]).
+    /// Events without a known origin never match an address condition.
+    pub fn matches(&self, event: &Event) -> bool {
+        if let Some(names) = &self.event_names {
+            if !contains_ignore_case(names, &event.name()) {
+                return false;
+            }
+        }
+        if let Some(addresses) = &self.addresses {
+            let Some(origin) = &event.origin else {
+                return false;
+            };
+            if !addresses.iter().any(|a| a.is_same_account(origin)) {
+                return false;
+            }
+        }
+        if matches!(self.from_block, Some(from) if event.block_number < from) {
+            return false;
+        }
+        if matches!(self.to_block, Some(to) if event.block_number > to) {
+            return false;
+        }
+        if !self.matches_chain_specific(&event.event) {
+            return false;
+        }
+        self.all_of.iter().all(|filter| filter.matches(event))
+            && (self.any_of.is_empty() || self.any_of.iter().any(|filter| filter.matches(event)))
+    }
+
+    fn matches_chain_specific(&self, event: &TransactionEvent) -> bool {
+        match event {
+            TransactionEvent::EvmLog { topics, data, .. } => {
+                if self.pallets.is_some() || self.variants.is_some() {
+                    return false;
+                }
+                let topics_match = self.topics.iter().flatten().enumerate().all(
+                    |(position, allowed)| match allowed {
+                        Some(allowed) => topics
+                            .get(position)
+                            .is_some_and(|topic| contains_ignore_case(allowed, topic)),
+                        None => true,
+                    },
+                );
+                topics_match && self.data_words.iter().all(|word| word.matches(data))
+            }
+            TransactionEvent::Substrate {
+                pallet, variant, ..
+            } => {
+                if self.topics.is_some() || !self.data_words.is_empty() {
+                    return false;
+                }
+                self.pallets
+                    .as_ref()
+                    .is_none_or(|pallets| contains_ignore_case(pallets, pallet))
+                    && self
+                        .variants
+                        .as_ref()
+                        .is_none_or(|variants| contains_ignore_case(variants, variant))
+            }
+        }
+    }
+}
+
+impl DataWord {
+    /// Whether the word in the 0x-prefixed hex `data` is within bounds
+    ///
+    /// Missing or malformed words never match.
+    pub fn matches(&self, data: &str) -> bool {
+        let data = data.strip_prefix("0x").unwrap_or(data);
+        let start = self.index * 64;
+        let Some(word) = data.get(start..start + 64) else {
+            return false;
+        };
+        let Ok(bytes) = hex::decode(word) else {
+            return false;
+        };
+        // Values that do not fit in a u128 exceed any bound
+        let (high, low) = bytes.split_at(16);
+        let value = if high.iter().any(|b| *b != 0) {
+            None
+        } else {
+            Some(u128::from_be_bytes(low.try_into().unwrap_or_default()))
+        };
+
+        let above_min = match (self.min, value) {
+            (Some(min), Some(value)) => value >= min,
+            _ => true,
+        };
+        let below_max = match (self.max, value) {
+            (Some(max), Some(value)) => value <= max,
+            (Some(_), None) => false,
+            (None, _) => true,
+        };
+        above_min && below_max
+    }
+}
+
+fn contains_ignore_case(values: &[String], value: &str) -> bool {
+    values.iter().any(|v| v.eq_ignore_ascii_case(value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ChainType;
+
+    const TRANSFER: &str = "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef";
+    const ALICE: &str = "0x742D35Cc6634C0532925A3B844bC9e7595f0bEB7";
+    const BOB: &str = "0x0000000000000000000000000000000000000001";
+
+    fn transfer_log(to: &str, amount: u128) -> Event {
+        Event {
+            chain_type: ChainType::Evm,
+            block_number: 1,
+            block_hash: format!("0x{:064x}", 1),
+            tx_hash: None,
+            index: 0,
+            origin: Some(Address::evm(ALICE)),
+            event: TransactionEvent::EvmLog {
+                address: ALICE.to_lowercase(),
+                topics: vec![
+                    TRANSFER.to_string(),
+                    Address::evm(BOB).to_topic().unwrap(),
+                    Address::evm(to).to_topic().unwrap(),
+                ],
+                data: format!("0x{:064x}", amount),
+                log_index: Some(0),
+            },
+        }
+    }
+
+    fn substrate_event(pallet: &str, variant: &str) -> Event {
+        Event {
+            chain_type: ChainType::Substrate,
+            block_number: 1,
+            block_hash: format!("0x{:064x}", 1),
+            tx_hash: None,
+            index: 0,
+            origin: None,
+            event: TransactionEvent::Substrate {
+                pallet: pallet.to_string(),
+                variant: variant.to_string(),
+                fields: String::new(),
+            },
+        }
+    }
+
+    #[test]
+    fn test_topic_and_data_conditions() {
+        let filter = EventFilter::new()
+            .topic(0, TRANSFER)
+            .topic_any(
+                2,
+                [ALICE, BOB]
+                    .iter()
+                    .filter_map(|a| Address::evm(*a).to_topic()),
+            )
+            .data_word_at_least(0, 1_000);
+
+        assert!(filter.matches(&transfer_log(ALICE, 1_000)));
+        assert!(filter.matches(&transfer_log(BOB, u128::MAX)));
+        assert!(!filter.matches(&transfer_log(ALICE, 999)));
+        assert!(!filter.matches(&transfer_log(
+            "0x00000000000000000000000000000000000000ff",
+            5_000
+        )));
+        assert!(!filter.matches(&substrate_event("Balances", "Transfer")));
+
+        assert!(!EventFilter::new()
+            .data_word_at_most(0, 10)
+            .matches(&transfer_log(ALICE, 11)));
+        assert!(!EventFilter::new()
+            .data_word_at_least(1, 0)
+            .matches(&transfer_log(ALICE, 11)));
+    }
+
+    #[test]
+    fn test_pallet_variant_and_composition() {
+        let transfers = EventFilter::new().pallet("balances").variant("Transfer");
+        assert!(transfers.matches(&substrate_event("Balances", "Transfer")));
+        assert!(!transfers.matches(&substrate_event("Balances", "Deposit")));
+        assert!(!transfers.matches(&transfer_log(ALICE, 1)));
+
+        let either = transfers.clone().or(EventFilter::new()
+            .pallet("System")
+            .variant("ExtrinsicFailed"));
+        assert!(either.matches(&substrate_event("System", "ExtrinsicFailed")));
+        assert!(either.matches(&substrate_event("Balances", "Transfer")));
+        assert!(!either.matches(&substrate_event("System", "ExtrinsicSuccess")));
+
+        let both = EventFilter::new()
+            .from_block(1)
+            .and(transfers)
+            .and(EventFilter::new().to_block(0));
+        assert!(!both.matches(&substrate_event("Balances", "Transfer")));
+    }
+
+    #[test]
+    fn test_filter_deserializes_without_new_fields() {
+        let filter: EventFilter = serde_json::from_str(
+            r#"{"event_names":null,"addresses":null,"from_block":5,"to_block":null}"#,
+        )
+        .unwrap();
+        assert_eq!(filter, EventFilter::new().from_block(5));
+    }
+}
@@ -9,9 +9,22 @@
 //!
 //! - **Chain**: Enumeration of supported blockchain networks
 //! - **ChainType**: Classification of chains (Substrate, EVM, Hybrid)
-//! - **Address**: Generic address type supporting multiple formats
+//! - **Address**: Generic address type supporting multiple formats, parsed
+//!   with format detection by `FromStr`
 //! - **TransactionStatus**: Unified transaction status representation
 //! - **CrossChainTransaction**: Cross-chain transaction information
+//! - **TransactionEvent**: Substrate events and EVM logs emitted by a transaction
+//! - **Receipt**: Block, outcome, fee and events of an included transaction
+//! - **Event** / **EventFilter**: Events observed on chain and subscription criteria
+//! - **BlockInfo** / **Finality**: Block headers observed on chain and how final they are
+//! - **ConfirmationPolicy**: When an included transaction counts as confirmed
+//! - **Amount** / **Fee**: Token amounts and fees with decimal-aware formatting and parsing
+//! - **Token**: Native token, ERC-20 contract or `Assets` pallet asset moved by a transfer
+//! - **registry** / **ChainSpec**: Chains defined at runtime from descriptor files
+//! - **genesis**: Genesis hashes identifying built-in Substrate and hybrid chains
+//! - **ss58**: SS58 address encoding, decoding and re-prefixing without `sp-core`
+//! - **eip55**: EIP-55 checksummed EVM addresses
+//! - **mapping** / **AccountMapping**: EVM and Substrate accounts on hybrid chains
 //!
 //! ## Example
 //!
@@ -19,7 +32,7 @@
 //! use apex_sdk_types::{Chain, ChainType, Address};
 //!
 //! // Create addresses for different chains
-//! let eth_addr = Address::evm("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb7");
+//! let eth_addr = Address::evm("0x742D35Cc6634C0532925A3B844bC9e7595f0bEB7");
 //! let dot_addr = Address::substrate("15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5");
 //!
 //! // Check chain types
@@ -29,6 +42,43 @@
 //! ```
 
 use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::Arc;
+
+pub mod amount;
+pub mod confirmation;
+pub mod eip55;
+pub mod filter;
+pub mod mapping;
+pub mod receipt;
+pub mod registry;
+pub mod ss58;
+
+pub use amount::{format_units, parse_units, Amount, Fee, Token};
+pub use confirmation::ConfirmationPolicy;
+pub use filter::{DataWord, EventFilter};
+pub use mapping::AccountMapping;
+pub use receipt::Receipt;
+pub use registry::{BridgeRoute, ChainDescriptor, ChainSpec};
+
+/// Genesis block hashes of built-in chains
+pub mod genesis {
+    /// Polkadot relay chain
+    pub const POLKADOT: &str = "0x91b171bb158e2d3848fa23a9f1c25182fb8e20313b2c1eb49219da7a70ce90c3";
+    /// Kusama relay chain
+    pub const KUSAMA: &str = "0xb0a8d493285c2df73290dfb7e61f870f17b41801197a149ca93654499ea3dafe";
+    /// Moonbeam parachain
+    pub const MOONBEAM: &str = "0xfe58ea77779b7abda7da4ec526d14db9b1e9cd40a217c34892af80a9b332b76d";
+    /// Astar parachain
+    pub const ASTAR: &str = "0x9eb76c5184c4ab8679d2d5d819fdf90b9c001403e9e17da2e14b6d8aec4029c6";
+    /// Paseo testnet
+    pub const PASEO: &str = "0x77afd6190f1554ad45fd0d31aee62aacc33c6db0ea801129acb813f913e0764f";
+    /// Moonbase Alpha testnet
+    pub const MOONBASE_ALPHA: &str =
+        "0x91bc6e169807aaa54802737e1c504b2577d4fafedd5a02c10293b1cd60e39527";
+    /// Shibuya testnet
+    pub const SHIBUYA: &str = "0xddb89973361a170839f80f152d2e9e38a376a5a7eccefcade763f46a8e567019";
+}
 
 /// Blockchain types
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -42,7 +92,10 @@ pub enum ChainType {
 }
 
 /// Supported blockchain networks
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+///
+/// Chains beyond the built-in ones are registered at runtime, see
+/// [`registry`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Chain {
     /// Polkadot relay chain
     Polkadot,
@@ -60,17 +113,38 @@ pub enum Chain {
     Moonbeam,
     /// Astar (Polkadot parachain with EVM)
     Astar,
+    /// Sepolia (Ethereum testnet)
+    Sepolia,
+    /// Holesky (Ethereum testnet)
+    Holesky,
+    /// Amoy (Polygon testnet, successor of Mumbai)
+    Amoy,
+    /// Moonbase Alpha (Moonbeam testnet)
+    MoonbaseAlpha,
+    /// Shibuya (Astar testnet)
+    Shibuya,
+    /// Paseo (Polkadot testnet)
+    Paseo,
+    /// Chain defined at runtime
+    Custom(Arc<ChainSpec>),
 }
 
 impl Chain {
     /// Get the chain type
     pub fn chain_type(&self) -> ChainType {
         match self {
-            Chain::Polkadot | Chain::Kusama => ChainType::Substrate,
-            Chain::Ethereum | Chain::BinanceSmartChain | Chain::Polygon | Chain::Avalanche => {
-                ChainType::Evm
+            Chain::Polkadot | Chain::Kusama | Chain::Paseo => ChainType::Substrate,
+            Chain::Ethereum
+            | Chain::BinanceSmartChain
+            | Chain::Polygon
+            | Chain::Avalanche
+            | Chain::Sepolia
+            | Chain::Holesky
+            | Chain::Amoy => ChainType::Evm,
+            Chain::Moonbeam | Chain::Astar | Chain::MoonbaseAlpha | Chain::Shibuya => {
+                ChainType::Hybrid
             }
-            Chain::Moonbeam | Chain::Astar => ChainType::Hybrid,
+            Chain::Custom(spec) => spec.chain_type.clone(),
         }
     }
 
@@ -85,12 +159,355 @@ impl Chain {
             Chain::Avalanche => "Avalanche",
             Chain::Moonbeam => "Moonbeam",
             Chain::Astar => "Astar",
+            Chain::Sepolia => "Sepolia",
+            Chain::Holesky => "Holesky",
+            Chain::Amoy => "Polygon Amoy",
+            Chain::MoonbaseAlpha => "Moonbase Alpha",
+            Chain::Shibuya => "Shibuya",
+            Chain::Paseo => "Paseo",
+            Chain::Custom(spec) => &spec.name,
+        }
+    }
+
+    /// Get the symbol of the chain's native token
+    pub fn native_symbol(&self) -> &str {
+        match self {
+            Chain::Polkadot => "DOT",
+            Chain::Kusama => "KSM",
+            Chain::Ethereum => "ETH",
+            Chain::BinanceSmartChain => "BNB",
+            Chain::Polygon => "MATIC",
+            Chain::Avalanche => "AVAX",
+            Chain::Moonbeam => "GLMR",
+            Chain::Astar => "ASTR",
+            Chain::Sepolia | Chain::Holesky => "ETH",
+            Chain::Amoy => "POL",
+            Chain::MoonbaseAlpha => "DEV",
+            Chain::Shibuya => "SBY",
+            Chain::Paseo => "PAS",
+            Chain::Custom(spec) => &spec.symbol,
+        }
+    }
+
+    /// Get the number of decimals of the chain's native token
+    pub fn native_decimals(&self) -> u8 {
+        match self {
+            Chain::Polkadot | Chain::Paseo => 10,
+            Chain::Kusama => 12,
+            Chain::Custom(spec) => spec.decimals,
+            _ => 18,
+        }
+    }
+
+    /// Whether the chain is a built-in test network
+    pub fn is_testnet(&self) -> bool {
+        matches!(
+            self,
+            Chain::Sepolia
+                | Chain::Holesky
+                | Chain::Amoy
+                | Chain::MoonbaseAlpha
+                | Chain::Shibuya
+                | Chain::Paseo
+        )
+    }
+
+    /// Public RPC endpoints, preferred first
+    ///
+    /// Built-in testnets come with public endpoints and registered chains
+    /// with those of their descriptor; built-in mainnets have none, as
+    /// production use needs a provider of its own.
+    pub fn endpoints(&self) -> Vec<&str> {
+        match self {
+            Chain::Sepolia => vec![
+                "https://ethereum-sepolia-rpc.publicnode.com",
+                "wss://ethereum-sepolia-rpc.publicnode.com",
+            ],
+            Chain::Holesky => vec![
+                "https://ethereum-holesky-rpc.publicnode.com",
+                "wss://ethereum-holesky-rpc.publicnode.com",
+            ],
+            Chain::Amoy => vec!["https://rpc-amoy.polygon.technology"],
+            Chain::MoonbaseAlpha => vec![
+                "https://rpc.api.moonbase.moonbeam.network",
+                "wss://wss.api.moonbase.moonbeam.network",
+            ],
+            Chain::Shibuya => vec![
+                "https://evm.shibuya.astar.network",
+                "wss://rpc.shibuya.astar.network",
+            ],
+            Chain::Paseo => vec!["wss://paseo.rpc.amforc.com", "wss://rpc.ibp.network/paseo"],
+            Chain::Custom(spec) => spec.endpoints.iter().map(String::as_str).collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Faucet handing out test tokens, for built-in testnets
+    pub fn faucet_url(&self) -> Option<&'static str> {
+        match self {
+            Chain::Sepolia => {
+                Some("https://cloud.google.com/application/web3/faucet/ethereum/sepolia")
+            }
+            Chain::Holesky => {
+                Some("https://cloud.google.com/application/web3/faucet/ethereum/holesky")
+            }
+            Chain::Amoy => Some("https://faucet.polygon.technology"),
+            Chain::MoonbaseAlpha => Some("https://faucet.moonbeam.network"),
+            Chain::Shibuya => Some("https://portal.astar.network"),
+            Chain::Paseo => Some("https://faucet.polkadot.io"),
+            _ => None,
+        }
+    }
+
+    /// Get the EIP-155 chain ID of an EVM or hybrid chain
+    pub fn evm_chain_id(&self) -> Option<u64> {
+        match self {
+            Chain::Polkadot | Chain::Kusama | Chain::Paseo => None,
+            Chain::Ethereum => Some(1),
+            Chain::BinanceSmartChain => Some(56),
+            Chain::Polygon => Some(137),
+            Chain::Avalanche => Some(43114),
+            Chain::Moonbeam => Some(1284),
+            Chain::Astar => Some(592),
+            Chain::Sepolia => Some(11155111),
+            Chain::Holesky => Some(17000),
+            Chain::Amoy => Some(80002),
+            Chain::MoonbaseAlpha => Some(1287),
+            Chain::Shibuya => Some(81),
+            Chain::Custom(spec) => spec.evm_chain_id,
+        }
+    }
+
+    /// Get the SS58 prefix of a chain with 32-byte Substrate accounts
+    pub fn ss58_prefix(&self) -> Option<u16> {
+        match self {
+            Chain::Polkadot | Chain::Paseo => Some(ss58::POLKADOT),
+            Chain::Kusama => Some(ss58::KUSAMA),
+            Chain::Astar | Chain::Shibuya => Some(ss58::ASTAR),
+            Chain::Custom(spec) => spec.ss58_prefix,
+            _ => None,
+        }
+    }
+
+    /// Get the genesis block hash of a Substrate or hybrid chain, as
+    /// lowercase 0x-prefixed hex
+    pub fn genesis_hash(&self) -> Option<&str> {
+        match self {
+            Chain::Polkadot => Some(genesis::POLKADOT),
+            Chain::Kusama => Some(genesis::KUSAMA),
+            Chain::Moonbeam => Some(genesis::MOONBEAM),
+            Chain::Astar => Some(genesis::ASTAR),
+            Chain::Paseo => Some(genesis::PASEO),
+            Chain::MoonbaseAlpha => Some(genesis::MOONBASE_ALPHA),
+            Chain::Shibuya => Some(genesis::SHIBUYA),
+            Chain::Custom(spec) => spec.genesis_hash.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Find the chain with genesis block hash `hash`, built-in or registered
+    pub fn from_genesis_hash(hash: &str) -> Option<Chain> {
+        [
+            Chain::Polkadot,
+            Chain::Kusama,
+            Chain::Moonbeam,
+            Chain::Astar,
+            Chain::Paseo,
+            Chain::MoonbaseAlpha,
+            Chain::Shibuya,
+        ]
+        .into_iter()
+        .chain(registry::registered())
+        .find(|chain| {
+            chain
+                .genesis_hash()
+                .is_some_and(|genesis| genesis.eq_ignore_ascii_case(hash))
+        })
+    }
+
+    /// Check that an endpoint reporting EIP-155 chain ID `actual` serves
+    /// this chain
+    ///
+    /// Passes for chains without a known chain ID.
+    pub fn verify_evm_chain_id(&self, actual: u64) -> Result<(), String> {
+        match self.evm_chain_id() {
+            Some(expected) if expected != actual => Err(format!(
+                "Endpoint serves chain ID {}{}, expected {} ({})",
+                actual,
+                Chain::from_evm_chain_id(actual)
+                    .map(|chain| format!(" ({})", chain))
+                    .unwrap_or_default(),
+                expected,
+                self
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Check that an endpoint reporting genesis hash `actual` serves this
+    /// chain
+    ///
+    /// Passes for chains without a known genesis hash.
+    pub fn verify_genesis_hash(&self, actual: &str) -> Result<(), String> {
+        match self.genesis_hash() {
+            Some(expected) if !expected.eq_ignore_ascii_case(actual) => Err(format!(
+                "Endpoint serves genesis {}{}, expected {} ({})",
+                actual,
+                Chain::from_genesis_hash(actual)
+                    .map(|chain| format!(" ({})", chain))
+                    .unwrap_or_default(),
+                expected,
+                self
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// How EVM and Substrate accounts relate, for hybrid chains that map
+    /// between them
+    pub fn account_mapping(&self) -> Option<AccountMapping> {
+        match self {
+            Chain::Moonbeam | Chain::MoonbaseAlpha => Some(AccountMapping::AccountId20),
+            Chain::Astar | Chain::Shibuya => Some(AccountMapping::Hashed),
+            Chain::Custom(spec) => spec.account_mapping,
+            _ => None,
+        }
+    }
+
+    /// Find the chain with EIP-155 chain ID `id`, built-in or registered
+    pub fn from_evm_chain_id(id: u64) -> Option<Chain> {
+        [
+            Chain::Ethereum,
+            Chain::BinanceSmartChain,
+            Chain::Polygon,
+            Chain::Avalanche,
+            Chain::Moonbeam,
+            Chain::Astar,
+            Chain::Sepolia,
+            Chain::Holesky,
+            Chain::Amoy,
+            Chain::MoonbaseAlpha,
+            Chain::Shibuya,
+        ]
+        .into_iter()
+        .chain(registry::registered())
+        .find(|chain| chain.evm_chain_id() == Some(id))
+    }
+
+    /// Built-in chain with the given normalized name or alias
+    pub(crate) fn builtin(name: &str) -> Option<Chain> {
+        match name {
+            "polkadot" | "dot" => Some(Chain::Polkadot),
+            "kusama" | "ksm" => Some(Chain::Kusama),
+            "ethereum" | "eth" => Some(Chain::Ethereum),
+            "binancesmartchain" | "bsc" | "bnb" => Some(Chain::BinanceSmartChain),
+            "polygon" | "matic" => Some(Chain::Polygon),
+            "avalanche" | "avax" => Some(Chain::Avalanche),
+            "moonbeam" | "glmr" => Some(Chain::Moonbeam),
+            "astar" | "astr" => Some(Chain::Astar),
+            "sepolia" => Some(Chain::Sepolia),
+            "holesky" => Some(Chain::Holesky),
+            "amoy" | "polygonamoy" => Some(Chain::Amoy),
+            "moonbasealpha" | "moonbase" => Some(Chain::MoonbaseAlpha),
+            "shibuya" | "sby" => Some(Chain::Shibuya),
+            "paseo" | "pas" => Some(Chain::Paseo),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Chain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl std::str::FromStr for Chain {
+    type Err = String;
+
+    /// Parse a chain name, case-insensitively (e.g. "ethereum", "bsc", "Polkadot"),
+    /// falling back to registered chains
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Chain::builtin(&registry::normalize(s))
+            .or_else(|| registry::lookup(s))
+            .ok_or_else(|| format!("Unknown chain: {}", s))
+    }
+}
+
+/// Serialized form of [`Chain`]: built-in chains by variant, registered
+/// chains by name
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "Chain")]
+enum ChainRepr<'a> {
+    Polkadot,
+    Kusama,
+    Ethereum,
+    BinanceSmartChain,
+    Polygon,
+    Avalanche,
+    Moonbeam,
+    Astar,
+    Sepolia,
+    Holesky,
+    Amoy,
+    MoonbaseAlpha,
+    Shibuya,
+    Paseo,
+    Custom(std::borrow::Cow<'a, str>),
+}
+
+impl Serialize for Chain {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Chain::Polkadot => ChainRepr::Polkadot,
+            Chain::Kusama => ChainRepr::Kusama,
+            Chain::Ethereum => ChainRepr::Ethereum,
+            Chain::BinanceSmartChain => ChainRepr::BinanceSmartChain,
+            Chain::Polygon => ChainRepr::Polygon,
+            Chain::Avalanche => ChainRepr::Avalanche,
+            Chain::Moonbeam => ChainRepr::Moonbeam,
+            Chain::Astar => ChainRepr::Astar,
+            Chain::Sepolia => ChainRepr::Sepolia,
+            Chain::Holesky => ChainRepr::Holesky,
+            Chain::Amoy => ChainRepr::Amoy,
+            Chain::MoonbaseAlpha => ChainRepr::MoonbaseAlpha,
+            Chain::Shibuya => ChainRepr::Shibuya,
+            Chain::Paseo => ChainRepr::Paseo,
+            Chain::Custom(spec) => ChainRepr::Custom(spec.name.as_str().into()),
         }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Chain {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(match ChainRepr::deserialize(deserializer)? {
+            ChainRepr::Polkadot => Chain::Polkadot,
+            ChainRepr::Kusama => Chain::Kusama,
+            ChainRepr::Ethereum => Chain::Ethereum,
+            ChainRepr::BinanceSmartChain => Chain::BinanceSmartChain,
+            ChainRepr::Polygon => Chain::Polygon,
+            ChainRepr::Avalanche => Chain::Avalanche,
+            ChainRepr::Moonbeam => Chain::Moonbeam,
+            ChainRepr::Astar => Chain::Astar,
+            ChainRepr::Sepolia => Chain::Sepolia,
+            ChainRepr::Holesky => Chain::Holesky,
+            ChainRepr::Amoy => Chain::Amoy,
+            ChainRepr::MoonbaseAlpha => Chain::MoonbaseAlpha,
+            ChainRepr::Shibuya => Chain::Shibuya,
+            ChainRepr::Paseo => Chain::Paseo,
+            ChainRepr::Custom(name) => registry::lookup(&name)
+                .ok_or_else(|| serde::de::Error::custom(format!("Unregistered chain: {}", name)))?,
+        })
     }
 }
 
 /// Generic address type for different chains
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+///
+/// EVM addresses compare and hash case-insensitively, so a checksummed and a
+/// lowercase address are equal. Substrate addresses compare as strings; use
+/// [`is_same_account`](Self::is_same_account) to ignore the SS58 prefix.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum Address {
     /// Substrate SS58 address
     Substrate(String),
@@ -98,6 +515,32 @@ pub enum Address {
     Evm(String),
 }
 
+impl PartialEq for Address {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Address::Evm(a), Address::Evm(b)) => a.eq_ignore_ascii_case(b),
+            (Address::Substrate(a), Address::Substrate(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for Address {}
+
+impl std::hash::Hash for Address {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match self {
+            Address::Evm(s) => {
+                for byte in s.bytes() {
+                    state.write_u8(byte.to_ascii_lowercase());
+                }
+            }
+            Address::Substrate(s) => s.hash(state),
+        }
+    }
+}
+
 impl Address {
     /// Create a Substrate address
     pub fn substrate(addr: impl Into<String>) -> Self {
@@ -109,14 +552,303 @@ impl Address {
         Address::Evm(addr.into())
     }
 
+    /// Create the Substrate address of a 32-byte public key under `prefix`
+    pub fn from_public_key(prefix: u16, public_key: &[u8; 32]) -> Result<Self, String> {
+        ss58::encode(prefix, public_key).map(Address::Substrate)
+    }
+
     /// Get the address as a string
     pub fn as_str(&self) -> &str {
         match self {
             Address::Substrate(s) | Address::Evm(s) => s,
         }
     }
+
+    /// SS58 prefix of a Substrate address, `None` for EVM or hex addresses
+    pub fn ss58_prefix(&self) -> Option<u16> {
+        match self {
+            Address::Substrate(s) => ss58::decode(s).ok().map(|(prefix, _)| prefix),
+            Address::Evm(_) => None,
+        }
+    }
+
+    /// Check that a Substrate address is meant for the network with `prefix`
+    ///
+    /// 0x-hex account IDs carry no prefix and pass; EVM addresses, malformed
+    /// addresses and SS58 addresses for another network fail.
+    pub fn validate_ss58_prefix(&self, prefix: u16) -> Result<(), String> {
+        match self {
+            Address::Substrate(s) if s.starts_with("0x") => self
+                .account_id()
+                .map(|_| ())
+                .ok_or_else(|| format!("Invalid Substrate address: {}", s)),
+            Address::Substrate(s) => match ss58::decode(s)? {
+                (actual, _) if actual == prefix => Ok(()),
+                (actual, _) => Err(format!(
+                    "Address {} is for SS58 prefix {}, expected {}",
+                    s, actual, prefix
+                )),
+            },
+            Address::Evm(s) => Err(format!("Not a Substrate address: {}", s)),
+        }
+    }
+
+    /// The 32-byte `AccountId32` of a Substrate address, `None` for EVM or
+    /// malformed addresses
+    pub fn account_id(&self) -> Option<[u8; 32]> {
+        match self {
+            Address::Substrate(_) => self.account_bytes()?.try_into().ok(),
+            Address::Evm(_) => None,
+        }
+    }
+
+    /// The same Substrate account under another SS58 prefix, e.g. to display
+    /// a Polkadot address in Kusama format
+    ///
+    /// Accepts SS58 and 0x-hex account IDs; fails for EVM addresses.
+    pub fn with_ss58_prefix(&self, prefix: u16) -> Result<Self, String> {
+        match self {
+            Address::Substrate(s) => {
+                let public_key = self
+                    .account_id()
+                    .ok_or_else(|| format!("Invalid Substrate address: {}", s))?;
+                Self::from_public_key(prefix, &public_key)
+            }
+            Address::Evm(s) => Err(format!("Not a Substrate address: {}", s)),
+        }
+    }
+
+    /// Raw account bytes, independent of formatting
+    ///
+    /// The 20-byte EVM address whatever its hex casing, or the 32-byte
+    /// Substrate account ID whatever its SS58 prefix (0x-hex account IDs are
+    /// accepted too). `None` if the address does not decode or its EIP-55 or
+    /// SS58 checksum is wrong.
+    pub fn account_bytes(&self) -> Option<Vec<u8>> {
+        match self {
+            Address::Evm(s) => eip55::parse(s).ok().map(|bytes| bytes.to_vec()),
+            Address::Substrate(s) => match s.strip_prefix("0x") {
+                Some(hex_str) => hex::decode(hex_str).ok().filter(|b| b.len() == 32),
+                None => ss58::public_key(s).ok().map(|key| key.to_vec()),
+            },
+        }
+    }
+
+    /// Whether the address decodes, with a valid checksum if it has one
+    pub fn is_valid(&self) -> bool {
+        self.account_bytes().is_some()
+    }
+
+    /// The address in canonical form: EIP-55 checksummed for EVM addresses,
+    /// unchanged for valid Substrate addresses
+    pub fn normalize(&self) -> Result<Self, String> {
+        let bytes = self
+            .account_bytes()
+            .ok_or_else(|| format!("Invalid address: {}", self.as_str()))?;
+        match self {
+            Address::Evm(_) => {
+                let mut address = [0u8; 20];
+                address.copy_from_slice(&bytes);
+                Ok(Address::Evm(eip55::to_checksum(&address)))
+            }
+            Address::Substrate(_) => Ok(self.clone()),
+        }
+    }
+
+    /// Like [`normalize`](Self::normalize), but with EVM addresses in
+    /// lowercase hex
+    pub fn normalize_lowercase(&self) -> Result<Self, String> {
+        match self.normalize()? {
+            Address::Evm(s) => Ok(Address::Evm(s.to_ascii_lowercase())),
+            substrate => Ok(substrate),
+        }
+    }
+
+    /// The address as a 32-byte EVM log topic, as indexed `address` parameters are logged
+    ///
+    /// `None` for addresses that are not 20-byte EVM addresses.
+    pub fn to_topic(&self) -> Option<String> {
+        match self {
+            Address::Evm(_) => Some(format!("0x{:0>64}", hex::encode(self.account_bytes()?))),
+            Address::Substrate(_) => None,
+        }
+    }
+
+    /// Check that the address is a valid account on `chain`
+    ///
+    /// EVM addresses need an EVM or hybrid chain, SS58 addresses the chain's
+    /// prefix. Chains with [`AccountMapping::AccountId20`] have no 32-byte
+    /// accounts, so Substrate addresses fail there.
+    pub fn validate_on(&self, chain: &Chain) -> Result<(), String> {
+        match self {
+            Address::Evm(s) => {
+                if chain.chain_type() == ChainType::Substrate {
+                    return Err(format!("{} has no EVM accounts: {}", chain, s));
+                }
+                eip55::parse(s).map(|_| ())
+            }
+            Address::Substrate(s) => match (chain.account_mapping(), chain.ss58_prefix()) {
+                (Some(AccountMapping::AccountId20), _) => Err(format!(
+                    "{} accounts are 20-byte EVM addresses: {}",
+                    chain, s
+                )),
+                (_, Some(prefix)) => self.validate_ss58_prefix(prefix),
+                (_, None) => Err(format!("{} has no Substrate accounts: {}", chain, s)),
+            },
+        }
+    }
+
+    /// The EVM account for this address on hybrid `chain`, checksummed
+    ///
+    /// EVM addresses are validated and returned as is. Substrate addresses
+    /// are mapped with the chain's [`AccountMapping`]; see [`mapping`] for
+    /// why a mapped account is not the same key as the original.
+    pub fn to_evm_on(&self, chain: &Chain) -> Result<Self, String> {
+        let mapping = chain
+            .account_mapping()
+            .ok_or_else(|| format!("{} does not map EVM and Substrate accounts", chain))?;
+        self.validate_on(chain)?;
+        match (self, mapping) {
+            (Address::Evm(_), _) => self.normalize(),
+            (Address::Substrate(s), AccountMapping::Hashed) => {
+                let account_id = self
+                    .account_id()
+                    .ok_or_else(|| format!("Invalid Substrate address: {}", s))?;
+                let h160 = mapping::account_id_to_h160(&account_id);
+                Ok(Address::Evm(eip55::to_checksum(&h160)))
+            }
+            (Address::Substrate(s), AccountMapping::AccountId20) => {
+                Err(format!("{} has no 32-byte accounts: {}", chain, s))
+            }
+        }
+    }
+
+    /// The Substrate account for this address on hybrid `chain`
+    ///
+    /// SS58 addresses are validated against the chain's prefix and 0x-hex
+    /// account IDs re-encoded with it. EVM addresses are mapped with the
+    /// chain's [`AccountMapping`]: under
+    /// [`AccountId20`](AccountMapping::AccountId20) the Substrate account is
+    /// the H160 itself and the checksummed EVM address is returned.
+    pub fn to_substrate_on(&self, chain: &Chain) -> Result<Self, String> {
+        let mapping = chain
+            .account_mapping()
+            .ok_or_else(|| format!("{} does not map EVM and Substrate accounts", chain))?;
+        self.validate_on(chain)?;
+        let prefix = || {
+            chain
+                .ss58_prefix()
+                .ok_or_else(|| format!("{} has no SS58 prefix", chain))
+        };
+        match (self, mapping) {
+            (Address::Evm(_), AccountMapping::AccountId20) => self.normalize(),
+            (Address::Evm(s), AccountMapping::Hashed) => {
+                let h160 = eip55::parse(s)?;
+                Self::from_public_key(prefix()?, &mapping::h160_to_account_id(&h160))
+            }
+            (Address::Substrate(_), _) => self.with_ss58_prefix(prefix()?),
+        }
+    }
+
+    /// Whether both addresses refer to the same account
+    ///
+    /// Compares [`account_bytes`](Self::account_bytes), falling back to the
+    /// exact strings for addresses that do not decode.
+    pub fn is_same_account(&self, other: &Address) -> bool {
+        match (self.account_bytes(), other.account_bytes()) {
+            (Some(a), Some(b)) => a == b,
+            _ => self == other,
+        }
+    }
+}
+
+impl fmt::Display for Address {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for Address {
+    type Err = AddressParseError;
+
+    /// Parse an address, detecting its format: `0x` and 40 hex digits is an
+    /// EVM address (checksum verified if mixed case), `0x` and 64 hex digits
+    /// a Substrate account ID, anything else must decode as SS58
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Address::parse_with(s, false)
+    }
+}
+
+impl Address {
+    /// Parse an address like [`FromStr`](std::str::FromStr), accepting only
+    /// formats that carry a checksum
+    ///
+    /// EVM addresses must be EIP-55 checksummed and Substrate addresses
+    /// SS58 encoded, so a mistyped character cannot produce a valid address.
+    pub fn parse_strict(s: &str) -> Result<Self, AddressParseError> {
+        Address::parse_with(s, true)
+    }
+
+    fn parse_with(s: &str, strict: bool) -> Result<Self, AddressParseError> {
+        if s.is_empty() {
+            return Err(AddressParseError::Empty);
+        }
+        let Some(hex_str) = s.strip_prefix("0x") else {
+            return ss58::decode(s)
+                .map(|_| Address::Substrate(s.to_string()))
+                .map_err(AddressParseError::InvalidSs58);
+        };
+        if !hex_str.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(AddressParseError::InvalidHex(s.to_string()));
+        }
+        match hex_str.len() {
+            40 => {
+                eip55::parse(s).map_err(|_| AddressParseError::BadChecksum(s.to_string()))?;
+                let mixed_case = hex_str.chars().any(|c| c.is_ascii_lowercase())
+                    && hex_str.chars().any(|c| c.is_ascii_uppercase());
+                if strict && !mixed_case {
+                    return Err(AddressParseError::MissingChecksum(s.to_string()));
+                }
+                Ok(Address::Evm(s.to_string()))
+            }
+            64 if strict => Err(AddressParseError::MissingChecksum(s.to_string())),
+            64 => Ok(Address::Substrate(s.to_string())),
+            _ => Err(AddressParseError::InvalidHex(s.to_string())),
+        }
+    }
+}
+
+/// Error parsing an [`Address`] from a string
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AddressParseError {
+    /// The string is empty
+    Empty,
+    /// `0x`-prefixed, but not 20 or 32 bytes of hex
+    InvalidHex(String),
+    /// Mixed-case EVM address whose EIP-55 checksum does not match
+    BadChecksum(String),
+    /// Valid, but in a format without a checksum (strict parsing only)
+    MissingChecksum(String),
+    /// Not hex and not a valid SS58 address
+    InvalidSs58(String),
+}
+
+impl fmt::Display for AddressParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AddressParseError::Empty => f.write_str("Empty address"),
+            AddressParseError::InvalidHex(s) => {
+                write!(f, "Invalid hex address, expected 20 or 32 bytes: {}", s)
+            }
+            AddressParseError::BadChecksum(s) => write!(f, "Invalid EIP-55 checksum: {}", s),
+            AddressParseError::MissingChecksum(s) => write!(f, "Address has no checksum: {}", s),
+            AddressParseError::InvalidSs58(e) => write!(f, "Invalid SS58 address: {}", e),
+        }
+    }
 }
 
+impl std::error::Error for AddressParseError {}
+
 /// Transaction status
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum TransactionStatus {
@@ -138,6 +870,122 @@ pub enum TransactionStatus {
     Unknown,
 }
 
+impl TransactionStatus {
+    /// Whether the transaction is confirmed with at least `min_confirmations`
+    /// blocks on top of its own
+    ///
+    /// Pending, failed and unknown transactions are never final.
+    pub fn is_final(&self, min_confirmations: u32) -> bool {
+        matches!(
+            self,
+            TransactionStatus::Confirmed { confirmations, .. } if *confirmations >= min_confirmations
+        )
+    }
+}
+
+impl fmt::Display for TransactionStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TransactionStatus::Pending => f.write_str("Pending"),
+            TransactionStatus::Confirmed {
+                block_number,
+                confirmations,
+            } => write!(
+                f,
+                "Confirmed in block #{} ({} confirmation{})",
+                block_number,
+                confirmations,
+                if *confirmations == 1 { "" } else { "s" }
+            ),
+            TransactionStatus::Failed { error } => write!(f, "Failed: {}", error),
+            TransactionStatus::Unknown => f.write_str("Unknown"),
+        }
+    }
+}
+
+/// Event emitted while executing a transaction
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TransactionEvent {
+    /// Runtime event emitted by a Substrate extrinsic
+    Substrate {
+        /// Pallet that emitted the event (e.g. "Balances")
+        pallet: String,
+        /// Event variant (e.g. "Transfer")
+        variant: String,
+        /// Decoded event fields in human-readable form
+        fields: String,
+    },
+    /// Log emitted by an EVM transaction
+    EvmLog {
+        /// Address of the contract that emitted the log
+        address: String,
+        /// Indexed topics (0x-prefixed hex)
+        topics: Vec<String>,
+        /// Unindexed data (0x-prefixed hex)
+        data: String,
+        /// Index of the log within the block
+        log_index: Option<u64>,
+    },
+}
+
+/// Event observed on chain: a Substrate runtime event or an EVM log
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Event {
+    /// Kind of chain the event was observed on
+    pub chain_type: ChainType,
+    /// Number of the block containing the event
+    pub block_number: u64,
+    /// Hash of the block containing the event
+    pub block_hash: String,
+    /// Hash of the transaction that emitted the event, if known
+    pub tx_hash: Option<String>,
+    /// Position of the event within the block
+    pub index: u32,
+    /// Contract or account that emitted the event, if known
+    pub origin: Option<Address>,
+    /// The event itself
+    pub event: TransactionEvent,
+}
+
+impl Event {
+    /// Event name: `Pallet.Variant` for Substrate, the first topic for EVM logs
+    pub fn name(&self) -> String {
+        match &self.event {
+            TransactionEvent::Substrate {
+                pallet, variant, ..
+            } => format!("{}.{}", pallet, variant),
+            TransactionEvent::EvmLog { topics, .. } => topics.first().cloned().unwrap_or_default(),
+        }
+    }
+}
+
+/// How final a block must be before it is reported
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Finality {
+    /// Newest block at the head of the chain; may still be reorganized
+    #[default]
+    Latest,
+    /// Block finalized by the chain's consensus
+    Finalized,
+}
+
+/// Block header observed on chain
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockInfo {
+    /// Kind of chain the block was observed on
+    pub chain_type: ChainType,
+    /// Block number
+    pub number: u64,
+    /// Block hash
+    pub hash: String,
+    /// Hash of the parent block
+    pub parent_hash: String,
+    /// Block timestamp in seconds since the Unix epoch, if known
+    pub timestamp: Option<u64>,
+    /// Finality level the block was reported at
+    pub finality: Finality,
+}
+
 /// Cross-chain transaction info
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CrossChainTransaction {
@@ -160,6 +1008,7 @@ pub struct CrossChainTransaction {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
 
     #[test]
     fn test_chain_type() {
@@ -168,12 +1017,408 @@ mod tests {
         assert_eq!(Chain::Moonbeam.chain_type(), ChainType::Hybrid);
     }
 
+    #[test]
+    fn test_chain_from_str() {
+        assert_eq!("ethereum".parse::<Chain>(), Ok(Chain::Ethereum));
+        assert_eq!("Polkadot".parse::<Chain>(), Ok(Chain::Polkadot));
+        assert_eq!("bsc".parse::<Chain>(), Ok(Chain::BinanceSmartChain));
+        assert_eq!(
+            "binance-smart-chain".parse::<Chain>(),
+            Ok(Chain::BinanceSmartChain)
+        );
+        assert_eq!(
+            Chain::BinanceSmartChain.name().parse::<Chain>(),
+            Ok(Chain::BinanceSmartChain)
+        );
+        assert!("solana".parse::<Chain>().is_err());
+    }
+
     #[test]
     fn test_address_creation() {
         let sub_addr = Address::substrate("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY");
         assert!(matches!(sub_addr, Address::Substrate(_)));
 
-        let evm_addr = Address::evm("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb7");
+        let evm_addr = Address::evm("0x742D35Cc6634C0532925A3B844bC9e7595f0bEB7");
         assert!(matches!(evm_addr, Address::Evm(_)));
     }
+
+    #[test]
+    fn test_address_normalization() {
+        let evm = Address::evm("0x742D35Cc6634C0532925A3B844bC9e7595f0bEB7");
+        assert!(evm.is_same_account(&Address::evm("0x742d35cc6634c0532925a3b844bc9e7595f0beb7")));
+        assert!(!evm.is_same_account(&Address::evm("0x0000000000000000000000000000000000000000")));
+
+        // Alice under the generic and Polkadot prefixes, and as a hex account ID
+        let alice = Address::substrate("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY");
+        assert!(alice.is_same_account(&Address::substrate(
+            "15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5"
+        )));
+        assert!(alice.is_same_account(&Address::substrate(
+            "0xd43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d"
+        )));
+        assert!(!alice.is_same_account(&evm));
+        assert_eq!(
+            evm.to_topic().unwrap(),
+            "0x000000000000000000000000742d35cc6634c0532925a3b844bc9e7595f0beb7"
+        );
+        assert_eq!(alice.to_topic(), None);
+
+        assert_eq!(Address::substrate("not an address").account_bytes(), None);
+        // Valid base58 with a broken checksum
+        assert_eq!(
+            Address::substrate("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQZ").account_bytes(),
+            None
+        );
+        assert!(Address::substrate("x").is_same_account(&Address::substrate("x")));
+    }
+
+    #[test]
+    fn test_ss58_prefix_validation() {
+        let alice = Address::substrate("15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5");
+        assert_eq!(alice.validate_ss58_prefix(ss58::POLKADOT), Ok(()));
+        assert!(alice
+            .validate_ss58_prefix(ss58::KUSAMA)
+            .unwrap_err()
+            .contains("prefix 0, expected 2"));
+
+        let kusama = alice.with_ss58_prefix(ss58::KUSAMA).unwrap();
+        assert_eq!(
+            kusama.as_str(),
+            "HNZata7iMYWmk5RvZRTiAsSDhV8366zq2YGb3tLH5Upf74F"
+        );
+        assert_eq!(kusama.validate_ss58_prefix(ss58::KUSAMA), Ok(()));
+        assert_eq!(kusama.account_id(), alice.account_id());
+        assert_eq!(
+            hex::encode(alice.account_id().unwrap()),
+            "d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d"
+        );
+
+        let hex_id = Address::substrate(
+            "0xd43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d",
+        );
+        assert_eq!(hex_id.validate_ss58_prefix(ss58::KUSAMA), Ok(()));
+        assert!(Address::substrate("invalid")
+            .validate_ss58_prefix(ss58::POLKADOT)
+            .is_err());
+        let evm = Address::evm("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed");
+        assert!(evm.validate_ss58_prefix(ss58::POLKADOT).is_err());
+        assert_eq!(evm.account_id(), None);
+    }
+
+    #[test]
+    fn test_testnets() {
+        let testnets = [
+            Chain::Sepolia,
+            Chain::Holesky,
+            Chain::Amoy,
+            Chain::MoonbaseAlpha,
+            Chain::Shibuya,
+            Chain::Paseo,
+        ];
+        for chain in testnets {
+            assert!(chain.is_testnet());
+            assert!(chain.faucet_url().is_some());
+            assert!(!chain.endpoints().is_empty());
+            assert_eq!(chain.name().parse::<Chain>().unwrap(), chain);
+            let json = serde_json::to_string(&chain).unwrap();
+            assert_eq!(serde_json::from_str::<Chain>(&json).unwrap(), chain);
+            if let Some(id) = chain.evm_chain_id() {
+                assert_eq!(Chain::from_evm_chain_id(id), Some(chain.clone()));
+            }
+        }
+
+        assert!(!Chain::Ethereum.is_testnet());
+        assert!(Chain::Ethereum.endpoints().is_empty());
+        assert_eq!(Chain::Sepolia.evm_chain_id(), Some(11155111));
+        assert_eq!(Chain::MoonbaseAlpha.chain_type(), ChainType::Hybrid);
+        assert_eq!(
+            Chain::MoonbaseAlpha.account_mapping(),
+            Some(AccountMapping::AccountId20)
+        );
+        assert_eq!(Chain::Shibuya.ss58_prefix(), Some(ss58::ASTAR));
+        assert_eq!(Chain::Paseo.chain_type(), ChainType::Substrate);
+        assert_eq!(Chain::Paseo.native_decimals(), 10);
+        assert_eq!("moonbase".parse::<Chain>().unwrap(), Chain::MoonbaseAlpha);
+    }
+
+    #[test]
+    fn test_chain_identity() {
+        assert_eq!(
+            Chain::from_genesis_hash(genesis::POLKADOT),
+            Some(Chain::Polkadot)
+        );
+        assert_eq!(
+            Chain::from_genesis_hash(&genesis::KUSAMA.to_uppercase().replace("0X", "0x")),
+            Some(Chain::Kusama)
+        );
+        assert_eq!(
+            Chain::from_genesis_hash(&format!("0x{}", "00".repeat(32))),
+            None
+        );
+        assert_eq!(Chain::Ethereum.genesis_hash(), None);
+        // Testnets are verified like their mainnets
+        for (chain, hash) in [
+            (Chain::Paseo, genesis::PASEO),
+            (Chain::MoonbaseAlpha, genesis::MOONBASE_ALPHA),
+            (Chain::Shibuya, genesis::SHIBUYA),
+        ] {
+            assert_eq!(chain.genesis_hash(), Some(hash));
+            assert_eq!(Chain::from_genesis_hash(hash), Some(chain.clone()));
+            assert!(chain.verify_genesis_hash(genesis::POLKADOT).is_err());
+        }
+
+        assert_eq!(
+            Chain::Polkadot.verify_genesis_hash(genesis::POLKADOT),
+            Ok(())
+        );
+        let err = Chain::Polkadot
+            .verify_genesis_hash(genesis::KUSAMA)
+            .unwrap_err();
+        assert!(err.contains("(Kusama)"), "{}", err);
+        assert!(Chain::Ethereum.verify_genesis_hash(genesis::KUSAMA).is_ok());
+
+        assert_eq!(Chain::Polygon.verify_evm_chain_id(137), Ok(()));
+        let err = Chain::Ethereum.verify_evm_chain_id(56).unwrap_err();
+        assert!(
+            err.contains("chain ID 56 (Binance Smart Chain), expected 1"),
+            "{}",
+            err
+        );
+        assert!(Chain::Polkadot.verify_evm_chain_id(1).is_ok());
+    }
+
+    #[test]
+    fn test_address_from_str() {
+        let inputs = [
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+            "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed",
+            "15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5",
+            "0xd43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d",
+        ];
+        for input in inputs {
+            let address: Address = input.parse().unwrap();
+            assert_eq!(address.to_string(), input);
+            assert_eq!(address.to_string().parse::<Address>().unwrap(), address);
+        }
+        assert!(matches!(inputs[0].parse(), Ok(Address::Evm(_))));
+        assert!(matches!(inputs[2].parse(), Ok(Address::Substrate(_))));
+        assert!(matches!(inputs[3].parse(), Ok(Address::Substrate(_))));
+
+        assert_eq!("".parse::<Address>(), Err(AddressParseError::Empty));
+        assert!(matches!(
+            "0xabc".parse::<Address>(),
+            Err(AddressParseError::InvalidHex(_))
+        ));
+        assert!(matches!(
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD".parse::<Address>(),
+            Err(AddressParseError::BadChecksum(_))
+        ));
+        assert!(matches!(
+            "not-an-address".parse::<Address>(),
+            Err(AddressParseError::InvalidSs58(_))
+        ));
+    }
+
+    #[test]
+    fn test_address_parse_strict() {
+        assert!(Address::parse_strict("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed").is_ok());
+        assert!(Address::parse_strict("15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5").is_ok());
+        for unchecked in [
+            "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed",
+            "0x5AAEB6053F3E94C9B9A09F33669435E7EF1BEAED",
+            "0xd43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d",
+        ] {
+            let err = Address::parse_strict(unchecked).unwrap_err();
+            assert_eq!(
+                err,
+                AddressParseError::MissingChecksum(unchecked.to_string())
+            );
+            assert!(err.to_string().contains("no checksum"));
+        }
+    }
+
+    #[test]
+    fn test_hybrid_account_mapping() {
+        let evm = Address::evm("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed");
+        let lower = Address::evm("0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed");
+
+        // Moonbeam: the same H160 on both sides, 32-byte accounts rejected
+        assert_eq!(
+            lower.to_substrate_on(&Chain::Moonbeam).unwrap().as_str(),
+            evm.as_str()
+        );
+        assert_eq!(
+            lower.to_evm_on(&Chain::Moonbeam).unwrap().as_str(),
+            evm.as_str()
+        );
+        let alice = Address::substrate("15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5");
+        assert!(alice.validate_on(&Chain::Moonbeam).is_err());
+        assert!(alice.to_evm_on(&Chain::Moonbeam).is_err());
+
+        // Astar: hashed, with the Astar prefix
+        let native = evm.to_substrate_on(&Chain::Astar).unwrap();
+        assert_eq!(native.ss58_prefix(), Some(ss58::ASTAR));
+        assert_eq!(
+            native.account_id(),
+            Some(mapping::h160_to_account_id(
+                &eip55::parse(evm.as_str()).unwrap()
+            ))
+        );
+        assert_eq!(lower.to_substrate_on(&Chain::Astar).unwrap(), native);
+        let mapped = native.to_evm_on(&Chain::Astar).unwrap();
+        assert!(eip55::is_valid(mapped.as_str()));
+        assert_ne!(mapped, evm);
+
+        // Polkadot-prefixed addresses are not Astar accounts until re-encoded
+        assert!(alice.to_evm_on(&Chain::Astar).is_err());
+        let astar_alice = alice.with_ss58_prefix(ss58::ASTAR).unwrap();
+        assert_eq!(
+            astar_alice.to_substrate_on(&Chain::Astar).unwrap(),
+            astar_alice
+        );
+        let hex_id = Address::substrate(format!("0x{}", hex::encode(alice.account_id().unwrap())));
+        assert_eq!(hex_id.to_substrate_on(&Chain::Astar).unwrap(), astar_alice);
+
+        // Bad checksums and chains without a mapping
+        let bad = Address::evm("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD");
+        assert!(bad.to_substrate_on(&Chain::Astar).is_err());
+        assert!(evm.to_substrate_on(&Chain::Ethereum).is_err());
+        assert!(alice.to_evm_on(&Chain::Polkadot).is_err());
+        assert!(evm.validate_on(&Chain::Ethereum).is_ok());
+        assert!(evm.validate_on(&Chain::Polkadot).is_err());
+    }
+
+    #[test]
+    fn test_evm_checksum() {
+        let checksummed = "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed";
+        let lower = Address::evm(checksummed.to_lowercase());
+        assert_eq!(lower, Address::evm(checksummed));
+        assert_eq!(lower.normalize().unwrap().as_str(), checksummed);
+        assert_eq!(
+            Address::evm(checksummed).normalize_lowercase().unwrap(),
+            lower
+        );
+        assert_eq!(
+            Address::evm(checksummed)
+                .normalize_lowercase()
+                .unwrap()
+                .as_str(),
+            lower.as_str()
+        );
+
+        // One letter's case flipped
+        let typo = Address::evm("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD");
+        assert!(!typo.is_valid());
+        assert!(typo.normalize().is_err());
+        assert!(lower.is_valid());
+
+        let set: std::collections::HashSet<_> = [lower, Address::evm(checksummed)].into();
+        assert_eq!(set.len(), 1);
+        assert_ne!(Address::evm(checksummed), Address::substrate(checksummed));
+    }
+
+    proptest! {
+        #[test]
+        fn prop_evm_account_bytes_ignore_case(key in any::<[u8; 20]>(), upper in any::<bool>()) {
+            let hex = hex::encode(key);
+            let hex = if upper { hex.to_uppercase() } else { hex };
+            let address = Address::evm(format!("0x{}", hex));
+            prop_assert_eq!(address.account_bytes(), Some(key.to_vec()));
+            let lower = Address::evm(format!("0x{}", hex::encode(key)));
+            prop_assert!(address.is_same_account(&lower));
+        }
+
+        #[test]
+        fn prop_substrate_account_bytes_ignore_prefix(
+            id in any::<[u8; 32]>(),
+            prefix in 0..=ss58::MAX_PREFIX,
+        ) {
+            let address = Address::from_public_key(prefix, &id).unwrap();
+            prop_assert_eq!(address.account_bytes(), Some(id.to_vec()));
+            prop_assert_eq!(address.ss58_prefix(), Some(prefix));
+            let hex_id = Address::substrate(format!("0x{}", hex::encode(id)));
+            prop_assert!(address.is_same_account(&hex_id));
+            prop_assert_eq!(hex_id.with_ss58_prefix(prefix), Ok(address));
+        }
+
+        #[test]
+        fn prop_account_bytes_length(value in "(0x)?[0-9a-fA-F]{0,70}|[1-9A-HJ-NP-Za-km-z]{0,60}|\\PC*") {
+            prop_assert!(Address::evm(value.clone()).account_bytes().is_none_or(|b| b.len() == 20));
+            prop_assert!(Address::substrate(value).account_bytes().is_none_or(|b| b.len() == 32));
+        }
+    }
+
+    #[test]
+    fn test_ss58_prefix() {
+        let alice = Address::substrate("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY");
+        assert_eq!(alice.ss58_prefix(), Some(ss58::SUBSTRATE));
+
+        let prefix = Chain::Polkadot.ss58_prefix().unwrap();
+        let polkadot = alice.with_ss58_prefix(prefix).unwrap();
+        assert_eq!(
+            polkadot.as_str(),
+            "15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5"
+        );
+        assert_eq!(polkadot.ss58_prefix(), Some(ss58::POLKADOT));
+        assert!(polkadot.is_same_account(&alice));
+
+        let evm = Address::evm("0x742D35Cc6634C0532925A3B844bC9e7595f0bEB7");
+        assert_eq!(evm.ss58_prefix(), None);
+        assert!(evm.with_ss58_prefix(0).is_err());
+        assert_eq!(Chain::Ethereum.ss58_prefix(), None);
+    }
+
+    #[test]
+    fn test_native_token() {
+        assert_eq!(Chain::Polkadot.native_symbol(), "DOT");
+        assert_eq!(Chain::Polkadot.native_decimals(), 10);
+        assert_eq!(Chain::Kusama.native_decimals(), 12);
+        assert_eq!(Chain::Ethereum.native_symbol(), "ETH");
+        assert_eq!(Chain::Ethereum.native_decimals(), 18);
+    }
+
+    #[test]
+    fn test_evm_chain_id() {
+        assert_eq!(Chain::Polygon.evm_chain_id(), Some(137));
+        assert_eq!(Chain::Polkadot.evm_chain_id(), None);
+        assert_eq!(Chain::from_evm_chain_id(1284), Some(Chain::Moonbeam));
+        assert_eq!(Chain::from_evm_chain_id(5), None);
+    }
+
+    #[test]
+    fn test_transaction_status_display() {
+        assert_eq!(TransactionStatus::Pending.to_string(), "Pending");
+        assert_eq!(
+            TransactionStatus::Confirmed {
+                block_number: 12345,
+                confirmations: 1
+            }
+            .to_string(),
+            "Confirmed in block #12345 (1 confirmation)"
+        );
+        assert_eq!(
+            TransactionStatus::Failed {
+                error: "out of gas".to_string()
+            }
+            .to_string(),
+            "Failed: out of gas"
+        );
+        assert_eq!(Chain::BinanceSmartChain.to_string(), "Binance Smart Chain");
+    }
+
+    #[test]
+    fn test_transaction_status_is_final() {
+        let confirmed = TransactionStatus::Confirmed {
+            block_number: 100,
+            confirmations: 6,
+        };
+        assert!(confirmed.is_final(0));
+        assert!(confirmed.is_final(6));
+        assert!(!confirmed.is_final(7));
+        assert!(!TransactionStatus::Pending.is_final(0));
+        assert!(!TransactionStatus::Failed {
+            error: "reverted".to_string()
+        }
+        .is_final(0));
+    }
 }
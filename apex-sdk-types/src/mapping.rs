@@ -0,0 +1,75 @@
+//! Accounts on hybrid chains
+//!
+//! Chains running an EVM next to Substrate pallets relate the two kinds of
+//! accounts in one of two ways:
+//!
+//! - [`AccountMapping::AccountId20`] (Moonbeam): Substrate accounts *are*
+//!   20-byte EVM addresses, so both sides use the same H160.
+//! - [`AccountMapping::Hashed`] (Astar): Substrate accounts are 32 bytes.
+//!   An H160's Substrate account is `blake2_256("evm:" ++ h160)`, and an
+//!   account's default H160 is the first 20 bytes of
+//!   `blake2_256("evm:" ++ account_id)`.
+//!
+//! The hashed mappings are one-way: the Substrate account of an H160 maps
+//! to a *different* H160, so funds sent to a converted address can only be
+//! spent by the key it was derived from. Accounts that bound an explicit
+//! mapping on chain (Astar's `UnifiedAccounts` pallet) no longer use the
+//! defaults.
+//!
+//! ```rust
+//! use apex_sdk_types::{Address, Chain};
+//!
+//! let evm = Address::evm("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed");
+//! let native = evm.to_substrate_on(&Chain::Astar).unwrap();
+//! assert_eq!(native.ss58_prefix(), Some(5));
+//!
+//! // Moonbeam accounts are H160s on both sides
+//! assert_eq!(evm.to_substrate_on(&Chain::Moonbeam).unwrap(), evm);
+//! ```
+
+use blake2::digest::consts::U32;
+use blake2::{Blake2b, Digest};
+use serde::{Deserialize, Serialize};
+
+/// How a hybrid chain relates its EVM and Substrate accounts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AccountMapping {
+    /// Substrate accounts are 20-byte EVM addresses
+    AccountId20,
+    /// 32-byte Substrate accounts, mapped to and from H160s by hashing
+    Hashed,
+}
+
+/// Default Substrate account of an H160 under [`AccountMapping::Hashed`]
+pub fn h160_to_account_id(h160: &[u8; 20]) -> [u8; 32] {
+    evm_hash(h160)
+}
+
+/// Default H160 of a Substrate account under [`AccountMapping::Hashed`]
+pub fn account_id_to_h160(account_id: &[u8; 32]) -> [u8; 20] {
+    let mut h160 = [0u8; 20];
+    h160.copy_from_slice(&evm_hash(account_id)[..20]);
+    h160
+}
+
+/// `blake2_256("evm:" ++ data)`
+fn evm_hash(data: &[u8]) -> [u8; 32] {
+    let mut hasher = Blake2b::<U32>::new();
+    hasher.update(b"evm:");
+    hasher.update(data);
+    hasher.finalize().into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hashed_mapping_is_one_way() {
+        let h160 = [0x11u8; 20];
+        let account_id = h160_to_account_id(&h160);
+        assert_eq!(account_id, h160_to_account_id(&h160));
+        assert_ne!(account_id_to_h160(&account_id), h160);
+        assert_ne!(account_id[..20], h160);
+    }
+}
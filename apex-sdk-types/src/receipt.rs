@@ -0,0 +1,163 @@
+//! Transaction receipts
+//!
+//! A [`Receipt`] describes an included transaction the same way on every
+//! chain: where it landed, whether it succeeded, what it cost and what it
+//! emitted. Substrate events are decoded against the runtime metadata; EVM
+//! logs are kept as raw topics and data, since decoding them needs the
+//! emitting contract's ABI.
+//!
+//! ```rust
+//! use apex_sdk_types::{Fee, Receipt};
+//!
+//! let receipt = Receipt::new("0xabc")
+//!     .with_block(12, "0xdef")
+//!     .with_fee(Fee::from_gas(21_000, 30_000_000_000));
+//! assert!(receipt.is_success());
+//! assert_eq!(receipt.effective_gas_price, Some(30_000_000_000));
+//!
+//! let reverted = Receipt::new("0xabc").with_error("Transaction reverted");
+//! assert!(!reverted.is_success());
+//! ```
+
+use crate::{Fee, TransactionEvent};
+use serde::{Deserialize, Serialize};
+
+/// Outcome of an included transaction, common to EVM and Substrate chains
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Receipt {
+    /// Transaction (EVM) or extrinsic (Substrate) hash
+    pub tx_hash: String,
+    /// Number of the block containing the transaction, if known
+    pub block_number: Option<u64>,
+    /// Hash of the block containing the transaction, if known
+    pub block_hash: Option<String>,
+    /// Revert reason (EVM) or dispatch error (Substrate); `None` on success
+    pub error: Option<String>,
+    /// Fee paid
+    pub fee: Option<Fee>,
+    /// Gas consumed (EVM only)
+    pub gas_used: Option<u64>,
+    /// Price actually paid per unit of gas (EVM only)
+    pub effective_gas_price: Option<u128>,
+    /// Events (Substrate) or logs (EVM) emitted by the transaction
+    #[serde(default)]
+    pub events: Vec<TransactionEvent>,
+    /// Address of the created contract, for deployments
+    #[serde(default)]
+    pub contract_address: Option<String>,
+}
+
+impl Receipt {
+    /// Create an empty, successful receipt for `tx_hash`
+    pub fn new(tx_hash: impl Into<String>) -> Self {
+        Self {
+            tx_hash: tx_hash.into(),
+            block_number: None,
+            block_hash: None,
+            error: None,
+            fee: None,
+            gas_used: None,
+            effective_gas_price: None,
+            events: Vec::new(),
+            contract_address: None,
+        }
+    }
+
+    /// Set the block containing the transaction
+    pub fn with_block(mut self, number: u64, hash: impl Into<String>) -> Self {
+        self.block_number = Some(number);
+        self.block_hash = Some(hash.into());
+        self
+    }
+
+    /// Mark the transaction as failed with `error`
+    pub fn with_error(mut self, error: impl Into<String>) -> Self {
+        self.error = Some(error.into());
+        self
+    }
+
+    /// Set the fee paid, along with its gas used and gas price if known
+    pub fn with_fee(mut self, fee: Fee) -> Self {
+        self.gas_used = fee.gas_used.or(self.gas_used);
+        self.effective_gas_price = fee.gas_price.or(self.effective_gas_price);
+        self.fee = Some(fee);
+        self
+    }
+
+    /// Set the emitted events
+    pub fn with_events(mut self, events: Vec<TransactionEvent>) -> Self {
+        self.events = events;
+        self
+    }
+
+    /// Whether the transaction executed successfully
+    pub fn is_success(&self) -> bool {
+        self.error.is_none()
+    }
+
+    /// Substrate events emitted by `pallet` with name `variant`
+    pub fn find_events<'a>(
+        &'a self,
+        pallet: &'a str,
+        variant: &'a str,
+    ) -> impl Iterator<Item = &'a TransactionEvent> + 'a {
+        self.events.iter().filter(move |event| {
+            matches!(
+                event,
+                TransactionEvent::Substrate { pallet: p, variant: v, .. }
+                    if p.eq_ignore_ascii_case(pallet) && v == variant
+            )
+        })
+    }
+
+    /// EVM logs emitted by `address` (compared case-insensitively)
+    pub fn logs_from<'a>(
+        &'a self,
+        address: &'a str,
+    ) -> impl Iterator<Item = &'a TransactionEvent> + 'a {
+        self.events.iter().filter(move |event| {
+            matches!(
+                event,
+                TransactionEvent::EvmLog { address: a, .. } if a.eq_ignore_ascii_case(address)
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_receipt_events_and_serde() {
+        let receipt = Receipt::new("0x01")
+            .with_block(7, "0x02")
+            .with_events(vec![
+                TransactionEvent::Substrate {
+                    pallet: "Balances".into(),
+                    variant: "Transfer".into(),
+                    fields: "{ amount: 5 }".into(),
+                },
+                TransactionEvent::EvmLog {
+                    address: "0xAbC0000000000000000000000000000000000001".into(),
+                    topics: vec![],
+                    data: "0x".into(),
+                    log_index: Some(0),
+                },
+            ])
+            .with_fee(Fee::new(10));
+
+        assert_eq!(receipt.find_events("balances", "Transfer").count(), 1);
+        assert_eq!(receipt.find_events("Balances", "Deposit").count(), 0);
+        assert_eq!(
+            receipt
+                .logs_from("0xabc0000000000000000000000000000000000001")
+                .count(),
+            1
+        );
+        assert_eq!(receipt.gas_used, None);
+
+        let json = serde_json::to_string(&receipt).unwrap();
+        assert_eq!(serde_json::from_str::<Receipt>(&json).unwrap(), receipt);
+    }
+}
@@ -0,0 +1,389 @@
+//! Chains defined at runtime
+//!
+//! Chains beyond the built-in [`Chain`] variants are described by a
+//! [`ChainSpec`] and registered with [`register`], or loaded in bulk from a
+//! JSON [`ChainDescriptor`] file, so adding a parachain or L2 is a config
+//! change. Registered chains are [`Chain::Custom`] values that parse by name
+//! or alias, deserialize, and route like the built-in ones.
+//!
+//! ```json
+//! {
+//!   "version": 1,
+//!   "chains": [
+//!     {
+//!       "name": "Base",
+//!       "chain_type": "Evm",
+//!       "symbol": "ETH",
+//!       "decimals": 18,
+//!       "evm_chain_id": 8453,
+//!       "endpoints": ["https://mainnet.base.org"],
+//!       "bridges": [{ "to": "Ethereum", "provider": "Base Bridge" }]
+//!     }
+//!   ]
+//! }
+//! ```
+//!
+//! ```rust
+//! use apex_sdk_types::{registry, Chain, ChainSpec, ChainType};
+//!
+//! let chains = registry::load_str(r#"{
+//!     "version": 1,
+//!     "chains": [{
+//!         "name": "Hydration",
+//!         "aliases": ["hdx"],
+//!         "chain_type": "Substrate",
+//!         "symbol": "HDX",
+//!         "decimals": 12,
+//!         "ss58_prefix": 63,
+//!         "endpoints": ["wss://rpc.hydradx.cloud"]
+//!     }]
+//! }"#).unwrap();
+//!
+//! let hydration: Chain = "hdx".parse().unwrap();
+//! assert_eq!(hydration, chains[0]);
+//! assert_eq!(hydration.chain_type(), ChainType::Substrate);
+//! assert_eq!(hydration.native_decimals(), 12);
+//!
+//! // Or defined in code
+//! let appchain = ChainSpec::new("Test Appchain", ChainType::Evm, "APP", 18)
+//!     .with_evm_chain_id(424242)
+//!     .with_endpoint("https://rpc.appchain.example")
+//!     .register()
+//!     .unwrap();
+//! assert_eq!(Chain::from_evm_chain_id(424242), Some(appchain));
+//! ```
+
+use crate::{AccountMapping, Chain, ChainType};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Arc, OnceLock, RwLock};
+
+/// Current descriptor format version
+pub const DESCRIPTOR_VERSION: u32 = 1;
+
+/// A bridge from a chain to another
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct BridgeRoute {
+    /// Destination chain name
+    pub to: String,
+    /// Bridge provider name, as reported by the provider
+    pub provider: String,
+}
+
+/// Definition of a chain
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ChainSpec {
+    /// Display name, unique among chains
+    pub name: String,
+    /// Other names the chain parses from (e.g. its token symbol)
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    /// Kind of chain, deciding which adapter serves it
+    pub chain_type: ChainType,
+    /// Native token symbol
+    pub symbol: String,
+    /// Native token decimals
+    pub decimals: u8,
+    /// EIP-155 chain ID of an EVM or hybrid chain
+    #[serde(default)]
+    pub evm_chain_id: Option<u64>,
+    /// SS58 prefix of a chain with 32-byte Substrate accounts
+    #[serde(default)]
+    pub ss58_prefix: Option<u16>,
+    /// Genesis block hash of a Substrate or hybrid chain, 0x-prefixed hex
+    #[serde(default)]
+    pub genesis_hash: Option<String>,
+    /// How EVM and Substrate accounts relate on a hybrid chain
+    #[serde(default)]
+    pub account_mapping: Option<AccountMapping>,
+    /// RPC endpoints, preferred first
+    #[serde(default)]
+    pub endpoints: Vec<String>,
+    /// Bridges to other chains
+    #[serde(default)]
+    pub bridges: Vec<BridgeRoute>,
+}
+
+impl ChainSpec {
+    /// Define a chain with no chain ID, SS58 prefix, endpoints or bridges
+    pub fn new(
+        name: impl Into<String>,
+        chain_type: ChainType,
+        symbol: impl Into<String>,
+        decimals: u8,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            aliases: Vec::new(),
+            chain_type,
+            symbol: symbol.into(),
+            decimals,
+            evm_chain_id: None,
+            ss58_prefix: None,
+            genesis_hash: None,
+            account_mapping: None,
+            endpoints: Vec::new(),
+            bridges: Vec::new(),
+        }
+    }
+
+    /// Add a name the chain also parses from
+    pub fn with_alias(mut self, alias: impl Into<String>) -> Self {
+        self.aliases.push(alias.into());
+        self
+    }
+
+    /// Set the EIP-155 chain ID
+    pub fn with_evm_chain_id(mut self, chain_id: u64) -> Self {
+        self.evm_chain_id = Some(chain_id);
+        self
+    }
+
+    /// Set the SS58 prefix
+    pub fn with_ss58_prefix(mut self, prefix: u16) -> Self {
+        self.ss58_prefix = Some(prefix);
+        self
+    }
+
+    /// Set the genesis block hash
+    pub fn with_genesis_hash(mut self, hash: impl Into<String>) -> Self {
+        self.genesis_hash = Some(hash.into());
+        self
+    }
+
+    /// Set how EVM and Substrate accounts relate
+    pub fn with_account_mapping(mut self, mapping: AccountMapping) -> Self {
+        self.account_mapping = Some(mapping);
+        self
+    }
+
+    /// Add an RPC endpoint, after those already added
+    pub fn with_endpoint(mut self, url: impl Into<String>) -> Self {
+        self.endpoints.push(url.into());
+        self
+    }
+
+    /// Declare a bridge to the chain named `to`
+    pub fn with_bridge(mut self, to: impl Into<String>, provider: impl Into<String>) -> Self {
+        self.bridges.push(BridgeRoute {
+            to: to.into(),
+            provider: provider.into(),
+        });
+        self
+    }
+
+    /// [`register`] this chain
+    pub fn register(self) -> Result<Chain, String> {
+        register(self)
+    }
+
+    /// The bridge route to `destination`, if declared
+    pub fn bridge_to(&self, destination: &Chain) -> Option<&BridgeRoute> {
+        self.bridges
+            .iter()
+            .find(|route| normalize(&route.to) == normalize(destination.name()))
+    }
+}
+
+/// A file of chain definitions
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChainDescriptor {
+    /// Format version, see [`DESCRIPTOR_VERSION`]
+    pub version: u32,
+    /// Chains to register
+    pub chains: Vec<ChainSpec>,
+}
+
+fn registry() -> &'static RwLock<HashMap<String, Arc<ChainSpec>>> {
+    static REGISTRY: OnceLock<RwLock<HashMap<String, Arc<ChainSpec>>>> = OnceLock::new();
+    REGISTRY.get_or_init(Default::default)
+}
+
+/// Lookup key: lowercase without separators, as [`Chain`] names parse
+pub(crate) fn normalize(name: &str) -> String {
+    name.to_ascii_lowercase().replace(['-', '_', ' '], "")
+}
+
+/// Register a chain, returning it as a [`Chain::Custom`]
+///
+/// Registering a name again replaces its definition for later lookups.
+///
+/// # Errors
+///
+/// Fails if the name or an alias is empty or belongs to a built-in chain,
+/// or if an alias belongs to another registered chain.
+pub fn register(spec: ChainSpec) -> Result<Chain, String> {
+    let key = normalize(&spec.name);
+    let mut chains = registry().write().unwrap_or_else(|e| e.into_inner());
+
+    for name in std::iter::once(&spec.name).chain(&spec.aliases) {
+        let alias = normalize(name);
+        if alias.is_empty() {
+            return Err(format!("Empty name for chain {}", spec.name));
+        }
+        if Chain::builtin(&alias).is_some() {
+            return Err(format!("{} is a built-in chain", name));
+        }
+        if chains
+            .get(&alias)
+            .is_some_and(|other| normalize(&other.name) != key)
+        {
+            return Err(format!("{} is already registered", name));
+        }
+    }
+
+    // Drop the aliases of a previous definition
+    chains.retain(|_, other| normalize(&other.name) != key);
+    let spec = Arc::new(spec);
+    for name in std::iter::once(&spec.name).chain(&spec.aliases) {
+        chains.insert(normalize(name), spec.clone());
+    }
+    Ok(Chain::Custom(spec))
+}
+
+/// Registered chain with the given name or alias
+pub fn lookup(name: &str) -> Option<Chain> {
+    let chains = registry().read().unwrap_or_else(|e| e.into_inner());
+    chains.get(&normalize(name)).cloned().map(Chain::Custom)
+}
+
+/// All registered chains
+pub fn registered() -> Vec<Chain> {
+    let chains = registry().read().unwrap_or_else(|e| e.into_inner());
+    let mut specs: Vec<_> = chains.values().cloned().collect();
+    specs.sort_by(|a, b| a.name.cmp(&b.name));
+    specs.dedup_by(|a, b| Arc::ptr_eq(a, b));
+    specs.into_iter().map(Chain::Custom).collect()
+}
+
+/// Register every chain in a JSON [`ChainDescriptor`]
+///
+/// Chains are registered in order; on error, those before the failing one
+/// stay registered.
+pub fn load_str(json: &str) -> Result<Vec<Chain>, String> {
+    let descriptor: ChainDescriptor =
+        serde_json::from_str(json).map_err(|e| format!("Invalid chain descriptor: {}", e))?;
+    if descriptor.version != DESCRIPTOR_VERSION {
+        return Err(format!(
+            "Unsupported chain descriptor version {} (expected {})",
+            descriptor.version, DESCRIPTOR_VERSION
+        ));
+    }
+    descriptor.chains.into_iter().map(register).collect()
+}
+
+/// Register every chain in the JSON [`ChainDescriptor`] file at `path`
+pub fn load_file(path: impl AsRef<Path>) -> Result<Vec<Chain>, String> {
+    let path = path.as_ref();
+    let json = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    load_str(&json)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ConfirmationPolicy;
+
+    fn spec(name: &str) -> ChainSpec {
+        ChainSpec::new(name, ChainType::Evm, "ETH", 18)
+    }
+
+    #[test]
+    fn test_load_descriptor() {
+        let chains = load_str(
+            r#"{
+                "version": 1,
+                "chains": [{
+                    "name": "Test Base",
+                    "aliases": ["testbase"],
+                    "chain_type": "Evm",
+                    "symbol": "ETH",
+                    "decimals": 18,
+                    "evm_chain_id": 984530,
+                    "endpoints": ["https://base.example"],
+                    "bridges": [{ "to": "ethereum", "provider": "Canonical" }]
+                }]
+            }"#,
+        )
+        .unwrap();
+        let base = &chains[0];
+
+        assert_eq!(base.name(), "Test Base");
+        assert_eq!("test-base".parse::<Chain>().as_ref(), Ok(base));
+        assert_eq!(Chain::from_evm_chain_id(984530).as_ref(), Some(base));
+        assert_eq!(base.chain_type(), ChainType::Evm);
+        assert_eq!(
+            ConfirmationPolicy::for_chain(base),
+            ConfirmationPolicy::Blocks(12)
+        );
+        assert!(registered().contains(base));
+
+        let Chain::Custom(spec) = base else {
+            panic!("not a custom chain")
+        };
+        assert_eq!(spec.endpoints, ["https://base.example"]);
+        assert_eq!(
+            spec.bridge_to(&Chain::Ethereum).unwrap().provider,
+            "Canonical"
+        );
+        assert!(spec.bridge_to(&Chain::Polkadot).is_none());
+
+        // Serialized by name, deserialized from the registry
+        let json = serde_json::to_string(base).unwrap();
+        assert_eq!(json, r#"{"Custom":"Test Base"}"#);
+        assert_eq!(&serde_json::from_str::<Chain>(&json).unwrap(), base);
+        assert_eq!(
+            serde_json::to_string(&Chain::Kusama).unwrap(),
+            r#""Kusama""#
+        );
+    }
+
+    #[test]
+    fn test_register_conflicts() {
+        assert!(register(spec("Polkadot")).is_err());
+        assert!(register(spec("")).is_err());
+
+        register(spec("Test Conflict").with_alias("tc")).unwrap();
+        assert!(register(spec("Test Other").with_alias("tc")).is_err());
+
+        // Replacing a definition drops its old aliases
+        register(spec("Test Conflict")).unwrap();
+        assert!(lookup("tc").is_none());
+        assert!(lookup("test conflict").is_some());
+        assert!(serde_json::from_str::<Chain>(r#"{"Custom":"Unregistered"}"#).is_err());
+    }
+
+    #[test]
+    fn test_custom_account_mapping() {
+        let chains = load_str(
+            r#"{
+                "version": 1,
+                "chains": [{
+                    "name": "Test Frontier",
+                    "chain_type": "Hybrid",
+                    "symbol": "TFR",
+                    "decimals": 18,
+                    "evm_chain_id": 984531,
+                    "ss58_prefix": 42,
+                    "account_mapping": "Hashed"
+                }]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(chains[0].account_mapping(), Some(AccountMapping::Hashed));
+
+        let evm = crate::Address::evm("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed");
+        let native = evm.to_substrate_on(&chains[0]).unwrap();
+        assert_eq!(native.ss58_prefix(), Some(42));
+        assert_eq!(spec("Test Unmapped").account_mapping, None);
+    }
+
+    #[test]
+    fn test_load_rejects_bad_descriptors() {
+        assert!(load_str(r#"{"version": 2, "chains": []}"#).is_err());
+        assert!(load_str(r#"{"version": 1, "chains": [{"name": "x"}]}"#).is_err());
+        assert!(load_file("/nonexistent/chains.json").is_err());
+    }
+}
@@ -0,0 +1,154 @@
+//! SS58 addresses
+//!
+//! A Substrate address is a network prefix, the account's 32-byte public key
+//! and a two-byte checksum, base58 encoded. These helpers encode, decode and
+//! re-prefix addresses without pulling in `sp-core`.
+//!
+//! ```rust
+//! use apex_sdk_types::ss58;
+//!
+//! let alice = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+//! let (prefix, public_key) = ss58::decode(alice).unwrap();
+//! assert_eq!(prefix, ss58::SUBSTRATE);
+//!
+//! let polkadot = ss58::reencode(alice, ss58::POLKADOT).unwrap();
+//! assert_eq!(polkadot, "15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5");
+//! assert_eq!(ss58::encode(ss58::SUBSTRATE, &public_key).unwrap(), alice);
+//! ```
+
+use blake2::{Blake2b512, Digest};
+
+/// Polkadot relay chain prefix
+pub const POLKADOT: u16 = 0;
+/// Kusama relay chain prefix
+pub const KUSAMA: u16 = 2;
+/// Astar prefix
+pub const ASTAR: u16 = 5;
+/// Generic Substrate prefix, used by dev chains
+pub const SUBSTRATE: u16 = 42;
+
+/// Largest prefix that fits the two-byte encoding
+pub const MAX_PREFIX: u16 = 16_383;
+
+const CHECKSUM_LEN: usize = 2;
+
+/// Encode a 32-byte public key as an SS58 address with the given prefix
+pub fn encode(prefix: u16, public_key: &[u8; 32]) -> Result<String, String> {
+    let mut bytes = match prefix {
+        0..=63 => vec![prefix as u8],
+        64..=MAX_PREFIX => vec![
+            ((prefix & 0b1111_1100) >> 2) as u8 | 0b0100_0000,
+            (prefix >> 8) as u8 | ((prefix & 0b11) << 6) as u8,
+        ],
+        _ => return Err(format!("Invalid SS58 prefix: {}", prefix)),
+    };
+    bytes.extend_from_slice(public_key);
+    let checksum = checksum(&bytes);
+    bytes.extend_from_slice(&checksum[..CHECKSUM_LEN]);
+    Ok(bs58::encode(bytes).into_string())
+}
+
+/// Decode an SS58 address into its prefix and 32-byte public key,
+/// verifying the checksum
+pub fn decode(address: &str) -> Result<(u16, [u8; 32]), String> {
+    let bytes = bs58::decode(address)
+        .into_vec()
+        .map_err(|_| format!("Invalid SS58 address: {}", address))?;
+
+    let (prefix_len, prefix) = match bytes.first() {
+        Some(first @ 0..=63) => (1, *first as u16),
+        Some(64..=127) if bytes.len() > 1 => {
+            let lower = (bytes[0] << 2) | (bytes[1] >> 6);
+            let upper = bytes[1] & 0b0011_1111;
+            (2, lower as u16 | (upper as u16) << 8)
+        }
+        _ => return Err(format!("Invalid SS58 prefix in address: {}", address)),
+    };
+    // Prefixes below 64 have a one-byte encoding only
+    if prefix_len == 2 && prefix < 64 {
+        return Err(format!("Invalid SS58 prefix in address: {}", address));
+    }
+    if bytes.len() != prefix_len + 32 + CHECKSUM_LEN {
+        return Err(format!(
+            "SS58 address {} does not hold a 32-byte public key",
+            address
+        ));
+    }
+
+    let (body, checksum_bytes) = bytes.split_at(prefix_len + 32);
+    if checksum(body)[..CHECKSUM_LEN] != *checksum_bytes {
+        return Err(format!("Invalid SS58 checksum in address: {}", address));
+    }
+
+    let mut public_key = [0u8; 32];
+    public_key.copy_from_slice(&body[prefix_len..]);
+    Ok((prefix, public_key))
+}
+
+/// The 32-byte public key behind an SS58 address
+pub fn public_key(address: &str) -> Result<[u8; 32], String> {
+    decode(address).map(|(_, public_key)| public_key)
+}
+
+/// Re-encode an SS58 address under another network prefix
+pub fn reencode(address: &str, prefix: u16) -> Result<String, String> {
+    encode(prefix, &public_key(address)?)
+}
+
+fn checksum(body: &[u8]) -> [u8; 64] {
+    let mut hasher = Blake2b512::new();
+    hasher.update(b"SS58PRE");
+    hasher.update(body);
+    hasher.finalize().into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    const ALICE: &str = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+
+    #[test]
+    fn test_decode() {
+        let (prefix, public_key) = decode(ALICE).unwrap();
+        assert_eq!(prefix, SUBSTRATE);
+        assert_eq!(
+            hex::encode(public_key),
+            "d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d"
+        );
+        assert_eq!(
+            reencode(ALICE, KUSAMA).unwrap(),
+            "HNZata7iMYWmk5RvZRTiAsSDhV8366zq2YGb3tLH5Upf74F"
+        );
+
+        // Two-byte prefix
+        let moonbeam = encode(1284, &public_key).unwrap();
+        assert_eq!(decode(&moonbeam).unwrap(), (1284, public_key));
+    }
+
+    #[test]
+    fn test_decode_rejects_malformed() {
+        // Last character changed, breaking the checksum
+        assert!(decode("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQZ").is_err());
+        assert!(decode("0x1234").is_err());
+        assert!(decode("").is_err());
+        assert!(decode("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHG").is_err());
+        assert!(encode(MAX_PREFIX + 1, &[0; 32]).is_err());
+    }
+
+    proptest! {
+        #[test]
+        fn prop_roundtrip(prefix in 0..=MAX_PREFIX, public_key in any::<[u8; 32]>()) {
+            let address = encode(prefix, &public_key).unwrap();
+            prop_assert_eq!(decode(&address), Ok((prefix, public_key)));
+        }
+
+        #[test]
+        fn prop_decode_is_canonical(address in "[1-9A-HJ-NP-Za-km-z]{0,60}|\\PC*") {
+            if let Ok((prefix, public_key)) = decode(&address) {
+                prop_assert_eq!(encode(prefix, &public_key), Ok(address));
+            }
+        }
+    }
+}
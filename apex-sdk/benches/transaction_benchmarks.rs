@@ -16,7 +16,7 @@ fn bench_evm_to_evm_transaction(c: &mut Criterion) {
     c.bench_function("build_evm_to_evm_transaction", |b| {
         b.iter(|| {
             TransactionBuilder::new()
-                .from_evm_address(black_box("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb7"))
+                .from_evm_address(black_box("0x742D35Cc6634C0532925A3B844bC9e7595f0bEB7"))
                 .to_evm_address(black_box("0x1234567890123456789012345678901234567890"))
                 .amount(black_box(1000))
                 .build()
@@ -51,7 +51,7 @@ fn bench_cross_chain_transaction(c: &mut Criterion) {
                 .from_substrate_account(black_box(
                     "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY",
                 ))
-                .to_evm_address(black_box("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb7"))
+                .to_evm_address(black_box("0x742D35Cc6634C0532925A3B844bC9e7595f0bEB7"))
                 .amount(black_box(1000))
                 .build()
                 .unwrap()
@@ -68,7 +68,7 @@ fn bench_transaction_with_data(c: &mut Criterion) {
         group.bench_with_input(BenchmarkId::from_parameter(size), size, |b, _| {
             b.iter(|| {
                 TransactionBuilder::new()
-                    .from_evm_address(black_box("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb7"))
+                    .from_evm_address(black_box("0x742D35Cc6634C0532925A3B844bC9e7595f0bEB7"))
                     .to_evm_address(black_box("0x1234567890123456789012345678901234567890"))
                     .amount(black_box(1000))
                     .with_data(black_box(data.clone()))
@@ -84,7 +84,7 @@ fn bench_transaction_with_data(c: &mut Criterion) {
 /// Benchmark transaction hash computation
 fn bench_transaction_hash(c: &mut Criterion) {
     let tx = TransactionBuilder::new()
-        .from_evm_address("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb7")
+        .from_evm_address("0x742D35Cc6634C0532925A3B844bC9e7595f0bEB7")
         .to_evm_address("0x1234567890123456789012345678901234567890")
         .amount(1000)
         .build()
@@ -96,7 +96,7 @@ fn bench_transaction_hash(c: &mut Criterion) {
 /// Benchmark is_cross_chain check
 fn bench_is_cross_chain(c: &mut Criterion) {
     let tx_same_chain = TransactionBuilder::new()
-        .from_evm_address("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb7")
+        .from_evm_address("0x742D35Cc6634C0532925A3B844bC9e7595f0bEB7")
         .to_evm_address("0x1234567890123456789012345678901234567890")
         .amount(1000)
         .build()
@@ -104,7 +104,7 @@ fn bench_is_cross_chain(c: &mut Criterion) {
 
     let tx_cross_chain = TransactionBuilder::new()
         .from_substrate_account("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY")
-        .to_evm_address("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb7")
+        .to_evm_address("0x742D35Cc6634C0532925A3B844bC9e7595f0bEB7")
         .amount(1000)
         .build()
         .unwrap();
@@ -121,7 +121,7 @@ fn bench_is_cross_chain(c: &mut Criterion) {
 /// Benchmark transaction serialization
 fn bench_transaction_serialization(c: &mut Criterion) {
     let tx = TransactionBuilder::new()
-        .from_evm_address("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb7")
+        .from_evm_address("0x742D35Cc6634C0532925A3B844bC9e7595f0bEB7")
         .to_evm_address("0x1234567890123456789012345678901234567890")
         .amount(1000)
         .with_data(vec![1, 2, 3, 4, 5, 6, 7, 8])
@@ -147,7 +147,7 @@ fn bench_transaction_amounts(c: &mut Criterion) {
         group.bench_with_input(BenchmarkId::from_parameter(amount), amount, |b, &amt| {
             b.iter(|| {
                 TransactionBuilder::new()
-                    .from_evm_address(black_box("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb7"))
+                    .from_evm_address(black_box("0x742D35Cc6634C0532925A3B844bC9e7595f0bEB7"))
                     .to_evm_address(black_box("0x1234567890123456789012345678901234567890"))
                     .amount(black_box(amt))
                     .build()
@@ -0,0 +1,330 @@
+//! Address book and look-alike (address poisoning) detection
+//!
+//! Address poisoning attacks send dust from an address that shares the first
+//! and last characters of one the victim uses, hoping it gets copied from the
+//! transaction history. [`LookalikePolicy`] flags destinations that are not
+//! known but match a known address (from the [`AddressBook`] or recent
+//! destinations) at both ends.
+//!
+//! # Example
+//!
+//! ```rust
+//! use apex_sdk::address_book::{AddressBook, LookalikePolicy};
+//! use apex_sdk::PolicyEngine;
+//! use std::sync::Arc;
+//!
+//! let book = Arc::new(AddressBook::new());
+//! book.add("0x742D35Cc6634C0532925A3B844bC9e7595f0bEB7", "treasury");
+//!
+//! let engine = PolicyEngine::new().with_policy(LookalikePolicy::new(book));
+//! ```
+
+use crate::error::Result;
+use crate::policy::{account_key, normalize, Policy, PolicyDecision};
+use crate::transaction::Transaction;
+use async_trait::async_trait;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex, RwLock};
+
+/// Known address with an optional label
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AddressEntry {
+    /// The address as added
+    pub address: String,
+    /// Human-readable label
+    pub label: Option<String>,
+}
+
+impl std::fmt::Display for AddressEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.label {
+            Some(label) => write!(f, "{} ({})", self.address, label),
+            None => write!(f, "{}", self.address),
+        }
+    }
+}
+
+/// Trusted addresses
+#[derive(Default)]
+pub struct AddressBook {
+    entries: RwLock<HashMap<String, AddressEntry>>,
+}
+
+impl AddressBook {
+    /// Create an empty address book
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add or relabel an address
+    pub fn add(&self, address: &str, label: impl Into<String>) {
+        self.insert(address, Some(label.into()));
+    }
+
+    /// Add an address without a label
+    pub fn add_unlabeled(&self, address: &str) {
+        self.insert(address, None);
+    }
+
+    fn insert(&self, address: &str, label: Option<String>) {
+        self.entries.write().unwrap().insert(
+            account_key(address),
+            AddressEntry {
+                address: address.to_string(),
+                label,
+            },
+        );
+    }
+
+    /// Remove an address
+    pub fn remove(&self, address: &str) -> Option<AddressEntry> {
+        self.entries.write().unwrap().remove(&account_key(address))
+    }
+
+    /// Look up an address
+    pub fn get(&self, address: &str) -> Option<AddressEntry> {
+        self.entries
+            .read()
+            .unwrap()
+            .get(&account_key(address))
+            .cloned()
+    }
+
+    /// Whether an address is in the book
+    pub fn contains(&self, address: &str) -> bool {
+        self.entries
+            .read()
+            .unwrap()
+            .contains_key(&account_key(address))
+    }
+
+    /// All entries
+    pub fn entries(&self) -> Vec<AddressEntry> {
+        self.entries.read().unwrap().values().cloned().collect()
+    }
+
+    /// Number of entries
+    pub fn len(&self) -> usize {
+        self.entries.read().unwrap().len()
+    }
+
+    /// Whether the book is empty
+    pub fn is_empty(&self) -> bool {
+        self.entries.read().unwrap().is_empty()
+    }
+}
+
+/// Whether `a` and `b` differ but share `prefix` leading and `suffix`
+/// trailing characters (ignoring `0x` and case)
+pub fn is_lookalike(a: &str, b: &str, prefix: usize, suffix: usize) -> bool {
+    let a = normalize(a);
+    let b = normalize(b);
+    let a = a.strip_prefix("0x").unwrap_or(&a).as_bytes();
+    let b = b.strip_prefix("0x").unwrap_or(&b).as_bytes();
+
+    if a == b || a.len() != b.len() || a.len() < prefix + suffix {
+        return false;
+    }
+    a[..prefix] == b[..prefix] && a[a.len() - suffix..] == b[b.len() - suffix..]
+}
+
+/// What to do when a look-alike destination is found
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LookalikeAction {
+    /// Deny the transaction
+    Block,
+    /// Require approval through the engine's approver
+    RequireConfirmation,
+}
+
+/// Flags destinations that resemble, but are not, a known address
+///
+/// Known addresses are the [`AddressBook`] plus destinations of recently
+/// executed transactions. By default the first and last 4 characters are
+/// compared (what most wallets show when truncating) and matches are blocked.
+pub struct LookalikePolicy {
+    book: Arc<AddressBook>,
+    recent: Mutex<VecDeque<String>>,
+    history_size: usize,
+    prefix: usize,
+    suffix: usize,
+    action: LookalikeAction,
+}
+
+impl LookalikePolicy {
+    /// Check destinations against `book`
+    pub fn new(book: Arc<AddressBook>) -> Self {
+        Self {
+            book,
+            recent: Mutex::new(VecDeque::new()),
+            history_size: 256,
+            prefix: 4,
+            suffix: 4,
+            action: LookalikeAction::Block,
+        }
+    }
+
+    /// Number of leading and trailing characters that must match
+    pub fn with_match_chars(mut self, prefix: usize, suffix: usize) -> Self {
+        self.prefix = prefix;
+        self.suffix = suffix;
+        self
+    }
+
+    /// Number of recent destinations to remember (0 disables history)
+    pub fn with_history_size(mut self, size: usize) -> Self {
+        self.history_size = size;
+        self
+    }
+
+    /// Action taken on a match
+    pub fn with_action(mut self, action: LookalikeAction) -> Self {
+        self.action = action;
+        self
+    }
+
+    /// Known addresses that `address` resembles
+    pub fn lookalikes(&self, address: &str) -> Vec<AddressEntry> {
+        if self.book.contains(address) || self.is_recent(address) {
+            return Vec::new();
+        }
+
+        let mut matches: Vec<AddressEntry> = self
+            .book
+            .entries()
+            .into_iter()
+            .filter(|entry| is_lookalike(address, &entry.address, self.prefix, self.suffix))
+            .collect();
+
+        for recent in self.recent.lock().unwrap().iter() {
+            if is_lookalike(address, recent, self.prefix, self.suffix)
+                && !matches.iter().any(|m| normalize(&m.address) == *recent)
+            {
+                matches.push(AddressEntry {
+                    address: recent.clone(),
+                    label: Some("recent destination".to_string()),
+                });
+            }
+        }
+        matches
+    }
+
+    fn is_recent(&self, address: &str) -> bool {
+        let address = normalize(address);
+        self.recent.lock().unwrap().contains(&address)
+    }
+}
+
+#[async_trait]
+impl Policy for LookalikePolicy {
+    fn name(&self) -> &str {
+        "lookalike"
+    }
+
+    async fn evaluate(&self, tx: &Transaction) -> Result<PolicyDecision> {
+        let matches = self.lookalikes(tx.to.as_str());
+        if matches.is_empty() {
+            return Ok(PolicyDecision::Allow);
+        }
+
+        let known = matches
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", ");
+        let reason = format!(
+            "{} resembles known address {}; possible address poisoning",
+            tx.to.as_str(),
+            known
+        );
+        tracing::warn!("{}", reason);
+
+        Ok(match self.action {
+            LookalikeAction::Block => PolicyDecision::Deny(reason),
+            LookalikeAction::RequireConfirmation => PolicyDecision::RequireApproval(reason),
+        })
+    }
+
+    async fn on_executed(&self, tx: &Transaction) {
+        if self.history_size == 0 {
+            return;
+        }
+        let to = normalize(tx.to.as_str());
+        let mut recent = self.recent.lock().unwrap();
+        recent.retain(|address| *address != to);
+        recent.push_back(to);
+        while recent.len() > self.history_size {
+            recent.pop_front();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transaction::TransactionBuilder;
+
+    const TREASURY: &str = "0x742D35Cc6634C0532925A3B844bC9e7595f0bEB7";
+    const POISONED: &str = "0x742d0000000000000000000000000000000fbeb7";
+
+    fn tx(to: &str) -> Transaction {
+        TransactionBuilder::new()
+            .from_evm_address("0x1234567890123456789012345678901234567890")
+            .to_evm_address(to)
+            .amount(1)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_is_lookalike() {
+        assert!(is_lookalike(TREASURY, POISONED, 4, 4));
+        assert!(!is_lookalike(TREASURY, &TREASURY.to_lowercase(), 4, 4));
+        assert!(!is_lookalike(TREASURY, POISONED, 6, 4));
+        assert!(is_lookalike(
+            "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY",
+            "5GrwXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXutQY",
+            4,
+            4
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_blocks_lookalike_of_book_entry() {
+        let book = Arc::new(AddressBook::new());
+        book.add(TREASURY, "treasury");
+        let policy = LookalikePolicy::new(book.clone());
+
+        assert_eq!(
+            policy.evaluate(&tx(TREASURY)).await.unwrap(),
+            PolicyDecision::Allow
+        );
+        match policy.evaluate(&tx(POISONED)).await.unwrap() {
+            PolicyDecision::Deny(reason) => assert!(reason.contains("treasury")),
+            other => panic!("expected deny, got {:?}", other),
+        }
+
+        // Explicitly trusting the address clears the warning
+        book.add_unlabeled(POISONED);
+        assert_eq!(
+            policy.evaluate(&tx(POISONED)).await.unwrap(),
+            PolicyDecision::Allow
+        );
+    }
+
+    #[tokio::test]
+    async fn test_recent_history_and_confirmation() {
+        let policy = LookalikePolicy::new(Arc::new(AddressBook::new()))
+            .with_action(LookalikeAction::RequireConfirmation);
+
+        assert_eq!(
+            policy.evaluate(&tx(POISONED)).await.unwrap(),
+            PolicyDecision::Allow
+        );
+        policy.on_executed(&tx(TREASURY)).await;
+        assert!(matches!(
+            policy.evaluate(&tx(POISONED)).await.unwrap(),
+            PolicyDecision::RequireApproval(_)
+        ));
+    }
+}
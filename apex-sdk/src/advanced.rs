@@ -0,0 +1,1292 @@
+//! Event and block subscriptions fed by live chain adapters
+//!
+//! An [`EventSubscription`] broadcasts the events matching its
+//! [`EventFilter`] to any number of receivers. The [`EventBridge`] follows
+//! the configured adapters (finalized Substrate blocks, new EVM blocks,
+//! pushed over WebSocket when available) and emits every observed event into
+//! the registered subscriptions, so applications only subscribe and receive.
+//! [`EventSubscription::backfill`]
+//! replays past events from the same adapters before continuing live.
+//!
+//! Likewise, the [`BlockMonitor`] follows new or finalized heads on every
+//! adapter and publishes a [`BlockInfo`], tagged with its chain type, to
+//! each [`BlockSubscription`].
+//!
+//! Each event receiver has a bounded queue. When a slow consumer fills it,
+//! the subscription's [`LagStrategy`] either drops the oldest events and
+//! reports the gap as [`EventItem::Lagged`], blocks the producer, or spills
+//! the overflow to disk; [`SubscriptionMetrics`] counts what happened.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use apex_sdk::advanced::EventItem;
+//! use apex_sdk::prelude::*;
+//! use apex_sdk::types::EventFilter;
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<()> {
+//! let sdk = ApexSDK::builder()
+//!     .with_evm_endpoint("https://eth.llamarpc.com")
+//!     .build()
+//!     .await?;
+//!
+//! let transfers = sdk.subscribe_events(EventFilter {
+//!     event_names: Some(vec![
+//!         "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef".to_string(),
+//!     ]),
+//!     ..Default::default()
+//! });
+//! let mut receiver = transfers.subscribe();
+//! while let Some(item) = receiver.recv().await {
+//!     match item {
+//!         EventItem::Event(event) => {
+//!             println!("{} in block {}", event.name(), event.block_number)
+//!         }
+//!         EventItem::Lagged(missed) => eprintln!("Missed {} transfers", missed),
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::checkpoint::{Checkpoint, Checkpointer};
+use crate::error::{Error, Result};
+use apex_sdk_evm::EvmAdapter;
+use apex_sdk_substrate::SubstrateAdapter;
+use apex_sdk_types::{BlockInfo, ChainType, Event, EventFilter, Finality};
+use async_trait::async_trait;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fs::File;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, RwLock, Weak};
+use std::time::{Duration, Instant};
+use tokio::sync::{broadcast, mpsc, Notify};
+use tokio::task::JoinHandle;
+
+/// Events queued in memory per subscription receiver
+const SUBSCRIPTION_CAPACITY: usize = 1024;
+
+/// Events buffered between a chain follower and the dispatcher
+const SOURCE_CAPACITY: usize = 1024;
+
+/// Delay before restarting a chain follower that failed
+const RESTART_DELAY: Duration = Duration::from_secs(5);
+
+/// Default interval between EVM block polls
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Most blocks fetched in a single `eth_getLogs` request while backfilling
+const HISTORY_LOG_RANGE: u64 = 1_000;
+
+/// Source of past events for [`EventSubscription::backfill`]
+#[async_trait]
+pub trait EventHistory: Send + Sync {
+    /// Kind of chain the events come from
+    fn chain_type(&self) -> ChainType;
+
+    /// Number of the newest block whose events are available
+    async fn latest_block(&self) -> Result<u64>;
+
+    /// Events emitted in blocks `from_block..=to_block`, in chain order
+    async fn events(&self, from_block: u64, to_block: u64) -> Result<Vec<Event>>;
+}
+
+#[async_trait]
+impl EventHistory for EvmAdapter {
+    fn chain_type(&self) -> ChainType {
+        ChainType::Evm
+    }
+
+    async fn latest_block(&self) -> Result<u64> {
+        Ok(self.block_number().await?)
+    }
+
+    async fn events(&self, from_block: u64, to_block: u64) -> Result<Vec<Event>> {
+        let mut events = Vec::new();
+        let mut from = from_block;
+        while from <= to_block {
+            let to = to_block.min(from.saturating_add(HISTORY_LOG_RANGE - 1));
+            events.extend(self.get_logs(from, to).await?);
+            from = to + 1;
+        }
+        Ok(events)
+    }
+}
+
+#[async_trait]
+impl EventHistory for SubstrateAdapter {
+    fn chain_type(&self) -> ChainType {
+        ChainType::Substrate
+    }
+
+    async fn latest_block(&self) -> Result<u64> {
+        Ok(self.finalized_block_number().await?)
+    }
+
+    async fn events(&self, from_block: u64, to_block: u64) -> Result<Vec<Event>> {
+        Ok(self.scan_events(from_block, to_block).await?)
+    }
+}
+
+/// What happens when a receiver falls a full queue behind its subscription
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum LagStrategy {
+    /// Drop the oldest queued events and report how many with [`EventItem::Lagged`]
+    #[default]
+    Notify,
+    /// Make the producer wait until the receiver catches up
+    ///
+    /// This slows down the chain follower, and with it every other
+    /// subscription fed by it.
+    Block,
+    /// Queue the overflow in a temporary file in the given directory
+    Spill(PathBuf),
+}
+
+/// Item received from an [`EventSubscription`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EventItem {
+    /// A matching event
+    Event(Event),
+    /// This many events were dropped because the receiver lagged behind
+    Lagged(u64),
+}
+
+/// Delivery counters for an [`EventSubscription`]
+#[derive(Debug, Default)]
+pub struct SubscriptionMetrics {
+    /// Events queued for receivers
+    pub delivered: AtomicU64,
+    /// Events dropped for lagging receivers
+    pub dropped: AtomicU64,
+    /// Events spilled to disk for lagging receivers
+    pub spilled: AtomicU64,
+    /// Total time the producer waited for lagging receivers, in milliseconds
+    pub blocked_ms: AtomicU64,
+}
+
+/// Delivers events matching a filter to any number of receivers
+///
+/// Each receiver has its own queue of up to
+/// [`capacity`](Self::with_capacity) events; the [`LagStrategy`] decides
+/// what happens when it is full.
+pub struct EventSubscription {
+    filter: EventFilter,
+    capacity: usize,
+    lag_strategy: LagStrategy,
+    inboxes: Mutex<Vec<Weak<Inbox>>>,
+    metrics: SubscriptionMetrics,
+    history: Vec<Arc<dyn EventHistory>>,
+}
+
+impl EventSubscription {
+    /// Create a subscription for events matching `filter`
+    pub fn new(filter: EventFilter) -> Self {
+        Self {
+            filter,
+            capacity: SUBSCRIPTION_CAPACITY,
+            lag_strategy: LagStrategy::default(),
+            inboxes: Mutex::new(Vec::new()),
+            metrics: SubscriptionMetrics::default(),
+            history: Vec::new(),
+        }
+    }
+
+    /// Set how many events each receiver queues in memory
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity.max(1);
+        self
+    }
+
+    /// Set what happens when a receiver's queue is full
+    pub fn with_lag_strategy(mut self, strategy: LagStrategy) -> Self {
+        self.lag_strategy = strategy;
+        self
+    }
+
+    /// Add a source of past events for [`backfill`](Self::backfill)
+    pub fn with_history(mut self, source: Arc<dyn EventHistory>) -> Self {
+        self.history.push(source);
+        self
+    }
+
+    /// The subscription's filter
+    pub fn filter(&self) -> &EventFilter {
+        &self.filter
+    }
+
+    /// The subscription's lag strategy
+    pub fn lag_strategy(&self) -> &LagStrategy {
+        &self.lag_strategy
+    }
+
+    /// Delivery counters
+    pub fn metrics(&self) -> &SubscriptionMetrics {
+        &self.metrics
+    }
+
+    /// Receive matching events emitted from now on
+    pub fn subscribe(&self) -> EventReceiver {
+        let inbox = Arc::new(Inbox::default());
+        self.inboxes
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(Arc::downgrade(&inbox));
+        EventReceiver { inbox }
+    }
+
+    /// Number of live receivers
+    pub fn receiver_count(&self) -> usize {
+        self.inboxes
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .filter(|inbox| inbox.strong_count() > 0)
+            .count()
+    }
+
+    /// Replay past events in `from_block..=to_block`, then continue live
+    ///
+    /// Live events are captured from the moment of the call and delivered
+    /// after the replayed ones, skipping any already replayed, so the stream
+    /// has neither gaps nor duplicates. The block range applies to every
+    /// history source.
+    pub async fn backfill(&self, from_block: u64, to_block: u64) -> Result<EventReplay> {
+        self.replay(|_| async move { Ok(Some((from_block, to_block, None))) })
+            .await
+    }
+
+    /// Continue from `checkpointer`'s last committed events, then live
+    ///
+    /// For every history source with a checkpoint, replays the matching
+    /// events after it up to the source's latest block; sources without one
+    /// start live. Events at or before a checkpoint are never delivered.
+    pub async fn resume(&self, checkpointer: &Checkpointer) -> Result<EventReplay> {
+        self.replay(|source| async move {
+            let Some(checkpoint) = checkpointer.load(&source.chain_type()).await? else {
+                return Ok(None);
+            };
+            let latest = source.latest_block().await?;
+            Ok(Some((checkpoint.block_number, latest, Some(checkpoint))))
+        })
+        .await
+    }
+
+    /// Subscribe, then replay the range `plan` picks for each history source
+    async fn replay<F, Fut>(&self, plan: F) -> Result<EventReplay>
+    where
+        F: Fn(Arc<dyn EventHistory>) -> Fut,
+        Fut: std::future::Future<Output = Result<Option<(u64, u64, Option<Checkpoint>)>>>,
+    {
+        if self.history.is_empty() {
+            return Err(Error::Config(
+                "No event history source attached to subscription".to_string(),
+            ));
+        }
+
+        let live = self.subscribe();
+        let mut history = VecDeque::new();
+        let mut resumed = HashMap::new();
+        for source in &self.history {
+            let Some((from_block, to_block, checkpoint)) = plan(source.clone()).await? else {
+                continue;
+            };
+            let events = source.events(from_block, to_block).await?;
+            history.extend(
+                events
+                    .into_iter()
+                    .filter(|e| self.matches_filter(e) && checkpoint.is_none_or(|c| c.precedes(e))),
+            );
+            if let Some(checkpoint) = checkpoint {
+                resumed.insert(source.chain_type(), checkpoint);
+            }
+        }
+        let replayed = history.iter().map(replay_key).collect();
+
+        Ok(EventReplay {
+            history,
+            replayed,
+            resumed,
+            live,
+        })
+    }
+
+    /// Deliver `event` to receivers if it matches the filter
+    ///
+    /// With [`LagStrategy::Block`] this waits for lagging receivers. Returns
+    /// whether the event matched.
+    pub async fn emit(&self, event: Event) -> bool {
+        if !self.matches_filter(&event) {
+            return false;
+        }
+
+        let inboxes: Vec<Arc<Inbox>> = {
+            let mut inboxes = self.inboxes.lock().unwrap_or_else(|e| e.into_inner());
+            inboxes.retain(|inbox| inbox.strong_count() > 0);
+            inboxes.iter().filter_map(Weak::upgrade).collect()
+        };
+        for inbox in inboxes {
+            if self.lag_strategy == LagStrategy::Block {
+                let started = Instant::now();
+                inbox.wait_for_space(self.capacity).await;
+                self.metrics
+                    .blocked_ms
+                    .fetch_add(started.elapsed().as_millis() as u64, Ordering::Relaxed);
+            }
+            inbox.push(
+                event.clone(),
+                self.capacity,
+                &self.lag_strategy,
+                &self.metrics,
+            );
+        }
+        true
+    }
+
+    /// Whether `event` matches the subscription's filter
+    ///
+    /// See [`EventFilter::matches`].
+    pub fn matches_filter(&self, event: &Event) -> bool {
+        self.filter.matches(event)
+    }
+}
+
+impl Drop for EventSubscription {
+    fn drop(&mut self) {
+        for inbox in self
+            .inboxes
+            .get_mut()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .filter_map(Weak::upgrade)
+        {
+            inbox.close();
+        }
+    }
+}
+
+/// Receives events from an [`EventSubscription`]
+pub struct EventReceiver {
+    inbox: Arc<Inbox>,
+}
+
+impl EventReceiver {
+    /// Receive the next item, waiting for one to arrive
+    ///
+    /// Returns `None` once the subscription is dropped and the queue drained.
+    pub async fn recv(&mut self) -> Option<EventItem> {
+        let inbox = self.inbox.clone();
+        loop {
+            // Registered before checking, so no wakeup is missed in between
+            let ready = inbox.readable.notified();
+            if let Some(item) = self.try_recv() {
+                return Some(item);
+            }
+            if inbox.lock().closed {
+                return None;
+            }
+            ready.await;
+        }
+    }
+
+    /// Receive the next item if one is queued
+    pub fn try_recv(&mut self) -> Option<EventItem> {
+        let item = self.inbox.lock().pop();
+        if item.is_some() {
+            self.inbox.writable.notify_waiters();
+        }
+        item
+    }
+
+    /// Number of events queued for this receiver, in memory and on disk
+    pub fn lag(&self) -> usize {
+        let queue = self.inbox.lock();
+        queue.events.len() + queue.spill.as_ref().map_or(0, |spill| spill.len)
+    }
+}
+
+impl Drop for EventReceiver {
+    fn drop(&mut self) {
+        // Release a producer blocked on this receiver
+        self.inbox.writable.notify_waiters();
+    }
+}
+
+/// A receiver's queue, shared with its subscription
+#[derive(Default)]
+struct Inbox {
+    queue: Mutex<Queue>,
+    readable: Notify,
+    writable: Notify,
+}
+
+#[derive(Default)]
+struct Queue {
+    events: VecDeque<Event>,
+    lagged: u64,
+    spill: Option<SpillFile>,
+    closed: bool,
+}
+
+impl Inbox {
+    fn lock(&self) -> std::sync::MutexGuard<'_, Queue> {
+        self.queue.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    async fn wait_for_space(self: &Arc<Self>, capacity: usize) {
+        loop {
+            let writable = self.writable.notified();
+            // Only the subscription's handle is left once the receiver is dropped
+            if self.lock().events.len() < capacity || Arc::strong_count(self) == 1 {
+                return;
+            }
+            writable.await;
+        }
+    }
+
+    fn push(
+        &self,
+        event: Event,
+        capacity: usize,
+        strategy: &LagStrategy,
+        metrics: &SubscriptionMetrics,
+    ) {
+        let mut queue = self.lock();
+        if queue.spill.is_none() && queue.events.len() < capacity {
+            queue.events.push_back(event);
+        } else {
+            match strategy {
+                LagStrategy::Notify | LagStrategy::Block => {
+                    queue.events.pop_front();
+                    queue.lagged += 1;
+                    queue.events.push_back(event);
+                    metrics.dropped.fetch_add(1, Ordering::Relaxed);
+                }
+                LagStrategy::Spill(dir) => {
+                    let spilled = match queue.spill.take() {
+                        Some(spill) => Ok(spill),
+                        None => SpillFile::create(dir),
+                    }
+                    .and_then(|mut spill| spill.append(&event).map(|()| spill));
+                    match spilled {
+                        Ok(spill) => {
+                            queue.spill = Some(spill);
+                            metrics.spilled.fetch_add(1, Ordering::Relaxed);
+                        }
+                        Err(e) => {
+                            tracing::warn!("Failed to spill event to disk, dropping it: {}", e);
+                            queue.lagged += 1;
+                            metrics.dropped.fetch_add(1, Ordering::Relaxed);
+                        }
+                    }
+                }
+            }
+        }
+        metrics.delivered.fetch_add(1, Ordering::Relaxed);
+        drop(queue);
+        self.readable.notify_waiters();
+    }
+
+    fn close(&self) {
+        self.lock().closed = true;
+        self.readable.notify_waiters();
+    }
+}
+
+impl Queue {
+    fn pop(&mut self) -> Option<EventItem> {
+        if self.lagged > 0 {
+            return Some(EventItem::Lagged(std::mem::take(&mut self.lagged)));
+        }
+        if self.events.is_empty() {
+            if let Some(spill) = &mut self.spill {
+                match spill.next() {
+                    Ok(Some(event)) => self.events.push_back(event),
+                    Ok(None) => {}
+                    Err(e) => {
+                        tracing::warn!("Failed to read spilled events, dropping them: {}", e);
+                        self.lagged += spill.len as u64;
+                        self.spill = None;
+                        return self.pop();
+                    }
+                }
+                if self.spill.as_ref().is_some_and(|spill| spill.len == 0) {
+                    self.spill = None;
+                }
+            }
+        }
+        self.events.pop_front().map(EventItem::Event)
+    }
+}
+
+/// Overflow events, one JSON document per line, removed when dropped
+struct SpillFile {
+    path: PathBuf,
+    writer: File,
+    reader: BufReader<File>,
+    len: usize,
+}
+
+impl SpillFile {
+    fn create(dir: &Path) -> std::io::Result<Self> {
+        static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+        let path = dir.join(format!(
+            "apex-events-{}-{}.jsonl",
+            std::process::id(),
+            NEXT_ID.fetch_add(1, Ordering::Relaxed)
+        ));
+        let writer = File::options().create_new(true).append(true).open(&path)?;
+        let reader = BufReader::new(File::open(&path)?);
+        Ok(Self {
+            path,
+            writer,
+            reader,
+            len: 0,
+        })
+    }
+
+    fn append(&mut self, event: &Event) -> std::io::Result<()> {
+        let mut line = serde_json::to_vec(event)?;
+        line.push(b'\n');
+        self.writer.write_all(&line)?;
+        self.len += 1;
+        Ok(())
+    }
+
+    fn next(&mut self) -> std::io::Result<Option<Event>> {
+        if self.len == 0 {
+            return Ok(None);
+        }
+        let mut line = String::new();
+        self.reader.read_line(&mut line)?;
+        self.len -= 1;
+        Ok(Some(serde_json::from_str(&line)?))
+    }
+}
+
+impl Drop for SpillFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// Replayed past events followed by live events, from
+/// [`EventSubscription::backfill`] or [`EventSubscription::resume`]
+pub struct EventReplay {
+    history: VecDeque<Event>,
+    replayed: HashSet<(ChainType, String, u32)>,
+    resumed: HashMap<ChainType, Checkpoint>,
+    live: EventReceiver,
+}
+
+impl EventReplay {
+    /// Receive the next item; replayed events come first
+    ///
+    /// Returns `None` once the subscription is dropped and all events are received.
+    pub async fn recv(&mut self) -> Option<EventItem> {
+        if let Some(event) = self.history.pop_front() {
+            return Some(EventItem::Event(event));
+        }
+        loop {
+            match self.live.recv().await? {
+                EventItem::Event(event) if self.already_delivered(&event) => {}
+                item => return Some(item),
+            }
+        }
+    }
+
+    /// Number of replayed events not yet received
+    pub fn remaining_history(&self) -> usize {
+        self.history.len()
+    }
+
+    fn already_delivered(&self, event: &Event) -> bool {
+        self.replayed.contains(&replay_key(event))
+            || self
+                .resumed
+                .get(&event.chain_type)
+                .is_some_and(|checkpoint| !checkpoint.precedes(event))
+    }
+}
+
+fn replay_key(event: &Event) -> (ChainType, String, u32) {
+    (
+        event.chain_type.clone(),
+        event.block_hash.clone(),
+        event.index,
+    )
+}
+
+/// Broadcasts block headers to its receivers
+pub struct BlockSubscription {
+    chain_type: Option<ChainType>,
+    sender: broadcast::Sender<BlockInfo>,
+}
+
+impl BlockSubscription {
+    /// Create a subscription for blocks of `chain_type`, or of every chain if `None`
+    pub fn new(chain_type: Option<ChainType>) -> Self {
+        let (sender, _) = broadcast::channel(SUBSCRIPTION_CAPACITY);
+        Self { chain_type, sender }
+    }
+
+    /// The chain type this subscription is limited to, if any
+    pub fn chain_type(&self) -> Option<&ChainType> {
+        self.chain_type.as_ref()
+    }
+
+    /// Receive blocks published from now on
+    pub fn subscribe(&self) -> broadcast::Receiver<BlockInfo> {
+        self.sender.subscribe()
+    }
+
+    /// Number of live receivers
+    pub fn receiver_count(&self) -> usize {
+        self.sender.receiver_count()
+    }
+
+    /// Deliver `block` to receivers if it is from the subscribed chain
+    ///
+    /// Returns whether the block matched.
+    pub fn emit(&self, block: BlockInfo) -> bool {
+        if !self.matches(&block) {
+            return false;
+        }
+        let _ = self.sender.send(block);
+        true
+    }
+
+    /// Whether `block` is from the subscribed chain
+    pub fn matches(&self, block: &BlockInfo) -> bool {
+        self.chain_type
+            .as_ref()
+            .is_none_or(|chain_type| *chain_type == block.chain_type)
+    }
+}
+
+/// Feeds events from chain adapters into registered subscriptions
+///
+/// Followers run as background tasks until the bridge is shut down or
+/// dropped; a follower that fails is restarted on a fresh connection after a
+/// short delay.
+#[derive(Default)]
+pub struct EventBridge {
+    subscriptions: Arc<Registry<EventSubscription>>,
+    history: RwLock<Vec<Arc<dyn EventHistory>>>,
+    tasks: Tasks,
+}
+
+impl EventBridge {
+    /// Create a bridge with no sources attached
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create and register a subscription for `filter`
+    ///
+    /// The subscription can backfill from every attached adapter, and is
+    /// unregistered once every handle to it is dropped.
+    pub fn subscribe(&self, filter: EventFilter) -> Arc<EventSubscription> {
+        self.subscribe_with(EventSubscription::new(filter))
+    }
+
+    /// Register a configured subscription, adding the attached adapters as
+    /// history sources
+    pub fn subscribe_with(&self, subscription: EventSubscription) -> Arc<EventSubscription> {
+        let subscription = self
+            .history
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .fold(subscription, |subscription, source| {
+                subscription.with_history(source.clone())
+            });
+        let subscription = Arc::new(subscription);
+        self.register(&subscription);
+        subscription
+    }
+
+    /// Register an existing subscription
+    pub fn register(&self, subscription: &Arc<EventSubscription>) {
+        self.subscriptions.register(subscription);
+    }
+
+    /// Number of registered, live subscriptions
+    pub fn subscription_count(&self) -> usize {
+        self.subscriptions.len()
+    }
+
+    /// Add a source of past events for subscriptions created from now on
+    pub fn add_history(&self, source: Arc<dyn EventHistory>) {
+        self.history
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(source);
+    }
+
+    /// Emit `event` into every registered subscription
+    ///
+    /// Returns the number of subscriptions it matched.
+    pub async fn dispatch(&self, event: &Event) -> usize {
+        dispatch(&self.subscriptions, event).await
+    }
+
+    /// Dispatch every event received from `source`
+    pub fn attach(&self, mut source: mpsc::Receiver<Event>) {
+        let subscriptions = self.subscriptions.clone();
+        self.tasks.spawn(async move {
+            while let Some(event) = source.recv().await {
+                dispatch(&subscriptions, &event).await;
+            }
+        });
+    }
+
+    /// Follow new EVM blocks and dispatch their logs
+    ///
+    /// Adapters connected over WebSocket are pushed logs by an
+    /// `eth_subscribe` subscription; others poll every `poll_interval`. The
+    /// adapter also becomes a history source.
+    pub fn attach_evm(&self, adapter: EvmAdapter, poll_interval: Duration) {
+        self.add_history(Arc::new(adapter.clone()));
+        let (sender, receiver) = mpsc::channel(SOURCE_CAPACITY);
+        self.tasks.spawn(follow(
+            "EVM event",
+            adapter,
+            move |adapter| {
+                let sender = sender.clone();
+                async move { adapter.watch_logs(poll_interval, sender).await }
+            },
+            |adapter| async move { adapter.reconnect().await },
+        ));
+        self.attach(receiver);
+    }
+
+    /// Follow finalized Substrate blocks and dispatch their events
+    ///
+    /// The adapter also becomes a history source.
+    pub fn attach_substrate(&self, adapter: SubstrateAdapter) {
+        self.add_history(Arc::new(adapter.clone()));
+        let (sender, receiver) = mpsc::channel(SOURCE_CAPACITY);
+        self.tasks.spawn(follow(
+            "Substrate event",
+            adapter,
+            move |adapter| {
+                let sender = sender.clone();
+                async move { adapter.watch_finalized_events(sender).await }
+            },
+            |adapter| async move { adapter.reconnect().await },
+        ));
+        self.attach(receiver);
+    }
+
+    /// Stop all followers
+    pub fn shutdown(&self) {
+        self.tasks.abort_all();
+    }
+}
+
+/// Follows new blocks on chain adapters and publishes them to block subscriptions
+///
+/// Blocks are reported at the monitor's [`Finality`] and tagged with the
+/// chain type they came from. Followers run until the monitor is shut down
+/// or dropped; a follower that fails is restarted on a fresh connection
+/// after a short delay.
+#[derive(Default)]
+pub struct BlockMonitor {
+    finality: Finality,
+    subscriptions: Arc<Registry<BlockSubscription>>,
+    tasks: Tasks,
+}
+
+impl BlockMonitor {
+    /// Create a monitor reporting blocks at `finality`, with no sources attached
+    pub fn new(finality: Finality) -> Self {
+        Self {
+            finality,
+            ..Default::default()
+        }
+    }
+
+    /// Finality level blocks are reported at
+    pub fn finality(&self) -> Finality {
+        self.finality
+    }
+
+    /// Create and register a subscription for blocks of `chain_type`, or of every chain
+    ///
+    /// The subscription is unregistered once every handle to it is dropped.
+    pub fn subscribe(&self, chain_type: Option<ChainType>) -> Arc<BlockSubscription> {
+        let subscription = Arc::new(BlockSubscription::new(chain_type));
+        self.register(&subscription);
+        subscription
+    }
+
+    /// Register an existing subscription
+    pub fn register(&self, subscription: &Arc<BlockSubscription>) {
+        self.subscriptions.register(subscription);
+    }
+
+    /// Number of registered, live subscriptions
+    pub fn subscription_count(&self) -> usize {
+        self.subscriptions.len()
+    }
+
+    /// Publish `block` to every registered subscription
+    ///
+    /// Returns the number of subscriptions it matched.
+    pub fn publish(&self, block: &BlockInfo) -> usize {
+        publish(&self.subscriptions, block)
+    }
+
+    /// Publish every block received from `source`
+    pub fn attach(&self, mut source: mpsc::Receiver<BlockInfo>) {
+        let subscriptions = self.subscriptions.clone();
+        self.tasks.spawn(async move {
+            while let Some(block) = source.recv().await {
+                publish(&subscriptions, &block);
+            }
+        });
+    }
+
+    /// Follow EVM blocks, polling every `poll_interval`
+    ///
+    /// Latest blocks are pushed by a `newHeads` subscription instead when
+    /// the adapter is connected over WebSocket.
+    pub fn attach_evm(&self, adapter: EvmAdapter, poll_interval: Duration) {
+        let (sender, receiver) = mpsc::channel(SOURCE_CAPACITY);
+        let finality = self.finality;
+        self.tasks.spawn(follow(
+            "EVM block",
+            adapter,
+            move |adapter| {
+                let sender = sender.clone();
+                async move { adapter.watch_blocks(finality, poll_interval, sender).await }
+            },
+            |adapter| async move { adapter.reconnect().await },
+        ));
+        self.attach(receiver);
+    }
+
+    /// Follow Substrate blocks
+    pub fn attach_substrate(&self, adapter: SubstrateAdapter) {
+        let (sender, receiver) = mpsc::channel(SOURCE_CAPACITY);
+        let finality = self.finality;
+        self.tasks.spawn(follow(
+            "Substrate block",
+            adapter,
+            move |adapter| {
+                let sender = sender.clone();
+                async move { adapter.watch_blocks(finality, sender).await }
+            },
+            |adapter| async move { adapter.reconnect().await },
+        ));
+        self.attach(receiver);
+    }
+
+    /// Stop all followers
+    pub fn shutdown(&self) {
+        self.tasks.abort_all();
+    }
+}
+
+/// Weakly held subscriptions, pruned once their last handle is dropped
+struct Registry<S>(RwLock<Vec<Weak<S>>>);
+
+impl<S> Default for Registry<S> {
+    fn default() -> Self {
+        Self(RwLock::new(Vec::new()))
+    }
+}
+
+impl<S> Registry<S> {
+    fn register(&self, subscription: &Arc<S>) {
+        self.0
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(Arc::downgrade(subscription));
+    }
+
+    fn len(&self) -> usize {
+        self.0
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .filter(|s| s.strong_count() > 0)
+            .count()
+    }
+
+    /// Live subscriptions, pruning dropped ones
+    fn live(&self) -> Vec<Arc<S>> {
+        let mut subscriptions = self.0.write().unwrap_or_else(|e| e.into_inner());
+        subscriptions.retain(|s| s.strong_count() > 0);
+        subscriptions.iter().filter_map(Weak::upgrade).collect()
+    }
+}
+
+/// Emit `event` into every live subscription, returning how many it matched
+async fn dispatch(subscriptions: &Registry<EventSubscription>, event: &Event) -> usize {
+    let mut matched = 0;
+    for subscription in subscriptions.live() {
+        matched += subscription.emit(event.clone()).await as usize;
+    }
+    matched
+}
+
+/// Emit `block` into every live subscription, returning how many it matched
+fn publish(subscriptions: &Registry<BlockSubscription>, block: &BlockInfo) -> usize {
+    subscriptions
+        .live()
+        .iter()
+        .filter(|subscription| subscription.emit(block.clone()))
+        .count()
+}
+
+/// Run a chain follower until it returns cleanly, reconnecting its adapter
+/// after each failure
+///
+/// Each restart waits [`RESTART_DELAY`]; if reconnecting fails, the old
+/// connection is tried again.
+async fn follow<A, E, W, R>(
+    name: &str,
+    mut adapter: A,
+    watch: impl Fn(A) -> W,
+    reconnect: impl Fn(A) -> R,
+) where
+    A: Clone,
+    E: std::fmt::Display,
+    W: std::future::Future<Output = std::result::Result<(), E>>,
+    R: std::future::Future<Output = std::result::Result<A, E>>,
+{
+    while let Err(e) = watch(adapter.clone()).await {
+        tracing::warn!("{} follower failed, reconnecting: {}", name, e);
+        tokio::time::sleep(RESTART_DELAY).await;
+        match reconnect(adapter.clone()).await {
+            Ok(reconnected) => adapter = reconnected,
+            Err(e) => tracing::warn!("{} follower failed to reconnect: {}", name, e),
+        }
+    }
+}
+
+/// Background tasks aborted together, at the latest on drop
+#[derive(Default)]
+struct Tasks(Mutex<Vec<JoinHandle<()>>>);
+
+impl Tasks {
+    fn spawn(&self, task: impl std::future::Future<Output = ()> + Send + 'static) {
+        self.0
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(tokio::spawn(task));
+    }
+
+    fn abort_all(&self) {
+        for task in self.0.lock().unwrap_or_else(|e| e.into_inner()).drain(..) {
+            task.abort();
+        }
+    }
+}
+
+impl Drop for Tasks {
+    fn drop(&mut self) {
+        self.abort_all();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checkpoint::MemoryCheckpointStore;
+    use apex_sdk_types::{Address, TransactionEvent};
+
+    fn transfer(block_number: u64) -> Event {
+        Event {
+            chain_type: ChainType::Substrate,
+            block_number,
+            block_hash: format!("0x{:064x}", block_number),
+            tx_hash: None,
+            index: 0,
+            origin: None,
+            event: TransactionEvent::Substrate {
+                pallet: "Balances".to_string(),
+                variant: "Transfer".to_string(),
+                fields: String::new(),
+            },
+        }
+    }
+
+    fn block_number(item: Option<EventItem>) -> u64 {
+        match item {
+            Some(EventItem::Event(event)) => event.block_number,
+            other => panic!("expected an event, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_matches_filter() {
+        let subscription = EventSubscription::new(EventFilter {
+            event_names: Some(vec!["balances.transfer".to_string()]),
+            from_block: Some(10),
+            to_block: Some(20),
+            ..Default::default()
+        });
+
+        assert!(subscription.matches_filter(&transfer(10)));
+        assert!(subscription.matches_filter(&transfer(20)));
+        assert!(!subscription.matches_filter(&transfer(9)));
+        assert!(!subscription.matches_filter(&transfer(21)));
+
+        let mut other = transfer(15);
+        other.event = TransactionEvent::Substrate {
+            pallet: "System".to_string(),
+            variant: "ExtrinsicSuccess".to_string(),
+            fields: String::new(),
+        };
+        assert!(!subscription.matches_filter(&other));
+    }
+
+    #[test]
+    fn test_matches_filter_addresses() {
+        let contract = EventSubscription::new(EventFilter {
+            addresses: Some(vec![Address::substrate(
+                "15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5",
+            )]),
+            ..Default::default()
+        });
+
+        let mut event = transfer(1);
+        assert!(!contract.matches_filter(&event));
+        event.origin = Some(Address::substrate(
+            "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY",
+        ));
+        assert!(contract.matches_filter(&event));
+        event.origin = Some(Address::substrate(
+            "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty",
+        ));
+        assert!(!contract.matches_filter(&event));
+
+        let token = EventSubscription::new(EventFilter {
+            addresses: Some(vec![Address::evm(
+                "0x742D35Cc6634C0532925A3B844bC9e7595f0bEB7",
+            )]),
+            ..Default::default()
+        });
+        event.origin = Some(Address::evm("0x742d35cc6634c0532925a3b844bc9e7595f0beb7"));
+        assert!(token.matches_filter(&event));
+    }
+
+    #[tokio::test]
+    async fn test_bridge_dispatches_from_source() {
+        let bridge = EventBridge::new();
+        let all = bridge.subscribe(EventFilter::default());
+        let late = bridge.subscribe(EventFilter {
+            from_block: Some(2),
+            ..Default::default()
+        });
+        let mut all_rx = all.subscribe();
+        let mut late_rx = late.subscribe();
+
+        let (sender, receiver) = mpsc::channel(8);
+        bridge.attach(receiver);
+        sender.send(transfer(1)).await.unwrap();
+        sender.send(transfer(2)).await.unwrap();
+
+        assert_eq!(block_number(all_rx.recv().await), 1);
+        assert_eq!(block_number(all_rx.recv().await), 2);
+        assert_eq!(block_number(late_rx.recv().await), 2);
+
+        drop(late_rx);
+        drop(late);
+        assert_eq!(bridge.dispatch(&transfer(3)).await, 1);
+        assert_eq!(bridge.subscription_count(), 1);
+    }
+
+    fn block(chain_type: ChainType, number: u64) -> BlockInfo {
+        BlockInfo {
+            chain_type,
+            number,
+            hash: format!("0x{:064x}", number),
+            parent_hash: format!("0x{:064x}", number - 1),
+            timestamp: None,
+            finality: Finality::Finalized,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_block_monitor_tags_chains() {
+        let monitor = BlockMonitor::new(Finality::Finalized);
+        let evm = monitor.subscribe(Some(ChainType::Evm));
+        let all = monitor.subscribe(None);
+        let mut evm_rx = evm.subscribe();
+        let mut all_rx = all.subscribe();
+
+        let (sender, receiver) = mpsc::channel(8);
+        monitor.attach(receiver);
+        sender.send(block(ChainType::Substrate, 7)).await.unwrap();
+        sender.send(block(ChainType::Evm, 100)).await.unwrap();
+
+        assert_eq!(
+            all_rx.recv().await.unwrap().chain_type,
+            ChainType::Substrate
+        );
+        assert_eq!(all_rx.recv().await.unwrap().chain_type, ChainType::Evm);
+        assert_eq!(evm_rx.recv().await.unwrap().number, 100);
+
+        drop(all_rx);
+        drop(all);
+        assert_eq!(monitor.publish(&block(ChainType::Substrate, 8)), 0);
+        assert_eq!(monitor.subscription_count(), 1);
+    }
+
+    struct FixedHistory(Vec<Event>);
+
+    #[async_trait]
+    impl EventHistory for FixedHistory {
+        fn chain_type(&self) -> ChainType {
+            ChainType::Substrate
+        }
+
+        async fn latest_block(&self) -> Result<u64> {
+            Ok(self.0.iter().map(|e| e.block_number).max().unwrap_or(0))
+        }
+
+        async fn events(&self, from_block: u64, to_block: u64) -> Result<Vec<Event>> {
+            Ok(self
+                .0
+                .iter()
+                .filter(|e| (from_block..=to_block).contains(&e.block_number))
+                .cloned()
+                .collect())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_backfill_then_live_without_duplicates() {
+        let bridge = EventBridge::new();
+        bridge.add_history(Arc::new(FixedHistory(vec![
+            transfer(1),
+            transfer(2),
+            transfer(3),
+        ])));
+        let subscription = bridge.subscribe(EventFilter::default());
+
+        let mut replay = subscription.backfill(2, 3).await.unwrap();
+        assert_eq!(replay.remaining_history(), 2);
+
+        // Block 3 arrives live while being replayed
+        bridge.dispatch(&transfer(3)).await;
+        bridge.dispatch(&transfer(4)).await;
+
+        let mut numbers = Vec::new();
+        for _ in 0..3 {
+            numbers.push(block_number(replay.recv().await));
+        }
+        assert_eq!(numbers, vec![2, 3, 4]);
+
+        let standalone = EventSubscription::new(EventFilter::default());
+        assert!(matches!(
+            standalone.backfill(0, 1).await,
+            Err(Error::Config(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_lagging_receiver_is_notified() {
+        let subscription = EventSubscription::new(EventFilter::default()).with_capacity(2);
+        let mut receiver = subscription.subscribe();
+        for number in 1..=5 {
+            subscription.emit(transfer(number)).await;
+        }
+
+        assert_eq!(receiver.lag(), 2);
+        assert_eq!(receiver.recv().await, Some(EventItem::Lagged(3)));
+        assert_eq!(block_number(receiver.recv().await), 4);
+        assert_eq!(block_number(receiver.recv().await), 5);
+        assert_eq!(subscription.metrics().dropped.load(Ordering::Relaxed), 3);
+
+        drop(subscription);
+        assert_eq!(receiver.recv().await, None);
+    }
+
+    #[tokio::test]
+    async fn test_block_strategy_waits_for_receiver() {
+        let subscription = Arc::new(
+            EventSubscription::new(EventFilter::default())
+                .with_capacity(1)
+                .with_lag_strategy(LagStrategy::Block),
+        );
+        let mut receiver = subscription.subscribe();
+
+        let producer = tokio::spawn({
+            let subscription = subscription.clone();
+            async move {
+                for number in 1..=3 {
+                    subscription.emit(transfer(number)).await;
+                }
+            }
+        });
+        for expected in 1..=3 {
+            assert_eq!(block_number(receiver.recv().await), expected);
+        }
+        producer.await.unwrap();
+        assert_eq!(subscription.metrics().dropped.load(Ordering::Relaxed), 0);
+    }
+
+    #[tokio::test]
+    async fn test_spill_strategy_keeps_order() {
+        let dir = std::env::temp_dir();
+        let subscription = EventSubscription::new(EventFilter::default())
+            .with_capacity(2)
+            .with_lag_strategy(LagStrategy::Spill(dir));
+        let mut receiver = subscription.subscribe();
+        for number in 1..=6 {
+            subscription.emit(transfer(number)).await;
+        }
+        assert_eq!(receiver.lag(), 6);
+        assert_eq!(subscription.metrics().spilled.load(Ordering::Relaxed), 4);
+
+        // Arrives while earlier events are still on disk
+        let mut numbers = vec![block_number(receiver.recv().await)];
+        subscription.emit(transfer(7)).await;
+        while receiver.lag() > 0 {
+            numbers.push(block_number(receiver.recv().await));
+        }
+        assert_eq!(numbers, (1..=7).collect::<Vec<_>>());
+    }
+
+    #[tokio::test]
+    async fn test_resume_from_checkpoint() {
+        let bridge = EventBridge::new();
+        let mut second = transfer(2);
+        second.index = 1;
+        bridge.add_history(Arc::new(FixedHistory(vec![
+            transfer(1),
+            transfer(2),
+            second,
+            transfer(3),
+        ])));
+        let subscription = bridge.subscribe(EventFilter::default());
+        let checkpointer = Checkpointer::new("indexer", Arc::new(MemoryCheckpointStore::new()));
+
+        // Without a checkpoint there is nothing to replay
+        let replay = subscription.resume(&checkpointer).await.unwrap();
+        assert_eq!(replay.remaining_history(), 0);
+
+        checkpointer.commit(&transfer(2)).await.unwrap();
+        let mut replay = subscription.resume(&checkpointer).await.unwrap();
+        assert_eq!(replay.remaining_history(), 2);
+        bridge.dispatch(&transfer(1)).await;
+        bridge.dispatch(&transfer(4)).await;
+
+        let mut positions = Vec::new();
+        for _ in 0..3 {
+            match replay.recv().await {
+                Some(EventItem::Event(event)) => positions.push((event.block_number, event.index)),
+                other => panic!("expected an event, got {:?}", other),
+            }
+        }
+        assert_eq!(positions, vec![(2, 1), (3, 0), (4, 0)]);
+    }
+}
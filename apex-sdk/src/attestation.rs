@@ -0,0 +1,347 @@
+//! Signed submission attestations
+//!
+//! With an [`Attestor`] attached, every transaction submitted through
+//! [`ApexSDK::execute`](crate::ApexSDK::execute) produces an [`Attestation`]:
+//! who signed it, a hash of its call data, the policy decisions that let it
+//! through and when it was requested and submitted. The record is signed
+//! (EIP-191) by the attestor's key, so external audit systems can verify it
+//! came from the SDK deployment, and handed to every [`AttestationSink`].
+//!
+//! Sinks:
+//! - [`JsonLinesAuditLog`]: append-only JSON lines file
+//! - `apex_sdk_sinks::Publisher`: NATS, Kafka or Redis, as an `attestation` event
+//!
+//! Other destinations (webhooks, SIEM collectors) implement [`AttestationSink`].
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use apex_sdk::attestation::{Attestor, JsonLinesAuditLog};
+//! use apex_sdk::evm::wallet::Wallet;
+//!
+//! let attestor = Attestor::new(Wallet::new_random())
+//!     .with_sink(JsonLinesAuditLog::new("/var/log/apex/attestations.jsonl"));
+//! ```
+
+use crate::error::{Error, Result};
+use crate::policy::PolicyDecision;
+use crate::transaction::Transaction;
+use apex_sdk_evm::wallet::Wallet;
+use apex_sdk_types::{Chain, Token};
+use async_trait::async_trait;
+use ethers::types::Signature;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::io::AsyncWriteExt;
+
+/// Attestation format version
+pub const ATTESTATION_VERSION: u32 = 1;
+
+/// A policy decision recorded in an attestation
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PolicyRecord {
+    /// Policy name
+    pub policy: String,
+    /// `allow`, `approved` or `deny`
+    pub decision: String,
+    /// Reason given with an approval or denial
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+impl PolicyRecord {
+    /// Record a decision that let a transaction through
+    pub fn new(policy: impl Into<String>, decision: &PolicyDecision) -> Self {
+        let (decision, reason) = match decision {
+            PolicyDecision::Allow => ("allow", None),
+            PolicyDecision::RequireApproval(reason) => ("approved", Some(reason.clone())),
+            PolicyDecision::Deny(reason) => ("deny", Some(reason.clone())),
+        };
+        Self {
+            policy: policy.into(),
+            decision: decision.to_string(),
+            reason,
+        }
+    }
+}
+
+/// The attested facts about a submission
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AttestationBody {
+    /// Format version
+    pub version: u32,
+    /// Address that signed the transaction
+    pub signer: String,
+    /// Destination address
+    pub to: String,
+    /// Amount transferred
+    pub amount: u128,
+    /// Token transferred; omitted for the native token, as in records that
+    /// predate token transfers
+    #[serde(default, skip_serializing_if = "Token::is_native")]
+    pub token: Token,
+    /// Source chain
+    pub source_chain: Chain,
+    /// Destination chain
+    pub destination_chain: Chain,
+    /// Keccak-256 of the call data (of empty data if there is none)
+    pub call_data_hash: String,
+    /// Transaction hash on the source chain
+    pub tx_hash: String,
+    /// Decisions of every policy consulted
+    pub policy_decisions: Vec<PolicyRecord>,
+    /// Unix timestamp (milliseconds) at which execution was requested
+    pub requested_at: u64,
+    /// Unix timestamp (milliseconds) at which the transaction was submitted
+    pub submitted_at: u64,
+}
+
+impl AttestationBody {
+    /// Bytes covered by the signature: the body as compact JSON
+    #[allow(clippy::result_large_err)]
+    pub fn signing_bytes(&self) -> Result<Vec<u8>> {
+        serde_json::to_vec(self).map_err(|e| Error::Serialization(e.to_string()))
+    }
+}
+
+/// A signed attestation record
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Attestation {
+    /// The attested facts
+    pub body: AttestationBody,
+    /// Address of the attestor key
+    pub attestor: String,
+    /// EIP-191 signature over [`AttestationBody::signing_bytes`]
+    pub signature: String,
+}
+
+impl Attestation {
+    /// Whether the signature over the body was made by `attestor`
+    #[allow(clippy::result_large_err)]
+    pub fn verify(&self) -> Result<bool> {
+        let signature: Signature = self
+            .signature
+            .parse()
+            .map_err(|e| Error::Serialization(format!("Invalid signature: {}", e)))?;
+        let attestor: ethers::types::Address = self
+            .attestor
+            .parse()
+            .map_err(|e| Error::InvalidAddress(format!("Invalid attestor: {}", e)))?;
+        Ok(signature
+            .verify(self.body.signing_bytes()?, attestor)
+            .is_ok())
+    }
+}
+
+/// Destination for attestations
+#[async_trait]
+pub trait AttestationSink: Send + Sync {
+    /// Deliver one attestation
+    async fn emit(&self, attestation: &Attestation) -> Result<()>;
+}
+
+#[async_trait]
+impl<S: AttestationSink + ?Sized> AttestationSink for Arc<S> {
+    async fn emit(&self, attestation: &Attestation) -> Result<()> {
+        (**self).emit(attestation).await
+    }
+}
+
+/// Audit log appending one JSON attestation per line
+pub struct JsonLinesAuditLog {
+    path: PathBuf,
+    lock: tokio::sync::Mutex<()>,
+}
+
+impl JsonLinesAuditLog {
+    /// Append to the file at `path`, creating it if needed
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            lock: tokio::sync::Mutex::new(()),
+        }
+    }
+}
+
+#[async_trait]
+impl AttestationSink for JsonLinesAuditLog {
+    async fn emit(&self, attestation: &Attestation) -> Result<()> {
+        let mut line =
+            serde_json::to_vec(attestation).map_err(|e| Error::Serialization(e.to_string()))?;
+        line.push(b'\n');
+
+        let _guard = self.lock.lock().await;
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .await
+            .map_err(|e| Error::Other(format!("Failed to open {:?}: {}", self.path, e)))?;
+        file.write_all(&line)
+            .await
+            .map_err(|e| Error::Other(format!("Failed to write {:?}: {}", self.path, e)))?;
+        file.flush()
+            .await
+            .map_err(|e| Error::Other(format!("Failed to write {:?}: {}", self.path, e)))
+    }
+}
+
+/// Signs attestations for submissions and emits them to sinks
+pub struct Attestor {
+    wallet: Wallet,
+    sinks: Vec<Arc<dyn AttestationSink>>,
+}
+
+impl Attestor {
+    /// Sign attestations with `wallet` (which may be KMS or HSM backed)
+    pub fn new(wallet: Wallet) -> Self {
+        Self {
+            wallet,
+            sinks: Vec::new(),
+        }
+    }
+
+    /// Emit attestations to `sink`
+    pub fn with_sink(mut self, sink: impl AttestationSink + 'static) -> Self {
+        self.sinks.push(Arc::new(sink));
+        self
+    }
+
+    /// Address of the attestor key
+    pub fn address(&self) -> String {
+        self.wallet.address()
+    }
+
+    /// Sign an attestation for a submitted transaction
+    pub async fn attest(
+        &self,
+        tx: &Transaction,
+        tx_hash: &str,
+        decisions: &[(String, PolicyDecision)],
+        requested_at: u64,
+    ) -> Result<Attestation> {
+        let body = AttestationBody {
+            version: ATTESTATION_VERSION,
+            signer: tx.from.as_str().to_string(),
+            to: tx.to.as_str().to_string(),
+            amount: tx.amount,
+            token: tx.token.clone(),
+            source_chain: tx.source_chain.clone(),
+            destination_chain: tx.destination_chain.clone(),
+            call_data_hash: format!(
+                "0x{}",
+                hex::encode(ethers::utils::keccak256(
+                    tx.data.as_deref().unwrap_or_default()
+                ))
+            ),
+            tx_hash: tx_hash.to_string(),
+            policy_decisions: decisions
+                .iter()
+                .map(|(policy, decision)| PolicyRecord::new(policy, decision))
+                .collect(),
+            requested_at,
+            submitted_at: now_millis(),
+        };
+
+        let signature = self.wallet.sign_message(body.signing_bytes()?).await?;
+        Ok(Attestation {
+            body,
+            attestor: self.address(),
+            signature: format!("0x{}", signature),
+        })
+    }
+
+    /// Deliver an attestation to every sink
+    ///
+    /// The transaction has already been submitted, so failures are logged
+    /// rather than returned.
+    pub async fn emit(&self, attestation: &Attestation) {
+        for sink in &self.sinks {
+            if let Err(e) = sink.emit(attestation).await {
+                tracing::error!(
+                    "Failed to emit attestation for {}: {}",
+                    attestation.body.tx_hash,
+                    e
+                );
+            }
+        }
+    }
+}
+
+/// Current Unix time in milliseconds
+pub(crate) fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transaction::TransactionBuilder;
+
+    fn tx() -> Transaction {
+        TransactionBuilder::new()
+            .from_evm_address("0x742D35Cc6634C0532925A3B844bC9e7595f0bEB7")
+            .to_evm_address("0x1234567890123456789012345678901234567890")
+            .amount(1000)
+            .with_data(vec![0xa9, 0x05, 0x9c, 0xbb])
+            .build()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_attestation_signed_and_verifiable() {
+        let attestor = Attestor::new(Wallet::new_random());
+        let decisions = vec![
+            ("spend_limit".to_string(), PolicyDecision::Allow),
+            (
+                "approval".to_string(),
+                PolicyDecision::RequireApproval("above threshold".to_string()),
+            ),
+        ];
+
+        let attestation = attestor
+            .attest(&tx(), "0xabc", &decisions, 1)
+            .await
+            .unwrap();
+        assert_eq!(attestation.attestor, attestor.address());
+        assert_eq!(attestation.body.policy_decisions[1].decision, "approved");
+        assert_eq!(
+            attestation.body.call_data_hash,
+            format!(
+                "0x{}",
+                hex::encode(ethers::utils::keccak256([0xa9, 0x05, 0x9c, 0xbb]))
+            )
+        );
+        assert!(attestation.verify().unwrap());
+
+        let mut tampered = attestation.clone();
+        tampered.body.amount += 1;
+        assert!(!tampered.verify().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_json_lines_audit_log() {
+        let path = std::env::temp_dir().join(format!("apex-attest-{}.jsonl", now_millis()));
+        let attestor = Attestor::new(Wallet::new_random()).with_sink(JsonLinesAuditLog::new(&path));
+
+        for hash in ["0x01", "0x02"] {
+            let attestation = attestor.attest(&tx(), hash, &[], 0).await.unwrap();
+            attestor.emit(&attestation).await;
+        }
+
+        let log = std::fs::read_to_string(&path).unwrap();
+        let records: Vec<Attestation> = log
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[1].body.tx_hash, "0x02");
+        assert!(records[0].verify().unwrap());
+        std::fs::remove_file(path).unwrap();
+    }
+}
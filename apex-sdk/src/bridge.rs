@@ -0,0 +1,576 @@
+//! Cross-chain bridges
+//!
+//! A [`BridgeProvider`] moves an asset from one chain to another in four
+//! steps: [`quote`](BridgeProvider::quote) the fees,
+//! [`execute`](BridgeProvider::execute) on the source chain,
+//! [`confirm`](BridgeProvider::confirm) arrival on the destination chain,
+//! and [`refund`](BridgeProvider::refund) when it never arrives. The
+//! [`BridgeRouter`] picks the provider with the best quote and runs those
+//! steps end to end.
+//!
+//! [`XcmMoonbeamBridge`] is the reference provider: DOT from Polkadot to an
+//! EVM account on Moonbeam, as a reserve transfer over XCM.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use apex_sdk::bridge::{BridgeRequest, BridgeRouter, XcmMoonbeamBridge};
+//! use apex_sdk::evm::EvmAdapter;
+//! use apex_sdk::substrate::{KeyPairType, SubstrateAdapter, Wallet};
+//! use apex_sdk::types::{Address, Chain};
+//! use std::time::Duration;
+//!
+//! # #[tokio::main]
+//! # async fn main() -> apex_sdk::Result<()> {
+//! let polkadot = SubstrateAdapter::connect("wss://polkadot.api.onfinality.io/public-ws").await?;
+//! let moonbeam = EvmAdapter::connect("https://rpc.api.moonbeam.network").await?;
+//! let wallet = Wallet::from_mnemonic("your mnemonic here", KeyPairType::Sr25519)?;
+//!
+//! let bridge = XcmMoonbeamBridge::new(polkadot, moonbeam, wallet);
+//! let router = BridgeRouter::new().with_provider(bridge);
+//! let request = BridgeRequest {
+//!     source: Chain::Polkadot,
+//!     destination: Chain::Moonbeam,
+//!     asset: "DOT".to_string(),
+//!     amount: 10_000_000_000, // 1 DOT
+//!     recipient: Address::evm("0x742D35Cc6634C0532925A3B844bC9e7595f0bEB7"),
+//! };
+//!
+//! let transfer = router.send(&request, Duration::from_secs(120)).await?;
+//! println!("{}: {}", transfer.transaction.id, transfer.transaction.status);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::{Error, Result};
+use apex_sdk_evm::EvmAdapter;
+use apex_sdk_substrate::{Junction, MultiLocation, SubstrateAdapter, Wallet, XcmAsset};
+use apex_sdk_types::{Address, Chain, CrossChainTransaction, TransactionStatus};
+use async_trait::async_trait;
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Moonbeam's parachain ID on Polkadot
+pub const MOONBEAM_PARA_ID: u32 = 2004;
+
+/// Address of the xcDOT token on Moonbeam
+pub const MOONBEAM_XCDOT: &str = "0xFfFFfFff1FcaCBd218EDc0EbA20Fc2308C778080";
+
+/// Conservative upper bound for the XCM execution fee Moonbeam charges, in Planck
+pub const MOONBEAM_DOT_FEE: u128 = 500_000_000;
+
+/// A transfer to perform across chains
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BridgeRequest {
+    /// Chain the asset leaves from
+    pub source: Chain,
+    /// Chain the asset arrives on
+    pub destination: Chain,
+    /// Asset symbol (e.g. "DOT")
+    pub asset: String,
+    /// Amount in the asset's smallest unit
+    pub amount: u128,
+    /// Account credited on the destination chain
+    pub recipient: Address,
+}
+
+/// Fees and expected outcome of a transfer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BridgeQuote {
+    /// Provider that produced the quote
+    pub provider: String,
+    /// The quoted transfer
+    pub request: BridgeRequest,
+    /// Fee paid on the source chain, in its native token
+    pub source_fee: u128,
+    /// Fee deducted from the amount on the destination chain
+    pub destination_fee: u128,
+    /// Amount the recipient receives
+    pub amount_received: u128,
+    /// Typical time until the funds arrive
+    pub estimated_time: Duration,
+}
+
+/// What happens to funds that did not arrive
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RefundOutcome {
+    /// The funds arrived or never left the source chain
+    NotNeeded,
+    /// Refunded by the given transaction
+    Refunded(String),
+    /// Recovery needs manual action, as described
+    Manual(String),
+}
+
+/// A transfer in flight
+#[derive(Debug, Clone)]
+pub struct BridgeTransfer {
+    /// The quote the transfer was executed at
+    pub quote: BridgeQuote,
+    /// Cross-chain progress; `id` is the source transaction hash
+    pub transaction: CrossChainTransaction,
+    /// Recipient's destination balance before the transfer, if the provider
+    /// confirms arrival by balance
+    pub destination_baseline: Option<u128>,
+    /// Result of [`BridgeProvider::refund`], once attempted
+    pub refund: Option<RefundOutcome>,
+}
+
+/// A route for moving assets between chains
+#[async_trait]
+pub trait BridgeProvider: Send + Sync {
+    /// Provider name
+    fn name(&self) -> &str;
+
+    /// Whether the provider can perform `request`
+    fn supports(&self, request: &BridgeRequest) -> bool;
+
+    /// Quote the fees of `request`
+    async fn quote(&self, request: &BridgeRequest) -> Result<BridgeQuote>;
+
+    /// Submit the transfer on the source chain
+    async fn execute(&self, quote: &BridgeQuote) -> Result<BridgeTransfer>;
+
+    /// Wait up to `timeout` for the funds to arrive, updating the transfer's status
+    ///
+    /// A transfer that has not arrived by then is marked failed.
+    async fn confirm(&self, transfer: &mut BridgeTransfer, timeout: Duration) -> Result<()>;
+
+    /// Recover the funds of a transfer that did not arrive
+    async fn refund(&self, transfer: &BridgeTransfer) -> Result<RefundOutcome>;
+}
+
+/// Routes transfers to the provider with the best quote
+#[derive(Default)]
+pub struct BridgeRouter {
+    providers: Vec<Arc<dyn BridgeProvider>>,
+}
+
+impl BridgeRouter {
+    /// Create a router without providers
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a provider
+    pub fn with_provider(mut self, provider: impl BridgeProvider + 'static) -> Self {
+        self.providers.push(Arc::new(provider));
+        self
+    }
+
+    /// Quote `request` with every supporting provider, returning the quote
+    /// delivering the most, with the provider that made it
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no provider supports the request or all quotes fail.
+    pub async fn quote(
+        &self,
+        request: &BridgeRequest,
+    ) -> Result<(Arc<dyn BridgeProvider>, BridgeQuote)> {
+        let mut best: Option<(Arc<dyn BridgeProvider>, BridgeQuote)> = None;
+        let mut last_error = None;
+
+        for provider in self
+            .providers
+            .iter()
+            .filter(|p| p.supports(request) && declares_route(request, p.name()))
+        {
+            match provider.quote(request).await {
+                Ok(quote) => {
+                    if best
+                        .as_ref()
+                        .is_none_or(|(_, b)| quote.amount_received > b.amount_received)
+                    {
+                        best = Some((provider.clone(), quote));
+                    }
+                }
+                Err(e) => {
+                    tracing::warn!("Bridge {} failed to quote: {}", provider.name(), e);
+                    last_error = Some(e);
+                }
+            }
+        }
+
+        best.ok_or_else(|| {
+            last_error.unwrap_or_else(|| {
+                Error::UnsupportedChain(format!(
+                    "No bridge for {} from {} to {}",
+                    request.asset, request.source, request.destination
+                ))
+            })
+        })
+    }
+
+    /// Quote, execute and confirm `request`, refunding if it does not arrive
+    /// within `timeout`
+    ///
+    /// The returned transfer is confirmed, or failed with
+    /// [`BridgeTransfer::refund`] set.
+    pub async fn send(&self, request: &BridgeRequest, timeout: Duration) -> Result<BridgeTransfer> {
+        let (provider, quote) = self.quote(request).await?;
+        let mut transfer = provider.execute(&quote).await?;
+        provider.confirm(&mut transfer, timeout).await?;
+
+        if !matches!(
+            transfer.transaction.status,
+            TransactionStatus::Confirmed { .. }
+        ) {
+            transfer.refund = Some(provider.refund(&transfer).await?);
+        }
+        Ok(transfer)
+    }
+}
+
+/// Whether a registered source chain that declares bridge routes routes
+/// `request` through `provider`; chains without declared routes allow any
+fn declares_route(request: &BridgeRequest, provider: &str) -> bool {
+    match &request.source {
+        Chain::Custom(spec) if !spec.bridges.is_empty() => spec
+            .bridge_to(&request.destination)
+            .is_some_and(|route| route.provider == provider),
+        _ => true,
+    }
+}
+
+/// DOT from Polkadot to an EVM account on Moonbeam over XCM
+///
+/// DOT is reserve-transferred from the relay chain to Moonbeam, where it
+/// arrives as xcDOT. Arrival is confirmed by the recipient's xcDOT balance.
+/// DOT that fails to execute on Moonbeam is trapped there and must be
+/// claimed manually, so [`refund`](BridgeProvider::refund) reports
+/// [`RefundOutcome::Manual`].
+pub struct XcmMoonbeamBridge {
+    polkadot: SubstrateAdapter,
+    moonbeam: EvmAdapter,
+    wallet: Wallet,
+    destination_fee: u128,
+    poll_interval: Duration,
+}
+
+impl XcmMoonbeamBridge {
+    /// Bridge DOT sent by `wallet` on `polkadot` to `moonbeam`
+    pub fn new(polkadot: SubstrateAdapter, moonbeam: EvmAdapter, wallet: Wallet) -> Self {
+        Self {
+            polkadot,
+            moonbeam,
+            wallet,
+            destination_fee: MOONBEAM_DOT_FEE,
+            poll_interval: Duration::from_secs(6),
+        }
+    }
+
+    /// Set the XCM execution fee assumed for Moonbeam, in Planck
+    pub fn with_destination_fee(mut self, fee: u128) -> Self {
+        self.destination_fee = fee;
+        self
+    }
+
+    /// Set how often the destination balance is checked while confirming
+    pub fn with_poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+
+    #[allow(clippy::result_large_err)]
+    fn locations(&self, request: &BridgeRequest) -> Result<(MultiLocation, MultiLocation)> {
+        let key: [u8; 20] = request
+            .recipient
+            .account_bytes()
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or_else(|| Error::InvalidAddress(request.recipient.as_str().to_string()))?;
+        Ok((
+            MultiLocation::new(0, vec![Junction::Parachain(MOONBEAM_PARA_ID)]),
+            MultiLocation::account20(key),
+        ))
+    }
+
+    async fn xcdot_balance(&self, recipient: &Address) -> Result<u128> {
+        let balance = self
+            .moonbeam
+            .token_balance(MOONBEAM_XCDOT, recipient.as_str())
+            .await?;
+        u128::try_from(balance).map_err(|_| {
+            Error::Other(format!(
+                "xcDOT balance of {} exceeds u128",
+                recipient.as_str()
+            ))
+        })
+    }
+}
+
+#[async_trait]
+impl BridgeProvider for XcmMoonbeamBridge {
+    fn name(&self) -> &str {
+        "xcm-moonbeam"
+    }
+
+    fn supports(&self, request: &BridgeRequest) -> bool {
+        request.source == Chain::Polkadot
+            && request.destination == Chain::Moonbeam
+            && request.asset.eq_ignore_ascii_case("DOT")
+            && matches!(request.recipient, Address::Evm(_))
+    }
+
+    async fn quote(&self, request: &BridgeRequest) -> Result<BridgeQuote> {
+        if request.amount <= self.destination_fee {
+            return Err(Error::Transaction(format!(
+                "Amount {} does not cover the destination fee {}",
+                request.amount, self.destination_fee
+            )));
+        }
+        let (dest, beneficiary) = self.locations(request)?;
+        let source_fee = self
+            .polkadot
+            .xcm_executor()
+            .estimate_reserve_transfer_fee(
+                &self.wallet,
+                dest,
+                beneficiary,
+                vec![XcmAsset::native(request.amount)],
+            )
+            .await?;
+
+        Ok(BridgeQuote {
+            provider: self.name().to_string(),
+            request: request.clone(),
+            source_fee,
+            destination_fee: self.destination_fee,
+            amount_received: request.amount - self.destination_fee,
+            estimated_time: Duration::from_secs(60),
+        })
+    }
+
+    async fn execute(&self, quote: &BridgeQuote) -> Result<BridgeTransfer> {
+        let request = &quote.request;
+        let (dest, beneficiary) = self.locations(request)?;
+        let baseline = self.xcdot_balance(&request.recipient).await?;
+
+        // Returns once the extrinsic is finalized on Polkadot
+        let tx_hash = self
+            .polkadot
+            .xcm_executor()
+            .reserve_transfer_to(
+                &self.wallet,
+                dest,
+                beneficiary,
+                vec![XcmAsset::native(request.amount)],
+            )
+            .await?;
+
+        Ok(BridgeTransfer {
+            quote: quote.clone(),
+            transaction: CrossChainTransaction {
+                id: tx_hash.clone(),
+                source_chain: request.source.clone(),
+                destination_chain: request.destination.clone(),
+                source_tx_hash: Some(tx_hash),
+                destination_tx_hash: None,
+                status: TransactionStatus::Pending,
+                timestamp: SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map(|d| d.as_secs())
+                    .unwrap_or_default(),
+            },
+            destination_baseline: Some(baseline),
+            refund: None,
+        })
+    }
+
+    async fn confirm(&self, transfer: &mut BridgeTransfer, timeout: Duration) -> Result<()> {
+        let baseline = transfer.destination_baseline.unwrap_or_default();
+        let recipient = transfer.quote.request.recipient.clone();
+        let deadline = Instant::now() + timeout;
+
+        loop {
+            match self.xcdot_balance(&recipient).await {
+                Ok(balance) if balance > baseline => {
+                    transfer.transaction.status = TransactionStatus::Confirmed {
+                        block_number: self.moonbeam.block_number().await?,
+                        confirmations: 0,
+                    };
+                    return Ok(());
+                }
+                Ok(_) => {}
+                Err(e) => tracing::debug!("Failed to check xcDOT balance: {}", e),
+            }
+            if Instant::now() >= deadline {
+                transfer.transaction.status = TransactionStatus::Failed {
+                    error: format!("Not received on Moonbeam within {:?}", timeout),
+                };
+                return Ok(());
+            }
+            tokio::time::sleep(self.poll_interval).await;
+        }
+    }
+
+    async fn refund(&self, transfer: &BridgeTransfer) -> Result<RefundOutcome> {
+        if matches!(
+            transfer.transaction.status,
+            TransactionStatus::Confirmed { .. }
+        ) {
+            return Ok(RefundOutcome::NotNeeded);
+        }
+        Ok(RefundOutcome::Manual(format!(
+            "DOT sent by {} may be trapped on Moonbeam; claim it with \
+             PolkadotXcm.claim_assets once the XCM failure is confirmed",
+            transfer.transaction.id
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Provider delivering `amount - fee`, arriving only if `arrives`
+    struct FixedFee {
+        name: &'static str,
+        fee: u128,
+        arrives: bool,
+    }
+
+    #[async_trait]
+    impl BridgeProvider for FixedFee {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn supports(&self, request: &BridgeRequest) -> bool {
+            request.asset == "DOT"
+        }
+
+        async fn quote(&self, request: &BridgeRequest) -> Result<BridgeQuote> {
+            Ok(BridgeQuote {
+                provider: self.name.to_string(),
+                request: request.clone(),
+                source_fee: 1,
+                destination_fee: self.fee,
+                amount_received: request.amount - self.fee,
+                estimated_time: Duration::ZERO,
+            })
+        }
+
+        async fn execute(&self, quote: &BridgeQuote) -> Result<BridgeTransfer> {
+            Ok(BridgeTransfer {
+                quote: quote.clone(),
+                transaction: CrossChainTransaction {
+                    id: "0x01".to_string(),
+                    source_chain: quote.request.source.clone(),
+                    destination_chain: quote.request.destination.clone(),
+                    source_tx_hash: Some("0x01".to_string()),
+                    destination_tx_hash: None,
+                    status: TransactionStatus::Pending,
+                    timestamp: 0,
+                },
+                destination_baseline: None,
+                refund: None,
+            })
+        }
+
+        async fn confirm(&self, transfer: &mut BridgeTransfer, _timeout: Duration) -> Result<()> {
+            transfer.transaction.status = if self.arrives {
+                TransactionStatus::Confirmed {
+                    block_number: 1,
+                    confirmations: 0,
+                }
+            } else {
+                TransactionStatus::Failed {
+                    error: "timeout".to_string(),
+                }
+            };
+            Ok(())
+        }
+
+        async fn refund(&self, _transfer: &BridgeTransfer) -> Result<RefundOutcome> {
+            Ok(RefundOutcome::Refunded("0x02".to_string()))
+        }
+    }
+
+    fn request(asset: &str) -> BridgeRequest {
+        BridgeRequest {
+            source: Chain::Polkadot,
+            destination: Chain::Moonbeam,
+            asset: asset.to_string(),
+            amount: 1_000,
+            recipient: Address::evm("0x742D35Cc6634C0532925A3B844bC9e7595f0bEB7"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_router_picks_best_quote() {
+        let router = BridgeRouter::new()
+            .with_provider(FixedFee {
+                name: "expensive",
+                fee: 50,
+                arrives: true,
+            })
+            .with_provider(FixedFee {
+                name: "cheap",
+                fee: 10,
+                arrives: true,
+            });
+
+        let (provider, quote) = router.quote(&request("DOT")).await.unwrap();
+        assert_eq!(provider.name(), "cheap");
+        assert_eq!(quote.amount_received, 990);
+
+        assert!(matches!(
+            router.quote(&request("BTC")).await,
+            Err(Error::UnsupportedChain(_))
+        ));
+
+        let transfer = router.send(&request("DOT"), Duration::ZERO).await.unwrap();
+        assert!(matches!(
+            transfer.transaction.status,
+            TransactionStatus::Confirmed { .. }
+        ));
+        assert_eq!(transfer.refund, None);
+    }
+
+    #[tokio::test]
+    async fn test_router_follows_declared_routes() {
+        let source = apex_sdk_types::ChainSpec::new(
+            "Bridge Source",
+            apex_sdk_types::ChainType::Substrate,
+            "BS",
+            12,
+        )
+        .with_bridge("Moonbeam", "expensive")
+        .register()
+        .unwrap();
+        let router = BridgeRouter::new()
+            .with_provider(FixedFee {
+                name: "expensive",
+                fee: 50,
+                arrives: true,
+            })
+            .with_provider(FixedFee {
+                name: "cheap",
+                fee: 10,
+                arrives: true,
+            });
+
+        let mut request = request("DOT");
+        request.source = source;
+        let (provider, _) = router.quote(&request).await.unwrap();
+        assert_eq!(provider.name(), "expensive");
+
+        request.destination = Chain::Astar;
+        assert!(router.quote(&request).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_router_refunds_lost_transfer() {
+        let router = BridgeRouter::new().with_provider(FixedFee {
+            name: "lossy",
+            fee: 10,
+            arrives: false,
+        });
+        let transfer = router.send(&request("DOT"), Duration::ZERO).await.unwrap();
+        assert_eq!(
+            transfer.refund,
+            Some(RefundOutcome::Refunded("0x02".to_string()))
+        );
+    }
+}
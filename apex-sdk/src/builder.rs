@@ -22,8 +22,20 @@
 //! }
 //! ```
 
+use crate::advanced::DEFAULT_POLL_INTERVAL;
+use crate::attestation::Attestor;
 use crate::error::{Error, Result};
-use crate::sdk::ApexSDK;
+use crate::policy::PolicyEngine;
+use crate::portfolio::PortfolioConfig;
+use crate::sdk::{ApexSDK, DEFAULT_BALANCE_CACHE_TTL, DEFAULT_RECEIPT_TIMEOUT};
+use apex_sdk_evm::signer::DigestSigner;
+use apex_sdk_evm::transaction::DEFAULT_GAS_LIMIT_MULTIPLIER;
+use apex_sdk_evm::wallet::Wallet as EvmWallet;
+use apex_sdk_substrate::Wallet as SubstrateWallet;
+use apex_sdk_types::{Chain, ChainType, ConfirmationPolicy, Finality};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
 
 /// Builder for constructing an ApexSDK instance with customizable configuration.
 ///
@@ -83,7 +95,19 @@ use crate::sdk::ApexSDK;
 pub struct ApexSDKBuilder {
     substrate_endpoint: Option<String>,
     evm_endpoint: Option<String>,
+    chain: Option<Chain>,
     timeout_seconds: Option<u64>,
+    policy: Option<Arc<PolicyEngine>>,
+    attestor: Option<Arc<Attestor>>,
+    event_poll_interval: Option<Duration>,
+    block_finality: Finality,
+    portfolio: PortfolioConfig,
+    confirmation_policies: HashMap<Chain, ConfirmationPolicy>,
+    evm_wallet: Option<EvmWallet>,
+    substrate_wallet: Option<SubstrateWallet>,
+    receipt_timeout: Option<Duration>,
+    balance_cache_ttl: Option<Duration>,
+    gas_limit_multiplier: Option<f64>,
 }
 
 impl ApexSDKBuilder {
@@ -146,6 +170,58 @@ impl ApexSDKBuilder {
         self
     }
 
+    /// Target `chain`, taking its [`endpoints`](Chain::endpoints): those of
+    /// a built-in testnet or of a registered chain's descriptor.
+    ///
+    /// Substrate chains use their first endpoint and EVM chains theirs; a
+    /// hybrid chain takes its first `ws(s)://` endpoint for Substrate and
+    /// first `http(s)://` endpoint for EVM. Built-in mainnets carry no
+    /// endpoints, so theirs must be set separately.
+    ///
+    /// [`build`](Self::build) checks that the endpoints serve `chain`: the
+    /// EVM endpoint by chain ID, the Substrate endpoint by genesis hash.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use apex_sdk::builder::ApexSDKBuilder;
+    /// use apex_sdk::types::registry;
+    ///
+    /// let chains = registry::load_file("chains.json").unwrap();
+    /// let builder = ApexSDKBuilder::new().with_chain(&chains[0]);
+    /// ```
+    pub fn with_chain(mut self, chain: &Chain) -> Self {
+        self.chain = Some(chain.clone());
+        let endpoints = chain.endpoints();
+        let find = |schemes: &[&str]| {
+            endpoints
+                .iter()
+                .find(|url| schemes.iter().any(|scheme| url.starts_with(scheme)))
+                .map(|url| url.to_string())
+        };
+        match chain.chain_type() {
+            ChainType::Substrate => {
+                if let Some(url) = endpoints.first() {
+                    self.substrate_endpoint = Some(url.to_string());
+                }
+            }
+            ChainType::Evm => {
+                if let Some(url) = endpoints.first() {
+                    self.evm_endpoint = Some(url.to_string());
+                }
+            }
+            ChainType::Hybrid => {
+                if let Some(url) = find(&["ws://", "wss://"]) {
+                    self.substrate_endpoint = Some(url);
+                }
+                if let Some(url) = find(&["http://", "https://"]) {
+                    self.evm_endpoint = Some(url);
+                }
+            }
+        }
+        self
+    }
+
     /// Set the connection timeout in seconds.
     ///
     /// This timeout applies to the initial connection attempts to the
@@ -170,6 +246,132 @@ impl ApexSDKBuilder {
         self
     }
 
+    /// Set the policy engine consulted before transactions are signed.
+    ///
+    /// Pass an `Arc` to keep a handle for [`PolicyEngine::reload`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use apex_sdk::builder::ApexSDKBuilder;
+    /// use apex_sdk::policy::{PolicyEngine, SpendLimitPolicy};
+    ///
+    /// let builder = ApexSDKBuilder::new()
+    ///     .with_evm_endpoint("https://mainnet.infura.io/v3/YOUR_KEY")
+    ///     .with_policy_engine(
+    ///         PolicyEngine::new().with_policy(SpendLimitPolicy::new().with_default_limit(1_000)),
+    ///     );
+    /// ```
+    pub fn with_policy_engine(mut self, engine: impl Into<Arc<PolicyEngine>>) -> Self {
+        self.policy = Some(engine.into());
+        self
+    }
+
+    /// Sign an attestation for every submitted transaction.
+    ///
+    /// See [`attestation`](crate::attestation) for the record format and sinks.
+    pub fn with_attestor(mut self, attestor: impl Into<Arc<Attestor>>) -> Self {
+        self.attestor = Some(attestor.into());
+        self
+    }
+
+    /// Set how often EVM blocks are polled for event and block subscriptions.
+    ///
+    /// Defaults to [`DEFAULT_POLL_INTERVAL`]. See [`advanced`](crate::advanced).
+    pub fn with_event_poll_interval(mut self, interval: Duration) -> Self {
+        self.event_poll_interval = Some(interval);
+        self
+    }
+
+    /// Set the finality level block subscriptions report blocks at.
+    ///
+    /// Defaults to [`Finality::Latest`].
+    pub fn with_block_finality(mut self, finality: Finality) -> Self {
+        self.block_finality = finality;
+        self
+    }
+
+    /// Set which balances [`ApexSDK::portfolio`] reads and how it values them.
+    ///
+    /// Defaults to native balances only, unvalued. See [`portfolio`](crate::portfolio).
+    pub fn with_portfolio_config(mut self, config: PortfolioConfig) -> Self {
+        self.portfolio = config;
+        self
+    }
+
+    /// Set when transactions on `chain` count as confirmed.
+    ///
+    /// Defaults to [`ConfirmationPolicy::for_chain`]. Applies to
+    /// [`ApexSDK::get_transaction_status`] and everything waiting on it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use apex_sdk::builder::ApexSDKBuilder;
+    /// use apex_sdk::types::{Chain, ConfirmationPolicy};
+    ///
+    /// let builder = ApexSDKBuilder::new()
+    ///     .with_evm_endpoint("https://mainnet.infura.io/v3/YOUR_KEY")
+    ///     .with_confirmation_policy(Chain::Ethereum, ConfirmationPolicy::Finalized);
+    /// ```
+    pub fn with_confirmation_policy(mut self, chain: Chain, policy: ConfirmationPolicy) -> Self {
+        self.confirmation_policies.insert(chain, policy);
+        self
+    }
+
+    /// Set the wallet signing EVM transactions sent by [`ApexSDK::execute`].
+    ///
+    /// The wallet is bound to the EVM endpoint's chain ID when the SDK is built.
+    pub fn with_evm_wallet(mut self, wallet: EvmWallet) -> Self {
+        self.evm_wallet = Some(wallet);
+        self
+    }
+
+    /// Sign EVM transactions sent by [`ApexSDK::execute`] with a key held in a
+    /// KMS or HSM.
+    ///
+    /// Shorthand for [`with_evm_wallet`](Self::with_evm_wallet) with
+    /// [`EvmWallet::from_signer`].
+    pub fn with_evm_signer(self, signer: impl DigestSigner + 'static) -> Self {
+        self.with_evm_wallet(EvmWallet::from_signer(signer))
+    }
+
+    /// Set the wallet signing Substrate extrinsics sent by [`ApexSDK::execute`].
+    pub fn with_substrate_wallet(mut self, wallet: SubstrateWallet) -> Self {
+        self.substrate_wallet = Some(wallet);
+        self
+    }
+
+    /// Set how long [`ApexSDK::execute`] waits for an EVM transaction to be
+    /// mined.
+    ///
+    /// Defaults to [`DEFAULT_RECEIPT_TIMEOUT`]. Substrate extrinsics are
+    /// always followed until finalized.
+    pub fn with_receipt_timeout(mut self, timeout: Duration) -> Self {
+        self.receipt_timeout = Some(timeout);
+        self
+    }
+
+    /// Set how long [`ApexSDK::get_balance`] results are cached.
+    ///
+    /// Defaults to [`DEFAULT_BALANCE_CACHE_TTL`]; `Duration::ZERO` disables
+    /// caching.
+    pub fn with_balance_cache_ttl(mut self, ttl: Duration) -> Self {
+        self.balance_cache_ttl = Some(ttl);
+        self
+    }
+
+    /// Set the safety margin [`ApexSDK::execute`] applies to estimated EVM
+    /// gas limits, e.g. `1.5` for 50% above the node's estimate.
+    ///
+    /// Defaults to [`DEFAULT_GAS_LIMIT_MULTIPLIER`]. Transactions with a
+    /// [`with_gas_limit`](crate::transaction::TransactionBuilder::with_gas_limit)
+    /// are sent with that limit instead.
+    pub fn with_gas_limit_multiplier(mut self, multiplier: f64) -> Self {
+        self.gas_limit_multiplier = Some(multiplier);
+        self
+    }
+
     /// Build the ApexSDK instance.
     ///
     /// This method consumes the builder and attempts to create an ApexSDK
@@ -182,6 +384,8 @@ impl ApexSDKBuilder {
     /// Returns an error if:
     /// - No adapters are configured
     /// - Connection to any configured endpoint fails
+    /// - An endpoint serves another chain than the one set with
+    ///   [`with_chain`](Self::with_chain)
     ///
     /// # Examples
     ///
@@ -226,9 +430,46 @@ impl ApexSDKBuilder {
             ));
         }
 
+        if let Some(chain) = &self.chain {
+            if chain.evm_chain_id().is_none() && chain.genesis_hash().is_none() {
+                tracing::warn!(
+                    "{} has no known chain ID or genesis hash; its endpoints are not verified",
+                    chain
+                );
+            }
+            if let Some(adapter) = &substrate_adapter {
+                adapter.verify_chain(chain).map_err(Error::Substrate)?;
+            }
+            if let Some(adapter) = &evm_adapter {
+                adapter.verify_chain(chain).await.map_err(Error::Evm)?;
+            }
+        }
+
+        // Wallets default to chain 1; sign for the chain actually connected to
+        let evm_wallet = match (self.evm_wallet, &evm_adapter) {
+            (Some(wallet), Some(adapter)) => Some(wallet.with_chain_id(adapter.chain_id().await?)),
+            (wallet, _) => wallet,
+        };
+
         Ok(ApexSDK {
             substrate_adapter,
             evm_adapter,
+            policy: self.policy,
+            attestor: self.attestor,
+            event_poll_interval: self.event_poll_interval.unwrap_or(DEFAULT_POLL_INTERVAL),
+            events: OnceLock::new(),
+            block_finality: self.block_finality,
+            blocks: OnceLock::new(),
+            portfolio: self.portfolio,
+            confirmation_policies: self.confirmation_policies,
+            evm_wallet,
+            substrate_wallet: self.substrate_wallet,
+            receipt_timeout: self.receipt_timeout.unwrap_or(DEFAULT_RECEIPT_TIMEOUT),
+            balance_cache_ttl: self.balance_cache_ttl.unwrap_or(DEFAULT_BALANCE_CACHE_TTL),
+            gas_limit_multiplier: self
+                .gas_limit_multiplier
+                .unwrap_or(DEFAULT_GAS_LIMIT_MULTIPLIER),
+            balances: Mutex::new(HashMap::new()),
         })
     }
 }
@@ -236,6 +477,7 @@ impl ApexSDKBuilder {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use apex_sdk_types::registry;
 
     #[tokio::test]
     async fn test_builder_new_creates_default_builder() {
@@ -243,6 +485,7 @@ mod tests {
         assert!(builder.substrate_endpoint.is_none());
         assert!(builder.evm_endpoint.is_none());
         assert!(builder.timeout_seconds.is_none());
+        assert!(builder.policy.is_none());
     }
 
     #[tokio::test]
@@ -287,6 +530,55 @@ mod tests {
         assert_eq!(builder.timeout_seconds, Some(120));
     }
 
+    #[test]
+    fn test_builder_with_confirmation_policy() {
+        let builder = ApexSDKBuilder::new()
+            .with_confirmation_policy(Chain::Ethereum, ConfirmationPolicy::Blocks(3));
+        assert_eq!(
+            builder.confirmation_policies.get(&Chain::Ethereum),
+            Some(&ConfirmationPolicy::Blocks(3))
+        );
+        assert!(!builder.confirmation_policies.contains_key(&Chain::Polygon));
+    }
+
+    #[test]
+    fn test_builder_with_chain() {
+        let chains = registry::load_str(
+            r#"{
+                "version": 1,
+                "chains": [{
+                    "name": "Builder Hybrid",
+                    "chain_type": "Hybrid",
+                    "symbol": "BH",
+                    "decimals": 18,
+                    "endpoints": ["https://rpc.example", "wss://ws.example"]
+                }]
+            }"#,
+        )
+        .unwrap();
+
+        let builder = ApexSDKBuilder::new().with_chain(&chains[0]);
+        assert_eq!(
+            builder.substrate_endpoint.as_deref(),
+            Some("wss://ws.example")
+        );
+        assert_eq!(builder.evm_endpoint.as_deref(), Some("https://rpc.example"));
+
+        let builder = ApexSDKBuilder::new().with_chain(&Chain::Ethereum);
+        assert!(builder.evm_endpoint.is_none());
+        assert_eq!(builder.chain, Some(Chain::Ethereum));
+
+        let builder = ApexSDKBuilder::new().with_chain(&Chain::Shibuya);
+        assert_eq!(
+            builder.substrate_endpoint.as_deref(),
+            Some("wss://rpc.shibuya.astar.network")
+        );
+        assert_eq!(
+            builder.evm_endpoint.as_deref(),
+            Some("https://evm.shibuya.astar.network")
+        );
+    }
+
     #[tokio::test]
     async fn test_builder_requires_at_least_one_adapter() {
         let result = ApexSDKBuilder::new().build().await;
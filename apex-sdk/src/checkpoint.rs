@@ -0,0 +1,268 @@
+//! Durable checkpoints for event subscriptions
+//!
+//! A [`Checkpointer`] records, per chain, the position of the last event a
+//! named consumer finished processing. After a restart,
+//! [`EventSubscription::resume`](crate::advanced::EventSubscription::resume)
+//! replays everything after that position from chain history before
+//! continuing live, so no event is missed or processed twice.
+//!
+//! Commit each event after processing it: an event processed but not yet
+//! committed when the service stops is delivered again.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use apex_sdk::advanced::EventItem;
+//! use apex_sdk::checkpoint::{Checkpointer, FileCheckpointStore};
+//! use apex_sdk::prelude::*;
+//! use apex_sdk::types::EventFilter;
+//! use std::sync::Arc;
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<()> {
+//! let sdk = ApexSDK::builder()
+//!     .with_evm_endpoint("https://eth.llamarpc.com")
+//!     .build()
+//!     .await?;
+//!
+//! let checkpointer = Checkpointer::new(
+//!     "transfer-indexer",
+//!     Arc::new(FileCheckpointStore::new("checkpoints.json")),
+//! );
+//! let subscription = sdk.subscribe_events(EventFilter::default());
+//! let mut events = subscription.resume(&checkpointer).await?;
+//! while let Some(EventItem::Event(event)) = events.recv().await {
+//!     // process the event, then record it
+//!     checkpointer.commit(&event).await?;
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::{Error, Result};
+use apex_sdk_types::{ChainType, Event};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, RwLock};
+
+/// Position of the last processed event on a chain
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Checkpoint {
+    /// Block containing the event
+    pub block_number: u64,
+    /// Position of the event within the block
+    pub index: u32,
+}
+
+impl Checkpoint {
+    /// Checkpoint at `event`
+    pub fn of(event: &Event) -> Self {
+        Self {
+            block_number: event.block_number,
+            index: event.index,
+        }
+    }
+
+    /// Whether `event` comes after this checkpoint
+    pub fn precedes(&self, event: &Event) -> bool {
+        (event.block_number, event.index) > (self.block_number, self.index)
+    }
+}
+
+/// Persistent storage for checkpoints
+#[async_trait]
+pub trait CheckpointStore: Send + Sync {
+    /// Load the checkpoint saved under `key`
+    async fn load(&self, key: &str) -> Result<Option<Checkpoint>>;
+
+    /// Save `checkpoint` under `key`, replacing any previous one
+    async fn save(&self, key: &str, checkpoint: Checkpoint) -> Result<()>;
+}
+
+/// Checkpoints kept in memory, for tests and short-lived processes
+#[derive(Default)]
+pub struct MemoryCheckpointStore {
+    checkpoints: RwLock<HashMap<String, Checkpoint>>,
+}
+
+impl MemoryCheckpointStore {
+    /// Create an empty store
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl CheckpointStore for MemoryCheckpointStore {
+    async fn load(&self, key: &str) -> Result<Option<Checkpoint>> {
+        Ok(self
+            .checkpoints
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(key)
+            .copied())
+    }
+
+    async fn save(&self, key: &str, checkpoint: Checkpoint) -> Result<()> {
+        self.checkpoints
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(key.to_string(), checkpoint);
+        Ok(())
+    }
+}
+
+/// Checkpoints kept in a JSON file
+///
+/// Every save rewrites the file through a temporary file and a rename, so a
+/// crash never leaves it half-written.
+pub struct FileCheckpointStore {
+    path: PathBuf,
+    lock: tokio::sync::Mutex<()>,
+}
+
+impl FileCheckpointStore {
+    /// Store checkpoints in the file at `path`, creating it on first save
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            lock: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    async fn read_all(&self) -> Result<HashMap<String, Checkpoint>> {
+        match tokio::fs::read(&self.path).await {
+            Ok(bytes) => {
+                serde_json::from_slice(&bytes).map_err(|e| Error::Serialization(e.to_string()))
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(HashMap::new()),
+            Err(e) => Err(Error::Other(format!(
+                "Failed to read {:?}: {}",
+                self.path, e
+            ))),
+        }
+    }
+}
+
+#[async_trait]
+impl CheckpointStore for FileCheckpointStore {
+    async fn load(&self, key: &str) -> Result<Option<Checkpoint>> {
+        let _guard = self.lock.lock().await;
+        Ok(self.read_all().await?.get(key).copied())
+    }
+
+    async fn save(&self, key: &str, checkpoint: Checkpoint) -> Result<()> {
+        let _guard = self.lock.lock().await;
+        let mut checkpoints = self.read_all().await?;
+        checkpoints.insert(key.to_string(), checkpoint);
+        let bytes = serde_json::to_vec_pretty(&checkpoints)
+            .map_err(|e| Error::Serialization(e.to_string()))?;
+
+        let tmp = self.path.with_extension("tmp");
+        tokio::fs::write(&tmp, bytes)
+            .await
+            .map_err(|e| Error::Other(format!("Failed to write {:?}: {}", tmp, e)))?;
+        tokio::fs::rename(&tmp, &self.path)
+            .await
+            .map_err(|e| Error::Other(format!("Failed to write {:?}: {}", self.path, e)))
+    }
+}
+
+/// Tracks a named consumer's progress through its events
+#[derive(Clone)]
+pub struct Checkpointer {
+    name: String,
+    store: Arc<dyn CheckpointStore>,
+}
+
+impl Checkpointer {
+    /// Track progress for the consumer `name` in `store`
+    pub fn new(name: impl Into<String>, store: Arc<dyn CheckpointStore>) -> Self {
+        Self {
+            name: name.into(),
+            store,
+        }
+    }
+
+    /// The consumer's name
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Last processed position on `chain_type`, if any
+    pub async fn load(&self, chain_type: &ChainType) -> Result<Option<Checkpoint>> {
+        self.store.load(&self.key(chain_type)).await
+    }
+
+    /// Record that `event` and everything before it has been processed
+    pub async fn commit(&self, event: &Event) -> Result<()> {
+        self.store
+            .save(&self.key(&event.chain_type), Checkpoint::of(event))
+            .await
+    }
+
+    fn key(&self, chain_type: &ChainType) -> String {
+        format!("{}/{:?}", self.name, chain_type)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use apex_sdk_types::TransactionEvent;
+
+    fn event(block_number: u64, index: u32) -> Event {
+        Event {
+            chain_type: ChainType::Evm,
+            block_number,
+            block_hash: format!("0x{:064x}", block_number),
+            tx_hash: None,
+            index,
+            origin: None,
+            event: TransactionEvent::EvmLog {
+                address: "0x0000000000000000000000000000000000000000".to_string(),
+                topics: vec![],
+                data: "0x".to_string(),
+                log_index: Some(index as u64),
+            },
+        }
+    }
+
+    #[test]
+    fn test_checkpoint_order() {
+        let checkpoint = Checkpoint::of(&event(10, 2));
+        assert!(checkpoint.precedes(&event(10, 3)));
+        assert!(checkpoint.precedes(&event(11, 0)));
+        assert!(!checkpoint.precedes(&event(10, 2)));
+        assert!(!checkpoint.precedes(&event(9, 5)));
+    }
+
+    #[tokio::test]
+    async fn test_file_store_survives_restart() {
+        let path = std::env::temp_dir().join(format!(
+            "apex-checkpoints-{}-{:?}.json",
+            std::process::id(),
+            std::thread::current().id()
+        ));
+        let _ = std::fs::remove_file(&path);
+
+        let checkpointer = Checkpointer::new("indexer", Arc::new(FileCheckpointStore::new(&path)));
+        assert_eq!(checkpointer.load(&ChainType::Evm).await.unwrap(), None);
+        checkpointer.commit(&event(5, 1)).await.unwrap();
+        checkpointer.commit(&event(7, 0)).await.unwrap();
+
+        let restarted = Checkpointer::new("indexer", Arc::new(FileCheckpointStore::new(&path)));
+        assert_eq!(
+            restarted.load(&ChainType::Evm).await.unwrap(),
+            Some(Checkpoint {
+                block_number: 7,
+                index: 0
+            })
+        );
+        assert_eq!(restarted.load(&ChainType::Substrate).await.unwrap(), None);
+
+        let _ = std::fs::remove_file(&path);
+    }
+}
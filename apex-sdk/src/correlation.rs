@@ -0,0 +1,719 @@
+//! Cross-chain event correlation
+//!
+//! A [`CorrelationEngine`] pairs events observed on a source chain (a bridge
+//! lock, an XCM `Sent`) with the matching events on the destination chain (a
+//! mint, a `MessageQueue.Processed`). Each [`CorrelationRule`] says which
+//! events are involved on either side and how to extract the shared
+//! correlation key from them: a message ID in an EVM topic or a Substrate
+//! field, or a hash of the log payload. Matched pairs update a
+//! [`CrossChainTransaction`], which subscribers receive on every change.
+//!
+//! Events may arrive in any order; a destination event seen before its source
+//! is held until the source shows up.
+//!
+//! A transaction is confirmed as soon as its destination event is seen,
+//! unless the destination chain has a [`ConfirmationPolicy`]: it then stays
+//! pending until blocks fed to [`CorrelationEngine::observe_block`] show the
+//! policy is met.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use apex_sdk::correlation::{CorrelationEngine, CorrelationRule};
+//! use apex_sdk::prelude::*;
+//! use apex_sdk::types::{Chain, EventFilter};
+//! use std::sync::Arc;
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<()> {
+//! let polkadot = ApexSDK::builder()
+//!     .with_substrate_endpoint("wss://polkadot.api.onfinality.io/public-ws")
+//!     .build()
+//!     .await?;
+//!
+//! let engine = Arc::new(
+//!     CorrelationEngine::new().with_rule(CorrelationRule::xcm(Chain::Polkadot, Chain::Moonbeam)),
+//! );
+//! let mut updates = engine.subscribe();
+//!
+//! let xcm = polkadot.subscribe_events(EventFilter::new().pallet("PolkadotXcm"));
+//! tokio::spawn({
+//!     let engine = engine.clone();
+//!     async move { engine.follow(Chain::Polkadot, xcm.subscribe()).await }
+//! });
+//!
+//! while let Ok(transfer) = updates.recv().await {
+//!     println!("{} is {}", transfer.id, transfer.status);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::advanced::{EventItem, EventReceiver};
+use apex_sdk_types::{
+    BlockInfo, Chain, ConfirmationPolicy, CrossChainTransaction, Event, EventFilter, Finality,
+    TransactionEvent, TransactionStatus,
+};
+use ethers::utils::keccak256;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::sync::broadcast;
+
+/// Capacity of the update channel
+const UPDATE_CAPACITY: usize = 1024;
+
+/// Custom correlation key extraction
+pub type KeyFn = Arc<dyn Fn(&Event) -> Option<String> + Send + Sync>;
+
+/// How a correlation key is read from an event
+#[derive(Clone)]
+pub enum KeyExtractor {
+    /// EVM log topic at this position
+    Topic(usize),
+    /// 32-byte word of EVM log data at this position
+    DataWord(usize),
+    /// Keccak-256 hash of the EVM log data
+    PayloadHash,
+    /// First present of these byte fields of a Substrate event
+    Field(Vec<String>),
+    /// Custom extraction
+    Custom(KeyFn),
+}
+
+impl KeyExtractor {
+    /// Substrate byte field `name`
+    pub fn field(name: impl Into<String>) -> Self {
+        KeyExtractor::Field(vec![name.into()])
+    }
+
+    /// The key of `event`, as lowercase 0x-prefixed hex for byte keys
+    pub fn extract(&self, event: &Event) -> Option<String> {
+        match (self, &event.event) {
+            (KeyExtractor::Topic(position), TransactionEvent::EvmLog { topics, .. }) => {
+                topics.get(*position).map(|topic| topic.to_lowercase())
+            }
+            (KeyExtractor::DataWord(position), TransactionEvent::EvmLog { data, .. }) => {
+                let data = data.trim_start_matches("0x");
+                data.get(position * 64..(position + 1) * 64)
+                    .map(|word| format!("0x{}", word.to_lowercase()))
+            }
+            (KeyExtractor::PayloadHash, TransactionEvent::EvmLog { data, .. }) => {
+                let bytes = hex::decode(data.trim_start_matches("0x")).ok()?;
+                Some(format!("0x{}", hex::encode(keccak256(bytes))))
+            }
+            (KeyExtractor::Field(names), TransactionEvent::Substrate { fields, .. }) => names
+                .iter()
+                .find_map(|name| field_bytes(fields, name))
+                .map(|bytes| format!("0x{}", hex::encode(bytes))),
+            (KeyExtractor::Custom(extract), _) => extract(event),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Debug for KeyExtractor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            KeyExtractor::Topic(position) => f.debug_tuple("Topic").field(position).finish(),
+            KeyExtractor::DataWord(position) => f.debug_tuple("DataWord").field(position).finish(),
+            KeyExtractor::PayloadHash => f.write_str("PayloadHash"),
+            KeyExtractor::Field(names) => f.debug_tuple("Field").field(names).finish(),
+            KeyExtractor::Custom(_) => f.write_str("Custom(..)"),
+        }
+    }
+}
+
+/// Which events on two chains belong together
+#[derive(Debug, Clone)]
+pub struct CorrelationRule {
+    name: String,
+    source_chain: Chain,
+    source_filter: EventFilter,
+    source_key: KeyExtractor,
+    destination_chain: Chain,
+    destination_filter: EventFilter,
+    destination_key: KeyExtractor,
+}
+
+impl CorrelationRule {
+    /// Correlate events matching `source_filter` on `source_chain` with events
+    /// matching `destination_filter` on `destination_chain`
+    ///
+    /// Both sides use `key` until changed with
+    /// [`with_destination_key`](Self::with_destination_key).
+    pub fn new(
+        name: impl Into<String>,
+        source_chain: Chain,
+        source_filter: EventFilter,
+        destination_chain: Chain,
+        destination_filter: EventFilter,
+        key: KeyExtractor,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            source_chain,
+            source_filter,
+            source_key: key.clone(),
+            destination_chain,
+            destination_filter,
+            destination_key: key,
+        }
+    }
+
+    /// XCM messages, from `PolkadotXcm.Sent` or `XcmpQueue.XcmpMessageSent`
+    /// to `MessageQueue.Processed`, keyed by message ID
+    pub fn xcm(source_chain: Chain, destination_chain: Chain) -> Self {
+        Self::new(
+            "xcm",
+            source_chain,
+            EventFilter::any([
+                EventFilter::new().pallet("PolkadotXcm").variant("Sent"),
+                EventFilter::new()
+                    .pallet("XcmpQueue")
+                    .variant("XcmpMessageSent"),
+            ]),
+            destination_chain,
+            EventFilter::new()
+                .pallet("MessageQueue")
+                .variant("Processed"),
+            KeyExtractor::Field(vec!["message_id".to_string(), "message_hash".to_string()]),
+        )
+        .with_destination_key(KeyExtractor::field("id"))
+    }
+
+    /// Read the key of destination events with `key`
+    pub fn with_destination_key(mut self, key: KeyExtractor) -> Self {
+        self.destination_key = key;
+        self
+    }
+
+    /// The rule's name
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn source_key(&self, chain: &Chain, event: &Event) -> Option<String> {
+        if *chain != self.source_chain || !self.source_filter.matches(event) {
+            return None;
+        }
+        self.source_key.extract(event)
+    }
+
+    fn destination_key(&self, chain: &Chain, event: &Event) -> Option<String> {
+        if *chain != self.destination_chain || !self.destination_filter.matches(event) {
+            return None;
+        }
+        self.destination_key.extract(event)
+    }
+}
+
+#[derive(Default)]
+struct State {
+    /// Transactions by rule index and correlation key
+    transactions: HashMap<(usize, String), CrossChainTransaction>,
+    /// Destination events whose source has not been seen yet
+    orphans: HashMap<(usize, String), Event>,
+    /// Completed transactions waiting for their confirmation policy
+    settling: HashMap<(usize, String), Settling>,
+}
+
+/// Where and when a settling transaction's destination event was seen
+struct Settling {
+    block_number: u64,
+    seen_at: u64,
+}
+
+/// Matches source and destination events into cross-chain transactions
+pub struct CorrelationEngine {
+    rules: Vec<CorrelationRule>,
+    confirmation_policies: HashMap<Chain, ConfirmationPolicy>,
+    state: Mutex<State>,
+    updates: broadcast::Sender<CrossChainTransaction>,
+}
+
+impl Default for CorrelationEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CorrelationEngine {
+    /// Create an engine without rules
+    pub fn new() -> Self {
+        let (updates, _) = broadcast::channel(UPDATE_CAPACITY);
+        Self {
+            rules: Vec::new(),
+            confirmation_policies: HashMap::new(),
+            state: Mutex::new(State::default()),
+            updates,
+        }
+    }
+
+    /// Add a correlation rule
+    pub fn with_rule(mut self, rule: CorrelationRule) -> Self {
+        self.rules.push(rule);
+        self
+    }
+
+    /// Hold transactions completed on `chain` until `policy` is met
+    ///
+    /// See [`ConfirmationPolicy::for_chain`] for per-chain defaults.
+    pub fn with_confirmation_policy(mut self, chain: Chain, policy: ConfirmationPolicy) -> Self {
+        self.confirmation_policies.insert(chain, policy);
+        self
+    }
+
+    /// Receive every created or updated transaction
+    pub fn subscribe(&self) -> broadcast::Receiver<CrossChainTransaction> {
+        self.updates.subscribe()
+    }
+
+    /// Process an event observed on `chain`
+    ///
+    /// Returns the transactions the event created or updated.
+    pub fn observe(&self, chain: &Chain, event: &Event) -> Vec<CrossChainTransaction> {
+        let mut updated = Vec::new();
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+
+        for (index, rule) in self.rules.iter().enumerate() {
+            if let Some(key) = rule.source_key(chain, event) {
+                let entry = (index, key.clone());
+                if state.transactions.contains_key(&entry) {
+                    continue;
+                }
+                let mut tx = CrossChainTransaction {
+                    id: key,
+                    source_chain: rule.source_chain.clone(),
+                    destination_chain: rule.destination_chain.clone(),
+                    source_tx_hash: Some(event_hash(event)),
+                    destination_tx_hash: None,
+                    status: TransactionStatus::Pending,
+                    timestamp: now_secs(),
+                };
+                if let Some(destination) = state.orphans.remove(&entry) {
+                    self.complete(&mut state, &entry, &mut tx, &destination);
+                }
+                state.transactions.insert(entry, tx.clone());
+                updated.push(tx);
+            }
+
+            if let Some(key) = rule.destination_key(chain, event) {
+                let entry = (index, key);
+                match state.transactions.get(&entry) {
+                    Some(tx) if tx.destination_tx_hash.is_none() => {
+                        let mut tx = tx.clone();
+                        self.complete(&mut state, &entry, &mut tx, event);
+                        state.transactions.insert(entry, tx.clone());
+                        updated.push(tx);
+                    }
+                    Some(_) => {}
+                    None => {
+                        state.orphans.insert(entry, event.clone());
+                    }
+                }
+            }
+        }
+        drop(state);
+
+        for tx in &updated {
+            let _ = self.updates.send(tx.clone());
+        }
+        updated
+    }
+
+    /// Register a transaction submitted by the application
+    ///
+    /// `tx.id` must be its correlation key under the rule named `rule`, so
+    /// that the destination event completes it. Returns `false` if there is
+    /// no such rule.
+    pub fn track(&self, rule: &str, tx: CrossChainTransaction) -> bool {
+        let Some(index) = self.rules.iter().position(|r| r.name == rule) else {
+            return false;
+        };
+        let mut tx = tx;
+        let entry = (index, tx.id.to_lowercase());
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(destination) = state.orphans.remove(&entry) {
+            self.complete(&mut state, &entry, &mut tx, &destination);
+        }
+        state.transactions.insert(entry, tx);
+        true
+    }
+
+    /// Process a block observed on `chain`
+    ///
+    /// Returns the transactions completed on `chain` that the block
+    /// confirms under the chain's confirmation policy.
+    pub fn observe_block(&self, chain: &Chain, block: &BlockInfo) -> Vec<CrossChainTransaction> {
+        let Some(policy) = self.confirmation_policies.get(chain) else {
+            return Vec::new();
+        };
+        let now = now_secs();
+        let mut updated = Vec::new();
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let State {
+            transactions,
+            settling,
+            ..
+        } = &mut *state;
+
+        settling.retain(|entry, seen| {
+            let Some(tx) = transactions.get_mut(entry) else {
+                return false;
+            };
+            if tx.destination_chain != *chain {
+                return true;
+            }
+            let confirmations =
+                u32::try_from(block.number.saturating_sub(seen.block_number)).unwrap_or(u32::MAX);
+            let finalized =
+                block.finality == Finality::Finalized && block.number >= seen.block_number;
+            let age = Duration::from_secs(now.saturating_sub(seen.seen_at));
+            if !policy.is_met(confirmations, finalized, Some(age)) {
+                return true;
+            }
+            tx.status = TransactionStatus::Confirmed {
+                block_number: seen.block_number,
+                confirmations,
+            };
+            updated.push(tx.clone());
+            false
+        });
+        drop(state);
+
+        for tx in &updated {
+            let _ = self.updates.send(tx.clone());
+        }
+        updated
+    }
+
+    /// Record `destination` as the completion of `tx`, leaving it pending
+    /// until the destination chain's confirmation policy is met
+    fn complete(
+        &self,
+        state: &mut State,
+        entry: &(usize, String),
+        tx: &mut CrossChainTransaction,
+        destination: &Event,
+    ) {
+        tx.destination_tx_hash = Some(event_hash(destination));
+        if !succeeded(destination) {
+            tx.status = TransactionStatus::Failed {
+                error: format!("{} reported failure", destination.name()),
+            };
+            return;
+        }
+
+        let policy = self
+            .confirmation_policies
+            .get(&tx.destination_chain)
+            .copied()
+            .unwrap_or_default();
+        if policy.is_met(0, false, Some(Duration::ZERO)) {
+            tx.status = TransactionStatus::Confirmed {
+                block_number: destination.block_number,
+                confirmations: 0,
+            };
+        } else {
+            state.settling.insert(
+                entry.clone(),
+                Settling {
+                    block_number: destination.block_number,
+                    seen_at: now_secs(),
+                },
+            );
+        }
+    }
+
+    /// The transaction with correlation key `id`
+    pub fn get(&self, id: &str) -> Option<CrossChainTransaction> {
+        let id = id.to_lowercase();
+        self.state
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .transactions
+            .iter()
+            .find(|((_, key), _)| *key == id)
+            .map(|(_, tx)| tx.clone())
+    }
+
+    /// Transactions whose destination event has not been seen yet
+    pub fn pending(&self) -> Vec<CrossChainTransaction> {
+        self.state
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .transactions
+            .values()
+            .filter(|tx| tx.destination_tx_hash.is_none())
+            .cloned()
+            .collect()
+    }
+
+    /// Process events from `receiver`, observed on `chain`, until it closes
+    pub async fn follow(&self, chain: Chain, mut receiver: EventReceiver) {
+        while let Some(item) = receiver.recv().await {
+            match item {
+                EventItem::Event(event) => {
+                    self.observe(&chain, &event);
+                }
+                EventItem::Lagged(missed) => {
+                    tracing::warn!("Correlation on {} missed {} events", chain, missed)
+                }
+            }
+        }
+    }
+
+    /// Process blocks from `receiver`, observed on `chain`, until it closes
+    pub async fn follow_blocks(&self, chain: Chain, mut receiver: broadcast::Receiver<BlockInfo>) {
+        loop {
+            match receiver.recv().await {
+                Ok(block) => {
+                    self.observe_block(&chain, &block);
+                }
+                Err(broadcast::error::RecvError::Lagged(missed)) => {
+                    tracing::warn!("Correlation on {} missed {} blocks", chain, missed)
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    }
+}
+
+/// Whether a destination event reports success (Substrate `success: false` does not)
+fn succeeded(event: &Event) -> bool {
+    match &event.event {
+        TransactionEvent::Substrate { fields, .. } => {
+            field_value(fields, "success").is_none_or(|value| !value.starts_with("false"))
+        }
+        TransactionEvent::EvmLog { .. } => true,
+    }
+}
+
+/// Transaction hash of an event, falling back to its block hash
+fn event_hash(event: &Event) -> String {
+    event
+        .tx_hash
+        .clone()
+        .unwrap_or_else(|| event.block_hash.clone())
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/// Text following `name: ` in decoded Substrate event fields
+fn field_value<'a>(fields: &'a str, name: &str) -> Option<&'a str> {
+    let pattern = format!("{}: ", name);
+    fields.match_indices(&pattern).find_map(|(at, _)| {
+        let standalone = fields[..at]
+            .chars()
+            .next_back()
+            .is_none_or(|c| !c.is_alphanumeric() && c != '_');
+        standalone.then(|| &fields[at + pattern.len()..])
+    })
+}
+
+/// Bytes of field `name`, written either as hex or as a tuple of byte values
+fn field_bytes(fields: &str, name: &str) -> Option<Vec<u8>> {
+    let value = field_value(fields, name)?.trim_start_matches(['(', ' ']);
+    if let Some(hex) = value.strip_prefix("0x") {
+        let end = hex
+            .find(|c: char| !c.is_ascii_hexdigit())
+            .unwrap_or(hex.len());
+        return hex::decode(&hex[..end]).ok();
+    }
+    let end = value.find(')')?;
+    value[..end]
+        .split(',')
+        .map(|byte| byte.trim().parse::<u8>().ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use apex_sdk_types::ChainType;
+
+    const LOCK_TOPIC: &str = "0x1111111111111111111111111111111111111111111111111111111111111111";
+    const MINT_TOPIC: &str = "0x2222222222222222222222222222222222222222222222222222222222222222";
+    const MESSAGE_ID: &str = "0xABABABABABABABABABABABABABABABABABABABABABABABABABABABABABABABAB";
+
+    fn log(block_number: u64, topics: &[&str], tx_hash: &str) -> Event {
+        Event {
+            chain_type: ChainType::Evm,
+            block_number,
+            block_hash: format!("0x{:064x}", block_number),
+            tx_hash: Some(tx_hash.to_string()),
+            index: 0,
+            origin: None,
+            event: TransactionEvent::EvmLog {
+                address: "0x0000000000000000000000000000000000000000".to_string(),
+                topics: topics.iter().map(|t| t.to_string()).collect(),
+                data: "0x".to_string(),
+                log_index: Some(0),
+            },
+        }
+    }
+
+    fn substrate(pallet: &str, variant: &str, fields: String) -> Event {
+        Event {
+            chain_type: ChainType::Substrate,
+            block_number: 9,
+            block_hash: format!("0x{:064x}", 9),
+            tx_hash: None,
+            index: 3,
+            origin: None,
+            event: TransactionEvent::Substrate {
+                pallet: pallet.to_string(),
+                variant: variant.to_string(),
+                fields,
+            },
+        }
+    }
+
+    fn bridge() -> CorrelationEngine {
+        CorrelationEngine::new().with_rule(CorrelationRule::new(
+            "bridge",
+            Chain::Ethereum,
+            EventFilter::new().topic(0, LOCK_TOPIC),
+            Chain::Polygon,
+            EventFilter::new().topic(0, MINT_TOPIC),
+            KeyExtractor::Topic(1),
+        ))
+    }
+
+    #[test]
+    fn test_lock_then_mint() {
+        let engine = bridge();
+        let mut updates = engine.subscribe();
+
+        let lock = log(5, &[LOCK_TOPIC, MESSAGE_ID], "0xaa");
+        assert!(engine.observe(&Chain::Polygon, &lock).is_empty());
+        let created = engine.observe(&Chain::Ethereum, &lock);
+        assert_eq!(created.len(), 1);
+        assert_eq!(created[0].id, MESSAGE_ID.to_lowercase());
+        assert_eq!(created[0].status, TransactionStatus::Pending);
+        assert_eq!(engine.pending().len(), 1);
+
+        let mint = log(40, &[MINT_TOPIC, MESSAGE_ID], "0xbb");
+        let completed = engine.observe(&Chain::Polygon, &mint);
+        assert_eq!(completed[0].source_tx_hash.as_deref(), Some("0xaa"));
+        assert_eq!(completed[0].destination_tx_hash.as_deref(), Some("0xbb"));
+        assert!(matches!(
+            completed[0].status,
+            TransactionStatus::Confirmed {
+                block_number: 40,
+                ..
+            }
+        ));
+        assert!(engine.pending().is_empty());
+        assert_eq!(
+            updates.try_recv().unwrap().status,
+            TransactionStatus::Pending
+        );
+        assert_eq!(
+            updates.try_recv().unwrap().destination_tx_hash.as_deref(),
+            Some("0xbb")
+        );
+    }
+
+    #[test]
+    fn test_destination_confirmation_policy() {
+        let engine =
+            bridge().with_confirmation_policy(Chain::Polygon, ConfirmationPolicy::Blocks(3));
+        let block = |number, finality| BlockInfo {
+            chain_type: ChainType::Evm,
+            number,
+            hash: format!("0x{:064x}", number),
+            parent_hash: format!("0x{:064x}", number - 1),
+            timestamp: None,
+            finality,
+        };
+
+        engine.observe(&Chain::Ethereum, &log(5, &[LOCK_TOPIC, MESSAGE_ID], "0xaa"));
+        let completed =
+            engine.observe(&Chain::Polygon, &log(40, &[MINT_TOPIC, MESSAGE_ID], "0xbb"));
+        assert_eq!(completed[0].destination_tx_hash.as_deref(), Some("0xbb"));
+        assert_eq!(completed[0].status, TransactionStatus::Pending);
+
+        assert!(engine
+            .observe_block(&Chain::Ethereum, &block(43, Finality::Latest))
+            .is_empty());
+        assert!(engine
+            .observe_block(&Chain::Polygon, &block(42, Finality::Latest))
+            .is_empty());
+        let confirmed = engine.observe_block(&Chain::Polygon, &block(43, Finality::Latest));
+        assert_eq!(
+            confirmed[0].status,
+            TransactionStatus::Confirmed {
+                block_number: 40,
+                confirmations: 3
+            }
+        );
+        assert!(engine
+            .observe_block(&Chain::Polygon, &block(44, Finality::Latest))
+            .is_empty());
+    }
+
+    #[test]
+    fn test_mint_before_lock() {
+        let engine = bridge();
+        let mint = log(40, &[MINT_TOPIC, MESSAGE_ID], "0xbb");
+        assert!(engine.observe(&Chain::Polygon, &mint).is_empty());
+
+        let lock = log(5, &[LOCK_TOPIC, MESSAGE_ID], "0xaa");
+        let created = engine.observe(&Chain::Ethereum, &lock);
+        assert_eq!(created[0].destination_tx_hash.as_deref(), Some("0xbb"));
+        assert!(engine.get(MESSAGE_ID).is_some());
+    }
+
+    #[test]
+    fn test_xcm_message_ids() {
+        let engine = CorrelationEngine::new()
+            .with_rule(CorrelationRule::xcm(Chain::Polkadot, Chain::Moonbeam));
+        let id = [0xab_u8; 32];
+        let tuple = id.map(|b| b.to_string()).join(", ");
+
+        let sent = substrate(
+            "PolkadotXcm",
+            "Sent",
+            format!(
+                "{{ origin: (), destination: (), message: (), message_id: (({})) }}",
+                tuple
+            ),
+        );
+        let created = engine.observe(&Chain::Polkadot, &sent);
+        assert_eq!(created[0].id, format!("0x{}", hex::encode(id)));
+
+        let processed = substrate(
+            "MessageQueue",
+            "Processed",
+            format!(
+                "{{ id: ({}), origin: Parent, weight_used: (), success: false }}",
+                tuple
+            ),
+        );
+        let completed = engine.observe(&Chain::Moonbeam, &processed);
+        assert!(matches!(
+            completed[0].status,
+            TransactionStatus::Failed { .. }
+        ));
+    }
+
+    #[test]
+    fn test_field_bytes() {
+        assert_eq!(
+            field_bytes("{ message_id: 0x0102, id: (3, 4) }", "id"),
+            Some(vec![3, 4])
+        );
+        assert_eq!(
+            field_bytes("{ message_id: 0x0102, id: (3, 4) }", "message_id"),
+            Some(vec![1, 2])
+        );
+        assert_eq!(field_bytes("{ id: 7 }", "message_id"), None);
+    }
+}
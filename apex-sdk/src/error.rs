@@ -28,6 +28,10 @@ pub enum Error {
     #[error("Invalid address format: {0}")]
     InvalidAddress(String),
 
+    /// Rejected by the transaction policy engine
+    #[error("Policy violation: {0}")]
+    Policy(String),
+
     /// Substrate adapter error
     #[error("Substrate adapter error: {0}")]
     Substrate(#[from] apex_sdk_substrate::Error),
@@ -85,6 +89,12 @@ mod tests {
         assert_eq!(error.to_string(), "Invalid address format: 0xinvalid");
     }
 
+    #[test]
+    fn test_policy_error_display() {
+        let error = Error::Policy("spend-limit: exceeded".to_string());
+        assert_eq!(error.to_string(), "Policy violation: spend-limit: exceeded");
+    }
+
     #[test]
     fn test_serialization_error_display() {
         let error = Error::Serialization("JSON parse error".to_string());
@@ -0,0 +1,784 @@
+//! Multi-chain governance
+//!
+//! A [`GovernanceSource`] reads the proposals of one governance system and
+//! votes on them. Proposals from every source are normalized into
+//! [`Proposal`]s, so [`Governance`] can list, tally and vote across chains
+//! in one place.
+//!
+//! Two sources are provided: [`OpenGovSource`] for Substrate OpenGov
+//! referenda, and [`GovernorSource`] for OpenZeppelin Governor-style EVM
+//! contracts.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use apex_sdk::evm::{wallet::Wallet as EvmWallet, EvmAdapter};
+//! use apex_sdk::governance::{Governance, GovernorSource, OpenGovSource, VoteChoice};
+//! use apex_sdk::substrate::{KeyPairType, SubstrateAdapter, Wallet};
+//! use apex_sdk::types::Chain;
+//!
+//! # #[tokio::main]
+//! # async fn main() -> apex_sdk::Result<()> {
+//! let polkadot = SubstrateAdapter::connect("wss://polkadot.api.onfinality.io/public-ws").await?;
+//! let ethereum = EvmAdapter::connect("https://eth.llamarpc.com").await?;
+//!
+//! let governance = Governance::new()
+//!     .with_source(
+//!         OpenGovSource::new(Chain::Polkadot, polkadot).with_voter(
+//!             Wallet::from_mnemonic("your mnemonic here", KeyPairType::Sr25519)?,
+//!             10_000_000_000, // 1 DOT
+//!         ),
+//!     )
+//!     .with_source(
+//!         GovernorSource::new(
+//!             Chain::Ethereum,
+//!             ethereum,
+//!             "0x408ED6354d4973f66138C91495F2f2FCbd8724C3",
+//!         )
+//!         .with_voter(EvmWallet::from_private_key("0x...")?.with_chain_id(1)),
+//!     );
+//!
+//! for proposal in governance.active_proposals().await {
+//!     println!("{} #{}: {:?}", proposal.chain, proposal.id, proposal.title);
+//!     governance.vote(&proposal, VoteChoice::Aye).await?;
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::{Error, Result};
+use apex_sdk_evm::wallet::Wallet as EvmWallet;
+use apex_sdk_evm::EvmAdapter;
+use apex_sdk_substrate::{
+    AccountVote, Conviction, Referendum, ReferendumStatus, SubstrateAdapter, Wallet,
+};
+use apex_sdk_types::{Chain, TransactionEvent};
+use async_trait::async_trait;
+use ethers::abi::{self, ParamType, Token};
+use ethers::types::{Address as EthAddress, U256};
+use std::sync::Arc;
+
+/// State of a proposal, common to all governance systems
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProposalStatus {
+    /// Submitted, voting has not started
+    Pending,
+    /// Open for voting
+    Active,
+    /// Passed, awaiting execution
+    Succeeded,
+    /// Passed and queued for execution
+    Queued,
+    /// Passed and executed
+    Executed,
+    /// Failed
+    Defeated,
+    /// Withdrawn or killed
+    Cancelled,
+    /// Lapsed without a decision or execution
+    Expired,
+}
+
+impl ProposalStatus {
+    /// Whether votes can still be cast
+    pub fn is_open(&self) -> bool {
+        matches!(self, ProposalStatus::Pending | ProposalStatus::Active)
+    }
+}
+
+/// Votes cast on a proposal, in the voting token's smallest unit
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Tally {
+    /// Votes in favour
+    pub ayes: u128,
+    /// Votes against
+    pub nays: u128,
+    /// Abstentions, where tracked separately
+    pub abstain: u128,
+}
+
+impl Tally {
+    /// Share of ayes among ayes and nays, `None` before any vote
+    pub fn approval(&self) -> Option<f64> {
+        let decided = self.ayes.saturating_add(self.nays);
+        (decided > 0).then(|| self.ayes as f64 / decided as f64)
+    }
+}
+
+impl std::ops::AddAssign for Tally {
+    fn add_assign(&mut self, other: Self) {
+        self.ayes = self.ayes.saturating_add(other.ayes);
+        self.nays = self.nays.saturating_add(other.nays);
+        self.abstain = self.abstain.saturating_add(other.abstain);
+    }
+}
+
+impl std::iter::Sum for Tally {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Tally::default(), |mut total, tally| {
+            total += tally;
+            total
+        })
+    }
+}
+
+/// A proposal from any governance system
+#[derive(Debug, Clone, PartialEq)]
+pub struct Proposal {
+    /// Chain the proposal lives on
+    pub chain: Chain,
+    /// Name of the source that reported it
+    pub source: String,
+    /// Identifier within the source (referendum index, Governor proposal ID)
+    pub id: String,
+    /// Title, where the source records one
+    pub title: Option<String>,
+    /// Account that submitted the proposal, where known
+    pub proposer: Option<String>,
+    /// Current state
+    pub status: ProposalStatus,
+    /// Current votes
+    pub tally: Tally,
+    /// Block at which voting closes, where known
+    pub voting_ends: Option<u64>,
+}
+
+/// Direction of a vote
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VoteChoice {
+    /// In favour
+    Aye,
+    /// Against
+    Nay,
+    /// Neither, but counted towards participation
+    Abstain,
+}
+
+/// A governance system on one chain
+#[async_trait]
+pub trait GovernanceSource: Send + Sync {
+    /// Source name
+    fn name(&self) -> &str;
+
+    /// Chain the source governs
+    fn chain(&self) -> &Chain;
+
+    /// All proposals the source knows of, newest first
+    async fn proposals(&self) -> Result<Vec<Proposal>>;
+
+    /// Proposal `id`, `None` if it does not exist
+    async fn proposal(&self, id: &str) -> Result<Option<Proposal>>;
+
+    /// Vote on proposal `id`, returning the transaction hash
+    async fn vote(&self, id: &str, choice: VoteChoice) -> Result<String>;
+}
+
+/// Proposals and voting across governance sources
+#[derive(Default)]
+pub struct Governance {
+    sources: Vec<Arc<dyn GovernanceSource>>,
+}
+
+impl Governance {
+    /// Create an aggregator without sources
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a source
+    pub fn with_source(mut self, source: impl GovernanceSource + 'static) -> Self {
+        self.sources.push(Arc::new(source));
+        self
+    }
+
+    /// Proposals from every source
+    ///
+    /// Sources that fail are logged and skipped.
+    pub async fn proposals(&self) -> Vec<Proposal> {
+        let mut proposals = Vec::new();
+        for source in &self.sources {
+            match source.proposals().await {
+                Ok(found) => proposals.extend(found),
+                Err(e) => tracing::warn!(
+                    "Governance source {} on {} failed: {}",
+                    source.name(),
+                    source.chain(),
+                    e
+                ),
+            }
+        }
+        proposals
+    }
+
+    /// Proposals that can still be voted on
+    pub async fn active_proposals(&self) -> Vec<Proposal> {
+        let mut proposals = self.proposals().await;
+        proposals.retain(|p| p.status.is_open());
+        proposals
+    }
+
+    /// Combined votes of `proposals`, e.g. the same motion put to several chains
+    pub fn combined_tally(proposals: &[Proposal]) -> Tally {
+        proposals.iter().map(|p| p.tally).sum()
+    }
+
+    /// Vote on `proposal` through the source that reported it
+    pub async fn vote(&self, proposal: &Proposal, choice: VoteChoice) -> Result<String> {
+        let source = self
+            .sources
+            .iter()
+            .find(|s| s.name() == proposal.source && *s.chain() == proposal.chain)
+            .ok_or_else(|| {
+                Error::UnsupportedChain(format!(
+                    "No governance source {} on {}",
+                    proposal.source, proposal.chain
+                ))
+            })?;
+        source.vote(&proposal.id, choice).await
+    }
+
+    /// Cast the same vote on each of `proposals`, returning one result each
+    pub async fn vote_all(
+        &self,
+        proposals: &[Proposal],
+        choice: VoteChoice,
+    ) -> Vec<Result<String>> {
+        let mut results = Vec::with_capacity(proposals.len());
+        for proposal in proposals {
+            results.push(self.vote(proposal, choice).await);
+        }
+        results
+    }
+}
+
+/// Substrate OpenGov referenda
+///
+/// Referenda carry no on-chain title, so [`Proposal::title`] is `None`.
+/// OpenGov does not tally abstentions separately.
+pub struct OpenGovSource {
+    chain: Chain,
+    adapter: SubstrateAdapter,
+    voter: Option<(Wallet, u128)>,
+    conviction: Conviction,
+}
+
+impl OpenGovSource {
+    /// Read referenda on `chain`
+    pub fn new(chain: Chain, adapter: SubstrateAdapter) -> Self {
+        Self {
+            chain,
+            adapter,
+            voter: None,
+            conviction: Conviction::None,
+        }
+    }
+
+    /// Vote from `wallet` with `balance` Planck
+    pub fn with_voter(mut self, wallet: Wallet, balance: u128) -> Self {
+        self.voter = Some((wallet, balance));
+        self
+    }
+
+    /// Set the conviction of aye and nay votes
+    pub fn with_conviction(mut self, conviction: Conviction) -> Self {
+        self.conviction = conviction;
+        self
+    }
+
+    fn to_proposal(&self, referendum: Referendum) -> Proposal {
+        let tally = referendum.tally.unwrap_or_default();
+        Proposal {
+            chain: self.chain.clone(),
+            source: self.name().to_string(),
+            id: referendum.index.to_string(),
+            title: None,
+            proposer: None,
+            status: match referendum.status {
+                ReferendumStatus::Preparing => ProposalStatus::Pending,
+                ReferendumStatus::Deciding => ProposalStatus::Active,
+                ReferendumStatus::Approved => ProposalStatus::Succeeded,
+                ReferendumStatus::Rejected => ProposalStatus::Defeated,
+                ReferendumStatus::Cancelled | ReferendumStatus::Killed => ProposalStatus::Cancelled,
+                ReferendumStatus::TimedOut => ProposalStatus::Expired,
+            },
+            tally: Tally {
+                ayes: tally.ayes,
+                nays: tally.nays,
+                abstain: 0,
+            },
+            voting_ends: None,
+        }
+    }
+}
+
+#[async_trait]
+impl GovernanceSource for OpenGovSource {
+    fn name(&self) -> &str {
+        "opengov"
+    }
+
+    fn chain(&self) -> &Chain {
+        &self.chain
+    }
+
+    async fn proposals(&self) -> Result<Vec<Proposal>> {
+        let referenda = self.adapter.open_gov().referenda().await?;
+        Ok(referenda.into_iter().map(|r| self.to_proposal(r)).collect())
+    }
+
+    async fn proposal(&self, id: &str) -> Result<Option<Proposal>> {
+        let index = parse_referendum_index(id)?;
+        let referendum = self.adapter.open_gov().referendum(index).await?;
+        Ok(referendum.map(|r| self.to_proposal(r)))
+    }
+
+    async fn vote(&self, id: &str, choice: VoteChoice) -> Result<String> {
+        let (wallet, balance) = self
+            .voter
+            .as_ref()
+            .ok_or_else(|| Error::Config("OpenGov source has no voter".to_string()))?;
+        let vote = match choice {
+            VoteChoice::Aye | VoteChoice::Nay => AccountVote::Standard {
+                aye: choice == VoteChoice::Aye,
+                conviction: self.conviction,
+                balance: *balance,
+            },
+            VoteChoice::Abstain => AccountVote::SplitAbstain {
+                aye: 0,
+                nay: 0,
+                abstain: *balance,
+            },
+        };
+
+        Ok(self
+            .adapter
+            .open_gov()
+            .vote(wallet, parse_referendum_index(id)?, vote)
+            .await?)
+    }
+}
+
+/// Event signature of a Governor's `ProposalCreated`
+const PROPOSAL_CREATED: &str =
+    "ProposalCreated(uint256,address,address[],uint256[],string[],bytes[],uint256,uint256,string)";
+
+/// Most blocks scanned in one `eth_getLogs` request
+const LOG_CHUNK: u64 = 10_000;
+
+/// An OpenZeppelin Governor-style contract with simple vote counting
+///
+/// Proposals are discovered from `ProposalCreated` logs within the
+/// lookback window; the first line of the description becomes the title.
+pub struct GovernorSource {
+    chain: Chain,
+    adapter: EvmAdapter,
+    governor: String,
+    voter: Option<EvmWallet>,
+    lookback: u64,
+}
+
+impl GovernorSource {
+    /// Read proposals of the Governor at `governor` on `chain`
+    pub fn new(chain: Chain, adapter: EvmAdapter, governor: impl Into<String>) -> Self {
+        Self {
+            chain,
+            adapter,
+            governor: governor.into(),
+            voter: None,
+            lookback: 200_000,
+        }
+    }
+
+    /// Vote from `wallet`, whose voting power is its delegated token balance
+    pub fn with_voter(mut self, wallet: EvmWallet) -> Self {
+        self.voter = Some(wallet);
+        self
+    }
+
+    /// Set how many recent blocks are searched for proposals
+    pub fn with_lookback(mut self, blocks: u64) -> Self {
+        self.lookback = blocks;
+        self
+    }
+
+    async fn call(
+        &self,
+        signature: &str,
+        args: &[Token],
+        output: &[ParamType],
+    ) -> Result<Vec<Token>> {
+        let output_bytes = self
+            .adapter
+            .call_contract(&self.governor, call_data(signature, args))
+            .await?;
+        abi::decode(output, &output_bytes)
+            .map_err(|e| Error::Transaction(format!("Unexpected {} response: {}", signature, e)))
+    }
+
+    /// Current state and votes of proposal `id`, `None` if it does not exist
+    async fn status(&self, id: U256) -> Result<Option<(ProposalStatus, Tally)>> {
+        let state = match self
+            .call("state(uint256)", &[Token::Uint(id)], &[ParamType::Uint(8)])
+            .await
+        {
+            Ok(tokens) => match tokens.as_slice() {
+                [Token::Uint(state)] => state.as_u32(),
+                _ => return Ok(None),
+            },
+            // state() reverts for unknown proposals
+            Err(_) => return Ok(None),
+        };
+        let status = match state {
+            0 => ProposalStatus::Pending,
+            1 => ProposalStatus::Active,
+            2 => ProposalStatus::Cancelled,
+            3 => ProposalStatus::Defeated,
+            4 => ProposalStatus::Succeeded,
+            5 => ProposalStatus::Queued,
+            6 => ProposalStatus::Expired,
+            7 => ProposalStatus::Executed,
+            other => {
+                return Err(Error::Other(format!(
+                    "Unknown Governor proposal state {}",
+                    other
+                )))
+            }
+        };
+
+        let votes = self
+            .call(
+                "proposalVotes(uint256)",
+                &[Token::Uint(id)],
+                &[
+                    ParamType::Uint(256),
+                    ParamType::Uint(256),
+                    ParamType::Uint(256),
+                ],
+            )
+            .await?;
+        let amount = |token: Option<&Token>| match token {
+            Some(Token::Uint(n)) => u128::try_from(*n).unwrap_or(u128::MAX),
+            _ => 0,
+        };
+        Ok(Some((
+            status,
+            Tally {
+                nays: amount(votes.first()),
+                ayes: amount(votes.get(1)),
+                abstain: amount(votes.get(2)),
+            },
+        )))
+    }
+}
+
+#[async_trait]
+impl GovernanceSource for GovernorSource {
+    fn name(&self) -> &str {
+        "governor"
+    }
+
+    fn chain(&self) -> &Chain {
+        &self.chain
+    }
+
+    async fn proposals(&self) -> Result<Vec<Proposal>> {
+        let latest = self.adapter.block_number().await?;
+        let topic = format!(
+            "{:?}",
+            ethers::types::H256(ethers::utils::keccak256(PROPOSAL_CREATED))
+        );
+
+        let mut proposals = Vec::new();
+        let mut from = latest.saturating_sub(self.lookback);
+        while from <= latest {
+            let to = (from + LOG_CHUNK - 1).min(latest);
+            for event in self
+                .adapter
+                .get_contract_logs(&self.governor, from, to)
+                .await?
+            {
+                let TransactionEvent::EvmLog { topics, data, .. } = &event.event else {
+                    continue;
+                };
+                if topics.first() != Some(&topic) {
+                    continue;
+                }
+                let Some(created) = decode_proposal_created(data) else {
+                    tracing::debug!("Skipping undecodable ProposalCreated log");
+                    continue;
+                };
+                if let Some((status, tally)) = self.status(created.id).await? {
+                    proposals.push(Proposal {
+                        chain: self.chain.clone(),
+                        source: self.name().to_string(),
+                        id: created.id.to_string(),
+                        title: created.title,
+                        proposer: Some(format!("{:?}", created.proposer)),
+                        status,
+                        tally,
+                        voting_ends: Some(created.vote_end),
+                    });
+                }
+            }
+            from = to + 1;
+        }
+
+        proposals.reverse();
+        Ok(proposals)
+    }
+
+    async fn proposal(&self, id: &str) -> Result<Option<Proposal>> {
+        let proposal_id = parse_proposal_id(id)?;
+        let Some((status, tally)) = self.status(proposal_id).await? else {
+            return Ok(None);
+        };
+        let deadline = self
+            .call(
+                "proposalDeadline(uint256)",
+                &[Token::Uint(proposal_id)],
+                &[ParamType::Uint(256)],
+            )
+            .await?;
+
+        Ok(Some(Proposal {
+            chain: self.chain.clone(),
+            source: self.name().to_string(),
+            id: proposal_id.to_string(),
+            title: None,
+            proposer: None,
+            status,
+            tally,
+            voting_ends: match deadline.as_slice() {
+                [Token::Uint(end)] => Some(end.low_u64()),
+                _ => None,
+            },
+        }))
+    }
+
+    async fn vote(&self, id: &str, choice: VoteChoice) -> Result<String> {
+        let wallet = self
+            .voter
+            .as_ref()
+            .ok_or_else(|| Error::Config("Governor source has no voter".to_string()))?;
+        // Support values of GovernorCountingSimple
+        let support = match choice {
+            VoteChoice::Nay => 0u8,
+            VoteChoice::Aye => 1,
+            VoteChoice::Abstain => 2,
+        };
+        let data = call_data(
+            "castVote(uint256,uint8)",
+            &[
+                Token::Uint(parse_proposal_id(id)?),
+                Token::Uint(support.into()),
+            ],
+        );
+        let governor: EthAddress = self
+            .governor
+            .parse()
+            .map_err(|_| Error::InvalidAddress(self.governor.clone()))?;
+
+        let hash = self
+            .adapter
+            .transaction_executor()
+            .send_transaction(wallet, governor, U256::zero(), Some(data))
+            .await?;
+        Ok(format!("{:?}", hash))
+    }
+}
+
+/// Fields of a `ProposalCreated` log used for a [`Proposal`]
+struct ProposalCreated {
+    id: U256,
+    proposer: EthAddress,
+    vote_end: u64,
+    title: Option<String>,
+}
+
+fn decode_proposal_created(data: &str) -> Option<ProposalCreated> {
+    let data = hex::decode(data.trim_start_matches("0x")).ok()?;
+    let tokens = abi::decode(
+        &[
+            ParamType::Uint(256),
+            ParamType::Address,
+            ParamType::Array(Box::new(ParamType::Address)),
+            ParamType::Array(Box::new(ParamType::Uint(256))),
+            ParamType::Array(Box::new(ParamType::String)),
+            ParamType::Array(Box::new(ParamType::Bytes)),
+            ParamType::Uint(256),
+            ParamType::Uint(256),
+            ParamType::String,
+        ],
+        &data,
+    )
+    .ok()?;
+
+    match tokens.as_slice() {
+        [Token::Uint(id), Token::Address(proposer), _, _, _, _, _, Token::Uint(vote_end), Token::String(description)] => {
+            Some(ProposalCreated {
+                id: *id,
+                proposer: *proposer,
+                vote_end: vote_end.low_u64(),
+                title: description
+                    .lines()
+                    .map(|line| line.trim_start_matches('#').trim())
+                    .find(|line| !line.is_empty())
+                    .map(str::to_string),
+            })
+        }
+        _ => None,
+    }
+}
+
+fn call_data(signature: &str, args: &[Token]) -> Vec<u8> {
+    let mut data = ethers::utils::id(signature).to_vec();
+    data.extend(abi::encode(args));
+    data
+}
+
+#[allow(clippy::result_large_err)]
+fn parse_referendum_index(id: &str) -> Result<u32> {
+    id.parse()
+        .map_err(|_| Error::Config(format!("Invalid referendum index {}", id)))
+}
+
+#[allow(clippy::result_large_err)]
+fn parse_proposal_id(id: &str) -> Result<U256> {
+    U256::from_dec_str(id).map_err(|_| Error::Config(format!("Invalid proposal ID {}", id)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    /// Source holding fixed proposals and recording votes
+    struct FixedSource {
+        chain: Chain,
+        proposals: Vec<Proposal>,
+        votes: Mutex<Vec<(String, VoteChoice)>>,
+    }
+
+    impl FixedSource {
+        fn new(chain: Chain, statuses: &[ProposalStatus]) -> Self {
+            let proposals = statuses
+                .iter()
+                .enumerate()
+                .map(|(i, status)| Proposal {
+                    chain: chain.clone(),
+                    source: "fixed".to_string(),
+                    id: i.to_string(),
+                    title: None,
+                    proposer: None,
+                    status: *status,
+                    tally: Tally {
+                        ayes: 10,
+                        nays: 5,
+                        abstain: 1,
+                    },
+                    voting_ends: None,
+                })
+                .collect();
+            Self {
+                chain,
+                proposals,
+                votes: Mutex::new(Vec::new()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl GovernanceSource for Arc<FixedSource> {
+        fn name(&self) -> &str {
+            "fixed"
+        }
+
+        fn chain(&self) -> &Chain {
+            &self.chain
+        }
+
+        async fn proposals(&self) -> Result<Vec<Proposal>> {
+            Ok(self.proposals.clone())
+        }
+
+        async fn proposal(&self, id: &str) -> Result<Option<Proposal>> {
+            Ok(self.proposals.iter().find(|p| p.id == id).cloned())
+        }
+
+        async fn vote(&self, id: &str, choice: VoteChoice) -> Result<String> {
+            self.votes.lock().unwrap().push((id.to_string(), choice));
+            Ok(format!("0x{}", id))
+        }
+    }
+
+    #[test]
+    fn test_tally() {
+        let tally = Tally {
+            ayes: 3,
+            nays: 1,
+            abstain: 7,
+        };
+        assert_eq!(tally.approval(), Some(0.75));
+        assert_eq!(Tally::default().approval(), None);
+
+        let total: Tally = [tally, tally].into_iter().sum();
+        assert_eq!(total.ayes, 6);
+        assert_eq!(total.abstain, 14);
+    }
+
+    #[tokio::test]
+    async fn test_aggregates_and_votes_across_sources() {
+        let polkadot = Arc::new(FixedSource::new(
+            Chain::Polkadot,
+            &[ProposalStatus::Active, ProposalStatus::Executed],
+        ));
+        let ethereum = Arc::new(FixedSource::new(
+            Chain::Ethereum,
+            &[ProposalStatus::Pending],
+        ));
+        let governance = Governance::new()
+            .with_source(polkadot.clone())
+            .with_source(ethereum.clone());
+
+        assert_eq!(governance.proposals().await.len(), 3);
+        let active = governance.active_proposals().await;
+        assert_eq!(active.len(), 2);
+        assert_eq!(Governance::combined_tally(&active).ayes, 20);
+
+        let results = governance.vote_all(&active, VoteChoice::Aye).await;
+        assert!(results.iter().all(|r| r.is_ok()));
+        assert_eq!(polkadot.votes.lock().unwrap().len(), 1);
+        assert_eq!(
+            ethereum.votes.lock().unwrap()[0],
+            ("0".to_string(), VoteChoice::Aye)
+        );
+
+        let mut unknown = active[0].clone();
+        unknown.chain = Chain::Kusama;
+        assert!(matches!(
+            governance.vote(&unknown, VoteChoice::Nay).await,
+            Err(Error::UnsupportedChain(_))
+        ));
+    }
+
+    #[test]
+    fn test_decode_proposal_created() {
+        let data = abi::encode(&[
+            Token::Uint(42.into()),
+            Token::Address(EthAddress::repeat_byte(1)),
+            Token::Array(vec![]),
+            Token::Array(vec![]),
+            Token::Array(vec![]),
+            Token::Array(vec![]),
+            Token::Uint(100.into()),
+            Token::Uint(200.into()),
+            Token::String("# Fund the grants program\n\nDetails".to_string()),
+        ]);
+
+        let created = decode_proposal_created(&format!("0x{}", hex::encode(data))).unwrap();
+        assert_eq!(created.id, 42.into());
+        assert_eq!(created.vote_end, 200);
+        assert_eq!(created.title.as_deref(), Some("Fund the grants program"));
+        assert!(decode_proposal_created("0x00").is_none());
+    }
+}
@@ -8,6 +8,8 @@
 //! - **Compile-Time Type Safety**: Catch errors before deployment
 //! - **Native Performance**: Rust-based implementation
 //! - **Cross-Chain Ready**: Built-in cross-chain communication support
+//! - **Event Subscriptions**: Chain events and blocks streamed from live adapters
+//! - **Scheduled Tasks**: Recurring jobs with cron triggers and retry policies
 //!
 //! ## Example
 //!
@@ -26,10 +28,26 @@
 //! }
 //! ```
 
+pub mod address_book;
+pub mod advanced;
+pub mod attestation;
+pub mod bridge;
 pub mod builder;
+pub mod checkpoint;
+pub mod correlation;
 pub mod error;
+pub mod governance;
+pub mod nft_bridge;
+pub mod payout;
+pub mod pending;
+pub mod policy;
+pub mod portfolio;
+pub mod rewards;
+pub mod scheduler;
 pub mod sdk;
+pub mod swap;
 pub mod transaction;
+pub mod watchdog;
 
 pub use apex_sdk_core as core;
 pub use apex_sdk_evm as evm;
@@ -38,14 +56,19 @@ pub use apex_sdk_types as types;
 
 pub use builder::ApexSDKBuilder;
 pub use error::{Error, Result};
+pub use policy::PolicyEngine;
 pub use sdk::ApexSDK;
-pub use transaction::{Transaction, TransactionBuilder, TransactionResult};
+pub use transaction::{
+    TokenTransfer, Transaction, TransactionBuilder, TransactionResult, WaitConfig,
+};
 
 /// Common imports for convenience
 pub mod prelude {
     pub use crate::builder::ApexSDKBuilder;
     pub use crate::error::{Error, Result};
     pub use crate::sdk::ApexSDK;
-    pub use crate::transaction::{Transaction, TransactionBuilder, TransactionResult};
+    pub use crate::transaction::{
+        TokenTransfer, Transaction, TransactionBuilder, TransactionResult, WaitConfig,
+    };
     pub use apex_sdk_types::{Address, Chain, ChainType, TransactionStatus};
 }
@@ -0,0 +1,851 @@
+//! NFT bridging between chains
+//!
+//! An [`NftBridge`] moves NFTs along allowlisted [`NftRoute`]s. Wrapping
+//! locks the original token in escrow on the source chain and, once the lock
+//! is confirmed, mints a wrapped token with the same ID and metadata URI on
+//! the destination chain. Unwrapping burns the wrapped token and, once the
+//! burn is confirmed, releases the original from escrow. Both directions
+//! check that the metadata of the two tokens hashes to the same value.
+//!
+//! Chains are reached through [`NftEndpoint`]s; [`EvmNftEndpoint`] covers
+//! ERC-721 collections and [`SubstrateNftEndpoint`] `pallet-nfts` /
+//! `pallet-uniques` collections. With [`with_tracker`](NftBridge::with_tracker),
+//! transfers are registered with a [`CorrelationEngine`] alongside other
+//! cross-chain transactions.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use apex_sdk::evm::{wallet::Wallet, EvmAdapter};
+//! use apex_sdk::nft_bridge::{EvmNftEndpoint, NftBridge, NftRoute};
+//! use apex_sdk::types::Chain;
+//!
+//! # #[tokio::main]
+//! # async fn main() -> apex_sdk::Result<()> {
+//! let user = Wallet::from_private_key("0x...")?;
+//! let operator = Wallet::from_private_key("0x...")?;
+//! let ethereum = EvmAdapter::connect("https://eth.llamarpc.com").await?;
+//! let polygon = EvmAdapter::connect("https://polygon-rpc.com").await?;
+//!
+//! let bridge = NftBridge::new()
+//!     .with_endpoint(EvmNftEndpoint::new(
+//!         Chain::Ethereum,
+//!         ethereum,
+//!         user.clone().with_chain_id(1),
+//!         operator.clone().with_chain_id(1),
+//!     ))
+//!     .with_endpoint(EvmNftEndpoint::new(
+//!         Chain::Polygon,
+//!         polygon,
+//!         user.with_chain_id(137),
+//!         operator.with_chain_id(137),
+//!     ))
+//!     .with_route(NftRoute::new(
+//!         Chain::Ethereum,
+//!         "0xBC4CA0EdA7647A8aB7C2061c2E118A18a936f13D",
+//!         Chain::Polygon,
+//!         "0x0000000000000000000000000000000000000b0c",
+//!     ));
+//!
+//! let transfer = bridge
+//!     .wrap(&Chain::Ethereum, "0xBC4CA0EdA7647A8aB7C2061c2E118A18a936f13D", 42u64.into())
+//!     .await?;
+//! println!("{} is {}", transfer.transaction.id, transfer.transaction.status);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::correlation::CorrelationEngine;
+use crate::error::{Error, Result};
+use apex_sdk_evm::wallet::Wallet as EvmWallet;
+use apex_sdk_evm::EvmAdapter;
+use apex_sdk_substrate::{NftPallet, Nfts, SubstrateAdapter, Wallet as SubstrateWallet};
+use apex_sdk_types::{Chain, ConfirmationPolicy, CrossChainTransaction, TransactionStatus};
+use async_trait::async_trait;
+use ethers::types::{Address as EthAddress, U256};
+use ethers::utils::keccak256;
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Keccak-256 hash of a metadata URI, `0x`-prefixed
+pub fn metadata_hash(uri: &str) -> String {
+    format!("0x{}", hex::encode(keccak256(uri.as_bytes())))
+}
+
+/// NFT operations on one chain
+///
+/// Each endpoint acts for one user, who owns the tokens being bridged, and
+/// one operator, who holds escrowed originals and may mint wrapped tokens.
+#[async_trait]
+pub trait NftEndpoint: Send + Sync {
+    /// Chain the endpoint operates on
+    fn chain(&self) -> &Chain;
+
+    /// Address of the user
+    fn user(&self) -> String;
+
+    /// Address holding escrowed tokens
+    fn escrow(&self) -> String;
+
+    /// Current owner of `token_id` in `collection`
+    async fn owner_of(&self, collection: &str, token_id: U256) -> Result<String>;
+
+    /// Metadata URI of `token_id` in `collection`
+    async fn token_uri(&self, collection: &str, token_id: U256) -> Result<String>;
+
+    /// Move `token_id` from the user into escrow, returning the transaction hash
+    async fn lock(&self, collection: &str, token_id: U256) -> Result<String>;
+
+    /// Release `token_id` from escrow to the user, returning the transaction hash
+    async fn unlock(&self, collection: &str, token_id: U256) -> Result<String>;
+
+    /// Mint wrapped `token_id` with metadata `uri` to the user
+    async fn mint(&self, collection: &str, token_id: U256, uri: &str) -> Result<String>;
+
+    /// Burn the user's wrapped `token_id`
+    async fn burn(&self, collection: &str, token_id: U256) -> Result<String>;
+
+    /// Status of transaction `tx_hash`, `Confirmed` only once it satisfies
+    /// the endpoint's confirmation policy
+    async fn status(&self, tx_hash: &str) -> Result<TransactionStatus>;
+}
+
+/// ERC-721 collections on an EVM chain
+pub struct EvmNftEndpoint {
+    chain: Chain,
+    adapter: EvmAdapter,
+    user: EvmWallet,
+    operator: EvmWallet,
+    confirmation: ConfirmationPolicy,
+}
+
+impl EvmNftEndpoint {
+    /// Bridge `user`'s tokens on `chain`, escrowing and minting with `operator`
+    pub fn new(chain: Chain, adapter: EvmAdapter, user: EvmWallet, operator: EvmWallet) -> Self {
+        Self {
+            confirmation: ConfirmationPolicy::for_chain(&chain),
+            chain,
+            adapter,
+            user,
+            operator,
+        }
+    }
+
+    /// Set when transactions count as confirmed, [`ConfirmationPolicy::for_chain`]
+    /// by default
+    pub fn with_confirmation_policy(mut self, policy: ConfirmationPolicy) -> Self {
+        self.confirmation = policy;
+        self
+    }
+}
+
+#[async_trait]
+impl NftEndpoint for EvmNftEndpoint {
+    fn chain(&self) -> &Chain {
+        &self.chain
+    }
+
+    fn user(&self) -> String {
+        format!("{:?}", self.user.eth_address())
+    }
+
+    fn escrow(&self) -> String {
+        format!("{:?}", self.operator.eth_address())
+    }
+
+    async fn owner_of(&self, collection: &str, token_id: U256) -> Result<String> {
+        let owner: EthAddress = self.adapter.erc721(collection)?.owner_of(token_id).await?;
+        Ok(format!("{:?}", owner))
+    }
+
+    async fn token_uri(&self, collection: &str, token_id: U256) -> Result<String> {
+        Ok(self.adapter.erc721(collection)?.token_uri(token_id).await?)
+    }
+
+    async fn lock(&self, collection: &str, token_id: U256) -> Result<String> {
+        let hash = self
+            .adapter
+            .erc721(collection)?
+            .transfer(&self.user, self.operator.eth_address(), token_id)
+            .await?;
+        Ok(format!("{:?}", hash))
+    }
+
+    async fn unlock(&self, collection: &str, token_id: U256) -> Result<String> {
+        let hash = self
+            .adapter
+            .erc721(collection)?
+            .transfer(&self.operator, self.user.eth_address(), token_id)
+            .await?;
+        Ok(format!("{:?}", hash))
+    }
+
+    async fn mint(&self, collection: &str, token_id: U256, uri: &str) -> Result<String> {
+        let hash = self
+            .adapter
+            .erc721(collection)?
+            .mint(&self.operator, self.user.eth_address(), token_id, uri)
+            .await?;
+        Ok(format!("{:?}", hash))
+    }
+
+    async fn burn(&self, collection: &str, token_id: U256) -> Result<String> {
+        let hash = self
+            .adapter
+            .erc721(collection)?
+            .burn(&self.user, token_id)
+            .await?;
+        Ok(format!("{:?}", hash))
+    }
+
+    async fn status(&self, tx_hash: &str) -> Result<TransactionStatus> {
+        Ok(self
+            .adapter
+            .confirmation_status(tx_hash, &self.confirmation)
+            .await?)
+    }
+}
+
+/// `pallet-nfts` or `pallet-uniques` collections on a Substrate chain
+///
+/// Collections and token IDs are the pallet's `u32` IDs, and metadata URIs
+/// are stored as item metadata. The operator must be issuer and admin of
+/// wrapped collections to mint into them.
+pub struct SubstrateNftEndpoint {
+    chain: Chain,
+    adapter: SubstrateAdapter,
+    user: SubstrateWallet,
+    operator: SubstrateWallet,
+    pallet: NftPallet,
+}
+
+impl SubstrateNftEndpoint {
+    /// Bridge `user`'s items on `chain`, escrowing and minting with
+    /// `operator`, through `pallet-nfts`
+    pub fn new(
+        chain: Chain,
+        adapter: SubstrateAdapter,
+        user: SubstrateWallet,
+        operator: SubstrateWallet,
+    ) -> Self {
+        Self {
+            chain,
+            adapter,
+            user,
+            operator,
+            pallet: NftPallet::Nfts,
+        }
+    }
+
+    /// Use `pallet` instead of `pallet-nfts`
+    pub fn with_pallet(mut self, pallet: NftPallet) -> Self {
+        self.pallet = pallet;
+        self
+    }
+
+    fn nfts(&self) -> Nfts {
+        self.adapter.nfts(self.pallet)
+    }
+}
+
+/// Pallet collection and item IDs of a bridged token
+#[allow(clippy::result_large_err)]
+fn substrate_ids(collection: &str, token_id: U256) -> Result<(u32, u32)> {
+    let collection = collection
+        .parse()
+        .map_err(|_| Error::Config(format!("Invalid collection ID {}", collection)))?;
+    if token_id > U256::from(u32::MAX) {
+        return Err(Error::Config(format!("Item ID {} exceeds u32", token_id)));
+    }
+    Ok((collection, token_id.as_u32()))
+}
+
+#[async_trait]
+impl NftEndpoint for SubstrateNftEndpoint {
+    fn chain(&self) -> &Chain {
+        &self.chain
+    }
+
+    fn user(&self) -> String {
+        self.user.address()
+    }
+
+    fn escrow(&self) -> String {
+        self.operator.address()
+    }
+
+    async fn owner_of(&self, collection: &str, token_id: U256) -> Result<String> {
+        let (collection, item) = substrate_ids(collection, token_id)?;
+        self.nfts()
+            .owner_of(collection, item)
+            .await?
+            .ok_or_else(|| Error::Other(format!("Item {}/{} does not exist", collection, item)))
+    }
+
+    async fn token_uri(&self, collection: &str, token_id: U256) -> Result<String> {
+        let (collection, item) = substrate_ids(collection, token_id)?;
+        Ok(self
+            .nfts()
+            .item_metadata(collection, item)
+            .await?
+            .unwrap_or_default())
+    }
+
+    async fn lock(&self, collection: &str, token_id: U256) -> Result<String> {
+        let (collection, item) = substrate_ids(collection, token_id)?;
+        let receipt = self
+            .nfts()
+            .transfer(&self.user, collection, item, &self.operator.address())
+            .await?;
+        Ok(receipt.hash)
+    }
+
+    async fn unlock(&self, collection: &str, token_id: U256) -> Result<String> {
+        let (collection, item) = substrate_ids(collection, token_id)?;
+        let receipt = self
+            .nfts()
+            .transfer(&self.operator, collection, item, &self.user.address())
+            .await?;
+        Ok(receipt.hash)
+    }
+
+    async fn mint(&self, collection: &str, token_id: U256, uri: &str) -> Result<String> {
+        let (collection, item) = substrate_ids(collection, token_id)?;
+        let nfts = self.nfts();
+        nfts.mint(&self.operator, collection, item, &self.user.address())
+            .await?;
+        let receipt = nfts
+            .set_metadata(&self.operator, collection, item, uri)
+            .await?;
+        Ok(receipt.hash)
+    }
+
+    async fn burn(&self, collection: &str, token_id: U256) -> Result<String> {
+        let (collection, item) = substrate_ids(collection, token_id)?;
+        let receipt = self.nfts().burn(&self.user, collection, item).await?;
+        Ok(receipt.hash)
+    }
+
+    async fn status(&self, tx_hash: &str) -> Result<TransactionStatus> {
+        // Extrinsics are submitted through to finalization
+        Ok(self.adapter.get_transaction_status(tx_hash).await?)
+    }
+}
+
+/// An allowlisted pairing of an original collection and its wrapped copy
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NftRoute {
+    /// Chain of the original collection
+    pub source: Chain,
+    /// Original collection
+    pub source_collection: String,
+    /// Chain of the wrapped collection
+    pub destination: Chain,
+    /// Wrapped collection, minting and burning on behalf of the bridge
+    pub wrapped_collection: String,
+}
+
+impl NftRoute {
+    /// Wrap `source_collection` on `source` as `wrapped_collection` on `destination`
+    pub fn new(
+        source: Chain,
+        source_collection: impl Into<String>,
+        destination: Chain,
+        wrapped_collection: impl Into<String>,
+    ) -> Self {
+        Self {
+            source,
+            source_collection: source_collection.into(),
+            destination,
+            wrapped_collection: wrapped_collection.into(),
+        }
+    }
+}
+
+/// A completed wrap or unwrap
+#[derive(Debug, Clone)]
+pub struct NftTransfer {
+    /// Route the token moved along
+    pub route: NftRoute,
+    /// Token ID, the same in both collections
+    pub token_id: U256,
+    /// Hash of the token's metadata URI
+    pub metadata_hash: String,
+    /// Lock and mint (or burn and unlock) transactions, keyed by the first
+    pub transaction: CrossChainTransaction,
+}
+
+/// Orchestrates NFT wrapping and unwrapping along allowlisted routes
+pub struct NftBridge {
+    endpoints: Vec<Arc<dyn NftEndpoint>>,
+    routes: Vec<NftRoute>,
+    tracker: Option<(Arc<CorrelationEngine>, String)>,
+    confirmation_timeout: Duration,
+    poll_interval: Duration,
+}
+
+impl Default for NftBridge {
+    fn default() -> Self {
+        Self {
+            endpoints: Vec::new(),
+            routes: Vec::new(),
+            tracker: None,
+            confirmation_timeout: Duration::from_secs(300),
+            poll_interval: Duration::from_secs(3),
+        }
+    }
+}
+
+impl NftBridge {
+    /// Create a bridge without endpoints or routes
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an endpoint, replacing any for the same chain
+    pub fn with_endpoint(mut self, endpoint: impl NftEndpoint + 'static) -> Self {
+        self.endpoints.retain(|e| e.chain() != endpoint.chain());
+        self.endpoints.push(Arc::new(endpoint));
+        self
+    }
+
+    /// Allow bridging along `route`
+    pub fn with_route(mut self, route: NftRoute) -> Self {
+        self.routes.push(route);
+        self
+    }
+
+    /// Register transfers with `engine` under the correlation rule named
+    /// `rule`, keyed by the hash of the lock or burn transaction
+    pub fn with_tracker(mut self, engine: Arc<CorrelationEngine>, rule: impl Into<String>) -> Self {
+        self.tracker = Some((engine, rule.into()));
+        self
+    }
+
+    /// Set how long to wait for each transaction to confirm
+    pub fn with_confirmation_timeout(mut self, timeout: Duration) -> Self {
+        self.confirmation_timeout = timeout;
+        self
+    }
+
+    /// Set how often transaction status is polled
+    pub fn with_poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+
+    /// Allowlisted routes
+    pub fn routes(&self) -> &[NftRoute] {
+        &self.routes
+    }
+
+    /// Lock `token_id` of `collection` on `source` and mint its wrapped copy
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Policy`] if `collection` is not allowlisted, and
+    /// [`Error::Transaction`] if the user does not own the token, a
+    /// transaction fails, or the wrapped metadata differs from the original.
+    pub async fn wrap(
+        &self,
+        source: &Chain,
+        collection: &str,
+        token_id: U256,
+    ) -> Result<NftTransfer> {
+        let route = self
+            .routes
+            .iter()
+            .find(|r| &r.source == source && r.source_collection.eq_ignore_ascii_case(collection))
+            .ok_or_else(|| {
+                Error::Policy(format!(
+                    "Collection {} on {} is not allowlisted for bridging",
+                    collection, source
+                ))
+            })?;
+        let origin = self.endpoint(&route.source)?;
+        let target = self.endpoint(&route.destination)?;
+
+        let owner = origin.owner_of(&route.source_collection, token_id).await?;
+        if !owner.eq_ignore_ascii_case(&origin.user()) {
+            return Err(Error::Transaction(format!(
+                "Token {} of {} is owned by {}, not {}",
+                token_id,
+                route.source_collection,
+                owner,
+                origin.user()
+            )));
+        }
+        let uri = origin.token_uri(&route.source_collection, token_id).await?;
+        let hash = metadata_hash(&uri);
+
+        let lock_tx = origin.lock(&route.source_collection, token_id).await?;
+        self.confirm(origin.as_ref(), &lock_tx).await?;
+        let mut transaction = self.track(&route.source, &route.destination, lock_tx);
+        tracing::info!(
+            "Locked token {} of {} on {}, minting on {}",
+            token_id,
+            route.source_collection,
+            route.source,
+            route.destination
+        );
+
+        let mint_tx = target
+            .mint(&route.wrapped_collection, token_id, &uri)
+            .await?;
+        let status = self.confirm(target.as_ref(), &mint_tx).await?;
+        let minted = target
+            .token_uri(&route.wrapped_collection, token_id)
+            .await?;
+        if metadata_hash(&minted) != hash {
+            return Err(Error::Transaction(format!(
+                "Wrapped token {} of {} has metadata {}, expected {}",
+                token_id, route.wrapped_collection, minted, uri
+            )));
+        }
+
+        transaction.destination_tx_hash = Some(mint_tx);
+        transaction.status = status;
+        Ok(NftTransfer {
+            route: route.clone(),
+            token_id,
+            metadata_hash: hash,
+            transaction,
+        })
+    }
+
+    /// Burn wrapped `token_id` of `collection` on `destination` and release
+    /// the original from escrow
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Policy`] if `collection` is not an allowlisted
+    /// wrapped collection, and [`Error::Transaction`] if the original is not
+    /// in escrow, the metadata of the two tokens differs, or a transaction
+    /// fails. Nothing is burned when a check fails.
+    pub async fn unwrap(
+        &self,
+        destination: &Chain,
+        collection: &str,
+        token_id: U256,
+    ) -> Result<NftTransfer> {
+        let route = self
+            .routes
+            .iter()
+            .find(|r| {
+                &r.destination == destination
+                    && r.wrapped_collection.eq_ignore_ascii_case(collection)
+            })
+            .ok_or_else(|| {
+                Error::Policy(format!(
+                    "Collection {} on {} is not an allowlisted wrapped collection",
+                    collection, destination
+                ))
+            })?;
+        let origin = self.endpoint(&route.source)?;
+        let target = self.endpoint(&route.destination)?;
+
+        let escrowed = origin.owner_of(&route.source_collection, token_id).await?;
+        if !escrowed.eq_ignore_ascii_case(&origin.escrow()) {
+            return Err(Error::Transaction(format!(
+                "Token {} of {} is not held in escrow",
+                token_id, route.source_collection
+            )));
+        }
+        let hash = metadata_hash(&origin.token_uri(&route.source_collection, token_id).await?);
+        let wrapped = target
+            .token_uri(&route.wrapped_collection, token_id)
+            .await?;
+        if metadata_hash(&wrapped) != hash {
+            return Err(Error::Transaction(format!(
+                "Wrapped token {} of {} does not match the escrowed original",
+                token_id, route.wrapped_collection
+            )));
+        }
+
+        let burn_tx = target.burn(&route.wrapped_collection, token_id).await?;
+        self.confirm(target.as_ref(), &burn_tx).await?;
+        let mut transaction = self.track(&route.destination, &route.source, burn_tx);
+        tracing::info!(
+            "Burned token {} of {} on {}, unlocking on {}",
+            token_id,
+            route.wrapped_collection,
+            route.destination,
+            route.source
+        );
+
+        let unlock_tx = origin.unlock(&route.source_collection, token_id).await?;
+        transaction.status = self.confirm(origin.as_ref(), &unlock_tx).await?;
+        transaction.destination_tx_hash = Some(unlock_tx);
+        Ok(NftTransfer {
+            route: route.clone(),
+            token_id,
+            metadata_hash: hash,
+            transaction,
+        })
+    }
+
+    #[allow(clippy::result_large_err)]
+    fn endpoint(&self, chain: &Chain) -> Result<Arc<dyn NftEndpoint>> {
+        self.endpoints
+            .iter()
+            .find(|e| e.chain() == chain)
+            .cloned()
+            .ok_or_else(|| Error::UnsupportedChain(format!("No NFT endpoint for {}", chain)))
+    }
+
+    /// Wait for `tx_hash` to confirm on `endpoint`
+    async fn confirm(
+        &self,
+        endpoint: &dyn NftEndpoint,
+        tx_hash: &str,
+    ) -> Result<TransactionStatus> {
+        let started = Instant::now();
+        loop {
+            match endpoint.status(tx_hash).await? {
+                status @ TransactionStatus::Confirmed { .. } => return Ok(status),
+                TransactionStatus::Failed { error } => {
+                    return Err(Error::Transaction(format!(
+                        "{} on {} failed: {}",
+                        tx_hash,
+                        endpoint.chain(),
+                        error
+                    )))
+                }
+                TransactionStatus::Pending | TransactionStatus::Unknown => {}
+            }
+            if started.elapsed() >= self.confirmation_timeout {
+                return Err(Error::Transaction(format!(
+                    "{} on {} not confirmed within {:?}",
+                    tx_hash,
+                    endpoint.chain(),
+                    self.confirmation_timeout
+                )));
+            }
+            tokio::time::sleep(self.poll_interval).await;
+        }
+    }
+
+    /// Record the first leg of a transfer, registering it with the tracker
+    fn track(&self, from: &Chain, to: &Chain, tx_hash: String) -> CrossChainTransaction {
+        let transaction = CrossChainTransaction {
+            id: tx_hash.clone(),
+            source_chain: from.clone(),
+            destination_chain: to.clone(),
+            source_tx_hash: Some(tx_hash),
+            destination_tx_hash: None,
+            status: TransactionStatus::Pending,
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or_default(),
+        };
+        if let Some((engine, rule)) = &self.tracker {
+            if !engine.track(rule, transaction.clone()) {
+                tracing::warn!("No correlation rule {} to track {}", rule, transaction.id);
+            }
+        }
+        transaction
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_substrate_ids() {
+        assert_eq!(substrate_ids("12", U256::from(7)).unwrap(), (12, 7));
+        assert!(substrate_ids("0xabc", U256::from(7)).is_err());
+        assert!(substrate_ids("12", U256::from(u64::MAX)).is_err());
+    }
+    use crate::correlation::CorrelationRule;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    const USER: &str = "0xuser";
+    const ESCROW: &str = "0xescrow";
+
+    #[derive(Default)]
+    struct FakeEndpoint {
+        chain: Option<Chain>,
+        // (collection, token) -> (owner, uri)
+        tokens: Mutex<HashMap<(String, U256), (String, String)>>,
+        txs: Mutex<Vec<String>>,
+    }
+
+    impl FakeEndpoint {
+        fn new(chain: Chain) -> Self {
+            Self {
+                chain: Some(chain),
+                ..Default::default()
+            }
+        }
+
+        fn with_token(self, collection: &str, id: u64, owner: &str, uri: &str) -> Self {
+            self.tokens.lock().unwrap().insert(
+                (collection.to_string(), id.into()),
+                (owner.to_string(), uri.to_string()),
+            );
+            self
+        }
+
+        #[allow(clippy::result_large_err)]
+        fn set_owner(&self, collection: &str, id: U256, owner: &str) -> Result<String> {
+            let mut tokens = self.tokens.lock().unwrap();
+            let token = tokens
+                .get_mut(&(collection.to_string(), id))
+                .ok_or_else(|| Error::Other("no such token".to_string()))?;
+            token.0 = owner.to_string();
+            Ok(self.record())
+        }
+
+        fn record(&self) -> String {
+            let mut txs = self.txs.lock().unwrap();
+            let hash = format!("0x{:02x}", txs.len());
+            txs.push(hash.clone());
+            hash
+        }
+    }
+
+    #[async_trait]
+    impl NftEndpoint for FakeEndpoint {
+        fn chain(&self) -> &Chain {
+            self.chain.as_ref().unwrap()
+        }
+
+        fn user(&self) -> String {
+            USER.to_string()
+        }
+
+        fn escrow(&self) -> String {
+            ESCROW.to_string()
+        }
+
+        async fn owner_of(&self, collection: &str, token_id: U256) -> Result<String> {
+            let tokens = self.tokens.lock().unwrap();
+            tokens
+                .get(&(collection.to_string(), token_id))
+                .map(|t| t.0.clone())
+                .ok_or_else(|| Error::Other("no such token".to_string()))
+        }
+
+        async fn token_uri(&self, collection: &str, token_id: U256) -> Result<String> {
+            let tokens = self.tokens.lock().unwrap();
+            tokens
+                .get(&(collection.to_string(), token_id))
+                .map(|t| t.1.clone())
+                .ok_or_else(|| Error::Other("no such token".to_string()))
+        }
+
+        async fn lock(&self, collection: &str, token_id: U256) -> Result<String> {
+            self.set_owner(collection, token_id, ESCROW)
+        }
+
+        async fn unlock(&self, collection: &str, token_id: U256) -> Result<String> {
+            self.set_owner(collection, token_id, USER)
+        }
+
+        async fn mint(&self, collection: &str, token_id: U256, uri: &str) -> Result<String> {
+            self.tokens.lock().unwrap().insert(
+                (collection.to_string(), token_id),
+                (USER.to_string(), uri.to_string()),
+            );
+            Ok(self.record())
+        }
+
+        async fn burn(&self, collection: &str, token_id: U256) -> Result<String> {
+            self.tokens
+                .lock()
+                .unwrap()
+                .remove(&(collection.to_string(), token_id));
+            Ok(self.record())
+        }
+
+        async fn status(&self, _tx_hash: &str) -> Result<TransactionStatus> {
+            Ok(TransactionStatus::Confirmed {
+                block_number: 1,
+                confirmations: 1,
+            })
+        }
+    }
+
+    fn bridge(ethereum: FakeEndpoint, polygon: FakeEndpoint) -> NftBridge {
+        NftBridge::new()
+            .with_endpoint(ethereum)
+            .with_endpoint(polygon)
+            .with_route(NftRoute::new(
+                Chain::Ethereum,
+                "apes",
+                Chain::Polygon,
+                "wapes",
+            ))
+            .with_poll_interval(Duration::from_millis(1))
+    }
+
+    #[tokio::test]
+    async fn test_wrap_and_unwrap() {
+        let bridge = bridge(
+            FakeEndpoint::new(Chain::Ethereum).with_token("apes", 42, USER, "ipfs://ape/42"),
+            FakeEndpoint::new(Chain::Polygon),
+        );
+        let id = U256::from(42);
+
+        let wrapped = bridge.wrap(&Chain::Ethereum, "apes", id).await.unwrap();
+        assert_eq!(wrapped.metadata_hash, metadata_hash("ipfs://ape/42"));
+        assert_eq!(wrapped.transaction.source_chain, Chain::Ethereum);
+        assert!(wrapped.transaction.destination_tx_hash.is_some());
+        let ethereum = bridge.endpoint(&Chain::Ethereum).unwrap();
+        let polygon = bridge.endpoint(&Chain::Polygon).unwrap();
+        assert_eq!(ethereum.owner_of("apes", id).await.unwrap(), ESCROW);
+        assert_eq!(polygon.owner_of("wapes", id).await.unwrap(), USER);
+
+        let unwrapped = bridge.unwrap(&Chain::Polygon, "wapes", id).await.unwrap();
+        assert_eq!(unwrapped.transaction.source_chain, Chain::Polygon);
+        assert_eq!(ethereum.owner_of("apes", id).await.unwrap(), USER);
+        assert!(polygon.owner_of("wapes", id).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_collection_not_allowlisted() {
+        let bridge = bridge(
+            FakeEndpoint::new(Chain::Ethereum).with_token("punks", 1, USER, "ipfs://punk/1"),
+            FakeEndpoint::new(Chain::Polygon),
+        );
+
+        let result = bridge.wrap(&Chain::Ethereum, "punks", 1u64.into()).await;
+        assert!(matches!(result, Err(Error::Policy(_))));
+        let result = bridge.unwrap(&Chain::Polygon, "apes", 1u64.into()).await;
+        assert!(matches!(result, Err(Error::Policy(_))));
+    }
+
+    #[tokio::test]
+    async fn test_unwrap_rejects_mismatched_metadata() {
+        let bridge = bridge(
+            FakeEndpoint::new(Chain::Ethereum).with_token("apes", 7, ESCROW, "ipfs://ape/7"),
+            FakeEndpoint::new(Chain::Polygon).with_token("wapes", 7, USER, "ipfs://forged"),
+        );
+
+        let result = bridge.unwrap(&Chain::Polygon, "wapes", 7u64.into()).await;
+        assert!(matches!(result, Err(Error::Transaction(_))));
+        let polygon = bridge.endpoint(&Chain::Polygon).unwrap();
+        assert_eq!(polygon.owner_of("wapes", 7u64.into()).await.unwrap(), USER);
+    }
+
+    #[tokio::test]
+    async fn test_wrap_tracks_transfer() {
+        let rule = CorrelationRule::xcm(Chain::Ethereum, Chain::Polygon);
+        let name = rule.name().to_string();
+        let engine = Arc::new(CorrelationEngine::new().with_rule(rule));
+        let bridge = bridge(
+            FakeEndpoint::new(Chain::Ethereum).with_token("apes", 1, USER, "ipfs://ape/1"),
+            FakeEndpoint::new(Chain::Polygon),
+        )
+        .with_tracker(engine.clone(), name);
+
+        let transfer = bridge
+            .wrap(&Chain::Ethereum, "apes", 1u64.into())
+            .await
+            .unwrap();
+        assert!(engine.get(&transfer.transaction.id).is_some());
+    }
+}
@@ -0,0 +1,965 @@
+//! Batch payouts
+//!
+//! A [`PayoutEngine`] pays a list of [`PayoutEntry`]s, built in code or read
+//! from CSV with [`parse_csv`]. Entries are validated up front, grouped by
+//! chain and token, and chunked into batches that each go out as a single
+//! transaction: a `Utility` batch on Substrate chains ([`SubstratePayouts`])
+//! or a call to a Disperse contract on EVM chains ([`EvmPayouts`]). Failed
+//! batches are retried, and every entry ends up in a [`PayoutReport`] with
+//! the transaction that paid it or the error that stopped it.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use apex_sdk::evm::{wallet::Wallet as EvmWallet, EvmAdapter};
+//! use apex_sdk::payout::{load_csv, EvmPayouts, PayoutEngine, SubstratePayouts, DISPERSE};
+//! use apex_sdk::substrate::{KeyPairType, SubstrateAdapter, Wallet};
+//! use apex_sdk::types::Chain;
+//!
+//! # #[tokio::main]
+//! # async fn main() -> apex_sdk::Result<()> {
+//! let polkadot = SubstrateAdapter::connect("wss://polkadot.api.onfinality.io/public-ws").await?;
+//! let ethereum = EvmAdapter::connect("https://eth.llamarpc.com").await?;
+//!
+//! let engine = PayoutEngine::new()
+//!     .with_executor(SubstratePayouts::new(
+//!         Chain::Polkadot,
+//!         polkadot,
+//!         Wallet::from_mnemonic("your mnemonic here", KeyPairType::Sr25519)?,
+//!     ))
+//!     .with_executor(EvmPayouts::new(
+//!         Chain::Ethereum,
+//!         ethereum,
+//!         EvmWallet::from_private_key("0x...")?.with_chain_id(1),
+//!         DISPERSE,
+//!     ));
+//!
+//! // address,amount,token,chain
+//! let entries = load_csv("payroll.csv")?;
+//! let report = engine.execute(&entries).await?;
+//! print!("{}", report.to_csv());
+//! println!("{} paid, {} failed", report.paid().len(), report.failed().len());
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::{Error, Result};
+use apex_sdk_evm::wallet::Wallet as EvmWallet;
+use apex_sdk_evm::EvmAdapter;
+use apex_sdk_substrate::{BatchMode, SubstrateAdapter, Wallet};
+use apex_sdk_types::{Address, Chain, ChainType};
+use async_trait::async_trait;
+use ethers::abi::{self, ParamType, Token};
+use ethers::types::{Address as EthAddress, U256};
+use std::collections::HashSet;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+/// Token name of a chain's native currency
+pub const NATIVE: &str = "native";
+
+/// Disperse contract, deployed at this address on most EVM chains
+pub const DISPERSE: &str = "0xD152f549545093347A162Dce210e7293f1452150";
+
+/// One payment to make
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PayoutEntry {
+    /// Recipient: SS58 on Substrate chains, `0x` hex on EVM chains
+    pub address: String,
+    /// Amount in the token's smallest unit
+    pub amount: u128,
+    /// [`NATIVE`], an ERC-20 contract address on EVM chains, or an asset ID
+    /// of the `Assets` pallet on Substrate chains
+    pub token: String,
+    /// Chain to pay on
+    pub chain: Chain,
+}
+
+impl PayoutEntry {
+    /// Pay `amount` of `token` to `address` on `chain`
+    pub fn new(
+        address: impl Into<String>,
+        amount: u128,
+        token: impl Into<String>,
+        chain: Chain,
+    ) -> Self {
+        Self {
+            address: address.into(),
+            amount,
+            token: token.into(),
+            chain,
+        }
+    }
+
+    /// Pay `amount` of the native currency to `address` on `chain`
+    pub fn native(address: impl Into<String>, amount: u128, chain: Chain) -> Self {
+        Self::new(address, amount, NATIVE, chain)
+    }
+
+    /// Whether the entry pays the native currency
+    pub fn is_native(&self) -> bool {
+        self.token.eq_ignore_ascii_case(NATIVE)
+    }
+
+    /// The recipient as an [`Address`] of the chain's kind
+    fn recipient(&self) -> Address {
+        match self.chain.chain_type() {
+            ChainType::Substrate => Address::substrate(&self.address),
+            ChainType::Evm | ChainType::Hybrid => Address::evm(&self.address),
+        }
+    }
+}
+
+/// Parse payout entries from CSV
+///
+/// Columns are `address,amount,token,chain`. A header row naming them may
+/// reorder them or leave out `token`, which then defaults to [`NATIVE`].
+/// Blank lines and lines starting with `#` are skipped, and fields may be
+/// double-quoted.
+///
+/// # Errors
+///
+/// Returns [`Error::Config`] naming the first malformed line.
+#[allow(clippy::result_large_err)]
+pub fn parse_csv(input: &str) -> Result<Vec<PayoutEntry>> {
+    let mut columns = [Some(0), Some(1), Some(2), Some(3)];
+    let mut entries = Vec::new();
+
+    for (number, line) in input.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let fields = split_csv_line(line);
+        let malformed = |reason: String| Error::Config(format!("Line {}: {}", number + 1, reason));
+
+        if entries.is_empty()
+            && fields
+                .iter()
+                .any(|field| field.eq_ignore_ascii_case("address"))
+        {
+            columns = ["address", "amount", "token", "chain"].map(|name| {
+                fields
+                    .iter()
+                    .position(|field| field.eq_ignore_ascii_case(name))
+            });
+            if let Some(missing) = ["address", "amount", "chain"]
+                .iter()
+                .zip([columns[0], columns[1], columns[3]])
+                .find_map(|(name, column)| column.is_none().then_some(name))
+            {
+                return Err(malformed(format!("Missing column {}", missing)));
+            }
+            continue;
+        }
+
+        let field = |column: Option<usize>| column.and_then(|i| fields.get(i)).map(String::as_str);
+        let address = field(columns[0])
+            .filter(|address| !address.is_empty())
+            .ok_or_else(|| malformed("Missing address".to_string()))?;
+        let amount = field(columns[1]).ok_or_else(|| malformed("Missing amount".to_string()))?;
+        let amount = amount
+            .parse()
+            .map_err(|_| malformed(format!("Invalid amount: {}", amount)))?;
+        let token = field(columns[2]).filter(|token| !token.is_empty());
+        let chain = field(columns[3])
+            .ok_or_else(|| malformed("Missing chain".to_string()))?
+            .parse()
+            .map_err(malformed)?;
+
+        entries.push(PayoutEntry::new(
+            address,
+            amount,
+            token.unwrap_or(NATIVE),
+            chain,
+        ));
+    }
+    Ok(entries)
+}
+
+/// Read payout entries from a CSV file, see [`parse_csv`]
+#[allow(clippy::result_large_err)]
+pub fn load_csv(path: impl AsRef<Path>) -> Result<Vec<PayoutEntry>> {
+    let path = path.as_ref();
+    let input = std::fs::read_to_string(path)
+        .map_err(|e| Error::Config(format!("Failed to read {}: {}", path.display(), e)))?;
+    parse_csv(&input)
+}
+
+fn split_csv_line(line: &str) -> Vec<String> {
+    let mut fields = vec![String::new()];
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                fields.last_mut().unwrap().push('"');
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(String::new()),
+            c => fields.last_mut().unwrap().push(c),
+        }
+    }
+    fields.into_iter().map(|f| f.trim().to_string()).collect()
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// An entry that failed validation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PayoutIssue {
+    /// Position of the entry in the list
+    pub index: usize,
+    /// What is wrong with it
+    pub reason: String,
+}
+
+/// Entries paid together in one transaction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PayoutBatch {
+    /// Chain the batch is paid on
+    pub chain: Chain,
+    /// Token every entry pays
+    pub token: String,
+    /// Entries of the batch
+    pub entries: Vec<PayoutEntry>,
+}
+
+impl PayoutBatch {
+    /// Sum of the amounts paid
+    pub fn total(&self) -> u128 {
+        self.entries.iter().map(|entry| entry.amount).sum()
+    }
+}
+
+/// Pays batches on one chain
+#[async_trait]
+pub trait PayoutExecutor: Send + Sync {
+    /// Chain the executor pays on
+    fn chain(&self) -> &Chain;
+
+    /// Whether `token` can be paid
+    fn supports_token(&self, token: &str) -> bool;
+
+    /// Most entries paid in one transaction
+    fn max_batch_size(&self) -> usize;
+
+    /// Pay every entry of `batch` in one transaction, returning its hash
+    ///
+    /// Must not return an error once the transaction was broadcast, or the
+    /// engine's retry pays the batch twice.
+    async fn pay(&self, batch: &PayoutBatch) -> Result<String>;
+}
+
+/// Outcome of one entry
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PayoutStatus {
+    /// Paid by the transaction
+    Paid {
+        /// Hash of the paying transaction
+        tx_hash: String,
+    },
+    /// Not paid
+    Failed {
+        /// Error of the last attempt
+        error: String,
+    },
+}
+
+/// Outcome of one batch
+#[derive(Debug, Clone)]
+pub struct BatchOutcome {
+    /// The batch
+    pub batch: PayoutBatch,
+    /// Outcome of every entry in the batch
+    pub status: PayoutStatus,
+    /// Number of attempts made
+    pub attempts: u32,
+}
+
+/// Reconciliation of an executed payout
+#[derive(Debug, Clone, Default)]
+pub struct PayoutReport {
+    /// Outcome of each batch, in execution order
+    pub batches: Vec<BatchOutcome>,
+}
+
+impl PayoutReport {
+    /// Entries paid, with the hash of the paying transaction
+    pub fn paid(&self) -> Vec<(&PayoutEntry, &str)> {
+        self.entries()
+            .filter_map(|(entry, status)| match status {
+                PayoutStatus::Paid { tx_hash } => Some((entry, tx_hash.as_str())),
+                PayoutStatus::Failed { .. } => None,
+            })
+            .collect()
+    }
+
+    /// Entries not paid, with the reason
+    pub fn failed(&self) -> Vec<(&PayoutEntry, &str)> {
+        self.entries()
+            .filter_map(|(entry, status)| match status {
+                PayoutStatus::Failed { error } => Some((entry, error.as_str())),
+                PayoutStatus::Paid { .. } => None,
+            })
+            .collect()
+    }
+
+    /// Whether every entry was paid
+    pub fn is_complete(&self) -> bool {
+        self.failed().is_empty()
+    }
+
+    /// Total amount of `token` paid on `chain`
+    pub fn total_paid(&self, chain: &Chain, token: &str) -> u128 {
+        self.paid()
+            .into_iter()
+            .filter(|(entry, _)| &entry.chain == chain && entry.token == token)
+            .map(|(entry, _)| entry.amount)
+            .sum()
+    }
+
+    /// One CSV row per entry: `address,amount,token,chain,status,tx_hash,error`
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("address,amount,token,chain,status,tx_hash,error\n");
+        for (entry, status) in self.entries() {
+            let (state, tx_hash, error) = match status {
+                PayoutStatus::Paid { tx_hash } => ("paid", tx_hash.as_str(), ""),
+                PayoutStatus::Failed { error } => ("failed", "", error.as_str()),
+            };
+            let row = [
+                entry.address.as_str(),
+                &entry.amount.to_string(),
+                &entry.token,
+                &entry.chain.to_string(),
+                state,
+                tx_hash,
+                error,
+            ]
+            .map(csv_field)
+            .join(",");
+            csv.push_str(&row);
+            csv.push('\n');
+        }
+        csv
+    }
+
+    fn entries(&self) -> impl Iterator<Item = (&PayoutEntry, &PayoutStatus)> {
+        self.batches.iter().flat_map(|outcome| {
+            outcome
+                .batch
+                .entries
+                .iter()
+                .map(move |entry| (entry, &outcome.status))
+        })
+    }
+}
+
+/// Validates, batches and pays payout entries
+///
+/// Batches run one at a time, so each executor submits its transactions in
+/// order from one account. A batch that still fails after the retries is
+/// reported and the remaining batches go ahead.
+pub struct PayoutEngine {
+    executors: Vec<Arc<dyn PayoutExecutor>>,
+    max_retries: u32,
+    retry_delay: Duration,
+}
+
+impl PayoutEngine {
+    /// Create an engine with no executors
+    pub fn new() -> Self {
+        Self {
+            executors: Vec::new(),
+            max_retries: 2,
+            retry_delay: Duration::from_secs(5),
+        }
+    }
+
+    /// Add an executor paying on its chain
+    pub fn with_executor(mut self, executor: impl PayoutExecutor + 'static) -> Self {
+        self.executors.push(Arc::new(executor));
+        self
+    }
+
+    /// Set how often a failed batch is retried
+    pub fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Set how long to wait before retrying a failed batch
+    pub fn with_retry_delay(mut self, delay: Duration) -> Self {
+        self.retry_delay = delay;
+        self
+    }
+
+    /// Check `entries` without paying anything
+    ///
+    /// Flags zero amounts, recipients that are not addresses of the entry's
+    /// chain, chains and tokens no executor pays, and repeated
+    /// (recipient, token, chain) combinations, which usually mean a
+    /// duplicated row.
+    pub fn validate(&self, entries: &[PayoutEntry]) -> Vec<PayoutIssue> {
+        let mut seen = HashSet::new();
+        let mut issues = Vec::new();
+
+        for (index, entry) in entries.iter().enumerate() {
+            let mut flag = |reason: String| issues.push(PayoutIssue { index, reason });
+            if entry.amount == 0 {
+                flag("Amount is zero".to_string());
+            }
+
+            let recipient = entry.recipient();
+            let account = match &recipient {
+                // The batch call takes SS58 addresses
+                Address::Substrate(address) if address.starts_with("0x") => None,
+                _ => recipient.account_bytes(),
+            };
+            match account {
+                Some(account) => {
+                    let key = (entry.chain.clone(), entry.token.to_lowercase(), account);
+                    if !seen.insert(key) {
+                        flag(format!("Duplicate payout to {}", entry.address));
+                    }
+                }
+                None => flag(format!(
+                    "Invalid {} address: {}",
+                    entry.chain, entry.address
+                )),
+            }
+
+            match self.executor(&entry.chain) {
+                Some(executor) if !executor.supports_token(&entry.token) => flag(format!(
+                    "Unsupported token on {}: {}",
+                    entry.chain, entry.token
+                )),
+                Some(_) => {}
+                None => flag(format!("No executor for {}", entry.chain)),
+            }
+        }
+        issues
+    }
+
+    /// Group `entries` into batches by chain and token, in order of first
+    /// appearance, each no larger than its executor allows
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Config`] listing the issues if validation fails.
+    #[allow(clippy::result_large_err)]
+    pub fn plan(&self, entries: &[PayoutEntry]) -> Result<Vec<PayoutBatch>> {
+        let issues = self.validate(entries);
+        if !issues.is_empty() {
+            let issues: Vec<_> = issues
+                .iter()
+                .map(|issue| format!("entry {}: {}", issue.index, issue.reason))
+                .collect();
+            return Err(Error::Config(format!(
+                "Invalid payout entries: {}",
+                issues.join("; ")
+            )));
+        }
+
+        let mut groups: Vec<PayoutBatch> = Vec::new();
+        for entry in entries {
+            match groups
+                .iter_mut()
+                .find(|group| group.chain == entry.chain && group.token == entry.token)
+            {
+                Some(group) => group.entries.push(entry.clone()),
+                None => groups.push(PayoutBatch {
+                    chain: entry.chain.clone(),
+                    token: entry.token.clone(),
+                    entries: vec![entry.clone()],
+                }),
+            }
+        }
+
+        let mut batches = Vec::new();
+        for group in groups {
+            let size = self
+                .executor(&group.chain)
+                .map_or(1, |executor| executor.max_batch_size().max(1));
+            batches.extend(group.entries.chunks(size).map(|chunk| PayoutBatch {
+                chain: group.chain.clone(),
+                token: group.token.clone(),
+                entries: chunk.to_vec(),
+            }));
+        }
+        Ok(batches)
+    }
+
+    /// Validate, batch and pay `entries`
+    ///
+    /// # Errors
+    ///
+    /// Nothing is paid if validation fails (see [`plan`](Self::plan)); failed
+    /// batches are reported, not returned as errors.
+    pub async fn execute(&self, entries: &[PayoutEntry]) -> Result<PayoutReport> {
+        let mut report = PayoutReport::default();
+        for batch in self.plan(entries)? {
+            let executor = self
+                .executor(&batch.chain)
+                .ok_or_else(|| Error::UnsupportedChain(batch.chain.to_string()))?;
+
+            let mut attempts = 0;
+            let status = loop {
+                attempts += 1;
+                match executor.pay(&batch).await {
+                    Ok(tx_hash) => break PayoutStatus::Paid { tx_hash },
+                    Err(e) if attempts <= self.max_retries => {
+                        tracing::warn!(
+                            "Payout batch of {} on {} failed (attempt {}): {}",
+                            batch.entries.len(),
+                            batch.chain,
+                            attempts,
+                            e
+                        );
+                        tokio::time::sleep(self.retry_delay).await;
+                    }
+                    Err(e) => {
+                        break PayoutStatus::Failed {
+                            error: e.to_string(),
+                        }
+                    }
+                }
+            };
+            report.batches.push(BatchOutcome {
+                batch,
+                status,
+                attempts,
+            });
+        }
+        Ok(report)
+    }
+
+    fn executor(&self, chain: &Chain) -> Option<&Arc<dyn PayoutExecutor>> {
+        self.executors
+            .iter()
+            .find(|executor| executor.chain() == chain)
+    }
+}
+
+impl Default for PayoutEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Pays on a Substrate chain with `Utility` batches
+///
+/// Tokens are [`NATIVE`] (`Balances`) or asset IDs of the `Assets` pallet.
+/// Each batch waits for finalization, so the next one is signed with the
+/// updated account nonce.
+pub struct SubstratePayouts {
+    chain: Chain,
+    adapter: SubstrateAdapter,
+    wallet: Wallet,
+    batch_mode: BatchMode,
+    batch_size: usize,
+}
+
+impl SubstratePayouts {
+    /// Pay on `chain` from `wallet`
+    pub fn new(chain: Chain, adapter: SubstrateAdapter, wallet: Wallet) -> Self {
+        Self {
+            chain,
+            adapter,
+            wallet,
+            batch_mode: BatchMode::AllOrNothing,
+            batch_size: 100,
+        }
+    }
+
+    /// Set the batch call, `Utility.batch_all` by default so a batch is paid
+    /// entirely or not at all
+    pub fn with_batch_mode(mut self, batch_mode: BatchMode) -> Self {
+        self.batch_mode = batch_mode;
+        self
+    }
+
+    /// Set the most transfers per batch
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size;
+        self
+    }
+}
+
+#[async_trait]
+impl PayoutExecutor for SubstratePayouts {
+    fn chain(&self) -> &Chain {
+        &self.chain
+    }
+
+    fn supports_token(&self, token: &str) -> bool {
+        token.eq_ignore_ascii_case(NATIVE) || token.parse::<u32>().is_ok()
+    }
+
+    fn max_batch_size(&self) -> usize {
+        self.batch_size
+    }
+
+    async fn pay(&self, batch: &PayoutBatch) -> Result<String> {
+        let asset_id = batch.token.parse::<u32>().ok();
+        let transfers: Vec<_> = batch
+            .entries
+            .iter()
+            .map(|entry| (entry.address.clone(), entry.amount))
+            .collect();
+        let receipt = self
+            .adapter
+            .transaction_executor()
+            .batch_transfer_with_receipt(&self.wallet, &transfers, asset_id, self.batch_mode)
+            .await?;
+        Ok(receipt.hash)
+    }
+}
+
+/// Pays on an EVM chain through a Disperse contract
+///
+/// Native payouts call `disperseEther`; ERC-20 payouts call `disperseToken`,
+/// which pulls the tokens from the wallet, so the contract must first be
+/// [`approve`](Self::approve)d for the total. Nonces are assigned locally, so
+/// batches can follow each other without waiting to be mined.
+pub struct EvmPayouts {
+    chain: Chain,
+    adapter: EvmAdapter,
+    wallet: EvmWallet,
+    disperse: String,
+    batch_size: usize,
+    next_nonce: Mutex<Option<U256>>,
+}
+
+impl EvmPayouts {
+    /// Pay on `chain` from `wallet` through the Disperse contract at
+    /// `disperse`, usually [`DISPERSE`]
+    pub fn new(
+        chain: Chain,
+        adapter: EvmAdapter,
+        wallet: EvmWallet,
+        disperse: impl Into<String>,
+    ) -> Self {
+        Self {
+            chain,
+            adapter,
+            wallet,
+            disperse: disperse.into(),
+            batch_size: 100,
+            next_nonce: Mutex::new(None),
+        }
+    }
+
+    /// Set the most transfers per batch
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size;
+        self
+    }
+
+    /// Allow the Disperse contract to spend `amount` of `token`, returning
+    /// the transaction hash
+    pub async fn approve(&self, token: &str, amount: u128) -> Result<String> {
+        let data = call_data(
+            "approve(address,uint256)",
+            &[
+                Token::Address(parse_address(&self.disperse)?),
+                Token::Uint(amount.into()),
+            ],
+        );
+        self.send(parse_address(token)?, U256::zero(), data).await
+    }
+
+    async fn allowance(&self, token: &str) -> Result<U256> {
+        let data = call_data(
+            "allowance(address,address)",
+            &[
+                Token::Address(self.wallet.eth_address()),
+                Token::Address(parse_address(&self.disperse)?),
+            ],
+        );
+        let output = self.adapter.call_contract(token, data).await?;
+        match abi::decode(&[ParamType::Uint(256)], &output)
+            .ok()
+            .as_deref()
+        {
+            Some([Token::Uint(allowance)]) => Ok(*allowance),
+            _ => Err(Error::Transaction(format!(
+                "Unexpected allowance response from {}",
+                token
+            ))),
+        }
+    }
+
+    /// Send a transaction with the next local nonce
+    async fn send(&self, to: EthAddress, value: U256, data: Vec<u8>) -> Result<String> {
+        let executor = self.adapter.transaction_executor();
+        let mut next_nonce = self.next_nonce.lock().await;
+        let mut tx = executor
+            .build_transaction(&self.wallet, to, value, Some(data), None)
+            .await?;
+
+        // The chain's count misses our transactions that are not mined yet
+        let nonce = match (tx.nonce().copied(), *next_nonce) {
+            (Some(chain), Some(local)) => chain.max(local),
+            (chain, local) => chain.or(local).unwrap_or_default(),
+        };
+        tx.set_nonce(nonce);
+
+        let hash = executor.send_raw_transaction(&self.wallet, tx).await?;
+        *next_nonce = Some(nonce + 1);
+        Ok(format!("{:?}", hash))
+    }
+}
+
+#[async_trait]
+impl PayoutExecutor for EvmPayouts {
+    fn chain(&self) -> &Chain {
+        &self.chain
+    }
+
+    fn supports_token(&self, token: &str) -> bool {
+        token.eq_ignore_ascii_case(NATIVE) || token.parse::<EthAddress>().is_ok()
+    }
+
+    fn max_batch_size(&self) -> usize {
+        self.batch_size
+    }
+
+    #[allow(clippy::result_large_err)]
+    async fn pay(&self, batch: &PayoutBatch) -> Result<String> {
+        let recipients = batch
+            .entries
+            .iter()
+            .map(|entry| parse_address(&entry.address).map(Token::Address))
+            .collect::<Result<Vec<_>>>()?;
+        let values = batch
+            .entries
+            .iter()
+            .map(|entry| Token::Uint(entry.amount.into()))
+            .collect();
+        let total = U256::from(batch.total());
+
+        if batch.entries.iter().all(PayoutEntry::is_native) {
+            let data = call_data(
+                "disperseEther(address[],uint256[])",
+                &[Token::Array(recipients), Token::Array(values)],
+            );
+            return self.send(parse_address(&self.disperse)?, total, data).await;
+        }
+
+        let allowance = self.allowance(&batch.token).await?;
+        if allowance < total {
+            return Err(Error::Transaction(format!(
+                "Disperse contract {} may spend only {} of {}; approve it first",
+                self.disperse, allowance, batch.token
+            )));
+        }
+        let data = call_data(
+            "disperseToken(address,address[],uint256[])",
+            &[
+                Token::Address(parse_address(&batch.token)?),
+                Token::Array(recipients),
+                Token::Array(values),
+            ],
+        );
+        self.send(parse_address(&self.disperse)?, U256::zero(), data)
+            .await
+    }
+}
+
+fn call_data(signature: &str, args: &[Token]) -> Vec<u8> {
+    let mut data = ethers::utils::id(signature).to_vec();
+    data.extend(abi::encode(args));
+    data
+}
+
+#[allow(clippy::result_large_err)]
+fn parse_address(address: &str) -> Result<EthAddress> {
+    address
+        .parse()
+        .map_err(|_| Error::InvalidAddress(address.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex as StdMutex;
+
+    const ALICE: &str = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+    const BOB: &str = "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty";
+    const CAROL: &str = "0x742D35CC6634c0532925A3b844BC9E7595F0BEb0";
+
+    /// Executor recording batches, failing the first `failures` attempts
+    struct FakeExecutor {
+        chain: Chain,
+        batch_size: usize,
+        failures: StdMutex<u32>,
+        paid: StdMutex<Vec<PayoutBatch>>,
+    }
+
+    impl FakeExecutor {
+        fn new(chain: Chain, batch_size: usize, failures: u32) -> Arc<Self> {
+            Arc::new(Self {
+                chain,
+                batch_size,
+                failures: StdMutex::new(failures),
+                paid: StdMutex::new(Vec::new()),
+            })
+        }
+    }
+
+    #[async_trait]
+    impl PayoutExecutor for Arc<FakeExecutor> {
+        fn chain(&self) -> &Chain {
+            &self.chain
+        }
+
+        fn supports_token(&self, token: &str) -> bool {
+            token == NATIVE || token == "1984"
+        }
+
+        fn max_batch_size(&self) -> usize {
+            self.batch_size
+        }
+
+        async fn pay(&self, batch: &PayoutBatch) -> Result<String> {
+            let mut failures = self.failures.lock().unwrap();
+            if *failures > 0 {
+                *failures -= 1;
+                return Err(Error::Transaction("node unavailable".to_string()));
+            }
+            let mut paid = self.paid.lock().unwrap();
+            paid.push(batch.clone());
+            Ok(format!("0x{:02x}", paid.len()))
+        }
+    }
+
+    fn engine(executor: &Arc<FakeExecutor>) -> PayoutEngine {
+        PayoutEngine::new()
+            .with_executor(executor.clone())
+            .with_retry_delay(Duration::ZERO)
+    }
+
+    #[test]
+    fn test_parse_csv() {
+        let csv = format!(
+            "# payroll\nchain,address,amount\npolkadot,{},100\n\nPolkadot,\"{}\",250\n",
+            ALICE, BOB
+        );
+        let entries = parse_csv(&csv).unwrap();
+        assert_eq!(
+            entries,
+            vec![
+                PayoutEntry::native(ALICE, 100, Chain::Polkadot),
+                PayoutEntry::native(BOB, 250, Chain::Polkadot),
+            ]
+        );
+
+        let entries = parse_csv(&format!("{},5,1984,kusama", ALICE)).unwrap();
+        assert_eq!(
+            entries,
+            vec![PayoutEntry::new(ALICE, 5, "1984", Chain::Kusama)]
+        );
+
+        let error = parse_csv(&format!(
+            "{},5,native,kusama\n{},-1,native,kusama",
+            ALICE, BOB
+        ))
+        .unwrap_err();
+        assert!(error.to_string().contains("Line 2"));
+        assert!(parse_csv("address,amount\n").is_err());
+    }
+
+    #[test]
+    fn test_validate_flags_bad_entries() {
+        let executor = FakeExecutor::new(Chain::Polkadot, 10, 0);
+        let entries = vec![
+            PayoutEntry::native(ALICE, 100, Chain::Polkadot),
+            PayoutEntry::native(ALICE, 100, Chain::Polkadot),
+            PayoutEntry::native(CAROL, 100, Chain::Polkadot),
+            PayoutEntry::native(BOB, 0, Chain::Polkadot),
+            PayoutEntry::new(BOB, 1, "USDT", Chain::Polkadot),
+            PayoutEntry::native(CAROL, 1, Chain::Ethereum),
+        ];
+        let issues = engine(&executor).validate(&entries);
+        let indexes: Vec<_> = issues.iter().map(|issue| issue.index).collect();
+        assert_eq!(indexes, vec![1, 2, 3, 4, 5]);
+        assert!(issues[0].reason.contains("Duplicate"));
+
+        assert!(engine(&executor).plan(&entries).is_err());
+    }
+
+    #[test]
+    fn test_plan_chunks_by_chain_and_token() {
+        let executor = FakeExecutor::new(Chain::Polkadot, 2, 0);
+        let entries = vec![
+            PayoutEntry::native(ALICE, 1, Chain::Polkadot),
+            PayoutEntry::new(ALICE, 2, "1984", Chain::Polkadot),
+            PayoutEntry::native(BOB, 3, Chain::Polkadot),
+            PayoutEntry::native(
+                "5DAAnrj7VHTznn2AWBemMuyBwZWs6FNFjdyVXUeYum3PTXFy",
+                4,
+                Chain::Polkadot,
+            ),
+        ];
+        let batches = engine(&executor).plan(&entries).unwrap();
+        let shape: Vec<_> = batches
+            .iter()
+            .map(|batch| (batch.token.as_str(), batch.entries.len(), batch.total()))
+            .collect();
+        assert_eq!(shape, vec![(NATIVE, 2, 4), (NATIVE, 1, 4), ("1984", 1, 2)]);
+    }
+
+    #[tokio::test]
+    async fn test_execute_retries_and_reports() {
+        let executor = FakeExecutor::new(Chain::Polkadot, 1, 1);
+        let entries = vec![
+            PayoutEntry::native(ALICE, 100, Chain::Polkadot),
+            PayoutEntry::native(BOB, 50, Chain::Polkadot),
+        ];
+
+        let report = engine(&executor).execute(&entries).await.unwrap();
+        assert!(report.is_complete());
+        assert_eq!(report.batches[0].attempts, 2);
+        assert_eq!(report.total_paid(&Chain::Polkadot, NATIVE), 150);
+        assert_eq!(executor.paid.lock().unwrap().len(), 2);
+
+        let csv = report.to_csv();
+        assert_eq!(csv.lines().count(), 3);
+        assert_eq!(
+            csv.lines().nth(1).unwrap(),
+            format!("{},100,native,Polkadot,paid,0x01,", ALICE)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_execute_reports_failed_batches() {
+        let executor = FakeExecutor::new(Chain::Polkadot, 1, 5);
+        let entries = vec![
+            PayoutEntry::native(ALICE, 100, Chain::Polkadot),
+            PayoutEntry::native(BOB, 50, Chain::Polkadot),
+        ];
+
+        let report = engine(&executor)
+            .with_max_retries(2)
+            .execute(&entries)
+            .await
+            .unwrap();
+        // Three attempts fail the first batch, the second succeeds after two
+        assert_eq!(report.failed().len(), 1);
+        assert_eq!(report.paid().len(), 1);
+        assert_eq!(report.paid()[0].0.address, BOB);
+        assert!(report
+            .to_csv()
+            .contains("failed,,Transaction error: node unavailable"));
+    }
+}
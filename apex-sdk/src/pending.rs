@@ -0,0 +1,268 @@
+//! Queue of pending EVM transactions with automatic resubmission
+//!
+//! A [`PendingTxQueue`] sends transactions from one wallet and watches them
+//! until they are mined. One that sits in the mempool longer than the
+//! configured [`pending_threshold`](WatchdogConfig::pending_threshold) is
+//! re-sent with the same nonce and higher fees, or only reported to an alert
+//! callback when resubmission is off. Every action is counted in
+//! [`PendingTxMetrics`].
+//!
+//! The queue is built on a [`TransactionWatchdog`]; use the watchdog directly
+//! for Substrate transactions or custom repricers.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use apex_sdk::evm::wallet::Wallet;
+//! use apex_sdk::pending::PendingTxQueue;
+//! use apex_sdk::prelude::*;
+//! use apex_sdk::types::Chain;
+//! use apex_sdk::watchdog::{StuckTransaction, WatchdogConfig};
+//! use std::sync::Arc;
+//! use std::time::Duration;
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<()> {
+//! let sdk = Arc::new(
+//!     ApexSDK::builder()
+//!         .with_evm_endpoint("https://eth.llamarpc.com")
+//!         .build()
+//!         .await?,
+//! );
+//! let wallet = Wallet::new_random().with_chain_id(1);
+//! let queue = Arc::new(
+//!     PendingTxQueue::new(sdk, Chain::Ethereum, wallet.clone())?
+//!         .with_config(WatchdogConfig {
+//!             pending_threshold: Duration::from_secs(60),
+//!             ..Default::default()
+//!         })
+//!         .with_alert(|stuck: &StuckTransaction| eprintln!("still stuck: {}", stuck.tx_hash)),
+//! );
+//! queue.start();
+//!
+//! let hash = queue.send(wallet.eth_address(), 1_000.into(), None).await?;
+//! println!("sent {:?}", hash);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::Result;
+use crate::sdk::ApexSDK;
+use crate::watchdog::{EvmRepricer, Notifier, TransactionWatchdog, WatchdogConfig, WatchdogReport};
+use apex_sdk_evm::transaction::TransactionExecutor;
+use apex_sdk_evm::wallet::Wallet;
+use apex_sdk_types::Chain;
+use ethers::types::transaction::eip2718::TypedTransaction;
+use ethers::types::{Address as EthAddress, H256, U256};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Weak};
+use tokio::task::JoinHandle;
+
+/// Fee increase per resubmission by default, in percent
+pub const DEFAULT_BUMP_PERCENT: u64 = 12;
+
+/// Counters for a [`PendingTxQueue`]
+#[derive(Debug, Default)]
+pub struct PendingTxMetrics {
+    /// Transactions added to the queue
+    pub submitted: AtomicU64,
+    /// Transactions mined or failed, including through a resubmission
+    pub settled: AtomicU64,
+    /// Resubmissions with higher fees
+    pub resubmitted: AtomicU64,
+    /// Stuck transactions reported to the alert callback
+    pub alerted: AtomicU64,
+}
+
+impl PendingTxMetrics {
+    /// Transactions in the queue
+    pub fn pending(&self) -> u64 {
+        self.submitted
+            .load(Ordering::Relaxed)
+            .saturating_sub(self.settled.load(Ordering::Relaxed))
+    }
+
+    fn record(&self, report: &WatchdogReport) {
+        self.settled
+            .fetch_add(report.settled.len() as u64, Ordering::Relaxed);
+        self.resubmitted
+            .fetch_add(report.replaced.len() as u64, Ordering::Relaxed);
+        self.alerted
+            .fetch_add(report.escalated.len() as u64, Ordering::Relaxed);
+    }
+}
+
+/// Sends EVM transactions and resubmits those stuck in the mempool
+pub struct PendingTxQueue {
+    chain: Chain,
+    executor: TransactionExecutor,
+    wallet: Wallet,
+    watchdog: TransactionWatchdog,
+    check_interval: std::time::Duration,
+    auto_resubmit: bool,
+    bump_percent: u64,
+    metrics: PendingTxMetrics,
+    task: std::sync::Mutex<Option<JoinHandle<()>>>,
+}
+
+impl PendingTxQueue {
+    /// Create a queue sending from `wallet` on the SDK's EVM `chain`
+    #[allow(clippy::result_large_err)]
+    pub fn new(sdk: Arc<ApexSDK>, chain: Chain, wallet: Wallet) -> Result<Self> {
+        let executor = sdk.evm()?.transaction_executor();
+        let config = WatchdogConfig::default();
+        Ok(Self {
+            chain,
+            executor,
+            wallet,
+            check_interval: config.check_interval,
+            watchdog: TransactionWatchdog::new(sdk),
+            auto_resubmit: true,
+            bump_percent: DEFAULT_BUMP_PERCENT,
+            metrics: PendingTxMetrics::default(),
+            task: std::sync::Mutex::new(None),
+        })
+    }
+
+    /// Send through `executor` instead of one with default settings
+    pub fn with_executor(mut self, executor: TransactionExecutor) -> Self {
+        self.executor = executor;
+        self
+    }
+
+    /// Set how long a transaction may stay pending, how often to check and
+    /// how many resubmissions to send before alerting
+    pub fn with_config(mut self, config: WatchdogConfig) -> Self {
+        self.check_interval = config.check_interval;
+        self.watchdog = self.watchdog.with_config(config);
+        self
+    }
+
+    /// Report transactions still stuck after the last resubmission, or
+    /// every stuck transaction without auto-resubmission, to `alert`
+    pub fn with_alert(mut self, alert: impl Notifier + 'static) -> Self {
+        self.watchdog = self.watchdog.with_notifier(Arc::new(alert));
+        self
+    }
+
+    /// Resubmit stuck transactions with higher fees (the default), or only
+    /// alert
+    pub fn with_auto_resubmit(mut self, enabled: bool) -> Self {
+        self.auto_resubmit = enabled;
+        self
+    }
+
+    /// Set the fee increase per resubmission in percent (minimum 10)
+    pub fn with_bump_percent(mut self, percent: u64) -> Self {
+        self.bump_percent = percent;
+        self
+    }
+
+    /// Counters of what the queue did
+    pub fn metrics(&self) -> &PendingTxMetrics {
+        &self.metrics
+    }
+
+    /// Hashes the queued transactions were sent under, sorted
+    pub async fn pending(&self) -> Vec<String> {
+        self.watchdog.tracked().await
+    }
+
+    /// Build, sign and send a transaction to `to`, and queue it
+    pub async fn send(&self, to: EthAddress, value: U256, data: Option<Vec<u8>>) -> Result<H256> {
+        let tx = self
+            .executor
+            .build_transaction(&self.wallet, to, value, data, None)
+            .await?;
+        self.submit(tx).await
+    }
+
+    /// Sign and send a built transaction, and queue it
+    ///
+    /// The transaction needs a nonce to be resubmitted.
+    pub async fn submit(&self, tx: TypedTransaction) -> Result<H256> {
+        let hash = self
+            .executor
+            .send_raw_transaction(&self.wallet, tx.clone())
+            .await?;
+        self.track(hash, tx).await;
+        Ok(hash)
+    }
+
+    /// Queue `tx`, already sent from this queue's wallet as `hash`
+    pub async fn track(&self, hash: H256, tx: TypedTransaction) {
+        let hash = format!("{:?}", hash);
+        if self.auto_resubmit && tx.nonce().is_some() {
+            let repricer = EvmRepricer::new(self.executor.clone(), self.wallet.clone(), tx)
+                .with_bump_percent(self.bump_percent);
+            self.watchdog
+                .track_with_repricer(self.chain.clone(), hash, repricer)
+                .await;
+        } else {
+            self.watchdog.track(self.chain.clone(), hash).await;
+        }
+        self.metrics.submitted.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Check every queued transaction once, resubmitting or alerting on
+    /// stuck ones
+    pub async fn check(&self) -> WatchdogReport {
+        let report = self.watchdog.check().await;
+        self.metrics.record(&report);
+        report
+    }
+
+    /// Check queued transactions every `check_interval` in the background
+    ///
+    /// The background task stops at its next check after the queue is
+    /// dropped.
+    pub fn start(self: &Arc<Self>) {
+        let queue: Weak<Self> = Arc::downgrade(self);
+        let interval = self.check_interval;
+        let task = tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(interval).await;
+                let Some(queue) = queue.upgrade() else {
+                    return;
+                };
+                queue.check().await;
+            }
+        });
+        if let Some(previous) = self
+            .task
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .replace(task)
+        {
+            previous.abort();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::watchdog::StuckTransaction;
+
+    #[test]
+    fn test_metrics_record_report() {
+        let metrics = PendingTxMetrics::default();
+        metrics.submitted.store(3, Ordering::Relaxed);
+        let stuck = StuckTransaction {
+            chain: Chain::Ethereum,
+            tx_hash: "0x01".to_string(),
+            latest_hash: "0x02".to_string(),
+            pending_for: std::time::Duration::from_secs(90),
+            replacements: 1,
+        };
+        metrics.record(&WatchdogReport {
+            settled: vec!["0x03".to_string()],
+            replaced: vec![stuck.clone()],
+            escalated: vec![stuck.clone(), stuck],
+        });
+
+        assert_eq!(metrics.pending(), 2);
+        assert_eq!(metrics.resubmitted.load(Ordering::Relaxed), 1);
+        assert_eq!(metrics.alerted.load(Ordering::Relaxed), 2);
+    }
+}
@@ -0,0 +1,940 @@
+//! Transaction policy engine
+//!
+//! A [`PolicyEngine`] attached to the SDK is consulted by
+//! [`ApexSDK::execute`](crate::ApexSDK::execute) before a transaction is
+//! signed. Every [`Policy`] must allow the transaction; policies can also ask
+//! for human approval, which is delegated to an [`Approver`].
+//!
+//! Built-in policies:
+//! - [`SpendLimitPolicy`]: per-signer spend limits over a rolling 24h window
+//! - [`AddressListPolicy`]: destination allowlist / denylist
+//! - [`FeeCapPolicy`]: cap on the declared max fee
+//! - [`ApprovalPolicy`]: require approval above an amount
+//!
+//! Amounts are compared per chain and token: native limits apply to the
+//! native token of each chain, and ERC-20 or `Assets` pallet tokens are only
+//! permitted up to limits set for them.
+//!
+//! Policies can be configured in code or loaded from a [`PolicySource`]
+//! (e.g. a JSON file) and hot-reloaded with [`PolicyEngine::reload`].
+//!
+//! # Example
+//!
+//! ```rust
+//! use apex_sdk::policy::{AddressListPolicy, PolicyEngine, SpendLimitPolicy};
+//!
+//! let engine = PolicyEngine::new()
+//!     .with_policy(SpendLimitPolicy::new().with_default_limit(10u128.pow(18)))
+//!     .with_policy(AddressListPolicy::new().deny("0x000000000000000000000000000000000000dEaD"));
+//! ```
+
+use crate::error::{Error, Result};
+use crate::transaction::Transaction;
+use apex_sdk_types::{Address, Chain, Token};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
+
+/// Default spend limit window
+pub const DAILY: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Outcome of evaluating a policy
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PolicyDecision {
+    /// The transaction may proceed
+    Allow,
+    /// The transaction must not be signed
+    Deny(String),
+    /// The transaction may proceed once approved
+    RequireApproval(String),
+}
+
+/// A rule consulted before signing
+#[async_trait]
+pub trait Policy: Send + Sync {
+    /// Name used in violation messages
+    fn name(&self) -> &str;
+
+    /// Decide whether `tx` may be signed
+    async fn evaluate(&self, tx: &Transaction) -> Result<PolicyDecision>;
+
+    /// Called after `tx` was executed successfully
+    async fn on_executed(&self, _tx: &Transaction) {}
+}
+
+/// Grants or rejects transactions that require approval
+#[async_trait]
+pub trait Approver: Send + Sync {
+    /// Approve `tx`, given the reasons approval was required
+    async fn approve(&self, tx: &Transaction, reasons: &[String]) -> Result<bool>;
+}
+
+/// Normalize an address for comparisons (EVM addresses are case-insensitive)
+pub(crate) fn normalize(address: &str) -> String {
+    if address.starts_with("0x") {
+        address.to_lowercase()
+    } else {
+        address.to_string()
+    }
+}
+
+/// Key identifying the account behind `address`: its decoded account bytes,
+/// so one account matches whatever its hex casing or SS58 prefix
+pub(crate) fn account_key(address: &str) -> String {
+    match address
+        .parse::<Address>()
+        .ok()
+        .and_then(|a| a.account_bytes())
+    {
+        Some(bytes) => format!("0x{}", hex::encode(bytes)),
+        None => normalize(address),
+    }
+}
+
+/// Identity of `token` on `chain`, ignoring declared decimals
+fn asset_key(chain: &Chain, token: &Token) -> String {
+    match token {
+        Token::Native => format!("{}/native", chain),
+        Token::Erc20 { address, .. } => format!("{}/erc20/{}", chain, address.to_lowercase()),
+        Token::Asset { id, .. } => format!("{}/asset/{}", chain, id),
+    }
+}
+
+/// An amount of one token on one chain, in the token's smallest unit
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenLimit {
+    /// Chain the token lives on
+    pub chain: Chain,
+    /// Token the amount is in
+    pub token: Token,
+    /// Amount in the token's smallest unit
+    pub amount: u128,
+}
+
+/// Amounts spent per signer, chain and token within a rolling window
+pub struct SpendLedger {
+    window: Duration,
+    entries: Mutex<HashMap<String, VecDeque<(Instant, u128)>>>,
+}
+
+impl SpendLedger {
+    /// Create a ledger with the given window
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn key(signer: &str, chain: &Chain, token: &Token) -> String {
+        format!("{} {}", account_key(signer), asset_key(chain, token))
+    }
+
+    /// Total of `token` on `chain` spent by `signer` within the window
+    pub fn spent(&self, signer: &str, chain: &Chain, token: &Token) -> u128 {
+        let mut entries = self.entries.lock().unwrap();
+        let Some(spends) = entries.get_mut(&Self::key(signer, chain, token)) else {
+            return 0;
+        };
+        while spends
+            .front()
+            .is_some_and(|(at, _)| at.elapsed() >= self.window)
+        {
+            spends.pop_front();
+        }
+        spends.iter().map(|(_, amount)| *amount).sum()
+    }
+
+    /// Record a spend of `amount` of `token` on `chain` by `signer`
+    pub fn record(&self, signer: &str, chain: &Chain, token: &Token, amount: u128) {
+        self.entries
+            .lock()
+            .unwrap()
+            .entry(Self::key(signer, chain, token))
+            .or_default()
+            .push_back((Instant::now(), amount));
+    }
+}
+
+impl Default for SpendLedger {
+    fn default() -> Self {
+        Self::new(DAILY)
+    }
+}
+
+/// Per-signer spend limit over a rolling window (24h by default)
+///
+/// Default and signer limits are in the smallest unit of the source chain's
+/// native token, and spends are totalled separately on each chain. Tokens
+/// are limited with [`with_token_limit`](Self::with_token_limit); a limited
+/// signer may not send tokens without one. Spends are recorded once a
+/// transaction executes, so concurrent transactions from the same signer are
+/// each checked against the same remaining budget.
+pub struct SpendLimitPolicy {
+    default_limit: Option<u128>,
+    limits: HashMap<String, u128>,
+    token_limits: HashMap<String, u128>,
+    ledger: Arc<SpendLedger>,
+}
+
+impl SpendLimitPolicy {
+    /// Create a policy with no limits
+    pub fn new() -> Self {
+        Self {
+            default_limit: None,
+            limits: HashMap::new(),
+            token_limits: HashMap::new(),
+            ledger: Arc::new(SpendLedger::default()),
+        }
+    }
+
+    /// Limit for signers without an explicit limit
+    pub fn with_default_limit(mut self, limit: u128) -> Self {
+        self.default_limit = Some(limit);
+        self
+    }
+
+    /// Limit for a specific signer
+    pub fn with_signer_limit(mut self, signer: &str, limit: u128) -> Self {
+        self.limits.insert(account_key(signer), limit);
+        self
+    }
+
+    /// Limit for every signer's spends of `token` on `chain`, in the token's
+    /// smallest unit
+    pub fn with_token_limit(mut self, chain: &Chain, token: &Token, limit: u128) -> Self {
+        self.token_limits.insert(asset_key(chain, token), limit);
+        self
+    }
+
+    /// Share a ledger, e.g. to keep history across policy reloads
+    pub fn with_ledger(mut self, ledger: Arc<SpendLedger>) -> Self {
+        self.ledger = ledger;
+        self
+    }
+
+    /// The spend ledger
+    pub fn ledger(&self) -> &Arc<SpendLedger> {
+        &self.ledger
+    }
+}
+
+impl Default for SpendLimitPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Policy for SpendLimitPolicy {
+    fn name(&self) -> &str {
+        "spend-limit"
+    }
+
+    async fn evaluate(&self, tx: &Transaction) -> Result<PolicyDecision> {
+        let signer = account_key(tx.from.as_str());
+        let native_limit = self.limits.get(&signer).copied().or(self.default_limit);
+        let limit = if tx.token.is_native() {
+            native_limit
+        } else {
+            let limit = self
+                .token_limits
+                .get(&asset_key(&tx.source_chain, &tx.token))
+                .copied();
+            if limit.is_none() && native_limit.is_some() {
+                return Ok(PolicyDecision::Deny(format!(
+                    "no limit set for {} on {}",
+                    tx.token, tx.source_chain
+                )));
+            }
+            limit
+        };
+        let Some(limit) = limit else {
+            return Ok(PolicyDecision::Allow);
+        };
+
+        let spent = self.ledger.spent(&signer, &tx.source_chain, &tx.token);
+        if spent.saturating_add(tx.amount) > limit {
+            return Ok(PolicyDecision::Deny(format!(
+                "{} would exceed limit of {} ({} already spent)",
+                tx.amount, limit, spent
+            )));
+        }
+        Ok(PolicyDecision::Allow)
+    }
+
+    async fn on_executed(&self, tx: &Transaction) {
+        self.ledger
+            .record(tx.from.as_str(), &tx.source_chain, &tx.token, tx.amount);
+    }
+}
+
+/// Destination allowlist and denylist
+///
+/// The denylist always wins. If the allowlist is non-empty, only listed
+/// destinations are permitted.
+#[derive(Default)]
+pub struct AddressListPolicy {
+    allow: HashSet<String>,
+    deny: HashSet<String>,
+}
+
+impl AddressListPolicy {
+    /// Create a policy with empty lists
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Permit a destination
+    pub fn allow(mut self, address: &str) -> Self {
+        self.allow.insert(account_key(address));
+        self
+    }
+
+    /// Forbid a destination
+    pub fn deny(mut self, address: &str) -> Self {
+        self.deny.insert(account_key(address));
+        self
+    }
+}
+
+#[async_trait]
+impl Policy for AddressListPolicy {
+    fn name(&self) -> &str {
+        "address-list"
+    }
+
+    async fn evaluate(&self, tx: &Transaction) -> Result<PolicyDecision> {
+        let to = account_key(tx.to.as_str());
+        if self.deny.contains(&to) {
+            return Ok(PolicyDecision::Deny(format!(
+                "{} is denylisted",
+                tx.to.as_str()
+            )));
+        }
+        if !self.allow.is_empty() && !self.allow.contains(&to) {
+            return Ok(PolicyDecision::Deny(format!(
+                "{} is not allowlisted",
+                tx.to.as_str()
+            )));
+        }
+        Ok(PolicyDecision::Allow)
+    }
+}
+
+/// Cap on the max fee declared with
+/// [`TransactionBuilder::with_max_fee`](crate::TransactionBuilder::with_max_fee)
+///
+/// Transactions without a declared max fee are denied unless
+/// [`allow_undeclared`](Self::allow_undeclared) is set.
+pub struct FeeCapPolicy {
+    max_fee: u128,
+    allow_undeclared: bool,
+}
+
+impl FeeCapPolicy {
+    /// Deny transactions whose max fee exceeds `max_fee`
+    pub fn new(max_fee: u128) -> Self {
+        Self {
+            max_fee,
+            allow_undeclared: false,
+        }
+    }
+
+    /// Let transactions without a declared max fee through
+    pub fn allow_undeclared(mut self) -> Self {
+        self.allow_undeclared = true;
+        self
+    }
+}
+
+#[async_trait]
+impl Policy for FeeCapPolicy {
+    fn name(&self) -> &str {
+        "fee-cap"
+    }
+
+    async fn evaluate(&self, tx: &Transaction) -> Result<PolicyDecision> {
+        Ok(match tx.max_fee {
+            Some(fee) if fee > self.max_fee => {
+                PolicyDecision::Deny(format!("max fee {} exceeds cap of {}", fee, self.max_fee))
+            }
+            Some(_) => PolicyDecision::Allow,
+            None if self.allow_undeclared => PolicyDecision::Allow,
+            None => PolicyDecision::Deny("max fee must be declared".to_string()),
+        })
+    }
+}
+
+/// Require approval for amounts above a threshold
+///
+/// The threshold is in the smallest unit of the source chain's native
+/// token. Token transfers need approval unless a threshold was set for the
+/// token with [`with_token_threshold`](Self::with_token_threshold).
+pub struct ApprovalPolicy {
+    threshold: u128,
+    token_thresholds: HashMap<String, u128>,
+}
+
+impl ApprovalPolicy {
+    /// Require approval when a native amount exceeds `threshold`
+    pub fn new(threshold: u128) -> Self {
+        Self {
+            threshold,
+            token_thresholds: HashMap::new(),
+        }
+    }
+
+    /// Require approval when an amount of `token` on `chain` exceeds
+    /// `threshold`, in the token's smallest unit
+    pub fn with_token_threshold(mut self, chain: &Chain, token: &Token, threshold: u128) -> Self {
+        self.token_thresholds
+            .insert(asset_key(chain, token), threshold);
+        self
+    }
+}
+
+#[async_trait]
+impl Policy for ApprovalPolicy {
+    fn name(&self) -> &str {
+        "approval"
+    }
+
+    async fn evaluate(&self, tx: &Transaction) -> Result<PolicyDecision> {
+        let threshold = if tx.token.is_native() {
+            self.threshold
+        } else {
+            let key = asset_key(&tx.source_chain, &tx.token);
+            let Some(threshold) = self.token_thresholds.get(&key).copied() else {
+                return Ok(PolicyDecision::RequireApproval(format!(
+                    "no approval threshold set for {} on {}",
+                    tx.token, tx.source_chain
+                )));
+            };
+            threshold
+        };
+
+        if tx.amount > threshold {
+            Ok(PolicyDecision::RequireApproval(format!(
+                "amount {} exceeds approval threshold of {}",
+                tx.amount, threshold
+            )))
+        } else {
+            Ok(PolicyDecision::Allow)
+        }
+    }
+}
+
+/// Declarative policy configuration, as loaded from a [`PolicySource`]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PolicyConfig {
+    /// Daily limit for signers without an explicit limit
+    pub default_daily_limit: Option<u128>,
+    /// Daily limits by signer address
+    pub daily_limits: HashMap<String, u128>,
+    /// Permitted destinations (empty permits all)
+    pub allowlist: Vec<String>,
+    /// Forbidden destinations
+    pub denylist: Vec<String>,
+    /// Cap on the declared max fee
+    pub max_fee: Option<u128>,
+    /// Daily limits by chain and token, for every signer
+    pub token_daily_limits: Vec<TokenLimit>,
+    /// Amount above which approval is required
+    pub approval_threshold: Option<u128>,
+    /// Approval thresholds by chain and token
+    pub token_approval_thresholds: Vec<TokenLimit>,
+}
+
+impl PolicyConfig {
+    /// Build the policies described by this configuration
+    pub fn into_policies(self, ledger: Arc<SpendLedger>) -> Vec<Arc<dyn Policy>> {
+        let mut policies: Vec<Arc<dyn Policy>> = Vec::new();
+
+        if self.default_daily_limit.is_some()
+            || !self.daily_limits.is_empty()
+            || !self.token_daily_limits.is_empty()
+        {
+            let mut spend = SpendLimitPolicy::new().with_ledger(ledger);
+            if let Some(limit) = self.default_daily_limit {
+                spend = spend.with_default_limit(limit);
+            }
+            for (signer, limit) in &self.daily_limits {
+                spend = spend.with_signer_limit(signer, *limit);
+            }
+            for limit in &self.token_daily_limits {
+                spend = spend.with_token_limit(&limit.chain, &limit.token, limit.amount);
+            }
+            policies.push(Arc::new(spend));
+        }
+
+        if !self.allowlist.is_empty() || !self.denylist.is_empty() {
+            let mut lists = AddressListPolicy::new();
+            for address in &self.allowlist {
+                lists = lists.allow(address);
+            }
+            for address in &self.denylist {
+                lists = lists.deny(address);
+            }
+            policies.push(Arc::new(lists));
+        }
+
+        if let Some(max_fee) = self.max_fee {
+            policies.push(Arc::new(FeeCapPolicy::new(max_fee)));
+        }
+
+        if self.approval_threshold.is_some() || !self.token_approval_thresholds.is_empty() {
+            let mut approval = ApprovalPolicy::new(self.approval_threshold.unwrap_or(u128::MAX));
+            for threshold in &self.token_approval_thresholds {
+                approval = approval.with_token_threshold(
+                    &threshold.chain,
+                    &threshold.token,
+                    threshold.amount,
+                );
+            }
+            policies.push(Arc::new(approval));
+        }
+
+        policies
+    }
+}
+
+/// Where policy configuration comes from
+#[async_trait]
+pub trait PolicySource: Send + Sync {
+    /// Load the current configuration
+    async fn load(&self) -> Result<PolicyConfig>;
+}
+
+#[async_trait]
+impl PolicySource for PolicyConfig {
+    async fn load(&self) -> Result<PolicyConfig> {
+        Ok(self.clone())
+    }
+}
+
+/// Policy configuration read from a JSON file
+pub struct FilePolicySource {
+    path: PathBuf,
+}
+
+impl FilePolicySource {
+    /// Read configuration from `path`
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+#[async_trait]
+impl PolicySource for FilePolicySource {
+    async fn load(&self) -> Result<PolicyConfig> {
+        let json = tokio::fs::read_to_string(&self.path).await.map_err(|e| {
+            Error::Config(format!(
+                "Failed to read policy file {}: {}",
+                self.path.display(),
+                e
+            ))
+        })?;
+        serde_json::from_str(&json).map_err(|e| {
+            Error::Config(format!(
+                "Invalid policy file {}: {}",
+                self.path.display(),
+                e
+            ))
+        })
+    }
+}
+
+/// Evaluates policies before transactions are signed
+#[derive(Default)]
+pub struct PolicyEngine {
+    policies: Vec<Arc<dyn Policy>>,
+    sourced: RwLock<Vec<Arc<dyn Policy>>>,
+    ledger: Arc<SpendLedger>,
+    approver: Option<Arc<dyn Approver>>,
+}
+
+impl PolicyEngine {
+    /// Create an engine with no policies
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create an engine from a policy source
+    pub async fn from_source(source: &dyn PolicySource) -> Result<Self> {
+        let engine = Self::new();
+        engine.reload(source).await?;
+        Ok(engine)
+    }
+
+    /// Add a policy
+    pub fn with_policy(mut self, policy: impl Policy + 'static) -> Self {
+        self.policies.push(Arc::new(policy));
+        self
+    }
+
+    /// Set the approver for transactions that require approval
+    ///
+    /// Without an approver, such transactions are rejected.
+    pub fn with_approver(mut self, approver: impl Approver + 'static) -> Self {
+        self.approver = Some(Arc::new(approver));
+        self
+    }
+
+    /// Replace the policies loaded from a source
+    ///
+    /// Policies added with [`with_policy`](Self::with_policy) are kept, and
+    /// spend history carries over.
+    pub async fn reload(&self, source: &dyn PolicySource) -> Result<()> {
+        let policies = source.load().await?.into_policies(self.ledger.clone());
+        *self.sourced.write().unwrap() = policies;
+        Ok(())
+    }
+
+    fn all_policies(&self) -> Vec<Arc<dyn Policy>> {
+        let mut policies = self.policies.clone();
+        policies.extend(self.sourced.read().unwrap().iter().cloned());
+        policies
+    }
+
+    /// Check `tx` against all policies
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Policy`] if any policy denies the transaction, or if
+    /// approval is required and not granted.
+    pub async fn check(&self, tx: &Transaction) -> Result<()> {
+        self.check_decisions(tx).await.map(|_| ())
+    }
+
+    /// Check `tx` against all policies, returning each policy's decision
+    ///
+    /// Fails like [`check`](Self::check); on success every returned decision
+    /// is either an allow or an approval that was granted.
+    pub async fn check_decisions(&self, tx: &Transaction) -> Result<Vec<(String, PolicyDecision)>> {
+        let mut decisions = Vec::new();
+        let mut approvals = Vec::new();
+        for policy in self.all_policies() {
+            let decision = policy.evaluate(tx).await?;
+            match &decision {
+                PolicyDecision::Allow => {}
+                PolicyDecision::Deny(reason) => {
+                    tracing::warn!("Policy {} denied transaction: {}", policy.name(), reason);
+                    return Err(Error::Policy(format!("{}: {}", policy.name(), reason)));
+                }
+                PolicyDecision::RequireApproval(reason) => {
+                    approvals.push(format!("{}: {}", policy.name(), reason));
+                }
+            }
+            decisions.push((policy.name().to_string(), decision));
+        }
+
+        if approvals.is_empty() {
+            return Ok(decisions);
+        }
+
+        let Some(approver) = &self.approver else {
+            return Err(Error::Policy(format!(
+                "approval required ({})",
+                approvals.join("; ")
+            )));
+        };
+
+        if approver.approve(tx, &approvals).await? {
+            tracing::info!("Transaction approved ({})", approvals.join("; "));
+            Ok(decisions)
+        } else {
+            Err(Error::Policy(format!(
+                "approval rejected ({})",
+                approvals.join("; ")
+            )))
+        }
+    }
+
+    /// Notify policies that `tx` was executed
+    pub async fn record(&self, tx: &Transaction) {
+        for policy in self.all_policies() {
+            policy.on_executed(tx).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transaction::TransactionBuilder;
+
+    const FROM: &str = "0x742D35Cc6634C0532925A3B844bC9e7595f0bEB7";
+    const TO: &str = "0x1234567890123456789012345678901234567890";
+
+    fn tx(amount: u128) -> Transaction {
+        TransactionBuilder::new()
+            .from_evm_address(FROM)
+            .to_evm_address(TO)
+            .amount(amount)
+            .build()
+            .unwrap()
+    }
+
+    struct FixedApprover(bool);
+
+    #[async_trait]
+    impl Approver for FixedApprover {
+        async fn approve(&self, _tx: &Transaction, _reasons: &[String]) -> Result<bool> {
+            Ok(self.0)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_spend_limit_is_cumulative() {
+        let engine = PolicyEngine::new().with_policy(
+            SpendLimitPolicy::new()
+                .with_default_limit(1_000)
+                .with_signer_limit(&FROM.to_uppercase().replace("0X", "0x"), 100),
+        );
+
+        engine.check(&tx(60)).await.unwrap();
+        engine.record(&tx(60)).await;
+        assert!(matches!(
+            engine.check(&tx(60)).await,
+            Err(Error::Policy(msg)) if msg.starts_with("spend-limit")
+        ));
+        engine.check(&tx(40)).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_spend_window_expires() {
+        let ledger = Arc::new(SpendLedger::new(Duration::from_millis(20)));
+        let policy = SpendLimitPolicy::new()
+            .with_default_limit(10)
+            .with_ledger(ledger.clone());
+
+        ledger.record(FROM, &Chain::Ethereum, &Token::Native, 10);
+        assert!(matches!(
+            policy.evaluate(&tx(1)).await.unwrap(),
+            PolicyDecision::Deny(_)
+        ));
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        assert_eq!(
+            policy.evaluate(&tx(1)).await.unwrap(),
+            PolicyDecision::Allow
+        );
+    }
+
+    #[tokio::test]
+    async fn test_spend_limits_per_chain_and_token() {
+        let usdc = Token::erc20("0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48");
+        let usdc_tx = |amount: u128| {
+            TransactionBuilder::new()
+                .from_evm_address(FROM)
+                .to_evm_address(TO)
+                .amount(amount)
+                .token(usdc.clone().with_decimals(6))
+                .build()
+                .unwrap()
+        };
+        let mut polygon_tx = tx(80);
+        polygon_tx.source_chain = Chain::Polygon;
+        polygon_tx.destination_chain = Chain::Polygon;
+
+        let engine =
+            PolicyEngine::new().with_policy(SpendLimitPolicy::new().with_default_limit(100));
+        // A limited signer can't send tokens without a limit for them
+        assert!(matches!(
+            engine.check(&usdc_tx(1)).await,
+            Err(Error::Policy(msg)) if msg.contains("no limit set")
+        ));
+
+        let engine = PolicyEngine::new().with_policy(
+            SpendLimitPolicy::new()
+                .with_default_limit(100)
+                .with_token_limit(&Chain::Ethereum, &usdc, 5_000_000),
+        );
+        engine.record(&tx(80)).await;
+        engine.record(&polygon_tx).await;
+        engine.record(&usdc_tx(4_000_000)).await;
+
+        // Each chain and token has its own budget
+        engine.check(&tx(20)).await.unwrap();
+        assert!(engine.check(&tx(21)).await.is_err());
+        polygon_tx.amount = 20;
+        engine.check(&polygon_tx).await.unwrap();
+        engine.check(&usdc_tx(1_000_000)).await.unwrap();
+        assert!(engine.check(&usdc_tx(1_000_001)).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_address_lists() {
+        let deny = AddressListPolicy::new().deny(&TO.to_uppercase().replace("0X", "0x"));
+        assert!(matches!(
+            deny.evaluate(&tx(1)).await.unwrap(),
+            PolicyDecision::Deny(_)
+        ));
+
+        let allow_other = AddressListPolicy::new().allow(FROM);
+        assert!(matches!(
+            allow_other.evaluate(&tx(1)).await.unwrap(),
+            PolicyDecision::Deny(_)
+        ));
+
+        let allow = AddressListPolicy::new().allow(TO);
+        assert_eq!(allow.evaluate(&tx(1)).await.unwrap(), PolicyDecision::Allow);
+    }
+
+    #[tokio::test]
+    async fn test_address_lists_match_ss58_accounts() {
+        // Alice under the Polkadot (0) and generic Substrate (42) prefixes
+        const ALICE_POLKADOT: &str = "15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5";
+        const ALICE_GENERIC: &str = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+        let to = |address: &str| {
+            TransactionBuilder::new()
+                .from_substrate_account(ALICE_GENERIC)
+                .to_substrate_account(address)
+                .amount(1)
+                .build()
+                .unwrap()
+        };
+
+        let deny = AddressListPolicy::new().deny(ALICE_POLKADOT);
+        for address in [ALICE_POLKADOT, ALICE_GENERIC] {
+            assert!(matches!(
+                deny.evaluate(&to(address)).await.unwrap(),
+                PolicyDecision::Deny(_)
+            ));
+        }
+
+        // Spends of one account add up whatever prefix it signs under
+        let policy = SpendLimitPolicy::new().with_signer_limit(ALICE_POLKADOT, 1);
+        policy.on_executed(&to(ALICE_POLKADOT)).await;
+        assert!(matches!(
+            policy.evaluate(&to(ALICE_POLKADOT)).await.unwrap(),
+            PolicyDecision::Deny(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_fee_cap() {
+        let policy = FeeCapPolicy::new(100);
+        let mut transaction = tx(1);
+        assert!(matches!(
+            policy.evaluate(&transaction).await.unwrap(),
+            PolicyDecision::Deny(_)
+        ));
+
+        transaction.max_fee = Some(100);
+        assert_eq!(
+            policy.evaluate(&transaction).await.unwrap(),
+            PolicyDecision::Allow
+        );
+        transaction.max_fee = Some(101);
+        assert!(matches!(
+            policy.evaluate(&transaction).await.unwrap(),
+            PolicyDecision::Deny(_)
+        ));
+
+        transaction.max_fee = None;
+        assert_eq!(
+            FeeCapPolicy::new(100)
+                .allow_undeclared()
+                .evaluate(&transaction)
+                .await
+                .unwrap(),
+            PolicyDecision::Allow
+        );
+    }
+
+    #[tokio::test]
+    async fn test_approval() {
+        let unattended = PolicyEngine::new().with_policy(ApprovalPolicy::new(100));
+        unattended.check(&tx(100)).await.unwrap();
+        assert!(unattended.check(&tx(101)).await.is_err());
+
+        let approved = PolicyEngine::new()
+            .with_policy(ApprovalPolicy::new(100))
+            .with_approver(FixedApprover(true));
+        approved.check(&tx(101)).await.unwrap();
+
+        let rejected = PolicyEngine::new()
+            .with_policy(ApprovalPolicy::new(100))
+            .with_approver(FixedApprover(false));
+        assert!(matches!(
+            rejected.check(&tx(101)).await,
+            Err(Error::Policy(msg)) if msg.contains("rejected")
+        ));
+
+        // Token amounts are compared to their own threshold only
+        let usdc = Token::erc20("0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48");
+        let mut usdc_tx = tx(1);
+        usdc_tx.token = usdc.clone();
+        assert!(matches!(
+            ApprovalPolicy::new(100).evaluate(&usdc_tx).await.unwrap(),
+            PolicyDecision::RequireApproval(_)
+        ));
+        let policy = ApprovalPolicy::new(100).with_token_threshold(&Chain::Ethereum, &usdc, 1_000);
+        usdc_tx.amount = 1_000;
+        assert_eq!(
+            policy.evaluate(&usdc_tx).await.unwrap(),
+            PolicyDecision::Allow
+        );
+        usdc_tx.amount = 1_001;
+        assert!(matches!(
+            policy.evaluate(&usdc_tx).await.unwrap(),
+            PolicyDecision::RequireApproval(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_reload_from_source_keeps_history() {
+        let config: PolicyConfig = serde_json::from_str(&format!(
+            r#"{{"default_daily_limit": 100, "denylist": ["{}"]}}"#,
+            "0xdead"
+        ))
+        .unwrap();
+        let engine = PolicyEngine::from_source(&config).await.unwrap();
+
+        engine.check(&tx(80)).await.unwrap();
+        engine.record(&tx(80)).await;
+        assert!(engine.check(&tx(30)).await.is_err());
+
+        let relaxed = PolicyConfig {
+            default_daily_limit: Some(200),
+            ..Default::default()
+        };
+        engine.reload(&relaxed).await.unwrap();
+        engine.check(&tx(30)).await.unwrap();
+        assert!(engine.check(&tx(130)).await.is_err());
+    }
+
+    #[test]
+    fn test_config_token_limits() {
+        let config: PolicyConfig = serde_json::from_str(
+            r#"{
+                "default_daily_limit": 100,
+                "token_daily_limits": [
+                    {"chain": "Ethereum", "token": {"erc20": {"address": "0xA0b8"}}, "amount": 5}
+                ],
+                "token_approval_thresholds": [
+                    {"chain": "Polkadot", "token": {"asset": {"id": 1984}}, "amount": 7}
+                ]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.token_daily_limits[0],
+            TokenLimit {
+                chain: Chain::Ethereum,
+                token: Token::erc20("0xA0b8"),
+                amount: 5,
+            }
+        );
+        assert_eq!(
+            config.token_approval_thresholds[0].token,
+            Token::asset(1984)
+        );
+        assert_eq!(config.into_policies(Arc::default()).len(), 2);
+    }
+}
@@ -0,0 +1,341 @@
+//! Portfolio summaries across chains
+//!
+//! [`ApexSDK::portfolio`](crate::ApexSDK::portfolio) reads the native and
+//! token balances of a set of accounts on every configured chain, plus their
+//! staked balance on Substrate chains when enabled, and values them through
+//! a [`PriceOracle`]. What is read and how it is priced is set by the
+//! [`PortfolioConfig`] given to the builder.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use apex_sdk::portfolio::{PortfolioConfig, StaticPrices, TrackedToken};
+//! use apex_sdk::prelude::*;
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<()> {
+//! let sdk = ApexSDK::builder()
+//!     .with_substrate_endpoint("wss://polkadot.api.onfinality.io/public-ws")
+//!     .with_evm_endpoint("https://eth.llamarpc.com")
+//!     .with_portfolio_config(
+//!         PortfolioConfig::new()
+//!             .with_token(TrackedToken::new(
+//!                 Chain::Ethereum,
+//!                 "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48",
+//!                 "USDC",
+//!                 6,
+//!             ))
+//!             .with_staking(true)
+//!             .with_price_oracle(
+//!                 StaticPrices::new()
+//!                     .with_price("DOT", 6.5)
+//!                     .with_price("ETH", 3200.0)
+//!                     .with_price("USDC", 1.0),
+//!             ),
+//!     )
+//!     .build()
+//!     .await?;
+//!
+//! let portfolio = sdk
+//!     .portfolio(&[
+//!         "15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5",
+//!         "0x742D35CC6634c0532925A3b844BC9E7595F0BEb0",
+//!     ])
+//!     .await?;
+//! for holding in &portfolio.holdings {
+//!     println!(
+//!         "{} {} {}: {}",
+//!         holding.account,
+//!         holding.chain,
+//!         holding.symbol,
+//!         holding.formatted_amount()
+//!     );
+//! }
+//! println!("Total: ${:.2}", portfolio.total_value());
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::Result;
+use apex_sdk_types::{format_units, Chain};
+use async_trait::async_trait;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+/// Prices tokens in a common quote currency, usually USD
+#[async_trait]
+pub trait PriceOracle: Send + Sync {
+    /// Price of one whole `symbol` token on `chain`, `None` if unknown
+    async fn price(&self, chain: &Chain, symbol: &str) -> Result<Option<f64>>;
+}
+
+/// Fixed prices by token symbol, on every chain
+#[derive(Debug, Clone, Default)]
+pub struct StaticPrices {
+    prices: HashMap<String, f64>,
+}
+
+impl StaticPrices {
+    /// Create an oracle without prices
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Price one whole `symbol` token at `price`
+    pub fn with_price(mut self, symbol: impl Into<String>, price: f64) -> Self {
+        self.prices.insert(symbol.into(), price);
+        self
+    }
+}
+
+#[async_trait]
+impl PriceOracle for StaticPrices {
+    async fn price(&self, _chain: &Chain, symbol: &str) -> Result<Option<f64>> {
+        Ok(self.prices.get(symbol).copied())
+    }
+}
+
+/// A non-native token whose balances are read
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrackedToken {
+    /// Chain the token lives on
+    pub chain: Chain,
+    /// ERC-20 contract address on EVM chains, `Assets` pallet asset ID on
+    /// Substrate chains
+    pub token: String,
+    /// Symbol the token is priced by
+    pub symbol: String,
+    /// Decimals of the token's smallest unit
+    pub decimals: u8,
+}
+
+impl TrackedToken {
+    /// Track `token` on `chain`
+    pub fn new(
+        chain: Chain,
+        token: impl Into<String>,
+        symbol: impl Into<String>,
+        decimals: u8,
+    ) -> Self {
+        Self {
+            chain,
+            token: token.into(),
+            symbol: symbol.into(),
+            decimals,
+        }
+    }
+}
+
+/// What a portfolio summary reads and how it is valued
+#[derive(Clone, Default)]
+pub struct PortfolioConfig {
+    pub(crate) tokens: Vec<TrackedToken>,
+    pub(crate) staking: bool,
+    pub(crate) oracle: Option<Arc<dyn PriceOracle>>,
+}
+
+impl PortfolioConfig {
+    /// Read native balances only, without valuing them
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Also read balances of `token`
+    pub fn with_token(mut self, token: TrackedToken) -> Self {
+        self.tokens.push(token);
+        self
+    }
+
+    /// Also read staked balances on Substrate chains
+    pub fn with_staking(mut self, staking: bool) -> Self {
+        self.staking = staking;
+        self
+    }
+
+    /// Value holdings through `oracle`
+    pub fn with_price_oracle(mut self, oracle: impl PriceOracle + 'static) -> Self {
+        self.oracle = Some(Arc::new(oracle));
+        self
+    }
+}
+
+impl std::fmt::Debug for PortfolioConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PortfolioConfig")
+            .field("tokens", &self.tokens)
+            .field("staking", &self.staking)
+            .field("oracle", &self.oracle.is_some())
+            .finish()
+    }
+}
+
+/// Kind of balance a holding is
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HoldingKind {
+    /// Free balance of the native currency
+    Native,
+    /// Balance of a [`TrackedToken`], identified by its contract or asset ID
+    Token(String),
+    /// Native currency bonded for staking
+    Staked,
+}
+
+/// One balance of one account
+#[derive(Debug, Clone, PartialEq)]
+pub struct Holding {
+    /// Account holding the balance
+    pub account: String,
+    /// Chain the balance is on
+    pub chain: Chain,
+    /// Kind of balance
+    pub kind: HoldingKind,
+    /// Token symbol
+    pub symbol: String,
+    /// Decimals of `amount`
+    pub decimals: u8,
+    /// Balance in the token's smallest unit
+    pub amount: u128,
+    /// Value of the balance, `None` if the token has no price
+    pub value: Option<f64>,
+}
+
+impl Holding {
+    /// The amount in whole tokens, e.g. `"1.5"`
+    pub fn formatted_amount(&self) -> String {
+        format_units(self.amount, self.decimals)
+    }
+
+    /// Set `value` from the price of one whole token
+    pub(crate) fn priced(mut self, price: Option<f64>) -> Self {
+        self.value =
+            price.map(|price| self.amount as f64 / 10f64.powi(self.decimals as i32) * price);
+        self
+    }
+}
+
+/// A balance that could not be read
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PortfolioError {
+    /// Account whose balance was read
+    pub account: String,
+    /// Chain it was read on
+    pub chain: Chain,
+    /// Symbol of the balance
+    pub symbol: String,
+    /// Why reading failed
+    pub error: String,
+}
+
+/// Balances of a set of accounts across chains
+///
+/// Balances that could not be read are listed in `errors` rather than
+/// failing the whole summary.
+#[derive(Debug, Clone, Default)]
+pub struct Portfolio {
+    /// Every non-zero balance read
+    pub holdings: Vec<Holding>,
+    /// Balances that could not be read
+    pub errors: Vec<PortfolioError>,
+}
+
+impl Portfolio {
+    /// Total value of all priced holdings
+    pub fn total_value(&self) -> f64 {
+        self.holdings
+            .iter()
+            .filter_map(|holding| holding.value)
+            .sum()
+    }
+
+    /// Total value held by each account
+    pub fn value_by_account(&self) -> BTreeMap<String, f64> {
+        self.sum_by(|holding| holding.account.clone())
+    }
+
+    /// Total value held on each chain
+    pub fn value_by_chain(&self) -> BTreeMap<String, f64> {
+        self.sum_by(|holding| holding.chain.to_string())
+    }
+
+    /// Total value of each token symbol, across chains and accounts
+    pub fn value_by_symbol(&self) -> BTreeMap<String, f64> {
+        self.sum_by(|holding| holding.symbol.clone())
+    }
+
+    /// Holdings without a price, excluded from the totals
+    pub fn unpriced(&self) -> impl Iterator<Item = &Holding> {
+        self.holdings
+            .iter()
+            .filter(|holding| holding.value.is_none())
+    }
+
+    fn sum_by(&self, key: impl Fn(&Holding) -> String) -> BTreeMap<String, f64> {
+        let mut totals = BTreeMap::new();
+        for holding in &self.holdings {
+            if let Some(value) = holding.value {
+                *totals.entry(key(holding)).or_insert(0.0) += value;
+            }
+        }
+        totals
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn holding(account: &str, chain: Chain, symbol: &str, amount: u128) -> Holding {
+        Holding {
+            account: account.to_string(),
+            decimals: chain.native_decimals(),
+            chain,
+            kind: HoldingKind::Native,
+            symbol: symbol.to_string(),
+            amount,
+            value: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_static_prices() {
+        let oracle = StaticPrices::new().with_price("DOT", 6.5);
+        assert_eq!(
+            oracle.price(&Chain::Polkadot, "DOT").await.unwrap(),
+            Some(6.5)
+        );
+        assert_eq!(oracle.price(&Chain::Polkadot, "KSM").await.unwrap(), None);
+    }
+
+    #[test]
+    fn test_holding_value() {
+        let dot = holding("alice", Chain::Polkadot, "DOT", 25_000_000_000).priced(Some(6.0));
+        assert_eq!(dot.formatted_amount(), "2.5");
+        assert_eq!(dot.value, Some(15.0));
+        assert_eq!(
+            holding("alice", Chain::Polkadot, "DOT", 1)
+                .priced(None)
+                .value,
+            None
+        );
+    }
+
+    #[test]
+    fn test_portfolio_totals() {
+        let portfolio = Portfolio {
+            holdings: vec![
+                holding("alice", Chain::Polkadot, "DOT", 20_000_000_000).priced(Some(5.0)),
+                holding("bob", Chain::Polkadot, "DOT", 10_000_000_000).priced(Some(5.0)),
+                holding("bob", Chain::Ethereum, "ETH", 500_000_000_000_000_000)
+                    .priced(Some(3000.0)),
+                holding("bob", Chain::Ethereum, "PEPE", 1),
+            ],
+            errors: Vec::new(),
+        };
+
+        assert_eq!(portfolio.total_value(), 1515.0);
+        assert_eq!(portfolio.value_by_account()["bob"], 1505.0);
+        assert_eq!(portfolio.value_by_chain()["Polkadot"], 15.0);
+        assert_eq!(portfolio.value_by_symbol()["ETH"], 1500.0);
+        assert_eq!(portfolio.unpriced().count(), 1);
+    }
+}
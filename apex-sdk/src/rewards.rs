@@ -0,0 +1,539 @@
+//! Staking rewards across chains
+//!
+//! A [`RewardTracker`] collects the staking rewards of a set of accounts
+//! from [`RewardSource`]s and groups them into period reports. Two sources
+//! are provided:
+//!
+//! - [`SubstrateRewards`] computes each era's reward from `pallet-staking`
+//!   history, for validators and nominators, and whether it was claimed
+//! - [`LiquidStakingRewards`] measures the appreciation of a liquid-staking
+//!   token such as wstETH or rETH against its underlying asset
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use apex_sdk::evm::EvmAdapter;
+//! use apex_sdk::rewards::{LiquidStakingRewards, RewardTracker, SubstrateRewards, WSTETH_RATE};
+//! use apex_sdk::substrate::SubstrateAdapter;
+//! use apex_sdk::types::Chain;
+//! use std::time::{Duration, SystemTime, UNIX_EPOCH};
+//!
+//! # #[tokio::main]
+//! # async fn main() -> apex_sdk::Result<()> {
+//! let polkadot = SubstrateAdapter::connect("wss://polkadot.api.onfinality.io/public-ws").await?;
+//! let ethereum = EvmAdapter::connect("https://eth.llamarpc.com").await?;
+//!
+//! let tracker = RewardTracker::new()
+//!     .with_source(SubstrateRewards::new(Chain::Polkadot, polkadot))
+//!     .with_source(LiquidStakingRewards::new(
+//!         Chain::Ethereum,
+//!         ethereum,
+//!         "0x7f39C581F595B53c5cb19bD0b3f8dA6c935E2Ca0",
+//!         WSTETH_RATE,
+//!     ));
+//!
+//! let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+//! let week = Duration::from_secs(7 * 24 * 3600);
+//! let report = tracker
+//!     .report(
+//!         &[
+//!             "15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5",
+//!             "0x742D35CC6634c0532925A3b844BC9E7595F0BEb0",
+//!         ],
+//!         now - 4 * week.as_secs(),
+//!         now,
+//!         week,
+//!     )
+//!     .await?;
+//! for period in &report.periods {
+//!     println!(
+//!         "{} {} from {}: {} accrued, {} unclaimed",
+//!         period.chain,
+//!         period.account,
+//!         period.start,
+//!         period.accrued,
+//!         period.unclaimed()
+//!     );
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::{Error, Result};
+use apex_sdk_evm::EvmAdapter;
+use apex_sdk_substrate::{ActiveEra, SubstrateAdapter};
+use apex_sdk_types::{Chain, ChainType};
+use async_trait::async_trait;
+use ethers::abi::{self, Token};
+use ethers::types::{Address as EthAddress, U256};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Exchange-rate getter of Lido's wstETH, in stETH per wstETH
+pub const WSTETH_RATE: &str = "stEthPerToken()";
+
+/// Exchange-rate getter of Rocket Pool's rETH, in ETH per rETH
+pub const RETH_RATE: &str = "getExchangeRate()";
+
+/// Whether a reward reached the account
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RewardStatus {
+    /// Earned but not paid out yet
+    Unclaimed,
+    /// Paid out
+    Claimed,
+    /// Reflected in the value of a held token, nothing to claim
+    Compounded,
+}
+
+/// A reward earned over one interval
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RewardRecord {
+    /// Chain the reward was earned on
+    pub chain: Chain,
+    /// Account that earned it
+    pub account: String,
+    /// What earned it, e.g. `era 1520` or the liquid-staking token address
+    pub source: String,
+    /// Unix timestamp (seconds) the interval started
+    pub timestamp: u64,
+    /// Reward in the smallest unit of the staked asset
+    pub amount: u128,
+    /// Whether the reward reached the account
+    pub status: RewardStatus,
+}
+
+/// Rewards of accounts on one chain
+#[async_trait]
+pub trait RewardSource: Send + Sync {
+    /// Chain the rewards are earned on
+    fn chain(&self) -> &Chain;
+
+    /// Rewards of `account` whose interval starts between `from` and `to`
+    /// (Unix seconds)
+    async fn rewards(&self, account: &str, from: u64, to: u64) -> Result<Vec<RewardRecord>>;
+}
+
+/// Era rewards of validators and nominators on a Substrate chain
+///
+/// Only eras still within the chain's staking history are reported.
+/// Nominator rewards follow the validators the account nominates today.
+pub struct SubstrateRewards {
+    chain: Chain,
+    adapter: SubstrateAdapter,
+    era_duration: Duration,
+}
+
+impl SubstrateRewards {
+    /// Track rewards on `chain`
+    pub fn new(chain: Chain, adapter: SubstrateAdapter) -> Self {
+        let era_duration = match chain {
+            Chain::Kusama => Duration::from_secs(6 * 3600),
+            _ => Duration::from_secs(24 * 3600),
+        };
+        Self {
+            chain,
+            adapter,
+            era_duration,
+        }
+    }
+
+    /// Set the era length used to date past eras, 24 hours by default (six
+    /// on Kusama)
+    pub fn with_era_duration(mut self, duration: Duration) -> Self {
+        self.era_duration = duration;
+        self
+    }
+}
+
+/// Estimated Unix timestamp (seconds) at which `era` started
+fn era_start(active: &ActiveEra, era: u32, era_duration: Duration, now: u64) -> u64 {
+    let active_start = active.start.map_or(now, |ms| ms / 1000);
+    let eras_ago = active.index.saturating_sub(era) as u64;
+    active_start.saturating_sub(eras_ago * era_duration.as_secs())
+}
+
+#[async_trait]
+impl RewardSource for SubstrateRewards {
+    fn chain(&self) -> &Chain {
+        &self.chain
+    }
+
+    async fn rewards(&self, account: &str, from: u64, to: u64) -> Result<Vec<RewardRecord>> {
+        let staking = self.adapter.staking();
+        let Some(active) = staking.active_era().await? else {
+            return Ok(Vec::new());
+        };
+        let oldest = active.index.saturating_sub(staking.history_depth()?);
+
+        let now = unix_now();
+        let mut records = Vec::new();
+        for era in oldest..active.index {
+            let timestamp = era_start(&active, era, self.era_duration, now);
+            if timestamp < from || timestamp >= to {
+                continue;
+            }
+            let Some(reward) = staking.era_reward(era, account).await? else {
+                continue;
+            };
+            if reward.amount == 0 {
+                continue;
+            }
+            records.push(RewardRecord {
+                chain: self.chain.clone(),
+                account: account.to_string(),
+                source: format!("era {}", era),
+                timestamp,
+                amount: reward.amount,
+                status: if reward.claimed {
+                    RewardStatus::Claimed
+                } else {
+                    RewardStatus::Unclaimed
+                },
+            });
+        }
+        Ok(records)
+    }
+}
+
+/// Appreciation of a liquid-staking token on an EVM chain
+///
+/// The token's exchange rate to its underlying asset (scaled by 1e18) is
+/// read at the start and end of each sample interval; the account's balance
+/// at the end, times the rate increase, is the reward in the underlying
+/// asset. Reading past state needs an archive node; blocks are located from
+/// the average block time.
+pub struct LiquidStakingRewards {
+    chain: Chain,
+    adapter: EvmAdapter,
+    token: String,
+    rate_call: String,
+    block_time: Duration,
+    sample_interval: Duration,
+}
+
+impl LiquidStakingRewards {
+    /// Track `token` on `chain`, whose exchange rate is returned by the
+    /// no-argument getter `rate_call`, e.g. [`WSTETH_RATE`]
+    pub fn new(
+        chain: Chain,
+        adapter: EvmAdapter,
+        token: impl Into<String>,
+        rate_call: impl Into<String>,
+    ) -> Self {
+        Self {
+            chain,
+            adapter,
+            token: token.into(),
+            rate_call: rate_call.into(),
+            block_time: Duration::from_secs(12),
+            sample_interval: Duration::from_secs(24 * 3600),
+        }
+    }
+
+    /// Set the average block time, 12 seconds by default
+    pub fn with_block_time(mut self, block_time: Duration) -> Self {
+        self.block_time = block_time;
+        self
+    }
+
+    /// Set the length of the intervals rewards are measured over, a day by
+    /// default
+    pub fn with_sample_interval(mut self, interval: Duration) -> Self {
+        self.sample_interval = interval;
+        self
+    }
+
+    async fn read_u256(&self, data: Vec<u8>, block: u64) -> Result<U256> {
+        let output = self
+            .adapter
+            .call_contract_at(&self.token, data, block)
+            .await?;
+        if output.len() < 32 {
+            return Err(Error::Transaction(format!(
+                "Unexpected response from {}",
+                self.token
+            )));
+        }
+        Ok(U256::from_big_endian(&output[..32]))
+    }
+}
+
+/// Reward of holding `balance` tokens while the rate went from `rate_from`
+/// to `rate_to` (both scaled by 1e18)
+fn appreciation(balance: U256, rate_from: U256, rate_to: U256) -> u128 {
+    let gain = rate_to.saturating_sub(rate_from);
+    let reward = balance.full_mul(gain) / U256::exp10(18);
+    u128::try_from(reward).unwrap_or(u128::MAX)
+}
+
+#[async_trait]
+impl RewardSource for LiquidStakingRewards {
+    fn chain(&self) -> &Chain {
+        &self.chain
+    }
+
+    async fn rewards(&self, account: &str, from: u64, to: u64) -> Result<Vec<RewardRecord>> {
+        let holder: EthAddress = account
+            .parse()
+            .map_err(|_| Error::InvalidAddress(account.to_string()))?;
+        let head = self.adapter.block_number().await?;
+        let now = unix_now();
+        let block_time = self.block_time.as_secs().max(1);
+        let block_at =
+            |timestamp: u64| head.saturating_sub(now.saturating_sub(timestamp) / block_time);
+
+        let rate_data = ethers::utils::id(&self.rate_call).to_vec();
+        let mut balance_data = ethers::utils::id("balanceOf(address)").to_vec();
+        balance_data.extend(abi::encode(&[Token::Address(holder)]));
+
+        let step = self.sample_interval.as_secs().max(1);
+        let mut records = Vec::new();
+        let mut start = from;
+        while start < to.min(now) {
+            let end = (start + step).min(now);
+            let (from_block, to_block) = (block_at(start), block_at(end));
+            if to_block > from_block {
+                let rate_from = self.read_u256(rate_data.clone(), from_block).await?;
+                let rate_to = self.read_u256(rate_data.clone(), to_block).await?;
+                let balance = self.read_u256(balance_data.clone(), to_block).await?;
+                let amount = appreciation(balance, rate_from, rate_to);
+                if amount > 0 {
+                    records.push(RewardRecord {
+                        chain: self.chain.clone(),
+                        account: account.to_string(),
+                        source: self.token.clone(),
+                        timestamp: start,
+                        amount,
+                        status: RewardStatus::Compounded,
+                    });
+                }
+            }
+            start = end;
+        }
+        Ok(records)
+    }
+}
+
+/// Rewards of one account on one chain over one period
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RewardPeriod {
+    /// Unix timestamp (seconds) the period starts at
+    pub start: u64,
+    /// Unix timestamp (seconds) the period ends at, exclusive
+    pub end: u64,
+    /// Chain the rewards were earned on
+    pub chain: Chain,
+    /// Account that earned them
+    pub account: String,
+    /// Everything earned in the period
+    pub accrued: u128,
+    /// Part of `accrued` that reached the account, claimed or compounded
+    pub claimed: u128,
+}
+
+impl RewardPeriod {
+    /// Part of `accrued` still to be claimed
+    pub fn unclaimed(&self) -> u128 {
+        self.accrued - self.claimed
+    }
+}
+
+/// Rewards grouped into periods
+#[derive(Debug, Clone, Default)]
+pub struct RewardReport {
+    /// Periods with rewards, by start time, then chain and account
+    pub periods: Vec<RewardPeriod>,
+    /// Every reward the periods were built from
+    pub records: Vec<RewardRecord>,
+}
+
+impl RewardReport {
+    /// Group `records` into periods of `period` starting at `from`
+    pub fn from_records(records: Vec<RewardRecord>, from: u64, period: Duration) -> Self {
+        let length = period.as_secs().max(1);
+        let mut periods: Vec<RewardPeriod> = Vec::new();
+
+        for record in &records {
+            let start = from + record.timestamp.saturating_sub(from) / length * length;
+            let index = match periods.iter().position(|p| {
+                p.start == start && p.chain == record.chain && p.account == record.account
+            }) {
+                Some(index) => index,
+                None => {
+                    periods.push(RewardPeriod {
+                        start,
+                        end: start + length,
+                        chain: record.chain.clone(),
+                        account: record.account.clone(),
+                        accrued: 0,
+                        claimed: 0,
+                    });
+                    periods.len() - 1
+                }
+            };
+            let period = &mut periods[index];
+            period.accrued += record.amount;
+            if record.status != RewardStatus::Unclaimed {
+                period.claimed += record.amount;
+            }
+        }
+
+        periods.sort_by(|a, b| {
+            (a.start, a.chain.name(), &a.account).cmp(&(b.start, b.chain.name(), &b.account))
+        });
+        Self { periods, records }
+    }
+
+    /// Everything earned on `chain`
+    pub fn total_accrued(&self, chain: &Chain) -> u128 {
+        self.on(chain).map(|period| period.accrued).sum()
+    }
+
+    /// Everything earned on `chain` and not claimed yet
+    pub fn total_unclaimed(&self, chain: &Chain) -> u128 {
+        self.on(chain).map(RewardPeriod::unclaimed).sum()
+    }
+
+    fn on<'a>(&'a self, chain: &'a Chain) -> impl Iterator<Item = &'a RewardPeriod> {
+        self.periods
+            .iter()
+            .filter(move |period| &period.chain == chain)
+    }
+}
+
+/// Collects staking rewards from several chains
+#[derive(Default)]
+pub struct RewardTracker {
+    sources: Vec<Arc<dyn RewardSource>>,
+}
+
+impl RewardTracker {
+    /// Create a tracker with no sources
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a chain to collect rewards from
+    pub fn with_source(mut self, source: impl RewardSource + 'static) -> Self {
+        self.sources.push(Arc::new(source));
+        self
+    }
+
+    /// Rewards of `accounts` earned between `from` and `to` (Unix seconds)
+    ///
+    /// `0x` accounts are looked up on EVM and hybrid chains, others on
+    /// Substrate chains.
+    pub async fn records(
+        &self,
+        accounts: &[&str],
+        from: u64,
+        to: u64,
+    ) -> Result<Vec<RewardRecord>> {
+        let mut records = Vec::new();
+        for &account in accounts {
+            let is_evm = account.starts_with("0x");
+            for source in &self.sources {
+                let evm_source = source.chain().chain_type() != ChainType::Substrate;
+                if evm_source == is_evm {
+                    records.extend(source.rewards(account, from, to).await?);
+                }
+            }
+        }
+        Ok(records)
+    }
+
+    /// Rewards of `accounts` between `from` and `to`, grouped into periods
+    /// of `period`
+    pub async fn report(
+        &self,
+        accounts: &[&str],
+        from: u64,
+        to: u64,
+        period: Duration,
+    ) -> Result<RewardReport> {
+        let records = self.records(accounts, from, to).await?;
+        Ok(RewardReport::from_records(records, from, period))
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY: u64 = 24 * 3600;
+
+    fn record(chain: Chain, timestamp: u64, amount: u128, status: RewardStatus) -> RewardRecord {
+        RewardRecord {
+            chain,
+            account: "alice".to_string(),
+            source: "test".to_string(),
+            timestamp,
+            amount,
+            status,
+        }
+    }
+
+    #[test]
+    fn test_era_start() {
+        let active = ActiveEra {
+            index: 100,
+            start: Some(10 * DAY * 1000),
+        };
+        let day = Duration::from_secs(DAY);
+        assert_eq!(era_start(&active, 100, day, 0), 10 * DAY);
+        assert_eq!(era_start(&active, 97, day, 0), 7 * DAY);
+        assert_eq!(era_start(&active, 0, day, 0), 0);
+
+        let pending = ActiveEra {
+            index: 5,
+            start: None,
+        };
+        assert_eq!(era_start(&pending, 4, day, 3 * DAY), 2 * DAY);
+    }
+
+    #[test]
+    fn test_appreciation() {
+        let eth = U256::exp10(18);
+        // 10 tokens while the rate rose from 1.10 to 1.11
+        let from = eth * 110 / 100;
+        let to = eth * 111 / 100;
+        assert_eq!(appreciation(eth * 10, from, to), 10u128.pow(17));
+        assert_eq!(appreciation(eth * 10, to, from), 0);
+    }
+
+    #[test]
+    fn test_report_groups_periods() {
+        let records = vec![
+            record(Chain::Polkadot, DAY, 100, RewardStatus::Claimed),
+            record(Chain::Polkadot, 2 * DAY, 50, RewardStatus::Unclaimed),
+            record(Chain::Polkadot, 8 * DAY, 70, RewardStatus::Unclaimed),
+            record(Chain::Ethereum, 3 * DAY, 5, RewardStatus::Compounded),
+        ];
+        let report = RewardReport::from_records(records, 0, Duration::from_secs(7 * DAY));
+
+        let periods: Vec<_> = report
+            .periods
+            .iter()
+            .map(|p| (p.start, p.chain.clone(), p.accrued, p.unclaimed()))
+            .collect();
+        assert_eq!(
+            periods,
+            vec![
+                (0, Chain::Ethereum, 5, 0),
+                (0, Chain::Polkadot, 150, 50),
+                (7 * DAY, Chain::Polkadot, 70, 70),
+            ]
+        );
+        assert_eq!(report.total_accrued(&Chain::Polkadot), 220);
+        assert_eq!(report.total_unclaimed(&Chain::Polkadot), 120);
+        assert_eq!(report.records.len(), 4);
+    }
+}
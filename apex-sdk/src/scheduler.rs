@@ -0,0 +1,554 @@
+//! Scheduled and recurring on-chain tasks
+//!
+//! A [`TaskManager`] runs [`ScheduledTask`]s against an SDK instance: jobs
+//! such as claiming staking rewards every era, rebalancing weekly or sweeping
+//! dust daily. Each task has a [`Trigger`] (a fixed interval or a cron
+//! expression), optional jitter to spread load, a [`RetryPolicy`] for failed
+//! runs and a [`FailurePolicy`] deciding what happens once retries are
+//! exhausted.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use apex_sdk::prelude::*;
+//! use apex_sdk::scheduler::{ScheduledTask, TaskManager, Trigger};
+//! use apex_sdk::types::Chain;
+//! use std::sync::Arc;
+//! use std::time::Duration;
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<()> {
+//! let sdk = Arc::new(
+//!     ApexSDK::builder()
+//!         .with_evm_endpoint("https://eth.llamarpc.com")
+//!         .build()
+//!         .await?,
+//! );
+//!
+//! let manager = TaskManager::new(sdk);
+//! // every day at 03:00 UTC (sec min hour day-of-month month day-of-week)
+//! let daily = Trigger::cron("0 0 3 * * *")?;
+//! let report = |sdk: Arc<ApexSDK>| async move {
+//!     let treasury = Address::evm("0x742D35Cc6634C0532925A3B844bC9e7595f0bEB7");
+//!     let balance = sdk.get_balance(&Chain::Ethereum, &treasury).await?;
+//!     println!("Treasury balance: {}", balance);
+//!     Ok(())
+//! };
+//! manager.schedule(
+//!     ScheduledTask::new("balance-report", daily, report).with_jitter(Duration::from_secs(60)),
+//! )?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::{Error, Result};
+use crate::sdk::ApexSDK;
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::future::Future;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, SystemTime};
+use tokio::task::JoinHandle;
+
+/// When a task runs
+#[derive(Debug, Clone)]
+pub enum Trigger {
+    /// Run repeatedly, waiting this long before each run
+    Interval(Duration),
+    /// Run at the times matched by a cron schedule, in UTC
+    Cron(Box<cron::Schedule>),
+}
+
+impl Trigger {
+    /// Run every `interval`
+    pub fn every(interval: Duration) -> Self {
+        Trigger::Interval(interval)
+    }
+
+    /// Run on a cron schedule
+    ///
+    /// The expression has six or seven fields: seconds, minutes, hours, day
+    /// of month, month, day of week and an optional year.
+    ///
+    /// # Errors
+    ///
+    /// Returns a configuration error if the expression is invalid.
+    #[allow(clippy::result_large_err)]
+    pub fn cron(expression: &str) -> Result<Self> {
+        cron::Schedule::from_str(expression)
+            .map(|schedule| Trigger::Cron(Box::new(schedule)))
+            .map_err(|e| Error::Config(format!("Invalid cron expression {:?}: {}", expression, e)))
+    }
+
+    /// Time until the next run, or `None` if the schedule has ended
+    pub fn next_delay(&self) -> Option<Duration> {
+        match self {
+            Trigger::Interval(interval) => Some(*interval),
+            Trigger::Cron(schedule) => {
+                let next = schedule.upcoming(chrono::Utc).next()?;
+                Some(
+                    (next - chrono::Utc::now())
+                        .to_std()
+                        .unwrap_or(Duration::ZERO),
+                )
+            }
+        }
+    }
+}
+
+/// Retry behaviour for a failed run
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Attempts per run, including the first (minimum 1)
+    pub max_attempts: u32,
+    /// Delay before the first retry
+    pub initial_delay: Duration,
+    /// Upper bound for the delay between retries
+    pub max_delay: Duration,
+    /// Factor applied to the delay after each failed attempt
+    pub multiplier: f64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(60),
+            multiplier: 2.0,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Never retry a failed run
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Default::default()
+        }
+    }
+
+    /// Delay before retry number `retry` (starting at 1)
+    fn delay(&self, retry: u32) -> Duration {
+        let factor = self
+            .multiplier
+            .max(1.0)
+            .powi(retry.saturating_sub(1) as i32);
+        self.initial_delay.mul_f64(factor).min(self.max_delay)
+    }
+}
+
+/// What to do when a run still fails after all retries
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FailurePolicy {
+    /// Record the failure and wait for the next trigger
+    #[default]
+    Continue,
+    /// Stop scheduling the task until [`TaskManager::resume`] is called
+    Pause,
+}
+
+/// Work performed by a scheduled task
+///
+/// Implemented for async closures taking the SDK.
+#[async_trait]
+pub trait Job: Send + Sync {
+    /// Perform one run of the job
+    async fn run(&self, sdk: Arc<ApexSDK>) -> Result<()>;
+}
+
+#[async_trait]
+impl<F, Fut> Job for F
+where
+    F: Fn(Arc<ApexSDK>) -> Fut + Send + Sync,
+    Fut: Future<Output = Result<()>> + Send,
+{
+    async fn run(&self, sdk: Arc<ApexSDK>) -> Result<()> {
+        self(sdk).await
+    }
+}
+
+/// A named job with its trigger and policies
+#[derive(Clone)]
+pub struct ScheduledTask {
+    name: String,
+    trigger: Trigger,
+    job: Arc<dyn Job>,
+    jitter: Duration,
+    retry: RetryPolicy,
+    on_failure: FailurePolicy,
+}
+
+impl ScheduledTask {
+    /// Create a task running `job` whenever `trigger` fires
+    pub fn new(name: impl Into<String>, trigger: Trigger, job: impl Job + 'static) -> Self {
+        Self {
+            name: name.into(),
+            trigger,
+            job: Arc::new(job),
+            jitter: Duration::ZERO,
+            retry: RetryPolicy::default(),
+            on_failure: FailurePolicy::default(),
+        }
+    }
+
+    /// Delay each run by a random amount up to `jitter`
+    pub fn with_jitter(mut self, jitter: Duration) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Set the retry policy
+    pub fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Set what happens when retries are exhausted
+    pub fn with_failure_policy(mut self, on_failure: FailurePolicy) -> Self {
+        self.on_failure = on_failure;
+        self
+    }
+
+    /// The task's name
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The task's trigger
+    pub fn trigger(&self) -> &Trigger {
+        &self.trigger
+    }
+
+    fn next_delay(&self) -> Option<Duration> {
+        let delay = self.trigger.next_delay()?;
+        let jitter = self.jitter.as_millis() as u64;
+        if jitter == 0 {
+            return Some(delay);
+        }
+        Some(delay + Duration::from_millis(rand::random_range(0..=jitter)))
+    }
+
+    /// Run the job, retrying according to the retry policy
+    async fn run(&self, sdk: &Arc<ApexSDK>) -> Result<()> {
+        let attempts = self.retry.max_attempts.max(1);
+        let mut attempt = 1;
+        loop {
+            match self.job.run(sdk.clone()).await {
+                Ok(()) => return Ok(()),
+                Err(e) if attempt >= attempts => return Err(e),
+                Err(e) => {
+                    tracing::warn!(
+                        "Task {} failed (attempt {}/{}): {}",
+                        self.name,
+                        attempt,
+                        attempts,
+                        e
+                    );
+                    tokio::time::sleep(self.retry.delay(attempt)).await;
+                    attempt += 1;
+                }
+            }
+        }
+    }
+}
+
+/// Progress of a scheduled task
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TaskStatus {
+    /// Completed runs, successful or not
+    pub runs: u64,
+    /// Runs that failed after all retries
+    pub failures: u64,
+    /// When the last run finished
+    pub last_run: Option<SystemTime>,
+    /// Error of the last run, if it failed
+    pub last_error: Option<String>,
+    /// When the next run is due, if one is scheduled
+    pub next_run: Option<SystemTime>,
+    /// Whether the task was paused by its failure policy
+    pub paused: bool,
+}
+
+struct ManagedTask {
+    task: ScheduledTask,
+    status: Arc<RwLock<TaskStatus>>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl Drop for ManagedTask {
+    fn drop(&mut self) {
+        if let Some(handle) = self.handle.take() {
+            handle.abort();
+        }
+    }
+}
+
+/// Runs scheduled tasks in the background
+///
+/// Tasks run until cancelled or until the manager is shut down or dropped.
+pub struct TaskManager {
+    sdk: Arc<ApexSDK>,
+    tasks: Mutex<HashMap<String, ManagedTask>>,
+}
+
+impl TaskManager {
+    /// Create a manager running tasks against `sdk`
+    pub fn new(sdk: Arc<ApexSDK>) -> Self {
+        Self {
+            sdk,
+            tasks: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Start running `task`
+    ///
+    /// # Errors
+    ///
+    /// Returns a configuration error if a task with the same name exists.
+    #[allow(clippy::result_large_err)]
+    pub fn schedule(&self, task: ScheduledTask) -> Result<()> {
+        let mut tasks = self.tasks.lock().unwrap_or_else(|e| e.into_inner());
+        if tasks.contains_key(task.name()) {
+            return Err(Error::Config(format!(
+                "Task {} is already scheduled",
+                task.name()
+            )));
+        }
+        let status = Arc::new(RwLock::new(TaskStatus::default()));
+        let handle = self.spawn(task.clone(), status.clone());
+        tasks.insert(
+            task.name().to_string(),
+            ManagedTask {
+                task,
+                status,
+                handle: Some(handle),
+            },
+        );
+        Ok(())
+    }
+
+    /// Stop and remove the task `name`
+    ///
+    /// Returns whether the task existed. A run in progress is aborted.
+    pub fn cancel(&self, name: &str) -> bool {
+        self.tasks
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(name)
+            .is_some()
+    }
+
+    /// Restart the task `name` after its failure policy paused it
+    ///
+    /// Returns whether a paused task was resumed.
+    pub fn resume(&self, name: &str) -> bool {
+        let mut tasks = self.tasks.lock().unwrap_or_else(|e| e.into_inner());
+        let Some(managed) = tasks.get_mut(name) else {
+            return false;
+        };
+        {
+            let mut status = managed.status.write().unwrap_or_else(|e| e.into_inner());
+            if !status.paused {
+                return false;
+            }
+            status.paused = false;
+        }
+        managed.handle = Some(self.spawn(managed.task.clone(), managed.status.clone()));
+        true
+    }
+
+    /// Status of the task `name`
+    pub fn status(&self, name: &str) -> Option<TaskStatus> {
+        self.tasks
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(name)
+            .map(|managed| {
+                managed
+                    .status
+                    .read()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .clone()
+            })
+    }
+
+    /// Names of all scheduled tasks, sorted
+    pub fn tasks(&self) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .tasks
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .keys()
+            .cloned()
+            .collect();
+        names.sort();
+        names
+    }
+
+    /// Stop and remove all tasks
+    pub fn shutdown(&self) {
+        self.tasks.lock().unwrap_or_else(|e| e.into_inner()).clear();
+    }
+
+    fn spawn(&self, task: ScheduledTask, status: Arc<RwLock<TaskStatus>>) -> JoinHandle<()> {
+        let sdk = self.sdk.clone();
+        tokio::spawn(async move {
+            while let Some(delay) = task.next_delay() {
+                status.write().unwrap_or_else(|e| e.into_inner()).next_run =
+                    Some(SystemTime::now() + delay);
+                tokio::time::sleep(delay).await;
+
+                let result = task.run(&sdk).await;
+                let mut status = status.write().unwrap_or_else(|e| e.into_inner());
+                status.runs += 1;
+                status.last_run = Some(SystemTime::now());
+                status.next_run = None;
+                match result {
+                    Ok(()) => status.last_error = None,
+                    Err(e) => {
+                        tracing::error!("Task {} failed: {}", task.name, e);
+                        status.failures += 1;
+                        status.last_error = Some(e.to_string());
+                        if task.on_failure == FailurePolicy::Pause {
+                            status.paused = true;
+                            return;
+                        }
+                    }
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sdk::DEFAULT_RECEIPT_TIMEOUT;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::OnceLock;
+
+    fn sdk() -> Arc<ApexSDK> {
+        Arc::new(ApexSDK {
+            substrate_adapter: None,
+            evm_adapter: None,
+            policy: None,
+            attestor: None,
+            event_poll_interval: Duration::from_secs(1),
+            events: OnceLock::new(),
+            block_finality: Default::default(),
+            blocks: OnceLock::new(),
+            portfolio: Default::default(),
+            confirmation_policies: Default::default(),
+            evm_wallet: None,
+            substrate_wallet: None,
+            receipt_timeout: DEFAULT_RECEIPT_TIMEOUT,
+            balance_cache_ttl: Duration::ZERO,
+            gas_limit_multiplier: apex_sdk_evm::transaction::DEFAULT_GAS_LIMIT_MULTIPLIER,
+            balances: Default::default(),
+        })
+    }
+
+    /// Job failing its first `failures` runs
+    fn flaky(failures: u32, calls: Arc<AtomicU32>) -> impl Job {
+        move |_sdk: Arc<ApexSDK>| {
+            let call = calls.fetch_add(1, Ordering::SeqCst);
+            async move {
+                if call < failures {
+                    Err(Error::Other(format!("failure {}", call)))
+                } else {
+                    Ok(())
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_cron_trigger() {
+        assert!(Trigger::cron("not a schedule").is_err());
+        let daily = Trigger::cron("0 0 3 * * *").unwrap();
+        let delay = daily.next_delay().unwrap();
+        assert!(delay <= Duration::from_secs(24 * 60 * 60));
+    }
+
+    #[test]
+    fn test_retry_backoff() {
+        let retry = RetryPolicy {
+            max_attempts: 5,
+            initial_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(3),
+            multiplier: 2.0,
+        };
+        assert_eq!(retry.delay(1), Duration::from_secs(1));
+        assert_eq!(retry.delay(2), Duration::from_secs(2));
+        assert_eq!(retry.delay(3), Duration::from_secs(3));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_interval_task_retries() {
+        let calls = Arc::new(AtomicU32::new(0));
+        let manager = TaskManager::new(sdk());
+        manager
+            .schedule(ScheduledTask::new(
+                "sweep",
+                Trigger::every(Duration::from_secs(60)),
+                flaky(2, calls.clone()),
+            ))
+            .unwrap();
+        assert!(manager
+            .schedule(ScheduledTask::new(
+                "sweep",
+                Trigger::every(Duration::from_secs(60)),
+                flaky(0, calls.clone()),
+            ))
+            .is_err());
+
+        // first run: two failures, then success after 1s + 2s of backoff
+        tokio::time::sleep(Duration::from_secs(64)).await;
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+        let status = manager.status("sweep").unwrap();
+        assert_eq!(status.runs, 1);
+        assert_eq!(status.failures, 0);
+        assert!(status.next_run.is_some());
+
+        tokio::time::sleep(Duration::from_secs(60)).await;
+        assert_eq!(manager.status("sweep").unwrap().runs, 2);
+
+        assert!(manager.cancel("sweep"));
+        assert!(manager.tasks().is_empty());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_failure_policy_pause() {
+        let calls = Arc::new(AtomicU32::new(0));
+        let manager = TaskManager::new(sdk());
+        manager
+            .schedule(
+                ScheduledTask::new(
+                    "rebalance",
+                    Trigger::every(Duration::from_secs(10)),
+                    flaky(1, calls.clone()),
+                )
+                .with_retry_policy(RetryPolicy::none())
+                .with_failure_policy(FailurePolicy::Pause),
+            )
+            .unwrap();
+
+        tokio::time::sleep(Duration::from_secs(35)).await;
+        let status = manager.status("rebalance").unwrap();
+        assert!(status.paused);
+        assert_eq!(status.failures, 1);
+        assert_eq!(status.last_error.as_deref(), Some("failure 0"));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        assert!(manager.resume("rebalance"));
+        assert!(!manager.resume("rebalance"));
+        tokio::time::sleep(Duration::from_secs(11)).await;
+        let status = manager.status("rebalance").unwrap();
+        assert!(!status.paused);
+        assert_eq!(status.runs, 2);
+        assert_eq!(status.last_error, None);
+    }
+}
@@ -6,6 +6,7 @@
 //! # Examples
 //!
 //! ```rust,no_run
+//! use apex_sdk::evm::wallet::Wallet;
 //! use apex_sdk::prelude::*;
 //!
 //! #[tokio::main]
@@ -14,6 +15,7 @@
 //!     let sdk = ApexSDK::builder()
 //!         .with_substrate_endpoint("wss://polkadot.api.onfinality.io/public-ws")
 //!         .with_evm_endpoint("https://mainnet.infura.io/v3/YOUR_KEY")
+//!         .with_evm_wallet(Wallet::from_private_key("0x...")?)
 //!         .build()
 //!         .await?;
 //!
@@ -22,9 +24,9 @@
 //!         println!("Ethereum is supported!");
 //!     }
 //!
-//!     // Create and execute a transaction
+//!     // Create and execute a transaction, signed by the configured wallet
 //!     let tx = sdk.transaction()
-//!         .from_evm_address("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb7")
+//!         .from_evm_address("0x742D35Cc6634C0532925A3B844bC9e7595f0bEB7")
 //!         .to_evm_address("0x1234567890123456789012345678901234567890")
 //!         .amount(1000)
 //!         .build()?;
@@ -36,12 +38,28 @@
 //! }
 //! ```
 
+use crate::advanced::{BlockMonitor, BlockSubscription, EventBridge, EventSubscription};
+use crate::attestation::{now_millis, Attestor};
 use crate::builder::ApexSDKBuilder;
 use crate::error::{Error, Result};
-use crate::transaction::{Transaction, TransactionBuilder, TransactionResult};
+use crate::policy::PolicyEngine;
+use crate::portfolio::{Holding, HoldingKind, Portfolio, PortfolioConfig, PortfolioError};
+use crate::transaction::{
+    TokenTransfer, Transaction, TransactionBuilder, TransactionResult, WaitConfig,
+};
+use apex_sdk_evm::transaction::GasConfig;
+use apex_sdk_evm::wallet::Wallet as EvmWallet;
 use apex_sdk_evm::EvmAdapter;
-use apex_sdk_substrate::SubstrateAdapter;
-use apex_sdk_types::{Chain, TransactionStatus};
+use apex_sdk_substrate::{FeeConfig, SubstrateAdapter, Wallet as SubstrateWallet};
+use apex_sdk_types::{
+    parse_units, Address, Amount, Chain, ChainType, ConfirmationPolicy, EventFilter, Finality,
+    Receipt, Token, TransactionStatus,
+};
+use ethers::types::transaction::eip2718::TypedTransaction;
+use ethers::types::U256;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
 
 /// Main Apex SDK struct providing unified interface to blockchain operations.
 ///
@@ -68,8 +86,30 @@ use apex_sdk_types::{Chain, TransactionStatus};
 pub struct ApexSDK {
     pub(crate) substrate_adapter: Option<SubstrateAdapter>,
     pub(crate) evm_adapter: Option<EvmAdapter>,
+    pub(crate) policy: Option<Arc<PolicyEngine>>,
+    pub(crate) attestor: Option<Arc<Attestor>>,
+    pub(crate) event_poll_interval: Duration,
+    pub(crate) events: OnceLock<Arc<EventBridge>>,
+    pub(crate) block_finality: Finality,
+    pub(crate) blocks: OnceLock<Arc<BlockMonitor>>,
+    pub(crate) portfolio: PortfolioConfig,
+    pub(crate) confirmation_policies: HashMap<Chain, ConfirmationPolicy>,
+    pub(crate) evm_wallet: Option<EvmWallet>,
+    pub(crate) substrate_wallet: Option<SubstrateWallet>,
+    pub(crate) receipt_timeout: Duration,
+    pub(crate) balance_cache_ttl: Duration,
+    pub(crate) gas_limit_multiplier: f64,
+    pub(crate) balances: Mutex<HashMap<(Chain, String), (Instant, u128)>>,
 }
 
+/// How long [`ApexSDK::execute`] waits for an EVM transaction to be mined by
+/// default
+pub const DEFAULT_RECEIPT_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// How long [`ApexSDK::get_balance`] results are cached by default, about
+/// one Polkadot block
+pub const DEFAULT_BALANCE_CACHE_TTL: Duration = Duration::from_secs(6);
+
 impl ApexSDK {
     /// Create a new builder for configuring the SDK.
     ///
@@ -206,50 +246,521 @@ impl ApexSDK {
     /// # }
     /// ```
     pub fn is_chain_supported(&self, chain: &Chain) -> bool {
-        match chain {
-            Chain::Polkadot | Chain::Kusama => self.substrate_adapter.is_some(),
-            Chain::Ethereum | Chain::Polygon | Chain::BinanceSmartChain | Chain::Avalanche => {
-                self.evm_adapter.is_some()
-            }
-            Chain::Moonbeam | Chain::Astar => {
-                self.substrate_adapter.is_some() && self.evm_adapter.is_some()
-            }
+        match chain.chain_type() {
+            ChainType::Substrate => self.substrate_adapter.is_some(),
+            ChainType::Evm => self.evm_adapter.is_some(),
+            ChainType::Hybrid => self.substrate_adapter.is_some() && self.evm_adapter.is_some(),
         }
     }
 
     /// Get the status of a transaction
+    ///
+    /// A transaction included in a block is reported as `Pending` until it
+    /// satisfies the chain's [`confirmation_policy`](Self::confirmation_policy).
     pub async fn get_transaction_status(
         &self,
         chain: &Chain,
         tx_hash: &str,
     ) -> Result<TransactionStatus> {
-        match chain {
-            Chain::Polkadot | Chain::Kusama => self
+        self.get_transaction_status_with(chain, tx_hash, &self.confirmation_policy(chain))
+            .await
+    }
+
+    /// Get the status of a transaction under `policy` instead of the chain's
+    /// configured one
+    pub async fn get_transaction_status_with(
+        &self,
+        chain: &Chain,
+        tx_hash: &str,
+        policy: &ConfirmationPolicy,
+    ) -> Result<TransactionStatus> {
+        match chain.chain_type() {
+            ChainType::Substrate => self
                 .substrate()?
-                .get_transaction_status(tx_hash)
+                .confirmation_status(tx_hash, policy)
                 .await
                 .map_err(Error::Substrate),
-            Chain::Ethereum | Chain::Polygon | Chain::BinanceSmartChain | Chain::Avalanche => self
+            ChainType::Evm => self
                 .evm()?
-                .get_transaction_status(tx_hash)
+                .confirmation_status(tx_hash, policy)
                 .await
                 .map_err(Error::Evm),
-            Chain::Moonbeam | Chain::Astar => {
+            ChainType::Hybrid => {
                 // Try EVM first for hybrid chains
                 self.evm()?
-                    .get_transaction_status(tx_hash)
+                    .confirmation_status(tx_hash, policy)
                     .await
                     .map_err(Error::Evm)
             }
         }
     }
 
+    /// Wait until a transaction has `config.confirmations` blocks on top of
+    /// its own and return its receipt
+    ///
+    /// EVM receipts are polled every `config.poll_interval`; hybrid chains
+    /// are waited on through the EVM adapter. Substrate extrinsics are only
+    /// found once finalized. Reverted or failed transactions are returned
+    /// with a `Failed` status, and their [`Receipt`] holds the revert or
+    /// dispatch error.
+    ///
+    /// # Errors
+    ///
+    /// [`Error::Transaction`] if the transaction is not confirmed within
+    /// `config.timeout`.
+    pub async fn wait_for_transaction(
+        &self,
+        chain: &Chain,
+        tx_hash: &str,
+        config: &WaitConfig,
+    ) -> Result<TransactionResult> {
+        match chain.chain_type() {
+            ChainType::Substrate => self.wait_for_extrinsic(tx_hash, config).await,
+            ChainType::Evm | ChainType::Hybrid => {
+                let adapter = self.evm()?;
+                let hash = tx_hash.parse().map_err(|_| {
+                    Error::Transaction(format!("Invalid transaction hash: {}", tx_hash))
+                })?;
+                let receipt = adapter
+                    .transaction_executor()
+                    .with_receipt_timeout(config.timeout)
+                    .with_receipt_poll_interval(config.poll_interval)
+                    .wait_for_receipt(hash, u64::from(config.confirmations) + 1)
+                    .await?;
+
+                let head = adapter.block_number().await?;
+                let mined = receipt.block_number.map(|n| n.as_u64()).unwrap_or(head);
+                let confirmations = u32::try_from(head.saturating_sub(mined)).unwrap_or(u32::MAX);
+                Ok(TransactionResult::from_evm_receipt(&receipt, confirmations))
+            }
+        }
+    }
+
+    /// Poll a Substrate extrinsic's status until it is deep enough or failed
+    async fn wait_for_extrinsic(
+        &self,
+        tx_hash: &str,
+        config: &WaitConfig,
+    ) -> Result<TransactionResult> {
+        let adapter = self.substrate()?;
+        let deadline = tokio::time::Instant::now() + config.timeout;
+        loop {
+            match adapter.get_transaction_status(tx_hash).await? {
+                TransactionStatus::Confirmed {
+                    block_number,
+                    confirmations,
+                } if confirmations >= config.confirmations => {
+                    let receipt = adapter
+                        .extrinsic_receipt(tx_hash, block_number)
+                        .await?
+                        .ok_or_else(|| {
+                            Error::Transaction(format!(
+                                "Transaction {} not found in block {}",
+                                tx_hash, block_number
+                            ))
+                        })?;
+                    let mut result = TransactionResult::from(receipt);
+                    result.status = TransactionStatus::Confirmed {
+                        block_number,
+                        confirmations,
+                    };
+                    return Ok(result);
+                }
+                TransactionStatus::Failed { error } => {
+                    return Ok(TransactionResult {
+                        source_tx_hash: tx_hash.to_string(),
+                        destination_tx_hash: None,
+                        status: TransactionStatus::Failed {
+                            error: error.clone(),
+                        },
+                        block_number: None,
+                        gas_used: None,
+                        block_hash: None,
+                        fee: None,
+                        events: Vec::new(),
+                        contract_address: None,
+                        finalized_at: None,
+                        receipt: Some(Receipt::new(tx_hash).with_error(error)),
+                    });
+                }
+                _ => {}
+            }
+            if tokio::time::Instant::now() >= deadline {
+                return Err(Error::Transaction(format!(
+                    "Transaction {} not confirmed within {:?}",
+                    tx_hash, config.timeout
+                )));
+            }
+            tokio::time::sleep(config.poll_interval).await;
+        }
+    }
+
+    /// When transactions on `chain` count as confirmed
+    ///
+    /// Set with [`ApexSDKBuilder::with_confirmation_policy`], otherwise
+    /// [`ConfirmationPolicy::for_chain`].
+    pub fn confirmation_policy(&self, chain: &Chain) -> ConfirmationPolicy {
+        self.confirmation_policies
+            .get(chain)
+            .copied()
+            .unwrap_or_else(|| ConfirmationPolicy::for_chain(chain))
+    }
+
+    /// Get the native token balance of an address, with the chain's decimals
+    /// and symbol.
+    ///
+    /// On hybrid chains (Moonbeam, Astar), `0x` addresses are queried through
+    /// the EVM adapter and SS58 addresses through the Substrate adapter.
+    ///
+    /// Balances are cached for [`with_balance_cache_ttl`]
+    /// (ApexSDKBuilder::with_balance_cache_ttl); [`execute`](Self::execute)
+    /// drops the cached balances of the accounts it touches.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidAddress`] without querying the chain if
+    /// `address` is not a valid account on `chain`.
+    pub async fn get_balance(&self, chain: &Chain, address: &Address) -> Result<Amount> {
+        address.validate_on(chain).map_err(Error::InvalidAddress)?;
+        let key = balance_key(chain, address);
+        let cached = self.balances.lock().unwrap().get(&key).copied();
+        if let Some((fetched_at, value)) = cached {
+            if fetched_at.elapsed() < self.balance_cache_ttl {
+                return Ok(Amount::native(value, chain));
+            }
+        }
+
+        let value = if Self::routes_to_evm(chain, address.as_str()) {
+            let balance = self.evm()?.get_balance(address.as_str()).await?;
+            u128::try_from(balance)
+                .map_err(|_| Error::Other(format!("Balance of {} exceeds u128", address)))?
+        } else {
+            self.substrate()?.get_balance(address.as_str()).await?
+        };
+
+        if !self.balance_cache_ttl.is_zero() {
+            self.balances
+                .lock()
+                .unwrap()
+                .insert(key, (Instant::now(), value));
+        }
+        Ok(Amount::native(value, chain))
+    }
+
+    /// Drop all cached balances
+    pub fn clear_balance_cache(&self) {
+        self.balances.lock().unwrap().clear();
+    }
+
+    /// Get the decimals of `token` on `chain`
+    ///
+    /// Decimals not set on the token are read from the ERC-20 contract or the
+    /// `Assets` pallet metadata.
+    pub async fn token_decimals(&self, chain: &Chain, token: &Token) -> Result<u8> {
+        if let Some(decimals) = token.decimals(chain) {
+            return Ok(decimals);
+        }
+        match token {
+            Token::Erc20 { address, .. } => Ok(self.evm()?.token_decimals(address).await?),
+            Token::Asset { id, .. } => Ok(self.substrate()?.get_asset_decimals(*id).await?),
+            Token::Native => Ok(chain.native_decimals()),
+        }
+    }
+
+    /// Convert a decimal amount of `token`, e.g. `"1.5"`, to its smallest
+    /// unit for [`TransactionBuilder::amount`]
+    pub async fn parse_amount(&self, chain: &Chain, token: &Token, amount: &str) -> Result<u128> {
+        let decimals = self.token_decimals(chain, token).await?;
+        parse_units(amount, decimals).map_err(Error::Transaction)
+    }
+
+    /// Summarize the balances of `accounts` on every configured chain.
+    ///
+    /// `0x` addresses are read on the EVM adapter's chain and SS58 addresses
+    /// on the Substrate adapter's chain: the native balance, every
+    /// [`TrackedToken`](crate::portfolio::TrackedToken) of that chain and,
+    /// when enabled, the staked balance. Holdings are valued through the
+    /// price oracle of the [`PortfolioConfig`], if any. Balances that fail to
+    /// read are reported in [`Portfolio::errors`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the chain of a configured adapter cannot be
+    /// identified.
+    pub async fn portfolio(&self, accounts: &[&str]) -> Result<Portfolio> {
+        let substrate_chain = match &self.substrate_adapter {
+            Some(adapter) => Some(
+                adapter
+                    .chain_name()
+                    .parse::<Chain>()
+                    .map_err(Error::UnsupportedChain)?,
+            ),
+            None => None,
+        };
+        let evm_chain = match &self.evm_adapter {
+            Some(adapter) => {
+                let id = adapter.chain_id().await?;
+                Some(
+                    Chain::from_evm_chain_id(id)
+                        .ok_or_else(|| Error::UnsupportedChain(format!("EVM chain ID {}", id)))?,
+                )
+            }
+            None => None,
+        };
+
+        let config = &self.portfolio;
+        let mut portfolio = Portfolio::default();
+        let mut prices: HashMap<(Chain, String), Option<f64>> = HashMap::new();
+
+        for &account in accounts {
+            let is_evm = account.starts_with("0x");
+            let Some(chain) = (if is_evm { &evm_chain } else { &substrate_chain }) else {
+                continue;
+            };
+
+            let mut reads = vec![(
+                HoldingKind::Native,
+                chain.native_symbol().to_string(),
+                chain.native_decimals(),
+            )];
+            reads.extend(
+                config
+                    .tokens
+                    .iter()
+                    .filter(|token| &token.chain == chain)
+                    .map(|token| {
+                        (
+                            HoldingKind::Token(token.token.clone()),
+                            token.symbol.clone(),
+                            token.decimals,
+                        )
+                    }),
+            );
+            if config.staking && !is_evm {
+                reads.push((
+                    HoldingKind::Staked,
+                    chain.native_symbol().to_string(),
+                    chain.native_decimals(),
+                ));
+            }
+
+            for (kind, symbol, decimals) in reads {
+                let amount = match self.read_holding(is_evm, &kind, account).await {
+                    Ok(amount) => amount,
+                    Err(e) => {
+                        portfolio.errors.push(PortfolioError {
+                            account: account.to_string(),
+                            chain: chain.clone(),
+                            symbol,
+                            error: e.to_string(),
+                        });
+                        continue;
+                    }
+                };
+                if amount == 0 {
+                    continue;
+                }
+
+                let price = match &config.oracle {
+                    Some(oracle) => {
+                        let key = (chain.clone(), symbol.clone());
+                        match prices.get(&key) {
+                            Some(price) => *price,
+                            None => {
+                                let price =
+                                    oracle.price(chain, &symbol).await.unwrap_or_else(|e| {
+                                        tracing::warn!(
+                                            "No price for {} on {}: {}",
+                                            symbol,
+                                            chain,
+                                            e
+                                        );
+                                        None
+                                    });
+                                prices.insert(key, price);
+                                price
+                            }
+                        }
+                    }
+                    None => None,
+                };
+                portfolio.holdings.push(
+                    Holding {
+                        account: account.to_string(),
+                        chain: chain.clone(),
+                        kind,
+                        symbol,
+                        decimals,
+                        amount,
+                        value: None,
+                    }
+                    .priced(price),
+                );
+            }
+        }
+        Ok(portfolio)
+    }
+
+    /// Read one balance of `account` for [`portfolio`](Self::portfolio)
+    async fn read_holding(&self, is_evm: bool, kind: &HoldingKind, account: &str) -> Result<u128> {
+        let too_large = || Error::Other(format!("Balance of {} exceeds u128", account));
+        match (is_evm, kind) {
+            (true, HoldingKind::Native) => {
+                u128::try_from(self.evm()?.get_balance(account).await?).map_err(|_| too_large())
+            }
+            (true, HoldingKind::Token(token)) => {
+                u128::try_from(self.evm()?.token_balance(token, account).await?)
+                    .map_err(|_| too_large())
+            }
+            (false, HoldingKind::Native) => Ok(self.substrate()?.get_balance(account).await?),
+            (false, HoldingKind::Token(asset)) => {
+                let asset_id = asset
+                    .parse()
+                    .map_err(|_| Error::Config(format!("Invalid asset ID: {}", asset)))?;
+                Ok(self
+                    .substrate()?
+                    .get_asset_balance(asset_id, account)
+                    .await?)
+            }
+            (false, HoldingKind::Staked) => {
+                Ok(self.substrate()?.get_staked_balance(account).await?)
+            }
+            (true, HoldingKind::Staked) => Ok(0),
+        }
+    }
+
+    /// Broadcast an already-signed transaction and return its hash.
+    ///
+    /// `raw` is an RLP-encoded transaction on EVM chains and a SCALE-encoded
+    /// extrinsic on Substrate chains. Hybrid chains are submitted through the
+    /// EVM adapter.
+    pub async fn submit_raw_transaction(&self, chain: &Chain, raw: &[u8]) -> Result<String> {
+        match chain.chain_type() {
+            ChainType::Substrate => {
+                Ok(self.substrate()?.submit_raw_extrinsic(raw.to_vec()).await?)
+            }
+            ChainType::Evm | ChainType::Hybrid => Ok(self.evm()?.send_raw_transaction(raw).await?),
+        }
+    }
+
+    fn routes_to_evm(chain: &Chain, address: &str) -> bool {
+        match chain.chain_type() {
+            ChainType::Evm => true,
+            ChainType::Substrate => false,
+            ChainType::Hybrid => address.starts_with("0x"),
+        }
+    }
+
+    /// Address of the wallet [`execute`](Self::execute) signs with on `chain`
+    ///
+    /// Hybrid chains sign with the EVM wallet if one is configured.
+    pub fn signer_address(&self, chain: &Chain) -> Option<String> {
+        let evm = || self.evm_wallet.as_ref().map(|wallet| wallet.address());
+        let substrate = || {
+            self.substrate_wallet
+                .as_ref()
+                .map(|wallet| wallet.address())
+        };
+        match chain.chain_type() {
+            ChainType::Evm => evm(),
+            ChainType::Substrate => substrate(),
+            ChainType::Hybrid => evm().or_else(substrate),
+        }
+    }
+
     /// Create a new transaction builder
     pub fn transaction(&self) -> TransactionBuilder {
         TransactionBuilder::new()
     }
 
+    /// Get the policy engine consulted by [`execute`](Self::execute), if any
+    pub fn policy_engine(&self) -> Option<&Arc<PolicyEngine>> {
+        self.policy.as_ref()
+    }
+
+    /// Get the attestor signing submission records, if any
+    pub fn attestor(&self) -> Option<&Arc<Attestor>> {
+        self.attestor.as_ref()
+    }
+
+    /// Get the event bridge following the configured adapters
+    ///
+    /// The bridge is started on first use and stopped when the SDK is
+    /// dropped. Must be called within a Tokio runtime.
+    pub fn events(&self) -> &Arc<EventBridge> {
+        self.events.get_or_init(|| {
+            let bridge = EventBridge::new();
+            if let Some(adapter) = &self.substrate_adapter {
+                bridge.attach_substrate(adapter.clone());
+            }
+            if let Some(adapter) = &self.evm_adapter {
+                bridge.attach_evm(adapter.clone(), self.event_poll_interval);
+            }
+            Arc::new(bridge)
+        })
+    }
+
+    /// Subscribe to chain events matching `filter`
+    ///
+    /// Events are fed automatically from every configured adapter; see
+    /// [`advanced`](crate::advanced).
+    pub fn subscribe_events(&self, filter: EventFilter) -> Arc<EventSubscription> {
+        self.events().subscribe(filter)
+    }
+
+    /// Get the block monitor following the configured adapters
+    ///
+    /// The monitor is started on first use and stopped when the SDK is
+    /// dropped. Must be called within a Tokio runtime.
+    pub fn blocks(&self) -> &Arc<BlockMonitor> {
+        self.blocks.get_or_init(|| {
+            let monitor = BlockMonitor::new(self.block_finality);
+            if let Some(adapter) = &self.substrate_adapter {
+                monitor.attach_substrate(adapter.clone());
+            }
+            if let Some(adapter) = &self.evm_adapter {
+                monitor.attach_evm(adapter.clone(), self.event_poll_interval);
+            }
+            Arc::new(monitor)
+        })
+    }
+
+    /// Subscribe to new blocks of `chain_type`, or of every configured chain if `None`
+    ///
+    /// Blocks are reported at the finality set with
+    /// [`with_block_finality`](ApexSDKBuilder::with_block_finality). They
+    /// come from Substrate block subscriptions and EVM `newHeads` over
+    /// WebSocket, or EVM polling over HTTP; followers reconnect when the
+    /// connection drops.
+    pub fn subscribe_blocks(&self, chain_type: Option<ChainType>) -> Arc<BlockSubscription> {
+        self.blocks().subscribe(chain_type)
+    }
+
+    /// Transfer a native token, ERC-20 token or `Assets` pallet asset
+    ///
+    /// Sent like [`execute`](Self::execute), through the `Balances` pallet,
+    /// the `Assets` pallet or the token's ERC-20 `transfer` as appropriate.
+    pub async fn transfer(&self, transfer: TokenTransfer) -> Result<TransactionResult> {
+        self.execute(transfer.into()).await
+    }
+
     /// Execute a transaction
+    ///
+    /// The transaction is signed with the wallet configured for its source
+    /// chain ([`with_evm_wallet`](ApexSDKBuilder::with_evm_wallet) or
+    /// [`with_substrate_wallet`](ApexSDKBuilder::with_substrate_wallet)),
+    /// which must be the sender's. EVM transactions are waited on until
+    /// mined, Substrate extrinsics until finalized; the result carries the
+    /// [`Receipt`] with its block, fee, gas price and events. ERC-20 and `Assets` pallet tokens
+    /// are sent through `transfer` calls.
+    ///
+    /// If a [`PolicyEngine`] is configured, the transaction is checked before
+    /// signing and rejected with [`Error::Policy`] if any policy denies it.
+    ///
+    /// # Errors
+    ///
+    /// Cross-chain transactions are rejected; send them through a
+    /// [`BridgeRouter`](crate::bridge::BridgeRouter).
     pub async fn execute(&self, transaction: Transaction) -> Result<TransactionResult> {
         tracing::info!(
             "Executing transaction from {:?} to {:?}",
@@ -257,67 +768,205 @@ impl ApexSDK {
             transaction.destination_chain
         );
 
-        // Validate that the required adapters are configured
-        match transaction.source_chain {
-            Chain::Polkadot | Chain::Kusama => {
-                self.substrate()?;
-            }
-            Chain::Ethereum | Chain::Polygon | Chain::BinanceSmartChain | Chain::Avalanche => {
-                self.evm()?;
-            }
-            Chain::Moonbeam | Chain::Astar => {
-                self.substrate()?;
-                self.evm()?;
-            }
+        if transaction.is_cross_chain() {
+            return Err(Error::Transaction(format!(
+                "Cannot execute a transfer from {} to {}; send it through a BridgeRouter",
+                transaction.source_chain, transaction.destination_chain
+            )));
+        }
+        // Token transfers need their token on the source chain
+        transaction.check_token()?;
+        let on_evm = Self::routes_to_evm(&transaction.source_chain, transaction.from.as_str());
+        if on_evm {
+            self.evm()?;
+        } else {
+            self.substrate()?;
         }
 
-        // For MVP, return a mock successful result
-        // In production, this would interact with the actual blockchain
-        let source_tx_hash = format!(
-            "0x{}",
-            hex::encode(
-                transaction
-                    .from
-                    .as_str()
-                    .as_bytes()
-                    .iter()
-                    .take(8)
-                    .cloned()
-                    .collect::<Vec<u8>>()
-            )
+        tracing::info!(
+            "Transferring {} of {}",
+            transaction.amount,
+            transaction.token
         );
 
-        let destination_tx_hash = if transaction.is_cross_chain() {
-            Some(format!(
-                "0x{}",
-                hex::encode(
-                    transaction
-                        .to
-                        .as_str()
-                        .as_bytes()
-                        .iter()
-                        .take(8)
-                        .cloned()
-                        .collect::<Vec<u8>>()
-                )
-            ))
+        let requested_at = now_millis();
+        let decisions = match &self.policy {
+            Some(policy) => policy.check_decisions(&transaction).await?,
+            None => Vec::new(),
+        };
+
+        let result = if on_evm {
+            self.execute_evm(&transaction).await?
         } else {
-            None
+            self.execute_substrate(&transaction).await?
         };
 
-        Ok(TransactionResult {
-            source_tx_hash,
-            destination_tx_hash,
-            status: TransactionStatus::Confirmed {
-                block_number: 12345,
-                confirmations: 1,
-            },
-            block_number: Some(12345),
-            gas_used: Some(21000),
-        })
+        {
+            let mut balances = self.balances.lock().unwrap();
+            balances.remove(&balance_key(&transaction.source_chain, &transaction.from));
+            balances.remove(&balance_key(
+                &transaction.destination_chain,
+                &transaction.to,
+            ));
+        }
+
+        if let Some(policy) = &self.policy {
+            policy.record(&transaction).await;
+        }
+
+        if let Some(attestor) = &self.attestor {
+            match attestor
+                .attest(
+                    &transaction,
+                    &result.source_tx_hash,
+                    &decisions,
+                    requested_at,
+                )
+                .await
+            {
+                Ok(attestation) => attestor.emit(&attestation).await,
+                Err(e) => tracing::error!("Failed to sign attestation: {}", e),
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Sign and send `transaction` with the EVM wallet, then wait until mined
+    async fn execute_evm(&self, transaction: &Transaction) -> Result<TransactionResult> {
+        let adapter = self.evm()?;
+        let wallet = self.evm_wallet.as_ref().ok_or_else(|| {
+            Error::Config(
+                "No EVM wallet configured; use with_evm_wallet or with_evm_signer".to_string(),
+            )
+        })?;
+        if parse_evm_address(transaction.from.as_str())? != wallet.eth_address() {
+            return Err(Error::Transaction(format!(
+                "Sender {} is not the configured EVM wallet {}",
+                transaction.from.as_str(),
+                wallet.address()
+            )));
+        }
+
+        let recipient = parse_evm_address(transaction.to.as_str())?;
+        let (to, value, data) = match &transaction.token {
+            Token::Erc20 { address, .. } => {
+                // transfer(address,uint256)
+                let data = [
+                    &ethers::utils::id("transfer(address,uint256)")[..],
+                    &ethers::abi::encode(&[
+                        ethers::abi::Token::Address(recipient),
+                        ethers::abi::Token::Uint(transaction.amount.into()),
+                    ]),
+                ]
+                .concat();
+                (parse_evm_address(address)?, U256::zero(), Some(data))
+            }
+            _ => (
+                recipient,
+                U256::from(transaction.amount),
+                transaction.data.clone(),
+            ),
+        };
+
+        let executor = adapter
+            .transaction_executor()
+            .with_gas_config(GasConfig {
+                gas_limit_multiplier: self.gas_limit_multiplier,
+                gas_limit: transaction.gas_limit.map(U256::from),
+                ..Default::default()
+            })
+            .with_receipt_timeout(self.receipt_timeout)
+            .with_receipt_poll_interval(self.event_poll_interval);
+        let tx = executor
+            .build_transaction(wallet, to, value, data, None)
+            .await?;
+        if let Some(max_fee) = transaction.max_fee {
+            let gas = tx.gas().copied().unwrap_or_default();
+            let price = match &tx {
+                TypedTransaction::Eip1559(tx) => tx.max_fee_per_gas,
+                _ => tx.gas_price(),
+            }
+            .unwrap_or_default();
+            let fee = gas.saturating_mul(price);
+            if fee > U256::from(max_fee) {
+                executor.release_nonce(wallet.eth_address(), &tx);
+                return Err(Error::Transaction(format!(
+                    "Estimated fee {} exceeds max fee {}",
+                    fee, max_fee
+                )));
+            }
+        }
+
+        let hash = executor.send_raw_transaction(wallet, tx).await?;
+        let receipt = executor.wait_for_receipt(hash, 1).await?;
+
+        let head = adapter.block_number().await?;
+        let mined = receipt.block_number.map(|n| n.as_u64()).unwrap_or(head);
+        let confirmations = u32::try_from(head.saturating_sub(mined)).unwrap_or(u32::MAX);
+        Ok(TransactionResult::from_evm_receipt(&receipt, confirmations))
+    }
+
+    /// Sign and submit `transaction` with the Substrate wallet, then wait
+    /// until finalized
+    async fn execute_substrate(&self, transaction: &Transaction) -> Result<TransactionResult> {
+        let adapter = self.substrate()?;
+        let wallet = self.substrate_wallet.as_ref().ok_or_else(|| {
+            Error::Config("No Substrate wallet configured; use with_substrate_wallet".to_string())
+        })?;
+        let sender = apex_sdk_types::ss58::public_key(transaction.from.as_str())
+            .map_err(Error::InvalidAddress)?;
+        if apex_sdk_types::ss58::public_key(&wallet.address()).ok() != Some(sender) {
+            return Err(Error::Transaction(format!(
+                "Sender {} is not the configured Substrate wallet {}",
+                transaction.from.as_str(),
+                wallet.address()
+            )));
+        }
+        if transaction.data.is_some() {
+            return Err(Error::Transaction(
+                "Call data is not supported on Substrate; use the transaction executor".to_string(),
+            ));
+        }
+
+        let mut executor = adapter.transaction_executor();
+        if let Some(max_fee) = transaction.max_fee {
+            executor = executor.with_fee_config(FeeConfig::new().with_max_fee(max_fee));
+        }
+        let to = transaction.to.as_str();
+        let receipt = match &transaction.token {
+            Token::Asset { id, .. } => {
+                executor
+                    .transfer_asset_with_receipt(wallet, *id, to, transaction.amount)
+                    .await?
+            }
+            _ => {
+                executor
+                    .transfer_with_receipt(wallet, to, transaction.amount)
+                    .await?
+            }
+        };
+        Ok(receipt.into())
     }
 }
 
+/// Balance cache key of `address` on `chain`, by account bytes so that
+/// differently formatted addresses of one account share an entry
+fn balance_key(chain: &Chain, address: &Address) -> (Chain, String) {
+    let account = address
+        .account_bytes()
+        .map(hex::encode)
+        .unwrap_or_else(|| address.as_str().to_string());
+    (chain.clone(), account)
+}
+
+#[allow(clippy::result_large_err)]
+fn parse_evm_address(address: &str) -> Result<ethers::types::Address> {
+    address
+        .parse()
+        .map_err(|_| Error::InvalidAddress(address.to_string()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -338,6 +987,20 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_balance_routing() {
+        assert!(ApexSDK::routes_to_evm(&Chain::Ethereum, "anything"));
+        assert!(!ApexSDK::routes_to_evm(&Chain::Polkadot, "0xabc"));
+        assert!(ApexSDK::routes_to_evm(
+            &Chain::Moonbeam,
+            "0x742D35Cc6634C0532925A3B844bC9e7595f0bEB7"
+        ));
+        assert!(!ApexSDK::routes_to_evm(
+            &Chain::Astar,
+            "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"
+        ));
+    }
+
     #[tokio::test]
     #[ignore] // Requires network connection
     async fn test_substrate_adapter_not_configured() {
@@ -435,7 +1098,7 @@ mod tests {
 
         let tx = sdk
             .transaction()
-            .from_evm_address("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb7")
+            .from_evm_address("0x742D35Cc6634C0532925A3B844bC9e7595f0bEB7")
             .to_evm_address("0x1234567890123456789012345678901234567890")
             .amount(1000)
             .build();
@@ -445,7 +1108,7 @@ mod tests {
 
     #[tokio::test]
     #[ignore] // Requires network connection
-    async fn test_execute_transaction() {
+    async fn test_execute_requires_wallet() {
         let sdk = ApexSDK::builder()
             .with_evm_endpoint("https://eth.llamarpc.com")
             .build()
@@ -454,23 +1117,18 @@ mod tests {
 
         let tx = sdk
             .transaction()
-            .from_evm_address("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb7")
+            .from_evm_address("0x742D35Cc6634C0532925A3B844bC9e7595f0bEB7")
             .to_evm_address("0x1234567890123456789012345678901234567890")
             .amount(1000)
             .build()
             .unwrap();
 
-        let result = sdk.execute(tx).await;
-        assert!(result.is_ok());
-
-        let tx_result = result.unwrap();
-        assert!(!tx_result.source_tx_hash.is_empty());
-        assert!(tx_result.destination_tx_hash.is_none());
+        assert!(matches!(sdk.execute(tx).await, Err(Error::Config(_))));
     }
 
     #[tokio::test]
     #[ignore] // Requires network connection
-    async fn test_execute_cross_chain_transaction() {
+    async fn test_execute_rejects_cross_chain_transaction() {
         let sdk = ApexSDK::builder()
             .with_substrate_endpoint("wss://rpc.polkadot.io")
             .with_evm_endpoint("https://eth.llamarpc.com")
@@ -481,18 +1139,12 @@ mod tests {
         let tx = sdk
             .transaction()
             .from_substrate_account("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY")
-            .to_evm_address("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb7")
+            .to_evm_address("0x742D35Cc6634C0532925A3B844bC9e7595f0bEB7")
             .amount(1000)
             .build()
             .unwrap();
 
         assert!(tx.is_cross_chain());
-
-        let result = sdk.execute(tx).await;
-        assert!(result.is_ok());
-
-        let tx_result = result.unwrap();
-        assert!(!tx_result.source_tx_hash.is_empty());
-        assert!(tx_result.destination_tx_hash.is_some());
+        assert!(matches!(sdk.execute(tx).await, Err(Error::Transaction(_))));
     }
 }
@@ -0,0 +1,687 @@
+//! Token swaps across DEX venues
+//!
+//! A [`SwapVenue`] quotes and executes swaps on one exchange. The
+//! [`SwapClient`] asks every venue that supports a [`SwapRequest`] for a
+//! quote, executes at the venue returning the most, and protects the swap
+//! with a minimum output derived from the slippage limit. Venues enforce
+//! that minimum on-chain, so a swap that would slip further reverts.
+//!
+//! Two venues are provided: [`UniswapV2Venue`] for Uniswap V2 style EVM
+//! routers, and [`AssetConversionVenue`] for Substrate's Asset Conversion
+//! pallet.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use apex_sdk::evm::{wallet::Wallet, EvmAdapter};
+//! use apex_sdk::swap::{SwapClient, SwapRequest, UniswapV2Venue};
+//! use apex_sdk::types::Chain;
+//!
+//! # #[tokio::main]
+//! # async fn main() -> apex_sdk::Result<()> {
+//! let adapter = EvmAdapter::connect("https://eth.llamarpc.com").await?;
+//! let wallet = Wallet::from_private_key("0x...")?.with_chain_id(1);
+//!
+//! let uniswap = UniswapV2Venue::new(
+//!     Chain::Ethereum,
+//!     adapter,
+//!     "0x7a250d5630B4cF539739dF2C5dAcb4c659F2488D",
+//!     wallet,
+//! );
+//! let client = SwapClient::new().with_venue(uniswap).with_max_slippage_bps(30);
+//!
+//! let result = client
+//!     .swap(&SwapRequest::new(
+//!         Chain::Ethereum,
+//!         "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48", // USDC
+//!         "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2", // WETH
+//!         1_000_000_000,                                // 1,000 USDC
+//!     ))
+//!     .await?;
+//! println!("{} via {}: {}", result.expected_amount_out, result.venue, result.tx_hash);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::{Error, Result};
+use apex_sdk_evm::wallet::Wallet as EvmWallet;
+use apex_sdk_evm::EvmAdapter;
+use apex_sdk_substrate::{AssetConversion, MultiLocation, SubstrateAdapter, Wallet};
+use apex_sdk_types::Chain;
+use async_trait::async_trait;
+use ethers::abi::{self, ParamType, Token};
+use ethers::types::{Address as EthAddress, U256};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Slippage tolerated when a request does not set one, in basis points
+pub const DEFAULT_MAX_SLIPPAGE_BPS: u32 = 50;
+
+/// A swap to perform
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SwapRequest {
+    /// Chain the swap happens on
+    pub chain: Chain,
+    /// Token paid, as the venue identifies it (contract address, asset ID)
+    pub token_in: String,
+    /// Token received, as the venue identifies it
+    pub token_out: String,
+    /// Amount paid in the smallest unit of `token_in`
+    pub amount_in: u128,
+    /// Slippage tolerated for this swap in basis points, overriding the
+    /// client's limit
+    pub max_slippage_bps: Option<u32>,
+}
+
+impl SwapRequest {
+    /// Swap `amount_in` of `token_in` for `token_out` on `chain`
+    pub fn new(
+        chain: Chain,
+        token_in: impl Into<String>,
+        token_out: impl Into<String>,
+        amount_in: u128,
+    ) -> Self {
+        Self {
+            chain,
+            token_in: token_in.into(),
+            token_out: token_out.into(),
+            amount_in,
+            max_slippage_bps: None,
+        }
+    }
+
+    /// Set the slippage tolerated for this swap, in basis points
+    pub fn with_max_slippage_bps(mut self, bps: u32) -> Self {
+        self.max_slippage_bps = Some(bps);
+        self
+    }
+}
+
+/// Expected outcome of a swap at one venue
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SwapQuote {
+    /// Venue that produced the quote
+    pub venue: String,
+    /// The quoted swap
+    pub request: SwapRequest,
+    /// Amount of `token_out` received at current prices, after pool fees
+    pub amount_out: u128,
+}
+
+/// A submitted swap
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SwapResult {
+    /// Venue the swap was executed at
+    pub venue: String,
+    /// Chain the swap happened on
+    pub chain: Chain,
+    /// Hash of the swap transaction
+    pub tx_hash: String,
+    /// Amount of `token_in` paid
+    pub amount_in: u128,
+    /// Amount of `token_out` quoted
+    pub expected_amount_out: u128,
+    /// Least amount of `token_out` the swap accepts
+    pub min_amount_out: u128,
+}
+
+/// An exchange that can swap tokens
+#[async_trait]
+pub trait SwapVenue: Send + Sync {
+    /// Venue name
+    fn name(&self) -> &str;
+
+    /// Whether the venue can perform `request`
+    fn supports(&self, request: &SwapRequest) -> bool;
+
+    /// Quote `request` at current prices
+    async fn quote(&self, request: &SwapRequest) -> Result<SwapQuote>;
+
+    /// Submit the quoted swap, reverting if less than `min_amount_out` would
+    /// be received
+    async fn execute(&self, quote: &SwapQuote, min_amount_out: u128) -> Result<SwapResult>;
+}
+
+/// Least amount accepted for a quoted `amount_out` with `slippage_bps` slippage
+pub fn min_amount_out(amount_out: u128, slippage_bps: u32) -> u128 {
+    let kept = 10_000u128.saturating_sub(slippage_bps as u128);
+    amount_out / 10_000 * kept + amount_out % 10_000 * kept / 10_000
+}
+
+/// Routes swaps to the venue with the best quote
+pub struct SwapClient {
+    venues: Vec<Arc<dyn SwapVenue>>,
+    max_slippage_bps: u32,
+}
+
+impl Default for SwapClient {
+    fn default() -> Self {
+        Self {
+            venues: Vec::new(),
+            max_slippage_bps: DEFAULT_MAX_SLIPPAGE_BPS,
+        }
+    }
+}
+
+impl SwapClient {
+    /// Create a client without venues
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a venue
+    pub fn with_venue(mut self, venue: impl SwapVenue + 'static) -> Self {
+        self.venues.push(Arc::new(venue));
+        self
+    }
+
+    /// Set the slippage tolerated by default, in basis points
+    pub fn with_max_slippage_bps(mut self, bps: u32) -> Self {
+        self.max_slippage_bps = bps;
+        self
+    }
+
+    /// Quote `request` with every supporting venue, best first
+    ///
+    /// Venues that fail to quote are skipped.
+    pub async fn quotes(&self, request: &SwapRequest) -> Vec<SwapQuote> {
+        let mut quotes = Vec::new();
+        for venue in self.venues.iter().filter(|v| v.supports(request)) {
+            match venue.quote(request).await {
+                Ok(quote) => quotes.push(quote),
+                Err(e) => tracing::warn!("Swap venue {} failed to quote: {}", venue.name(), e),
+            }
+        }
+        quotes.sort_by_key(|q| std::cmp::Reverse(q.amount_out));
+        quotes
+    }
+
+    /// Quote `request` with every supporting venue, returning the quote
+    /// paying the most, with the venue that made it
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no venue supports the request or all quotes fail.
+    pub async fn best_quote(
+        &self,
+        request: &SwapRequest,
+    ) -> Result<(Arc<dyn SwapVenue>, SwapQuote)> {
+        let mut best: Option<(Arc<dyn SwapVenue>, SwapQuote)> = None;
+        let mut last_error = None;
+
+        for venue in self.venues.iter().filter(|v| v.supports(request)) {
+            match venue.quote(request).await {
+                Ok(quote) => {
+                    if best
+                        .as_ref()
+                        .is_none_or(|(_, b)| quote.amount_out > b.amount_out)
+                    {
+                        best = Some((venue.clone(), quote));
+                    }
+                }
+                Err(e) => {
+                    tracing::warn!("Swap venue {} failed to quote: {}", venue.name(), e);
+                    last_error = Some(e);
+                }
+            }
+        }
+
+        best.ok_or_else(|| {
+            last_error.unwrap_or_else(|| {
+                Error::UnsupportedChain(format!(
+                    "No swap venue for {} to {} on {}",
+                    request.token_in, request.token_out, request.chain
+                ))
+            })
+        })
+    }
+
+    /// Swap at the venue with the best quote, accepting at most the
+    /// request's (or the client's) slippage
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Config`] for a slippage limit above 100%, and
+    /// [`Error::Transaction`] if the best quote is zero.
+    pub async fn swap(&self, request: &SwapRequest) -> Result<SwapResult> {
+        let slippage_bps = request.max_slippage_bps.unwrap_or(self.max_slippage_bps);
+        if slippage_bps > 10_000 {
+            return Err(Error::Config(format!(
+                "Slippage limit of {} bps exceeds 100%",
+                slippage_bps
+            )));
+        }
+
+        let (venue, quote) = self.best_quote(request).await?;
+        if quote.amount_out == 0 {
+            return Err(Error::Transaction(format!(
+                "{} quoted no output for {} {}",
+                quote.venue, request.amount_in, request.token_in
+            )));
+        }
+
+        let min_out = min_amount_out(quote.amount_out, slippage_bps);
+        tracing::info!(
+            "Swapping {} {} for at least {} {} via {}",
+            request.amount_in,
+            request.token_in,
+            min_out,
+            request.token_out,
+            quote.venue
+        );
+        venue.execute(&quote, min_out).await
+    }
+}
+
+/// A Uniswap V2 style router on an EVM chain
+///
+/// Swaps go directly between the two tokens, so a pool for the pair must
+/// exist. The router must be allowed to spend `token_in`; see
+/// [`approve`](Self::approve).
+pub struct UniswapV2Venue {
+    name: String,
+    chain: Chain,
+    adapter: EvmAdapter,
+    router: String,
+    wallet: EvmWallet,
+    deadline: Duration,
+}
+
+impl UniswapV2Venue {
+    /// Swap through `router` on `chain`, paying from and receiving into `wallet`
+    pub fn new(
+        chain: Chain,
+        adapter: EvmAdapter,
+        router: impl Into<String>,
+        wallet: EvmWallet,
+    ) -> Self {
+        Self {
+            name: "uniswap-v2".to_string(),
+            chain,
+            adapter,
+            router: router.into(),
+            wallet,
+            deadline: Duration::from_secs(600),
+        }
+    }
+
+    /// Set the venue name, e.g. for a fork of Uniswap V2
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    /// Set how long a submitted swap stays valid
+    pub fn with_deadline(mut self, deadline: Duration) -> Self {
+        self.deadline = deadline;
+        self
+    }
+
+    /// Allow the router to spend `amount` of `token`, returning the transaction hash
+    pub async fn approve(&self, token: &str, amount: u128) -> Result<String> {
+        // approve(address,uint256)
+        let data = call_data(
+            [0x09, 0x5e, 0xa7, 0xb3],
+            &[
+                Token::Address(parse_address(&self.router)?),
+                Token::Uint(amount.into()),
+            ],
+        );
+        let hash = self
+            .adapter
+            .transaction_executor()
+            .send_transaction(
+                &self.wallet,
+                parse_address(token)?,
+                U256::zero(),
+                Some(data),
+            )
+            .await?;
+        Ok(format!("{:?}", hash))
+    }
+
+    async fn allowance(&self, token: &str) -> Result<U256> {
+        // allowance(address,address)
+        let data = call_data(
+            [0xdd, 0x62, 0xed, 0x3e],
+            &[
+                Token::Address(self.wallet.eth_address()),
+                Token::Address(parse_address(&self.router)?),
+            ],
+        );
+        let output = self.adapter.call_contract(token, data).await?;
+        match abi::decode(&[ParamType::Uint(256)], &output)
+            .ok()
+            .as_deref()
+        {
+            Some([Token::Uint(allowance)]) => Ok(*allowance),
+            _ => Err(Error::Transaction(format!(
+                "Unexpected allowance response from {}",
+                token
+            ))),
+        }
+    }
+
+    #[allow(clippy::result_large_err)]
+    fn path(request: &SwapRequest) -> Result<Token> {
+        Ok(Token::Array(vec![
+            Token::Address(parse_address(&request.token_in)?),
+            Token::Address(parse_address(&request.token_out)?),
+        ]))
+    }
+}
+
+#[async_trait]
+impl SwapVenue for UniswapV2Venue {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn supports(&self, request: &SwapRequest) -> bool {
+        request.chain == self.chain
+            && request.token_in.parse::<EthAddress>().is_ok()
+            && request.token_out.parse::<EthAddress>().is_ok()
+    }
+
+    async fn quote(&self, request: &SwapRequest) -> Result<SwapQuote> {
+        // getAmountsOut(uint256,address[])
+        let data = call_data(
+            [0xd0, 0x6c, 0xa6, 0x1f],
+            &[Token::Uint(request.amount_in.into()), Self::path(request)?],
+        );
+        let output = self.adapter.call_contract(&self.router, data).await?;
+        let amounts = abi::decode(&[ParamType::Array(Box::new(ParamType::Uint(256)))], &output)
+            .map_err(|e| Error::Transaction(format!("Unexpected getAmountsOut response: {}", e)))?;
+
+        let amount_out = match amounts.as_slice() {
+            [Token::Array(amounts)] => match amounts.last() {
+                Some(Token::Uint(amount)) => *amount,
+                _ => {
+                    return Err(Error::Transaction(
+                        "Empty getAmountsOut response".to_string(),
+                    ))
+                }
+            },
+            _ => {
+                return Err(Error::Transaction(
+                    "Unexpected getAmountsOut response".to_string(),
+                ))
+            }
+        };
+
+        Ok(SwapQuote {
+            venue: self.name.clone(),
+            request: request.clone(),
+            amount_out: u128::try_from(amount_out)
+                .map_err(|_| Error::Other("Quoted amount exceeds u128".to_string()))?,
+        })
+    }
+
+    async fn execute(&self, quote: &SwapQuote, min_amount_out: u128) -> Result<SwapResult> {
+        let request = &quote.request;
+        let allowance = self.allowance(&request.token_in).await?;
+        if allowance < U256::from(request.amount_in) {
+            return Err(Error::Transaction(format!(
+                "Router {} may spend only {} of {}; approve it first",
+                self.router, allowance, request.token_in
+            )));
+        }
+
+        let deadline = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d + self.deadline)
+            .unwrap_or_default()
+            .as_secs();
+        // swapExactTokensForTokens(uint256,uint256,address[],address,uint256)
+        let data = call_data(
+            [0x38, 0xed, 0x17, 0x39],
+            &[
+                Token::Uint(request.amount_in.into()),
+                Token::Uint(min_amount_out.into()),
+                Self::path(request)?,
+                Token::Address(self.wallet.eth_address()),
+                Token::Uint(deadline.into()),
+            ],
+        );
+        let hash = self
+            .adapter
+            .transaction_executor()
+            .send_transaction(
+                &self.wallet,
+                parse_address(&self.router)?,
+                U256::zero(),
+                Some(data),
+            )
+            .await?;
+
+        Ok(SwapResult {
+            venue: self.name.clone(),
+            chain: self.chain.clone(),
+            tx_hash: format!("{:?}", hash),
+            amount_in: request.amount_in,
+            expected_amount_out: quote.amount_out,
+            min_amount_out,
+        })
+    }
+}
+
+/// Substrate's Asset Conversion pallet, e.g. on Asset Hub
+///
+/// Tokens are `"native"` for the relay chain token, or the numeric ID of a
+/// `pallet-assets` asset (e.g. `"1984"` for USDT on Polkadot Asset Hub).
+pub struct AssetConversionVenue {
+    chain: Chain,
+    adapter: SubstrateAdapter,
+    wallet: Wallet,
+}
+
+impl AssetConversionVenue {
+    /// Swap on `chain`, paying from and receiving into `wallet`
+    pub fn new(chain: Chain, adapter: SubstrateAdapter, wallet: Wallet) -> Self {
+        Self {
+            chain,
+            adapter,
+            wallet,
+        }
+    }
+
+    fn location(token: &str) -> Option<MultiLocation> {
+        if token.eq_ignore_ascii_case("native") {
+            return Some(AssetConversion::native());
+        }
+        token.parse().ok().map(AssetConversion::asset)
+    }
+
+    #[allow(clippy::result_large_err)]
+    fn locations(request: &SwapRequest) -> Result<(MultiLocation, MultiLocation)> {
+        let location = |token: &str| {
+            Self::location(token)
+                .ok_or_else(|| Error::Config(format!("Unknown Asset Conversion token {}", token)))
+        };
+        Ok((location(&request.token_in)?, location(&request.token_out)?))
+    }
+}
+
+#[async_trait]
+impl SwapVenue for AssetConversionVenue {
+    fn name(&self) -> &str {
+        "asset-conversion"
+    }
+
+    fn supports(&self, request: &SwapRequest) -> bool {
+        request.chain == self.chain && Self::locations(request).is_ok()
+    }
+
+    async fn quote(&self, request: &SwapRequest) -> Result<SwapQuote> {
+        let (asset_in, asset_out) = Self::locations(request)?;
+        let amount_out = self
+            .adapter
+            .asset_conversion()
+            .quote_exact_in(&asset_in, &asset_out, request.amount_in)
+            .await?
+            .ok_or_else(|| {
+                Error::Transaction(format!(
+                    "No Asset Conversion pool for {} and {}",
+                    request.token_in, request.token_out
+                ))
+            })?;
+
+        Ok(SwapQuote {
+            venue: self.name().to_string(),
+            request: request.clone(),
+            amount_out,
+        })
+    }
+
+    async fn execute(&self, quote: &SwapQuote, min_amount_out: u128) -> Result<SwapResult> {
+        let request = &quote.request;
+        let (asset_in, asset_out) = Self::locations(request)?;
+
+        // Returns once the extrinsic is finalized
+        let tx_hash = self
+            .adapter
+            .asset_conversion()
+            .swap_exact_in(
+                &self.wallet,
+                &[asset_in, asset_out],
+                request.amount_in,
+                min_amount_out,
+                true,
+            )
+            .await?;
+
+        Ok(SwapResult {
+            venue: self.name().to_string(),
+            chain: self.chain.clone(),
+            tx_hash,
+            amount_in: request.amount_in,
+            expected_amount_out: quote.amount_out,
+            min_amount_out,
+        })
+    }
+}
+
+fn call_data(selector: [u8; 4], args: &[Token]) -> Vec<u8> {
+    let mut data = selector.to_vec();
+    data.extend(abi::encode(args));
+    data
+}
+
+#[allow(clippy::result_large_err)]
+fn parse_address(address: &str) -> Result<EthAddress> {
+    address
+        .parse()
+        .map_err(|_| Error::InvalidAddress(address.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Venue paying `rate` per unit of `token_in`
+    struct FixedRate {
+        name: &'static str,
+        rate: u128,
+    }
+
+    #[async_trait]
+    impl SwapVenue for FixedRate {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn supports(&self, request: &SwapRequest) -> bool {
+            request.token_in == "USDC"
+        }
+
+        async fn quote(&self, request: &SwapRequest) -> Result<SwapQuote> {
+            Ok(SwapQuote {
+                venue: self.name.to_string(),
+                request: request.clone(),
+                amount_out: request.amount_in * self.rate,
+            })
+        }
+
+        async fn execute(&self, quote: &SwapQuote, min_amount_out: u128) -> Result<SwapResult> {
+            Ok(SwapResult {
+                venue: self.name.to_string(),
+                chain: quote.request.chain.clone(),
+                tx_hash: "0x01".to_string(),
+                amount_in: quote.request.amount_in,
+                expected_amount_out: quote.amount_out,
+                min_amount_out,
+            })
+        }
+    }
+
+    fn client() -> SwapClient {
+        SwapClient::new()
+            .with_venue(FixedRate {
+                name: "worse",
+                rate: 2,
+            })
+            .with_venue(FixedRate {
+                name: "better",
+                rate: 3,
+            })
+    }
+
+    #[test]
+    fn test_min_amount_out() {
+        assert_eq!(min_amount_out(10_000, 50), 9_950);
+        assert_eq!(min_amount_out(999, 0), 999);
+        assert_eq!(min_amount_out(999, 10_000), 0);
+        assert_eq!(
+            min_amount_out(u128::MAX, 1),
+            u128::MAX / 10_000 * 9_999 + 1_454
+        );
+    }
+
+    #[tokio::test]
+    async fn test_client_swaps_at_best_venue() {
+        let client = client();
+        let request = SwapRequest::new(Chain::Ethereum, "USDC", "WETH", 1_000);
+
+        let quotes = client.quotes(&request).await;
+        assert_eq!(quotes.len(), 2);
+        assert_eq!(quotes[0].venue, "better");
+
+        let result = client.swap(&request).await.unwrap();
+        assert_eq!(result.venue, "better");
+        assert_eq!(result.expected_amount_out, 3_000);
+        assert_eq!(result.min_amount_out, 2_985);
+
+        let result = client
+            .swap(&request.with_max_slippage_bps(1_000))
+            .await
+            .unwrap();
+        assert_eq!(result.min_amount_out, 2_700);
+    }
+
+    #[tokio::test]
+    async fn test_client_rejects_invalid_swaps() {
+        let client = client();
+
+        assert!(matches!(
+            client
+                .swap(&SwapRequest::new(Chain::Ethereum, "DAI", "WETH", 1_000))
+                .await,
+            Err(Error::UnsupportedChain(_))
+        ));
+        assert!(matches!(
+            client
+                .swap(
+                    &SwapRequest::new(Chain::Ethereum, "USDC", "WETH", 1_000)
+                        .with_max_slippage_bps(10_001)
+                )
+                .await,
+            Err(Error::Config(_))
+        ));
+        assert!(matches!(
+            client
+                .swap(&SwapRequest::new(Chain::Ethereum, "USDC", "WETH", 0))
+                .await,
+            Err(Error::Transaction(_))
+        ));
+    }
+}
@@ -1,18 +1,25 @@
 //! Transaction building and execution
 
 use crate::error::{Error, Result};
-use apex_sdk_types::{Address, Chain, TransactionStatus};
+use apex_sdk_substrate::ExtrinsicReceipt;
+use apex_sdk_types::{
+    Address, Chain, ChainType, Fee, Receipt, Token, TransactionEvent, TransactionStatus,
+};
 use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::time::Duration;
 
 /// Transaction builder for creating cross-chain transactions
 pub struct TransactionBuilder {
     from: Option<Address>,
     to: Option<Address>,
     amount: Option<u128>,
+    token: Token,
     source_chain: Option<Chain>,
     destination_chain: Option<Chain>,
     data: Option<Vec<u8>>,
     gas_limit: Option<u64>,
+    max_fee: Option<u128>,
 }
 
 impl TransactionBuilder {
@@ -22,10 +29,12 @@ impl TransactionBuilder {
             from: None,
             to: None,
             amount: None,
+            token: Token::Native,
             source_chain: None,
             destination_chain: None,
             data: None,
             gas_limit: None,
+            max_fee: None,
         }
     }
 
@@ -71,6 +80,20 @@ impl TransactionBuilder {
         self
     }
 
+    /// Set the token transferred, the source chain's native token by default
+    ///
+    /// [`amount`](Self::amount) is in the smallest unit of the token.
+    pub fn token(mut self, token: Token) -> Self {
+        self.token = token;
+        self
+    }
+
+    /// Transfer asset `asset_id` of the `Assets` pallet
+    pub fn asset_id(mut self, asset_id: u32) -> Self {
+        self.token = Token::asset(asset_id);
+        self
+    }
+
     /// Set the source chain
     pub fn on_chain(mut self, chain: Chain) -> Self {
         self.source_chain = Some(chain);
@@ -89,7 +112,18 @@ impl TransactionBuilder {
         self
     }
 
+    /// Set the maximum fee the sender is willing to pay, in the smallest unit
+    /// of the source chain
+    pub fn with_max_fee(mut self, max_fee: u128) -> Self {
+        self.max_fee = Some(max_fee);
+        self
+    }
+
     /// Build the transaction
+    ///
+    /// The source chain defaults to Polkadot or Ethereum depending on the
+    /// sender's address. The recipient receives on the source chain if its
+    /// address is valid there, and otherwise on Polkadot or Ethereum.
     #[allow(clippy::result_large_err)]
     pub fn build(self) -> Result<Transaction> {
         let from = self
@@ -108,20 +142,29 @@ impl TransactionBuilder {
             Address::Evm(_) => Chain::Ethereum,
         });
 
-        let destination_chain = self.destination_chain.unwrap_or(match &to {
-            Address::Substrate(_) => Chain::Polkadot,
-            Address::Evm(_) => Chain::Ethereum,
-        });
-
-        Ok(Transaction {
+        // Recipients with an address on the source chain receive there
+        let destination_chain =
+            self.destination_chain
+                .unwrap_or_else(|| match (&to, source_chain.chain_type()) {
+                    (Address::Substrate(_), ChainType::Substrate | ChainType::Hybrid)
+                    | (Address::Evm(_), ChainType::Evm | ChainType::Hybrid) => source_chain.clone(),
+                    (Address::Substrate(_), ChainType::Evm) => Chain::Polkadot,
+                    (Address::Evm(_), ChainType::Substrate) => Chain::Ethereum,
+                });
+
+        let transaction = Transaction {
             from,
             to,
             amount,
+            token: self.token,
             source_chain,
             destination_chain,
             data: self.data,
             gas_limit: self.gas_limit,
-        })
+            max_fee: self.max_fee,
+        };
+        transaction.check_token()?;
+        Ok(transaction)
     }
 }
 
@@ -131,6 +174,43 @@ impl Default for TransactionBuilder {
     }
 }
 
+/// Transfer of a native token, ERC-20 token or `Assets` pallet asset on
+/// one chain
+///
+/// Sent with [`ApexSDK::transfer`](crate::ApexSDK::transfer), which calls the
+/// `Balances` pallet, the `Assets` pallet or the token's ERC-20 `transfer`
+/// depending on `token` and `chain`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenTransfer {
+    /// Chain the transfer happens on
+    pub chain: Chain,
+    /// Token transferred
+    #[serde(default)]
+    pub token: Token,
+    /// Sender address
+    pub from: Address,
+    /// Recipient address
+    pub to: Address,
+    /// Amount to transfer, in the smallest unit of `token`
+    pub amount: u128,
+}
+
+impl From<TokenTransfer> for Transaction {
+    fn from(transfer: TokenTransfer) -> Self {
+        Self {
+            from: transfer.from,
+            to: transfer.to,
+            amount: transfer.amount,
+            token: transfer.token,
+            source_chain: transfer.chain.clone(),
+            destination_chain: transfer.chain,
+            data: None,
+            gas_limit: None,
+            max_fee: None,
+        }
+    }
+}
+
 /// Represents a blockchain transaction
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Transaction {
@@ -138,8 +218,11 @@ pub struct Transaction {
     pub from: Address,
     /// Recipient address
     pub to: Address,
-    /// Amount to transfer
+    /// Amount to transfer, in the smallest unit of `token`
     pub amount: u128,
+    /// Token transferred
+    #[serde(default, skip_serializing_if = "Token::is_native")]
+    pub token: Token,
     /// Source blockchain
     pub source_chain: Chain,
     /// Destination blockchain
@@ -148,6 +231,9 @@ pub struct Transaction {
     pub data: Option<Vec<u8>>,
     /// Gas limit
     pub gas_limit: Option<u64>,
+    /// Maximum fee the sender is willing to pay
+    #[serde(default)]
+    pub max_fee: Option<u128>,
 }
 
 impl Transaction {
@@ -156,6 +242,45 @@ impl Transaction {
         self.source_chain != self.destination_chain
     }
 
+    /// Check that `token` can be transferred from the sender on the source
+    /// chain
+    #[allow(clippy::result_large_err)]
+    pub(crate) fn check_token(&self) -> Result<()> {
+        let chain_type = self.source_chain.chain_type();
+        let (kind, supported) = match &self.token {
+            Token::Native => return Ok(()),
+            Token::Erc20 { .. } => (
+                "ERC-20 tokens",
+                matches!(self.from, Address::Evm(_)) && chain_type != ChainType::Substrate,
+            ),
+            Token::Asset { .. } => (
+                "Assets pallet tokens",
+                matches!(self.from, Address::Substrate(_)) && chain_type != ChainType::Evm,
+            ),
+        };
+        if !supported {
+            return Err(Error::Transaction(format!(
+                "{} cannot be sent from {} on {}",
+                kind,
+                self.from.as_str(),
+                self.source_chain.name()
+            )));
+        }
+        if self.is_cross_chain() {
+            return Err(Error::Transaction(format!(
+                "{} cannot be transferred across chains",
+                kind
+            )));
+        }
+        if self.data.is_some() {
+            return Err(Error::Transaction(format!(
+                "Call data cannot be attached to a transfer of {}",
+                kind
+            )));
+        }
+        Ok(())
+    }
+
     /// Get transaction hash (placeholder for actual implementation)
     pub fn hash(&self) -> String {
         // Simple hash based on sender/receiver addresses
@@ -164,6 +289,56 @@ impl Transaction {
     }
 }
 
+/// How [`ApexSDK::wait_for_transaction`](crate::ApexSDK::wait_for_transaction)
+/// waits for a transaction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WaitConfig {
+    /// Blocks required on top of the transaction's block
+    pub confirmations: u32,
+    /// How long to wait before giving up
+    pub timeout: Duration,
+    /// Interval between status checks
+    pub poll_interval: Duration,
+}
+
+impl Default for WaitConfig {
+    /// Wait up to [`DEFAULT_RECEIPT_TIMEOUT`](crate::sdk::DEFAULT_RECEIPT_TIMEOUT)
+    /// for inclusion, checking every
+    /// [`DEFAULT_POLL_INTERVAL`](crate::advanced::DEFAULT_POLL_INTERVAL)
+    fn default() -> Self {
+        Self {
+            confirmations: 0,
+            timeout: crate::sdk::DEFAULT_RECEIPT_TIMEOUT,
+            poll_interval: crate::advanced::DEFAULT_POLL_INTERVAL,
+        }
+    }
+}
+
+impl WaitConfig {
+    /// Create the default configuration
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Wait for `confirmations` blocks on top of the transaction's block
+    pub fn with_confirmations(mut self, confirmations: u32) -> Self {
+        self.confirmations = confirmations;
+        self
+    }
+
+    /// Give up after `timeout`
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Check the transaction every `poll_interval`
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+}
+
 /// Transaction execution result
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TransactionResult {
@@ -177,12 +352,151 @@ pub struct TransactionResult {
     pub block_number: Option<u64>,
     /// Gas used
     pub gas_used: Option<u64>,
+    /// Hash of the block where transaction was included
+    #[serde(default)]
+    pub block_hash: Option<String>,
+    /// Effective fee paid on the source chain
+    #[serde(default)]
+    pub fee: Option<Fee>,
+    /// Events (Substrate) or logs (EVM) emitted by the transaction
+    #[serde(default)]
+    pub events: Vec<TransactionEvent>,
+    /// Address of the created contract, for deployments
+    #[serde(default)]
+    pub contract_address: Option<String>,
+    /// Unix timestamp (seconds) at which finality was observed
+    #[serde(default)]
+    pub finalized_at: Option<u64>,
+    /// Full receipt of the source transaction, with its fee, events and
+    /// dispatch or revert error
+    #[serde(default)]
+    pub receipt: Option<Receipt>,
+}
+
+impl TransactionResult {
+    /// Build a result from a mined EVM transaction receipt
+    pub fn from_evm_receipt(
+        receipt: &ethers::types::TransactionReceipt,
+        confirmations: u32,
+    ) -> Self {
+        let receipt = apex_sdk_evm::transaction::to_receipt(receipt);
+        let status = match (&receipt.error, receipt.block_number) {
+            (Some(error), _) => TransactionStatus::Failed {
+                error: error.clone(),
+            },
+            (None, Some(block_number)) => TransactionStatus::Confirmed {
+                block_number,
+                confirmations,
+            },
+            (None, None) => TransactionStatus::Pending,
+        };
+
+        Self {
+            source_tx_hash: receipt.tx_hash.clone(),
+            destination_tx_hash: None,
+            status,
+            block_number: receipt.block_number,
+            gas_used: receipt.gas_used,
+            block_hash: receipt.block_hash.clone(),
+            fee: receipt.fee.clone(),
+            events: receipt.events.clone(),
+            contract_address: receipt.contract_address.clone(),
+            finalized_at: None,
+            receipt: Some(receipt),
+        }
+    }
+
+    /// Render the result for display on the given chain.
+    ///
+    /// Unlike the [`fmt::Display`] implementation this renders the fee in the
+    /// chain's native token and includes the chain name, e.g.
+    /// `"Confirmed in block #12345 (1 confirmation) (fee 0.0021 DOT) [0xabc...] on Polkadot"`.
+    pub fn format_with(&self, chain: &Chain) -> String {
+        let fee = self.fee.as_ref().map(|fee| fee.format_with(chain));
+        format!("{} on {}", Summary(self, fee), chain)
+    }
+}
+
+impl From<ExtrinsicReceipt> for TransactionResult {
+    fn from(receipt: ExtrinsicReceipt) -> Self {
+        let unified = receipt.to_receipt();
+        let status = match receipt.error {
+            Some(error) => TransactionStatus::Failed { error },
+            None => TransactionStatus::Confirmed {
+                block_number: receipt.block_number,
+                confirmations: 0,
+            },
+        };
+        Self {
+            source_tx_hash: receipt.hash,
+            destination_tx_hash: None,
+            status,
+            block_number: Some(receipt.block_number),
+            gas_used: None,
+            block_hash: Some(receipt.block_hash),
+            fee: receipt.fee,
+            events: receipt.events,
+            contract_address: None,
+            finalized_at: Some(receipt.finalized_at),
+            receipt: Some(unified),
+        }
+    }
+}
+
+/// Display helper shared by `Display` and `format_with`
+struct Summary<'a>(&'a TransactionResult, Option<String>);
+
+impl fmt::Display for Summary<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Summary(result, fee) = self;
+        write!(f, "{}", result.status)?;
+        match (fee, result.gas_used) {
+            (Some(fee), _) => write!(f, " (fee {})", fee)?,
+            (None, Some(gas_used)) => write!(f, " (gas used {})", gas_used)?,
+            (None, None) => {}
+        }
+        write!(f, " [{}", result.source_tx_hash)?;
+        if let Some(destination) = &result.destination_tx_hash {
+            write!(f, " -> {}", destination)?;
+        }
+        f.write_str("]")
+    }
+}
+
+impl fmt::Display for TransactionResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let fee = self.fee.as_ref().map(|fee| fee.amount.to_string());
+        write!(f, "{}", Summary(self, fee))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_token_transfer_into_transaction() {
+        let transfer = TokenTransfer {
+            chain: Chain::Moonbeam,
+            token: Token::erc20("0xFfFFfFff1FcaCBd218EDc0EbA20Fc2308C778080"),
+            from: Address::evm("0x742D35CC6634c0532925A3b844BC9E7595F0BEb0"),
+            to: Address::evm("0x1234567890123456789012345678901234567890"),
+            amount: 5,
+        };
+        let tx = Transaction::from(transfer.clone());
+        assert_eq!(tx.source_chain, Chain::Moonbeam);
+        assert!(!tx.is_cross_chain());
+        assert_eq!(tx.token, transfer.token);
+        assert!(tx.check_token().is_ok());
+
+        // Assets pallet tokens cannot leave an EVM account
+        let tx = Transaction::from(TokenTransfer {
+            token: Token::asset(1984),
+            ..transfer
+        });
+        assert!(tx.check_token().is_err());
+    }
+
     #[test]
     fn test_transaction_builder_new() {
         let builder = TransactionBuilder::new();
@@ -201,7 +515,7 @@ mod tests {
     #[test]
     fn test_transaction_builder_evm_to_evm() {
         let tx = TransactionBuilder::new()
-            .from_evm_address("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb7")
+            .from_evm_address("0x742D35Cc6634C0532925A3B844bC9e7595f0bEB7")
             .to_evm_address("0x1234567890123456789012345678901234567890")
             .amount(1000)
             .build();
@@ -218,7 +532,7 @@ mod tests {
     fn test_transaction_builder_substrate_to_evm() {
         let tx = TransactionBuilder::new()
             .from_substrate_account("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY")
-            .to_evm_address("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb7")
+            .to_evm_address("0x742D35Cc6634C0532925A3B844bC9e7595f0bEB7")
             .amount(500)
             .build();
 
@@ -247,7 +561,7 @@ mod tests {
     #[test]
     fn test_transaction_builder_with_explicit_chain() {
         let tx = TransactionBuilder::new()
-            .from_evm_address("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb7")
+            .from_evm_address("0x742D35Cc6634C0532925A3B844bC9e7595f0bEB7")
             .to_evm_address("0x1234567890123456789012345678901234567890")
             .amount(1000)
             .on_chain(Chain::Polygon)
@@ -256,6 +570,119 @@ mod tests {
         assert!(tx.is_ok());
         let tx = tx.unwrap();
         assert_eq!(tx.source_chain, Chain::Polygon);
+        assert_eq!(tx.destination_chain, Chain::Polygon);
+    }
+
+    #[test]
+    fn test_transaction_builder_destination_defaults() {
+        let evm = "0x742D35Cc6634C0532925A3B844bC9e7595f0bEB7";
+        let ss58 = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+        let destination = |from: Address, to: Address, chain: Chain| {
+            TransactionBuilder::new()
+                .from(from)
+                .to(to)
+                .amount(1)
+                .on_chain(chain)
+                .build()
+                .unwrap()
+                .destination_chain
+        };
+
+        // Recipients on the source chain's VM stay on the source chain
+        assert_eq!(
+            destination(Address::evm(evm), Address::evm(evm), Chain::Polygon),
+            Chain::Polygon
+        );
+        assert_eq!(
+            destination(
+                Address::substrate(ss58),
+                Address::substrate(ss58),
+                Chain::Kusama
+            ),
+            Chain::Kusama
+        );
+        assert_eq!(
+            destination(Address::substrate(ss58), Address::evm(evm), Chain::Moonbeam),
+            Chain::Moonbeam
+        );
+        // Others cross to Polkadot or Ethereum
+        assert_eq!(
+            destination(Address::evm(evm), Address::substrate(ss58), Chain::Polygon),
+            Chain::Polkadot
+        );
+        assert_eq!(
+            destination(Address::substrate(ss58), Address::evm(evm), Chain::Kusama),
+            Chain::Ethereum
+        );
+    }
+
+    #[test]
+    fn test_transaction_builder_token_transfers() {
+        let usdc = Token::erc20("0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48").with_decimals(6);
+        let tx = TransactionBuilder::new()
+            .from_evm_address("0x742D35Cc6634C0532925A3B844bC9e7595f0bEB7")
+            .to_evm_address("0x1234567890123456789012345678901234567890")
+            .amount(1_500_000)
+            .token(usdc.clone())
+            .on_chain(Chain::Polygon)
+            .build()
+            .unwrap();
+        assert_eq!(tx.token, usdc);
+        assert!(!tx.is_cross_chain());
+
+        let tx = TransactionBuilder::new()
+            .from_substrate_account("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY")
+            .to_substrate_account("5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty")
+            .amount(1_000)
+            .asset_id(1984)
+            .build()
+            .unwrap();
+        assert_eq!(tx.token, Token::asset(1984));
+
+        let json = serde_json::to_value(&tx).unwrap();
+        assert_eq!(json["token"]["asset"]["id"], 1984);
+        let native = TransactionBuilder::new()
+            .from_evm_address("0x742D35Cc6634C0532925A3B844bC9e7595f0bEB7")
+            .to_evm_address("0x1234567890123456789012345678901234567890")
+            .amount(1_000)
+            .build()
+            .unwrap();
+        assert!(serde_json::to_value(&native)
+            .unwrap()
+            .get("token")
+            .is_none());
+
+        // Assets live on Substrate chains, ERC-20 tokens on EVM chains
+        assert!(TransactionBuilder::new()
+            .from_evm_address("0x742D35Cc6634C0532925A3B844bC9e7595f0bEB7")
+            .to_evm_address("0x1234567890123456789012345678901234567890")
+            .amount(1_000)
+            .asset_id(1984)
+            .build()
+            .is_err());
+        assert!(TransactionBuilder::new()
+            .from_substrate_account("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY")
+            .to_substrate_account("5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty")
+            .amount(1_000)
+            .token(usdc.clone())
+            .build()
+            .is_err());
+        // Token transfers stay on one chain and carry no call data
+        assert!(TransactionBuilder::new()
+            .from_substrate_account("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY")
+            .to_evm_address("0x1234567890123456789012345678901234567890")
+            .amount(1_000)
+            .asset_id(1984)
+            .build()
+            .is_err());
+        assert!(TransactionBuilder::new()
+            .from_evm_address("0x742D35Cc6634C0532925A3B844bC9e7595f0bEB7")
+            .to_evm_address("0x1234567890123456789012345678901234567890")
+            .amount(1_000)
+            .token(usdc)
+            .with_data(vec![1])
+            .build()
+            .is_err());
     }
 
     #[test]
@@ -277,7 +704,7 @@ mod tests {
     #[test]
     fn test_transaction_builder_missing_to() {
         let result = TransactionBuilder::new()
-            .from_evm_address("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb7")
+            .from_evm_address("0x742D35Cc6634C0532925A3B844bC9e7595f0bEB7")
             .amount(100)
             .build();
 
@@ -293,7 +720,7 @@ mod tests {
     #[test]
     fn test_transaction_builder_missing_amount() {
         let result = TransactionBuilder::new()
-            .from_evm_address("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb7")
+            .from_evm_address("0x742D35Cc6634C0532925A3B844bC9e7595f0bEB7")
             .to_evm_address("0x1234567890123456789012345678901234567890")
             .build();
 
@@ -309,7 +736,7 @@ mod tests {
     #[test]
     fn test_transaction_with_data() {
         let tx = TransactionBuilder::new()
-            .from_evm_address("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb7")
+            .from_evm_address("0x742D35Cc6634C0532925A3B844bC9e7595f0bEB7")
             .to_evm_address("0x1234567890123456789012345678901234567890")
             .amount(1000)
             .with_data(vec![1, 2, 3, 4])
@@ -325,7 +752,7 @@ mod tests {
     #[test]
     fn test_transaction_with_empty_data() {
         let tx = TransactionBuilder::new()
-            .from_evm_address("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb7")
+            .from_evm_address("0x742D35Cc6634C0532925A3B844bC9e7595f0bEB7")
             .to_evm_address("0x1234567890123456789012345678901234567890")
             .amount(1000)
             .with_data(vec![])
@@ -339,13 +766,15 @@ mod tests {
     #[test]
     fn test_transaction_is_cross_chain() {
         let tx = Transaction {
-            from: Address::evm("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb7"),
+            from: Address::evm("0x742D35Cc6634C0532925A3B844bC9e7595f0bEB7"),
             to: Address::substrate("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"),
             amount: 1000,
+            token: Token::Native,
             source_chain: Chain::Ethereum,
             destination_chain: Chain::Polkadot,
             data: None,
             gas_limit: None,
+            max_fee: None,
         };
 
         assert!(tx.is_cross_chain());
@@ -354,13 +783,15 @@ mod tests {
     #[test]
     fn test_transaction_is_not_cross_chain() {
         let tx = Transaction {
-            from: Address::evm("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb7"),
+            from: Address::evm("0x742D35Cc6634C0532925A3B844bC9e7595f0bEB7"),
             to: Address::evm("0x1234567890123456789012345678901234567890"),
             amount: 1000,
+            token: Token::Native,
             source_chain: Chain::Ethereum,
             destination_chain: Chain::Ethereum,
             data: None,
             gas_limit: None,
+            max_fee: None,
         };
 
         assert!(!tx.is_cross_chain());
@@ -369,13 +800,15 @@ mod tests {
     #[test]
     fn test_transaction_hash() {
         let tx = Transaction {
-            from: Address::evm("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb7"),
+            from: Address::evm("0x742D35Cc6634C0532925A3B844bC9e7595f0bEB7"),
             to: Address::evm("0x1234567890123456789012345678901234567890"),
             amount: 1000,
+            token: Token::Native,
             source_chain: Chain::Ethereum,
             destination_chain: Chain::Ethereum,
             data: None,
             gas_limit: None,
+            max_fee: None,
         };
 
         let hash = tx.hash();
@@ -386,13 +819,15 @@ mod tests {
     #[test]
     fn test_transaction_clone() {
         let tx = Transaction {
-            from: Address::evm("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb7"),
+            from: Address::evm("0x742D35Cc6634C0532925A3B844bC9e7595f0bEB7"),
             to: Address::evm("0x1234567890123456789012345678901234567890"),
             amount: 1000,
+            token: Token::Native,
             source_chain: Chain::Ethereum,
             destination_chain: Chain::Ethereum,
             data: Some(vec![1, 2, 3]),
             gas_limit: Some(21000),
+            max_fee: None,
         };
 
         let cloned = tx.clone();
@@ -412,6 +847,12 @@ mod tests {
             },
             block_number: Some(12345),
             gas_used: Some(21000),
+            block_hash: None,
+            fee: None,
+            events: vec![],
+            contract_address: None,
+            finalized_at: None,
+            receipt: None,
         };
 
         let json = serde_json::to_string(&result).unwrap();
@@ -422,4 +863,129 @@ mod tests {
         assert_eq!(result.block_number, deserialized.block_number);
         assert_eq!(result.gas_used, deserialized.gas_used);
     }
+
+    #[test]
+    fn test_transaction_result_deserialize_without_details() {
+        let json = r#"{
+            "source_tx_hash": "0xabc123",
+            "destination_tx_hash": null,
+            "status": "Pending",
+            "block_number": null,
+            "gas_used": null
+        }"#;
+
+        let result: TransactionResult = serde_json::from_str(json).unwrap();
+        assert!(result.fee.is_none());
+        assert!(result.events.is_empty());
+        assert!(result.contract_address.is_none());
+        assert!(result.receipt.is_none());
+    }
+
+    #[test]
+    fn test_transaction_result_from_substrate_receipt() {
+        let receipt = ExtrinsicReceipt {
+            hash: "0xabc123".to_string(),
+            block_hash: "0xdef456".to_string(),
+            block_number: 12345,
+            events: vec![TransactionEvent::Substrate {
+                pallet: "Balances".to_string(),
+                variant: "Transfer".to_string(),
+                fields: String::new(),
+            }],
+            fee: Some(Fee::new(21_000_000)),
+            error: None,
+            finalized_at: 1_700_000_000,
+        };
+
+        let failed = TransactionResult::from(ExtrinsicReceipt {
+            error: Some("Balances::InsufficientBalance".to_string()),
+            ..receipt.clone()
+        });
+        assert!(matches!(failed.status, TransactionStatus::Failed { .. }));
+        assert_eq!(
+            failed.receipt.unwrap().error.as_deref(),
+            Some("Balances::InsufficientBalance")
+        );
+
+        let result = TransactionResult::from(receipt);
+        let unified = result.receipt.as_ref().unwrap();
+        assert!(unified.is_success());
+        assert_eq!(unified.block_hash.as_deref(), Some("0xdef456"));
+        assert_eq!(unified.find_events("Balances", "Transfer").count(), 1);
+        assert_eq!(result.block_number, Some(12345));
+        assert_eq!(result.events.len(), 1);
+        assert_eq!(result.finalized_at, Some(1_700_000_000));
+        assert_eq!(
+            result.format_with(&Chain::Polkadot),
+            "Confirmed in block #12345 (0 confirmations) (fee 0.0021 DOT) [0xabc123] on Polkadot"
+        );
+    }
+
+    #[test]
+    fn test_transaction_result_from_evm_receipt() {
+        let receipt = ethers::types::TransactionReceipt {
+            block_number: Some(100u64.into()),
+            status: Some(1u64.into()),
+            gas_used: Some(21_000u64.into()),
+            effective_gas_price: Some(1_000_000_000u64.into()),
+            contract_address: Some(ethers::types::H160::repeat_byte(0x22)),
+            ..Default::default()
+        };
+
+        let result = TransactionResult::from_evm_receipt(&receipt, 2);
+        assert_eq!(
+            result.status,
+            TransactionStatus::Confirmed {
+                block_number: 100,
+                confirmations: 2
+            }
+        );
+        assert_eq!(result.fee.unwrap().amount, 21_000_000_000_000);
+        assert_eq!(
+            result.receipt.unwrap().effective_gas_price,
+            Some(1_000_000_000)
+        );
+        assert_eq!(
+            result.contract_address,
+            Some(format!("0x{}", "22".repeat(20)))
+        );
+
+        let reverted = ethers::types::TransactionReceipt {
+            block_number: Some(100u64.into()),
+            status: Some(0u64.into()),
+            ..Default::default()
+        };
+        let result = TransactionResult::from_evm_receipt(&reverted, 0);
+        assert!(matches!(result.status, TransactionStatus::Failed { .. }));
+        assert!(!result.receipt.unwrap().is_success());
+    }
+
+    #[test]
+    fn test_transaction_result_display() {
+        let result = TransactionResult {
+            source_tx_hash: "0xabc123".to_string(),
+            destination_tx_hash: None,
+            status: TransactionStatus::Confirmed {
+                block_number: 12345,
+                confirmations: 1,
+            },
+            block_number: Some(12345),
+            gas_used: Some(21000),
+            block_hash: None,
+            fee: None,
+            events: vec![],
+            contract_address: None,
+            finalized_at: None,
+            receipt: None,
+        };
+
+        assert_eq!(
+            result.to_string(),
+            "Confirmed in block #12345 (1 confirmation) (gas used 21000) [0xabc123]"
+        );
+        assert_eq!(
+            result.format_with(&Chain::Ethereum),
+            "Confirmed in block #12345 (1 confirmation) (gas used 21000) [0xabc123] on Ethereum"
+        );
+    }
 }
@@ -0,0 +1,414 @@
+//! Watchdog for stuck transactions
+//!
+//! A [`TransactionWatchdog`] tracks submitted transactions until they are
+//! mined. One that stays pending longer than the configured threshold is
+//! replaced through its [`Repricer`]: on EVM chains an [`EvmRepricer`]
+//! re-sends it with the same nonce and higher fees; on Substrate chains a
+//! repricer re-submits the same call with the same nonce and a higher tip
+//! (see `TransactionExecutor::submit_with_tip` in the Substrate adapter).
+//! Transactions without a repricer, or still stuck after the maximum number
+//! of replacements, are escalated to the [`Notifier`].
+//!
+//! Replacing is only safe when the new transaction does the same thing as the
+//! old one: whichever is mined first invalidates the other through the shared
+//! nonce, so the operation happens exactly once.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use apex_sdk::evm::wallet::Wallet;
+//! use apex_sdk::prelude::*;
+//! use apex_sdk::types::Chain;
+//! use apex_sdk::watchdog::{EvmRepricer, TransactionWatchdog};
+//! use std::sync::Arc;
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<()> {
+//! let sdk = Arc::new(
+//!     ApexSDK::builder()
+//!         .with_evm_endpoint("https://eth.llamarpc.com")
+//!         .build()
+//!         .await?,
+//! );
+//! let watchdog = Arc::new(TransactionWatchdog::new(sdk.clone()));
+//! watchdog.start();
+//!
+//! let wallet = Wallet::new_random().with_chain_id(1);
+//! let executor = sdk.evm()?.transaction_executor();
+//! let tx = executor
+//!     .build_transaction(&wallet, wallet.eth_address(), 1_000.into(), None, None)
+//!     .await?;
+//! let hash = executor.send_raw_transaction(&wallet, tx.clone()).await?;
+//!
+//! watchdog
+//!     .track_with_repricer(
+//!         Chain::Ethereum,
+//!         format!("{:?}", hash),
+//!         EvmRepricer::new(executor, wallet, tx),
+//!     )
+//!     .await;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::Result;
+use crate::sdk::ApexSDK;
+use apex_sdk_evm::transaction::TransactionExecutor;
+use apex_sdk_evm::wallet::Wallet;
+use apex_sdk_types::{Chain, ConfirmationPolicy, TransactionStatus};
+use async_trait::async_trait;
+use ethers::types::transaction::eip2718::TypedTransaction;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Weak};
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+
+/// Thresholds for the watchdog
+#[derive(Debug, Clone)]
+pub struct WatchdogConfig {
+    /// How long a transaction may stay pending before it counts as stuck
+    pub pending_threshold: Duration,
+    /// Time between checks when started with [`TransactionWatchdog::start`]
+    pub check_interval: Duration,
+    /// Replacements per transaction before escalating instead
+    pub max_replacements: u32,
+}
+
+impl Default for WatchdogConfig {
+    fn default() -> Self {
+        Self {
+            pending_threshold: Duration::from_secs(180),
+            check_interval: Duration::from_secs(15),
+            max_replacements: 3,
+        }
+    }
+}
+
+/// A transaction that stayed pending past the threshold
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StuckTransaction {
+    /// Chain the transaction was submitted to
+    pub chain: Chain,
+    /// Hash the transaction was tracked under
+    pub tx_hash: String,
+    /// Hash of the most recent replacement, or `tx_hash` if there is none
+    pub latest_hash: String,
+    /// Time since the latest submission
+    pub pending_for: Duration,
+    /// Replacements sent so far
+    pub replacements: u32,
+}
+
+/// Re-submits a stuck transaction at a higher price
+///
+/// Implemented for async closures taking the replacement number (starting
+/// at 1) and returning the new transaction hash.
+#[async_trait]
+pub trait Repricer: Send + Sync {
+    /// Send replacement number `replacement`, returning its hash
+    async fn reprice(&self, replacement: u32) -> Result<String>;
+}
+
+#[async_trait]
+impl<F, Fut> Repricer for F
+where
+    F: Fn(u32) -> Fut + Send + Sync,
+    Fut: Future<Output = Result<String>> + Send,
+{
+    async fn reprice(&self, replacement: u32) -> Result<String> {
+        self(replacement).await
+    }
+}
+
+/// Replaces an EVM transaction with a same-nonce copy paying higher fees
+pub struct EvmRepricer {
+    executor: TransactionExecutor,
+    wallet: Wallet,
+    tx: Mutex<TypedTransaction>,
+    bump_percent: u64,
+}
+
+impl EvmRepricer {
+    /// Reprice `tx`, as sent by `wallet`, through `executor`
+    pub fn new(executor: TransactionExecutor, wallet: Wallet, tx: TypedTransaction) -> Self {
+        Self {
+            executor,
+            wallet,
+            tx: Mutex::new(tx),
+            bump_percent: 12,
+        }
+    }
+
+    /// Set the fee increase per replacement in percent (minimum 10)
+    pub fn with_bump_percent(mut self, percent: u64) -> Self {
+        self.bump_percent = percent;
+        self
+    }
+}
+
+#[async_trait]
+impl Repricer for EvmRepricer {
+    async fn reprice(&self, _replacement: u32) -> Result<String> {
+        let mut tx = self.tx.lock().await;
+        let (replacement, hash) = self
+            .executor
+            .replace_transaction(&self.wallet, &tx, self.bump_percent)
+            .await?;
+        *tx = replacement;
+        Ok(format!("{:?}", hash))
+    }
+}
+
+/// Receives transactions the watchdog could not unstick
+///
+/// Implemented for closures taking the [`StuckTransaction`].
+#[async_trait]
+pub trait Notifier: Send + Sync {
+    /// Report `stuck`
+    async fn notify(&self, stuck: &StuckTransaction) -> Result<()>;
+}
+
+#[async_trait]
+impl<F> Notifier for F
+where
+    F: Fn(&StuckTransaction) + Send + Sync,
+{
+    async fn notify(&self, stuck: &StuckTransaction) -> Result<()> {
+        self(stuck);
+        Ok(())
+    }
+}
+
+/// What one [`TransactionWatchdog::check`] did
+#[derive(Debug, Clone, Default)]
+pub struct WatchdogReport {
+    /// Tracked hashes of transactions that were mined or failed
+    pub settled: Vec<String>,
+    /// Transactions replaced with a higher price
+    pub replaced: Vec<StuckTransaction>,
+    /// Transactions escalated to the notifier
+    pub escalated: Vec<StuckTransaction>,
+}
+
+struct Tracked {
+    chain: Chain,
+    hashes: Vec<String>,
+    submitted_at: Instant,
+    replacements: u32,
+    repricer: Option<Arc<dyn Repricer>>,
+    escalated: bool,
+}
+
+impl Tracked {
+    fn stuck(&self, tx_hash: &str) -> StuckTransaction {
+        StuckTransaction {
+            chain: self.chain.clone(),
+            tx_hash: tx_hash.to_string(),
+            latest_hash: self.hashes.last().cloned().unwrap_or_default(),
+            pending_for: self.submitted_at.elapsed(),
+            replacements: self.replacements,
+        }
+    }
+}
+
+/// Monitors in-flight transactions and replaces or escalates stuck ones
+pub struct TransactionWatchdog {
+    sdk: Arc<ApexSDK>,
+    config: WatchdogConfig,
+    notifier: Option<Arc<dyn Notifier>>,
+    tracked: Mutex<HashMap<String, Tracked>>,
+    task: std::sync::Mutex<Option<JoinHandle<()>>>,
+}
+
+impl TransactionWatchdog {
+    /// Create a watchdog with the default configuration
+    pub fn new(sdk: Arc<ApexSDK>) -> Self {
+        Self {
+            sdk,
+            config: WatchdogConfig::default(),
+            notifier: None,
+            tracked: Mutex::new(HashMap::new()),
+            task: std::sync::Mutex::new(None),
+        }
+    }
+
+    /// Set the thresholds
+    pub fn with_config(mut self, config: WatchdogConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Escalate transactions that cannot be unstuck to `notifier`
+    pub fn with_notifier(mut self, notifier: Arc<dyn Notifier>) -> Self {
+        self.notifier = Some(notifier);
+        self
+    }
+
+    /// Watch `tx_hash`, escalating it if it gets stuck
+    pub async fn track(&self, chain: Chain, tx_hash: impl Into<String>) {
+        self.insert(chain, tx_hash.into(), None).await;
+    }
+
+    /// Watch `tx_hash`, replacing it through `repricer` if it gets stuck
+    pub async fn track_with_repricer(
+        &self,
+        chain: Chain,
+        tx_hash: impl Into<String>,
+        repricer: impl Repricer + 'static,
+    ) {
+        self.insert(chain, tx_hash.into(), Some(Arc::new(repricer)))
+            .await;
+    }
+
+    async fn insert(&self, chain: Chain, tx_hash: String, repricer: Option<Arc<dyn Repricer>>) {
+        self.tracked.lock().await.insert(
+            tx_hash.clone(),
+            Tracked {
+                chain,
+                hashes: vec![tx_hash],
+                submitted_at: Instant::now(),
+                replacements: 0,
+                repricer,
+                escalated: false,
+            },
+        );
+    }
+
+    /// Stop watching `tx_hash`, returning whether it was tracked
+    pub async fn untrack(&self, tx_hash: &str) -> bool {
+        self.tracked.lock().await.remove(tx_hash).is_some()
+    }
+
+    /// Hashes of the transactions being watched, sorted
+    pub async fn tracked(&self) -> Vec<String> {
+        let mut hashes: Vec<String> = self.tracked.lock().await.keys().cloned().collect();
+        hashes.sort();
+        hashes
+    }
+
+    /// Check every tracked transaction once
+    ///
+    /// Mined or failed transactions (including replacements) stop being
+    /// tracked; stuck ones are replaced or escalated. Status lookups that
+    /// fail are retried on the next check.
+    pub async fn check(&self) -> WatchdogReport {
+        let mut report = WatchdogReport::default();
+        let mut tracked = self.tracked.lock().await;
+
+        for (tx_hash, entry) in tracked.iter_mut() {
+            if self.settled(entry).await {
+                report.settled.push(tx_hash.clone());
+                continue;
+            }
+            if entry.escalated || entry.submitted_at.elapsed() < self.config.pending_threshold {
+                continue;
+            }
+
+            let stuck = entry.stuck(tx_hash);
+            match &entry.repricer {
+                Some(repricer) if entry.replacements < self.config.max_replacements => {
+                    match repricer.reprice(entry.replacements + 1).await {
+                        Ok(hash) => {
+                            tracing::info!(
+                                "Replaced stuck transaction {} with {}",
+                                stuck.latest_hash,
+                                hash
+                            );
+                            entry.replacements += 1;
+                            entry.hashes.push(hash);
+                            entry.submitted_at = Instant::now();
+                            report.replaced.push(entry.stuck(tx_hash));
+                        }
+                        Err(e) => {
+                            tracing::warn!("Failed to replace transaction {}: {}", tx_hash, e);
+                            self.escalate(entry, stuck, &mut report).await;
+                        }
+                    }
+                }
+                _ => self.escalate(entry, stuck, &mut report).await,
+            }
+        }
+
+        for tx_hash in &report.settled {
+            tracked.remove(tx_hash);
+        }
+        report
+    }
+
+    async fn settled(&self, entry: &Tracked) -> bool {
+        // Any inclusion settles it: only transactions still in the mempool
+        // can be replaced
+        let included = ConfirmationPolicy::Blocks(0);
+        for hash in &entry.hashes {
+            match self
+                .sdk
+                .get_transaction_status_with(&entry.chain, hash, &included)
+                .await
+            {
+                Ok(TransactionStatus::Confirmed { .. } | TransactionStatus::Failed { .. }) => {
+                    return true
+                }
+                Ok(_) => {}
+                Err(e) => tracing::debug!("Failed to get status of {}: {}", hash, e),
+            }
+        }
+        false
+    }
+
+    async fn escalate(
+        &self,
+        entry: &mut Tracked,
+        stuck: StuckTransaction,
+        report: &mut WatchdogReport,
+    ) {
+        tracing::warn!(
+            "Transaction {} pending for {:?} after {} replacements",
+            stuck.tx_hash,
+            stuck.pending_for,
+            stuck.replacements
+        );
+        if let Some(notifier) = &self.notifier {
+            if let Err(e) = notifier.notify(&stuck).await {
+                tracing::error!("Failed to escalate transaction {}: {}", stuck.tx_hash, e);
+                return;
+            }
+        }
+        entry.escalated = true;
+        report.escalated.push(stuck);
+    }
+
+    /// Check tracked transactions every `check_interval` in the background
+    ///
+    /// The background task stops when the watchdog is dropped.
+    pub fn start(self: &Arc<Self>) {
+        let watchdog: Weak<Self> = Arc::downgrade(self);
+        let interval = self.config.check_interval;
+        let task = tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(interval).await;
+                let Some(watchdog) = watchdog.upgrade() else {
+                    return;
+                };
+                watchdog.check().await;
+            }
+        });
+        if let Some(previous) = self
+            .task
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .replace(task)
+        {
+            previous.abort();
+        }
+    }
+}
+
+impl Drop for TransactionWatchdog {
+    fn drop(&mut self) {
+        if let Some(task) = self.task.lock().unwrap_or_else(|e| e.into_inner()).take() {
+            task.abort();
+        }
+    }
+}
@@ -1,5 +1,6 @@
 //! Apex SDK CLI tool
 
+use apex_sdk::types::{Address, Chain};
 use clap::{Parser, Subcommand};
 use std::path::PathBuf;
 
@@ -33,6 +34,17 @@ enum Commands {
         #[arg(short, long)]
         filter: Option<String>,
     },
+    /// Inspect an SS58 address or re-encode it under another prefix
+    Address {
+        /// SS58 address or 0x-prefixed 32-byte public key
+        address: String,
+        /// Re-encode under this SS58 prefix
+        #[arg(short, long, conflicts_with = "chain")]
+        prefix: Option<u16>,
+        /// Re-encode under this chain's SS58 prefix (polkadot, kusama, astar)
+        #[arg(short, long)]
+        chain: Option<Chain>,
+    },
     /// Show version information
     Version,
 }
@@ -68,6 +80,36 @@ async fn main() -> anyhow::Result<()> {
             }
             println!("Tests passed!");
         }
+        Commands::Address {
+            address,
+            prefix,
+            chain,
+        } => {
+            let address = Address::substrate(address);
+            let public_key = address.account_bytes().ok_or_else(|| {
+                anyhow::anyhow!("Invalid Substrate address: {}", address.as_str())
+            })?;
+            if let Some(prefix) = address.ss58_prefix() {
+                println!("SS58 prefix: {}", prefix);
+            }
+            let public_key: String = public_key.iter().map(|b| format!("{:02x}", b)).collect();
+            println!("Public key:  0x{}", public_key);
+
+            let prefix = match chain {
+                Some(chain) => Some(
+                    chain
+                        .ss58_prefix()
+                        .ok_or_else(|| anyhow::anyhow!("{} has no SS58 prefix", chain))?,
+                ),
+                None => prefix,
+            };
+            if let Some(prefix) = prefix {
+                let reencoded = address
+                    .with_ss58_prefix(prefix)
+                    .map_err(anyhow::Error::msg)?;
+                println!("Address:     {}", reencoded.as_str());
+            }
+        }
         Commands::Version => {
             println!("Apex SDK CLI v{}", env!("CARGO_PKG_VERSION"));
             println!("Rust SDK for Substrate & EVM blockchain development");
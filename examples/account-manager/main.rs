@@ -96,7 +96,7 @@ async fn main() -> Result<()> {
     println!("      Network: Polkadot (prefix: 0)");
 
     // EVM address (ECDSA/secp256k1)
-    let evm_address = "0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb7";
+    let evm_address = "0x742D35Cc6634C0532925A3B844bC9e7595f0bEB7";
     println!("\n    EVM (secp256k1):     {}", evm_address);
     println!("      Format: Hexadecimal (0x-prefixed)");
     println!("      Compatible: Ethereum, BSC, Polygon, etc.");
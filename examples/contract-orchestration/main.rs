@@ -43,7 +43,7 @@ async fn main() -> Result<()> {
 
     // User's accounts on both chains
     let substrate_account = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
-    let evm_account = "0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb7";
+    let evm_account = "0x742D35Cc6634C0532925A3B844bC9e7595f0bEB7";
 
     println!("User Accounts:");
     println!("  Substrate (SS58): {}", substrate_account);
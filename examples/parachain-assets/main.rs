@@ -63,7 +63,7 @@ async fn main() -> Result<()> {
 
     // Asset creator's account
     let creator = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
-    let evm_address = "0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb7";
+    let evm_address = "0x742D35Cc6634C0532925A3B844bC9e7595f0bEB7";
 
     println!("Asset Creator:");
     println!("  Polkadot: {}", creator);
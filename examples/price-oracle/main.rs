@@ -256,7 +256,7 @@ async fn main() -> Result<()> {
 
     let eth_publish_tx = sdk
         .transaction()
-        .from_evm_address("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb7")
+        .from_evm_address("0x742D35Cc6634C0532925A3B844bC9e7595f0bEB7")
         .to_evm_address(eth_oracle)
         .amount(0)
         .with_data(encode_oracle_update_evm(asset, aggregated.vwap_price_usd))
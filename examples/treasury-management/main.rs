@@ -0,0 +1,125 @@
+//! Treasury Management Example
+//!
+//! This example summarizes the holdings of a multi-chain treasury: a DAO
+//! keeping DOT (partly staked) on Polkadot and ETH and USDC on Ethereum.
+//!
+//! **Use Case:**
+//! A treasury committee that:
+//! 1. Reads every treasury account's balances in one call
+//! 2. Values them in USD through a price oracle
+//! 3. Reviews the allocation per chain and per asset
+//! 4. Flags balances that could not be read or priced
+//!
+//! Set `TREASURY_SUBSTRATE` and `TREASURY_EVM` to comma-separated account
+//! lists to summarize your own treasury.
+
+use apex_sdk::portfolio::{HoldingKind, PortfolioConfig, StaticPrices, TrackedToken};
+use apex_sdk::prelude::*;
+
+const USDC: &str = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48";
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    tracing_subscriber::fmt::init();
+
+    println!("=== Treasury Management ===\n");
+
+    // Prices would normally come from a live feed; fixed prices keep the
+    // example self-contained
+    let prices = StaticPrices::new()
+        .with_price("DOT", 6.5)
+        .with_price("ETH", 3200.0)
+        .with_price("USDC", 1.0);
+
+    let sdk = ApexSDK::builder()
+        .with_substrate_endpoint("wss://polkadot.api.onfinality.io/public-ws")
+        .with_evm_endpoint("https://eth.llamarpc.com")
+        .with_portfolio_config(
+            PortfolioConfig::new()
+                .with_token(TrackedToken::new(Chain::Ethereum, USDC, "USDC", 6))
+                .with_staking(true)
+                .with_price_oracle(prices),
+        )
+        .build()
+        .await?;
+
+    let substrate_accounts = std::env::var("TREASURY_SUBSTRATE")
+        .unwrap_or_else(|_| "13UVJyLnbVp9RBZYFwFGyDvVd1y27Tt8tkntv6Q7JVPhFsTB".to_string());
+    let evm_accounts = std::env::var("TREASURY_EVM")
+        .unwrap_or_else(|_| "0x742D35CC6634c0532925A3b844BC9E7595F0BEb0".to_string());
+    let accounts: Vec<&str> = substrate_accounts
+        .split(',')
+        .chain(evm_accounts.split(','))
+        .map(str::trim)
+        .filter(|account| !account.is_empty())
+        .collect();
+
+    let portfolio = sdk.portfolio(&accounts).await?;
+
+    println!("Holdings:");
+    for holding in &portfolio.holdings {
+        let kind = match &holding.kind {
+            HoldingKind::Native => "free",
+            HoldingKind::Token(_) => "token",
+            HoldingKind::Staked => "staked",
+        };
+        let value = holding
+            .value
+            .map_or("unpriced".to_string(), |value| format!("${:.2}", value));
+        println!(
+            "  {:<10} {:<8} {:>20} {:<5} {:>14}  {}",
+            holding.chain.to_string(),
+            kind,
+            holding.formatted_amount(),
+            holding.symbol,
+            value,
+            holding.account
+        );
+    }
+
+    let total = portfolio.total_value();
+    println!("\nAllocation by chain:");
+    for (chain, value) in portfolio.value_by_chain() {
+        println!(
+            "  {:<10} ${:>14.2} ({:.1}%)",
+            chain,
+            value,
+            share(value, total)
+        );
+    }
+
+    println!("\nAllocation by asset:");
+    for (symbol, value) in portfolio.value_by_symbol() {
+        println!(
+            "  {:<10} ${:>14.2} ({:.1}%)",
+            symbol,
+            value,
+            share(value, total)
+        );
+    }
+
+    println!("\nTotal treasury value: ${:.2}", total);
+
+    for holding in portfolio.unpriced() {
+        println!(
+            "Warning: no price for {} on {}",
+            holding.symbol, holding.chain
+        );
+    }
+    for error in &portfolio.errors {
+        println!(
+            "Warning: could not read {} of {} on {}: {}",
+            error.symbol, error.account, error.chain, error.error
+        );
+    }
+
+    Ok(())
+}
+
+fn share(value: f64, total: f64) -> f64 {
+    if total > 0.0 {
+        value / total * 100.0
+    } else {
+        0.0
+    }
+}
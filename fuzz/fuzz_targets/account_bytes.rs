@@ -0,0 +1,10 @@
+#![no_main]
+
+use apex_sdk_testing::invariants::assert_account_bytes_well_formed;
+use apex_sdk_types::Address;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|address: &str| {
+    assert_account_bytes_well_formed(&Address::evm(address));
+    assert_account_bytes_well_formed(&Address::substrate(address));
+});
@@ -0,0 +1,9 @@
+#![no_main]
+
+use apex_sdk_testing::invariants::assert_parse_units_canonical;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: (&str, u8)| {
+    let (value, decimals) = input;
+    assert_parse_units_canonical(value, decimals);
+});
@@ -0,0 +1,8 @@
+#![no_main]
+
+use apex_sdk_testing::invariants::assert_decode_location_canonical;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    assert_decode_location_canonical(data);
+});